@@ -0,0 +1,41 @@
+//! Cell construction benchmarks.
+//!
+//! Run with `cargo bench --bench cell`.
+
+use noun::{Atom, Cell, Noun, Rc};
+use std::time::{Duration, Instant};
+
+/// Returns the mean time it takes to run `f`.
+fn time<T>(mut f: impl FnMut() -> T) -> Duration {
+    const RUNS: u32 = 10;
+    let start = Instant::now();
+    for _ in 0..RUNS {
+        std::hint::black_box(f());
+    }
+    start.elapsed() / RUNS
+}
+
+fn main() {
+    for len in [1_000u32, 10_000, 100_000] {
+        let nouns: Vec<Rc<Noun>> = (0..len)
+            .map(|i| Rc::new(Noun::from(Atom::from(i))))
+            .collect();
+        println!(
+            "cell from vec, {:>7} items: {:?}",
+            len,
+            // Dropping a long list recursively would overflow the stack.
+            time(|| std::mem::forget(Cell::from(nouns.clone())))
+        );
+    }
+
+    // Destructuring lots of small cells, as a parser does.
+    let cells: Vec<Cell> = (0..1_000_000u32)
+        .map(|i| Cell::from([i, i + 1, i + 2]))
+        .collect();
+    println!(
+        "destructure {} cells: {:?} (to_array), {:?} (to_array_ref)",
+        cells.len(),
+        time(|| cells.iter().filter_map(Cell::to_array::<3>).count()),
+        time(|| cells.iter().filter_map(Cell::to_array_ref::<3>).count())
+    );
+}
@@ -0,0 +1,36 @@
+//! Conversion benchmarks.
+//!
+//! Run with `cargo bench --bench convert --features rayon`.
+
+use noun::{
+    convert::{self, IntoNoun},
+    Noun,
+};
+use std::time::{Duration, Instant};
+
+/// Returns the mean time it takes to run `f`.
+fn time<T>(mut f: impl FnMut() -> T) -> Duration {
+    const RUNS: u32 = 10;
+    let start = Instant::now();
+    for _ in 0..RUNS {
+        std::hint::black_box(f());
+    }
+    start.elapsed() / RUNS
+}
+
+fn main() {
+    for len in [10_000u32, 100_000, 1_000_000] {
+        let elems: Vec<_> = (0..len)
+            .map(|i| (i, format!("element {}", i), vec![i; 4]).into_noun())
+            .collect();
+        let noun = Noun::from(elems);
+        println!(
+            "list to vec, {:>7} elements: {:?} (serial), {:?} (parallel)",
+            len,
+            time(|| Vec::<(u32, String, Vec<u32>)>::try_from(&noun).expect("convert")),
+            time(|| convert::par_list_to_vec::<(u32, String, Vec<u32>)>(&noun).expect("convert"))
+        );
+        // Dropping a long list recursively would overflow the stack.
+        std::mem::forget(noun);
+    }
+}
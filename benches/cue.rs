@@ -0,0 +1,112 @@
+//! Cue benchmarks.
+//!
+//! Run with `cargo bench --bench cue`.
+
+use noun::{serdes::Cue, Atom, Cell, Noun, Rc};
+use std::{
+    io::Cursor,
+    time::{Duration, Instant},
+};
+
+/// Returns the mean time it takes to run `f`.
+fn time<T>(mut f: impl FnMut() -> T) -> Duration {
+    const RUNS: u32 = 10;
+    let start = Instant::now();
+    for _ in 0..RUNS {
+        std::hint::black_box(f());
+    }
+    start.elapsed() / RUNS
+}
+
+/// Builds a cell wrapping an atom of `len` bytes, preceded by an atom whose length isn't a
+/// multiple of 8 so that the large atom isn't byte-aligned in the jammed noun.
+fn binary(len: usize) -> Noun {
+    let bytes: Vec<u8> = (0..len).map(|i| (i % 251) as u8 + 1).collect();
+    Noun::from(Cell::from([
+        Noun::from(Atom::from("file")),
+        Noun::from(Atom::from(bytes)),
+    ]))
+}
+
+/// Builds a noun shaped like a pill: a balanced tree of about `nodes` atoms and cells, most atoms
+/// short, with every eighth subtree a repeat of an earlier one.
+fn pill(nodes: u64) -> Noun {
+    fn build(state: &mut u64, nodes: u64, seen: &mut Vec<Rc<Noun>>) -> Rc<Noun> {
+        // xorshift
+        *state ^= *state << 13;
+        *state ^= *state >> 7;
+        *state ^= *state << 17;
+        let rand = *state;
+        if nodes > 16 && rand.is_multiple_of(8) && !seen.is_empty() {
+            return seen[(rand >> 8) as usize % seen.len()].clone();
+        }
+        let noun = if nodes < 2 {
+            let len = (rand >> 3) % 8;
+            Rc::new(Noun::from(Atom::from(rand >> (64 - 8 * len.max(1)))))
+        } else {
+            let head_nodes = (nodes - 1) / 2;
+            let head = build(state, head_nodes, seen);
+            let tail = build(state, nodes - 1 - head_nodes, seen);
+            Rc::new(Noun::from(Cell::from([head, tail])))
+        };
+        if nodes > 16 {
+            seen.push(noun.clone());
+        }
+        noun
+    }
+    let noun = build(&mut 0x9e37_79b9_7f4a_7c15, nodes, &mut Vec::new());
+    Rc::try_unwrap(noun).unwrap()
+}
+
+/// Builds a list of lists of 1000 atoms each, `len` distinct atoms in all. A noun without
+/// backreferences makes the decoder's cache as large as it can be.
+fn lists(len: u64) -> Noun {
+    fn list(items: impl Iterator<Item = Rc<Noun>>) -> Rc<Noun> {
+        let items: Vec<_> = items.collect();
+        items
+            .into_iter()
+            .rev()
+            .fold(Rc::new(Noun::null()), |tail, head| {
+                Rc::new(Noun::from(Cell::from([head, tail])))
+            })
+    }
+    let noun = list((0..len.div_ceil(1000)).map(|i| {
+        list((i * 1000..len.min((i + 1) * 1000)).map(|j| Rc::new(Noun::from(Atom::from(j + 1)))))
+    }));
+    Rc::try_unwrap(noun).unwrap()
+}
+
+fn main() {
+    for nodes in [100_000, 1_000_000, 10_000_000] {
+        let jammed_noun = pill(nodes).jam_to_vec();
+        let atom = Atom::from(jammed_noun);
+        println!(
+            "cue pill-shaped noun, {:>8} nodes ({} bytes): {:?}",
+            nodes,
+            atom.as_bytes().len(),
+            time(|| Noun::cue(atom.clone()).unwrap())
+        );
+    }
+
+    for len in [100_000, 1_000_000, 5_000_000] {
+        let atom = Atom::from(lists(len).jam_to_vec());
+        println!(
+            "cue lists of {:>7} atoms ({} bytes): {:?}, {:?} (without backreferences)",
+            len,
+            atom.as_bytes().len(),
+            time(|| Noun::cue(atom.clone()).unwrap()),
+            time(|| Noun::cue_nodedup(&atom).unwrap()),
+        );
+    }
+
+    for len in [1 << 10, 1 << 20, 1 << 24] {
+        let jammed_noun = binary(len).jam_to_vec();
+        let atom = Atom::from(jammed_noun.clone());
+        println!(
+            "cue {:>8}-byte atom: {:?} (from atom), {:?} (from reader)",
+            len,
+            time(|| Noun::cue(atom.clone()).unwrap()),
+            time(|| Noun::cue_from_reader(Cursor::new(&jammed_noun)).unwrap())
+        );
+    }
+}
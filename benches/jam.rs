@@ -0,0 +1,116 @@
+//! Jam benchmarks.
+//!
+//! Run with `cargo bench --bench jam`.
+
+use noun::{Atom, Cell, Noun, Rc};
+use std::time::{Duration, Instant};
+
+/// Returns the mean time it takes to run `f`.
+fn time<T>(mut f: impl FnMut() -> T) -> Duration {
+    const RUNS: u32 = 10;
+    let start = Instant::now();
+    for _ in 0..RUNS {
+        std::hint::black_box(f());
+    }
+    start.elapsed() / RUNS
+}
+
+/// Builds a noun whose head and tail are the same noun, `levels` deep.
+fn shared(levels: u32) -> Noun {
+    let mut noun = Rc::new(Noun::from(Atom::from(0xdead_beef_u32)));
+    for _ in 0..levels {
+        noun = Rc::new(Noun::from(Cell::from([noun.clone(), noun])));
+    }
+    Rc::try_unwrap(noun).unwrap()
+}
+
+/// Builds a list of `len` distinct atoms.
+fn list(len: u32) -> Noun {
+    let mut noun = Noun::null();
+    for i in 0..len {
+        noun = Noun::from(Cell::from([Noun::from(Atom::from(i)), noun]));
+    }
+    noun
+}
+
+/// Builds a cell wrapping an atom of `len` bytes.
+fn binary(len: usize) -> Noun {
+    let bytes: Vec<u8> = (0..len).map(|i| (i % 251) as u8 + 1).collect();
+    Noun::from(Cell::from([
+        Noun::from(Atom::from("file")),
+        Noun::from(Atom::from(bytes)),
+    ]))
+}
+
+/// Builds a noun shaped like a pill: a balanced tree of about `nodes` atoms and cells, most atoms
+/// short, with every eighth subtree a repeat of an earlier one.
+fn pill(nodes: u64) -> Noun {
+    fn build(state: &mut u64, nodes: u64, seen: &mut Vec<Rc<Noun>>) -> Rc<Noun> {
+        // xorshift
+        *state ^= *state << 13;
+        *state ^= *state >> 7;
+        *state ^= *state << 17;
+        let rand = *state;
+        if nodes > 16 && rand.is_multiple_of(8) && !seen.is_empty() {
+            return seen[(rand >> 8) as usize % seen.len()].clone();
+        }
+        let noun = if nodes < 2 {
+            let len = (rand >> 3) % 8;
+            Rc::new(Noun::from(Atom::from(rand >> (64 - 8 * len.max(1)))))
+        } else {
+            let head_nodes = (nodes - 1) / 2;
+            let head = build(state, head_nodes, seen);
+            let tail = build(state, nodes - 1 - head_nodes, seen);
+            Rc::new(Noun::from(Cell::from([head, tail])))
+        };
+        if nodes > 16 {
+            seen.push(noun.clone());
+        }
+        noun
+    }
+    let noun = build(&mut 0x9e37_79b9_7f4a_7c15, nodes, &mut Vec::new());
+    Rc::try_unwrap(noun).unwrap()
+}
+
+fn main() {
+    // The logical size of these nouns doubles at every level, but the number of allocations only
+    // grows by one, so jamming them should take time proportional to the number of levels.
+    for levels in [8, 16, 24, 32, 64] {
+        let noun = shared(levels);
+        println!(
+            "jam shared noun, {:>2} levels: {:?}",
+            levels,
+            time(|| noun.jam_to_vec())
+        );
+    }
+
+    for len in [10_000, 100_000, 1_000_000] {
+        let noun = list(len);
+        println!(
+            "jam list, {:>9} items: {:?}",
+            len,
+            time(|| noun.jam_to_vec())
+        );
+        // Dropping a long list recursively would overflow the stack.
+        std::mem::forget(noun);
+    }
+
+    for nodes in [100_000, 1_000_000, 10_000_000] {
+        let noun = pill(nodes);
+        println!(
+            "jam pill-shaped noun, {:>8} nodes: {:?}",
+            nodes,
+            time(|| noun.jam_to_vec())
+        );
+    }
+
+    for len in [1 << 10, 1 << 20, 1 << 24] {
+        let noun = binary(len);
+        println!(
+            "jam {:>8}-byte atom: {:?} (to Vec), {:?} (to writer)",
+            len,
+            time(|| noun.jam_to_vec()),
+            time(|| noun.jam_to_writer(std::io::sink()).unwrap())
+        );
+    }
+}
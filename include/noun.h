@@ -0,0 +1,81 @@
+/*
+ * C interface to the noun crate, enabled by its `ffi` feature.
+ *
+ * Nouns are passed around as opaque `noun_t *` handles, each of which owns a
+ * reference to a noun and must be released with noun_free(). Functions that
+ * can fail return a noun_status_t and write their results through out
+ * pointers, which are left untouched on failure. Functions that return a
+ * handle return NULL on failure. See the documentation of the crate's `ffi`
+ * module for details.
+ */
+
+#ifndef NOUN_H
+#define NOUN_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct NounHandle noun_t;
+
+typedef enum {
+  NOUN_OK = 0,
+  NOUN_NULL_POINTER = 1,
+  NOUN_CUE_FAILED = 2,
+  NOUN_UNEXPECTED_ATOM = 3,
+  NOUN_UNEXPECTED_CELL = 4,
+  NOUN_PANIC = 5,
+} noun_status_t;
+
+/* Creates an atom from `len` bytes, least significant first. */
+noun_t *noun_atom_new(const uint8_t *bytes, size_t len);
+
+/* Creates a cell, sharing `head` and `tail`, which the caller still owns. */
+noun_t *noun_cell_new(const noun_t *head, const noun_t *tail);
+
+/* Creates another handle to the same noun. */
+noun_t *noun_clone(const noun_t *noun);
+
+/* Releases a handle. Does nothing if `noun` is NULL. */
+void noun_free(noun_t *noun);
+
+/* Cues `len` bytes into a noun, ignoring any bytes after the jammed noun. */
+noun_status_t noun_cue(const uint8_t *bytes, size_t len, noun_t **out);
+
+/* Jams a noun. The bytes must be released with noun_bytes_free(). */
+noun_status_t noun_jam(const noun_t *noun, uint8_t **out_bytes,
+                       size_t *out_len);
+
+/* Releases bytes returned by noun_jam(). Does nothing if `bytes` is NULL. */
+void noun_bytes_free(uint8_t *bytes, size_t len);
+
+/* Returns true if `noun` is a cell, and false if it's an atom or NULL. */
+bool noun_is_cell(const noun_t *noun);
+
+/* Returns true if two nouns are equal, and false otherwise or if either is
+ * NULL. */
+bool noun_equal(const noun_t *a, const noun_t *b);
+
+/* Takes the head of a cell into a new handle. */
+noun_status_t noun_head(const noun_t *noun, noun_t **out);
+
+/* Takes the tail of a cell into a new handle. */
+noun_status_t noun_tail(const noun_t *noun, noun_t **out);
+
+/*
+ * Gets the bytes of an atom, least significant first. The bytes belong to
+ * the noun and stay valid as long as a handle to it does. `*out_bytes` must
+ * not be read if `*out_len` is 0.
+ */
+noun_status_t noun_atom_bytes(const noun_t *noun, const uint8_t **out_bytes,
+                              size_t *out_len);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* NOUN_H */
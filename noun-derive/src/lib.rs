@@ -0,0 +1,411 @@
+//! Derive macros for the `FromNoun` and `IntoNoun` traits of the [`noun`] crate.
+//!
+//! Don't depend on this crate directly: enable the `derive` feature of the [`noun`] crate, which
+//! re-exports these macros from `noun::convert` alongside the traits they implement.
+//!
+//! [`noun`]: https://docs.rs/noun
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{
+    parse_macro_input, parse_quote, Attribute, Data, DataEnum, DataStruct, DeriveInput, Error,
+    Fields, Generics, Ident, Index, LitByteStr, LitStr, Member, Path, Result,
+};
+
+/// Derives `FromNoun` for a struct or an enum.
+///
+/// The fields of a struct or enum variant are read from a right-nested cell in declaration order,
+/// so `struct Point { x: u32, y: u32, z: u32 }` is read from `[x y z]`. A struct with a single
+/// field is read from that field's noun, and a struct with no fields is read from `~`.
+///
+/// An enum variant is read from a cell whose head is the variant's tag and whose tail holds the
+/// variant's fields, so `Shape::Circle { radius: u32 }` is read from `[%circle radius]`. A variant
+/// with no fields is read from its bare tag. A variant's tag is the `kebab-case` form of its name
+/// unless the variant is renamed with `#[noun(rename = "tag")]`.
+///
+/// A field marked `#[noun(skip)]` isn't read from the noun at all and is set to its [`Default`]
+/// value instead.
+///
+/// A field that fails to convert fails the whole conversion with a `convert::Error::At` naming
+/// the field and its axis, and a noun with an unrecognized tag fails with
+/// `convert::Error::UnknownTag`.
+#[proc_macro_derive(FromNoun, attributes(noun))]
+pub fn derive_from_noun(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    from_noun(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Derives `IntoNoun` for a struct or an enum.
+///
+/// Structs and enums are converted to the same nouns that [`FromNoun`](macro@FromNoun) reads them
+/// from, and the same `#[noun(...)]` attributes apply. Fields marked `#[noun(skip)]` are left out.
+#[proc_macro_derive(IntoNoun, attributes(noun))]
+pub fn derive_into_noun(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    into_noun(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// A field of a struct or enum variant.
+struct Field {
+    /// How the field is accessed, either by name or by index.
+    member: Member,
+    /// The variable the field is bound to.
+    binding: Ident,
+    /// The path segment the field is reported at in conversion errors.
+    segment: TokenStream2,
+    /// Whether the field is left out of the noun.
+    skip: bool,
+}
+
+/// A variant of an enum.
+struct Variant {
+    /// The name of the variant.
+    ident: Ident,
+    /// The tag at the head of the variant's noun.
+    tag: String,
+    /// The fields of the variant.
+    fields: Vec<Field>,
+}
+
+fn from_noun(mut input: DeriveInput) -> Result<TokenStream2> {
+    container_attrs(&input.attrs)?;
+    add_bounds(&mut input.generics, parse_quote!(::noun::convert::FromNoun));
+    let ident = &input.ident;
+    let body = match &input.data {
+        Data::Struct(DataStruct { fields, .. }) => {
+            let fields = fields_of(fields)?;
+            let read = read_fields(&fields, quote!(noun));
+            let construct = construct(quote!(Self), &fields);
+            quote! {
+                #read
+                ::core::result::Result::Ok(#construct)
+            }
+        }
+        Data::Enum(DataEnum { variants, .. }) => {
+            let variants = variants_of(variants.iter())?;
+            let arms = variants.iter().map(|variant| {
+                let Variant {
+                    ident: variant_ident,
+                    tag,
+                    fields,
+                } = variant;
+                let tag = LitByteStr::new(tag.as_bytes(), variant_ident.span());
+                let construct = construct(quote!(Self::#variant_ident), fields);
+                if fields.iter().all(|field| field.skip) {
+                    let bindings = fields.iter().map(|field| &field.binding);
+                    quote! {
+                        (#tag, ::core::option::Option::None) => {
+                            #(let #bindings = ::core::default::Default::default();)*
+                            ::core::result::Result::Ok(#construct)
+                        }
+                    }
+                } else {
+                    let read = read_fields(fields, quote!(__fields));
+                    let name = &variant.tag;
+                    quote! {
+                        (#tag, ::core::option::Option::Some(__fields)) => {
+                            let __read = || -> ::core::result::Result<Self, ::noun::convert::Error> {
+                                #read
+                                ::core::result::Result::Ok(#construct)
+                            };
+                            __read().map_err(|err| {
+                                err.at_segment(::noun::convert::PathSegment::Field(#name), 3)
+                            })
+                        }
+                    }
+                }
+            });
+            quote! {
+                match ::noun::convert::tag_from_noun(noun)? {
+                    #(#arms)*
+                    _ => ::core::result::Result::Err(::noun::convert::Error::UnknownTag),
+                }
+            }
+        }
+        Data::Union(data) => {
+            return Err(Error::new(
+                data.union_token.span,
+                "FromNoun can't be derived for unions",
+            ))
+        }
+    };
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::noun::convert::FromNoun for #ident #ty_generics #where_clause {
+            fn from_noun(
+                noun: &::noun::Noun,
+            ) -> ::core::result::Result<Self, ::noun::convert::Error> {
+                #body
+            }
+        }
+    })
+}
+
+fn into_noun(mut input: DeriveInput) -> Result<TokenStream2> {
+    container_attrs(&input.attrs)?;
+    add_bounds(&mut input.generics, parse_quote!(::noun::convert::IntoNoun));
+    let ident = &input.ident;
+    let body = match &input.data {
+        Data::Struct(DataStruct { fields, .. }) => {
+            let fields = fields_of(fields)?;
+            let pattern = pattern(quote!(Self), &fields);
+            let write = write_fields(None, &fields);
+            quote! {
+                let #pattern = self;
+                #write
+            }
+        }
+        Data::Enum(DataEnum { variants, .. }) => {
+            let variants = variants_of(variants.iter())?;
+            let arms = variants.iter().map(|variant| {
+                let Variant {
+                    ident: variant_ident,
+                    tag,
+                    fields,
+                } = variant;
+                let pattern = pattern(quote!(Self::#variant_ident), fields);
+                let write = write_fields(Some(tag), fields);
+                quote!(#pattern => { #write })
+            });
+            quote! {
+                match self {
+                    #(#arms)*
+                }
+            }
+        }
+        Data::Union(data) => {
+            return Err(Error::new(
+                data.union_token.span,
+                "IntoNoun can't be derived for unions",
+            ))
+        }
+    };
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::noun::convert::IntoNoun for #ident #ty_generics #where_clause {
+            fn into_noun(self) -> ::noun::Noun {
+                #body
+            }
+        }
+    })
+}
+
+/// Binds each field to a variable by reading it from the right-nested cell `noun`.
+fn read_fields(fields: &[Field], noun: TokenStream2) -> TokenStream2 {
+    let read = fields.iter().filter(|field| !field.skip).count();
+    let mut index = 0usize;
+    let reads = fields.iter().map(|field| {
+        let binding = &field.binding;
+        if field.skip {
+            return quote!(let #binding = ::core::default::Default::default(););
+        }
+        let last = index + 1 == read;
+        let segment = &field.segment;
+        let read = quote! {
+            let #binding =
+                ::noun::convert::field_from_noun(&mut __noun, #index, #last, #segment)?;
+        };
+        index += 1;
+        read
+    });
+    if read == 0 {
+        quote! {
+            ::noun::convert::null_from_noun(#noun)?;
+            #(#reads)*
+        }
+    } else {
+        quote! {
+            let mut __noun = #noun;
+            #(#reads)*
+        }
+    }
+}
+
+/// Converts the fields bound to variables into a right-nested cell, preceded by `tag` if there is
+/// one.
+fn write_fields(tag: Option<&str>, fields: &[Field]) -> TokenStream2 {
+    let tag = tag.map(|tag| quote!(::noun::Noun::from(::noun::Atom::from(#tag)),));
+    let writes = fields.iter().filter(|field| !field.skip).map(|field| {
+        let binding = &field.binding;
+        quote!(::noun::convert::IntoNoun::into_noun(#binding),)
+    });
+    quote!(::noun::convert::fields_into_noun(
+        ::std::vec![#tag #(#writes)*]
+    ))
+}
+
+/// Returns an expression that constructs `path` from the fields bound to variables.
+fn construct(path: TokenStream2, fields: &[Field]) -> TokenStream2 {
+    let fields = fields.iter().map(
+        |Field {
+             member, binding, ..
+         }| quote!(#member: #binding),
+    );
+    quote!(#path { #(#fields),* })
+}
+
+/// Returns a pattern that binds each field of `path` that isn't skipped to a variable.
+fn pattern(path: TokenStream2, fields: &[Field]) -> TokenStream2 {
+    let fields = fields.iter().map(
+        |Field {
+             member,
+             binding,
+             skip,
+             ..
+         }| {
+            if *skip {
+                quote!(#member: _)
+            } else {
+                quote!(#member: #binding)
+            }
+        },
+    );
+    quote!(#path { #(#fields),* })
+}
+
+/// Requires every type parameter of `generics` to implement `bound`.
+fn add_bounds(generics: &mut Generics, bound: Path) {
+    let params: Vec<_> = generics
+        .type_params()
+        .map(|param| param.ident.clone())
+        .collect();
+    let where_clause = generics.make_where_clause();
+    for param in params {
+        where_clause.predicates.push(parse_quote!(#param: #bound));
+    }
+}
+
+fn fields_of(fields: &Fields) -> Result<Vec<Field>> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            let skip = field_attrs(&field.attrs)?;
+            Ok(match &field.ident {
+                Some(ident) => Field {
+                    member: Member::Named(ident.clone()),
+                    binding: format_ident!("__field_{}", ident),
+                    segment: {
+                        let name = ident.to_string();
+                        quote!(::noun::convert::PathSegment::Field(#name))
+                    },
+                    skip,
+                },
+                None => Field {
+                    member: Member::Unnamed(Index::from(i)),
+                    binding: format_ident!("__field_{}", i),
+                    segment: quote!(::noun::convert::PathSegment::Index(#i)),
+                    skip,
+                },
+            })
+        })
+        .collect()
+}
+
+fn variants_of<'a>(variants: impl Iterator<Item = &'a syn::Variant>) -> Result<Vec<Variant>> {
+    let mut parsed: Vec<Variant> = Vec::new();
+    for variant in variants {
+        let (tag, span) = match variant_attrs(&variant.attrs)? {
+            Some(rename) => (rename.value(), rename.span()),
+            None => (kebab_case(&variant.ident.to_string()), variant.ident.span()),
+        };
+        if !is_term(&tag) {
+            return Err(Error::new(
+                span,
+                format!(
+                    "`{}` isn't a valid term; rename the variant with #[noun(rename = \"...\")]",
+                    tag
+                ),
+            ));
+        }
+        if parsed.iter().any(|other| other.tag == tag) {
+            return Err(Error::new(span, format!("duplicate tag `{}`", tag)));
+        }
+        parsed.push(Variant {
+            ident: variant.ident.clone(),
+            tag,
+            fields: fields_of(&variant.fields)?,
+        });
+    }
+    Ok(parsed)
+}
+
+/// Rejects `#[noun(...)]` attributes on a struct or enum, which has none.
+fn container_attrs(attrs: &[Attribute]) -> Result<()> {
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("noun")) {
+        attr.parse_nested_meta(|meta| Err(meta.error("unsupported noun attribute")))?;
+    }
+    Ok(())
+}
+
+/// Parses the `#[noun(...)]` attributes of a field, returning whether the field is skipped.
+fn field_attrs(attrs: &[Attribute]) -> Result<bool> {
+    let mut skip = false;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("noun")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("skip") {
+                skip = true;
+                Ok(())
+            } else {
+                Err(meta.error("unsupported noun attribute"))
+            }
+        })?;
+    }
+    Ok(skip)
+}
+
+/// Parses the `#[noun(...)]` attributes of a variant, returning the variant's renamed tag.
+fn variant_attrs(attrs: &[Attribute]) -> Result<Option<LitStr>> {
+    let mut rename = None;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("noun")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                rename = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("unsupported noun attribute"))
+            }
+        })?;
+    }
+    Ok(rename)
+}
+
+/// Converts a `CamelCase` identifier to `kebab-case`, keeping acronyms together, so that
+/// `HttpRequest` and `HTTPRequest` both become `http-request`.
+fn kebab_case(ident: &str) -> String {
+    let chars: Vec<char> = ident.chars().collect();
+    let mut kebab = String::with_capacity(ident.len());
+    for (i, &c) in chars.iter().enumerate() {
+        if c == '_' {
+            kebab.push('-');
+            continue;
+        }
+        if c.is_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).is_some_and(|next| next.is_lowercase());
+            if prev.is_lowercase()
+                || prev.is_ascii_digit()
+                || (prev.is_uppercase() && next_is_lower)
+            {
+                kebab.push('-');
+            }
+        }
+        kebab.extend(c.to_lowercase());
+    }
+    kebab
+}
+
+/// Returns `true` if `tag` is a valid term (`@tas`): a lowercase letter followed by lowercase
+/// letters, digits, and hyphens.
+fn is_term(tag: &str) -> bool {
+    let mut chars = tag.chars();
+    chars.next().is_some_and(|c| c.is_ascii_lowercase())
+        && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+}
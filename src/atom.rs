@@ -1,26 +1,194 @@
+#[cfg(feature = "bitstream-io")]
+use bitstream_io::{
+    BitRead, BitWrite, Endianness, LittleEndian, Numeric, Primitive, SignedNumeric,
+};
 use std::{
     collections::hash_map::DefaultHasher,
     ffi::OsStr,
-    fmt::{Display, Error, Formatter},
+    fmt::{Alignment, Display, Error, Formatter, Write},
     hash::Hasher,
+    io::{self, BufReader, Read},
+    iter::{Product, Sum},
+    ops::{Add, AddAssign, Mul, MulAssign},
     str::{self, Utf8Error},
+    time::Duration,
 };
 
+// Bit positions and lengths are u64 throughout, even though byte positions and lengths are usize,
+// because a usize can't count the bits of a large atom on a 32-bit target.
+
 /// Returns the length in bits of a sequence of bytes.
-fn bit_len(bytes: &[u8]) -> usize {
+pub(crate) fn bit_len(bytes: &[u8]) -> u64 {
     if let Some(last_byte) = bytes.last() {
-        let byte_len = u32::try_from(bytes.len()).expect("usize to u32");
-        let bit_len = u8::BITS * (byte_len - 1) + (u8::BITS - last_byte.leading_zeros());
-        usize::try_from(bit_len).expect("u32 to usize")
+        let byte_len = u64::try_from(bytes.len()).expect("usize to u64");
+        u64::from(u8::BITS) * (byte_len - 1) + u64::from(u8::BITS - last_byte.leading_zeros())
     } else {
         0
     }
 }
 
+/// Returns the index of the byte containing bit `bit_idx`, which must be the index of a bit in
+/// memory.
+fn byte_idx(bit_idx: u64) -> usize {
+    usize::try_from(bit_idx / u64::from(u8::BITS)).expect("u64 to usize")
+}
+
+/// Returns the index of bit `bit_idx` within its byte.
+fn bit_shift(bit_idx: u64) -> u32 {
+    (bit_idx % u64::from(u8::BITS)) as u32
+}
+
+/// Returns the number of bytes needed to hold `bit_len` bits.
+fn byte_len(bit_len: u64) -> usize {
+    usize::try_from(bit_len.div_ceil(u64::from(u8::BITS))).expect("u64 to usize")
+}
+
+/// Converts a bit position or length into a `usize` for the public accessors that return one.
+///
+/// # Panics
+///
+/// Panics if `bits` doesn't fit in a `usize`, which is only possible on targets where a `usize`
+/// is narrower than 64 bits.
+fn bits_to_usize(bits: u64) -> usize {
+    usize::try_from(bits).expect("bit position or length exceeds usize::MAX")
+}
+
+/// Copies the `len` bits of `bytes` starting at bit `bit_idx` into a new little-endian byte
+/// vector.
+///
+/// Whole bytes are copied at a time, shifting them into place if `bit_idx` isn't byte-aligned.
+/// `bytes` must contain at least `bit_idx + len` bits.
+pub(crate) fn copy_bits(bytes: &[u8], bit_idx: u64, len: u64) -> Vec<u8> {
+    let bytes = &bytes[byte_idx(bit_idx)..];
+    let shift = bit_shift(bit_idx);
+    let byte_len = byte_len(len);
+    let mut copy = if shift == 0 {
+        bytes[..byte_len].to_vec()
+    } else {
+        (0..byte_len)
+            .map(|i| {
+                let low = bytes[i] >> shift;
+                let high = bytes
+                    .get(i + 1)
+                    .map_or(0, |byte| byte << (u8::BITS - shift));
+                low | high
+            })
+            .collect()
+    };
+    let last_bits = bit_shift(len);
+    if last_bits != 0 {
+        if let Some(last) = copy.last_mut() {
+            *last &= (1 << last_bits) - 1;
+        }
+    }
+    copy
+}
+
+/// Computes the 32-bit MurmurHash3 (x86 variant) of `key` with seed `seed`.
+pub(crate) fn murmur3_32(seed: u32, key: &[u8]) -> u32 {
+    let mut hasher = Murmur3::new(seed);
+    hasher.write(key);
+    hasher.finish()
+}
+
+/// An incremental 32-bit MurmurHash3 (x86 variant), for keys that arrive in pieces.
+///
+/// The hash of the concatenation of everything written is the same as [`murmur3_32()`] of it.
+#[derive(Clone, Debug)]
+pub(crate) struct Murmur3 {
+    hash: u32,
+    /// The bytes of a block that's been partly written.
+    block: [u8; 4],
+    block_len: usize,
+    len: u64,
+}
+
+impl Murmur3 {
+    const C1: u32 = 0xcc9e_2d51;
+    const C2: u32 = 0x1b87_3593;
+
+    pub(crate) fn new(seed: u32) -> Self {
+        Self {
+            hash: seed,
+            block: [0; 4],
+            block_len: 0,
+            len: 0,
+        }
+    }
+
+    fn scramble(k: u32) -> u32 {
+        k.wrapping_mul(Self::C1)
+            .rotate_left(15)
+            .wrapping_mul(Self::C2)
+    }
+
+    fn mix(&mut self, block: [u8; 4]) {
+        self.hash = (self.hash ^ Self::scramble(u32::from_le_bytes(block)))
+            .rotate_left(13)
+            .wrapping_mul(5)
+            .wrapping_add(0xe654_6b64);
+    }
+
+    pub(crate) fn write(&mut self, mut bytes: &[u8]) {
+        self.len += bytes.len() as u64;
+        if self.block_len != 0 {
+            let len = bytes.len().min(4 - self.block_len);
+            self.block[self.block_len..self.block_len + len].copy_from_slice(&bytes[..len]);
+            self.block_len += len;
+            bytes = &bytes[len..];
+            if self.block_len < 4 {
+                return;
+            }
+            self.mix(self.block);
+            self.block_len = 0;
+        }
+        let mut blocks = bytes.chunks_exact(4);
+        for block in &mut blocks {
+            self.mix(block.try_into().expect("4-byte block"));
+        }
+        let rest = blocks.remainder();
+        self.block[..rest.len()].copy_from_slice(rest);
+        self.block_len = rest.len();
+    }
+
+    pub(crate) fn finish(&self) -> u32 {
+        let mut hash = self.hash;
+        if self.block_len != 0 {
+            let mut k = [0; 4];
+            k[..self.block_len].copy_from_slice(&self.block[..self.block_len]);
+            hash ^= Self::scramble(u32::from_le_bytes(k));
+        }
+
+        // Only the low 32 bits of the length are mixed in, as in the reference implementation.
+        hash ^= self.len as u32;
+        hash ^= hash >> 16;
+        hash = hash.wrapping_mul(0x85eb_ca6b);
+        hash ^= hash >> 13;
+        hash = hash.wrapping_mul(0xc2b2_ae35);
+        hash ^ hash >> 16
+    }
+}
+
+/// Hashes `key` into a nonzero 31-bit mug, Hoon's `++mum`.
+///
+/// The 32-bit hash is folded into 31 bits, and the seed is incremented until the result is
+/// nonzero, giving up after eight tries and returning `fallback`.
+pub(crate) fn mum(mut seed: u32, fallback: u32, key: &[u8]) -> u32 {
+    for _ in 0..8 {
+        let hash = murmur3_32(seed, key);
+        let mug = (hash >> 31) ^ (hash & 0x7fff_ffff);
+        if mug != 0 {
+            return mug;
+        }
+        seed = seed.wrapping_add(1);
+    }
+    fallback
+}
+
 /// A bitwise [`Atom`] builder.
 pub struct Builder {
     bytes: Vec<u8>,
-    bit_idx: usize,
+    bit_idx: u64,
 }
 
 impl Builder {
@@ -33,19 +201,28 @@ impl Builder {
     }
 
     /// Returns the current bitwise position of the end of this builder.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the position exceeds `usize::MAX`, which is only possible on targets where a
+    /// `usize` is narrower than 64 bits. Use [`pos_u64()`](Self::pos_u64()) on those targets.
     pub fn pos(&self) -> usize {
+        bits_to_usize(self.bit_idx)
+    }
+
+    /// Returns the current bitwise position of the end of this builder as a `u64`.
+    pub fn pos_u64(&self) -> u64 {
         self.bit_idx
     }
 
     /// Pushes a bit onto the end of this builder.
     pub fn push_bit(&mut self, bit: bool) {
-        let u8_bits = usize::try_from(u8::BITS).expect("u32 to usize");
-        let byte_idx = self.bit_idx / u8_bits;
+        let byte_idx = byte_idx(self.bit_idx);
         if byte_idx == self.bytes.len() {
             self.bytes.push(0);
         }
         let byte = &mut self.bytes[byte_idx];
-        let shift = self.bit_idx % u8_bits;
+        let shift = bit_shift(self.bit_idx);
         if bit {
             *byte |= 1 << shift;
         } else {
@@ -54,11 +231,79 @@ impl Builder {
         self.bit_idx += 1;
     }
 
+    /// Reserves space for at least `bit_len` more bits to be pushed onto this builder.
+    pub fn reserve(&mut self, bit_len: usize) {
+        self.reserve_u64(u64::try_from(bit_len).expect("usize to u64"));
+    }
+
+    /// Reserves space for at least `bit_len` more bits to be pushed onto this builder.
+    pub(crate) fn reserve_u64(&mut self, bit_len: u64) {
+        let len = self
+            .bit_idx
+            .saturating_add(bit_len)
+            .div_ceil(u64::from(u8::BITS));
+        let len = usize::try_from(len).unwrap_or(usize::MAX);
+        self.bytes.reserve(len.saturating_sub(self.bytes.len()));
+    }
+
+    /// Pushes the `len` least significant bits of `bits` onto the end of this builder, starting
+    /// with the least significant bit.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` is greater than 64.
+    pub fn push_bits(&mut self, bits: u64, len: u32) {
+        assert!(len <= u64::BITS, "cannot push more than 64 bits at a time");
+        if len == 0 {
+            return;
+        }
+        let shift = bit_shift(self.bit_idx);
+        let bits = u128::from(bits) & ((1 << len) - 1);
+        // Bits past the end of the builder are always zero, so the shifted bits can be or'ed in.
+        let bits = bits << shift;
+        let byte_idx = byte_idx(self.bit_idx);
+        let end_bit_idx = self.bit_idx + u64::from(len);
+        self.bytes.resize(byte_len(end_bit_idx), 0);
+        for (i, byte) in self.bytes[byte_idx..].iter_mut().enumerate() {
+            *byte |= (bits >> (i * 8)) as u8;
+        }
+        self.bit_idx = end_bit_idx;
+    }
+
+    /// Pushes the bits of `atom` onto the end of this builder, starting with the least
+    /// significant bit.
+    ///
+    /// Whole bytes are copied at a time, so this is much faster than pushing the bits of `atom`
+    /// one at a time.
+    pub fn push_atom(&mut self, atom: &Atom) {
+        let shift = bit_shift(self.bit_idx);
+        let end_bit_idx = self.bit_idx + atom.bit_len_u64();
+        if shift == 0 {
+            self.bytes.extend_from_slice(atom.as_bytes());
+        } else {
+            self.bytes.reserve(atom.as_bytes().len());
+            for &byte in atom.as_bytes() {
+                // The last byte of the builder is partially filled when it's not aligned.
+                if let Some(last) = self.bytes.last_mut() {
+                    *last |= byte << shift;
+                }
+                self.bytes.push(byte >> (u8::BITS - shift));
+            }
+        }
+        self.bytes.truncate(byte_len(end_bit_idx));
+        self.bit_idx = end_bit_idx;
+    }
+
+    /// Converts this builder into a byte vector, consuming the builder.
+    pub(crate) fn into_vec(self) -> Vec<u8> {
+        self.bytes
+    }
+
     /// Converts this builder into an `Atom`, consuming the builder.
     pub fn into_atom(self) -> Atom {
-        let bytes = self.bytes;
-        let bit_len = bit_len(&bytes[..]);
-        Atom { bytes, bit_len }
+        // Trailing zero bytes are trimmed so that the atom compares equal to any other atom with
+        // the same value.
+        Atom::from(self.bytes)
     }
 }
 
@@ -68,6 +313,39 @@ impl Default for Builder {
     }
 }
 
+/// Pushes bytes onto the end of this builder, each as 8 bits starting with its least significant
+/// bit.
+///
+/// If the builder isn't byte-aligned, the bytes are shifted to start at its current position,
+/// just like [`push_bits(byte, 8)`](Builder::push_bits()), rather than padded to the next byte.
+/// Trailing zero bytes are kept until [`into_atom()`](Builder::into_atom()) trims them.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::atom::Atom;
+/// let mut builder = Atom::builder();
+/// builder.push_bits(0b101, 3);
+/// builder.extend([0xff, 0x00]);
+/// assert_eq!(builder.pos(), 19);
+/// assert_eq!(builder.into_atom(), 0xff << 3 | 0b101u16);
+/// ```
+impl Extend<u8> for Builder {
+    fn extend<I: IntoIterator<Item = u8>>(&mut self, bytes: I) {
+        let bytes = bytes.into_iter();
+        if bit_shift(self.bit_idx) == 0 {
+            self.bytes.extend(bytes);
+            self.bit_idx =
+                u64::from(u8::BITS) * u64::try_from(self.bytes.len()).expect("usize to u64");
+        } else {
+            self.reserve(bytes.size_hint().0 * u8::BITS as usize);
+            for byte in bytes {
+                self.push_bits(u64::from(byte), u8::BITS);
+            }
+        }
+    }
+}
+
 /// An arbitrarily large unsigned integer.
 ///
 /// An [atom] is an arbitrarily large unsigned integer represented as a little-endian contiguous
@@ -99,9 +377,60 @@ impl Default for Builder {
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub struct Atom {
     bytes: Vec<u8>,
-    bit_len: usize,
+    bit_len: u64,
+}
+
+/// The error returned when a relative time (`@dr`) doesn't fit in a [`Duration`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum DrError {
+    /// The atom is wider than 128 bits, or rounding its fraction up to the next second overflows
+    /// the seconds of a [`Duration`].
+    OutOfRange,
+}
+
+impl Display for DrError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        match self {
+            Self::OutOfRange => write!(f, "the relative time is out of range for a `Duration`"),
+        }
+    }
+}
+
+impl std::error::Error for DrError {}
+
+/// The error returned when a string isn't an atom in the given radix.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ParseAtomError {
+    /// The string has no digits.
+    Empty,
+    /// A character isn't a digit in the radix or a `_` separator.
+    InvalidDigit {
+        /// The index of the character, which is also its byte offset since every character before
+        /// it is ASCII.
+        index: usize,
+        /// The character.
+        character: char,
+    },
+}
+
+impl Display for ParseAtomError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        match self {
+            Self::Empty => write!(f, "cannot parse an atom from a string without digits"),
+            Self::InvalidDigit { index, character } => {
+                write!(f, "invalid digit {:?} at index {}", character, index)
+            }
+        }
+    }
 }
 
+impl std::error::Error for ParseAtomError {}
+
+/// The number of nanoseconds in a second.
+const NANOS_PER_SEC: u128 = 1_000_000_000;
+
 /// Converts an atom into an unsigned integer, returning `None` if the byte width of the atom
 /// exceeds the byte width of the target unsigned integer type.
 macro_rules! atom_as_uint {
@@ -137,11 +466,26 @@ impl Atom {
 
     /// Returns `true` if this atom is null (i.e. the atom `0`).
     pub const fn is_null(&self) -> bool {
-        self.bit_len() == 0
+        self.bit_len == 0
     }
 
     /// Returns the length in bits of this atom.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length exceeds `usize::MAX`, which is only possible on targets where a
+    /// `usize` is narrower than 64 bits. Use [`bit_len_u64()`](Self::bit_len_u64()) on those
+    /// targets.
     pub const fn bit_len(&self) -> usize {
+        assert!(
+            self.bit_len <= usize::MAX as u64,
+            "bit position or length exceeds usize::MAX"
+        );
+        self.bit_len as usize
+    }
+
+    /// Returns the length in bits of this atom as a `u64`.
+    pub const fn bit_len_u64(&self) -> u64 {
         self.bit_len
     }
 
@@ -152,6 +496,22 @@ impl Atom {
         hasher.finish()
     }
 
+    /// Computes the mug of this atom, Urbit's 31-bit hash of a noun.
+    ///
+    /// Unlike [`hash()`](Self::hash()), the mug of an atom is the same on every platform and in
+    /// every process, and agrees with Hoon's `++mug`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::atom::Atom;
+    /// assert_eq!(Atom::null().mug(), 2_046_756_072);
+    /// assert_eq!(Atom::from(10_000u16).mug(), 795_713_195);
+    /// ```
+    pub fn mug(&self) -> u32 {
+        mum(0xcafe_babe, 0x7fff, self.as_bytes())
+    }
+
     /// Converts this atom into a byte slice.
     pub fn as_bytes(&self) -> &[u8] {
         &self.bytes
@@ -163,6 +523,118 @@ impl Atom {
         str::from_utf8(self.as_bytes())
     }
 
+    /// Converts the ASCII uppercase letters of this cord (`@t`) to lowercase, like Hoon's
+    /// `++cass`.
+    ///
+    /// Every other byte is left as is, so letters outside of ASCII keep their case, and the cord
+    /// doesn't need to be UTF-8: no byte of a multi-byte UTF-8 character is ASCII, so valid UTF-8
+    /// stays valid. Use [`as_str()`](Self::as_str()) and [`str::to_lowercase()`] for Unicode case
+    /// mapping instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::atom::Atom;
+    /// assert_eq!(Atom::from("Content-Type").to_lowercase(), "content-type");
+    /// assert_eq!(Atom::from("CAFÉ").to_lowercase(), "cafÉ");
+    /// ```
+    pub fn to_lowercase(&self) -> Self {
+        Self::from(self.bytes.to_ascii_lowercase())
+    }
+
+    /// Converts the ASCII lowercase letters of this cord (`@t`) to uppercase, like Hoon's
+    /// `++cuss`.
+    ///
+    /// As with [`to_lowercase()`](Self::to_lowercase()), letters outside of ASCII keep their case.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::atom::Atom;
+    /// assert_eq!(Atom::from("get").to_uppercase(), "GET");
+    /// assert_eq!(Atom::from("café").to_uppercase(), "CAFé");
+    /// ```
+    pub fn to_uppercase(&self) -> Self {
+        Self::from(self.bytes.to_ascii_uppercase())
+    }
+
+    /// Removes leading and trailing whitespace from this cord (`@t`), as [`str::trim()`] does,
+    /// returning an error if the cord isn't UTF-8.
+    ///
+    /// NUL isn't whitespace, so it isn't trimmed, but a NUL that ends up at the end of the trimmed
+    /// cord is dropped, since a cord can't end in NUL.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::atom::Atom;
+    /// assert_eq!(Atom::from(" \t~zod\n").trim().unwrap(), "~zod");
+    /// assert!(Atom::from(vec![b' ', 0xff]).trim().is_err());
+    /// ```
+    pub fn trim(&self) -> Result<Self, Utf8Error> {
+        Ok(Self::from(self.as_str()?.trim()))
+    }
+
+    /// Splits this cord (`@t`) into the cords separated by the byte `sep`, as [`str::split()`]
+    /// does, so the null cord splits into a single null cord.
+    ///
+    /// The cord doesn't need to be UTF-8, and if it is and `sep` is ASCII, so is every piece.
+    ///
+    /// A cord can contain NULs, just not at the end, since the trailing zero bytes of an atom
+    /// aren't part of it. The same goes for each piece: NULs right before a separator are dropped
+    /// from the piece they end, so `"a\0/b"` split at `/` is `"a"` and `"b"`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::atom::Atom;
+    /// let segments: Vec<_> = Atom::from("gx/~zod//updates").split(b'/').collect();
+    /// assert_eq!(segments, ["gx", "~zod", "", "updates"]);
+    /// ```
+    pub fn split(&self, sep: u8) -> impl Iterator<Item = Self> + '_ {
+        self.bytes
+            .split(move |&byte| byte == sep)
+            .map(|piece| Self::from(piece.to_vec()))
+    }
+
+    /// Returns `true` if the bytes of this cord (`@t`) start with those of `prefix`.
+    ///
+    /// A prefix that ends in NUL only matches a cord with a byte other than NUL after it, because
+    /// a cord can't end in NUL.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::atom::Atom;
+    /// assert!(Atom::from("~sampel-palnet").starts_with("~sampel"));
+    /// assert!(!Atom::from("a").starts_with("a\0"));
+    /// ```
+    pub fn starts_with(&self, prefix: &str) -> bool {
+        self.bytes.starts_with(prefix.as_bytes())
+    }
+
+    /// Concatenates the bytes of `cords` (`@t`) into a single cord, like Hoon's `(rap 3 cords)`.
+    ///
+    /// Null cords have no bytes, so they add nothing, and neither can any other cord add NULs at
+    /// its end. The result is UTF-8 if every cord is.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::atom::Atom;
+    /// let (scheme, host) = (Atom::from("https://"), Atom::from("urbit.org"));
+    /// assert_eq!(Atom::concat(&[&scheme, &Atom::null(), &host]), "https://urbit.org");
+    /// ```
+    pub fn concat(cords: &[&Self]) -> Self {
+        let len = cords.iter().map(|cord| cord.bytes.len()).sum();
+        let mut bytes = Vec::with_capacity(len);
+        for cord in cords {
+            bytes.extend_from_slice(&cord.bytes);
+        }
+        // The last byte of the last non-null cord isn't zero, so there's nothing to trim.
+        Self::from(bytes)
+    }
+
     /// Converts this atom into an 8-bit unsigned integer, returning `None` if the atom is greater
     /// than `u8::MAX`.
     ///
@@ -247,6 +719,76 @@ impl Atom {
         atom_as_uint!(self, usize)
     }
 
+    /// The largest direct atom.
+    ///
+    /// Vere stores atoms that fit in 62 bits inline in a tagged 64-bit word, which it calls
+    /// direct, and every larger atom on the heap, which it calls indirect. The top two bits of the
+    /// word are its tag.
+    pub const DIRECT_MAX: u64 = (1 << 62) - 1;
+
+    /// Returns `true` if this atom is direct, i.e. no greater than
+    /// [`DIRECT_MAX`](Self::DIRECT_MAX).
+    pub fn is_direct(&self) -> bool {
+        self.as_direct().is_some()
+    }
+
+    /// Converts this atom into a 64-bit unsigned integer, returning `None` if the atom isn't
+    /// direct.
+    ///
+    /// # Examples
+    /// ```
+    /// # use noun::atom::Atom;
+    /// assert_eq!(Atom::from(Atom::DIRECT_MAX).as_direct(), Some(Atom::DIRECT_MAX));
+    /// assert_eq!(Atom::from(1u64 << 62).as_direct(), None);
+    /// ```
+    pub fn as_direct(&self) -> Option<u64> {
+        self.as_u64().filter(|&direct| direct <= Self::DIRECT_MAX)
+    }
+
+    /// Creates a direct atom.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `direct` is greater than [`DIRECT_MAX`](Self::DIRECT_MAX).
+    pub fn from_direct(direct: u64) -> Self {
+        assert!(
+            direct <= Self::DIRECT_MAX,
+            "{} is too large to be a direct atom",
+            direct
+        );
+        Self::from(direct)
+    }
+
+    /// Returns `true` if the last byte of this atom is zero, which violates the invariant that
+    /// atoms never have trailing zero bytes.
+    pub(crate) fn has_trailing_zeros(&self) -> bool {
+        self.bytes.last() == Some(&0)
+    }
+
+    /// Returns the length in bits of the bytes of this atom, which is what its cached length is
+    /// supposed to be.
+    pub(crate) fn bytes_bit_len(&self) -> u64 {
+        bit_len(&self.bytes)
+    }
+
+    /// Returns `true` if this atom has no trailing zero bytes and its cached length matches its
+    /// bytes.
+    pub(crate) fn is_normalized(&self) -> bool {
+        !self.has_trailing_zeros() && self.bit_len == self.bytes_bit_len()
+    }
+
+    /// Returns a copy of this atom with its trailing zero bytes trimmed and its length recomputed.
+    pub(crate) fn normalized(&self) -> Self {
+        Self::from(self.to_vec())
+    }
+
+    /// Creates an atom from its parts as is, without upholding any of its invariants, to test
+    /// code that checks them.
+    #[cfg(test)]
+    pub(crate) fn from_raw_parts(bytes: Vec<u8>, bit_len: u64) -> Self {
+        Self { bytes, bit_len }
+    }
+
     /// Copies this atom into a byte vector.
     pub fn to_vec(&self) -> Vec<u8> {
         Vec::from(self.as_bytes())
@@ -260,62 +802,386 @@ impl Atom {
     }
 
     /// Returns a bitwise iterator over this atom.
-    pub fn iter(&self) -> Iter {
+    pub fn iter(&self) -> Iter<'_> {
         Iter {
-            atom: self,
+            bytes: self.as_bytes(),
+            bit_len: self.bit_len,
             bit_idx: 0,
             bit_mask: 0b1,
         }
     }
-}
 
-impl Display for Atom {
-    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
-        write!(f, "0x")?;
-        if self.bytes.is_empty() {
-            write!(f, "0")
-        } else {
-            for (i, byte) in (self.bytes).iter().enumerate() {
-                if i > 0 && i % 4 == 0 {
-                    write!(f, ".")?;
-                }
-                write!(f, "{:x}", byte)?;
-            }
-            Ok(())
+    /// Reads an atom from the little-endian bytes of `reader`, up to its end.
+    ///
+    /// Fails with [`io::ErrorKind::InvalidData`] if there are more than `max` bytes, without
+    /// reading more than one byte past `max`. Trailing zero bytes count towards `max` even though
+    /// they don't change the atom.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::atom::Atom;
+    /// let atom = Atom::from_reader(&[0x34, 0x12, 0x00][..], 3).unwrap();
+    /// assert_eq!(atom, 0x1234u16);
+    /// assert!(Atom::from_reader(&[0x34, 0x12, 0x00][..], 2).is_err());
+    /// ```
+    pub fn from_reader(reader: impl Read, max: usize) -> io::Result<Self> {
+        let limit = u64::try_from(max).unwrap_or(u64::MAX).saturating_add(1);
+        let mut len = 0;
+        let atom = BufReader::new(reader.take(limit))
+            .bytes()
+            .inspect(|_| len += 1)
+            .collect::<io::Result<Self>>()?;
+        if len > max {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("atom is longer than {} bytes", max),
+            ));
         }
+        Ok(atom)
     }
-}
-
-impl TryFrom<&OsStr> for Atom {
-    type Error = ();
-
-    fn try_from(string: &OsStr) -> Result<Self, Self::Error> {
-        Ok(Self::from(string.to_str().ok_or(())?))
-    }
-}
 
-impl From<&str> for Atom {
-    fn from(string: &str) -> Self {
-        let bytes = string.as_bytes().to_vec();
-        let bit_len = bit_len(&bytes[..]);
-        Self { bytes, bit_len }
+    /// Converts a duration into a relative time (`@dr`).
+    ///
+    /// A relative time is a 64.64 fixed-point number of seconds: the high 64 bits are whole
+    /// seconds, and the low 64 bits are a fraction of a second in units of 2^-64 seconds. The
+    /// nanoseconds of the duration are rounded to the nearest such unit, with ties rounding up.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::atom::Atom;
+    /// # use std::time::Duration;
+    /// // ~s30..5000, 30 and 0x5000 / 0x1.0000 seconds.
+    /// let atom = Atom::from_duration(Duration::from_millis(30_312) + Duration::from_micros(500));
+    /// assert_eq!(atom, 30u128 << 64 | 0x5000 << 48);
+    /// ```
+    pub fn from_duration(duration: Duration) -> Self {
+        let secs = u128::from(duration.as_secs());
+        let nanos = u128::from(duration.subsec_nanos());
+        // Less than 2^64 because nanos is less than NANOS_PER_SEC.
+        let frac = ((nanos << 64) + NANOS_PER_SEC / 2) / NANOS_PER_SEC;
+        Self::from(secs << 64 | frac)
     }
-}
 
-impl From<String> for Atom {
-    fn from(string: String) -> Self {
-        Self::from(string.into_bytes())
+    /// Converts this atom, a relative time (`@dr`), into a duration.
+    ///
+    /// The fraction of a second is rounded to the nearest nanosecond, with ties rounding up, so
+    /// converting a duration into a relative time and back gives the same duration. A relative
+    /// time whose fraction isn't a whole number of nanoseconds, like `~s0..0001`, doesn't survive
+    /// the trip back.
+    ///
+    /// Fails with [`DrError::OutOfRange`] if this atom is wider than 128 bits or if rounding
+    /// overflows the seconds of the duration.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::atom::Atom;
+    /// # use std::time::Duration;
+    /// // ~m1..8000
+    /// let atom = Atom::from(60u128 << 64 | 0x8000 << 48);
+    /// assert_eq!(atom.to_duration().unwrap(), Duration::from_millis(60_500));
+    ///
+    /// // ~s0..0001 is 15,258.789... nanoseconds.
+    /// let atom = Atom::from(1u64 << 48);
+    /// assert_eq!(atom.to_duration().unwrap(), Duration::from_nanos(15_259));
+    /// ```
+    pub fn to_duration(&self) -> Result<Duration, DrError> {
+        let dr = self.as_u128().ok_or(DrError::OutOfRange)?;
+        let secs = (dr >> 64) as u64;
+        let frac = dr & u128::from(u64::MAX);
+        let nanos = (frac * NANOS_PER_SEC + (1 << 63)) >> 64;
+        if nanos == NANOS_PER_SEC {
+            let secs = secs.checked_add(1).ok_or(DrError::OutOfRange)?;
+            Ok(Duration::from_secs(secs))
+        } else {
+            Ok(Duration::new(secs, nanos as u32))
+        }
     }
-}
 
-/// Convert an unsigned integer primitive into an atom.
-macro_rules! impl_from_uint_for_atom {
-    ($uint:ty) => {
-        impl From<$uint> for Atom {
-            fn from(uint: $uint) -> Self {
-                Atom::from(Vec::from(uint.to_le_bytes()))
-            }
-        }
+    /// Parses an atom from a string of digits in `radix`, most significant digit first, which can
+    /// be of any length.
+    ///
+    /// Digits past `9` are the letters `a` to `z` in either case, and `_` separators can appear
+    /// anywhere. Fails if there are no digits or if any other character appears.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `radix` isn't in the range `2..=36`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::atom::{Atom, ParseAtomError};
+    /// let atom = Atom::from_str_radix("1_0000_0000_0000_0000_0000_0000_0000_0000", 16).unwrap();
+    /// assert_eq!(atom, Atom::from(vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]));
+    /// assert_eq!(Atom::from_str_radix("zz", 36).unwrap(), 1295u16);
+    /// assert_eq!(
+    ///     Atom::from_str_radix("12a", 10),
+    ///     Err(ParseAtomError::InvalidDigit { index: 2, character: 'a' })
+    /// );
+    /// ```
+    pub fn from_str_radix(digits: &str, radix: u32) -> Result<Self, ParseAtomError> {
+        assert!(
+            (2..=36).contains(&radix),
+            "radix {} is not in the range 2..=36",
+            radix
+        );
+        let mut values = Vec::with_capacity(digits.len());
+        for (index, character) in digits.chars().enumerate() {
+            if character == '_' {
+                continue;
+            }
+            match character.to_digit(radix) {
+                Some(value) => values.push(value as u8),
+                None => return Err(ParseAtomError::InvalidDigit { index, character }),
+            }
+        }
+        if values.is_empty() {
+            return Err(ParseAtomError::Empty);
+        }
+
+        if radix.is_power_of_two() {
+            // Each digit is a fixed number of bits, so the digits are packed directly, least
+            // significant first.
+            let digit_len = radix.trailing_zeros();
+            let mut builder = Self::builder();
+            builder.reserve(values.len() * digit_len as usize);
+            for &value in values.iter().rev() {
+                builder.push_bits(u64::from(value), digit_len);
+            }
+            return Ok(builder.into_atom());
+        }
+
+        // Multiply-accumulate as many digits at a time as fit in a limb.
+        let (chunk_len, _) = limb_chunk(radix);
+        let mut limbs: Vec<u64> = Vec::new();
+        for chunk in values.chunks(chunk_len) {
+            let mut carry = chunk
+                .iter()
+                .fold(0, |acc, &value| acc * u64::from(radix) + u64::from(value));
+            // The final chunk can be short, so it isn't necessarily scaled by `chunk_radix`.
+            let scale = u64::from(radix).pow(chunk.len() as u32);
+            for limb in &mut limbs {
+                let product = u128::from(*limb) * u128::from(scale) + u128::from(carry);
+                *limb = product as u64;
+                carry = (product >> u64::BITS) as u64;
+            }
+            if carry != 0 {
+                limbs.push(carry);
+            }
+        }
+        Ok(Self::from(
+            limbs
+                .iter()
+                .flat_map(|limb| limb.to_le_bytes())
+                .collect::<Vec<_>>(),
+        ))
+    }
+
+    /// Formats this atom as a string of digits in `radix`, most significant digit first, without
+    /// separators.
+    ///
+    /// Digits past `9` are the lowercase letters `a` to `z`. This is the inverse of
+    /// [`from_str_radix()`](Self::from_str_radix()).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `radix` isn't in the range `2..=36`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::atom::Atom;
+    /// assert_eq!(Atom::from(255u8).to_string_radix(2), "11111111");
+    /// assert_eq!(Atom::from(1295u16).to_string_radix(36), "zz");
+    /// assert_eq!(Atom::null().to_string_radix(10), "0");
+    /// ```
+    pub fn to_string_radix(&self, radix: u32) -> String {
+        assert!(
+            (2..=36).contains(&radix),
+            "radix {} is not in the range 2..=36",
+            radix
+        );
+        if self.is_null() {
+            return String::from("0");
+        }
+        // Digits are collected least significant first.
+        let mut digits: Vec<u8> = Vec::new();
+        if radix.is_power_of_two() {
+            // A digit is at most 5 bits, so it spans at most two bytes.
+            let digit_len = radix.trailing_zeros() as u64;
+            let mask = (1 << digit_len) - 1;
+            for bit_idx in (0..self.bit_len).step_by(digit_len as usize) {
+                let byte_idx = (bit_idx / u64::from(u8::BITS)) as usize;
+                let window = u16::from(self.bytes[byte_idx])
+                    | u16::from(self.bytes.get(byte_idx + 1).copied().unwrap_or(0)) << u8::BITS;
+                digits.push((window >> (bit_idx % u64::from(u8::BITS)) & mask) as u8);
+            }
+        } else {
+            // Divide by as large a power of the radix as fits in a limb until nothing is left.
+            let (chunk_len, chunk_radix) = limb_chunk(radix);
+            let mut limbs: Vec<u64> = self
+                .bytes
+                .chunks(8)
+                .map(|chunk| {
+                    let mut limb = [0; 8];
+                    limb[..chunk.len()].copy_from_slice(chunk);
+                    u64::from_le_bytes(limb)
+                })
+                .collect();
+            while !limbs.is_empty() {
+                let mut rem: u64 = 0;
+                for limb in limbs.iter_mut().rev() {
+                    let dividend = u128::from(rem) << u64::BITS | u128::from(*limb);
+                    *limb = (dividend / u128::from(chunk_radix)) as u64;
+                    rem = (dividend % u128::from(chunk_radix)) as u64;
+                }
+                while limbs.last() == Some(&0) {
+                    limbs.pop();
+                }
+                for _ in 0..chunk_len {
+                    digits.push((rem % u64::from(radix)) as u8);
+                    rem /= u64::from(radix);
+                }
+            }
+            // The most significant chunk is padded with zeros.
+            while digits.last() == Some(&0) {
+                digits.pop();
+            }
+        }
+        digits
+            .iter()
+            .rev()
+            .map(|&digit| char::from_digit(u32::from(digit), radix).expect("digit"))
+            .collect()
+    }
+}
+
+/// Returns the largest number of digits in `radix` that always fit in a `u64`, and `radix` raised
+/// to that power.
+fn limb_chunk(radix: u32) -> (usize, u64) {
+    let radix = u64::from(radix);
+    let mut chunk_len = 1;
+    let mut chunk_radix = radix;
+    while let Some(next) = chunk_radix.checked_mul(radix) {
+        chunk_len += 1;
+        chunk_radix = next;
+    }
+    (chunk_len, chunk_radix)
+}
+
+impl Atom {
+    /// Writes the first `shown` bytes of this atom as hex, followed by an ellipsis and the total
+    /// length if that isn't all of them.
+    fn write_hex(&self, f: &mut Formatter<'_>, shown: usize) -> Result<(), Error> {
+        write!(f, "0x")?;
+        if self.bytes.is_empty() {
+            return write!(f, "0");
+        }
+        for (i, byte) in self.bytes[..shown].iter().enumerate() {
+            if i > 0 && i % 4 == 0 {
+                write!(f, ".")?;
+            }
+            write!(f, "{:x}", byte)?;
+        }
+        if shown < self.bytes.len() {
+            write!(f, "...({} bytes)", self.bytes.len())?;
+        }
+        Ok(())
+    }
+
+    /// Returns the number of characters [`write_hex()`](Self::write_hex()) writes.
+    fn hex_len(&self, shown: usize) -> usize {
+        if self.bytes.is_empty() {
+            return "0x0".len();
+        }
+        let digits: usize = self.bytes[..shown]
+            .iter()
+            .map(|&byte| if byte < 0x10 { 1 } else { 2 })
+            .sum();
+        let dots = shown.saturating_sub(1) / 4;
+        let ellipsis = if shown < self.bytes.len() {
+            format!("...({} bytes)", self.bytes.len()).len()
+        } else {
+            0
+        };
+        "0x".len() + digits + dots + ellipsis
+    }
+}
+
+/// Writes the atom in hex, least significant byte first, with a `.` between every four bytes.
+///
+/// Width, fill, and alignment are honored as they are for strings. A precision of `N` shows at most
+/// `N` bytes of the atom, followed by an ellipsis and the total number of bytes, which keeps huge
+/// atoms from flooding a log.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::atom::Atom;
+/// let atom = Atom::from(vec![0xab; 6]);
+/// assert_eq!(atom.to_string(), "0xabababab.abab");
+/// assert_eq!(format!("{:.2}", atom), "0xabab...(6 bytes)");
+/// assert_eq!(format!("{:*>20.2}", atom), "**0xabab...(6 bytes)");
+/// ```
+impl Display for Atom {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        let shown = f.precision().map_or(self.bytes.len(), |precision| {
+            precision.min(self.bytes.len())
+        });
+        let Some(width) = f.width() else {
+            return self.write_hex(f, shown);
+        };
+        // The padding is computed from the length of the output rather than by padding a string of
+        // it, which could be huge.
+        let padding = width.saturating_sub(self.hex_len(shown));
+        let (before, after) = match f.align() {
+            Some(Alignment::Right) => (padding, 0),
+            Some(Alignment::Center) => (padding / 2, padding.div_ceil(2)),
+            Some(Alignment::Left) | None => (0, padding),
+        };
+        let fill = f.fill();
+        for _ in 0..before {
+            f.write_char(fill)?;
+        }
+        self.write_hex(f, shown)?;
+        for _ in 0..after {
+            f.write_char(fill)?;
+        }
+        Ok(())
+    }
+}
+
+impl TryFrom<&OsStr> for Atom {
+    type Error = ();
+
+    fn try_from(string: &OsStr) -> Result<Self, Self::Error> {
+        Ok(Self::from(string.to_str().ok_or(())?))
+    }
+}
+
+impl From<&str> for Atom {
+    fn from(string: &str) -> Self {
+        Self::from(string.as_bytes().to_vec())
+    }
+}
+
+impl From<String> for Atom {
+    fn from(string: String) -> Self {
+        Self::from(string.into_bytes())
+    }
+}
+
+/// Convert an unsigned integer primitive into an atom.
+macro_rules! impl_from_uint_for_atom {
+    ($uint:ty) => {
+        impl From<$uint> for Atom {
+            fn from(uint: $uint) -> Self {
+                Atom::from(Vec::from(uint.to_le_bytes()))
+            }
+        }
     };
 }
 
@@ -341,6 +1207,32 @@ impl From<Vec<u8>> for Atom {
     }
 }
 
+/// Converts little-endian bytes into an atom, trimming any trailing zero bytes.
+///
+/// This is the inverse of converting an atom into a byte array, which zero-extends the atom.
+impl<const N: usize> From<[u8; N]> for Atom {
+    fn from(bytes: [u8; N]) -> Self {
+        Self::from(Vec::from(bytes))
+    }
+}
+
+/// Collects little-endian bytes into an atom, trimming trailing zero bytes once at the end.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::atom::Atom;
+/// let atom: Atom = (1..=4u8).collect();
+/// assert_eq!(atom, 0x0403_0201u32);
+/// ```
+impl FromIterator<u8> for Atom {
+    fn from_iter<I: IntoIterator<Item = u8>>(bytes: I) -> Self {
+        let mut builder = Self::builder();
+        builder.extend(bytes);
+        builder.into_atom()
+    }
+}
+
 impl PartialEq<&Self> for Atom {
     fn eq(&self, other: &&Self) -> bool {
         self.bytes == other.bytes
@@ -389,45 +1281,471 @@ impl_partial_eq_uint_for_atom!(u64, as_u64);
 impl_partial_eq_uint_for_atom!(u128, as_u128);
 impl_partial_eq_uint_for_atom!(usize, as_usize);
 
+/// Adds an atom to this atom in place, growing it by at most one byte.
+impl AddAssign<&Atom> for Atom {
+    fn add_assign(&mut self, rhs: &Atom) {
+        if self.bytes.len() < rhs.bytes.len() {
+            self.bytes.resize(rhs.bytes.len(), 0);
+        }
+        let mut carry = 0;
+        for (i, byte) in self.bytes.iter_mut().enumerate() {
+            if i >= rhs.bytes.len() && carry == 0 {
+                break;
+            }
+            let sum = u16::from(*byte) + u16::from(rhs.bytes.get(i).copied().unwrap_or(0)) + carry;
+            *byte = sum as u8;
+            carry = sum >> u8::BITS;
+        }
+        if carry != 0 {
+            self.bytes.push(carry as u8);
+        }
+        self.bit_len = bit_len(&self.bytes);
+    }
+}
+
+impl AddAssign for Atom {
+    fn add_assign(&mut self, rhs: Atom) {
+        *self += &rhs;
+    }
+}
+
+impl Add<&Atom> for Atom {
+    type Output = Atom;
+
+    fn add(mut self, rhs: &Atom) -> Atom {
+        self += rhs;
+        self
+    }
+}
+
+impl Add for Atom {
+    type Output = Atom;
+
+    fn add(mut self, rhs: Atom) -> Atom {
+        self += &rhs;
+        self
+    }
+}
+
+impl Add for &Atom {
+    type Output = Atom;
+
+    fn add(self, rhs: &Atom) -> Atom {
+        self.clone() + rhs
+    }
+}
+
+/// Multiplies this atom by an atom, replacing it with the product.
+impl MulAssign<&Atom> for Atom {
+    fn mul_assign(&mut self, rhs: &Atom) {
+        let mut product = vec![0u8; self.bytes.len() + rhs.bytes.len()];
+        for (i, &a) in self.bytes.iter().enumerate() {
+            // `255 + 255 * 255 + 255` fits in a `u16`, so neither the carry nor the partial sum
+            // can overflow.
+            let mut carry = 0;
+            for (j, &b) in rhs.bytes.iter().enumerate() {
+                let sum = u16::from(product[i + j]) + u16::from(a) * u16::from(b) + carry;
+                product[i + j] = sum as u8;
+                carry = sum >> u8::BITS;
+            }
+            product[i + rhs.bytes.len()] = carry as u8;
+        }
+        *self = Self::from(product);
+    }
+}
+
+impl MulAssign for Atom {
+    fn mul_assign(&mut self, rhs: Atom) {
+        *self *= &rhs;
+    }
+}
+
+impl Mul<&Atom> for Atom {
+    type Output = Atom;
+
+    fn mul(mut self, rhs: &Atom) -> Atom {
+        self *= rhs;
+        self
+    }
+}
+
+impl Mul for Atom {
+    type Output = Atom;
+
+    fn mul(mut self, rhs: Atom) -> Atom {
+        self *= &rhs;
+        self
+    }
+}
+
+impl Mul for &Atom {
+    type Output = Atom;
+
+    fn mul(self, rhs: &Atom) -> Atom {
+        self.clone() * rhs
+    }
+}
+
+/// Sums atoms into a single accumulator, which is `0` for no atoms.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::atom::Atom;
+/// let atoms = [Atom::from(u64::MAX), Atom::from(1u8)];
+/// assert_eq!(atoms.iter().sum::<Atom>(), 1u128 << 64);
+/// assert_eq!(Vec::<Atom>::new().into_iter().sum::<Atom>(), 0u8);
+/// ```
+impl Sum for Atom {
+    fn sum<I: Iterator<Item = Atom>>(iter: I) -> Self {
+        iter.fold(Self::null(), |mut sum, atom| {
+            sum += &atom;
+            sum
+        })
+    }
+}
+
+impl<'a> Sum<&'a Atom> for Atom {
+    fn sum<I: Iterator<Item = &'a Atom>>(iter: I) -> Self {
+        iter.fold(Self::null(), |mut sum, atom| {
+            sum += atom;
+            sum
+        })
+    }
+}
+
+/// Multiplies atoms together, which is `1` for no atoms.
+impl Product for Atom {
+    fn product<I: Iterator<Item = Atom>>(iter: I) -> Self {
+        iter.fold(Self::from(1u8), |mut product, atom| {
+            product *= &atom;
+            product
+        })
+    }
+}
+
+impl<'a> Product<&'a Atom> for Atom {
+    fn product<I: Iterator<Item = &'a Atom>>(iter: I) -> Self {
+        iter.fold(Self::from(1u8), |mut product, atom| {
+            product *= atom;
+            product
+        })
+    }
+}
+
 /// An iterator over the bits of an [`Atom`].
 ///
 /// Iteration starts with the least significant bit of the [`Atom`] and ends with the most
 /// significant bit.
 pub struct Iter<'a> {
-    /// Atom being interated over.
-    atom: &'a Atom,
+    /// Bytes of the atom being interated over.
+    bytes: &'a [u8],
+    /// Length in bits of the atom being iterated over.
+    bit_len: u64,
     /// Index of the current bit.
-    bit_idx: usize,
+    bit_idx: u64,
     /// Mask to access current bit.
     bit_mask: u8,
 }
 
+impl<'a> Iter<'a> {
+    /// Creates a bitwise iterator over a little-endian sequence of bytes, ignoring any trailing
+    /// zero bytes.
+    pub(crate) fn from_bytes(bytes: &'a [u8]) -> Self {
+        let len = match bytes.iter().rposition(|x| *x != 0) {
+            Some(idx) => idx + 1,
+            None => 0,
+        };
+        let bytes = &bytes[..len];
+        Self {
+            bytes,
+            bit_len: bit_len(bytes),
+            bit_idx: 0,
+            bit_mask: 0b1,
+        }
+    }
+}
+
 impl Iter<'_> {
     /// Returns the current bitwise position of this iterator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the position exceeds `usize::MAX`, which is only possible on targets where a
+    /// `usize` is narrower than 64 bits. Use [`pos_u64()`](Self::pos_u64()) on those targets.
     pub fn pos(&self) -> usize {
+        bits_to_usize(self.bit_idx)
+    }
+
+    /// Returns the current bitwise position of this iterator as a `u64`.
+    pub fn pos_u64(&self) -> u64 {
         self.bit_idx
     }
+
+    /// Returns the number of bits left to iterate over.
+    pub(crate) fn remaining(&self) -> u64 {
+        self.bit_len - self.bit_idx
+    }
+
+    /// Reads the next `len` bits as an atom, advancing this iterator past them.
+    ///
+    /// Whole bytes are copied at a time, so this is much faster than reading `len` bits one at a
+    /// time. Returns `None`, leaving this iterator unchanged, if fewer than `len` bits remain.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::atom::Atom;
+    /// let atom = Atom::from(0b1011_0110u8);
+    /// let mut iter = atom.iter();
+    /// assert_eq!(iter.next(), Some(false));
+    /// assert_eq!(iter.read_bits(4), Some(Atom::from(0b1011u8)));
+    /// assert_eq!(iter.read_bits(4), None);
+    /// assert_eq!(iter.read_bits(3), Some(Atom::from(0b101u8)));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    pub fn read_bits(&mut self, len: usize) -> Option<Atom> {
+        self.read_bits_u64(u64::try_from(len).expect("usize to u64"))
+    }
+
+    /// Reads the next `len` bits as an atom like [`read_bits()`](Self::read_bits()).
+    pub(crate) fn read_bits_u64(&mut self, len: u64) -> Option<Atom> {
+        if len > self.remaining() {
+            return None;
+        }
+        let atom = Atom::from(copy_bits(self.bytes, self.bit_idx, len));
+        self.seek(self.bit_idx + len);
+        Some(atom)
+    }
+
+    /// Moves this iterator to bit `bit_idx`, or to its end if `bit_idx` is past the end.
+    pub(crate) fn seek(&mut self, bit_idx: u64) {
+        self.bit_idx = bit_idx.min(self.bit_len);
+        self.bit_mask = 1 << bit_shift(self.bit_idx);
+    }
 }
 
 impl Iterator for Iter<'_> {
     type Item = bool;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.bit_idx == self.atom.bit_len {
+        if self.bit_idx == self.bit_len {
             return None;
         }
-        let byte_idx = self.bit_idx / usize::try_from(u8::BITS).expect("u32 to usize");
-        let bit = (self.atom.bytes[byte_idx] & self.bit_mask) != 0;
+        let bit = (self.bytes[byte_idx(self.bit_idx)] & self.bit_mask) != 0;
         self.bit_mask = self.bit_mask.rotate_left(1);
         self.bit_idx += 1;
         Some(bit)
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match usize::try_from(self.remaining()) {
+            Ok(len) => (len, Some(len)),
+            Err(_) => (usize::MAX, None),
+        }
+    }
+}
+
+impl ExactSizeIterator for Iter<'_> {}
+
+/// Returns an error for reading or writing more bits of a value than its type has.
+#[cfg(feature = "bitstream-io")]
+fn excessive_bits() -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, "more bits than the type has")
+}
+
+/// A [`BitRead`] over the bits of an atom, least significant first, just as jam and cue read
+/// them.
+///
+/// This reads exactly the bits of the atom, so unlike a [`BitReader`](bitstream_io::BitReader)
+/// over [`Atom::as_bytes()`], it can't read the zero bits that pad the atom's last byte. Reading
+/// past the end of the atom fails with [`io::ErrorKind::UnexpectedEof`], leaving the reader where
+/// it was.
+///
+/// # Examples
+///
+/// ```
+/// # use bitstream_io::BitRead;
+/// # use noun::atom::{Atom, AtomBitReader};
+/// let atom = Atom::from(0b1011_0110u8);
+/// let mut reader = AtomBitReader::new(atom.iter());
+/// assert!(!reader.read_bit().unwrap());
+/// assert_eq!(reader.read::<u8>(4).unwrap(), 0b1011);
+/// assert!(reader.read::<u8>(4).is_err());
+/// assert_eq!(reader.read::<u8>(3).unwrap(), 0b101);
+/// ```
+#[cfg(feature = "bitstream-io")]
+pub struct AtomBitReader<'a> {
+    iter: Iter<'a>,
+}
+
+#[cfg(feature = "bitstream-io")]
+impl<'a> AtomBitReader<'a> {
+    /// Creates a reader that starts at the current position of `iter`.
+    pub fn new(iter: Iter<'a>) -> Self {
+        Self { iter }
+    }
+
+    /// Returns the underlying iterator, positioned just past the last bit read.
+    pub fn into_inner(self) -> Iter<'a> {
+        self.iter
+    }
+
+    /// Fails unless at least `len` bits are left to read.
+    fn check_remaining(&self, len: u32) -> io::Result<()> {
+        if u64::from(len) > self.iter.remaining() {
+            Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "read past the end of the atom",
+            ))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(feature = "bitstream-io")]
+impl BitRead for AtomBitReader<'_> {
+    fn read_bit(&mut self) -> io::Result<bool> {
+        self.check_remaining(1)?;
+        Ok(self.iter.next().expect("bit"))
+    }
+
+    fn read<U: Numeric>(&mut self, bits: u32) -> io::Result<U> {
+        if bits > U::BITS_SIZE {
+            return Err(excessive_bits());
+        }
+        self.check_remaining(bits)?;
+        let mut value = U::default();
+        for i in 0..bits {
+            if self.iter.next().expect("bit") {
+                value |= U::ONE << i;
+            }
+        }
+        Ok(value)
+    }
+
+    fn read_signed<S: SignedNumeric>(&mut self, bits: u32) -> io::Result<S> {
+        if bits > S::BITS_SIZE {
+            return Err(excessive_bits());
+        }
+        self.check_remaining(bits)?;
+        LittleEndian::read_signed(self, bits)
+    }
+
+    fn read_to<V: Primitive>(&mut self) -> io::Result<V> {
+        self.check_remaining(V::buffer().as_ref().len() as u32 * u8::BITS)?;
+        LittleEndian::read_primitive(self)
+    }
+
+    fn read_as_to<F: Endianness, V: Primitive>(&mut self) -> io::Result<V> {
+        self.check_remaining(V::buffer().as_ref().len() as u32 * u8::BITS)?;
+        F::read_primitive(self)
+    }
+
+    fn skip(&mut self, bits: u32) -> io::Result<()> {
+        self.check_remaining(bits)?;
+        self.iter.seek(self.iter.pos_u64() + u64::from(bits));
+        Ok(())
+    }
+
+    fn byte_aligned(&self) -> bool {
+        self.iter.pos_u64().is_multiple_of(u64::from(u8::BITS))
+    }
+
+    /// Skips to the start of the next byte, or to the end of the atom if that comes first.
+    fn byte_align(&mut self) {
+        let pos = self.iter.pos_u64().next_multiple_of(u64::from(u8::BITS));
+        self.iter.seek(pos);
+    }
+}
+
+/// Writes bits onto the end of the atom, least significant first, just as jam writes them.
+///
+/// # Examples
+///
+/// ```
+/// # use bitstream_io::BitWrite;
+/// # use noun::atom::Atom;
+/// let mut builder = Atom::builder();
+/// builder.write_bit(false).unwrap();
+/// builder.write(4, 0b1011u8).unwrap();
+/// builder.write(3, 0b101u8).unwrap();
+/// assert_eq!(builder.into_atom(), 0b1011_0110u8);
+/// ```
+#[cfg(feature = "bitstream-io")]
+impl BitWrite for Builder {
+    fn write_bit(&mut self, bit: bool) -> io::Result<()> {
+        self.push_bit(bit);
+        Ok(())
+    }
+
+    fn write<U: Numeric>(&mut self, bits: u32, value: U) -> io::Result<()> {
+        if bits > U::BITS_SIZE {
+            return Err(excessive_bits());
+        }
+        if bits < U::BITS_SIZE && !(value >> bits).is_zero() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "value too large for the number of bits",
+            ));
+        }
+        for i in 0..bits {
+            self.push_bit((value >> i).to_u8() & 1 != 0);
+        }
+        Ok(())
+    }
+
+    fn write_signed<S: SignedNumeric>(&mut self, bits: u32, value: S) -> io::Result<()> {
+        if bits > S::BITS_SIZE {
+            return Err(excessive_bits());
+        }
+        LittleEndian::write_signed(self, bits, value)
+    }
+
+    fn write_from<V: Primitive>(&mut self, value: V) -> io::Result<()> {
+        LittleEndian::write_primitive(self, value)
+    }
+
+    fn write_as_from<F: Endianness, V: Primitive>(&mut self, value: V) -> io::Result<()> {
+        F::write_primitive(self, value)
+    }
+
+    fn byte_aligned(&self) -> bool {
+        self.bit_idx.is_multiple_of(u64::from(u8::BITS))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn murmur3() {
+        // Reference vectors of MurmurHash3_x86_32.
+        let cases: [(&[u8], u32, u32); 5] = [
+            (b"", 0, 0),
+            (b"", 1, 0x514e_28b7),
+            (b"", 0xffff_ffff, 0x81f1_6f39),
+            (b"test", 0, 0xba6b_d213),
+            (b"Hello, world!", 1234, 0xfaf6_cdb3),
+        ];
+        for (key, seed, hash) in cases {
+            assert_eq!(murmur3_32(seed, key), hash);
+        }
+
+        // Hashing in pieces of any size gives the same hash as hashing all at once.
+        let key: Vec<u8> = (0..=255).collect();
+        for len in 1..=9 {
+            let mut hasher = Murmur3::new(0xcafe_babe);
+            for piece in key.chunks(len) {
+                hasher.write(piece);
+            }
+            hasher.write(&[]);
+            assert_eq!(hasher.finish(), murmur3_32(0xcafe_babe, &key), "{}", len);
+        }
+    }
+
     #[test]
     fn bit_len() {
         {
@@ -454,12 +1772,154 @@ mod tests {
         }
     }
 
+    #[test]
+    fn builder() {
+        // Pushing bits and atoms at every alignment matches pushing them a bit at a time.
+        let atoms = [
+            Atom::from(0u8),
+            Atom::from(1u8),
+            Atom::from(0xffu8),
+            Atom::from(0x1234u16),
+            Atom::from(u128::MAX),
+            Atom::from("hello, world"),
+        ];
+        let words = [
+            (0u64, 0),
+            (0b1011, 4),
+            (u64::MAX, 64),
+            (0xdead_beef, 32),
+            (u64::MAX, 7),
+        ];
+        for offset in 0..16 {
+            for atom in &atoms {
+                let mut expected = Builder::new();
+                let mut actual = Builder::new();
+                for i in 0..offset {
+                    expected.push_bit(i % 3 == 0);
+                    actual.push_bit(i % 3 == 0);
+                }
+                for bit in atom.iter() {
+                    expected.push_bit(bit);
+                }
+                actual.push_atom(atom);
+                assert_eq!(actual.pos(), expected.pos());
+                // Make sure the bits following the atom don't clobber it.
+                expected.push_bit(true);
+                actual.push_bit(true);
+                assert_eq!(actual.into_atom(), expected.into_atom());
+            }
+
+            for (bits, len) in words {
+                let mut expected = Builder::new();
+                let mut actual = Builder::new();
+                for i in 0..offset {
+                    expected.push_bit(i % 2 == 0);
+                    actual.push_bit(i % 2 == 0);
+                }
+                for i in 0..len {
+                    expected.push_bit(bits & (1 << i) != 0);
+                }
+                actual.push_bits(bits, len);
+                assert_eq!(actual.pos(), expected.pos());
+                expected.push_bit(true);
+                actual.push_bit(true);
+                assert_eq!(actual.into_atom(), expected.into_atom());
+            }
+        }
+    }
+
+    #[test]
+    fn from_bytes() {
+        // Trailing zero bytes are trimmed, however many there are.
+        {
+            let atom: Atom = [0x01, 0x02, 0x00, 0x00].into_iter().collect();
+            assert_eq!(atom, 0x0201u16);
+            assert_eq!(atom.as_bytes(), [0x01, 0x02]);
+            assert_eq!(atom.bit_len(), 10);
+
+            let atom: Atom = std::iter::repeat_n(0, 1000).collect();
+            assert!(atom.is_null());
+            assert_eq!(std::iter::empty().collect::<Atom>(), Atom::null());
+        }
+
+        // Bytes extend an aligned builder as whole bytes, and a builder that's mid-byte as bits
+        // shifted to its position.
+        {
+            let mut builder = Atom::builder();
+            builder.extend([0xab, 0x00]);
+            assert_eq!(builder.pos(), 16);
+            builder.push_bit(true);
+            assert_eq!(builder.into_atom(), 0x1_00abu32);
+
+            for offset in 1..8 {
+                let mut expected = Atom::builder();
+                let mut actual = Atom::builder();
+                for i in 0..offset {
+                    expected.push_bit(i % 2 == 0);
+                    actual.push_bit(i % 2 == 0);
+                }
+                for byte in [0xff, 0x81, 0x00, 0x00] {
+                    expected.push_bits(byte, 8);
+                }
+                actual.extend([0xff, 0x81, 0x00, 0x00]);
+                assert_eq!(actual.pos(), expected.pos());
+                assert_eq!(actual.pos(), offset + 32);
+                expected.push_bit(true);
+                actual.push_bit(true);
+                assert_eq!(actual.into_atom(), expected.into_atom());
+            }
+        }
+
+        // Reading from a reader, where trailing zero bytes count towards the limit.
+        {
+            let bytes = [0xef, 0xbe, 0xad, 0xde, 0x00, 0x00];
+            for max in [6, 7, usize::MAX] {
+                let atom = Atom::from_reader(&bytes[..], max).expect("read");
+                assert_eq!(atom, 0xdead_beefu32);
+            }
+            let err = Atom::from_reader(&bytes[..], 5).expect_err("too long");
+            assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+            assert_eq!(Atom::from_reader(&[][..], 0).ok(), Some(Atom::null()));
+            assert!(Atom::from_reader(&[0][..], 0).is_err());
+        }
+    }
+
     #[test]
     fn is_null() {
         assert!(Atom::from(0u8).is_null());
         assert!(!Atom::from(1u8).is_null());
     }
 
+    #[test]
+    fn direct() {
+        // The boundary.
+        {
+            let max = Atom::from((1u64 << 62) - 1);
+            assert!(max.is_direct());
+            assert_eq!(max.as_direct(), Some((1 << 62) - 1));
+            assert_eq!(Atom::from_direct((1 << 62) - 1), max);
+            for atom in [Atom::from(1u64 << 62), Atom::from(1u64 << 63)] {
+                assert!(!atom.is_direct());
+                assert_eq!(atom.as_direct(), None);
+            }
+        }
+
+        // Small and large atoms.
+        {
+            assert!(Atom::null().is_direct());
+            assert_eq!(Atom::null().as_direct(), Some(0));
+            assert_eq!(Atom::from_direct(0), Atom::null());
+            assert_eq!(Atom::from(u64::MAX).as_direct(), None);
+            assert!(!Atom::from(u128::MAX).is_direct());
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "too large to be a direct atom")]
+    fn from_direct_too_large() {
+        Atom::from_direct(1 << 62);
+    }
+
     #[test]
     fn iter() {
         {
@@ -531,6 +1991,13 @@ mod tests {
             assert_ne!(atom, "goodbye");
         }
 
+        // Trailing null bytes are leading zeros of the atom.
+        {
+            let atom = Atom::from("a\0b\0\0");
+            assert_eq!(atom, Atom::from(String::from("a\0b")));
+            assert_eq!(atom.as_bytes(), b"a\0b");
+        }
+
         {
             macro_rules! uint_eq_test {
                 ($uint:expr) => {
@@ -564,4 +2031,625 @@ mod tests {
             uint_ne_test!(64_222u16, 127usize);
         }
     }
+
+    #[test]
+    fn cord() {
+        // Case conversion changes ASCII letters only, including around multi-byte characters.
+        {
+            let cord = Atom::from("Hello, Wörld! ÀÉ ñ 🚀 Zz");
+            assert_eq!(cord.to_lowercase(), "hello, wörld! ÀÉ ñ 🚀 zz");
+            assert_eq!(cord.to_uppercase(), "HELLO, WöRLD! ÀÉ ñ 🚀 ZZ");
+            assert_eq!(Atom::null().to_lowercase(), Atom::null());
+            let invalid = Atom::from(vec![b'A', 0xff, b'b']);
+            assert_eq!(invalid.to_lowercase(), Atom::from(vec![b'a', 0xff, b'b']));
+            assert_eq!(invalid.to_uppercase(), Atom::from(vec![b'A', 0xff, b'B']));
+        }
+
+        // Trimming removes Unicode whitespace but not NULs, and fails on invalid UTF-8.
+        {
+            assert_eq!(Atom::from("\u{3000} é \u{a0}").trim().expect("trim"), "é");
+            assert_eq!(Atom::from("  \t\n").trim().expect("trim"), Atom::null());
+            assert_eq!(Atom::from("a\0 ").trim().expect("trim"), "a");
+            assert_eq!(Atom::from("\0 a").trim().expect("trim"), "\0 a");
+            assert!(Atom::from(vec![b' ', 0xc3, b' ']).trim().is_err());
+        }
+
+        // Splitting, including around NULs.
+        {
+            let split = |cord: &Atom, sep| cord.split(sep).collect::<Vec<_>>();
+            assert_eq!(split(&Atom::from("/a/é/"), b'/'), ["", "a", "é", ""]);
+            assert_eq!(split(&Atom::null(), b'/'), [Atom::null()]);
+            assert_eq!(split(&Atom::from("abc"), b'/'), ["abc"]);
+            assert_eq!(split(&Atom::from("a\0b/c\0/d"), b'/'), ["a\0b", "c", "d"]);
+            assert_eq!(split(&Atom::from("a\0b\0c"), 0), ["a", "b", "c"]);
+            // A separator that isn't ASCII can split a character.
+            assert_eq!(
+                split(&Atom::from("€¢"), 0xa2),
+                [Atom::from(vec![0xe2, 0x82, 0xac, 0xc2]), Atom::null()]
+            );
+        }
+
+        // Prefixes compare bytes, and can contain interior NULs.
+        {
+            let cord = Atom::from("a\0bé");
+            assert!(cord.starts_with(""));
+            assert!(cord.starts_with("a\0"));
+            assert!(cord.starts_with("a\0bé"));
+            assert!(!cord.starts_with("a\0bé\0"));
+            assert!(!cord.starts_with("a\0b\u{e8}"));
+            assert!(Atom::null().starts_with(""));
+            assert!(!Atom::null().starts_with("\0"));
+        }
+
+        // Concatenation keeps interior NULs and adds nothing for null cords.
+        {
+            let parts = [
+                Atom::from("a\0b"),
+                Atom::null(),
+                Atom::from("\0c"),
+                Atom::from("é"),
+            ];
+            let refs: Vec<_> = parts.iter().collect();
+            assert_eq!(Atom::concat(&refs), "a\0b\0cé");
+            assert_eq!(Atom::concat(&[]), Atom::null());
+            assert_eq!(Atom::concat(&[&Atom::null(), &Atom::null()]), Atom::null());
+            let cord = Atom::from("x/y\0z/");
+            let pieces: Vec<_> = cord.split(b'/').collect();
+            let refs: Vec<_> = pieces.iter().collect();
+            assert_eq!(Atom::concat(&refs), "xy\0z");
+        }
+    }
+
+    #[test]
+    fn display() {
+        let atom = Atom::from(vec![b'a', 0xff]);
+
+        // No flags.
+        {
+            assert_eq!(Atom::null().to_string(), "0x0");
+            assert_eq!(atom.to_string(), "0x61ff");
+            assert_eq!(
+                Atom::from(vec![1; 17]).to_string(),
+                "0x1111.1111.1111.1111.1"
+            );
+        }
+
+        // Width, fill, and alignment pad just like they pad a string.
+        {
+            let string = atom.to_string();
+            assert_eq!(format!("{:9}", atom), format!("{:9}", string));
+            assert_eq!(format!("{:<9}", atom), "0x61ff   ");
+            assert_eq!(format!("{:>9}", atom), "   0x61ff");
+            assert_eq!(format!("{:^9}", atom), format!("{:^9}", string));
+            assert_eq!(format!("{:-^10}", atom), "--0x61ff--");
+            assert_eq!(format!("{:é>8}", atom), "éé0x61ff");
+            assert_eq!(format!("{:>1$}", atom, 7), " 0x61ff");
+            // Output longer than the width isn't truncated.
+            assert_eq!(format!("{:>3}", atom), "0x61ff");
+            assert_eq!(format!("{:>3}", Atom::null()), "0x0");
+        }
+
+        // Precision limits the number of bytes shown.
+        {
+            let atom = Atom::from(vec![0xab; 6]);
+            assert_eq!(format!("{:.6}", atom), "0xabababab.abab");
+            assert_eq!(format!("{:.100}", atom), "0xabababab.abab");
+            assert_eq!(format!("{:.5}", atom), "0xabababab.ab...(6 bytes)");
+            assert_eq!(format!("{:.4}", atom), "0xabababab...(6 bytes)");
+            assert_eq!(format!("{:.0}", atom), "0x...(6 bytes)");
+            assert_eq!(format!("{:.0}", Atom::null()), "0x0");
+            assert_eq!(format!("{:.*}", 1, atom), "0xab...(6 bytes)");
+        }
+
+        // Precision and width together.
+        {
+            let atom = Atom::from(vec![0x0f; 6]);
+            assert_eq!(format!("{:>20.2}", atom), "    0xff...(6 bytes)");
+            assert_eq!(format!("{:_<20.2}", atom), "0xff...(6 bytes)____");
+            assert_eq!(format!("{:^19.2}", atom), " 0xff...(6 bytes)  ");
+            assert_eq!(format!("{:4.2}", atom), "0xff...(6 bytes)");
+        }
+
+        // A 1 MB atom.
+        {
+            let atom = Atom::from(vec![0x12; 1 << 20]);
+            assert_eq!(format!("{:.3}", atom), "0x121212...(1048576 bytes)");
+            assert_eq!(format!("{:>30.3}", atom), "    0x121212...(1048576 bytes)");
+            assert_eq!(format!("{:>30}", atom), atom.to_string());
+        }
+    }
+
+    #[test]
+    fn radix() {
+        // Known constants.
+        {
+            // The field prime of secp256k1, 2^256 - 2^32 - 977.
+            let mut bytes = vec![0xff; 32];
+            bytes[..4].copy_from_slice(&[0x2f, 0xfc, 0xff, 0xff]);
+            bytes[4] = 0xfe;
+            let p = Atom::from(bytes);
+            let decimal =
+                "115792089237316195423570985008687907853269984665640564039457584007908834671663";
+            assert_eq!(Atom::from_str_radix(decimal, 10), Ok(p.clone()));
+            assert_eq!(p.to_string_radix(10), decimal);
+            let hex = "ffffffff_ffffffff_ffffffff_ffffffff_ffffffff_ffffffff_fffffffe_fffffc2f";
+            assert_eq!(Atom::from_str_radix(hex, 16), Ok(p.clone()));
+            assert_eq!(p.to_string_radix(16), hex.replace('_', ""));
+            assert_eq!(Atom::from_str_radix(&hex.to_uppercase(), 16), Ok(p));
+
+            // 2^255 - 19.
+            let mut bytes = vec![0xff; 32];
+            bytes[0] = 0xed;
+            bytes[31] = 0x7f;
+            let p = Atom::from(bytes);
+            let decimal =
+                "57896044618658097711785492504343953926634992332820282019728792003956564819949";
+            assert_eq!(Atom::from_str_radix(decimal, 10), Ok(p.clone()));
+            assert_eq!(p.to_string_radix(10), decimal);
+            assert_eq!(p.to_string_radix(2), format!("{}01101", "1".repeat(250)));
+        }
+
+        // Agreement with the standard library for every radix, and round trips of atoms too large
+        // for it.
+        {
+            let mut state = 0x2545_f491_4f6c_dd1du64;
+            let mut next = || {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                state
+            };
+            for radix in 2..=36 {
+                for _ in 0..50 {
+                    let n = u128::from(next()) << 64 | u128::from(next());
+                    let n = n >> (next() % 128);
+                    let atom = Atom::from(n);
+                    let digits = atom.to_string_radix(radix);
+                    assert_eq!(u128::from_str_radix(&digits, radix), Ok(n));
+                    assert_eq!(Atom::from_str_radix(&digits, radix), Ok(atom));
+
+                    let bytes: Vec<u8> = (0..next() % 100).map(|_| next() as u8).collect();
+                    let atom = Atom::from(bytes);
+                    let digits = atom.to_string_radix(radix);
+                    assert_eq!(Atom::from_str_radix(&digits, radix), Ok(atom));
+                }
+            }
+            let n = u128::MAX - 12345;
+            assert_eq!(Atom::from(n).to_string_radix(2), format!("{:b}", n));
+            assert_eq!(Atom::from(n).to_string_radix(8), format!("{:o}", n));
+            assert_eq!(Atom::from(n).to_string_radix(10), n.to_string());
+            assert_eq!(Atom::from(n).to_string_radix(16), format!("{:x}", n));
+        }
+
+        // Zero, leading zeros, and separators.
+        {
+            for radix in [2, 10, 16, 36] {
+                assert_eq!(Atom::from_str_radix("0", radix), Ok(Atom::null()));
+                assert_eq!(Atom::from_str_radix("000_000", radix), Ok(Atom::null()));
+                assert_eq!(Atom::null().to_string_radix(radix), "0");
+            }
+            assert_eq!(
+                Atom::from_str_radix("0001_000", 10),
+                Ok(Atom::from(1000u16))
+            );
+            assert_eq!(Atom::from_str_radix("_1__0_", 2), Ok(Atom::from(2u8)));
+            assert_eq!(Atom::from_str_radix("00ff", 16), Ok(Atom::from(255u8)));
+        }
+
+        // Strings that aren't atoms.
+        {
+            assert_eq!(Atom::from_str_radix("", 10), Err(ParseAtomError::Empty));
+            assert_eq!(Atom::from_str_radix("___", 10), Err(ParseAtomError::Empty));
+            assert_eq!(
+                Atom::from_str_radix("12_3x", 10),
+                Err(ParseAtomError::InvalidDigit {
+                    index: 4,
+                    character: 'x'
+                })
+            );
+            assert_eq!(
+                Atom::from_str_radix("102", 2),
+                Err(ParseAtomError::InvalidDigit {
+                    index: 2,
+                    character: '2'
+                })
+            );
+            assert_eq!(
+                Atom::from_str_radix("-1", 10),
+                Err(ParseAtomError::InvalidDigit {
+                    index: 0,
+                    character: '-'
+                })
+            );
+            let err = Atom::from_str_radix("1é", 16).expect_err("non-ASCII");
+            assert_eq!(
+                err,
+                ParseAtomError::InvalidDigit {
+                    index: 1,
+                    character: 'é'
+                }
+            );
+            assert_eq!(err.to_string(), "invalid digit 'é' at index 1");
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "radix 37 is not in the range 2..=36")]
+    fn radix_out_of_range() {
+        let _ = Atom::from_str_radix("1", 37);
+    }
+
+    #[cfg(feature = "bitstream-io")]
+    #[test]
+    fn bitstream() {
+        use bitstream_io::{BigEndian, BitReader, BitWriter};
+
+        let mut state = 0x9e37_79b9_7f4a_7c15u64;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+
+        // Applies the same random operation to two writers.
+        fn write_op(op: u64, arg: u64, a: &mut impl BitWrite, b: &mut impl BitWrite) {
+            let len = (arg % 64) as u32 + 1;
+            match op % 8 {
+                0 => {
+                    assert!(a.write_bit(arg & 1 != 0).is_ok());
+                    assert!(b.write_bit(arg & 1 != 0).is_ok());
+                }
+                1 => {
+                    let value = arg >> (64 - len);
+                    assert_eq!(a.write(len, value).ok(), b.write(len, value).ok());
+                }
+                2 => {
+                    let len = len.min(32);
+                    let value = arg as i32 >> (32 - len);
+                    assert_eq!(
+                        a.write_signed(len, value).ok(),
+                        b.write_signed(len, value).ok()
+                    );
+                }
+                3 => {
+                    assert!(a.write_from(arg as u32).is_ok());
+                    assert!(b.write_from(arg as u32).is_ok());
+                }
+                4 => {
+                    assert!(a.write_as_from::<BigEndian, u16>(arg as u16).is_ok());
+                    assert!(b.write_as_from::<BigEndian, u16>(arg as u16).is_ok());
+                }
+                5 => {
+                    assert!(a.byte_align().is_ok());
+                    assert!(b.byte_align().is_ok());
+                }
+                6 => {
+                    assert!(a.write_unary1(len % 20).is_ok());
+                    assert!(b.write_unary1(len % 20).is_ok());
+                }
+                _ => {
+                    // Values and lengths that don't fit fail for both.
+                    assert!(a.write(9, 0x200u16).is_err());
+                    assert!(b.write(9, 0x200u16).is_err());
+                    assert!(a.write(9, 0u8).is_err());
+                    assert!(b.write(9, 0u8).is_err());
+                }
+            }
+            assert_eq!(a.byte_aligned(), b.byte_aligned());
+        }
+
+        // Writing matches a little-endian `BitWriter`.
+        for _ in 0..200 {
+            let mut builder = Atom::builder();
+            let mut writer = BitWriter::endian(Vec::new(), LittleEndian);
+            for _ in 0..next() % 50 {
+                write_op(next(), next(), &mut builder, &mut writer);
+            }
+            // A set bit marks the end, since the atom doesn't keep trailing zeros.
+            builder.write_bit(true).expect("end");
+            writer.write_bit(true).expect("end");
+            writer.byte_align().expect("align");
+            assert_eq!(builder.into_atom().as_bytes(), writer.into_writer());
+        }
+
+        // Reading matches a little-endian `BitReader` over the same bytes until the end of the
+        // atom, where reading fails.
+        for _ in 0..200 {
+            let mut bytes: Vec<u8> = (0..next() % 40).map(|_| next() as u8).collect();
+            bytes.push(next() as u8 | 0x80);
+            let atom = Atom::from(bytes.clone());
+            let mut ours = AtomBitReader::new(atom.iter());
+            let mut theirs = BitReader::endian(&bytes[..], LittleEndian);
+            loop {
+                let arg = next();
+                let len = (arg % 64) as u32 + 1;
+                let (a, b) = match next() % 8 {
+                    0 => (
+                        ours.read_bit().map(u64::from),
+                        theirs.read_bit().map(u64::from),
+                    ),
+                    1 => (ours.read::<u64>(len), theirs.read::<u64>(len)),
+                    2 => {
+                        let len = len.min(32);
+                        (
+                            ours.read_signed::<i32>(len).map(|n| n as u64),
+                            theirs.read_signed::<i32>(len).map(|n| n as u64),
+                        )
+                    }
+                    3 => (
+                        ours.read_to::<u32>().map(u64::from),
+                        theirs.read_to::<u32>().map(u64::from),
+                    ),
+                    4 => (
+                        ours.read_as_to::<BigEndian, u16>().map(u64::from),
+                        theirs.read_as_to::<BigEndian, u16>().map(u64::from),
+                    ),
+                    5 => (ours.skip(len).map(|()| 0), theirs.skip(len).map(|()| 0)),
+                    6 => {
+                        ours.byte_align();
+                        theirs.byte_align();
+                        (Ok(0), Ok(0))
+                    }
+                    _ => (
+                        ours.read_unary0().map(u64::from),
+                        theirs.read_unary0().map(u64::from),
+                    ),
+                };
+                match a {
+                    Ok(a) => {
+                        assert_eq!(Some(a), b.ok());
+                        assert_eq!(ours.byte_aligned(), theirs.byte_aligned());
+                    }
+                    Err(err) => {
+                        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+                        break;
+                    }
+                }
+            }
+        }
+
+        // A failed read leaves the reader where it was.
+        {
+            let atom = Atom::from(0b1_0110u8);
+            let mut reader = AtomBitReader::new(atom.iter());
+            assert!(reader.read::<u8>(6).is_err());
+            assert!(reader.read_to::<u8>().is_err());
+            assert!(reader.read::<u8>(9).is_err());
+            assert_eq!(reader.read::<u8>(5).ok(), Some(0b1_0110));
+            assert_eq!(reader.into_inner().pos(), 5);
+        }
+    }
+
+    #[test]
+    fn arithmetic() {
+        // Addition and multiplication agree with `u128` wherever it doesn't overflow.
+        {
+            let values = [
+                0u128,
+                1,
+                0xff,
+                0x100,
+                0xffff_ffff,
+                u64::MAX as u128,
+                0x1234_5678_9abc,
+            ];
+            for a in values {
+                for b in values {
+                    let (x, y) = (Atom::from(a), Atom::from(b));
+                    assert_eq!(&x + &y, a + b);
+                    assert_eq!(x.clone() + y.clone(), a + b);
+                    if let Some(product) = a.checked_mul(b) {
+                        assert_eq!(&x * &y, product);
+                        assert_eq!(x * y, product);
+                    }
+                }
+            }
+        }
+
+        // Carries that grow an atom, and products wider than `u128`.
+        {
+            let mut atom = Atom::from(vec![0xff; 100]);
+            atom += Atom::from(1u8);
+            assert_eq!(atom.bit_len(), 801);
+            let mut expected = vec![0; 100];
+            expected.push(1);
+            assert_eq!(atom, Atom::from(expected));
+
+            let max = Atom::from(u128::MAX);
+            // (2^128 - 1)^2 = 2^256 - 2^129 + 1.
+            let mut expected = vec![0; 32];
+            expected[0] = 1;
+            expected[16] = 0xfe;
+            expected[17..].fill(0xff);
+            assert_eq!(&max * &max, Atom::from(expected));
+            assert_eq!(&max * &Atom::null(), 0u8);
+        }
+    }
+
+    #[test]
+    fn sum_product() {
+        // Known values.
+        {
+            let atoms: Vec<_> = (1u8..=10).map(Atom::from).collect();
+            assert_eq!(atoms.iter().sum::<Atom>(), 55u8);
+            assert_eq!(atoms.iter().product::<Atom>(), 3_628_800u32);
+            assert_eq!(atoms.clone().into_iter().sum::<Atom>(), 55u8);
+            assert_eq!(atoms.into_iter().product::<Atom>(), 3_628_800u32);
+
+            // 30! doesn't fit in a `u64`.
+            let factorial: Atom = (1u8..=30).map(Atom::from).product();
+            assert_eq!(
+                factorial.to_string_radix(10),
+                "265252859812191058636308480000000"
+            );
+        }
+
+        // The empty sum is 0 and the empty product is 1.
+        {
+            assert_eq!(std::iter::empty::<Atom>().sum::<Atom>(), 0u8);
+            assert_eq!(std::iter::empty::<&Atom>().sum::<Atom>(), 0u8);
+            assert_eq!(std::iter::empty::<Atom>().product::<Atom>(), 1u8);
+            assert_eq!(std::iter::empty::<&Atom>().product::<Atom>(), 1u8);
+        }
+
+        // Enough maximal `u64`s to carry past several limbs.
+        {
+            let count = 1u32 << 20;
+            let max = Atom::from(u64::MAX);
+            let sum: Atom = std::iter::repeat_n(&max, count as usize).sum();
+            assert_eq!(sum, u128::from(u64::MAX) * u128::from(count));
+            assert_eq!(sum.bit_len(), 84);
+
+            let product: Atom = std::iter::repeat_n(max, 4).product();
+            assert_eq!(product.bit_len(), 256);
+            assert_eq!(
+                product.to_string_radix(10),
+                "115792089237316195398462578067141184799968521174335529155754622898352762650625"
+            );
+        }
+    }
+
+    #[test]
+    fn duration() {
+        // Relative time literals, as Hoon parses them.
+        {
+            let cases = [
+                // ~s0
+                (0u128, Duration::ZERO),
+                // ~s30..5000
+                (30 << 64 | 0x5000 << 48, Duration::new(30, 312_500_000)),
+                // ~s1..8000
+                (1 << 64 | 0x8000 << 48, Duration::from_millis(1_500)),
+                // ~m1
+                (60 << 64, Duration::from_secs(60)),
+                // ~d1.h2.m3.s4
+                (93_784 << 64, Duration::from_secs(93_784)),
+                // ~s0..0000.0004.4b82.fa0a, the nearest relative time to a nanosecond.
+                (0x4_4b82_fa0a, Duration::from_nanos(1)),
+            ];
+            for (dr, duration) in cases {
+                assert_eq!(Atom::from_duration(duration), Atom::from(dr));
+                assert_eq!(Atom::from(dr).to_duration(), Ok(duration));
+            }
+        }
+
+        // Durations round trip at every precision.
+        {
+            let cases = [
+                Duration::from_secs(1),
+                Duration::from_millis(1),
+                Duration::from_millis(999),
+                Duration::from_micros(1),
+                Duration::from_micros(123_456_789),
+                Duration::from_nanos(1),
+                Duration::from_nanos(999_999_999),
+                Duration::from_nanos(u64::MAX),
+                Duration::new(u64::MAX, 0),
+                Duration::MAX,
+            ];
+            for duration in cases {
+                assert_eq!(Atom::from_duration(duration).to_duration(), Ok(duration));
+            }
+        }
+
+        // Fractions that aren't whole nanoseconds round to the nearest one.
+        {
+            let cases = [
+                // ~s0..0000.0000.0000.0001
+                (1u128, Duration::ZERO),
+                // ~s0..0001
+                (1 << 48, Duration::from_nanos(15_259)),
+                // ~s1..ffff.ffff.ffff.ffff rounds up to the next second.
+                (1 << 64 | u128::from(u64::MAX), Duration::from_secs(2)),
+            ];
+            for (dr, duration) in cases {
+                assert_eq!(Atom::from(dr).to_duration(), Ok(duration));
+            }
+        }
+
+        // Relative times that don't fit in a duration.
+        {
+            let cases = [
+                Atom::from(u128::MAX),
+                Atom::from(vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]),
+            ];
+            for atom in cases {
+                assert_eq!(atom.to_duration(), Err(DrError::OutOfRange));
+            }
+        }
+    }
+
+    #[test]
+    fn read_bits() {
+        let atom = Atom::from("the quick brown fox jumps over the lazy dog");
+        for start in 0..24 {
+            for len in [
+                0,
+                1,
+                7,
+                8,
+                9,
+                15,
+                16,
+                17,
+                63,
+                64,
+                65,
+                200,
+                atom.bit_len() - start,
+            ] {
+                let mut iter = atom.iter();
+                for _ in 0..start {
+                    iter.next();
+                }
+                let mut expected = Builder::new();
+                for bit in atom.iter().skip(start).take(len) {
+                    expected.push_bit(bit);
+                }
+                assert_eq!(iter.read_bits(len), Some(expected.into_atom()));
+                assert_eq!(iter.pos(), start + len);
+                assert!(iter.eq(atom.iter().skip(start + len)));
+            }
+
+            let mut iter = atom.iter();
+            for _ in 0..start {
+                iter.next();
+            }
+            assert_eq!(iter.read_bits(atom.bit_len() - start + 1), None);
+            assert_eq!(iter.pos(), start);
+        }
+    }
+
+    #[test]
+    #[ignore = "allocates 512 MiB; run with --ignored"]
+    fn huge() {
+        // An atom with more bits than fit in a u32. The zeroed allocation is mapped lazily, so
+        // only the pages that are written to take up memory.
+        let byte_len = (1 << 29) + 2;
+        let mut bytes = vec![0u8; byte_len];
+        bytes[byte_len - 1] = 0b10;
+        bytes[byte_len - 2] = 0b1000_0000;
+        let atom = Atom::from(bytes);
+        let bit_len = (1 << 32) + 10;
+        assert_eq!(atom.bit_len_u64(), bit_len);
+
+        let mut iter = atom.iter();
+        iter.seek(bit_len - 10);
+        assert_eq!(iter.pos_u64(), bit_len - 10);
+        assert_eq!(iter.read_bits_u64(10), Some(Atom::from(0b10_1000_0000u16)));
+        assert_eq!(iter.remaining(), 0);
+        assert_eq!(iter.next(), None);
+    }
+
+    #[cfg(target_pointer_width = "32")]
+    #[test]
+    #[ignore = "allocates 512 MiB; run with --ignored"]
+    #[should_panic(expected = "exceeds usize::MAX")]
+    fn huge_usize() {
+        let byte_len = (1 << 29) + 2;
+        let mut bytes = vec![0u8; byte_len];
+        bytes[byte_len - 1] = 0b10;
+        let _ = Atom::from(bytes).bit_len();
+    }
 }
@@ -0,0 +1,233 @@
+//! Axis arithmetic.
+//!
+//! An axis names a subnoun by its position in a binary tree: axis `1` is the noun itself, and the
+//! head and tail of the subnoun at axis `a` are at axes `2a` and `2a + 1`. The bits of an axis
+//! below its most significant bit are therefore the path to the subnoun, read from the most
+//! significant end, with `0` for the head and `1` for the tail.
+//!
+//! These functions compose and decompose axes like Hoon's `++peg`, `++cap`, and `++mas`. The
+//! `u64` versions return `None` for axis `0`, which doesn't exist, and for results that don't fit
+//! in a `u64`; the `_atom` versions work on axes of any size.
+//!
+//! # Examples
+//!
+//! ```
+//! # use noun::{axis, cell::Cell, Noun};
+//! let noun = Noun::from(Cell::from([1u8, 2u8, 3u8]));
+//! let tail = noun.get(3).unwrap();
+//! assert_eq!(noun.get(axis::peg(3, 2).unwrap()), tail.get(2));
+//! assert_eq!(axis::cap(6), Some(3));
+//! assert_eq!(axis::mas(6), Some(2));
+//! ```
+
+use crate::atom::Atom;
+
+/// Returns the number of bits below the most significant bit of `axis`, which is the number of
+/// steps from the root to the subnoun at `axis`.
+fn depth(axis: u64) -> u32 {
+    u64::BITS - 1 - axis.leading_zeros()
+}
+
+/// Returns the axis of the subnoun at `b` within the subnoun at `a`, or `None` if either axis is
+/// `0` or the result doesn't fit in a `u64`.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::axis::peg;
+/// assert_eq!(peg(3, 2), Some(6));
+/// assert_eq!(peg(6, 7), Some(27));
+/// assert_eq!(peg(1, 5), Some(5));
+/// assert_eq!(peg(1 << 63, 2), None);
+/// ```
+pub fn peg(a: u64, b: u64) -> Option<u64> {
+    if a == 0 || b == 0 {
+        return None;
+    }
+    let depth = depth(b);
+    if a.leading_zeros() < depth {
+        return None;
+    }
+    Some(a << depth | (b ^ 1 << depth))
+}
+
+/// Returns `2` if the subnoun at `axis` is within the head of the noun and `3` if it's within the
+/// tail, or `None` if `axis` is `0` or `1`, which aren't within either.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::axis::cap;
+/// assert_eq!(cap(2), Some(2));
+/// assert_eq!(cap(13), Some(3));
+/// assert_eq!(cap(1), None);
+/// ```
+pub fn cap(axis: u64) -> Option<u64> {
+    if axis < 2 {
+        return None;
+    }
+    Some(2 | axis >> (depth(axis) - 1) & 1)
+}
+
+/// Returns the axis of the subnoun at `axis` within the head or tail of the noun that contains
+/// it, as given by [`cap()`], or `None` if `axis` is `0` or `1`.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::axis::{cap, mas, peg};
+/// assert_eq!(mas(2), Some(1));
+/// assert_eq!(mas(13), Some(5));
+/// assert_eq!(peg(cap(13).unwrap(), mas(13).unwrap()), Some(13));
+/// ```
+pub fn mas(axis: u64) -> Option<u64> {
+    if axis < 2 {
+        return None;
+    }
+    let depth = depth(axis);
+    Some(axis & !(1 << depth) | 1 << (depth - 1))
+}
+
+/// Returns the axis of the subnoun at `b` within the subnoun at `a`, like [`peg()`], for axes of
+/// any size, or `None` if either axis is `0`.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{atom::Atom, axis::peg_atom};
+/// let a = Atom::from(1u128 << 100);
+/// assert_eq!(peg_atom(&a, &Atom::from(3u8)), Some(Atom::from(1u128 << 101 | 1)));
+/// ```
+pub fn peg_atom(a: &Atom, b: &Atom) -> Option<Atom> {
+    if a.is_null() || b.is_null() {
+        return None;
+    }
+    let mut builder = Atom::builder();
+    for bit in b.iter().take(b.bit_len() - 1) {
+        builder.push_bit(bit);
+    }
+    builder.push_atom(a);
+    Some(builder.into_atom())
+}
+
+/// Returns `2` or `3` for an axis within the head or tail, like [`cap()`], for axes of any size.
+pub fn cap_atom(axis: &Atom) -> Option<u64> {
+    let len = axis.bit_len();
+    if len < 2 {
+        return None;
+    }
+    let byte = axis.as_bytes()[(len - 2) / 8];
+    Some(2 | u64::from(byte >> ((len - 2) % 8) & 1))
+}
+
+/// Returns an axis with its head-or-tail step removed, like [`mas()`], for axes of any size.
+pub fn mas_atom(axis: &Atom) -> Option<Atom> {
+    let len = axis.bit_len();
+    if len < 2 {
+        return None;
+    }
+    let mut builder = Atom::builder();
+    for bit in axis.iter().take(len - 2) {
+        builder.push_bit(bit);
+    }
+    builder.push_bit(true);
+    Some(builder.into_atom())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{cell::Cell, noun::Noun};
+
+    /// Builds a random noun with at most `depth` levels of cells, whose atoms are distinct.
+    fn tree(state: &mut u64, depth: u32, next_atom: &mut u64) -> Noun {
+        // A xorshift PRNG.
+        *state ^= *state << 13;
+        *state ^= *state >> 7;
+        *state ^= *state << 17;
+        if depth == 0 || state.is_multiple_of(4) {
+            *next_atom += 1;
+            Noun::from(*next_atom)
+        } else {
+            let head = tree(state, depth - 1, next_atom);
+            let tail = tree(state, depth - 1, next_atom);
+            Noun::from(Cell::from([head, tail]))
+        }
+    }
+
+    #[test]
+    fn axis() {
+        // The subnoun at `peg(a, b)` is the subnoun at `b` within the subnoun at `a`, and `cap()`
+        // and `mas()` split an axis back into the two.
+        {
+            let mut state = 0x9e37_79b9_7f4a_7c15;
+            for _ in 0..20 {
+                let noun = tree(&mut state, 6, &mut 0);
+                for a in 1..128 {
+                    for b in 1..128 {
+                        let axis = peg(a, b).expect("small axes");
+                        assert_eq!(noun.get(axis), noun.get(a).and_then(|n| n.get(b)));
+                        if axis >= 2 {
+                            let (cap, mas) = (cap(axis).expect("cap"), mas(axis).expect("mas"));
+                            assert_eq!(noun.get(axis), noun.get(cap).and_then(|n| n.get(mas)));
+                        }
+                    }
+                }
+            }
+        }
+
+        // Identities.
+        {
+            for x in [1, 2, 3, 6, 7, 1000, u64::MAX] {
+                assert_eq!(peg(1, x), Some(x));
+                assert_eq!(peg(x, 1), Some(x));
+                if x >= 2 {
+                    assert_eq!(peg(cap(x).unwrap(), mas(x).unwrap()), Some(x));
+                }
+            }
+            for x in [1, 2, 3, 6, 7, 1000] {
+                assert_eq!(cap(peg(2, x).unwrap()), Some(2));
+                assert_eq!(cap(peg(3, x).unwrap()), Some(3));
+                assert_eq!(mas(peg(2, x).unwrap()), Some(x));
+                assert_eq!(mas(peg(3, x).unwrap()), Some(x));
+                // `++peg` is associative.
+                assert_eq!(peg(peg(6, x).unwrap(), 5), peg(6, peg(x, 5).unwrap()));
+            }
+        }
+
+        // Axes that don't exist, and results that don't fit in a `u64`.
+        {
+            assert_eq!(peg(0, 2), None);
+            assert_eq!(peg(2, 0), None);
+            assert_eq!((cap(0), cap(1), mas(0), mas(1)), (None, None, None, None));
+            assert_eq!(peg(1 << 62, 3), Some(1 << 63 | 1));
+            assert_eq!(peg(1 << 63, 2), None);
+            assert_eq!(peg(2, 1 << 63), None);
+        }
+
+        // Atoms agree with `u64`s, and go further.
+        {
+            let mut state = 0x2545_f491_4f6c_dd1d_u64;
+            for _ in 0..1000 {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                let (a, b) = (state >> 40, state & 0xf_ffff);
+                let (atom_a, atom_b) = (Atom::from(a), Atom::from(b));
+                assert_eq!(peg_atom(&atom_a, &atom_b), peg(a, b).map(Atom::from));
+                assert_eq!(cap_atom(&atom_a), cap(a));
+                assert_eq!(mas_atom(&atom_a), mas(a).map(Atom::from));
+            }
+
+            let big = Atom::from(u128::MAX);
+            let pegged = peg_atom(&big, &Atom::from(6u8)).expect("peg");
+            assert_eq!(pegged.bit_len(), 130);
+            assert_eq!(cap_atom(&pegged), Some(3));
+            let cap = Atom::from(cap_atom(&pegged).expect("cap"));
+            let mas = mas_atom(&pegged).expect("mas");
+            assert_eq!(peg_atom(&cap, &mas), Some(pegged));
+            assert_eq!(peg_atom(&Atom::null(), &Atom::from(2u8)), None);
+            assert_eq!(cap_atom(&Atom::from(1u8)), None);
+        }
+    }
+}
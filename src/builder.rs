@@ -0,0 +1,273 @@
+//! Building nouns one piece at a time.
+//!
+//! [`NounBuilder`] builds a noun from a flat sequence of atoms, subnouns, and cell boundaries, the
+//! way a parser or a scan over a database produces them, without recursing and without knowing
+//! the shape of the noun in advance.
+
+use crate::{atom::Atom, cell::Cell, noun::Noun, Rc};
+use std::fmt::{self, Display, Formatter};
+
+/// Errors that occur when the pieces given to a [`NounBuilder`] don't form a noun.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum BuildError {
+    /// A cell was closed with fewer than two elements.
+    TooFewElements(usize),
+    /// A cell was closed without being opened.
+    UnmatchedClose,
+    /// The builder was finished with this many cells still open.
+    UnclosedCells(usize),
+    /// The builder was finished with nothing in it.
+    Empty,
+    /// The builder was finished with this many nouns outside of any cell.
+    MultipleNouns(usize),
+}
+
+impl Display for BuildError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        match self {
+            Self::TooFewElements(len) => write!(
+                f,
+                "a cell was closed with {} element(s), but a cell needs at least 2",
+                len
+            ),
+            Self::UnmatchedClose => write!(f, "a cell was closed without being opened"),
+            Self::UnclosedCells(len) => write!(f, "{} cell(s) were never closed", len),
+            Self::Empty => write!(f, "nothing was built"),
+            Self::MultipleNouns(len) => write!(f, "{} nouns were built instead of 1", len),
+        }
+    }
+}
+
+impl std::error::Error for BuildError {}
+
+/// A noun builder.
+///
+/// [`open_cell()`](Self::open_cell()) and [`close_cell()`](Self::close_cell()) bracket the
+/// elements of a cell like `[` and `]` in Hoon: a cell closed with more than two elements is
+/// right-nested, so `[a b c]` is `[a [b c]]`, just like [`Cell::from()`](Cell::from()) with an
+/// array. Finished cells and atoms are kept on a stack, so a noun of any depth is built without
+/// recursing.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{builder::NounBuilder, cell::Cell, Noun};
+/// let mut builder = NounBuilder::new();
+/// builder.open_cell();
+/// builder.push_atom("GET".into());
+/// builder.open_cell();
+/// builder.push_atom(1u8.into());
+/// builder.push_atom(2u8.into());
+/// builder.close_cell().unwrap();
+/// builder.push_atom(3u8.into());
+/// builder.close_cell().unwrap();
+/// assert_eq!(
+///     builder.finish().unwrap(),
+///     Noun::from(Cell::from([
+///         Noun::from("GET"),
+///         Noun::from(Cell::from([1u8, 2])),
+///         Noun::from(3u8),
+///     ]))
+/// );
+/// ```
+#[derive(Debug, Default)]
+pub struct NounBuilder {
+    /// The finished nouns that aren't yet part of a cell, in order.
+    nouns: Vec<Rc<Noun>>,
+    /// The length of `nouns` when each open cell was opened, innermost last.
+    opened: Vec<usize>,
+}
+
+impl NounBuilder {
+    /// Creates an empty noun builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of cells that have been opened but not closed.
+    pub fn depth(&self) -> usize {
+        self.opened.len()
+    }
+
+    /// Appends an atom.
+    pub fn push_atom(&mut self, atom: Atom) {
+        self.nouns.push(Rc::new(Noun::from(atom)));
+    }
+
+    /// Appends a noun, which is shared rather than copied.
+    pub fn push_noun(&mut self, noun: Rc<Noun>) {
+        self.nouns.push(noun);
+    }
+
+    /// Opens a cell, whose elements are everything appended until the matching
+    /// [`close_cell()`](Self::close_cell()).
+    pub fn open_cell(&mut self) {
+        self.opened.push(self.nouns.len());
+    }
+
+    /// Closes the innermost open cell, replacing its elements with the right-nested cell of them.
+    ///
+    /// Fails if no cell is open or if the cell has fewer than two elements, in which case nothing
+    /// changes.
+    pub fn close_cell(&mut self) -> Result<(), BuildError> {
+        let start = *self.opened.last().ok_or(BuildError::UnmatchedClose)?;
+        let len = self.nouns.len() - start;
+        if len < 2 {
+            return Err(BuildError::TooFewElements(len));
+        }
+        self.opened.pop();
+        let mut cell = self.nouns.pop().expect("tail");
+        while self.nouns.len() > start {
+            let head = self.nouns.pop().expect("head");
+            cell = Rc::new(Noun::from(Cell::from([head, cell])));
+        }
+        self.nouns.push(cell);
+        Ok(())
+    }
+
+    /// Finishes building, returning the noun that was built.
+    ///
+    /// Fails if a cell is still open or if there isn't exactly one noun outside of any cell.
+    pub fn finish(mut self) -> Result<Noun, BuildError> {
+        if !self.opened.is_empty() {
+            return Err(BuildError::UnclosedCells(self.opened.len()));
+        }
+        match self.nouns.len() {
+            0 => Err(BuildError::Empty),
+            1 => {
+                let noun = self.nouns.pop().expect("noun");
+                Ok(Rc::try_unwrap(noun).unwrap_or_else(|noun| (*noun).clone()))
+            }
+            len => Err(BuildError::MultipleNouns(len)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builder() {
+        // An Eyre HTTP request, `[%request secure=%.n address=[%ipv4 .127.0.0.1] method=%'POST'
+        // url='/~/login' header-list=~[['host' 'localhost'] ['accept' '*/*']] body=[~ 4 'a=b&']]`.
+        {
+            let expected = Noun::from(Cell::from([
+                Noun::from("request"),
+                Noun::from(1u8),
+                Noun::from(Cell::from([Atom::from("ipv4"), Atom::from(0x7f00_0001u32)])),
+                Noun::from(Cell::from([
+                    Noun::from("POST"),
+                    Noun::from("/~/login"),
+                    Noun::from(Cell::from([
+                        Noun::from(Cell::from(["host", "localhost"])),
+                        Noun::from(Cell::from(["accept", "*/*"])),
+                        Noun::null(),
+                    ])),
+                    Noun::from(Cell::from([
+                        Noun::null(),
+                        Noun::from(4u8),
+                        Noun::from("a=b&"),
+                    ])),
+                ])),
+            ]));
+
+            let mut builder = NounBuilder::new();
+            builder.open_cell();
+            builder.push_atom(Atom::from("request"));
+            builder.push_atom(Atom::from(1u8));
+            builder.open_cell();
+            builder.push_atom(Atom::from("ipv4"));
+            builder.push_atom(Atom::from(0x7f00_0001u32));
+            builder.close_cell().expect("address");
+            builder.open_cell();
+            builder.push_atom(Atom::from("POST"));
+            builder.push_atom(Atom::from("/~/login"));
+            builder.open_cell();
+            for (key, value) in [("host", "localhost"), ("accept", "*/*")] {
+                builder.open_cell();
+                builder.push_atom(Atom::from(key));
+                builder.push_atom(Atom::from(value));
+                builder.close_cell().expect("header");
+            }
+            builder.push_atom(Atom::null());
+            builder.close_cell().expect("header list");
+            builder.open_cell();
+            builder.push_atom(Atom::null());
+            builder.push_atom(Atom::from(4u8));
+            builder.push_atom(Atom::from("a=b&"));
+            builder.close_cell().expect("body");
+            assert_eq!(builder.depth(), 2);
+            builder.close_cell().expect("request");
+            builder.close_cell().expect("event");
+            assert_eq!(builder.finish().expect("finish"), expected);
+        }
+
+        // Pushed nouns are shared, and a lone atom is a noun.
+        {
+            let shared = Rc::new(Noun::from(Cell::from([1u8, 2])));
+            let mut builder = NounBuilder::new();
+            builder.open_cell();
+            builder.push_noun(shared.clone());
+            builder.push_noun(shared.clone());
+            builder.close_cell().expect("cell");
+            let noun = builder.finish().expect("finish");
+            assert!(std::ptr::eq(noun.get(2).unwrap(), noun.get(3).unwrap()));
+            assert_eq!(Rc::strong_count(&shared), 3);
+
+            let mut builder = NounBuilder::new();
+            builder.push_atom(Atom::from(7u8));
+            assert_eq!(builder.finish(), Ok(Noun::from(7u8)));
+        }
+
+        // A noun too deep to build recursively.
+        {
+            let depth = 200_000;
+            let mut builder = NounBuilder::new();
+            for _ in 0..depth {
+                builder.open_cell();
+                builder.push_atom(Atom::null());
+            }
+            builder.push_atom(Atom::from(1u8));
+            for _ in 0..depth {
+                builder.close_cell().expect("cell");
+            }
+            let noun = builder.finish().expect("finish");
+            let mut tail = &noun;
+            for _ in 0..depth {
+                tail = tail.get(3).expect("tail");
+            }
+            assert_eq!(*tail, 1u8);
+            // Dropping a noun this deep recursively would overflow the stack.
+            std::mem::forget(noun);
+        }
+    }
+
+    #[test]
+    fn builder_errors() {
+        // Unbalanced sequences.
+        {
+            let mut builder = NounBuilder::new();
+            assert_eq!(builder.close_cell(), Err(BuildError::UnmatchedClose));
+            builder.open_cell();
+            assert_eq!(builder.close_cell(), Err(BuildError::TooFewElements(0)));
+            builder.push_atom(Atom::from(1u8));
+            assert_eq!(builder.close_cell(), Err(BuildError::TooFewElements(1)));
+            builder.push_atom(Atom::from(2u8));
+            builder.open_cell();
+            assert_eq!(builder.finish(), Err(BuildError::UnclosedCells(2)));
+        }
+
+        // Too few or too many nouns.
+        {
+            assert_eq!(NounBuilder::new().finish(), Err(BuildError::Empty));
+            let mut builder = NounBuilder::new();
+            builder.push_atom(Atom::from(1u8));
+            builder.push_atom(Atom::from(2u8));
+            let err = builder.finish().expect_err("two nouns");
+            assert_eq!(err, BuildError::MultipleNouns(2));
+            assert_eq!(err.to_string(), "2 nouns were built instead of 1");
+        }
+    }
+}
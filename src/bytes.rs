@@ -0,0 +1,312 @@
+//! Conversions between byte vectors and the three ways Hoon represents binary data.
+//!
+//! - A raw atom, whose bytes are the bytes of the data, least significant first. An atom has no
+//!   trailing zero bytes, so data that ends in zeros loses them: `[0x61, 0x00]` and `[0x61]` are
+//!   both the atom `0x61`.
+//! - `octs`, `[p=@ud q=@]`, an atom paired with the length of the data, which keeps trailing
+//!   zeros. This is [`Octs`].
+//! - A byte list, `(list @D)`, a null-terminated list with one atom per byte, which keeps every
+//!   byte but costs a cell per byte. A tape is a byte list of text.
+//!
+//! Each form has its own pair of functions here, and each `from_*` function checks that the noun
+//! has the expected form. A noun that doesn't is reported along with the form it looks like
+//! instead, if any, since mixing the three up is an easy mistake to make.
+
+use crate::{
+    atom::Atom,
+    convert::{list_from_noun, Error, FromNoun},
+    noun::Noun,
+    octs::Octs,
+};
+use std::fmt::{self, Display, Formatter};
+
+/// One of the three forms of binary data.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ByteForm {
+    /// A raw atom.
+    Atom,
+    /// `octs`, `[p=@ud q=@]`.
+    Octs,
+    /// A byte list, `(list @D)`.
+    List,
+}
+
+impl Display for ByteForm {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        match self {
+            Self::Atom => write!(f, "an atom"),
+            Self::Octs => write!(f, "octs"),
+            Self::List => write!(f, "a byte list"),
+        }
+    }
+}
+
+/// The error returned when a noun isn't binary data of the expected form.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct BytesError {
+    /// The form the noun was expected to have.
+    pub expected: ByteForm,
+    /// The form the noun has instead, if it has one of the other two.
+    pub found: Option<ByteForm>,
+    /// The error converting the noun.
+    pub source: Error,
+}
+
+impl BytesError {
+    fn new(expected: ByteForm, noun: &Noun, source: Error) -> Self {
+        let found = [ByteForm::List, ByteForm::Octs, ByteForm::Atom]
+            .into_iter()
+            .filter(|&form| form != expected)
+            .find(|&form| from_form(form, noun).is_ok());
+        Self {
+            expected,
+            found,
+            source,
+        }
+    }
+}
+
+impl Display for BytesError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "expected {}", self.expected)?;
+        if let Some(found) = self.found {
+            write!(f, ", found what looks like {}", found)?;
+        }
+        write!(f, ": {}", self.source)
+    }
+}
+
+impl std::error::Error for BytesError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+/// Converts bytes into a raw atom, dropping trailing zero bytes.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{atom::Atom, bytes, Noun};
+/// assert_eq!(bytes::to_atom_bytes(b"hi\0"), Noun::from(Atom::from("hi")));
+/// ```
+pub fn to_atom_bytes(bytes: &[u8]) -> Noun {
+    Noun::from(Atom::from(bytes.to_vec()))
+}
+
+/// Converts a raw atom into its bytes, which never end in a zero byte.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{bytes, cell::Cell, Noun};
+/// assert_eq!(bytes::from_atom_bytes(&Noun::from("hi")).unwrap(), b"hi");
+///
+/// let octs = Noun::from(Cell::from([3u16, 0x6968]));
+/// let err = bytes::from_atom_bytes(&octs).unwrap_err();
+/// assert_eq!(err.to_string(), "expected an atom, found what looks like octs: \
+///     a cell was encountered when an atom was expected");
+/// ```
+pub fn from_atom_bytes(noun: &Noun) -> Result<Vec<u8>, BytesError> {
+    atom_bytes(noun).map_err(|err| BytesError::new(ByteForm::Atom, noun, err))
+}
+
+/// Converts bytes into `octs`, `[p=@ud q=@]`.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{atom::Atom, bytes, cell::Cell, Noun};
+/// assert_eq!(
+///     bytes::to_octs_bytes(b"hi\0"),
+///     Noun::from(Cell::from([Atom::from(3u8), Atom::from("hi")]))
+/// );
+/// ```
+pub fn to_octs_bytes(bytes: &[u8]) -> Noun {
+    Noun::from(Octs::from(bytes))
+}
+
+/// Converts `octs`, `[p=@ud q=@]`, into its bytes.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{bytes, cell::Cell, Noun};
+/// let octs = Noun::from(Cell::from([3u16, 0x6968]));
+/// assert_eq!(bytes::from_octs_bytes(&octs).unwrap(), b"hi\0");
+/// ```
+pub fn from_octs_bytes(noun: &Noun) -> Result<Vec<u8>, BytesError> {
+    octs_bytes(noun).map_err(|err| BytesError::new(ByteForm::Octs, noun, err))
+}
+
+/// Converts bytes into a byte list, `(list @D)`.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{bytes, cell::Cell, Noun};
+/// assert_eq!(bytes::to_byte_list(b"hi\0"), Noun::from(Cell::from([b'h', b'i', 0, 0])));
+/// ```
+pub fn to_byte_list(bytes: &[u8]) -> Noun {
+    Noun::from(bytes.to_vec())
+}
+
+/// Converts a byte list, `(list @D)`, into its bytes.
+///
+/// Every element must be less than 256.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{bytes, cell::Cell, Noun};
+/// let list = Noun::from(Cell::from([b'h', b'i', 0, 0]));
+/// assert_eq!(bytes::from_byte_list(&list).unwrap(), b"hi\0");
+///
+/// let err = bytes::from_byte_list(&Noun::from(Cell::from([3u16, 0x6968]))).unwrap_err();
+/// assert!(err.to_string().starts_with("expected a byte list, found what looks like octs"));
+/// ```
+pub fn from_byte_list(noun: &Noun) -> Result<Vec<u8>, BytesError> {
+    byte_list(noun).map_err(|err| BytesError::new(ByteForm::List, noun, err))
+}
+
+fn atom_bytes(noun: &Noun) -> Result<Vec<u8>, Error> {
+    Atom::from_noun(noun).map(Atom::into_vec)
+}
+
+fn octs_bytes(noun: &Noun) -> Result<Vec<u8>, Error> {
+    Octs::from_noun(noun).map(Octs::into_vec)
+}
+
+fn byte_list(noun: &Noun) -> Result<Vec<u8>, Error> {
+    list_from_noun(noun, u8::from_noun)
+}
+
+/// Converts a noun of the given form into its bytes.
+fn from_form(form: ByteForm, noun: &Noun) -> Result<Vec<u8>, Error> {
+    match form {
+        ByteForm::Atom => atom_bytes(noun),
+        ByteForm::Octs => octs_bytes(noun),
+        ByteForm::List => byte_list(noun),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{cell::Cell, convert::PathSegment};
+
+    #[test]
+    fn bytes() {
+        // Leading and trailing zeros are where the forms differ: only an atom drops trailing
+        // zeros, and only a byte list spends a cell on each byte.
+        {
+            let cases: [(&[u8], Atom, Noun, Noun); 5] = [
+                (
+                    b"",
+                    Atom::null(),
+                    Noun::from(Cell::from([0u8, 0])),
+                    Noun::null(),
+                ),
+                (
+                    b"\0",
+                    Atom::null(),
+                    Noun::from(Cell::from([1u8, 0])),
+                    Noun::from(Cell::from([0u8, 0])),
+                ),
+                (
+                    b"ab",
+                    Atom::from("ab"),
+                    Noun::from(Cell::from([Noun::from(2u8), Noun::from("ab")])),
+                    Noun::from(Cell::from([b'a', b'b', 0])),
+                ),
+                (
+                    b"\0ab\0\0",
+                    Atom::from(0x62_6100u32),
+                    Noun::from(Cell::from([5u32, 0x62_6100])),
+                    Noun::from(Cell::from([0, b'a', b'b', 0, 0, 0])),
+                ),
+                (
+                    b"\0\0\x01",
+                    Atom::from(0x1_0000u32),
+                    Noun::from(Cell::from([3u32, 0x1_0000])),
+                    Noun::from(Cell::from([0u8, 0, 1, 0])),
+                ),
+            ];
+            for (bytes, atom, octs, list) in cases {
+                let trimmed = atom.to_vec();
+                let atom = Noun::from(atom);
+                assert_eq!(to_atom_bytes(bytes), atom);
+                assert_eq!(from_atom_bytes(&atom).expect("atom"), trimmed);
+                assert!(bytes.starts_with(&trimmed));
+
+                assert_eq!(to_octs_bytes(bytes), octs);
+                assert_eq!(from_octs_bytes(&octs).expect("octs"), bytes);
+
+                assert_eq!(to_byte_list(bytes), list);
+                assert_eq!(from_byte_list(&list).expect("byte list"), bytes);
+            }
+        }
+    }
+
+    #[test]
+    fn bytes_errors() {
+        // Nouns of one form read as another.
+        {
+            let atom = Noun::from("abc");
+            let octs = Noun::from(Cell::from([Noun::from(3u8), Noun::from("abc")]));
+            let list = Noun::from(Cell::from([b'a', b'b', b'c', 0]));
+            let cases = [
+                (from_atom_bytes(&octs), ByteForm::Atom, Some(ByteForm::Octs)),
+                (from_atom_bytes(&list), ByteForm::Atom, Some(ByteForm::List)),
+                (from_octs_bytes(&atom), ByteForm::Octs, Some(ByteForm::Atom)),
+                (from_octs_bytes(&list), ByteForm::Octs, Some(ByteForm::List)),
+                (from_byte_list(&atom), ByteForm::List, Some(ByteForm::Atom)),
+                (from_byte_list(&octs), ByteForm::List, Some(ByteForm::Octs)),
+            ];
+            for (res, expected, found) in cases {
+                match res {
+                    Err(err) => {
+                        assert_eq!(err.expected, expected);
+                        assert_eq!(err.found, found);
+                    }
+                    res => panic!("unexpected result: {:?}", res),
+                }
+            }
+        }
+
+        // Byte list elements that aren't bytes.
+        {
+            let list = Noun::from(Cell::from([b'a' as u16, 256, 0]));
+            match from_byte_list(&list) {
+                Err(BytesError {
+                    expected: ByteForm::List,
+                    found: None,
+                    source: Error::At { axis, path, source },
+                }) => {
+                    assert_eq!(axis, Some(6));
+                    assert_eq!(path, [PathSegment::Index(1)]);
+                    assert!(matches!(*source, Error::AtomToUint));
+                }
+                res => panic!("unexpected result: {:?}", res),
+            }
+        }
+
+        // Nouns of none of the forms.
+        {
+            let noun = Noun::from(Cell::from([
+                Noun::from(Cell::from([1u8, 2])),
+                Noun::from(3u8),
+            ]));
+            for res in [
+                from_atom_bytes(&noun),
+                from_octs_bytes(&noun),
+                from_byte_list(&noun),
+            ] {
+                let err = res.expect_err("not bytes");
+                assert_eq!(err.found, None);
+                assert!(!err.to_string().contains("looks like"));
+            }
+        }
+    }
+}
@@ -1,4 +1,9 @@
-use crate::{atom::Atom, noun::Noun, Rc};
+use crate::{
+    atom::Atom,
+    convert,
+    noun::{ListIter, Noun},
+    Rc,
+};
 use std::{
     collections::hash_map::DefaultHasher,
     fmt::{Display, Error, Formatter},
@@ -6,6 +11,20 @@ use std::{
     mem::MaybeUninit,
 };
 
+/// Create a cell of the form `[a1 a2 ... aN]` from an owned `N`-element array or [`Vec`] of
+/// [`Rc<Noun>`], moving each element into the cell rather than cloning it.
+macro_rules! cell_from_array {
+    ($array:expr) => {{
+        debug_assert!($array.len() >= 2);
+        let mut nouns = $array.into_iter().rev();
+        let tail = nouns.next().expect("tail");
+        let head = nouns.next().expect("head");
+        nouns.fold(Cell::new(head, tail), |cell, head| {
+            Cell::new(head, Rc::new(Noun::from(cell)))
+        })
+    }};
+}
+
 /// A pair of reference-counted nouns.
 ///
 /// A cell can be:
@@ -65,6 +84,18 @@ impl Cell {
         &self.tail
     }
 
+    /// Returns a mutable reference to the head of this cell, to replace it or to modify it in
+    /// place with [`Rc::make_mut()`](std::rc::Rc::make_mut()).
+    pub fn head_mut(&mut self) -> &mut Rc<Noun> {
+        &mut self.head
+    }
+
+    /// Returns a mutable reference to the tail of this cell, to replace it or to modify it in
+    /// place with [`Rc::make_mut()`](std::rc::Rc::make_mut()).
+    pub fn tail_mut(&mut self) -> &mut Rc<Noun> {
+        &mut self.tail
+    }
+
     /// Computes the hash of this cell.
     pub fn hash(&self) -> u64 {
         let mut hasher = DefaultHasher::new();
@@ -119,6 +150,112 @@ impl Cell {
         Some(nouns)
     }
 
+    /// Unpacks the first `N` elements of this cell into an array, also returning the rest of the
+    /// cell, or returns `None` if the cell has fewer than `N` elements before its last tail.
+    ///
+    /// Unlike [`to_array()`](Self::to_array()), whose last element is always the rest of the cell,
+    /// this takes exactly `N` heads off the right-nested spine of the cell, so the rest can be an
+    /// atom (such as the null terminating a list) or any other noun.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::{cell::Cell, Noun};
+    /// let cell = Cell::from(["poke", "hood", "a", "b", ""]);
+    /// let ([tag, app], rest) = cell.to_array_with_tail::<2>().unwrap();
+    /// assert_eq!((&*tag, &*app), (&Noun::from("poke"), &Noun::from("hood")));
+    /// assert_eq!(*rest, Noun::from(Cell::from(["a", "b", ""])));
+    ///
+    /// let (elems, rest) = cell.to_array_with_tail::<4>().unwrap();
+    /// assert_eq!(*elems[3], Noun::from("b"));
+    /// assert!(rest.is_null());
+    ///
+    /// assert_eq!(cell.to_array_with_tail::<5>(), None);
+    /// ```
+    pub fn to_array_with_tail<const N: usize>(&self) -> Option<([Rc<Noun>; N], Rc<Noun>)> {
+        // Make sure there are `N` elements before cloning any of them.
+        let mut noun = self.tail_ref();
+        for _ in 1..N {
+            match noun {
+                Noun::Atom(_) => return None,
+                Noun::Cell(cell) => noun = cell.tail_ref(),
+            }
+        }
+        let mut rest: Option<Rc<Noun>> = None;
+        let nouns = std::array::from_fn(|_| {
+            let (head, tail) = match rest.as_deref() {
+                None => (self.head(), self.tail()),
+                Some(Noun::Cell(cell)) => (cell.head(), cell.tail()),
+                Some(Noun::Atom(_)) => unreachable!("the first N tails are cells"),
+            };
+            rest = Some(tail);
+            head
+        });
+        let rest = rest.unwrap_or_else(|| Rc::new(Noun::from(self.clone())));
+        Some((nouns, rest))
+    }
+
+    /// Unpacks this cell into an array of references to its first `N - 1` elements and the rest of
+    /// the cell, returning `None` if the cell is not of the form `[a1 a2 ... aN]`.
+    ///
+    /// This is just like [`to_array()`](Self::to_array()), but borrows the elements from the cell
+    /// instead of cloning an [`Rc`] for each of them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::{cell::Cell, Noun};
+    /// let cell = Cell::from(["GET", "/", "a=b&"]);
+    /// let [method, url, body] = cell.to_array_ref::<3>().unwrap();
+    /// assert_eq!((method, url, body), (&Noun::from("GET"), &Noun::from("/"), &Noun::from("a=b&")));
+    /// assert!(std::ptr::eq(body, cell.to_array::<3>().unwrap()[2].as_ref()));
+    ///
+    /// assert_eq!(cell.to_array_ref::<4>(), None);
+    /// ```
+    pub fn to_array_ref<const N: usize>(&self) -> Option<[&Noun; N]> {
+        debug_assert!(N >= 2);
+        let mut nouns = [self.head_ref(); N];
+        let mut noun = self.tail_ref();
+        for (i, n) in nouns.iter_mut().enumerate().skip(1) {
+            match noun {
+                Noun::Atom(_) if i < N - 1 => return None,
+                Noun::Cell(cell) if i < N - 1 => {
+                    *n = cell.head_ref();
+                    noun = cell.tail_ref();
+                }
+                _ => *n = noun,
+            }
+        }
+        Some(nouns)
+    }
+
+    /// Converts this cell into a tuple of `N` elements, converting the first `N - 1` elements of
+    /// the cell and then the rest of the cell with the [`FromNoun`](convert::FromNoun) impls of the
+    /// elements of the tuple.
+    ///
+    /// Because the last element of the tuple is converted from the rest of the cell, it can be a
+    /// list or another cell. A conversion that fails reports the position of the element in the
+    /// tuple as a [`PathSegment::Index`](convert::PathSegment::Index).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::{cell::Cell, convert::Error};
+    /// let cell = Cell::from(["GET", "/", "a", "b", ""]);
+    /// let (method, url, body) = cell.to_tuple::<(String, String, Vec<u8>)>().unwrap();
+    /// assert_eq!((method.as_str(), url.as_str()), ("GET", "/"));
+    /// assert_eq!(body, [b'a', b'b']);
+    ///
+    /// let err = cell.to_tuple::<(u8, String, Vec<u8>)>().unwrap_err();
+    /// assert_eq!(
+    ///     err.to_string(),
+    ///     "the atom is too large to fit in the unsigned integer type at [0] (axis 2)"
+    /// );
+    /// ```
+    pub fn to_tuple<T: convert::Tuple>(&self) -> Result<T, convert::Error> {
+        T::from_noun(&Noun::Cell(self.clone()))
+    }
+
     /// Unpacks this cell into a vector.
     ///
     /// If the length of the cell is known at compile-time, use [`to_array()`](Self::to_array()) instead.
@@ -158,6 +295,59 @@ impl Cell {
     pub fn into_parts(self) -> (Rc<Noun>, Rc<Noun>) {
         (self.head, self.tail)
     }
+
+    /// Creates the pair chain `[a1 a2 ... aN]` of a vector of nouns, which is
+    /// `[a1 [a2 [... [aN-1 aN]]]]`, failing if there are fewer than two nouns.
+    ///
+    /// The last noun is the tail of the innermost cell, not the head of a cell ending in null, so
+    /// a pair chain is only a list if its last noun is null. Use [`Noun::list_from()`] for a list
+    /// of the nouns instead, which also works for no nouns or one.
+    ///
+    /// `Cell::from()` of a vector does the same, but panics on short input.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::{cell::{Cell, TooFewNounsError}, Noun, Rc};
+    /// let nouns: Vec<_> = (1u8..=3).map(|i| Rc::new(Noun::from(i))).collect();
+    /// assert_eq!(Cell::try_from_vec(nouns), Ok(Cell::from([1u8, 2, 3])));
+    ///
+    /// let err = Cell::try_from_vec(vec![Rc::new(Noun::null())]).unwrap_err();
+    /// assert_eq!(err, TooFewNounsError { len: 1 });
+    /// ```
+    pub fn try_from_vec(nouns: Vec<Rc<Noun>>) -> Result<Self, TooFewNounsError> {
+        if nouns.len() < 2 {
+            return Err(TooFewNounsError { len: nouns.len() });
+        }
+        Ok(cell_from_array!(nouns))
+    }
+}
+
+/// The error returned when a cell is created from fewer than two nouns.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct TooFewNounsError {
+    /// The number of nouns there were.
+    pub len: usize,
+}
+
+impl Display for TooFewNounsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        write!(f, "a cell needs at least 2 nouns, but got {}", self.len)
+    }
+}
+
+impl std::error::Error for TooFewNounsError {}
+
+impl<'a> IntoIterator for &'a Cell {
+    type Item = Result<&'a Noun, convert::Error>;
+    type IntoIter = ListIter<'a>;
+
+    /// Iterates over the elements of a null-terminated list `[e0 e1 ... eN 0]`.
+    ///
+    /// See [`ListIter`] for how improper lists are iterated.
+    fn into_iter(self) -> ListIter<'a> {
+        ListIter::from_cell(self)
+    }
 }
 
 impl Display for Cell {
@@ -182,26 +372,6 @@ impl Display for Cell {
     }
 }
 
-/// Create a cell of the form `[a1 a2 ... aN]` from an `N`-element array of [`Rc<Noun>`].
-macro_rules! cell_from_array {
-    ($array:expr) => {{
-        debug_assert!($array.len() >= 2);
-        let (mut remaining, pair) = $array.split_at($array.len() - 2);
-        let mut cell = {
-            let head: &Rc<Noun> = &pair[0];
-            let tail: &Rc<Noun> = &pair[1];
-            Cell::new(head.clone(), tail.clone())
-        };
-        while !remaining.is_empty() {
-            let split = remaining.split_at(remaining.len() - 1);
-            remaining = split.0;
-            let single = split.1;
-            cell = Cell::new(single[0].clone(), Rc::new(Noun::from(cell)));
-        }
-        cell
-    }};
-}
-
 macro_rules! impl_from_array_for_cell {
     (n = $n:expr) => {
         impl_from_array_for_cell!([Atom; $n]);
@@ -289,19 +459,28 @@ impl_from_array_for_cell!(n = 28);
 impl_from_array_for_cell!(n = 29);
 impl_from_array_for_cell!(n = 30);
 
+/// Creates the pair chain `[a1 a2 ... aN]` of a vector of nouns, like
+/// [`Cell::try_from_vec()`].
+///
+/// # Panics
+///
+/// Panics if there are fewer than two nouns.
 impl From<Vec<Rc<Noun>>> for Cell {
     fn from(nouns: Vec<Rc<Noun>>) -> Self {
-        cell_from_array!(nouns)
+        Self::try_from_vec(nouns).unwrap_or_else(|err| panic!("{}", err))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{convert::FromNoun, octs::Octs};
 
     #[test]
     fn to_array() {
         {
+            const BODY: &str =
+                r#"[{"params":[],"id":"block number","jsonrpc":"2.0","method":"eth_blockNumber"}]"#;
             let cell = Cell::from([
                 Noun::from(Atom::from("request")),
                 Noun::from(Atom::from(0u8)),
@@ -314,90 +493,271 @@ mod tests {
                     ])),
                     Noun::from(Atom::from(0u8)),
                 ])),
-                Noun::from(Atom::from(0u8)),
-                Noun::from(Atom::from(78u8)),
-                Noun::from(Atom::from(
-                    r#"[{"params":[],"id":"block number","jsonrpc":"2.0","method":"eth_blockNumber"}]"#,
-                )),
+                Noun::from(Some(Octs::from(BODY.as_bytes()))),
             ]);
             let [tag, req_num, method, uri, headers, body] = cell.to_array::<6>().expect("as list");
-            if let (Noun::Atom(tag), Noun::Atom(req_num), Noun::Atom(method), Noun::Atom(uri)) =
-                (&*tag, &*req_num, &*method, &*uri)
-            {
-                assert_eq!(tag, "request");
-                assert_eq!(*req_num, 0u8);
-                assert_eq!(method, "POST");
-                assert_eq!(uri, "http://eth-mainnet.urbit.org:8545");
-            } else {
-                panic!("unexpected cell");
-            }
+            assert_eq!(*tag, "request");
+            assert_eq!(*req_num, 0u8);
+            assert_eq!(*method, "POST");
+            assert_eq!(*uri, "http://eth-mainnet.urbit.org:8545");
             if let Noun::Cell(headers) = &*headers {
-                if let Noun::Cell(header) = &*headers.head() {
-                    if let (Noun::Atom(key), Noun::Atom(val)) = (&*header.head(), &*header.tail()) {
-                        assert_eq!(key, "Content-Type");
-                        assert_eq!(val, "application/json");
-                    } else {
-                        panic!("unexpected cell");
-                    }
+                if let Noun::Cell(header) = headers.head_ref() {
+                    assert_eq!(*header.head_ref(), "Content-Type");
+                    assert_eq!(*header.tail_ref(), "application/json");
                 } else {
                     panic!("unexpected atom");
                 }
-                if let Noun::Atom(null) = &*headers.tail() {
-                    assert_eq!(*null, 0u8);
-                } else {
-                    panic!("unexpected cell");
-                }
+                assert_eq!(*headers.tail_ref(), 0u8);
             } else {
                 panic!("unexpected atom");
             }
             if let Noun::Cell(body) = &*body {
-                if let Noun::Atom(null) = &*body.head() {
-                    assert_eq!(*null, 0u8);
-                } else {
-                    panic!("unexpected cell");
-                }
-                if let Noun::Cell(body) = &*body.tail() {
-                    if let (Noun::Atom(body_len), Noun::Atom(body)) = (&*body.head(), &*body.tail())
-                    {
-                        assert_eq!(*body_len, 78u8);
-                        assert_eq!(
-                            body,
-                            r#"[{"params":[],"id":"block number","jsonrpc":"2.0","method":"eth_blockNumber"}]"#
-                        );
-                    } else {
-                        panic!("unexpected cell");
-                    }
+                assert_eq!(*body.head_ref(), 0u8);
+                if let Noun::Cell(body) = body.tail_ref() {
+                    assert_eq!(*body.head_ref(), 78u8);
+                    assert_eq!(*body.tail_ref(), BODY);
                 } else {
                     panic!("unexpected atom");
                 }
             } else {
                 panic!("unexpected atom");
             }
+            assert_eq!(
+                Option::<Octs>::from_noun(&body).expect("unit of octs"),
+                Some(Octs::from(BODY.as_bytes()))
+            );
+
+            let refs = cell.to_array_ref::<6>().expect("as list");
+            let rcs = [tag, req_num, method, uri, headers, body];
+            assert!(refs.iter().zip(&rcs).all(|(r, rc)| std::ptr::eq(*r, &**rc)));
+        }
+
+        // Leading elements and whatever follows them.
+        {
+            let cell = Cell::from([
+                Noun::from("request"),
+                Noun::from(0u8),
+                Noun::from("POST"),
+                Noun::from("http://eth-mainnet.urbit.org:8545"),
+                Noun::from(Cell::from([
+                    Noun::from(Cell::from(["Content-Type", "application/json"])),
+                    Noun::null(),
+                ])),
+                Noun::from(Some(Octs::from(&b"{}"[..]))),
+            ]);
+            let rebuild = |elems: &[Rc<Noun>], rest: Rc<Noun>| {
+                let mut nouns = elems.to_vec();
+                nouns.push(rest);
+                Cell::from(nouns)
+            };
+
+            let ([tag, req_num], rest) = cell.to_array_with_tail::<2>().expect("2 + rest");
+            assert_eq!(*tag, "request");
+            assert_eq!(*req_num, 0u8);
+            assert_eq!(rebuild(&[tag, req_num], rest.clone()), cell);
+            // The rest is shared with the cell rather than copied.
+            let Noun::Cell(tail) = cell.tail_ref() else {
+                panic!("unexpected atom");
+            };
+            assert!(Rc::ptr_eq(&rest, &tail.tail()));
+            assert_eq!(*rest.get(2).expect("method"), "POST");
+
+            // Past the 5 elements before the body, the 6th element is the head of the body.
+            let (elems, rest) = cell.to_array_with_tail::<6>().expect("6 + rest");
+            assert!(elems[5].is_null());
+            assert_eq!(
+                *rest,
+                Noun::from(Cell::from([Noun::from(2u8), Noun::from("{}")]))
+            );
+            assert_eq!(rebuild(&elems, rest), cell);
+
+            assert_eq!(cell.to_array_with_tail::<8>(), None);
+            let ([], rest) = cell.to_array_with_tail::<0>().expect("0 + rest");
+            assert_eq!(*rest, Noun::from(cell.clone()));
+        }
+
+        // Borrowing the elements agrees with cloning them, including on cells that are too short.
+        {
+            fn agree<const N: usize>(cell: &Cell) -> bool {
+                match (cell.to_array::<N>(), cell.to_array_ref::<N>()) {
+                    (None, None) => true,
+                    (Some(rcs), Some(refs)) => {
+                        rcs.iter().zip(refs).all(|(rc, r)| std::ptr::eq(&**rc, r))
+                    }
+                    _ => false,
+                }
+            }
+
+            let cells = [
+                Cell::from([1u8, 2]),
+                Cell::from([1u8, 2, 3]),
+                Cell::from([
+                    Noun::from(Cell::from([1u8, 2])),
+                    Noun::from(3u8),
+                    Noun::from(Cell::from([4u8, 5])),
+                ]),
+                Cell::from(["a", "b", "c", "d", ""]),
+            ];
+            for cell in &cells {
+                assert!(agree::<2>(cell));
+                assert!(agree::<3>(cell));
+                assert!(agree::<4>(cell));
+                assert!(agree::<5>(cell));
+                assert!(agree::<6>(cell));
+            }
+            assert_eq!(cells[1].to_array_ref::<4>(), None);
+            assert_eq!(
+                cells[2].to_array_ref::<3>(),
+                Some([
+                    &Noun::from(Cell::from([1u8, 2])),
+                    &Noun::from(3u8),
+                    &Noun::from(Cell::from([4u8, 5]))
+                ])
+            );
+        }
+    }
+
+    #[test]
+    fn to_tuple() {
+        // Each element converts with its own impl, and the last takes the rest of the cell.
+        {
+            let cell = Cell::from(["poke", "hood", "a", "b", ""]);
+            let (tag, app, data) = cell
+                .to_tuple::<(String, String, Vec<u8>)>()
+                .expect("cell to tuple");
+            assert_eq!(tag, "poke");
+            assert_eq!(app, "hood");
+            assert_eq!(data, b"ab");
+
+            let (tag, rest) = cell.to_tuple::<(String, Noun)>().expect("cell to pair");
+            assert_eq!(tag, "poke");
+            assert_eq!(rest, Noun::from(Cell::from(["hood", "a", "b", ""])));
+
+            let cell = Cell::from([1u8, 2, 3, 4, 5, 6, 7, 8]);
+            assert_eq!(
+                cell.to_tuple::<(u8, u16, u32, u64, u128, usize, u8, u8)>()
+                    .expect("cell to 8-tuple"),
+                (1, 2, 3, 4, 5, 6, 7, 8)
+            );
+        }
+
+        // Arity mismatches.
+        {
+            let cell = Cell::from([1u8, 2u8]);
+            match cell.to_tuple::<(u8, u8, u8)>() {
+                Err(convert::Error::At { axis, path, source }) => {
+                    assert_eq!(axis, Some(3));
+                    assert_eq!(path, [convert::PathSegment::Index(1)]);
+                    assert!(matches!(*source, convert::Error::UnexpectedAtom));
+                }
+                res => panic!("unexpected result: {:?}", res),
+            }
+
+            let cell = Cell::from([1u8, 2u8, 3u8]);
+            match cell.to_tuple::<(u8, u8)>() {
+                Err(convert::Error::At { axis, path, source }) => {
+                    assert_eq!(axis, Some(3));
+                    assert_eq!(path, [convert::PathSegment::Index(1)]);
+                    assert!(matches!(*source, convert::Error::UnexpectedCell));
+                }
+                res => panic!("unexpected result: {:?}", res),
+            }
+        }
+
+        // Conversion failures report the position of the element.
+        {
+            let cell = Cell::from([
+                Noun::from("ok"),
+                Noun::from(Cell::from(["a", "b", ""])),
+                Noun::from(300u16),
+            ]);
+            match cell.to_tuple::<(String, Vec<String>, u8)>() {
+                Err(convert::Error::At { axis, path, source }) => {
+                    assert_eq!(axis, Some(7));
+                    assert_eq!(path, [convert::PathSegment::Index(2)]);
+                    assert!(matches!(*source, convert::Error::AtomToUint));
+                }
+                res => panic!("unexpected result: {:?}", res),
+            }
+            match cell.to_tuple::<(String, Vec<bool>, u16)>() {
+                Err(convert::Error::At { axis, path, source }) => {
+                    assert_eq!(axis, Some(12));
+                    assert_eq!(
+                        path,
+                        [
+                            convert::PathSegment::Index(1),
+                            convert::PathSegment::Index(0)
+                        ]
+                    );
+                    assert!(matches!(*source, convert::Error::ImplType));
+                }
+                res => panic!("unexpected result: {:?}", res),
+            }
         }
     }
 
     #[test]
     fn from_vec() {
         {
-            let _0 = Rc::<Noun>::from(Atom::from(0u8));
-            let _2 = Rc::<Noun>::from(Atom::from(2u8));
-            let _8 = Rc::<Noun>::from(Atom::from(8u8));
-            let _32 = Rc::<Noun>::from(Atom::from(32u8));
-            let _128 = Rc::<Noun>::from(Atom::from(128u8));
+            let n0 = Rc::<Noun>::from(Atom::from(0u8));
+            let n2 = Rc::<Noun>::from(Atom::from(2u8));
+            let n8 = Rc::<Noun>::from(Atom::from(8u8));
+            let n32 = Rc::<Noun>::from(Atom::from(32u8));
+            let n128 = Rc::<Noun>::from(Atom::from(128u8));
             let cell = Cell::from(vec![
-                _0.clone(),
-                _2.clone(),
-                _8.clone(),
-                _32.clone(),
-                _128.clone(),
+                n0.clone(),
+                n2.clone(),
+                n8.clone(),
+                n32.clone(),
+                n128.clone(),
             ]);
 
             let [a, b, c, d, e] = cell.to_array::<5>().expect("cell to array");
-            assert_eq!(a, _0);
-            assert_eq!(b, _2);
-            assert_eq!(c, _8);
-            assert_eq!(d, _32);
-            assert_eq!(e, _128);
+            assert_eq!(a, n0);
+            assert_eq!(b, n2);
+            assert_eq!(c, n8);
+            assert_eq!(d, n32);
+            assert_eq!(e, n128);
         }
+
+        // The elements are moved into the cell, so once the caller's vector or array is gone the
+        // cell holds the only reference to each of them.
+        {
+            let nouns: Vec<_> = (0..5u8).map(|i| Rc::new(Noun::from(i))).collect();
+            let weak: Vec<_> = nouns.iter().map(Rc::downgrade).collect();
+            let cell = Cell::from(nouns);
+            assert!(weak.iter().all(|noun| noun.strong_count() == 1));
+            drop(cell);
+            assert!(weak.iter().all(|noun| noun.strong_count() == 0));
+
+            let nouns = [0u8, 1, 2].map(|i| Rc::new(Noun::from(i)));
+            let weak = nouns.each_ref().map(Rc::downgrade);
+            let cell = Cell::from(nouns);
+            assert!(weak.iter().all(|noun| noun.strong_count() == 1));
+            assert_eq!(cell, Cell::from([0u8, 1, 2]));
+        }
+
+        // Too few nouns for a cell.
+        {
+            let nouns = |len: u8| {
+                (1..=len)
+                    .map(|i| Rc::new(Noun::from(i)))
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(
+                Cell::try_from_vec(nouns(0)),
+                Err(TooFewNounsError { len: 0 })
+            );
+            let err = Cell::try_from_vec(nouns(1)).expect_err("one noun");
+            assert_eq!(err, TooFewNounsError { len: 1 });
+            assert_eq!(err.to_string(), "a cell needs at least 2 nouns, but got 1");
+            assert_eq!(Cell::try_from_vec(nouns(2)), Ok(Cell::from([1u8, 2])));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "a cell needs at least 2 nouns, but got 1")]
+    fn from_short_vec() {
+        let _ = Cell::from(vec![Rc::new(Noun::null())]);
     }
 }
@@ -0,0 +1,183 @@
+//! Jamming values into bytes and cuing them back, by way of their nouns.
+//!
+//! [`to_jammed()`] converts a value into a noun with [`IntoNoun`] and jams it, and
+//! [`from_jammed()`] cues the bytes and converts the noun with [`FromNoun`], which makes any type
+//! that converts to and from a noun into a message format.
+
+use crate::{
+    convert::{self, FromNoun, IntoNoun},
+    noun::Noun,
+    serdes,
+};
+use std::{
+    any,
+    fmt::{self, Display, Formatter},
+};
+
+/// Errors that occur when converting jammed bytes into a value.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum CodecError {
+    /// The bytes aren't a jammed noun.
+    Cue(serdes::Error),
+    /// The jammed noun doesn't convert into the type.
+    Convert {
+        /// The name of the type the noun was converted into.
+        type_name: &'static str,
+        /// The error converting the noun.
+        source: convert::Error,
+    },
+}
+
+impl Display for CodecError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        match self {
+            Self::Cue(err) => write!(f, "the bytes are not a jammed noun: {}", err),
+            Self::Convert { type_name, source } => {
+                write!(f, "the jammed noun is not a `{}`: {}", type_name, source)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CodecError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Cue(err) => Some(err),
+            Self::Convert { source, .. } => Some(source),
+        }
+    }
+}
+
+impl From<serdes::Error> for CodecError {
+    fn from(err: serdes::Error) -> Self {
+        Self::Cue(err)
+    }
+}
+
+/// Converts a value into a noun and jams it into bytes.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::codec;
+/// let bytes = codec::to_jammed((0u8, 19u8));
+/// assert_eq!(bytes, [0x09, 0x9b]);
+/// assert_eq!(codec::from_jammed::<(u8, u8)>(&bytes).unwrap(), (0, 19));
+/// ```
+pub fn to_jammed<T: IntoNoun>(value: T) -> Vec<u8> {
+    value.into_noun().jam_to_vec()
+}
+
+/// Cues jammed bytes into a noun and converts it into a value.
+///
+/// Trailing zero bytes are ignored, as with [`Noun::cue_bytes()`].
+///
+/// # Examples
+///
+/// ```
+/// # use noun::codec::{self, CodecError};
+/// let bytes = codec::to_jammed(String::from("hello"));
+/// assert_eq!(codec::from_jammed::<String>(&bytes).unwrap(), "hello");
+///
+/// let err = codec::from_jammed::<(String, String)>(&bytes).unwrap_err();
+/// assert!(matches!(err, CodecError::Convert { .. }));
+/// assert!(matches!(codec::from_jammed::<String>(&[0xff]), Err(CodecError::Cue(_))));
+/// ```
+pub fn from_jammed<T: FromNoun>(bytes: &[u8]) -> Result<T, CodecError> {
+    let noun = Noun::cue_bytes(bytes)?;
+    T::from_noun(&noun).map_err(|source| CodecError::Convert {
+        type_name: any::type_name::<T>(),
+        source,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cell::Cell;
+
+    /// An event as it might be persisted to a log.
+    #[derive(Clone, Debug, PartialEq)]
+    struct Event {
+        id: u64,
+        kind: String,
+        tags: Vec<String>,
+        payload: Option<Vec<u8>>,
+    }
+
+    impl From<Event> for Noun {
+        fn from(event: Event) -> Self {
+            (event.id, event.kind, event.tags, event.payload).into_noun()
+        }
+    }
+
+    impl TryFrom<&Noun> for Event {
+        type Error = convert::Error;
+
+        fn try_from(noun: &Noun) -> Result<Self, convert::Error> {
+            let (id, kind, tags, payload) = FromNoun::from_noun(noun)?;
+            Ok(Self {
+                id,
+                kind,
+                tags,
+                payload,
+            })
+        }
+    }
+
+    #[test]
+    fn jammed() {
+        // Values round trip through their jammed nouns.
+        {
+            let events = [
+                Event {
+                    id: 0,
+                    kind: String::new(),
+                    tags: Vec::new(),
+                    payload: None,
+                },
+                Event {
+                    id: u64::MAX,
+                    kind: String::from("poke"),
+                    tags: vec![String::from("hood"), String::from("hood")],
+                    payload: Some(vec![0, 1, 2, 255]),
+                },
+            ];
+            for event in events {
+                let bytes = to_jammed(event.clone());
+                assert_eq!(bytes, Noun::from(event.clone()).jam_to_vec());
+                assert_eq!(from_jammed::<Event>(&bytes).expect("round trip"), event);
+            }
+        }
+
+        // Valid jam of the wrong shape.
+        {
+            let bytes = to_jammed(Cell::from(["poke", "hood"]));
+            match from_jammed::<Event>(&bytes) {
+                Err(CodecError::Convert { type_name, source }) => {
+                    assert!(type_name.ends_with("Event"));
+                    assert!(matches!(
+                        source.root_cause(),
+                        convert::Error::UnexpectedAtom
+                    ));
+                }
+                res => panic!("unexpected result: {:?}", res),
+            }
+
+            let bytes = to_jammed((1u8, "poke", Cell::from(["hood", "no terminator"])));
+            let err = from_jammed::<Event>(&bytes).expect_err("improper list");
+            assert!(err.to_string().contains("Event"));
+        }
+
+        // Bytes that aren't jam.
+        {
+            for bytes in [&[][..], &[0xff], &[0x01, 0x00, 0x80]] {
+                assert!(matches!(
+                    from_jammed::<Event>(bytes),
+                    Err(CodecError::Cue(_))
+                ));
+            }
+        }
+    }
+}
@@ -1,11 +1,102 @@
-//! Conversions to and from [`Noun`](crate::noun::Noun).
+//! Conversions to and from [`Noun`].
+//!
+//! [`FromNoun`] and [`IntoNoun`] convert between nouns and Rust types, and because they're traits,
+//! conversions compose: a `Vec<(String, Option<u32>)>` converts to and from a noun as long as each
+//! of its parts does. Every type that implements [`TryFrom`]`<&Noun>` with [`Error`] as its error
+//! type implements [`FromNoun`], and every type that implements [`Into`]`<Noun>` implements
+//! [`IntoNoun`].
+//!
+//! Rust types are mapped to nouns as follows:
+//!
+//! | Rust type                      | Noun                                                      |
+//! |--------------------------------|-----------------------------------------------------------|
+//! | unsigned integers              | an atom                                                   |
+//! | [`bool`]                       | a loobean: `0` for `true` and `1` for `false`             |
+//! | [`char`]                       | an atom of the char's UTF-8 bytes                         |
+//! | [`String`], [`&str`]           | an atom of UTF-8 bytes (a cord)                           |
+//! | `()`                           | `0`                                                       |
+//! | [`Option<T>`]                  | a unit: `0` for [`None`] and `[0 t]` for [`Some`]`(t)`    |
+//! | [`Vec<T>`]                     | a null-terminated list `[t0 t1 ... tN 0]`                 |
+//! | [`HashMap<K, V>`]              | a null-terminated list of pairs `[[k0 v0] ... [kN vN] 0]` |
+//! | tuples `(A, B, ...)`           | a right-nested cell `[a b ...]`                           |
+//! | [`Each<A, B>`]                 | `each`: `[0 a]` for [`Yes`]`(a)` and `[1 b]` for [`No`]`(b)` |
+//! | [`Octs`]                       | `octs`: the byte length and an atom of the bytes `[p q]`  |
+//! | [`Path`]                       | a null-terminated list of knots `/a/b/c`                  |
+//! | [`Noun`], [`Atom`], [`Cell`]   | themselves                                                |
+//!
+//! Every type in the table except [`HashMap`] converts into a noun with [`Noun::from()`] as well as
+//! with [`IntoNoun`]. A [`Vec<u8>`] is a list of bytes like any other [`Vec`]; convert it with
+//! [`Atom::from()`] for an atom of the bytes.
+//!
+//! A [`HashMap`] converts to a list of pairs rather than to a Hoon map, `(map k v)`, which is a
+//! treap ordered by the mugs of its keys. Use [`hoon_map`](crate::hoon_map) for those, and
+//! [`hoon_set`](crate::hoon_set) for Hoon sets.
+//!
+//! # Deriving
+//!
+//! With the `derive` feature enabled, [`FromNoun`] and [`IntoNoun`] can be derived for structs and
+//! enums. The fields of a struct become a right-nested cell in declaration order, and an enum
+//! variant becomes a cell whose head is a `%kebab-case` tag of the variant's name and whose tail
+//! holds the variant's fields. A variant's tag can be renamed with `#[noun(rename = "tag")]`, and a
+//! field can be left out of the noun with `#[noun(skip)]`, in which case it's set to its
+//! [`Default`] value when converting from a noun. See the derive macros for the details.
+//!
+//! ```
+//! # #[cfg(feature = "derive")]
+//! # {
+//! # use noun::{atom::Atom, cell::Cell, convert::{FromNoun, IntoNoun}, Noun};
+//! #[derive(Debug, FromNoun, IntoNoun, PartialEq)]
+//! enum Action {
+//!     Poke { app: String, data: Vec<u8> },
+//!     #[noun(rename = "leave")]
+//!     Unsubscribe(u64),
+//! }
+//!
+//! let action = Action::Poke { app: String::from("hood"), data: vec![1, 2] };
+//! let noun = Noun::from(Cell::from([
+//!     Noun::from(Atom::from("poke")),
+//!     Noun::from(Atom::from("hood")),
+//!     Noun::from(Cell::from([1u8, 2u8, 0u8])),
+//! ]));
+//! assert_eq!(action.into_noun(), noun);
+//! assert_eq!(
+//!     Action::from_noun(&Noun::from(Cell::from(["leave", "a"]))).unwrap(),
+//!     Action::Unsubscribe(u64::from(b'a')),
+//! );
+//! # }
+//! ```
+//!
+//! Head-tagged nouns can also be converted by hand: [`Noun::tag()`] and [`Noun::untag()`] read the
+//! tag of a noun, and [`match_tags!`](crate::match_tags) dispatches on it.
+//!
+//! [`&str`]: prim@str
+//! [`Each<A, B>`]: crate::each::Each
+//! [`No`]: crate::each::Each::No
+//! [`Octs`]: crate::octs::Octs
+//! [`Path`]: crate::path::Path
+//! [`Yes`]: crate::each::Each::Yes
 
-use std::fmt::{self, Display, Formatter};
+use crate::{atom::Atom, axis::peg, cell::Cell, noun::Noun, Rc};
+use std::{
+    collections::HashMap,
+    convert::Infallible,
+    fmt::{self, Display, Formatter},
+    hash::Hash,
+};
+
+#[cfg(feature = "derive")]
+pub use noun_derive::{FromNoun, IntoNoun};
 
 /// Errors that occur when converting from a noun.
+///
+/// A conversion that fails inside a noun, such as on an element of a list or a field of a
+/// struct, reports the failure wrapped in [`Error::At`], which records where in the noun it
+/// happened. Use [`root_cause()`](Self::root_cause()) to get at the failure itself.
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum Error {
-    /// An atom could not be converted into an unsigned integer.
+    /// An atom could not be converted into an unsigned integer or a byte array because it was
+    /// too large.
     AtomToUint,
     /// An atom could not be converted into a string.
     AtomToStr,
@@ -15,10 +106,106 @@ pub enum Error {
     ImplType,
     /// No value exists at a particular axis of a cell.
     MissingValue,
+    /// A Hoon map or set wasn't ordered the way Hoon orders them.
+    InvalidTreap,
     /// Encountered an atom when a cell was expected.
     UnexpectedAtom,
     /// Encountered a cell when an atom was expected.
     UnexpectedCell,
+    /// The tag at the head of a noun didn't match any variant of an enum.
+    UnknownTag,
+    /// Converting failed somewhere inside a noun.
+    At {
+        /// The axis of the noun that failed to convert, or [`None`] if the axis doesn't fit in a
+        /// `u64`.
+        axis: Option<u64>,
+        /// The fields and elements leading to the noun that failed to convert, outermost first.
+        path: Vec<PathSegment>,
+        /// The error converting the noun.
+        source: Box<Error>,
+    },
+}
+
+impl Error {
+    /// Notes that this error occurred in the noun at `axis` relative to the noun being converted.
+    ///
+    /// Conversions that convert the parts of a noun apply this to the errors of those parts as
+    /// they unwind, so that the axis of an [`Error::At`] is relative to the outermost noun.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::convert::Error;
+    /// let err = Error::UnexpectedCell.at(6).at(3);
+    /// assert_eq!(err.to_string(), "a cell was encountered when an atom was expected at axis 14");
+    /// assert!(matches!(err, Error::At { axis: Some(14), .. }));
+    /// ```
+    pub fn at(self, axis: u64) -> Self {
+        self.nest(None, Some(axis))
+    }
+
+    /// Notes that this error occurred in the field or element `segment`, whose axis is `axis`
+    /// relative to the noun being converted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::convert::{Error, PathSegment};
+    /// let err = Error::AtomToUint
+    ///     .at_segment(PathSegment::Field("id"), 2)
+    ///     .at_segment(PathSegment::Index(1), 6);
+    /// assert_eq!(
+    ///     err.to_string(),
+    ///     "the atom is too large to fit in the unsigned integer type at [1].id (axis 12)"
+    /// );
+    /// ```
+    pub fn at_segment(self, segment: PathSegment, axis: u64) -> Self {
+        self.nest(Some(segment), Some(axis))
+    }
+
+    /// Returns the error that caused this one, looking through any [`Error::At`]s.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::{cell::Cell, convert::{Error, FromNoun}, Noun};
+    /// let noun = Noun::from(Cell::from(["a", "b", "no terminator"]));
+    /// let err = Vec::<String>::from_noun(&noun).unwrap_err();
+    /// assert!(matches!(err, Error::At { axis: Some(7), .. }));
+    /// assert!(matches!(err.root_cause(), Error::ExpectedNull));
+    /// ```
+    pub fn root_cause(&self) -> &Self {
+        match self {
+            Self::At { source, .. } => source.root_cause(),
+            err => err,
+        }
+    }
+
+    /// Notes that this error occurred in the noun at `outer`, which is at `segment` if it's a field
+    /// or element, relative to the noun being converted.
+    pub(crate) fn nest(self, segment: Option<PathSegment>, outer: Option<u64>) -> Self {
+        match self {
+            Self::At {
+                axis,
+                mut path,
+                source,
+            } => {
+                if let Some(segment) = segment {
+                    path.insert(0, segment);
+                }
+                Self::At {
+                    axis: outer.zip(axis).and_then(|(outer, axis)| peg(outer, axis)),
+                    path,
+                    source,
+                }
+            }
+            err => Self::At {
+                axis: outer,
+                path: segment.into_iter().collect(),
+                source: Box::new(err),
+            },
+        }
+    }
 }
 
 impl Display for Error {
@@ -31,16 +218,579 @@ impl Display for Error {
             Self::AtomToStr => write!(f, "the atom is not composed of valid UTF-8 bytes"),
             Self::ExpectedNull => write!(f, "a null atom was expected"),
             Self::ImplType => write!(f, "an error specific to the implementing type occurred"),
+            Self::InvalidTreap => write!(f, "the map or set is not ordered the way Hoon orders it"),
             Self::MissingValue => write!(f, "the noun does not have a value at this axis"),
             Self::UnexpectedAtom => write!(f, "an atom was encountered when a cell was expected"),
             Self::UnexpectedCell => write!(f, "a cell was encountered when an atom was expected"),
+            Self::UnknownTag => write!(f, "the tag does not match any variant of the enum"),
+            Self::At { axis, path, source } => {
+                write!(f, "{} at ", source)?;
+                for (i, segment) in path.iter().enumerate() {
+                    match segment {
+                        PathSegment::Field(name) if i == 0 => write!(f, "{}", name)?,
+                        PathSegment::Field(name) => write!(f, ".{}", name)?,
+                        PathSegment::Index(index) => write!(f, "[{}]", index)?,
+                    }
+                }
+                match axis {
+                    Some(axis) if path.is_empty() => write!(f, "axis {}", axis),
+                    Some(axis) => write!(f, " (axis {})", axis),
+                    None if path.is_empty() => write!(f, "an axis too large to represent"),
+                    None => Ok(()),
+                }
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::At { source, .. } => Some(&**source),
+            _ => None,
+        }
+    }
+}
+
+/// A step on the path to a noun that failed to convert.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PathSegment {
+    /// A named field of a struct, or the fields of an enum variant.
+    Field(&'static str),
+    /// An element of a list or map, or an unnamed field of a tuple.
+    Index(usize),
+}
+
+/// Returns the axis of the `index`th element of a right-nested cell or a list, which is the head
+/// of the `index`th tail, or [`None`] if the axis doesn't fit in a `u64`.
+pub(crate) fn elem_axis(index: usize) -> Option<u64> {
+    tail_axis(index).and_then(|axis| axis.checked_mul(2))
+}
+
+/// Returns the axis of the `index`th tail of a right-nested cell or a list, or [`None`] if the
+/// axis doesn't fit in a `u64`.
+pub(crate) fn tail_axis(index: usize) -> Option<u64> {
+    u32::try_from(index + 1)
+        .ok()
+        .and_then(|bits| 1u64.checked_shl(bits))
+        .map(|axis| axis - 1)
+}
+
+/// Conversion from a [`Noun`].
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{cell::Cell, convert::FromNoun, Noun};
+/// let noun = Noun::from(Cell::from(["hello", "world", ""]));
+/// let vec = Vec::<String>::from_noun(&noun).unwrap();
+/// assert_eq!(vec, ["hello", "world"]);
+/// ```
+pub trait FromNoun: Sized {
+    /// Converts `noun` into this type.
+    fn from_noun(noun: &Noun) -> Result<Self, Error>;
+}
+
+/// Conversion into a [`Noun`].
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{atom::Atom, cell::Cell, convert::IntoNoun, Noun};
+/// let noun = (1u8, Some("one")).into_noun();
+/// assert_eq!(noun, Noun::from(Cell::from([
+///     Noun::from(Atom::from(1u8)),
+///     Noun::null(),
+///     Noun::from(Atom::from("one")),
+/// ])));
+/// ```
+pub trait IntoNoun {
+    /// Converts this value into a noun.
+    fn into_noun(self) -> Noun;
+}
+
+impl<T> FromNoun for T
+where
+    T: for<'a> TryFrom<&'a Noun, Error = Error>,
+{
+    fn from_noun(noun: &Noun) -> Result<Self, Error> {
+        Self::try_from(noun)
+    }
+}
+
+impl<T: Into<Noun>> IntoNoun for T {
+    fn into_noun(self) -> Noun {
+        self.into()
+    }
+}
+
+impl FromNoun for Noun {
+    fn from_noun(noun: &Noun) -> Result<Self, Error> {
+        Ok(noun.clone())
+    }
+}
+
+impl FromNoun for Rc<Noun> {
+    fn from_noun(noun: &Noun) -> Result<Self, Error> {
+        Ok(Rc::new(noun.clone()))
+    }
+}
+
+impl IntoNoun for Rc<Noun> {
+    fn into_noun(self) -> Noun {
+        Rc::try_unwrap(self).unwrap_or_else(|noun| (*noun).clone())
+    }
+}
+
+impl FromNoun for Atom {
+    fn from_noun(noun: &Noun) -> Result<Self, Error> {
+        match noun {
+            Noun::Atom(atom) => Ok(atom.clone()),
+            Noun::Cell(_) => Err(Error::UnexpectedCell),
+        }
+    }
+}
+
+impl FromNoun for Cell {
+    fn from_noun(noun: &Noun) -> Result<Self, Error> {
+        match noun {
+            Noun::Atom(_) => Err(Error::UnexpectedAtom),
+            Noun::Cell(cell) => Ok(cell.clone()),
+        }
+    }
+}
+
+/// Implements [`FromNoun`] for an unsigned integer primitive and converts it into an atom.
+macro_rules! impl_noun_conversions_for_uint {
+    ($uint:ty, $as_uint:ident) => {
+        impl FromNoun for $uint {
+            fn from_noun(noun: &Noun) -> Result<Self, Error> {
+                match noun {
+                    Noun::Atom(atom) => atom.$as_uint().ok_or(Error::AtomToUint),
+                    Noun::Cell(_) => Err(Error::UnexpectedCell),
+                }
+            }
+        }
+
+        impl From<$uint> for Noun {
+            fn from(uint: $uint) -> Self {
+                Self::from(Atom::from(uint))
+            }
+        }
+    };
+}
+
+impl_noun_conversions_for_uint!(u8, as_u8);
+impl_noun_conversions_for_uint!(u16, as_u16);
+impl_noun_conversions_for_uint!(u32, as_u32);
+impl_noun_conversions_for_uint!(u64, as_u64);
+impl_noun_conversions_for_uint!(u128, as_u128);
+impl_noun_conversions_for_uint!(usize, as_usize);
+
+impl FromNoun for bool {
+    fn from_noun(noun: &Noun) -> Result<Self, Error> {
+        match u8::from_noun(noun)? {
+            0 => Ok(true),
+            1 => Ok(false),
+            _ => Err(Error::ImplType),
+        }
+    }
+}
+
+impl FromNoun for char {
+    fn from_noun(noun: &Noun) -> Result<Self, Error> {
+        let string = <&str>::try_from(noun)?;
+        let mut chars = string.chars();
+        match (chars.next(), chars.next()) {
+            // The atom of `'\0'` is null, which has no bytes.
+            (None, _) => Ok('\0'),
+            (Some(c), None) => Ok(c),
+            (Some(_), Some(_)) => Err(Error::ImplType),
+        }
+    }
+}
+
+impl FromNoun for () {
+    fn from_noun(noun: &Noun) -> Result<Self, Error> {
+        null_from_noun(noun)
+    }
+}
+
+impl From<bool> for Noun {
+    fn from(flag: bool) -> Self {
+        Self::from(Atom::from(u8::from(!flag)))
+    }
+}
+
+impl From<char> for Noun {
+    fn from(c: char) -> Self {
+        Self::from(Atom::from(c.encode_utf8(&mut [0; 4]) as &str))
+    }
+}
+
+impl From<String> for Noun {
+    fn from(string: String) -> Self {
+        Self::from(Atom::from(string))
+    }
+}
+
+impl From<&str> for Noun {
+    fn from(string: &str) -> Self {
+        Self::from(Atom::from(string))
+    }
+}
+
+impl From<()> for Noun {
+    fn from(_: ()) -> Self {
+        Self::null()
+    }
+}
+
+impl<T: FromNoun> TryFrom<&Noun> for Option<T> {
+    type Error = Error;
+
+    fn try_from(noun: &Noun) -> Result<Self, Error> {
+        unit_from_noun(noun, T::from_noun)
+    }
+}
+
+impl<T: IntoNoun> From<Option<T>> for Noun {
+    fn from(unit: Option<T>) -> Self {
+        match unit {
+            Some(val) => Self::from(Cell::from([Self::null(), val.into_noun()])),
+            None => Self::null(),
+        }
+    }
+}
+
+impl<T: FromNoun> TryFrom<&Noun> for Vec<T> {
+    type Error = Error;
+
+    fn try_from(noun: &Noun) -> Result<Self, Error> {
+        list_from_noun(noun, T::from_noun)
+    }
+}
+
+impl<T: IntoNoun> From<Vec<T>> for Noun {
+    fn from(elems: Vec<T>) -> Self {
+        list_into_noun(elems.into_iter().map(|elem| Ok(elem.into_noun())))
+            .unwrap_or_else(|never: Infallible| match never {})
+    }
+}
+
+impl<K: FromNoun + Eq + Hash, V: FromNoun> TryFrom<&Noun> for HashMap<K, V> {
+    type Error = Error;
+
+    fn try_from(noun: &Noun) -> Result<Self, Error> {
+        map_from_noun(noun, K::from_noun, V::from_noun)
+    }
+}
+
+impl<K: IntoNoun, V: IntoNoun> IntoNoun for HashMap<K, V> {
+    fn into_noun(self) -> Noun {
+        pairs_to_noun(self)
+    }
+}
+
+/// A tuple of two to eight types that implement [`FromNoun`], which
+/// [`Cell::to_tuple()`](crate::Cell::to_tuple()) converts a cell into.
+///
+/// This trait is sealed and can't be implemented outside of this crate.
+pub trait Tuple: FromNoun + private::Sealed {}
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// Implements [`FromNoun`], [`Tuple`], and [`IntoNoun`] for a tuple, which maps to a right-nested
+/// cell.
+macro_rules! impl_noun_conversions_for_tuple {
+    ($($elem:ident $var:ident $index:literal),+; $last:ident $last_var:ident $last_index:literal) => {
+        impl<$($elem: FromNoun,)+ $last: FromNoun> FromNoun for ($($elem,)+ $last) {
+            fn from_noun(mut noun: &Noun) -> Result<Self, Error> {
+                $(
+                    let $var = field_from_noun(&mut noun, $index, false, PathSegment::Index($index))?;
+                )+
+                let $last_var =
+                    field_from_noun(&mut noun, $last_index, true, PathSegment::Index($last_index))?;
+                Ok(($($var,)+ $last_var))
+            }
+        }
+
+        impl<$($elem: FromNoun,)+ $last: FromNoun> private::Sealed for ($($elem,)+ $last) {}
+
+        impl<$($elem: FromNoun,)+ $last: FromNoun> Tuple for ($($elem,)+ $last) {}
+
+        impl<$($elem: IntoNoun,)+ $last: IntoNoun> IntoNoun for ($($elem,)+ $last) {
+            fn into_noun(self) -> Noun {
+                let ($($var,)+ $last_var) = self;
+                Noun::from(Cell::from([$($var.into_noun(),)+ $last_var.into_noun()]))
+            }
+        }
+    };
+}
+
+impl_noun_conversions_for_tuple!(A a 0; B b 1);
+impl_noun_conversions_for_tuple!(A a 0, B b 1; C c 2);
+impl_noun_conversions_for_tuple!(A a 0, B b 1, C c 2; D d 3);
+impl_noun_conversions_for_tuple!(A a 0, B b 1, C c 2, D d 3; E e 4);
+impl_noun_conversions_for_tuple!(A a 0, B b 1, C c 2, D d 3, E e 4; F f 5);
+impl_noun_conversions_for_tuple!(A a 0, B b 1, C c 2, D d 3, E e 4, F f 5; G g 6);
+impl_noun_conversions_for_tuple!(A a 0, B b 1, C c 2, D d 3, E e 4, F f 5, G g 6; H h 7);
+
+/// Converts an iterator of pairs into a null-terminated list of pairs `[[k0 v0] ... [kN vN] 0]`, in
+/// iteration order.
+///
+/// This is the inverse of converting a noun to a [`HashMap`] or to a [`Vec`] of pairs, and an empty
+/// iterator converts to null.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{cell::Cell, convert::{self, FromNoun}, Noun};
+/// let headers = [("content-type", "text/plain"), ("content-length", "5")];
+/// let noun = convert::pairs_to_noun(headers);
+/// assert_eq!(noun, Noun::from(Cell::from([
+///     Noun::from(Cell::from(["content-type", "text/plain"])),
+///     Noun::from(Cell::from(["content-length", "5"])),
+///     Noun::null(),
+/// ])));
+/// ```
+pub fn pairs_to_noun<K, V>(pairs: impl IntoIterator<Item = (K, V)>) -> Noun
+where
+    K: IntoNoun,
+    V: IntoNoun,
+{
+    let pairs: Vec<_> = pairs
+        .into_iter()
+        .map(|(key, val)| Ok(Noun::from(Cell::from([key.into_noun(), val.into_noun()]))))
+        .collect();
+    list_into_noun(pairs.into_iter()).unwrap_or_else(|never: Infallible| match never {})
+}
+
+/// Converts a unit, which is `0` or `[0 t]`, into an [`Option`], converting `t` with `val`.
+#[doc(hidden)]
+pub fn unit_from_noun<'a, T>(
+    noun: &'a Noun,
+    val: impl FnOnce(&'a Noun) -> Result<T, Error>,
+) -> Result<Option<T>, Error> {
+    match noun {
+        Noun::Atom(atom) if atom.is_null() => Ok(None),
+        Noun::Atom(_) => Err(Error::ExpectedNull),
+        Noun::Cell(cell) if cell.head_ref().is_null() => {
+            val(cell.tail_ref()).map(Some).map_err(|err| err.at(3))
+        }
+        Noun::Cell(_) => Err(Error::ExpectedNull),
+    }
+}
+
+/// Converts a null-terminated list into a [`Vec`], converting each element with `elem`.
+#[doc(hidden)]
+pub fn list_from_noun<'a, T>(
+    noun: &'a Noun,
+    mut elem: impl FnMut(&'a Noun) -> Result<T, Error>,
+) -> Result<Vec<T>, Error> {
+    noun.into_iter()
+        .enumerate()
+        .map(|(index, noun)| {
+            elem(noun?).map_err(|err| err.nest(Some(PathSegment::Index(index)), elem_axis(index)))
+        })
+        .collect()
+}
+
+/// Converts a null-terminated list into a [`Vec`], converting its elements in parallel on rayon's
+/// global thread pool.
+///
+/// The list is walked once, sequentially, to borrow its elements, which are then converted in
+/// parallel. The result is in list order and is the same as [`Vec::try_from()`]'s, errors
+/// included: if elements fail to convert, the error is the one for the element nearest the front,
+/// and elements after a failed one may not be converted at all.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{cell::Cell, convert, Noun};
+/// let noun = Noun::from(Cell::from([1u8, 2, 3, 0]));
+/// assert_eq!(convert::par_list_to_vec::<u64>(&noun).unwrap(), [1, 2, 3]);
+///
+/// let noun = Noun::from(Cell::from(["a", "bb", "cc", ""]));
+/// let err = convert::par_list_to_vec::<u8>(&noun).unwrap_err();
+/// assert_eq!(
+///     err.to_string(),
+///     "the atom is too large to fit in the unsigned integer type at [1] (axis 6)"
+/// );
+/// ```
+#[cfg(feature = "rayon")]
+pub fn par_list_to_vec<T: FromNoun + Send>(noun: &Noun) -> Result<Vec<T>, Error> {
+    use rayon::prelude::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let mut elems = Vec::new();
+    let mut end = Ok(());
+    for elem in noun {
+        match elem {
+            Ok(elem) => elems.push(elem),
+            Err(err) => {
+                end = Err(err);
+                break;
+            }
+        }
+    }
+
+    let first_err = AtomicUsize::new(usize::MAX);
+    let converted: Vec<Option<Result<T, Error>>> = elems
+        .into_par_iter()
+        .enumerate()
+        .map(|(index, elem)| {
+            if index > first_err.load(Ordering::Relaxed) {
+                return None;
+            }
+            let result = T::from_noun(elem)
+                .map_err(|err| err.nest(Some(PathSegment::Index(index)), elem_axis(index)));
+            if result.is_err() {
+                first_err.fetch_min(index, Ordering::Relaxed);
+            }
+            Some(result)
+        })
+        .collect();
+    // Only elements after a failed one are skipped, so collecting stops at an error before it
+    // would reach a skipped element.
+    let vec = converted.into_iter().flatten().collect::<Result<_, _>>()?;
+    end.map(|()| vec)
+}
+
+/// Converts a null-terminated list of pairs into a [`Vec`] of pairs in list order, converting each
+/// key with `key` and each value with `val`.
+#[doc(hidden)]
+pub fn pairs_from_noun<'a, K, V>(
+    noun: &'a Noun,
+    mut key: impl FnMut(&'a Noun) -> Result<K, Error>,
+    mut val: impl FnMut(&'a Noun) -> Result<V, Error>,
+) -> Result<Vec<(K, V)>, Error> {
+    list_from_noun(noun, |pair| pair_from_noun(pair, &mut key, &mut val))
+}
+
+/// Converts a null-terminated list of pairs into a [`HashMap`], converting each key with `key`
+/// and each value with `val`.
+#[doc(hidden)]
+pub fn map_from_noun<'a, K: Eq + Hash, V>(
+    noun: &'a Noun,
+    mut key: impl FnMut(&'a Noun) -> Result<K, Error>,
+    mut val: impl FnMut(&'a Noun) -> Result<V, Error>,
+) -> Result<HashMap<K, V>, Error> {
+    let mut map = HashMap::new();
+    list_from_noun(noun, |pair| {
+        let (key, val) = pair_from_noun(pair, &mut key, &mut val)?;
+        map.insert(key, val);
+        Ok(())
+    })?;
+    Ok(map)
+}
+
+/// Converts a pair `[k v]`, converting the key with `key` and the value with `val`.
+pub(crate) fn pair_from_noun<'a, K, V>(
+    pair: &'a Noun,
+    key: impl FnOnce(&'a Noun) -> Result<K, Error>,
+    val: impl FnOnce(&'a Noun) -> Result<V, Error>,
+) -> Result<(K, V), Error> {
+    match pair {
+        Noun::Atom(_) => Err(Error::UnexpectedAtom),
+        Noun::Cell(pair) => Ok((
+            key(pair.head_ref()).map_err(|err| err.at_segment(PathSegment::Index(0), 2))?,
+            val(pair.tail_ref()).map_err(|err| err.at_segment(PathSegment::Index(1), 3))?,
+        )),
+    }
+}
+
+/// Converts the elements yielded by a double-ended iterator into a null-terminated list, stopping
+/// at the first element that fails to convert.
+#[doc(hidden)]
+pub fn list_into_noun<E>(
+    elems: impl DoubleEndedIterator<Item = Result<Noun, E>>,
+) -> Result<Noun, E> {
+    let mut noun = Rc::new(Noun::null());
+    for elem in elems.rev() {
+        noun = Rc::new(Noun::from(Cell::from([Rc::new(elem?), noun])));
+    }
+    Ok(Rc::try_unwrap(noun).unwrap_or_else(|noun| (*noun).clone()))
+}
+
+/// Converts the `index`th field of a right-nested cell of fields, where `noun` is the `index`th
+/// tail of the cell, advancing `noun` past the field. The last field is the rest of the cell.
+/// Errors are reported at `segment`.
+#[doc(hidden)]
+pub fn field_from_noun<T: FromNoun>(
+    noun: &mut &Noun,
+    index: usize,
+    last: bool,
+    segment: PathSegment,
+) -> Result<T, Error> {
+    if last {
+        return T::from_noun(noun).map_err(|err| err.nest(Some(segment), tail_axis(index)));
+    }
+    match *noun {
+        Noun::Atom(_) => Err(Error::UnexpectedAtom.nest(Some(segment), tail_axis(index))),
+        Noun::Cell(cell) => {
+            *noun = cell.tail_ref();
+            T::from_noun(cell.head_ref()).map_err(|err| err.nest(Some(segment), elem_axis(index)))
         }
     }
 }
 
+/// Checks that a noun with no fields is null.
+#[doc(hidden)]
+pub fn null_from_noun(noun: &Noun) -> Result<(), Error> {
+    match noun {
+        Noun::Atom(atom) if atom.is_null() => Ok(()),
+        Noun::Atom(_) => Err(Error::ExpectedNull),
+        Noun::Cell(_) => Err(Error::UnexpectedCell),
+    }
+}
+
+/// Splits a head-tagged noun into the bytes of its tag and its fields, which are [`None`] if the
+/// noun is a bare tag.
+#[doc(hidden)]
+pub fn tag_from_noun(noun: &Noun) -> Result<(&[u8], Option<&Noun>), Error> {
+    match noun {
+        Noun::Atom(tag) => Ok((tag.as_bytes(), None)),
+        Noun::Cell(cell) => match cell.head_ref() {
+            Noun::Atom(tag) => Ok((tag.as_bytes(), Some(cell.tail_ref()))),
+            Noun::Cell(_) => Err(Error::UnexpectedCell),
+        },
+    }
+}
+
+/// Converts fields into a right-nested cell, or into the only field if there's just one, or into
+/// null if there are none.
+#[doc(hidden)]
+pub fn fields_into_noun(mut fields: Vec<Noun>) -> Noun {
+    match fields.len() {
+        0 => Noun::null(),
+        1 => fields.remove(0),
+        _ => Noun::from(Cell::from(
+            fields.into_iter().map(Rc::new).collect::<Vec<_>>(),
+        )),
+    }
+}
+
+/// Calls `f` with `noun`, which gives the closures passed to [`match_tags!`] their argument type.
+#[doc(hidden)]
+pub fn call_with_noun<'a, T>(noun: &'a Noun, f: impl FnOnce(&'a Noun) -> T) -> T {
+    f(noun)
+}
+
+/// Borrows the noun held by `noun`, which lets [`convert!`] take a `&Noun`, a `&Rc<Noun>`, or a
+/// reference to anything else that implements [`AsRef`]`<Noun>`.
+#[doc(hidden)]
+pub fn as_noun<T: AsRef<Noun> + ?Sized>(noun: &T) -> &Noun {
+    noun.as_ref()
+}
+
 /// Converts [`Noun`](crate::Noun)s to and from other complex types.
 ///
-/// There are three forms of this macro:
+/// This macro predates [`FromNoun`] and [`IntoNoun`], which should be preferred.
+///
+/// There are five forms of this macro:
 ///
 /// - Convert a [`&Noun`] of the form `[e0 e1 ... eN 0]` (a null-terminated list) to a
 ///   [`Vec`]`<$elem_type>`, returning [`Result`]`<`[`Vec`]`<$elem_type>, `[`Error`]`>`.
@@ -99,6 +849,39 @@ impl Display for Error {
 /// assert_eq!(map.get("Pujols"), Some(&"Albert"));
 /// ```
 ///
+/// - Convert a [`&Noun`] of the form `[[k0 v0] [k1 v1] ... [kN vN] 0]` (a null-terminated list of
+///   pairs) to a [`Vec`]`<($key_type, $val_type)>`, returning
+///   [`Result`]`<`[`Vec`]`<($key_type, $val_type)>, `[`Error`]`>`.
+///
+///   `$key_type` and `$val_type` must each implement [`TryFrom`]`<`[`&Noun`]`>`.
+///
+///   Unlike the [`HashMap`] form, the resulting [`Vec`] preserves the order of the pairs and any
+///   duplicate keys.
+///
+/// ```
+/// # use noun::{cell::Cell, convert, noun::Noun};
+/// let noun = Noun::from(Cell::from([
+///     Noun::from(Cell::from(["vary", "Origin"])),
+///     Noun::from(Cell::from(["vary", "Accept-Encoding"])),
+///     Noun::null()
+/// ]));
+/// let headers = convert!(&noun => Vec<(&str, &str)>).unwrap();
+/// assert_eq!(headers, [("vary", "Origin"), ("vary", "Accept-Encoding")]);
+/// ```
+///
+/// - Convert a [`&Noun`] of the form `0` or `[0 t]` (a unit) to an [`Option`]`<$val_type>`,
+///   returning [`Result`]`<`[`Option`]`<$val_type>, `[`Error`]`>`. `0` converts to [`None`] and
+///   `[0 t]` to [`Some`]`(t)`.
+///
+///   `$val_type` must implement [`TryFrom`]`<`[`&Noun`]`>`.
+///
+/// ```
+/// # use noun::{cell::Cell, convert, noun::Noun};
+/// let noun = Noun::from(Cell::from(["", "value"]));
+/// assert_eq!(convert!(&noun => Option<&str>).unwrap(), Some("value"));
+/// assert_eq!(convert!(&Noun::null() => Option<&str>).unwrap(), None);
+/// ```
+///
 /// - Convert an iterator of the form `[e0, e1, ... eN]` where each element has type `T` into a
 ///   [`Noun`] of the form `[e0 e1 ... eN 0]` (a null-terminated list), returning
 ///   [`Result`]`<`[`Noun`]`, <err_type>>`, where `<err_type>` is the type of error returned by
@@ -108,7 +891,7 @@ impl Display for Error {
 ///
 /// ```
 /// # use noun::{atom::Atom, cell::Cell, convert, noun::Noun};
-/// let strings = [];
+/// let strings: [&str; 0] = [];
 /// let noun = convert!(strings.iter() => Noun).unwrap();
 /// assert!(noun.is_null());
 /// ```
@@ -134,93 +917,140 @@ impl Display for Error {
 /// );
 /// ```
 ///
+/// The element, key, and value types of the first four forms can themselves be lists, maps, or
+/// units, because [`Vec`]`<T>`, [`HashMap`]`<K, V>`, and [`Option`]`<T>` implement
+/// [`TryFrom`]`<`[`&Noun`]`>` whenever their parts implement [`FromNoun`]:
+///
+/// ```
+/// # use noun::{cell::Cell, convert, noun::Noun};
+/// let noun = Noun::from(Cell::from([
+///     Noun::from(Cell::from(["a", "b", ""])),
+///     Noun::null(),
+///     Noun::null(),
+/// ]));
+/// let lists = convert!(&noun => Vec<Vec<String>>).unwrap();
+/// assert_eq!(lists, [vec!["a", "b"], vec![]]);
+/// ```
+///
+/// Wherever a form takes a [`&Noun`], it also takes a reference to anything that implements
+/// [`AsRef`]`<`[`Noun`]`>`, such as an [`&Rc<Noun>`](crate::Rc). Elements can borrow from the noun
+/// instead of copying out of it: [`&str`](prim@str), [`&Atom`](crate::Atom), and
+/// [`&Cell`](crate::Cell) implement [`TryFrom`]`<`[`&Noun`]`>` with the lifetime of the noun.
+///
+/// ```
+/// # use noun::{atom::Atom, cell::Cell, convert, noun::Noun, Rc};
+/// let noun = Rc::new(Noun::from(Cell::from([
+///     Noun::from(Cell::from(["vary", "Origin"])),
+///     Noun::null(),
+/// ])));
+/// let headers = convert!(&noun => Vec<(&str, &Atom)>).unwrap();
+/// assert_eq!(headers, [("vary", &Atom::from("Origin"))]);
+/// ```
+///
 /// [`Err(Error)`]: Error
 /// [`HashMap`]: std::collections::HashMap
 /// [`&Noun`]: crate::Noun
 /// [`Noun`]: crate::Noun
 #[macro_export]
 macro_rules! convert {
-    ($noun:expr => Vec<$elem_type:ty>) => {{
-        use $crate::{convert::Error, noun::Noun};
-        let mut noun = $noun;
-        let mut elems: Vec<$elem_type> = Vec::new();
-        loop {
-            match noun {
-                Noun::Atom(atom) => {
-                    if atom.is_null() {
-                        break Ok(elems);
-                    } else {
-                        break Err(Error::ExpectedNull);
-                    }
-                }
-                Noun::Cell(cell) => match <$elem_type>::try_from(cell.head_ref()) {
-                    Ok(elem) => {
-                        elems.push(elem);
-                        noun = cell.tail_ref();
-                    }
-                    Err(err) => break Err(err),
-                },
-            }
-        }
-    }};
-    ($noun:expr => HashMap<$key_type:ty, $val_type:ty>) => {{
-        use std::collections::HashMap;
-        use $crate::{convert::Error, noun::Noun};
-        let mut noun = $noun;
-        let mut map: HashMap<$key_type, $val_type> = HashMap::new();
-        loop {
-            match noun {
-                Noun::Atom(atom) => {
-                    if atom.is_null() {
-                        break Ok(map);
-                    } else {
-                        break Err(Error::ExpectedNull);
-                    }
-                }
-                Noun::Cell(cell) => {
-                    if let Noun::Cell(head) = cell.head_ref() {
-                        match (
-                            <$key_type>::try_from(head.head_ref()),
-                            <$val_type>::try_from(head.tail_ref()),
-                        ) {
-                            (Ok(key), Ok(val)) => {
-                                map.insert(key, val);
-                                noun = cell.tail_ref();
-                            }
-                            (Err(err), _) => break Err(err),
-                            (_, Err(err)) => break Err(err),
-                        }
-                    } else {
-                        break Err(Error::UnexpectedAtom);
-                    }
+    // This has to come before the Vec<$elem_type> form, which would match a tuple too.
+    ($noun:expr => Vec<($key_type:ty, $val_type:ty)>) => {
+        $crate::convert::pairs_from_noun(
+            $crate::convert::as_noun($noun),
+            <$key_type>::try_from,
+            <$val_type>::try_from,
+        )
+    };
+    ($noun:expr => Vec<$elem_type:ty>) => {
+        $crate::convert::list_from_noun($crate::convert::as_noun($noun), <$elem_type>::try_from)
+    };
+    ($noun:expr => HashMap<$key_type:ty, $val_type:ty>) => {
+        $crate::convert::map_from_noun(
+            $crate::convert::as_noun($noun),
+            <$key_type>::try_from,
+            <$val_type>::try_from,
+        )
+    };
+    ($noun:expr => Option<$val_type:ty>) => {
+        $crate::convert::unit_from_noun($crate::convert::as_noun($noun), <$val_type>::try_from)
+    };
+    ($iter:expr => Noun) => {
+        $crate::convert::list_into_noun($iter.map($crate::noun::Noun::try_from))
+    };
+}
+
+/// Dispatches on the tag of a head-tagged cell like `[%add a b]`.
+///
+/// Each arm pairs a tag with a closure that's called with the tail of the cell if its head is that
+/// tag, and the `else` arm is called with the whole noun if no tag matches, including when the
+/// noun isn't a head-tagged cell at all. Tags are compared with [`Noun::tag()`], so only terms
+/// (`@tas`) match. Every closure must return the same type.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{atom::Atom, cell::Cell, convert::{Error, FromNoun}, match_tags, Noun};
+/// enum Expr {
+///     Lit(u64),
+///     Neg(Box<Expr>),
+///     Add(Box<Expr>, Box<Expr>),
+/// }
+///
+/// fn parse(noun: &Noun) -> Result<Expr, Error> {
+///     match_tags!(noun, {
+///         "lit" => |val| u64::from_noun(val).map(Expr::Lit),
+///         "neg" => |expr| Ok(Expr::Neg(Box::new(parse(expr)?))),
+///         "add" => |args| {
+///             let (a, b) = <(Noun, Noun)>::from_noun(args)?;
+///             Ok(Expr::Add(Box::new(parse(&a)?), Box::new(parse(&b)?)))
+///         },
+///         else => |_| Err(Error::UnknownTag),
+///     })
+/// }
+///
+/// fn eval(expr: &Expr) -> i64 {
+///     match expr {
+///         Expr::Lit(val) => *val as i64,
+///         Expr::Neg(expr) => -eval(expr),
+///         Expr::Add(a, b) => eval(a) + eval(b),
+///     }
+/// }
+///
+/// // [%add [%lit 2] [%neg [%lit 5]]]
+/// let noun = Noun::from(Cell::from([
+///     Noun::from(Atom::from("add")),
+///     Noun::from(Cell::from([Noun::from(Atom::from("lit")), Noun::from(Atom::from(2u8))])),
+///     Noun::from(Cell::from([
+///         Noun::from(Atom::from("neg")),
+///         Noun::from(Cell::from([Noun::from(Atom::from("lit")), Noun::from(Atom::from(5u8))])),
+///     ])),
+/// ]));
+/// assert_eq!(eval(&parse(&noun).unwrap()), -3);
+/// ```
+#[macro_export]
+macro_rules! match_tags {
+    ($noun:expr, { $($tag:literal => $arm:expr,)* else => $else:expr $(,)? }) => {{
+        let noun: &$crate::noun::Noun = $noun;
+        let tagged = match noun {
+            $crate::noun::Noun::Cell(cell) => noun
+                .tag()
+                .map(|tag| (tag.as_bytes(), cell.tail_ref())),
+            $crate::noun::Noun::Atom(_) => None,
+        };
+        match tagged {
+            $(
+                Some((tag, tail)) if tag == str::as_bytes($tag) => {
+                    $crate::convert::call_with_noun(tail, $arm)
                 }
-            }
-        }
-    }};
-    ($iter:expr => Noun) => {{
-        use $crate::{cell::Cell, noun::Noun, Rc};
-        let mut noun = Rc::<Noun>::from(Noun::null());
-        let mut iter = $iter.rev();
-        loop {
-            match iter.next() {
-                Some(elem) => match Noun::try_from(elem) {
-                    Ok(elem) => {
-                        noun = Rc::<Noun>::from(Noun::from(Cell::from([
-                            Rc::<Noun>::from(elem),
-                            noun,
-                        ])));
-                    }
-                    Err(err) => break Err(err),
-                },
-                None => break Ok(Rc::try_unwrap(noun).unwrap()),
-            }
+            )*
+            _ => $crate::convert::call_with_noun(noun, $else),
         }
     }};
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{atom::Atom, cell::Cell, noun::Noun};
+    use super::*;
 
     #[test]
     fn convert() {
@@ -240,6 +1070,143 @@ mod tests {
             }
         }
 
+        // Noun -> Vec<(&str, &str)>: expect success, in order.
+        {
+            let noun = Noun::from(Cell::from([
+                Noun::from(Cell::from(["x-cached", "HIT"])),
+                Noun::from(Cell::from(["vary", "Origin"])),
+                Noun::from(Cell::from(["vary", "Accept-Encoding"])),
+                Noun::from(Cell::from(["connection", "keep-alive"])),
+                Noun::from(Cell::from(["content-length", "59"])),
+                Noun::from(Cell::from(["content-type", "application/json"])),
+                Noun::from(Cell::from(["date", "Fri, 08 Jul 2022 16:43:50 GMT"])),
+                Noun::from(Cell::from(["server", "nginx/1.14.0 (Ubuntu)"])),
+                Noun::from(0u8),
+            ]));
+            let headers = convert!(&noun => Vec<(&str, &str)>).expect("Noun to Vec<(&str, &str)>");
+            assert_eq!(
+                headers,
+                [
+                    ("x-cached", "HIT"),
+                    ("vary", "Origin"),
+                    ("vary", "Accept-Encoding"),
+                    ("connection", "keep-alive"),
+                    ("content-length", "59"),
+                    ("content-type", "application/json"),
+                    ("date", "Fri, 08 Jul 2022 16:43:50 GMT"),
+                    ("server", "nginx/1.14.0 (Ubuntu)"),
+                ]
+            );
+
+            // The map form keeps only the last of the duplicate keys.
+            let map = convert!(&noun => HashMap<&str, &str>).expect("Noun to HashMap<&str, &str>");
+            assert_eq!(map.len(), headers.len() - 1);
+            assert_eq!(map.get("vary"), Some(&"Accept-Encoding"));
+
+            assert!(convert!(&Noun::null() => Vec<(&str, &str)>)
+                .expect("null to Vec<(&str, &str)>")
+                .is_empty());
+
+            // The strings borrow the bytes of the noun's atoms rather than copying them.
+            for (i, (key, val)) in headers.iter().enumerate() {
+                let pair = noun.get(elem_axis(i).expect("axis")).expect("pair");
+                assert_eq!(
+                    key.as_ptr(),
+                    <&Atom>::try_from(pair.get(2).expect("key"))
+                        .expect("key atom")
+                        .as_bytes()
+                        .as_ptr()
+                );
+                assert_eq!(
+                    val.as_ptr(),
+                    <&Atom>::try_from(pair.get(3).expect("value"))
+                        .expect("value atom")
+                        .as_bytes()
+                        .as_ptr()
+                );
+            }
+        }
+
+        // Noun -> Vec<&Atom> and Vec<&str> from an Rc<Noun>.
+        {
+            let noun = Rc::new(Noun::from(Cell::from(["gx", "~zod", ""])));
+            let atoms = convert!(&noun => Vec<&Atom>).expect("Rc<Noun> to Vec<&Atom>");
+            assert_eq!(atoms, [&Atom::from("gx"), &Atom::from("~zod")]);
+            let strings = convert!(&noun => Vec<&str>).expect("Rc<Noun> to Vec<&str>");
+            assert_eq!(strings, ["gx", "~zod"]);
+            assert_eq!(strings[1].as_ptr(), atoms[1].as_bytes().as_ptr());
+
+            let noun = Rc::new(Noun::from(Cell::from(["", "value"])));
+            assert_eq!(
+                convert!(&noun => Option<&str>).expect("unit"),
+                Some("value")
+            );
+            assert!(convert!(&noun => Vec<&Cell>).is_err());
+        }
+
+        // Noun -> Vec<(&str, &str)>: expect failure.
+        {
+            let noun = Noun::from(Cell::from([
+                Noun::from(Cell::from(["vary", "Origin"])),
+                Noun::from("not a pair"),
+                Noun::null(),
+            ]));
+            assert!(matches!(
+                convert!(&noun => Vec<(&str, &str)>)
+                    .as_ref()
+                    .map_err(Error::root_cause),
+                Err(Error::UnexpectedAtom)
+            ));
+
+            let noun = Noun::from(Cell::from([
+                Noun::from(Cell::from(["vary", "Origin"])),
+                Noun::from(Cell::from(["no", "terminator"])),
+                Noun::from(1u8),
+            ]));
+            assert!(matches!(
+                convert!(&noun => Vec<(&str, &str)>)
+                    .as_ref()
+                    .map_err(Error::root_cause),
+                Err(Error::ExpectedNull)
+            ));
+
+            let noun = Noun::from(Cell::from([
+                Noun::from(Cell::from([
+                    Noun::from("vary"),
+                    Noun::from(Cell::from(["not", "an atom"])),
+                ])),
+                Noun::null(),
+            ]));
+            assert!(matches!(
+                convert!(&noun => Vec<(&str, &str)>)
+                    .as_ref()
+                    .map_err(Error::root_cause),
+                Err(Error::UnexpectedCell)
+            ));
+        }
+
+        // Noun -> Option<&str>.
+        {
+            let noun = Noun::from(Cell::from(["", "value"]));
+            assert_eq!(
+                convert!(&noun => Option<&str>).expect("unit to Option<&str>"),
+                Some("value")
+            );
+            assert_eq!(
+                convert!(&Noun::null() => Option<&str>).expect("null to Option<&str>"),
+                None
+            );
+            for noun in [
+                Noun::from(1u8),
+                Noun::from(Cell::from(["not null", "value"])),
+            ] {
+                assert!(matches!(
+                    convert!(&noun => Option<&str>),
+                    Err(Error::ExpectedNull)
+                ));
+            }
+        }
+
         // &[&str] -> Noun: expect success.
         {
             {
@@ -257,4 +1224,683 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn traits() {
+        // The examples of convert!, using the traits instead.
+        {
+            let vec = Vec::<String>::from_noun(&Noun::null()).expect("Noun to Vec<String>");
+            assert!(vec.is_empty());
+        }
+
+        {
+            let noun = Noun::from(Cell::from([
+                Atom::from("hello"),
+                Atom::from("world"),
+                Atom::null(),
+            ]));
+            let vec = Vec::<String>::from_noun(&noun).expect("Noun to Vec<String>");
+            assert_eq!(vec, vec!["hello", "world"]);
+        }
+
+        {
+            let map = HashMap::<String, String>::from_noun(&Noun::null())
+                .expect("Noun to HashMap<String, String>");
+            assert_eq!(map.len(), 0);
+        }
+
+        {
+            let noun = Noun::from(Cell::from([
+                Noun::from(Cell::from(["Ruth", "Babe"])),
+                Noun::from(Cell::from(["Williams", "Ted"])),
+                Noun::from(Cell::from(["Bonds", "Barry"])),
+                Noun::from(Cell::from(["Pujols", "Albert"])),
+                Noun::null(),
+            ]));
+            let map = HashMap::<String, String>::from_noun(&noun)
+                .expect("Noun to HashMap<String, String>");
+            assert_eq!(map.len(), 4);
+            assert_eq!(map.get("Ruth").map(String::as_str), Some("Babe"));
+            assert_eq!(map.get("Williams").map(String::as_str), Some("Ted"));
+            assert_eq!(map.get("Bonds").map(String::as_str), Some("Barry"));
+            assert_eq!(map.get("Pujols").map(String::as_str), Some("Albert"));
+        }
+
+        {
+            let strings: Vec<String> = vec![];
+            assert!(strings.into_noun().is_null());
+        }
+
+        {
+            let strings = vec![
+                String::from("1"),
+                String::from("2"),
+                String::from("3"),
+                String::from("4"),
+            ];
+            assert_eq!(
+                strings.into_noun(),
+                Noun::from(Cell::from([
+                    Atom::from("1"),
+                    Atom::from("2"),
+                    Atom::from("3"),
+                    Atom::from("4"),
+                    Atom::null(),
+                ]))
+            );
+        }
+
+        // The failure cases of convert!, using the traits instead.
+        {
+            let noun = Noun::from(Cell::from(["no", "null", "terminator"]));
+            assert!(matches!(
+                Vec::<String>::from_noun(&noun)
+                    .as_ref()
+                    .map_err(Error::root_cause),
+                Err(Error::ExpectedNull)
+            ));
+
+            let noun = Noun::from(Cell::from([
+                Noun::from(Cell::from(["unexpected", "cell"])),
+                Noun::null(),
+            ]));
+            assert!(matches!(
+                Vec::<String>::from_noun(&noun)
+                    .as_ref()
+                    .map_err(Error::root_cause),
+                Err(Error::UnexpectedCell)
+            ));
+        }
+
+        // Composite types round trip.
+        {
+            type Composite = Vec<(String, Option<u32>, bool)>;
+            let value: Composite = vec![
+                (String::from("a"), Some(1), true),
+                (String::from("b"), None, false),
+            ];
+            let noun = value.clone().into_noun();
+            assert_eq!(
+                noun,
+                Noun::from(Cell::from([
+                    Noun::from(Cell::from([
+                        Noun::from("a"),
+                        Noun::from(Cell::from([0u8, 1u8])),
+                        Noun::from(0u8),
+                    ])),
+                    Noun::from(Cell::from(
+                        [Noun::from("b"), Noun::null(), Noun::from(1u8),]
+                    )),
+                    Noun::null(),
+                ]))
+            );
+            assert_eq!(Composite::from_noun(&noun).expect("round trip"), value);
+
+            let map = HashMap::from([(1u64, vec![1u8, 2]), (2, vec![]), (3, vec![3])]);
+            let noun = map.clone().into_noun();
+            assert_eq!(HashMap::from_noun(&noun).expect("round trip"), map);
+
+            let tuple = (
+                1u8,
+                2u16,
+                3u32,
+                4u64,
+                5u128,
+                6usize,
+                Noun::null(),
+                Atom::from(8u8),
+            );
+            let noun = tuple.clone().into_noun();
+            assert_eq!(noun, Noun::from(Cell::from([1u8, 2, 3, 4, 5, 6, 0, 8])));
+            assert_eq!(<_>::from_noun(&noun).map_err(|_| ()), Ok(tuple));
+        }
+
+        // Standard types convert into nouns with Noun::from().
+        {
+            assert_eq!(Noun::from(7u8), Noun::from(Atom::from(7u8)));
+            assert_eq!(Noun::from(u128::MAX), Noun::from(Atom::from(u128::MAX)));
+            assert_eq!(Noun::from("~zod"), Noun::from(Atom::from("~zod")));
+            assert_eq!(Noun::from(String::from("~zod")), Noun::from("~zod"));
+            assert_eq!(Noun::from(true), Noun::from(0u8));
+            assert_eq!(Noun::from(false), Noun::from(1u8));
+            assert_eq!(Noun::from(()), Noun::null());
+            assert_eq!(
+                Noun::from(vec!["a", "b"]),
+                Noun::from(Cell::from(["a", "b", ""]))
+            );
+            assert_eq!(Noun::from(Vec::<u8>::new()), Noun::null());
+            assert_eq!(
+                Noun::from(Some(vec![1u8])),
+                Noun::from(Cell::from([0u8, 1u8, 0u8]))
+            );
+        }
+
+        // Chars and unit.
+        {
+            for c in ['a', '\u{e9}', '\u{1f600}', '\0'] {
+                let noun = Noun::from(c);
+                assert_eq!(noun, Noun::from(c.to_string().as_str()));
+                assert_eq!(char::from_noun(&noun).expect("Noun to char"), c);
+            }
+            assert!(matches!(
+                char::from_noun(&Noun::from("ab")),
+                Err(Error::ImplType)
+            ));
+            assert!(<()>::from_noun(&Noun::null()).is_ok());
+            assert!(matches!(
+                <()>::from_noun(&Noun::from(1u8)),
+                Err(Error::ExpectedNull)
+            ));
+        }
+
+        // Mismatched nouns fail.
+        {
+            assert!(matches!(
+                u8::from_noun(&256u16.into_noun()),
+                Err(Error::AtomToUint)
+            ));
+            assert!(matches!(
+                bool::from_noun(&2u8.into_noun()),
+                Err(Error::ImplType)
+            ));
+            assert!(matches!(
+                Option::<u8>::from_noun(&(1u8, 1u8).into_noun()),
+                Err(Error::ExpectedNull)
+            ));
+            assert!(matches!(
+                <(u8, u8, u8)>::from_noun(&(1u8, 2u8).into_noun())
+                    .as_ref()
+                    .map_err(Error::root_cause),
+                Err(Error::UnexpectedAtom)
+            ));
+            assert!(matches!(
+                Cell::from_noun(&1u8.into_noun()),
+                Err(Error::UnexpectedAtom)
+            ));
+        }
+    }
+
+    #[test]
+    fn unit() {
+        // Nested units.
+        {
+            let cases = [
+                (None, Noun::null()),
+                (Some(None), Noun::from(Cell::from([0u8, 0u8]))),
+                (Some(Some(7u8)), Noun::from(Cell::from([0u8, 0u8, 7u8]))),
+                (Some(Some(0u8)), Noun::from(Cell::from([0u8, 0u8, 0u8]))),
+            ];
+            for (unit, noun) in cases {
+                assert_eq!(Noun::from(unit), noun);
+                assert_eq!(unit.into_noun(), noun);
+                assert_eq!(
+                    Option::<Option<u8>>::try_from(&noun).expect("Noun to Option<Option<u8>>"),
+                    unit
+                );
+                assert_eq!(
+                    convert!(&noun => Option<Option<u8>>).expect("Noun to Option<Option<u8>>"),
+                    unit
+                );
+            }
+        }
+
+        // Units of other conversions.
+        {
+            let unit = Some(vec![(String::from("a"), true)]);
+            let noun = Noun::from(unit.clone());
+            assert_eq!(
+                noun,
+                Noun::from(Cell::from([
+                    Noun::null(),
+                    Noun::from(Cell::from([
+                        Noun::from(Cell::from([Atom::from("a"), Atom::from(0u8)])),
+                        Noun::null(),
+                    ])),
+                ]))
+            );
+            assert_eq!(
+                Option::<Vec<(String, bool)>>::from_noun(&noun).expect("round trip"),
+                unit
+            );
+        }
+
+        // Anything other than ~ or [~ t].
+        {
+            for noun in [
+                Noun::from(1u8),
+                Noun::from(Cell::from([1u8, 7u8])),
+                Noun::from(Cell::from([
+                    Noun::from(Cell::from([0u8, 0u8])),
+                    Noun::null(),
+                ])),
+            ] {
+                assert!(matches!(
+                    Option::<u8>::try_from(&noun),
+                    Err(Error::ExpectedNull)
+                ));
+            }
+            assert!(matches!(
+                Option::<u8>::try_from(&Noun::from(Cell::from([0u16, 256u16])))
+                    .as_ref()
+                    .map_err(Error::root_cause),
+                Err(Error::AtomToUint)
+            ));
+        }
+    }
+
+    #[test]
+    fn nested() {
+        /// Converts lines of text into a wall, a list of tapes, where a tape is a list of bytes.
+        fn wall(lines: &[&str]) -> Noun {
+            let tapes: Vec<_> = lines
+                .iter()
+                .map(|line| {
+                    let mut tape: Vec<_> = line
+                        .bytes()
+                        .map(|c| Rc::new(Noun::from(Atom::from(c))))
+                        .collect();
+                    tape.push(Rc::new(Noun::null()));
+                    match tape.len() {
+                        1 => Rc::new(Noun::null()),
+                        _ => Rc::new(Noun::from(Cell::from(tape))),
+                    }
+                })
+                .chain([Rc::new(Noun::null())])
+                .collect();
+            Noun::from(Cell::from(tapes))
+        }
+
+        // Vec<Vec<T>>: a wall round trips.
+        {
+            let lines = ["hello", "", "urbit", "world!"];
+            let noun = wall(&lines);
+            let tapes: Vec<Vec<u8>> = lines.iter().map(|line| line.bytes().collect()).collect();
+            assert_eq!(tapes.clone().into_noun(), noun);
+            assert_eq!(
+                Vec::<Vec<u8>>::from_noun(&noun).expect("Noun to Vec<Vec<u8>>"),
+                tapes
+            );
+            assert_eq!(
+                convert!(&noun => Vec<Vec<u8>>).expect("Noun to Vec<Vec<u8>>"),
+                tapes
+            );
+        }
+
+        // Vec<Vec<T>>: errors name the outer and inner index.
+        {
+            let tape =
+                |line: &str| -> Vec<Noun> { line.bytes().map(IntoNoun::into_noun).collect() };
+            let mut tapes = vec![tape("ab"), tape("cd"), tape("efg")];
+            tapes[2].push(Noun::from(256u16));
+            let noun = tapes.into_noun();
+            let err = Vec::<Vec<u8>>::from_noun(&noun).expect_err("256 isn't a u8");
+            match &err {
+                Error::At { axis, path, source } => {
+                    assert_eq!(*axis, Some(238));
+                    assert_eq!(path, &[PathSegment::Index(2), PathSegment::Index(3)]);
+                    assert!(matches!(**source, Error::AtomToUint));
+                }
+                err => panic!("unexpected error: {:?}", err),
+            }
+            assert_eq!(noun.get(238), Some(&Noun::from(256u16)));
+            assert_eq!(
+                err.to_string(),
+                "the atom is too large to fit in the unsigned integer type at [2][3] (axis 238)"
+            );
+            assert!(matches!(
+                convert!(&noun => Vec<Vec<u8>>),
+                Err(Error::At {
+                    axis: Some(238),
+                    ..
+                })
+            ));
+        }
+
+        // HashMap<K, Vec<V>>.
+        {
+            let map = HashMap::from([
+                (String::from("primes"), vec![2u32, 3, 5, 7]),
+                (String::from("none"), vec![]),
+                (String::from("big"), vec![u32::MAX]),
+            ]);
+            let noun = map.clone().into_noun();
+            assert_eq!(
+                HashMap::<String, Vec<u32>>::from_noun(&noun).expect("round trip"),
+                map
+            );
+            assert_eq!(
+                convert!(&noun => HashMap<String, Vec<u32>>).expect("round trip"),
+                map
+            );
+
+            // The value of the only pair is at [0][1], and its third element at [0][1][2].
+            let noun = HashMap::from([("big", vec![1u64, 2, u64::MAX])]).into_noun();
+            let err = HashMap::<String, Vec<u32>>::from_noun(&noun).expect_err("u64::MAX");
+            assert_eq!(
+                err.to_string(),
+                "the atom is too large to fit in the unsigned integer type at [0][1][2] (axis 46)"
+            );
+            assert_eq!(noun.get(46), Some(&Noun::from(Atom::from(u64::MAX))));
+        }
+
+        // Option<Vec<T>>.
+        {
+            let cases = [
+                (None, Noun::null()),
+                (Some(vec![]), Noun::from(Cell::from([0u8, 0u8]))),
+                (
+                    Some(vec![String::from("a"), String::from("b")]),
+                    Noun::from(Cell::from(["", "a", "b", ""])),
+                ),
+            ];
+            for (unit, noun) in cases {
+                assert_eq!(unit.clone().into_noun(), noun);
+                assert_eq!(
+                    Option::<Vec<String>>::from_noun(&noun).expect("Noun to Option<Vec<String>>"),
+                    unit
+                );
+                assert_eq!(
+                    convert!(&noun => Option<Vec<String>>).expect("Noun to Option<Vec<String>>"),
+                    unit
+                );
+            }
+
+            let noun = Noun::from(Cell::from(["", "a", "b"]));
+            let err = Option::<Vec<String>>::from_noun(&noun).expect_err("no terminator");
+            assert!(matches!(
+                err,
+                Error::At { axis: Some(7), ref path, .. } if path == &[PathSegment::Index(1)]
+            ));
+        }
+    }
+
+    #[test]
+    fn errors() {
+        // A failure nested three cells deep: in the unit of the second field of the second element.
+        {
+            let noun = Noun::from(Cell::from([
+                Noun::from(Cell::from([Noun::from("a"), Noun::null()])),
+                Noun::from(Cell::from([
+                    Noun::from("b"),
+                    Noun::from(Cell::from([0u16, 300u16])),
+                ])),
+                Noun::null(),
+            ]));
+            let err = Vec::<(String, Option<u8>)>::from_noun(&noun).expect_err("300 isn't a u8");
+            match &err {
+                Error::At { axis, path, source } => {
+                    assert_eq!(*axis, Some(27));
+                    assert_eq!(path, &[PathSegment::Index(1), PathSegment::Index(1)]);
+                    assert!(matches!(**source, Error::AtomToUint));
+                }
+                err => panic!("unexpected error: {:?}", err),
+            }
+            assert_eq!(noun.get(27), Some(&Noun::from(300u16)));
+            assert_eq!(
+                err.to_string(),
+                "the atom is too large to fit in the unsigned integer type at [1][1] (axis 27)"
+            );
+            assert!(matches!(
+                std::error::Error::source(&err).and_then(|source| source.downcast_ref::<Error>()),
+                Some(Error::AtomToUint)
+            ));
+
+            // The same failure through convert!.
+            let err = convert!(&noun => Vec<(&str, Option<u8>)>).expect_err("300 isn't a u8");
+            assert!(matches!(err, Error::At { axis: Some(27), .. }));
+        }
+
+        // Keys and values of pairs, and list terminators.
+        {
+            let noun = Noun::from(Cell::from([
+                Noun::from(Cell::from(["vary", "Origin"])),
+                Noun::from(Cell::from([
+                    Noun::from(Cell::from(["not", "an atom"])),
+                    Noun::from("value"),
+                ])),
+                Noun::null(),
+            ]));
+            let err = convert!(&noun => HashMap<&str, &str>).expect_err("cell key");
+            assert_eq!(
+                err.to_string(),
+                "a cell was encountered when an atom was expected at [1][0] (axis 12)"
+            );
+
+            let noun = Noun::from(Cell::from(["a", "b", "c"]));
+            let err = Vec::<String>::from_noun(&noun).expect_err("no terminator");
+            assert!(matches!(
+                err,
+                Error::At { axis: Some(7), ref path, .. } if path == &[PathSegment::Index(2)]
+            ));
+        }
+
+        // Axes too large for a u64 are dropped, but the path is kept.
+        {
+            let mut elems: Vec<_> = (0..80u8)
+                .map(|i| Rc::new(Noun::from(Atom::from(i))))
+                .collect();
+            elems.push(Rc::new(Noun::from(Cell::from([1u8, 2u8]))));
+            elems.push(Rc::new(Noun::null()));
+            let noun = Noun::from(Cell::from(elems));
+            let err = Vec::<u8>::from_noun(&noun).expect_err("cell element");
+            assert!(matches!(
+                err,
+                Error::At { axis: None, ref path, .. } if path == &[PathSegment::Index(80)]
+            ));
+            assert_eq!(
+                err.to_string(),
+                "a cell was encountered when an atom was expected at [80]"
+            );
+        }
+    }
+
+    #[test]
+    fn match_tags() {
+        #[derive(Debug, PartialEq)]
+        enum Sign {
+            PokeAck(Option<String>),
+            WatchAck(Option<String>),
+            Fact(String, Noun),
+        }
+
+        fn parse(noun: &Noun) -> Result<Sign, Error> {
+            match_tags!(noun, {
+                "poke-ack" => |err| Option::from_noun(err).map(Sign::PokeAck),
+                "watch-ack" => |err| Option::from_noun(err).map(Sign::WatchAck),
+                "fact" => |fact| {
+                    let (mark, data) = FromNoun::from_noun(fact)?;
+                    Ok(Sign::Fact(mark, data))
+                },
+                else => |_| Err(Error::UnknownTag),
+            })
+        }
+
+        {
+            let cases = [
+                (
+                    Noun::from(Cell::from([Noun::from("poke-ack"), Noun::null()])),
+                    Sign::PokeAck(None),
+                ),
+                (
+                    Noun::from(Cell::from(["watch-ack", "", "no such path"])),
+                    Sign::WatchAck(Some(String::from("no such path"))),
+                ),
+                (
+                    Noun::from(Cell::from([
+                        Noun::from("fact"),
+                        Noun::from("json"),
+                        Noun::from(Cell::from([1u8, 2u8])),
+                    ])),
+                    Sign::Fact(String::from("json"), Noun::from(Cell::from([1u8, 2u8]))),
+                ),
+            ];
+            for (noun, sign) in cases {
+                assert!(noun.tag().is_some());
+                assert_eq!(parse(&noun).expect("known tag"), sign);
+            }
+        }
+
+        // Unknown tags and nouns that aren't head-tagged fall through to the else arm.
+        {
+            for noun in [
+                Noun::from(Cell::from(["kick", ""])),
+                Noun::from(Cell::from(["fact-", ""])),
+                Noun::from("fact"),
+                Noun::from(Cell::from([
+                    Noun::from(Cell::from(["fact", "json"])),
+                    Noun::null(),
+                ])),
+            ] {
+                assert!(matches!(parse(&noun), Err(Error::UnknownTag)));
+            }
+        }
+
+        // Errors in the arms are passed through.
+        {
+            let noun = Noun::from(Cell::from(["poke-ack", "not null"]));
+            assert!(matches!(parse(&noun), Err(Error::ExpectedNull)));
+        }
+
+        // The else arm gets the whole noun, and there needn't be any tag arms.
+        {
+            let noun = Noun::from(Cell::from(["kick", ""]));
+            assert_eq!(match_tags!(&noun, { else => |noun| noun.clone() }), noun);
+        }
+    }
+
+    #[test]
+    fn pairs_to_noun() {
+        // Round trip through Vec<(K, V)>, in order.
+        {
+            let pairs = [
+                ("vary", "Origin"),
+                ("vary", "Accept-Encoding"),
+                ("date", "today"),
+            ];
+            // Only an Iterator, not a DoubleEndedIterator.
+            let noun = super::pairs_to_noun(pairs.iter().copied().filter(|_| true));
+            assert_eq!(
+                noun,
+                Noun::from(Cell::from([
+                    Noun::from(Cell::from(["vary", "Origin"])),
+                    Noun::from(Cell::from(["vary", "Accept-Encoding"])),
+                    Noun::from(Cell::from(["date", "today"])),
+                    Noun::null(),
+                ]))
+            );
+            assert_eq!(
+                convert!(&noun => Vec<(&str, &str)>).expect("Noun to Vec<(&str, &str)>"),
+                pairs
+            );
+        }
+
+        // Round trip through HashMap<K, V>.
+        {
+            let map = HashMap::from([
+                (String::from("one"), String::from("1")),
+                (String::from("two"), String::from("2")),
+            ]);
+            let noun = super::pairs_to_noun(map.clone());
+            assert_eq!(
+                convert!(&noun => HashMap<String, String>)
+                    .expect("Noun to HashMap<String, String>"),
+                map
+            );
+            assert_eq!(
+                HashMap::<String, String>::from_noun(&map.clone().into_noun()).expect("round trip"),
+                map
+            );
+        }
+
+        // Values that are themselves nouns.
+        {
+            let noun =
+                super::pairs_to_noun([(Atom::from("key"), Noun::from(Cell::from([1u8, 2u8])))]);
+            assert_eq!(
+                noun,
+                Noun::from(Cell::from([
+                    Noun::from(Cell::from([
+                        Noun::from("key"),
+                        Noun::from(Cell::from([1u8, 2u8])),
+                    ])),
+                    Noun::null(),
+                ]))
+            );
+        }
+
+        // An empty iterator is null.
+        {
+            assert!(super::pairs_to_noun(Vec::<(u8, u8)>::new()).is_null());
+            assert!(super::pairs_to_noun(HashMap::<u8, u8>::new()).is_null());
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn par_list_to_vec() {
+        // The parallel conversion gives exactly what the serial one does, errors included.
+        fn check<T: FromNoun + Send + PartialEq + std::fmt::Debug>(noun: &Noun) {
+            match (super::par_list_to_vec::<T>(noun), Vec::<T>::try_from(noun)) {
+                (Ok(par), Ok(serial)) => assert_eq!(par, serial),
+                (Err(par), Err(serial)) => {
+                    assert_eq!(format!("{:?}", par), format!("{:?}", serial))
+                }
+                (par, serial) => panic!("{:?} != {:?}", par, serial),
+            }
+        }
+
+        // A wide list.
+        {
+            let elems: Vec<_> = (0..10_000u32)
+                .map(|i| (i, i.to_string()).into_noun())
+                .collect();
+            let noun = Noun::from(elems);
+            let vec = super::par_list_to_vec::<(u32, String)>(&noun).expect("convert");
+            assert_eq!(vec.len(), 10_000);
+            assert_eq!(vec[1234], (1234, String::from("1234")));
+            check::<(u32, String)>(&noun);
+            // Dropping a long list recursively would overflow the stack.
+            std::mem::forget(noun);
+        }
+
+        // Several elements fail, and the one nearest the front is reported, however the work is
+        // split up.
+        {
+            let elems: Vec<_> = (0..5_000u32)
+                .map(|i| match i {
+                    1_500 | 3_000 | 4_999 => Noun::from(Cell::from([i, i])),
+                    _ => Noun::from(Atom::from(i)),
+                })
+                .collect();
+            let noun = Noun::from(elems);
+            for _ in 0..10 {
+                let err = super::par_list_to_vec::<u32>(&noun).expect_err("cell element");
+                assert!(
+                    matches!(err, Error::At { ref path, .. } if path == &[PathSegment::Index(1_500)])
+                );
+            }
+            check::<u32>(&noun);
+            std::mem::forget(noun);
+        }
+
+        // Lists that aren't null-terminated, with and without an element that fails first.
+        {
+            let cases = [
+                Noun::from(Cell::from([1u8, 2, 3])),
+                Noun::from(Cell::from([
+                    Noun::from(Cell::from([1u8, 2])),
+                    Noun::from(3u8),
+                ])),
+                Noun::from(7u8),
+                Noun::null(),
+            ];
+            for noun in &cases {
+                check::<u8>(noun);
+            }
+            assert!(super::par_list_to_vec::<u8>(&cases[0]).is_err());
+            assert!(super::par_list_to_vec::<u8>(&cases[3])
+                .expect("empty")
+                .is_empty());
+        }
+    }
 }
@@ -0,0 +1,159 @@
+//! Conversions to and from Hoon's `each`.
+//!
+//! `(each a b)` is either `[%.y p=a]` or `[%.n p=b]`. The heads are loobeans, so `%.y` (also written
+//! `%&`) is `0` and `%.n` (`%|`) is `1`: `[0 p]` holds the first type and `[1 p]` the second. It's
+//! commonly used like a [`Result`], as in `(each vase tang)`, and converts to and from one.
+
+use crate::{
+    cell::Cell,
+    convert::{Error, FromNoun, IntoNoun},
+    noun::Noun,
+};
+
+/// A Hoon `(each a b)`: `[%.y p=a]` or `[%.n p=b]`.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{cell::Cell, each::Each, Noun};
+/// let yes: Each<u8, String> = Each::Yes(5);
+/// assert_eq!(Noun::from(yes.clone()), Noun::from(Cell::from([0u8, 5])));
+///
+/// let noun = Noun::from(Cell::from([Noun::from(1u8), Noun::from("bad")]));
+/// let no = Each::<u8, String>::try_from(&noun).unwrap();
+/// assert_eq!(Result::from(no), Err(String::from("bad")));
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Each<A, B> {
+    /// `[%.y p=a]`, whose head is `0`.
+    Yes(A),
+    /// `[%.n p=b]`, whose head is `1`.
+    No(B),
+}
+
+impl<A, B> From<Result<A, B>> for Each<A, B> {
+    fn from(res: Result<A, B>) -> Self {
+        match res {
+            Ok(a) => Self::Yes(a),
+            Err(b) => Self::No(b),
+        }
+    }
+}
+
+impl<A, B> From<Each<A, B>> for Result<A, B> {
+    fn from(each: Each<A, B>) -> Self {
+        match each {
+            Each::Yes(a) => Ok(a),
+            Each::No(b) => Err(b),
+        }
+    }
+}
+
+impl<A: IntoNoun, B: IntoNoun> From<Each<A, B>> for Noun {
+    fn from(each: Each<A, B>) -> Self {
+        let (head, p) = match each {
+            Each::Yes(a) => (true, a.into_noun()),
+            Each::No(b) => (false, b.into_noun()),
+        };
+        Noun::from(Cell::from([Noun::from(head), p]))
+    }
+}
+
+impl<A: FromNoun, B: FromNoun> TryFrom<&Noun> for Each<A, B> {
+    type Error = Error;
+
+    /// Converts `[0 p]` into [`Each::Yes`] and `[1 p]` into [`Each::No`].
+    ///
+    /// Any other head fails with [`UnknownTag`](Error::UnknownTag).
+    fn try_from(noun: &Noun) -> Result<Self, Error> {
+        let Noun::Cell(cell) = noun else {
+            return Err(Error::UnexpectedAtom);
+        };
+        let p = cell.tail_ref();
+        match cell.head_ref() {
+            Noun::Atom(head) if head.is_null() => A::from_noun(p).map(Self::Yes),
+            Noun::Atom(head) if head == &1u8 => B::from_noun(p).map(Self::No),
+            Noun::Atom(_) => return Err(Error::UnknownTag.at(2)),
+            Noun::Cell(_) => return Err(Error::UnexpectedCell.at(2)),
+        }
+        .map_err(|err| err.at(3))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn each() {
+        // [%.y 5] is [0 5], and [%.n 'error'] is [1 'error'].
+        {
+            let cases = [
+                (Each::Yes(5), Noun::from(Cell::from([0u8, 5]))),
+                (Each::Yes(0), Noun::from(Cell::from([0u8, 0]))),
+                (
+                    Each::No(String::from("error")),
+                    Noun::from(Cell::from([Noun::from(1u8), Noun::from("error")])),
+                ),
+                (
+                    Each::No(String::new()),
+                    Noun::from(Cell::from([Noun::from(1u8), Noun::null()])),
+                ),
+            ];
+            for (each, noun) in cases {
+                assert_eq!(Noun::from(each.clone()), noun);
+                assert_eq!(Each::<u32, String>::try_from(&noun).expect("each"), each);
+            }
+        }
+
+        // The polarity matches loobeans and Result.
+        {
+            let yes = Noun::from(Each::<bool, bool>::Yes(true));
+            assert_eq!(yes, Noun::from(Cell::from([0u8, 0])));
+            assert_eq!(
+                Noun::from(Each::<bool, bool>::No(true)),
+                Noun::from(Cell::from([1u8, 0]))
+            );
+            assert_eq!(
+                Result::from(Each::<u8, u8>::try_from(&yes).expect("each")),
+                Ok(0)
+            );
+            assert_eq!(Each::from(Ok::<u8, u8>(7)), Each::Yes(7));
+            assert_eq!(Each::from(Err::<u8, u8>(7)), Each::No(7));
+        }
+
+        // Eaches nest in other conversions.
+        {
+            let events: Vec<Each<u8, String>> = vec![Each::Yes(1), Each::No(String::from("x"))];
+            let noun = events.clone().into_noun();
+            assert_eq!(
+                Vec::<Each<u8, String>>::from_noun(&noun).expect("list"),
+                events
+            );
+        }
+    }
+
+    #[test]
+    fn each_errors() {
+        let cases = [
+            (Noun::from(0u8), None),
+            (Noun::from(Cell::from([2u8, 5])), Some(2)),
+            (
+                Noun::from(Cell::from([
+                    Noun::from(Cell::from([0u8, 0])),
+                    Noun::from(5u8),
+                ])),
+                Some(2),
+            ),
+            (Noun::from(Cell::from([0u8, 0, 5])), Some(3)),
+            (Noun::from(Cell::from([1u8, 0xff])), Some(3)),
+        ];
+        for (noun, axis) in cases {
+            match (Each::<u8, String>::try_from(&noun), axis) {
+                (Err(Error::UnexpectedAtom), None) => {}
+                (Err(Error::At { axis: at, .. }), Some(_)) => assert_eq!(at, axis),
+                (res, _) => panic!("unexpected result: {:?}", res),
+            }
+        }
+    }
+}
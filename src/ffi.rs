@@ -0,0 +1,452 @@
+//! A C interface for cueing, jamming, and taking apart nouns.
+//!
+//! Nouns cross the boundary as opaque `noun_t *` handles, each owning a reference to a noun.
+//! Handles are created by [`noun_atom_new()`], [`noun_cell_new()`], [`noun_clone()`],
+//! [`noun_cue()`], [`noun_head()`], and [`noun_tail()`], and every one of them must be released
+//! with [`noun_free()`]. Cloning a handle or taking the head of a cell doesn't copy the noun.
+//!
+//! Functions that can fail return a [`NounStatus`] and write their results through out pointers,
+//! which are left untouched on failure. Panics never cross the boundary: a function that panics
+//! returns [`NounStatus::Panic`], or `NULL` if it returns a handle.
+//!
+//! The C declarations are in `include/noun.h`, and `tests/ffi/smoke.c` is a small C program that
+//! uses them. Unless the `thread-safe` feature is enabled, a noun and every handle to it must stay
+//! on the thread that created them.
+
+use crate::{
+    atom::{Atom, Iter as AtomIter},
+    cell::Cell,
+    noun::Noun,
+    serdes::{self, CueLimits},
+    Rc,
+};
+use std::{
+    panic::{self, AssertUnwindSafe},
+    ptr, slice,
+};
+
+/// An opaque handle to a noun, `noun_t` in C.
+pub struct NounHandle(Rc<Noun>);
+
+/// The result of a fallible FFI function, `noun_status_t` in C.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum NounStatus {
+    /// The function succeeded, `NOUN_OK`.
+    Ok = 0,
+    /// A required pointer was `NULL`, `NOUN_NULL_POINTER`.
+    NullPointer = 1,
+    /// The bytes aren't a jammed noun, `NOUN_CUE_FAILED`.
+    CueFailed = 2,
+    /// An atom was passed where a cell was expected, `NOUN_UNEXPECTED_ATOM`.
+    UnexpectedAtom = 3,
+    /// A cell was passed where an atom was expected, `NOUN_UNEXPECTED_CELL`.
+    UnexpectedCell = 4,
+    /// The function panicked, `NOUN_PANIC`.
+    Panic = 5,
+}
+
+/// Runs `f`, turning a panic into [`NounStatus::Panic`].
+fn guard(f: impl FnOnce() -> NounStatus) -> NounStatus {
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or(NounStatus::Panic)
+}
+
+/// Runs `f`, turning a panic into `NULL`.
+fn guard_handle(f: impl FnOnce() -> *mut NounHandle) -> *mut NounHandle {
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or(ptr::null_mut())
+}
+
+/// Moves a noun into a new handle.
+fn into_handle(noun: Rc<Noun>) -> *mut NounHandle {
+    Box::into_raw(Box::new(NounHandle(noun)))
+}
+
+/// Returns the bytes at `bytes`, which may be `NULL` if `len` is `0`.
+///
+/// # Safety
+///
+/// `bytes` must be `NULL` or point to `len` readable bytes.
+unsafe fn bytes_from_raw<'a>(bytes: *const u8, len: usize) -> Option<&'a [u8]> {
+    match (bytes.is_null(), len) {
+        (true, 0) => Some(&[]),
+        (true, _) => None,
+        (false, _) => Some(slice::from_raw_parts(bytes, len)),
+    }
+}
+
+/// Creates an atom from `len` bytes, least significant first.
+///
+/// Returns `NULL` if `bytes` is `NULL` and `len` isn't `0`.
+///
+/// # Safety
+///
+/// `bytes` must be `NULL` or point to `len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn noun_atom_new(bytes: *const u8, len: usize) -> *mut NounHandle {
+    guard_handle(|| match bytes_from_raw(bytes, len) {
+        Some(bytes) => into_handle(Rc::new(Noun::from(Atom::from(bytes.to_vec())))),
+        None => ptr::null_mut(),
+    })
+}
+
+/// Creates a cell from a head and a tail, which are shared rather than copied.
+///
+/// Returns `NULL` if either handle is `NULL`. The caller still owns both handles.
+///
+/// # Safety
+///
+/// `head` and `tail` must each be `NULL` or a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn noun_cell_new(
+    head: *const NounHandle,
+    tail: *const NounHandle,
+) -> *mut NounHandle {
+    guard_handle(|| match (head.as_ref(), tail.as_ref()) {
+        (Some(head), Some(tail)) => into_handle(Rc::new(Noun::from(Cell::from([
+            head.0.clone(),
+            tail.0.clone(),
+        ])))),
+        _ => ptr::null_mut(),
+    })
+}
+
+/// Creates another handle to the same noun.
+///
+/// Returns `NULL` if `noun` is `NULL`.
+///
+/// # Safety
+///
+/// `noun` must be `NULL` or a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn noun_clone(noun: *const NounHandle) -> *mut NounHandle {
+    guard_handle(|| match noun.as_ref() {
+        Some(noun) => into_handle(noun.0.clone()),
+        None => ptr::null_mut(),
+    })
+}
+
+/// Releases a handle. Does nothing if `noun` is `NULL`.
+///
+/// # Safety
+///
+/// `noun` must be `NULL` or a live handle, which mustn't be used again.
+#[no_mangle]
+pub unsafe extern "C" fn noun_free(noun: *mut NounHandle) {
+    if !noun.is_null() {
+        let _ = panic::catch_unwind(AssertUnwindSafe(|| drop(Box::from_raw(noun))));
+    }
+}
+
+/// Cues `len` bytes into a noun, writing a new handle to it to `out`.
+///
+/// Bytes following the jammed noun are ignored, even if they have set bits, so a jammed noun can
+/// be cued from the front of a larger buffer.
+///
+/// # Safety
+///
+/// `bytes` must be `NULL` or point to `len` readable bytes, and `out` must be `NULL` or writable.
+#[no_mangle]
+pub unsafe extern "C" fn noun_cue(
+    bytes: *const u8,
+    len: usize,
+    out: *mut *mut NounHandle,
+) -> NounStatus {
+    guard(|| {
+        let Some(bytes) = bytes_from_raw(bytes, len) else {
+            return NounStatus::NullPointer;
+        };
+        if out.is_null() {
+            return NounStatus::NullPointer;
+        }
+        // Unlike cue_bytes(), decode() stops at the end of the jammed noun.
+        match serdes::cue::decode(&mut AtomIter::from_bytes(bytes), &CueLimits::UNLIMITED) {
+            Ok(noun) => {
+                *out = into_handle(Rc::new(noun));
+                NounStatus::Ok
+            }
+            Err(_) => NounStatus::CueFailed,
+        }
+    })
+}
+
+/// Jams a noun, writing a pointer to the bytes to `out_bytes` and their length to `out_len`.
+///
+/// The bytes must be released with [`noun_bytes_free()`].
+///
+/// # Safety
+///
+/// `noun` must be `NULL` or a live handle, and `out_bytes` and `out_len` must each be `NULL` or
+/// writable.
+#[no_mangle]
+pub unsafe extern "C" fn noun_jam(
+    noun: *const NounHandle,
+    out_bytes: *mut *mut u8,
+    out_len: *mut usize,
+) -> NounStatus {
+    guard(|| {
+        let Some(noun) = noun.as_ref() else {
+            return NounStatus::NullPointer;
+        };
+        if out_bytes.is_null() || out_len.is_null() {
+            return NounStatus::NullPointer;
+        }
+        let bytes = noun.0.jam_to_vec().into_boxed_slice();
+        *out_len = bytes.len();
+        *out_bytes = Box::into_raw(bytes).cast::<u8>();
+        NounStatus::Ok
+    })
+}
+
+/// Releases bytes returned by [`noun_jam()`]. Does nothing if `bytes` is `NULL`.
+///
+/// # Safety
+///
+/// `bytes` must be `NULL` or a pointer returned by [`noun_jam()`] along with `len`, which mustn't
+/// be used again.
+#[no_mangle]
+pub unsafe extern "C" fn noun_bytes_free(bytes: *mut u8, len: usize) {
+    if !bytes.is_null() {
+        drop(Box::from_raw(ptr::slice_from_raw_parts_mut(bytes, len)));
+    }
+}
+
+/// Returns `true` if a noun is a cell, and `false` if it's an atom or `noun` is `NULL`.
+///
+/// # Safety
+///
+/// `noun` must be `NULL` or a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn noun_is_cell(noun: *const NounHandle) -> bool {
+    matches!(noun.as_ref(), Some(NounHandle(noun)) if matches!(**noun, Noun::Cell(_)))
+}
+
+/// Returns `true` if two nouns are equal, and `false` if they aren't or either is `NULL`.
+///
+/// # Safety
+///
+/// `a` and `b` must each be `NULL` or a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn noun_equal(a: *const NounHandle, b: *const NounHandle) -> bool {
+    match (a.as_ref(), b.as_ref()) {
+        (Some(a), Some(b)) => panic::catch_unwind(AssertUnwindSafe(|| a.0 == b.0)).unwrap_or(false),
+        _ => false,
+    }
+}
+
+/// Takes the head or tail of a cell into a new handle.
+unsafe fn cell_part(
+    noun: *const NounHandle,
+    out: *mut *mut NounHandle,
+    part: fn(&Cell) -> Rc<Noun>,
+) -> NounStatus {
+    guard(|| {
+        let Some(noun) = noun.as_ref() else {
+            return NounStatus::NullPointer;
+        };
+        if out.is_null() {
+            return NounStatus::NullPointer;
+        }
+        match &*noun.0 {
+            Noun::Cell(cell) => {
+                *out = into_handle(part(cell));
+                NounStatus::Ok
+            }
+            Noun::Atom(_) => NounStatus::UnexpectedAtom,
+        }
+    })
+}
+
+/// Writes a new handle to the head of a cell to `out`.
+///
+/// # Safety
+///
+/// `noun` must be `NULL` or a live handle, and `out` must be `NULL` or writable.
+#[no_mangle]
+pub unsafe extern "C" fn noun_head(
+    noun: *const NounHandle,
+    out: *mut *mut NounHandle,
+) -> NounStatus {
+    cell_part(noun, out, Cell::head)
+}
+
+/// Writes a new handle to the tail of a cell to `out`.
+///
+/// # Safety
+///
+/// `noun` must be `NULL` or a live handle, and `out` must be `NULL` or writable.
+#[no_mangle]
+pub unsafe extern "C" fn noun_tail(
+    noun: *const NounHandle,
+    out: *mut *mut NounHandle,
+) -> NounStatus {
+    cell_part(noun, out, Cell::tail)
+}
+
+/// Writes a pointer to the bytes of an atom, least significant first, to `out_bytes` and their
+/// length to `out_len`.
+///
+/// The bytes belong to the noun and stay valid as long as a handle to it does. The null atom has
+/// no bytes, and `out_bytes` may be set to a dangling pointer that mustn't be read.
+///
+/// # Safety
+///
+/// `noun` must be `NULL` or a live handle, and `out_bytes` and `out_len` must each be `NULL` or
+/// writable.
+#[no_mangle]
+pub unsafe extern "C" fn noun_atom_bytes(
+    noun: *const NounHandle,
+    out_bytes: *mut *const u8,
+    out_len: *mut usize,
+) -> NounStatus {
+    guard(|| {
+        let Some(noun) = noun.as_ref() else {
+            return NounStatus::NullPointer;
+        };
+        if out_bytes.is_null() || out_len.is_null() {
+            return NounStatus::NullPointer;
+        }
+        match &*noun.0 {
+            Noun::Atom(atom) => {
+                let bytes = atom.as_bytes();
+                *out_bytes = bytes.as_ptr();
+                *out_len = bytes.len();
+                NounStatus::Ok
+            }
+            Noun::Cell(_) => NounStatus::UnexpectedCell,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Takes a handle returned by the FFI, failing if it's `NULL`.
+    fn owned(handle: *mut NounHandle) -> *mut NounHandle {
+        assert!(!handle.is_null());
+        handle
+    }
+
+    #[test]
+    fn ffi() {
+        unsafe {
+            // Building [1 0x1234] and jamming and cueing it.
+            {
+                let head = owned(noun_atom_new([1u8].as_ptr(), 1));
+                let tail = owned(noun_atom_new([0x34u8, 0x12, 0, 0].as_ptr(), 4));
+                let cell = owned(noun_cell_new(head, tail));
+                noun_free(head);
+                noun_free(tail);
+                assert!(noun_is_cell(cell));
+
+                let (mut bytes, mut len) = (ptr::null_mut(), 0);
+                assert_eq!(noun_jam(cell, &mut bytes, &mut len), NounStatus::Ok);
+                assert_eq!(
+                    slice::from_raw_parts(bytes, len),
+                    Noun::from(Cell::from([1u16, 0x1234])).jam_to_vec()
+                );
+
+                let mut cued = ptr::null_mut();
+                assert_eq!(noun_cue(bytes, len, &mut cued), NounStatus::Ok);
+                noun_bytes_free(bytes, len);
+                assert!(noun_equal(cell, owned(cued)));
+
+                let copy = owned(noun_clone(cued));
+                noun_free(cued);
+                let mut tail = ptr::null_mut();
+                assert_eq!(noun_tail(copy, &mut tail), NounStatus::Ok);
+                noun_free(copy);
+                let (mut bytes, mut len) = (ptr::null(), 0);
+                assert_eq!(noun_atom_bytes(tail, &mut bytes, &mut len), NounStatus::Ok);
+                assert_eq!(slice::from_raw_parts(bytes, len), [0x34, 0x12]);
+                noun_free(tail);
+
+                let mut head = ptr::null_mut();
+                assert_eq!(noun_head(cell, &mut head), NounStatus::Ok);
+                assert!(!noun_is_cell(head));
+                noun_free(head);
+                noun_free(cell);
+            }
+
+            // Bytes following the jammed noun are ignored, even if they have set bits.
+            {
+                let jammed = [0x09, 0x9b, 0x0c];
+                let mut cued = ptr::null_mut();
+                assert_eq!(
+                    noun_cue(jammed.as_ptr(), jammed.len(), &mut cued),
+                    NounStatus::Ok
+                );
+                let head = owned(noun_atom_new(ptr::null(), 0));
+                let tail = owned(noun_atom_new([19u8].as_ptr(), 1));
+                let expected = owned(noun_cell_new(head, tail));
+                assert!(noun_equal(owned(cued), expected));
+                noun_free(head);
+                noun_free(tail);
+                noun_free(cued);
+                noun_free(expected);
+            }
+
+            // The null atom has no bytes and may be created from a null pointer.
+            {
+                let null = owned(noun_atom_new(ptr::null(), 0));
+                let (mut bytes, mut len) = (ptr::null(), 1);
+                assert_eq!(noun_atom_bytes(null, &mut bytes, &mut len), NounStatus::Ok);
+                assert_eq!(len, 0);
+                noun_free(null);
+            }
+        }
+    }
+
+    #[test]
+    fn ffi_errors() {
+        unsafe {
+            let atom = owned(noun_atom_new([7u8].as_ptr(), 1));
+            let cell = owned(noun_cell_new(atom, atom));
+
+            // Null pointers.
+            {
+                let mut out = ptr::null_mut();
+                assert!(noun_atom_new(ptr::null(), 1).is_null());
+                assert!(noun_cell_new(atom, ptr::null()).is_null());
+                assert!(noun_clone(ptr::null()).is_null());
+                assert_eq!(noun_cue(ptr::null(), 1, &mut out), NounStatus::NullPointer);
+                assert_eq!(
+                    noun_cue([0x01u8].as_ptr(), 1, ptr::null_mut()),
+                    NounStatus::NullPointer
+                );
+                assert_eq!(noun_head(ptr::null(), &mut out), NounStatus::NullPointer);
+                assert_eq!(noun_tail(cell, ptr::null_mut()), NounStatus::NullPointer);
+                assert_eq!(
+                    noun_jam(atom, ptr::null_mut(), &mut 0),
+                    NounStatus::NullPointer
+                );
+                assert!(out.is_null());
+                assert!(!noun_is_cell(ptr::null()));
+                assert!(!noun_equal(atom, ptr::null()));
+                noun_free(ptr::null_mut());
+                noun_bytes_free(ptr::null_mut(), 0);
+            }
+
+            // Nouns of the wrong kind, and bytes that aren't a jammed noun.
+            {
+                let mut out = ptr::null_mut();
+                assert_eq!(noun_head(atom, &mut out), NounStatus::UnexpectedAtom);
+                assert_eq!(noun_tail(atom, &mut out), NounStatus::UnexpectedAtom);
+                let (mut bytes, mut len) = (ptr::null(), 0);
+                assert_eq!(
+                    noun_atom_bytes(cell, &mut bytes, &mut len),
+                    NounStatus::UnexpectedCell
+                );
+                assert_eq!(noun_cue(ptr::null(), 0, &mut out), NounStatus::CueFailed);
+                assert_eq!(
+                    noun_cue([0x00u8].as_ptr(), 1, &mut out),
+                    NounStatus::CueFailed
+                );
+                assert!(out.is_null());
+            }
+
+            noun_free(cell);
+            noun_free(atom);
+        }
+    }
+}
@@ -0,0 +1,355 @@
+//! Saving nouns to files that detect their own corruption.
+//!
+//! A bare jam has no length and no checksum, so a file cut short by a crash, or with a flipped
+//! bit, can cue without error into a different noun. [`save()`] wraps the jam in a header and a
+//! footer, and [`load()`] refuses to cue a file whose length or checksum doesn't match:
+//!
+//! | Bytes    | Contents                                                   |
+//! |----------|------------------------------------------------------------|
+//! | 8        | The magic bytes `nounjam\0`.                               |
+//! | 4        | The version of the format, `1`, little-endian.             |
+//! | 8        | The length `n` of the jam in bytes, little-endian.         |
+//! | `n`      | The jam.                                                   |
+//! | 4        | The mug of the jam as an atom, little-endian.              |
+
+use crate::{
+    atom::{self, Murmur3},
+    noun::Noun,
+    serdes,
+};
+use std::{
+    ffi::OsString,
+    fmt::{self, Display, Formatter},
+    fs::{self, File},
+    io::{self, BufWriter, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+};
+
+/// The magic bytes a file starts with.
+const MAGIC: [u8; 8] = *b"nounjam\0";
+
+/// The version of the format.
+const VERSION: u32 = 1;
+
+/// The length of the header: the magic bytes, the version, and the length of the jam.
+const HEADER_LEN: u64 = 8 + 4 + 8;
+
+/// The length of the footer, the mug of the jam.
+const FOOTER_LEN: u64 = 4;
+
+/// The seed of the mug of an atom.
+const MUG_SEED: u32 = 0xcafe_babe;
+
+/// Errors that occur when loading a noun from a file.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum LoadError {
+    /// The file couldn't be read.
+    Io(io::Error),
+    /// The file doesn't start with the magic bytes, so it wasn't written by [`save()`].
+    Magic,
+    /// The file was written in a version of the format this library doesn't know.
+    Version(u32),
+    /// The file is longer or shorter than its header says, as when a write was cut short.
+    Length {
+        /// The length of the file according to its header, in bytes.
+        expected: u64,
+        /// The length of the file, in bytes.
+        actual: u64,
+    },
+    /// The mug of the jam doesn't match the one in the footer.
+    Checksum {
+        /// The mug in the footer.
+        expected: u32,
+        /// The mug of the jam.
+        actual: u32,
+    },
+    /// The checksum matched, but the jam isn't a jammed noun.
+    Cue(serdes::Error),
+}
+
+impl Display for LoadError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        match self {
+            Self::Io(err) => write!(f, "the file could not be read: {}", err),
+            Self::Magic => write!(f, "the file is not a saved noun"),
+            Self::Version(version) => {
+                write!(f, "version {} of the format is not supported", version)
+            }
+            Self::Length { expected, actual } => write!(
+                f,
+                "the file is {} bytes long, but its header says {}",
+                actual, expected
+            ),
+            Self::Checksum { expected, actual } => write!(
+                f,
+                "the mug of the jam is {:#x}, but the file says {:#x}",
+                actual, expected
+            ),
+            Self::Cue(err) => write!(f, "the file is not a jammed noun: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for LoadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Cue(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for LoadError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<serdes::Error> for LoadError {
+    fn from(err: serdes::Error) -> Self {
+        Self::Cue(err)
+    }
+}
+
+/// Saves a noun to a file, replacing the file if it exists.
+///
+/// The noun is jammed as it's written, into a temporary file next to `path` that's synced to disk
+/// and then renamed over `path`, so `path` is never left partly written: after a crash it holds
+/// either the old contents or the new.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{cell::Cell, fs, Noun};
+/// let noun = Noun::from(Cell::from(["saved", "noun"]));
+/// let path = std::env::temp_dir().join("fs-doctest.noun");
+/// fs::save(&path, &noun).unwrap();
+/// assert_eq!(fs::load(&path).unwrap(), noun);
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+pub fn save(path: impl AsRef<Path>, noun: &Noun) -> io::Result<()> {
+    let path = path.as_ref();
+    let temp = temp_path(path)?;
+    let saved = write(&temp, noun).and_then(|()| fs::rename(&temp, path));
+    if saved.is_err() {
+        let _ = fs::remove_file(&temp);
+    }
+    saved
+}
+
+/// Returns the path of the temporary file [`save()`] writes before renaming it to `path`.
+fn temp_path(path: &Path) -> io::Result<PathBuf> {
+    let Some(name) = path.file_name() else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "the path does not name a file",
+        ));
+    };
+    let mut temp = OsString::from(".");
+    temp.push(name);
+    temp.push(format!(".{}.tmp", std::process::id()));
+    Ok(path.with_file_name(temp))
+}
+
+/// Writes a noun to a new file in the format of [`save()`], and syncs it to disk.
+fn write(path: &Path, noun: &Noun) -> io::Result<()> {
+    let mut file = BufWriter::new(File::create(path)?);
+    file.write_all(&MAGIC)?;
+    file.write_all(&VERSION.to_le_bytes())?;
+    // The length isn't known until the noun has been jammed, so it's filled in afterwards.
+    file.write_all(&0u64.to_le_bytes())?;
+
+    let mut writer = MugWriter {
+        inner: &mut file,
+        hasher: Murmur3::new(MUG_SEED),
+    };
+    let len = noun.jam_to_writer(&mut writer)?;
+    let hash = writer.hasher.finish();
+    let mut mug = (hash >> 31) ^ (hash & 0x7fff_ffff);
+    if mug == 0 {
+        // Hoon's `++mum` tries again with the next seed, which needs the bytes again. This only
+        // happens for one jam in 2^31.
+        mug = atom::mum(MUG_SEED, 0x7fff, &noun.jam_to_vec());
+    }
+    file.write_all(&mug.to_le_bytes())?;
+
+    file.seek(SeekFrom::Start(HEADER_LEN - 8))?;
+    file.write_all(&len.to_le_bytes())?;
+    let file = file.into_inner().map_err(io::IntoInnerError::into_error)?;
+    file.sync_all()
+}
+
+/// A writer that computes the mug of the bytes written through it.
+struct MugWriter<W: Write> {
+    inner: W,
+    hasher: Murmur3,
+}
+
+impl<W: Write> Write for MugWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let len = self.inner.write(buf)?;
+        self.hasher.write(&buf[..len]);
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Loads a noun from a file written by [`save()`].
+///
+/// The whole file is read and its length and checksum are checked before the jam is cued, so a
+/// file that was cut short or corrupted fails rather than cueing into the wrong noun.
+pub fn load(path: impl AsRef<Path>) -> Result<Noun, LoadError> {
+    let bytes = fs::read(path)?;
+    let (header, rest) = bytes.split_at(bytes.len().min(HEADER_LEN as usize));
+    if !header.starts_with(&MAGIC) && !MAGIC.starts_with(header) {
+        return Err(LoadError::Magic);
+    }
+    if header.len() < HEADER_LEN as usize {
+        return Err(LoadError::Length {
+            expected: HEADER_LEN + FOOTER_LEN,
+            actual: bytes.len() as u64,
+        });
+    }
+    let version = u32::from_le_bytes(header[8..12].try_into().expect("4 bytes"));
+    if version != VERSION {
+        return Err(LoadError::Version(version));
+    }
+    let len = u64::from_le_bytes(header[12..20].try_into().expect("8 bytes"));
+    let expected = HEADER_LEN.saturating_add(len).saturating_add(FOOTER_LEN);
+    if expected != bytes.len() as u64 {
+        return Err(LoadError::Length {
+            expected,
+            actual: bytes.len() as u64,
+        });
+    }
+
+    let (jam, footer) = rest.split_at(rest.len() - FOOTER_LEN as usize);
+    let expected = u32::from_le_bytes(footer.try_into().expect("4 bytes"));
+    let actual = atom::mum(MUG_SEED, 0x7fff, jam);
+    if actual != expected {
+        return Err(LoadError::Checksum { expected, actual });
+    }
+    Ok(Noun::cue_bytes(jam)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{atom::Atom, cell::Cell, Rc};
+
+    /// Returns a path in the temporary directory that's unique to this process and `name`.
+    fn temp_file(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("noun-fs-{}-{}", std::process::id(), name))
+    }
+
+    fn sample() -> Noun {
+        let shared = Rc::new(Noun::from(Cell::from(["state", "v1"])));
+        Noun::from(Cell::from([
+            shared.clone(),
+            Rc::new(Noun::from(Atom::from(vec![0x5a; 300]))),
+            shared,
+        ]))
+    }
+
+    #[test]
+    fn save_load() {
+        // Round trips, including of nouns whose jams are a single byte.
+        {
+            let path = temp_file("round-trip");
+            for noun in [sample(), Noun::null(), Noun::from(1u8)] {
+                save(&path, &noun).expect("save");
+                assert_eq!(load(&path).expect("load"), noun);
+            }
+            let bytes = fs::read(&path).expect("read");
+            assert_eq!(bytes.len() as u64, HEADER_LEN + 1 + FOOTER_LEN);
+            assert!(bytes.starts_with(b"nounjam\0\x01\0\0\0\x01\0\0\0\0\0\0\0"));
+            // Saving over a file replaces it and leaves no temporary file behind.
+            let temp = temp_path(&path).expect("temp path");
+            assert!(!temp.exists());
+            fs::remove_file(&path).expect("remove");
+        }
+
+        // The footer is the mug of the jam.
+        {
+            let path = temp_file("footer");
+            let noun = sample();
+            save(&path, &noun).expect("save");
+            let bytes = fs::read(&path).expect("read");
+            let mug = Atom::from(noun.jam_to_vec()).mug();
+            assert!(bytes.ends_with(&mug.to_le_bytes()));
+            fs::remove_file(&path).expect("remove");
+        }
+    }
+
+    #[test]
+    fn load_errors() {
+        let path = temp_file("errors");
+        save(&path, &sample()).expect("save");
+        let saved = fs::read(&path).expect("read");
+
+        // Truncated files, whether cut short in the header, the jam, or the footer.
+        {
+            for len in [0, 5, 19, 20, 30, saved.len() - 1] {
+                fs::write(&path, &saved[..len]).expect("write");
+                let err = load(&path).expect_err("truncated");
+                assert!(matches!(err, LoadError::Length { actual, .. } if actual == len as u64));
+            }
+            let mut extended = saved.clone();
+            extended.push(0);
+            fs::write(&path, &extended).expect("write");
+            assert!(matches!(load(&path), Err(LoadError::Length { .. })));
+        }
+
+        // A flipped bit anywhere in the jam or the footer.
+        {
+            for idx in [
+                HEADER_LEN as usize,
+                saved.len() / 2,
+                saved.len() - 5,
+                saved.len() - 1,
+            ] {
+                let mut corrupted = saved.clone();
+                corrupted[idx] ^= 0x10;
+                fs::write(&path, &corrupted).expect("write");
+                let err = load(&path).expect_err("corrupted");
+                assert!(matches!(err, LoadError::Checksum { .. }), "{}", err);
+            }
+        }
+
+        // The wrong magic, as for a bare jam, and an unknown version.
+        {
+            let bare = sample().jam_to_vec();
+            fs::write(&path, &bare).expect("write");
+            assert!(matches!(load(&path), Err(LoadError::Magic)));
+
+            let mut next_version = saved.clone();
+            next_version[8] = 2;
+            fs::write(&path, &next_version).expect("write");
+            let err = load(&path).expect_err("unknown version");
+            assert_eq!(err.to_string(), "version 2 of the format is not supported");
+        }
+
+        // A checksum that matches bytes that aren't a jammed noun.
+        {
+            let mut bytes = saved[..HEADER_LEN as usize].to_vec();
+            bytes[12..20].copy_from_slice(&1u64.to_le_bytes());
+            bytes.push(0x03);
+            bytes.extend(atom::mum(MUG_SEED, 0x7fff, &[0x03]).to_le_bytes());
+            fs::write(&path, &bytes).expect("write");
+            assert!(matches!(load(&path), Err(LoadError::Cue(_))));
+        }
+
+        // A file that doesn't exist.
+        {
+            fs::remove_file(&path).expect("remove");
+            assert!(
+                matches!(load(&path), Err(LoadError::Io(err)) if err.kind() == io::ErrorKind::NotFound)
+            );
+        }
+    }
+}
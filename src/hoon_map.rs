@@ -0,0 +1,590 @@
+//! Conversions to and from Hoon maps.
+//!
+//! A Hoon map, `(map k v)`, isn't a list of pairs but a treap: a binary tree of nodes
+//! `[n=[k v] l r]`, where `~` is the empty tree, that is a search tree by one order on its keys and
+//! a heap by another.
+//!
+//! - From left to right, keys are ordered by `++gor`: by [mug](crate::Noun::mug()), and by value if
+//!   the mugs collide. Every key in `l` comes before `k`, and every key in `r` comes after it.
+//! - From top to bottom, keys are ordered by `++mor`: by the mug of the mug, and by value if those
+//!   collide. `k` comes before the keys at the roots of `l` and `r`.
+//!
+//! Both orders are total, so a set of keys has exactly one treap. [`to_map_noun()`] builds that
+//! treap, which is the map Hoon would build from the same entries, and [`from_map_noun()`] checks
+//! that a noun is that treap before reading it.
+//!
+//! Keys compare by value the way `++dor` compares them: an atom comes before a cell, atoms compare
+//! numerically, and cells compare by their heads and then by their tails.
+
+use crate::{
+    atom::Atom,
+    axis::peg,
+    cell::Cell,
+    convert::{pair_from_noun, Error, FromNoun, IntoNoun, PathSegment},
+    noun::Noun,
+    Rc,
+};
+use std::cmp::Ordering;
+
+/// Converts entries into the Hoon map `(map k v)` of them.
+///
+/// If a key occurs more than once, the last entry with that key wins, as with Hoon's `++put:by`.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{atom::Atom, cell::Cell, hoon_map, Noun};
+/// let noun = hoon_map::to_map_noun([(1u8, "one"), (2, "two"), (1, "uno")]);
+/// let entry = |key: u8, val: &str| {
+///     Noun::from(Cell::from([Noun::from(Atom::from(key)), Noun::from(Atom::from(val))]))
+/// };
+/// assert_eq!(
+///     noun,
+///     Noun::from(Cell::from([
+///         entry(2, "two"),
+///         Noun::from(Cell::from([entry(1, "uno"), Noun::null(), Noun::null()])),
+///         Noun::null(),
+///     ]))
+/// );
+/// ```
+pub fn to_map_noun<K, V>(entries: impl IntoIterator<Item = (K, V)>) -> Noun
+where
+    K: IntoNoun,
+    V: IntoNoun,
+{
+    let items = entries
+        .into_iter()
+        .map(|(key, val)| {
+            let key = Rc::new(key.into_noun());
+            let entry = Cell::from([key.clone(), Rc::new(val.into_noun())]);
+            Item::new(key, Rc::new(Noun::from(entry)))
+        })
+        .collect();
+    treap_into_noun(items)
+}
+
+/// Converts a Hoon map into its entries, in order from left to right.
+///
+/// Fails with [`Error::InvalidTreap`] if the noun isn't the treap Hoon would build for its keys,
+/// which includes a treap with a repeated key. Use [`from_map_noun_unvalidated()`] to read any
+/// treap.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{cell::Cell, convert::Error, hoon_map, Noun};
+/// let noun = hoon_map::to_map_noun([("a", 1u8), ("b", 2)]);
+/// assert_eq!(
+///     hoon_map::from_map_noun::<String, u8>(&noun).unwrap(),
+///     [(String::from("b"), 2), (String::from("a"), 1)]
+/// );
+///
+/// // Hoon puts `'b'` at the root of this map, not `'a'`.
+/// let noun = Noun::from(Cell::from([
+///     Noun::from(Cell::from(["a", "1"])),
+///     Noun::null(),
+///     Noun::from(Cell::from([
+///         Noun::from(Cell::from(["b", "2"])),
+///         Noun::null(),
+///         Noun::null(),
+///     ])),
+/// ]));
+/// let err = hoon_map::from_map_noun::<String, String>(&noun).unwrap_err();
+/// assert!(matches!(err, Error::At { axis: Some(7), .. }));
+/// assert!(matches!(err.root_cause(), Error::InvalidTreap));
+/// assert!(hoon_map::from_map_noun_unvalidated::<String, String>(&noun).is_ok());
+/// ```
+pub fn from_map_noun<K: FromNoun, V: FromNoun>(noun: &Noun) -> Result<Vec<(K, V)>, Error> {
+    map_from_noun(noun, true)
+}
+
+/// Converts any treap `[n=[k v] l r]` into its entries, in order from left to right, without
+/// checking that it's ordered the way Hoon orders its maps.
+pub fn from_map_noun_unvalidated<K: FromNoun, V: FromNoun>(
+    noun: &Noun,
+) -> Result<Vec<(K, V)>, Error> {
+    map_from_noun(noun, false)
+}
+
+fn map_from_noun<K: FromNoun, V: FromNoun>(
+    noun: &Noun,
+    validate: bool,
+) -> Result<Vec<(K, V)>, Error> {
+    treap_from_noun(noun, validate.then_some(map_key))?
+        .into_iter()
+        .enumerate()
+        .map(|(index, node)| {
+            pair_from_noun(node.entry, K::from_noun, V::from_noun)
+                .map_err(|err| err.nest(Some(PathSegment::Index(index)), node.axis(2)))
+        })
+        .collect()
+}
+
+/// Returns the key of an entry `[k v]` of a map.
+fn map_key(entry: &Noun) -> Option<&Noun> {
+    match entry {
+        Noun::Atom(_) => None,
+        Noun::Cell(entry) => Some(entry.head_ref()),
+    }
+}
+
+/// An entry on its way into a treap.
+pub(crate) struct Item {
+    key: Rc<Noun>,
+    /// The noun stored at the node, `n`.
+    entry: Rc<Noun>,
+    mug: u32,
+    priority: u32,
+}
+
+impl Item {
+    /// Creates an item for `entry`, ordered by `key`.
+    pub(crate) fn new(key: Rc<Noun>, entry: Rc<Noun>) -> Self {
+        let mug = key.mug();
+        Self {
+            key,
+            entry,
+            mug,
+            priority: Atom::from(mug).mug(),
+        }
+    }
+
+    fn key(&self) -> Key<'_> {
+        Key {
+            noun: &self.key,
+            mug: self.mug,
+            priority: self.priority,
+        }
+    }
+}
+
+/// Converts items into the treap Hoon would build from them, keeping the last of the items with
+/// the same key.
+pub(crate) fn treap_into_noun(mut items: Vec<Item>) -> Noun {
+    // Sorting is stable, so reversing first puts the last of the items with the same key first,
+    // which is the one `dedup_by()` keeps.
+    items.reverse();
+    items.sort_by(|a, b| gor(&a.key(), &b.key()));
+    items.dedup_by(|a, b| dor(&a.key, &b.key).is_eq());
+    treap(&items)
+}
+
+/// Converts items sorted by `++gor` into a treap whose root is the item first in `++mor` order.
+///
+/// The depth of the treap is logarithmic in the number of items unless the mugs of the keys are
+/// chosen to make it otherwise, so this recurses.
+fn treap(items: &[Item]) -> Noun {
+    let root = (0..items.len()).min_by(|&i, &j| mor(&items[i].key(), &items[j].key()));
+    let Some(root) = root else {
+        return Noun::null();
+    };
+    Noun::from(Cell::from([
+        items[root].entry.clone(),
+        Rc::new(treap(&items[..root])),
+        Rc::new(treap(&items[root + 1..])),
+    ]))
+}
+
+/// A node of a treap read from a noun.
+pub(crate) struct Node<'a> {
+    /// The noun stored at the node, `n`.
+    pub(crate) entry: &'a Noun,
+    /// The axis of the node within the treap, or [`None`] if it doesn't fit in a `u64`.
+    axis: Option<u64>,
+}
+
+impl Node<'_> {
+    /// Returns the axis of the noun at `axis` within this node relative to the treap, or [`None`]
+    /// if it doesn't fit in a `u64`.
+    pub(crate) fn axis(&self, axis: u64) -> Option<u64> {
+        self.axis.and_then(|outer| peg(outer, axis))
+    }
+}
+
+/// A subtree of a treap waiting to be read, along with the keys that bound it.
+struct Subtree<'a> {
+    noun: &'a Noun,
+    axis: Option<u64>,
+    /// The key every key in the subtree comes after.
+    after: Option<Key<'a>>,
+    /// The key every key in the subtree comes before.
+    before: Option<Key<'a>>,
+    /// The key of the node whose child the subtree is.
+    parent: Option<Key<'a>>,
+}
+
+/// Reads the nodes of a treap `[n l r]` in order from left to right.
+///
+/// If `key` is given, the treap is checked against the orders Hoon keeps its treaps in, using
+/// `key` to get the key of each entry (or [`None`] if the entry has no key).
+pub(crate) fn treap_from_noun<'a>(
+    noun: &'a Noun,
+    key: Option<fn(&Noun) -> Option<&Noun>>,
+) -> Result<Vec<Node<'a>>, Error> {
+    let mut nodes = Vec::new();
+    // Reads the treap in order without recursing, since a treap that isn't Hoon's can be
+    // arbitrarily deep.
+    let mut rights: Vec<(Node, Subtree)> = Vec::new();
+    let mut next = Some(Subtree {
+        noun,
+        axis: Some(1),
+        after: None,
+        before: None,
+        parent: None,
+    });
+    loop {
+        if let Some(subtree) = next.take() {
+            let node = match subtree.noun {
+                Noun::Atom(atom) if atom.is_null() => None,
+                Noun::Atom(_) => return Err(Error::ExpectedNull.nest(None, subtree.axis)),
+                Noun::Cell(node) => Some(node),
+            };
+            if let Some(node) = node {
+                let at = |axis| subtree.axis.and_then(|outer| peg(outer, axis));
+                let Noun::Cell(branches) = node.tail_ref() else {
+                    return Err(Error::UnexpectedAtom.nest(None, at(3)));
+                };
+                let entry = node.head_ref();
+                let key = match key {
+                    Some(key) => {
+                        let key = key(entry).ok_or(Error::UnexpectedAtom.nest(None, at(2)))?;
+                        let key = Key::new(key);
+                        let ordered = subtree.after.is_none_or(|after| gor(&after, &key).is_lt())
+                            && subtree
+                                .before
+                                .is_none_or(|before| gor(&key, &before).is_lt())
+                            && subtree
+                                .parent
+                                .is_none_or(|parent| mor(&parent, &key).is_lt());
+                        if !ordered {
+                            return Err(Error::InvalidTreap.nest(None, subtree.axis));
+                        }
+                        Some(key)
+                    }
+                    None => None,
+                };
+                next = Some(Subtree {
+                    noun: branches.head_ref(),
+                    axis: at(6),
+                    after: subtree.after,
+                    before: key.or(subtree.before),
+                    parent: key,
+                });
+                rights.push((
+                    Node {
+                        entry,
+                        axis: subtree.axis,
+                    },
+                    Subtree {
+                        noun: branches.tail_ref(),
+                        axis: at(7),
+                        after: key.or(subtree.after),
+                        before: subtree.before,
+                        parent: key,
+                    },
+                ));
+                continue;
+            }
+        }
+        let Some((node, right)) = rights.pop() else {
+            return Ok(nodes);
+        };
+        nodes.push(node);
+        next = Some(right);
+    }
+}
+
+/// A key of a treap along with the mugs that order it.
+#[derive(Clone, Copy)]
+struct Key<'a> {
+    noun: &'a Noun,
+    /// The mug of the key, which orders the treap from left to right.
+    mug: u32,
+    /// The mug of the mug of the key, which orders the treap from top to bottom.
+    priority: u32,
+}
+
+impl<'a> Key<'a> {
+    fn new(noun: &'a Noun) -> Self {
+        let mug = noun.mug();
+        Self {
+            noun,
+            mug,
+            priority: Atom::from(mug).mug(),
+        }
+    }
+}
+
+/// Hoon's `++gor`: by mug, and by value if the mugs collide.
+fn gor(a: &Key, b: &Key) -> Ordering {
+    a.mug.cmp(&b.mug).then_with(|| dor(a.noun, b.noun))
+}
+
+/// Hoon's `++mor`: by the mug of the mug, and by value if those collide.
+fn mor(a: &Key, b: &Key) -> Ordering {
+    a.priority
+        .cmp(&b.priority)
+        .then_with(|| dor(a.noun, b.noun))
+}
+
+/// Hoon's `++dor`: an atom comes before a cell, atoms compare by value, and cells compare by their
+/// heads and then by their tails.
+pub(crate) fn dor(a: &Noun, b: &Noun) -> Ordering {
+    let mut stack = vec![(a, b)];
+    while let Some((a, b)) = stack.pop() {
+        let ordering = match (a, b) {
+            (Noun::Atom(a), Noun::Atom(b)) => cmp_atoms(a, b),
+            (Noun::Atom(_), Noun::Cell(_)) => Ordering::Less,
+            (Noun::Cell(_), Noun::Atom(_)) => Ordering::Greater,
+            (Noun::Cell(a), Noun::Cell(b)) => {
+                stack.push((a.tail_ref(), b.tail_ref()));
+                stack.push((a.head_ref(), b.head_ref()));
+                continue;
+            }
+        };
+        if ordering.is_ne() {
+            return ordering;
+        }
+    }
+    Ordering::Equal
+}
+
+/// Compares two atoms by value.
+fn cmp_atoms(a: &Atom, b: &Atom) -> Ordering {
+    a.bit_len_u64()
+        .cmp(&b.bit_len_u64())
+        .then_with(|| a.as_bytes().iter().rev().cmp(b.as_bytes().iter().rev()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    /// Returns the entry `[key val]` of a map.
+    fn entry(key: impl IntoNoun, val: impl IntoNoun) -> Noun {
+        Noun::from(Cell::from([key.into_noun(), val.into_noun()]))
+    }
+
+    /// Returns the treap node `[entry left right]`.
+    fn node(entry: Noun, left: Noun, right: Noun) -> Noun {
+        Noun::from(Cell::from([entry, left, right]))
+    }
+
+    /// Returns a leaf of a treap, `[entry ~ ~]`.
+    fn leaf(entry: Noun) -> Noun {
+        node(entry, Noun::null(), Noun::null())
+    }
+
+    #[test]
+    fn map() {
+        // The treaps Hoon builds for a handful of key sets.
+        {
+            let ud = |key: u8| entry(key, u32::from(key) * 10);
+            let cases = [
+                (
+                    // (malt (limo ~[[1 10] [2 20] [3 30] [4 40] [5 50]]))
+                    to_map_noun((1..=5u8).map(|key| (key, u32::from(key) * 10))),
+                    node(
+                        ud(5),
+                        Noun::null(),
+                        node(ud(4), node(ud(2), leaf(ud(1)), leaf(ud(3))), Noun::null()),
+                    ),
+                ),
+                (
+                    // (malt (limo ~[[0 0] [1 10] ... [9 90]]))
+                    to_map_noun((0..10u8).map(|key| (key, u32::from(key) * 10))),
+                    node(
+                        ud(6),
+                        node(ud(8), Noun::null(), node(ud(7), leaf(ud(5)), Noun::null())),
+                        node(
+                            ud(0),
+                            node(ud(2), node(ud(9), Noun::null(), leaf(ud(1))), leaf(ud(3))),
+                            leaf(ud(4)),
+                        ),
+                    ),
+                ),
+                (
+                    // (malt (limo ~[['a' 'A'] ['b' 'B'] ['c' 'C'] ['d' 'D'] ['e' 'E']]))
+                    to_map_noun([("a", "A"), ("b", "B"), ("c", "C"), ("d", "D"), ("e", "E")]),
+                    node(
+                        entry("b", "B"),
+                        leaf(entry("e", "E")),
+                        node(
+                            entry("d", "D"),
+                            Noun::null(),
+                            node(entry("c", "C"), leaf(entry("a", "A")), Noun::null()),
+                        ),
+                    ),
+                ),
+                (
+                    // (malt (limo ~[[[1 2] 'x'] [[3 4] 'y'] [5 'z'] [0 'w']]))
+                    to_map_noun([
+                        (Noun::from(Cell::from([1u8, 2u8])), "x"),
+                        (Noun::from(Cell::from([3u8, 4u8])), "y"),
+                        (Noun::from(5u8), "z"),
+                        (Noun::null(), "w"),
+                    ]),
+                    node(
+                        entry(Cell::from([3u8, 4u8]), "y"),
+                        Noun::null(),
+                        node(
+                            entry(0u8, "w"),
+                            node(
+                                entry(5u8, "z"),
+                                Noun::null(),
+                                leaf(entry(Cell::from([1u8, 2u8]), "x")),
+                            ),
+                            Noun::null(),
+                        ),
+                    ),
+                ),
+                (to_map_noun(Vec::<(u8, u8)>::new()), Noun::null()),
+            ];
+            for (noun, fixture) in cases {
+                assert_eq!(noun, fixture);
+                assert!(from_map_noun::<Noun, Noun>(&fixture).is_ok());
+            }
+        }
+
+        // Entries are read from left to right.
+        {
+            let noun = to_map_noun((1..=5u8).map(|key| (key, u32::from(key) * 10)));
+            assert_eq!(
+                from_map_noun::<u8, u32>(&noun).expect("map"),
+                [(5, 50), (1, 10), (2, 20), (3, 30), (4, 40)]
+            );
+        }
+
+        // The treap doesn't depend on the order of the entries, and the last of a repeated key
+        // wins.
+        {
+            let entries: Vec<_> = (0..1000u16).map(|key| (key, key % 7)).collect();
+            let noun = to_map_noun(entries.iter().copied());
+            assert_eq!(noun, to_map_noun(entries.iter().rev().copied()));
+            assert_eq!(
+                noun,
+                to_map_noun(
+                    entries
+                        .iter()
+                        .map(|&(key, _)| (key, 100u16))
+                        .chain(entries.iter().copied())
+                )
+            );
+            let map: HashMap<_, _> = from_map_noun::<u16, u16>(&noun)
+                .expect("map")
+                .into_iter()
+                .collect();
+            assert_eq!(map, entries.into_iter().collect());
+        }
+
+        // Any treap is read without validation.
+        {
+            let noun = node(entry(1u8, 10u8), Noun::null(), leaf(entry(1u8, 20u8)));
+            assert_eq!(
+                from_map_noun_unvalidated::<u8, u8>(&noun).expect("treap"),
+                [(1, 10), (1, 20)]
+            );
+        }
+    }
+
+    #[test]
+    fn map_errors() {
+        // Treaps Hoon wouldn't build. The treap Hoon builds for 1, 2, and 3 is
+        // [[2 20] [[1 10] ~ ~] [[3 30] ~ ~]].
+        {
+            let ud = |key: u8| entry(key, key * 10);
+            let cases = [
+                // Out of order.
+                (node(ud(2), leaf(ud(3)), leaf(ud(1))), 6),
+                // Out of order with a grandparent.
+                (
+                    node(ud(2), leaf(ud(1)), node(ud(3), leaf(ud(1)), Noun::null())),
+                    30,
+                ),
+                // Not a heap.
+                (
+                    node(ud(1), Noun::null(), node(ud(2), Noun::null(), leaf(ud(3)))),
+                    7,
+                ),
+                // A repeated key.
+                (node(ud(2), leaf(ud(2)), leaf(ud(3))), 6),
+            ];
+            for (noun, axis) in cases {
+                match from_map_noun::<u8, u8>(&noun) {
+                    Err(Error::At {
+                        axis: Some(at),
+                        path,
+                        source,
+                    }) => {
+                        assert_eq!(at, axis);
+                        assert!(path.is_empty());
+                        assert!(matches!(*source, Error::InvalidTreap));
+                    }
+                    res => panic!("unexpected result: {:?}", res),
+                }
+                assert!(from_map_noun_unvalidated::<u8, u8>(&noun).is_ok());
+            }
+        }
+
+        // Nouns that aren't treaps.
+        {
+            let cases = [
+                (Noun::from(5u8), 1),
+                (Noun::from(Cell::from([entry(1u8, 10u8), Noun::null()])), 3),
+                (leaf(Noun::from(1u8)), 2),
+                (node(entry(1u8, 10u8), Noun::from(1u8), Noun::null()), 6),
+            ];
+            for (noun, axis) in cases {
+                let res = from_map_noun::<u8, u8>(&noun);
+                assert!(matches!(res, Err(Error::At { axis: Some(at), .. }) if at == axis));
+            }
+        }
+
+        // Entries that fail to convert report their axis.
+        {
+            let noun = to_map_noun([(1u8, 10u16), (2, 20), (3, 300)]);
+            match from_map_noun::<u8, u8>(&noun) {
+                Err(Error::At { axis, path, source }) => {
+                    assert_eq!(axis, Some(29));
+                    assert_eq!(path, [PathSegment::Index(2), PathSegment::Index(1)]);
+                    assert!(matches!(*source, Error::AtomToUint));
+                }
+                res => panic!("unexpected result: {:?}", res),
+            }
+            assert_eq!(noun.get(29), Some(&Noun::from(300u16)));
+        }
+    }
+
+    #[test]
+    fn dor() {
+        let cases = [
+            (Noun::null(), Noun::from(1u8), Ordering::Less),
+            (Noun::from(256u16), Noun::from(255u8), Ordering::Greater),
+            (
+                Noun::from(Atom::from(u128::MAX)),
+                Noun::from(Cell::from([0u8, 0u8])),
+                Ordering::Less,
+            ),
+            (
+                Noun::from(Cell::from([1u8, 9u8])),
+                Noun::from(Cell::from([2u8, 0u8])),
+                Ordering::Less,
+            ),
+            (
+                Noun::from(Cell::from([1u8, 9u8])),
+                Noun::from(Cell::from([1u8, 8u8])),
+                Ordering::Greater,
+            ),
+            (
+                Noun::from(Cell::from(["a", "b", "c"])),
+                Noun::from(Cell::from(["a", "b", "c"])),
+                Ordering::Equal,
+            ),
+        ];
+        for (a, b, ordering) in cases {
+            assert_eq!(super::dor(&a, &b), ordering);
+            assert_eq!(super::dor(&b, &a), ordering.reverse());
+        }
+    }
+}
@@ -0,0 +1,205 @@
+//! Conversions to and from Hoon sets.
+//!
+//! A Hoon set, `(set t)`, is a treap of nodes `[n=t l r]` ordered the same way as a
+//! [Hoon map](crate::hoon_map), with the elements of the set in place of the keys of the map.
+
+use crate::{
+    convert::{Error, FromNoun, IntoNoun, PathSegment},
+    hoon_map::{treap_from_noun, treap_into_noun, Item},
+    noun::Noun,
+    Rc,
+};
+
+/// Converts elements into the Hoon set `(set t)` of them.
+///
+/// A repeated element is only added once, as with Hoon's `++put:in`.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{atom::Atom, cell::Cell, hoon_set, Noun};
+/// // [%b ~ %c [%a ~ ~] ~]
+/// let noun = hoon_set::to_set_noun(["a", "b", "c", "a"]);
+/// assert_eq!(
+///     noun,
+///     Noun::from(Cell::from([
+///         Noun::from(Atom::from("b")),
+///         Noun::null(),
+///         Noun::from(Atom::from("c")),
+///         Noun::from(Cell::from(["a", "", ""])),
+///         Noun::null(),
+///     ]))
+/// );
+/// ```
+pub fn to_set_noun<T: IntoNoun>(elems: impl IntoIterator<Item = T>) -> Noun {
+    let items = elems
+        .into_iter()
+        .map(|elem| {
+            let elem = Rc::new(elem.into_noun());
+            Item::new(elem.clone(), elem)
+        })
+        .collect();
+    treap_into_noun(items)
+}
+
+/// Converts a Hoon set into its elements, in order from left to right.
+///
+/// Fails with [`Error::InvalidTreap`] if the noun isn't the treap Hoon would build for its
+/// elements, which includes a treap with a repeated element. Use [`from_set_noun_unvalidated()`]
+/// to read any treap.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{hoon_set, Noun};
+/// # use std::collections::HashSet;
+/// let noun = hoon_set::to_set_noun(1..=3u8);
+/// assert_eq!(hoon_set::from_set_noun::<u8>(&noun).unwrap(), [1, 2, 3]);
+///
+/// let set: HashSet<u8> = hoon_set::from_set_noun(&noun).unwrap().into_iter().collect();
+/// assert_eq!(set, HashSet::from([1, 2, 3]));
+/// ```
+pub fn from_set_noun<T: FromNoun>(noun: &Noun) -> Result<Vec<T>, Error> {
+    set_from_noun(noun, true)
+}
+
+/// Converts any treap `[n=t l r]` into its elements, in order from left to right, without checking
+/// that it's ordered the way Hoon orders its sets.
+pub fn from_set_noun_unvalidated<T: FromNoun>(noun: &Noun) -> Result<Vec<T>, Error> {
+    set_from_noun(noun, false)
+}
+
+fn set_from_noun<T: FromNoun>(noun: &Noun, validate: bool) -> Result<Vec<T>, Error> {
+    treap_from_noun(noun, validate.then_some(set_key))?
+        .into_iter()
+        .enumerate()
+        .map(|(index, node)| {
+            T::from_noun(node.entry)
+                .map_err(|err| err.nest(Some(PathSegment::Index(index)), node.axis(2)))
+        })
+        .collect()
+}
+
+/// Returns the key of an element of a set, which is the element itself.
+fn set_key(elem: &Noun) -> Option<&Noun> {
+    Some(elem)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cell::Cell;
+    use std::collections::HashSet;
+
+    /// Returns the treap node `[elem left right]`.
+    fn node(elem: impl IntoNoun, left: Noun, right: Noun) -> Noun {
+        Noun::from(Cell::from([elem.into_noun(), left, right]))
+    }
+
+    /// Returns a leaf of a treap, `[elem ~ ~]`.
+    fn leaf(elem: impl IntoNoun) -> Noun {
+        node(elem, Noun::null(), Noun::null())
+    }
+
+    #[test]
+    fn set() {
+        // The treaps Hoon builds for a handful of sets.
+        {
+            let cases = [
+                (
+                    // (silt (limo ~[1 2 3 4 5 6 7]))
+                    to_set_noun(1..=7u8),
+                    node(
+                        6u8,
+                        node(7u8, leaf(5u8), Noun::null()),
+                        node(4u8, node(2u8, leaf(1u8), leaf(3u8)), Noun::null()),
+                    ),
+                ),
+                (
+                    // (silt (limo ~[~zod ~nec ~bud ~wes]))
+                    to_set_noun(["~zod", "~nec", "~bud", "~wes"]),
+                    node(
+                        "~zod",
+                        node("~bud", leaf("~nec"), leaf("~wes")),
+                        Noun::null(),
+                    ),
+                ),
+                (to_set_noun(Vec::<u8>::new()), Noun::null()),
+            ];
+            for (noun, fixture) in cases {
+                assert_eq!(noun, fixture);
+                assert!(from_set_noun::<Noun>(&fixture).is_ok());
+            }
+        }
+
+        // Elements are read from left to right.
+        {
+            let noun = to_set_noun(1..=7u8);
+            assert_eq!(
+                from_set_noun::<u8>(&noun).expect("set"),
+                [5, 7, 6, 1, 2, 3, 4]
+            );
+        }
+
+        // The treap doesn't depend on the order of the elements, and repeated elements are only
+        // added once.
+        {
+            let noun = to_set_noun(0..1000u16);
+            assert_eq!(noun, to_set_noun((0..1000u16).rev()));
+            assert_eq!(noun, to_set_noun((0..1000u16).chain(0..1000u16)));
+            let set: HashSet<u16> = from_set_noun(&noun).expect("set").into_iter().collect();
+            assert_eq!(set, (0..1000u16).collect());
+        }
+
+        // Sets of cells.
+        {
+            let elems = [
+                Noun::from(Cell::from([1u8, 2u8])),
+                Noun::from(Cell::from([3u8, 4u8])),
+                Noun::from(5u8),
+            ];
+            let noun = to_set_noun(elems.clone());
+            let mut read = from_set_noun::<Noun>(&noun).expect("set");
+            read.sort_by(crate::hoon_map::dor);
+            assert_eq!(read, [elems[2].clone(), elems[0].clone(), elems[1].clone()]);
+        }
+    }
+
+    #[test]
+    fn set_errors() {
+        // Treaps Hoon wouldn't build. The treap Hoon builds for 1, 2, and 3 is [2 [1 ~ ~] [3 ~ ~]].
+        {
+            let cases = [
+                (node(2u8, leaf(3u8), leaf(1u8)), 6),
+                (
+                    node(1u8, Noun::null(), node(2u8, Noun::null(), leaf(3u8))),
+                    7,
+                ),
+                (node(2u8, leaf(1u8), leaf(2u8)), 7),
+            ];
+            for (noun, axis) in cases {
+                let res = from_set_noun::<u8>(&noun);
+                assert!(matches!(
+                    res.as_ref().map_err(Error::root_cause),
+                    Err(Error::InvalidTreap)
+                ));
+                assert!(matches!(res, Err(Error::At { axis: Some(at), .. }) if at == axis));
+                assert!(from_set_noun_unvalidated::<u8>(&noun).is_ok());
+            }
+        }
+
+        // Elements that fail to convert report their axis.
+        {
+            let noun = to_set_noun([1u16, 2, 300]);
+            match from_set_noun::<u8>(&noun) {
+                Err(Error::At { axis, path, source }) => {
+                    assert_eq!(axis, Some(2));
+                    assert_eq!(path, [PathSegment::Index(0)]);
+                    assert!(matches!(*source, Error::AtomToUint));
+                }
+                res => panic!("unexpected result: {:?}", res),
+            }
+            assert_eq!(noun.get(2), Some(&Noun::from(300u16)));
+        }
+    }
+}
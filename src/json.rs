@@ -0,0 +1,375 @@
+//! Conversions between nouns and [JSON].
+//!
+//! Hoon represents a JSON value as a noun of the `json` type from its standard library:
+//!
+//! ```hoon
+//! +$  json
+//!   $@  ~
+//!   $%  [%a p=(list json)]
+//!       [%b p=?]
+//!       [%o p=(map @t json)]
+//!       [%n p=@ta]
+//!       [%s p=@t]
+//!   ==
+//! ```
+//!
+//! [`from_json()`] converts a [`Value`] into the noun `de:json:html` parses from the same
+//! document, and [`to_json()`] converts any `json` noun back into a [`Value`].
+//!
+//! Two parts of the encoding are easy to get wrong:
+//!
+//! - A number is stored as its text, a knot (`@ta`), rather than as an atom of its value, so `5` is
+//!   `[%n '5']`, i.e. `[%n 53]`. Because [`Value`] doesn't keep the text of the numbers it parses,
+//!   a number is stored as `serde_json` formats it, which for some floats isn't the text of the
+//!   original document (`1e3` is stored as `'1000.0'`, where Hoon would store `'1e3'`).
+//! - An object is a [Hoon map](crate::hoon_map), a treap ordered by the mugs of its keys, rather
+//!   than a list of pairs. [`from_json()`] builds exactly the treap Hoon would for the same keys, so
+//!   the resulting noun can be compared with, and is accepted by, Hoon code that uses the map.
+//!
+//! [JSON]: https://www.json.org
+
+use crate::{
+    atom::Atom,
+    cell::Cell,
+    convert::{self, elem_axis, FromNoun, IntoNoun, PathSegment},
+    hoon_map, match_tags,
+    noun::Noun,
+};
+use serde_json::{Map, Number, Value};
+use std::fmt::{self, Display, Formatter};
+
+/// Errors that occur when converting a noun into JSON.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum JsonError {
+    /// The noun isn't a `json` noun.
+    Noun(convert::Error),
+    /// The text of a number (`[%n @ta]`) isn't a JSON number.
+    Number(String),
+}
+
+impl JsonError {
+    /// Notes that this error occurred in the noun at `axis`, which is at `segment` if it's an
+    /// element of an array, relative to the noun being converted.
+    fn nest(self, segment: Option<PathSegment>, axis: Option<u64>) -> Self {
+        match self {
+            Self::Noun(err) => Self::Noun(err.nest(segment, axis)),
+            err => err,
+        }
+    }
+}
+
+impl Display for JsonError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        match self {
+            Self::Noun(err) => write!(f, "the noun is not JSON: {}", err),
+            Self::Number(text) => write!(f, "`{}` is not a JSON number", text),
+        }
+    }
+}
+
+impl std::error::Error for JsonError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Noun(err) => Some(err),
+            Self::Number(_) => None,
+        }
+    }
+}
+
+impl From<convert::Error> for JsonError {
+    fn from(err: convert::Error) -> Self {
+        Self::Noun(err)
+    }
+}
+
+/// Converts a JSON value into a `json` noun.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{atom::Atom, cell::Cell, json, Noun};
+/// let value = serde_json::json!([true, 5]);
+/// assert_eq!(
+///     json::from_json(&value),
+///     Noun::from(Cell::from([
+///         Noun::from(Atom::from("a")),
+///         Noun::from(Cell::from(["b", ""])),
+///         Noun::from(Cell::from(["n", "5"])),
+///         Noun::null(),
+///     ]))
+/// );
+/// ```
+pub fn from_json(value: &Value) -> Noun {
+    let tagged =
+        |tag: &str, noun: Noun| Noun::from(Cell::from([Noun::from(Atom::from(tag)), noun]));
+    match value {
+        Value::Null => Noun::null(),
+        Value::Bool(flag) => tagged("b", flag.into_noun()),
+        Value::Number(num) => tagged("n", Noun::from(Atom::from(num.to_string()))),
+        Value::String(text) => tagged("s", Noun::from(Atom::from(text.as_str()))),
+        Value::Array(elems) => tagged(
+            "a",
+            elems.iter().map(from_json).collect::<Vec<_>>().into_noun(),
+        ),
+        Value::Object(obj) => tagged(
+            "o",
+            hoon_map::to_map_noun(
+                obj.iter()
+                    .map(|(key, val)| (Atom::from(key.as_str()), from_json(val))),
+            ),
+        ),
+    }
+}
+
+/// Converts a `json` noun into a JSON value.
+///
+/// Any treap is accepted for an object, even one Hoon wouldn't have built.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{cell::Cell, json, Noun};
+/// let noun = Noun::from(Cell::from(["n", "-2.5"]));
+/// assert_eq!(json::to_json(&noun).unwrap(), serde_json::json!(-2.5));
+///
+/// let noun = Noun::from(Cell::from(["n", "five"]));
+/// assert!(matches!(json::to_json(&noun), Err(json::JsonError::Number(_))));
+/// ```
+pub fn to_json(noun: &Noun) -> Result<Value, JsonError> {
+    if noun.is_null() {
+        return Ok(Value::Null);
+    }
+    let in_tail = |err: convert::Error| JsonError::Noun(err.at(3));
+    match_tags!(noun, {
+        "a" => |elems| array_from_noun(elems)
+            .map(Value::Array)
+            .map_err(|err| err.nest(None, Some(3))),
+        "b" => |flag| bool::from_noun(flag).map(Value::Bool).map_err(in_tail),
+        "n" => |num| {
+            let text = <&str>::try_from(num).map_err(in_tail)?;
+            text.parse::<Number>()
+                .map(Value::Number)
+                .map_err(|_| JsonError::Number(text.to_owned()))
+        },
+        "o" => |map| {
+            let mut obj = Map::new();
+            object_from_noun(map, &mut obj).map_err(|err| err.nest(None, Some(3)))?;
+            Ok(Value::Object(obj))
+        },
+        "s" => |text| String::from_noun(text).map(Value::String).map_err(in_tail),
+        else => |_| Err(JsonError::Noun(convert::Error::UnknownTag)),
+    })
+}
+
+/// Converts a list of `json` nouns into the elements of an array.
+fn array_from_noun(noun: &Noun) -> Result<Vec<Value>, JsonError> {
+    noun.into_iter()
+        .enumerate()
+        .map(|(index, elem)| {
+            to_json(elem?)
+                .map_err(|err| err.nest(Some(PathSegment::Index(index)), elem_axis(index)))
+        })
+        .collect()
+}
+
+/// Reads the entries of a treap `[n=[k v] l r]` of `json` nouns keyed by cords into `obj`.
+fn object_from_noun(noun: &Noun, obj: &mut Map<String, Value>) -> Result<(), JsonError> {
+    for node in hoon_map::treap_from_noun(noun, None)? {
+        let Noun::Cell(entry) = node.entry else {
+            return Err(JsonError::Noun(
+                convert::Error::UnexpectedAtom.nest(None, node.axis(2)),
+            ));
+        };
+        let key =
+            String::from_noun(entry.head_ref()).map_err(|err| err.nest(None, node.axis(4)))?;
+        let val = to_json(entry.tail_ref()).map_err(|err| err.nest(None, node.axis(5)))?;
+        obj.insert(key, val);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// Returns `[%tag noun]`.
+    fn tagged(tag: &str, noun: Noun) -> Noun {
+        Noun::from(Cell::from([Noun::from(Atom::from(tag)), noun]))
+    }
+
+    /// Returns the treap node `[[key val] left right]`.
+    fn node(key: &str, val: Noun, left: Noun, right: Noun) -> Noun {
+        Noun::from(Cell::from([
+            Noun::from(Cell::from([Noun::from(Atom::from(key)), val])),
+            left,
+            right,
+        ]))
+    }
+
+    /// Returns a leaf of a treap, `[[key val] ~ ~]`.
+    fn leaf(key: &str, val: Noun) -> Noun {
+        node(key, val, Noun::null(), Noun::null())
+    }
+
+    #[test]
+    fn json() {
+        // Scalars.
+        {
+            let cases = [
+                (json!(null), Noun::null()),
+                (json!(true), Noun::from(Cell::from(["b", ""]))),
+                (json!(false), tagged("b", Noun::from(1u8))),
+                (json!(5), Noun::from(Cell::from(["n", "5"]))),
+                (json!(-7), Noun::from(Cell::from(["n", "-7"]))),
+                (json!(2.5), Noun::from(Cell::from(["n", "2.5"]))),
+                (json!(""), tagged("s", Noun::null())),
+                (json!("~zod"), Noun::from(Cell::from(["s", "~zod"]))),
+                (json!([]), tagged("a", Noun::null())),
+                (json!({}), tagged("o", Noun::null())),
+            ];
+            for (value, noun) in cases {
+                assert_eq!(from_json(&value), noun);
+                assert_eq!(to_json(&noun).expect("json noun"), value);
+            }
+        }
+
+        // A document round trips through the noun `de:json:html` parses it into. The mugs of the
+        // keys put `tags` at the root of the outer object and `name` at the root of the inner ones.
+        {
+            let value = json!({
+                "name": "~zod",
+                "ships": [
+                    {"name": "~zod", "online": true, "peers": 3},
+                    {"name": "~nec", "online": false, "peers": 0},
+                ],
+                "version": "1.2",
+                "count": -7,
+                "ratio": 2.5,
+                "tags": [],
+                "meta": null,
+            });
+            let ship = |name: &str, online: bool, peers: &str| {
+                tagged(
+                    "o",
+                    node(
+                        "name",
+                        Noun::from(Cell::from(["s", name])),
+                        node(
+                            "peers",
+                            Noun::from(Cell::from(["n", peers])),
+                            leaf("online", tagged("b", online.into_noun())),
+                            Noun::null(),
+                        ),
+                        Noun::null(),
+                    ),
+                )
+            };
+            let fixture = tagged(
+                "o",
+                node(
+                    "tags",
+                    tagged("a", Noun::null()),
+                    node(
+                        "version",
+                        Noun::from(Cell::from(["s", "1.2"])),
+                        leaf("meta", Noun::null()),
+                        node(
+                            "ratio",
+                            Noun::from(Cell::from(["n", "2.5"])),
+                            Noun::null(),
+                            node(
+                                "name",
+                                Noun::from(Cell::from(["s", "~zod"])),
+                                node(
+                                    "count",
+                                    Noun::from(Cell::from(["n", "-7"])),
+                                    Noun::null(),
+                                    leaf(
+                                        "ships",
+                                        tagged(
+                                            "a",
+                                            Noun::from(Cell::from([
+                                                ship("~zod", true, "3"),
+                                                ship("~nec", false, "0"),
+                                                Noun::null(),
+                                            ])),
+                                        ),
+                                    ),
+                                ),
+                                Noun::null(),
+                            ),
+                        ),
+                    ),
+                    Noun::null(),
+                ),
+            );
+            assert_eq!(from_json(&value), fixture);
+            assert_eq!(to_json(&fixture).expect("json noun"), value);
+        }
+
+        // Any treap is read, not just the one Hoon would build.
+        {
+            let noun = tagged(
+                "o",
+                node(
+                    "b",
+                    Noun::from(Cell::from(["n", "2"])),
+                    leaf("a", Noun::from(Cell::from(["n", "1"]))),
+                    Noun::null(),
+                ),
+            );
+            assert_eq!(to_json(&noun).expect("json noun"), json!({"a": 1, "b": 2}));
+        }
+    }
+
+    #[test]
+    fn json_errors() {
+        // Unknown tags and malformed values.
+        {
+            for noun in [
+                Noun::from("s"),
+                Noun::from(Cell::from(["x", "value"])),
+                tagged("b", Noun::from(2u8)),
+                tagged("s", Noun::from(Cell::from(["not", "a cord"]))),
+            ] {
+                assert!(matches!(to_json(&noun), Err(JsonError::Noun(_))));
+            }
+        }
+
+        // A number whose text isn't a JSON number.
+        {
+            for text in ["", "five", "1.2.3", "0x10"] {
+                let noun = tagged("n", Noun::from(Atom::from(text)));
+                match to_json(&noun) {
+                    Err(JsonError::Number(number)) => assert_eq!(number, text),
+                    res => panic!("unexpected result: {:?}", res),
+                }
+            }
+        }
+
+        // Errors inside arrays and objects report their axis.
+        {
+            // [%a [%s 's'] [%o [['k' [%b 2]] ~ ~]] ~]
+            let noun = tagged(
+                "a",
+                Noun::from(Cell::from([
+                    Noun::from(Cell::from(["s", "s"])),
+                    tagged("o", leaf("k", tagged("b", Noun::from(2u8)))),
+                    Noun::null(),
+                ])),
+            );
+            let err = to_json(&noun).expect_err("[%b 2]");
+            match &err {
+                JsonError::Noun(convert::Error::At { axis, path, source }) => {
+                    assert_eq!(*axis, Some(235));
+                    assert_eq!(path, &[PathSegment::Index(1)]);
+                    assert!(matches!(**source, convert::Error::ImplType));
+                }
+                err => panic!("unexpected error: {:?}", err),
+            }
+            assert_eq!(noun.get(235), Some(&Noun::from(2u8)));
+        }
+    }
+}
@@ -0,0 +1,247 @@
+//! Conversions to and from Ames lanes.
+//!
+//! A lane is where Ames sends packets for a peer: `(each @pC address)`, either a galaxy, `[%.y p]`,
+//! whose address the runtime looks up itself, or an address, `[%.n p]`, which the runtime packs into
+//! a single atom. The low 32 bits of the atom are the IPv4 address, read as a big-endian integer so
+//! that `1.2.3.4` is `0x102.0304`, and the next 16 bits are the UDP port. Older code writes an
+//! address as `[%if p=@if q=@ud]` instead, with the address (`@if`) and port as separate atoms;
+//! [`from_noun()`] reads either form.
+//!
+//! Ames only speaks IPv4, and neither form can hold an IPv6 address, so there's no conversion from
+//! [`SocketAddrV6`](std::net::SocketAddrV6).
+
+use crate::{
+    atom::Atom,
+    cell::Cell,
+    convert::{Error, FromNoun},
+    each::Each,
+    match_tags,
+    noun::Noun,
+};
+use std::net::{Ipv4Addr, SocketAddrV4};
+
+/// A lane, `(each @pC address)`.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{atom::Atom, cell::Cell, lane::{self, Lane}, Noun};
+/// # use std::net::{Ipv4Addr, SocketAddrV4};
+/// let addr = SocketAddrV4::new(Ipv4Addr::new(127, 0, 0, 1), 31337);
+/// let noun = lane::to_noun(&Lane::from(addr));
+/// assert_eq!(
+///     noun,
+///     Noun::from(Cell::from([Atom::from(1u8), Atom::from(0x7a69_7f00_0001u64)]))
+/// );
+/// assert_eq!(SocketAddrV4::try_from(lane::from_noun(&noun).unwrap()), Ok(addr));
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Lane {
+    /// A galaxy, by its number: `0` is `~zod`.
+    Galaxy(u8),
+    /// An IPv4 address and UDP port.
+    Ip(SocketAddrV4),
+}
+
+impl Lane {
+    /// Returns the atom the runtime packs the address of this lane into, or [`None`] if this lane
+    /// is a galaxy.
+    pub fn address(&self) -> Option<Atom> {
+        match self {
+            Self::Galaxy(_) => None,
+            Self::Ip(addr) => Some(Atom::from(
+                u64::from(addr.port()) << 32 | u64::from(u32::from(*addr.ip())),
+            )),
+        }
+    }
+
+    /// Unpacks an address the runtime packed into an atom.
+    ///
+    /// Fails with [`AtomToUint`](Error::AtomToUint) if the atom has more than 48 bits.
+    pub fn from_address(address: &Atom) -> Result<Self, Error> {
+        let address = address.as_u64().ok_or(Error::AtomToUint)?;
+        if address >> 48 != 0 {
+            return Err(Error::AtomToUint);
+        }
+        let ip = Ipv4Addr::from(address as u32);
+        let port = (address >> 32) as u16;
+        Ok(Self::Ip(SocketAddrV4::new(ip, port)))
+    }
+}
+
+impl From<SocketAddrV4> for Lane {
+    fn from(addr: SocketAddrV4) -> Self {
+        Self::Ip(addr)
+    }
+}
+
+impl TryFrom<Lane> for SocketAddrV4 {
+    type Error = Lane;
+
+    /// Converts a lane into its address, failing with the lane itself if it's a galaxy.
+    fn try_from(lane: Lane) -> Result<Self, Lane> {
+        match lane {
+            Lane::Galaxy(_) => Err(lane),
+            Lane::Ip(addr) => Ok(addr),
+        }
+    }
+}
+
+impl From<Lane> for Noun {
+    fn from(lane: Lane) -> Self {
+        to_noun(&lane)
+    }
+}
+
+impl TryFrom<&Noun> for Lane {
+    type Error = Error;
+
+    fn try_from(noun: &Noun) -> Result<Self, Error> {
+        from_noun(noun)
+    }
+}
+
+/// Converts a lane into `(each @pC address)`, packing an address into a single atom.
+pub fn to_noun(lane: &Lane) -> Noun {
+    match lane {
+        Lane::Galaxy(galaxy) => Noun::from(Each::<u8, Atom>::Yes(*galaxy)),
+        Lane::Ip(_) => Noun::from(Each::<u8, Atom>::No(lane.address().expect("address"))),
+    }
+}
+
+/// Converts an address into the older `[%if p=@if q=@ud]` form.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{atom::Atom, cell::Cell, lane, Noun};
+/// # use std::net::{Ipv4Addr, SocketAddrV4};
+/// let addr = SocketAddrV4::new(Ipv4Addr::new(1, 2, 3, 4), 13337);
+/// let noun = lane::to_if_noun(&addr);
+/// assert_eq!(
+///     noun,
+///     Noun::from(Cell::from([
+///         Atom::from("if"),
+///         Atom::from(0x0102_0304u32),
+///         Atom::from(13337u16),
+///     ]))
+/// );
+/// assert_eq!(lane::from_noun(&noun).unwrap(), lane::Lane::Ip(addr));
+/// ```
+pub fn to_if_noun(addr: &SocketAddrV4) -> Noun {
+    Noun::from(Cell::from([
+        Noun::from("if"),
+        Noun::from(u32::from(*addr.ip())),
+        Noun::from(addr.port()),
+    ]))
+}
+
+/// Converts `(each @pC address)` or `[%if p=@if q=@ud]` into a lane.
+///
+/// A galaxy must be less than 256, an address must fit in 48 bits, and an `@if` and port must fit
+/// in 32 and 16 bits.
+pub fn from_noun(noun: &Noun) -> Result<Lane, Error> {
+    match_tags!(noun, {
+        "if" => |addr| {
+            let (ip, port) = <(u32, u16)>::from_noun(addr).map_err(|err| err.at(3))?;
+            Ok(Lane::Ip(SocketAddrV4::new(Ipv4Addr::from(ip), port)))
+        },
+        else => |lane| match Each::<u8, Atom>::try_from(lane)? {
+            Each::Yes(galaxy) => Ok(Lane::Galaxy(galaxy)),
+            Each::No(address) => Lane::from_address(&address).map_err(|err| err.at(3)),
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lane() {
+        // Addresses pack the IPv4 address into the low 32 bits and the port into the next 16. These
+        // atoms are worked out by hand from the runtime's packing, `port << 32 ^ ip`, rather than
+        // captured from a ship.
+        {
+            let cases = [
+                ((127, 0, 0, 1), 31337, 0x7a69_7f00_0001u64),
+                ((35, 247, 119, 159), 13337, 0x3419_23f7_779f),
+                ((192, 168, 1, 10), 443, 0x01bb_c0a8_010a),
+                ((255, 255, 255, 255), 65535, 0xffff_ffff_ffff),
+                ((0, 0, 0, 0), 0, 0),
+                ((0, 0, 0, 1), 1, 0x0001_0000_0001),
+            ];
+            for ((a, b, c, d), port, address) in cases {
+                let addr = SocketAddrV4::new(Ipv4Addr::new(a, b, c, d), port);
+                let lane = Lane::from(addr);
+                assert_eq!(lane.address(), Some(Atom::from(address)));
+                assert_eq!(
+                    Lane::from_address(&Atom::from(address)).expect("lane"),
+                    lane
+                );
+
+                let noun = Noun::from(lane);
+                assert_eq!(noun, Noun::from(Cell::from([1u64, address])));
+                assert_eq!(from_noun(&noun).expect("lane"), lane);
+
+                let noun = to_if_noun(&addr);
+                assert_eq!(
+                    noun.get(6),
+                    Some(&Noun::from(u32::from_be_bytes([a, b, c, d])))
+                );
+                assert_eq!(Lane::try_from(&noun).expect("lane"), lane);
+            }
+        }
+
+        // Galaxies.
+        {
+            for galaxy in [0u8, 1, 255] {
+                let noun = to_noun(&Lane::Galaxy(galaxy));
+                assert_eq!(noun, Noun::from(Cell::from([0u8, galaxy])));
+                assert_eq!(from_noun(&noun).expect("lane"), Lane::Galaxy(galaxy));
+                assert_eq!(Lane::Galaxy(galaxy).address(), None);
+                assert_eq!(
+                    SocketAddrV4::try_from(Lane::Galaxy(galaxy)),
+                    Err(Lane::Galaxy(galaxy))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn lane_errors() {
+        let cases = [
+            // A galaxy that's too large.
+            (Noun::from(Cell::from([0u16, 256])), Some(3)),
+            // An address that's too large.
+            (Noun::from(Cell::from([1u64, 1 << 48])), Some(3)),
+            // A head that isn't a loobean.
+            (Noun::from(Cell::from([2u8, 1])), Some(2)),
+            // An @if or port that's too large.
+            (
+                Noun::from(Cell::from([
+                    Noun::from("if"),
+                    Noun::from(1u64 << 32),
+                    Noun::from(1u8),
+                ])),
+                Some(6),
+            ),
+            (
+                Noun::from(Cell::from([
+                    Noun::from("if"),
+                    Noun::from(1u8),
+                    Noun::from(65536u32),
+                ])),
+                Some(7),
+            ),
+            (Noun::from(1u8), None),
+        ];
+        for (noun, axis) in cases {
+            match (from_noun(&noun), axis) {
+                (Err(Error::At { axis: at, .. }), Some(_)) => assert_eq!(at, axis),
+                (Err(Error::UnexpectedAtom), None) => {}
+                (res, _) => panic!("unexpected result for {}: {:?}", noun, res),
+            }
+        }
+    }
+}
@@ -13,16 +13,54 @@
 
 #[doc(hidden)]
 pub mod atom;
+pub mod aura;
+pub mod axis;
+pub mod builder;
+pub mod bytes;
 #[doc(hidden)]
 pub mod cell;
+pub mod codec;
 pub mod convert;
+pub mod each;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod fs;
+pub mod hoon_map;
+pub mod hoon_set;
+#[cfg(feature = "serde_json")]
+pub mod json;
+pub mod lane;
+pub mod list;
 pub mod marker;
+pub mod mime;
+#[cfg(feature = "mmap")]
+pub mod mmap;
+pub mod mug;
+pub mod nock;
 #[doc(hidden)]
 pub mod noun;
+pub mod octs;
+pub mod path;
+pub mod pill;
+#[cfg(feature = "python")]
+pub mod python;
+#[cfg(feature = "serde")]
+pub mod serde;
 pub mod serdes;
+pub mod shape;
+pub mod ship;
+pub mod table;
+pub mod tank;
+pub mod text;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+pub mod zipper;
 
+#[cfg(feature = "bitstream-io")]
 #[doc(inline)]
-pub use crate::atom::{Atom, Builder as AtomBuilder, Iter as AtomIter};
+pub use crate::atom::AtomBitReader;
+#[doc(inline)]
+pub use crate::atom::{Atom, Builder as AtomBuilder, DrError, Iter as AtomIter, ParseAtomError};
 #[doc(inline)]
 pub use crate::cell::*;
 #[doc(inline)]
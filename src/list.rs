@@ -0,0 +1,295 @@
+//! Nouns that are known to be lists.
+//!
+//! A null-terminated list `[e0 e1 ... eN 0]` is just a noun, so code that takes a noun as a list
+//! has to handle an improper list every time it walks one. A [`List`] checks once, when it's
+//! created, so that walking it afterwards can't fail.
+
+use crate::{
+    convert,
+    noun::{ListIter, Noun},
+    Rc,
+};
+use std::iter::FusedIterator;
+
+/// A null-terminated list `[e0 e1 ... eN 0]`, with its length.
+///
+/// Accessing an element by index takes time proportional to the index, as it does in Hoon.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{cell::Cell, list::List, Noun};
+/// let list = List::try_from(&Noun::from(Cell::from(["a", "b", "c", ""]))).unwrap();
+/// assert_eq!(list.len(), 3);
+/// assert_eq!(list.get(1), Some(&Noun::from("b")));
+/// assert_eq!(list.last(), Some(&Noun::from("c")));
+/// assert!(list.contains(&Noun::from("a")));
+///
+/// assert!(List::try_from(&Noun::from(Cell::from(["a", "b"]))).is_err());
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct List {
+    noun: Rc<Noun>,
+    len: usize,
+}
+
+impl List {
+    /// Creates the empty list, `0`.
+    pub fn new() -> Self {
+        Self {
+            noun: Rc::new(Noun::null()),
+            len: 0,
+        }
+    }
+
+    /// Returns the number of elements in this list.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if this list has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the element at `index`, or [`None`] if `index` is past the end.
+    pub fn get(&self, index: usize) -> Option<&Noun> {
+        self.iter().nth(index)
+    }
+
+    /// Returns the first element, or [`None`] if this list is empty.
+    pub fn first(&self) -> Option<&Noun> {
+        self.iter().next()
+    }
+
+    /// Returns the last element, or [`None`] if this list is empty.
+    pub fn last(&self) -> Option<&Noun> {
+        self.iter().last()
+    }
+
+    /// Returns `true` if an element of this list is equal to `noun`.
+    pub fn contains(&self, noun: &Noun) -> bool {
+        self.iter().any(|elem| elem == noun)
+    }
+
+    /// Returns an iterator over the elements of this list.
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            inner: self.noun.into_iter(),
+            remaining: self.len,
+        }
+    }
+
+    /// Returns the elements of this list, which are shared with it rather than cloned.
+    pub fn to_vec(&self) -> Vec<Rc<Noun>> {
+        let mut elems = Vec::with_capacity(self.len);
+        let mut rest = self.noun.clone();
+        while let Noun::Cell(cell) = &*rest {
+            elems.push(cell.head());
+            rest = cell.tail();
+        }
+        elems
+    }
+
+    /// Returns the noun of this list.
+    pub fn as_noun(&self) -> &Rc<Noun> {
+        &self.noun
+    }
+
+    /// Converts this list into its noun.
+    pub fn into_noun(self) -> Rc<Noun> {
+        self.noun
+    }
+}
+
+impl Default for List {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TryFrom<Rc<Noun>> for List {
+    type Error = convert::Error;
+
+    /// Checks that a noun is a list, failing with an
+    /// [`ExpectedNull`](convert::Error::ExpectedNull) error at the axis of the atom that ends it
+    /// if it isn't.
+    fn try_from(noun: Rc<Noun>) -> Result<Self, convert::Error> {
+        let mut len = 0;
+        for elem in &*noun {
+            elem?;
+            len += 1;
+        }
+        Ok(Self { noun, len })
+    }
+}
+
+impl TryFrom<&Noun> for List {
+    type Error = convert::Error;
+
+    /// Checks that a noun is a list, as [`List::try_from()`] of an [`Rc<Noun>`] does. The noun is
+    /// cloned, which only clones the [`Rc`]s of its head and tail.
+    fn try_from(noun: &Noun) -> Result<Self, convert::Error> {
+        Self::try_from(Rc::new(noun.clone()))
+    }
+}
+
+impl From<List> for Noun {
+    fn from(list: List) -> Self {
+        Rc::unwrap_or_clone(list.noun)
+    }
+}
+
+impl FromIterator<Rc<Noun>> for List {
+    fn from_iter<I: IntoIterator<Item = Rc<Noun>>>(elems: I) -> Self {
+        let elems: Vec<_> = elems.into_iter().collect();
+        let len = elems.len();
+        Self {
+            noun: Rc::new(Noun::list_from(elems)),
+            len,
+        }
+    }
+}
+
+impl FromIterator<Noun> for List {
+    fn from_iter<I: IntoIterator<Item = Noun>>(elems: I) -> Self {
+        elems.into_iter().map(Rc::new).collect()
+    }
+}
+
+impl<'a> IntoIterator for &'a List {
+    type Item = &'a Noun;
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Iter<'a> {
+        self.iter()
+    }
+}
+
+/// An iterator over the elements of a [`List`].
+#[derive(Clone, Debug)]
+pub struct Iter<'a> {
+    inner: ListIter<'a>,
+    remaining: usize,
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = &'a Noun;
+
+    fn next(&mut self) -> Option<&'a Noun> {
+        let elem = self
+            .inner
+            .next()?
+            .expect("list was checked when it was created");
+        self.remaining -= 1;
+        Some(elem)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl ExactSizeIterator for Iter<'_> {}
+
+impl FusedIterator for Iter<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{atom::Atom, cell::Cell};
+
+    #[test]
+    fn list() {
+        // Every accessor of a known list.
+        {
+            let noun = Noun::from(Cell::from([
+                Noun::from(1u8),
+                Noun::from(Cell::from([2u8, 3])),
+                Noun::from(4u8),
+                Noun::null(),
+            ]));
+            let list = List::try_from(&noun).expect("list");
+            assert_eq!(list.len(), 3);
+            assert!(!list.is_empty());
+            assert_eq!(list.get(0), Some(&Noun::from(1u8)));
+            assert_eq!(list.get(1), Some(&Noun::from(Cell::from([2u8, 3]))));
+            assert_eq!(list.get(3), None);
+            assert_eq!(list.first(), Some(&Noun::from(1u8)));
+            assert_eq!(list.last(), Some(&Noun::from(4u8)));
+            assert!(list.contains(&Noun::from(Cell::from([2u8, 3]))));
+            assert!(!list.contains(&Noun::from(2u8)));
+            let mut iter = list.iter();
+            assert_eq!(iter.len(), 3);
+            iter.next();
+            assert_eq!(iter.len(), 2);
+            assert_eq!(
+                list.iter().collect::<Vec<_>>(),
+                [
+                    &Noun::from(1u8),
+                    &Noun::from(Cell::from([2u8, 3])),
+                    &Noun::from(4u8),
+                ]
+            );
+            // The elements of `to_vec()` are the elements of the list, not copies.
+            let elems = list.to_vec();
+            assert_eq!(elems.len(), 3);
+            for (elem, expected) in elems.iter().zip(&list) {
+                assert!(std::ptr::eq(&**elem, expected));
+            }
+            assert_eq!(Noun::from(list), noun);
+        }
+
+        // The empty list.
+        {
+            for list in [
+                List::new(),
+                List::try_from(&Noun::null()).expect("list"),
+                List::from_iter(Vec::<Noun>::new()),
+            ] {
+                assert_eq!(list.len(), 0);
+                assert!(list.is_empty());
+                assert_eq!((list.get(0), list.first(), list.last()), (None, None, None));
+                assert!(!list.contains(&Noun::null()));
+                assert_eq!(list.iter().count(), 0);
+                assert!(list.to_vec().is_empty());
+                assert!(list.as_noun().is_null());
+            }
+        }
+
+        // Building a list.
+        {
+            let list: List = (0..5u8).map(Noun::from).collect();
+            assert_eq!(list.len(), 5);
+            assert_eq!(
+                *list.as_noun(),
+                Rc::new(Noun::from(Cell::from([0u8, 1, 2, 3, 4, 0])))
+            );
+            let shared = Rc::new(Noun::from("shared"));
+            let list: List = [shared.clone(), shared.clone()].into_iter().collect();
+            assert!(list.iter().all(|elem| std::ptr::eq(elem, &*shared)));
+        }
+
+        // Nouns that aren't lists.
+        {
+            let cases = [
+                (Noun::from(5u8), 1),
+                (Noun::from(Cell::from([1u8, 2])), 3),
+                (Noun::from(Cell::from([1u8, 2, 3])), 7),
+                (
+                    Noun::from(Cell::from([Atom::from(1u8), Atom::from("end")])),
+                    3,
+                ),
+            ];
+            for (noun, axis) in cases {
+                let err = List::try_from(&noun).expect_err("not a list");
+                assert!(
+                    matches!(err, convert::Error::At { axis: Some(at), .. } if at == axis),
+                    "{:?}",
+                    err
+                );
+                assert!(matches!(err.root_cause(), convert::Error::ExpectedNull));
+            }
+        }
+    }
+}
@@ -0,0 +1,179 @@
+//! Conversions to and from `mime`, Hoon's typed byte strings.
+//!
+//! Eyre hands HTTP bodies to agents, and Clay stores files it has no mark for, as a `mime`:
+//!
+//! ```hoon
+//! +$  mime  (pair mite octs)
+//! +$  mite  (list @ta)
+//! ```
+//!
+//! The `mite` is a MIME type split at its `/` into knots, so `text/plain` is `/text/plain`, and
+//! the body is [`octs`](crate::octs), which keeps trailing zero bytes.
+
+use crate::{
+    convert::{Error, FromNoun, IntoNoun},
+    noun::Noun,
+    octs::Octs,
+    path::Path,
+};
+
+/// A byte string tagged with its MIME type, `[p=mite q=octs]`.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{atom::Atom, cell::Cell, mime::Mime, Noun};
+/// let mime = Mime::text("hi\0");
+/// let noun = Noun::from(mime.clone());
+/// assert_eq!(
+///     noun,
+///     Noun::from(Cell::from([
+///         Noun::from(Cell::from(["text", "plain", ""])),
+///         Noun::from(Cell::from([Atom::from(3u8), Atom::from("hi")])),
+///     ]))
+/// );
+/// assert_eq!(Mime::try_from(&noun).unwrap(), mime);
+/// ```
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Mime {
+    /// The segments of the MIME type, like `["text", "plain"]`, each of which should be a knot
+    /// (`@ta`).
+    pub mime_type: Vec<String>,
+    /// The body.
+    pub data: Vec<u8>,
+}
+
+impl Mime {
+    /// Creates a `text/plain` body.
+    pub fn text(text: &str) -> Self {
+        Self {
+            mime_type: vec![String::from("text"), String::from("plain")],
+            data: text.as_bytes().to_vec(),
+        }
+    }
+
+    /// Creates an `application/json` body of a JSON value, serialized as compactly as
+    /// [`Value::to_string()`](ToString::to_string) does.
+    #[cfg(feature = "serde_json")]
+    pub fn json(value: &serde_json::Value) -> Self {
+        Self {
+            mime_type: vec![String::from("application"), String::from("json")],
+            data: value.to_string().into_bytes(),
+        }
+    }
+}
+
+impl From<Mime> for Noun {
+    /// Converts into `[mite octs]`. Segments of the MIME type are converted as they are, even if
+    /// they aren't knots, in which case the noun won't convert back.
+    fn from(mime: Mime) -> Self {
+        (mime.mime_type, Octs::from(mime.data)).into_noun()
+    }
+}
+
+impl TryFrom<&Noun> for Mime {
+    type Error = Error;
+
+    /// Converts `[mite octs]`, failing with [`ImplType`](Error::ImplType) if a segment of the MIME
+    /// type isn't a knot or the body is longer than its stated length.
+    fn try_from(noun: &Noun) -> Result<Self, Error> {
+        let (mime_type, data) = <(Path, Octs)>::from_noun(noun)?;
+        Ok(Self {
+            mime_type: mime_type.segments().to_vec(),
+            data: data.into_vec(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{atom::Atom, cell::Cell};
+
+    #[test]
+    fn mime() {
+        // Round trips, including bodies that are empty or end in zero bytes.
+        {
+            let cases = [
+                Mime::text(""),
+                Mime::text("hello, world"),
+                Mime {
+                    mime_type: vec![String::from("application"), String::from("octet-stream")],
+                    data: vec![0, 1, 0, 0],
+                },
+                Mime {
+                    mime_type: Vec::new(),
+                    data: vec![0],
+                },
+            ];
+            for mime in cases {
+                let noun = Noun::from(mime.clone());
+                assert_eq!(Mime::try_from(&noun).expect("round trip"), mime);
+            }
+        }
+
+        // The body of a file `/=base=/foo/txt` as `.^(mime %cx ...)` would give it, built by
+        // hand from the types rather than captured from a ship: `[/text/plain [6 'hello\0']]`.
+        {
+            let noun = Noun::from(Cell::from([
+                Noun::from(Cell::from(["text", "plain", ""])),
+                Noun::from(Cell::from([Atom::from(6u8), Atom::from("hello")])),
+            ]));
+            let mime = Mime::try_from(&noun).expect("mime");
+            assert_eq!(mime.mime_type, ["text", "plain"]);
+            assert_eq!(mime.data, b"hello\0");
+            assert_eq!(Noun::from(mime), noun);
+        }
+
+        // JSON.
+        #[cfg(feature = "serde_json")]
+        {
+            let mime = Mime::json(&serde_json::json!({"ok": true}));
+            assert_eq!(mime.mime_type, ["application", "json"]);
+            assert_eq!(mime.data, br#"{"ok":true}"#);
+        }
+    }
+
+    #[test]
+    fn mime_errors() {
+        let body = Noun::from(Cell::from([Atom::from(2u8), Atom::from("ok")]));
+        let cases = [
+            // A segment that isn't a knot.
+            (
+                Noun::from(Cell::from([
+                    Noun::from(Cell::from(["Text", "plain", ""])),
+                    body.clone(),
+                ])),
+                Some(2),
+            ),
+            // A MIME type that isn't null-terminated.
+            (
+                Noun::from(Cell::from([
+                    Noun::from(Cell::from(["text", "plain"])),
+                    body.clone(),
+                ])),
+                Some(2),
+            ),
+            // A body longer than its length.
+            (
+                Noun::from(Cell::from([
+                    Noun::null(),
+                    Noun::from(Cell::from([Atom::from(1u8), Atom::from("ok")])),
+                ])),
+                Some(3),
+            ),
+            // No body.
+            (Noun::from(Cell::from(["text", "plain", ""])), None),
+        ];
+        for (noun, axis) in cases {
+            let err = Mime::try_from(&noun).expect_err("not a mime");
+            if let Some(axis) = axis {
+                let Error::At { axis: Some(at), .. } = err else {
+                    panic!("error at an axis: {:?}", err);
+                };
+                // The error is within the head or the tail.
+                assert_eq!(crate::axis::cap(at), Some(axis), "{:?}", err);
+            }
+        }
+    }
+}
@@ -0,0 +1,186 @@
+//! Atoms backed by memory-mapped files.
+//!
+//! A jammed pill can be larger than the memory of the machine cueing it. An [`MmapAtom`] maps the
+//! file into memory instead of reading it, so its bytes are paged in from disk as they're read and
+//! can be paged out again under memory pressure, rather than taking up anonymous memory.
+
+use crate::{
+    atom::{self, Atom},
+    noun::Noun,
+    serdes::{self, cue, CueLimits},
+    AtomIter,
+};
+use memmap2::Mmap;
+use std::{fs::File, io, path::Path};
+
+/// The bytes of a file as an atom, least significant byte first, without loading them into
+/// memory.
+///
+/// The file isn't truncated: trailing zero bytes, which aren't part of an atom, are left in the
+/// file and excluded from [`as_bytes()`](Self::as_bytes).
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{cell::Cell, mmap::MmapAtom, Noun};
+/// let noun = Noun::from(Cell::from([0u8, 19u8]));
+/// let path = std::env::temp_dir().join("mmap-atom-doctest.jam");
+/// std::fs::write(&path, noun.jam_to_vec()).unwrap();
+///
+/// // SAFETY: nothing else modifies the file while it's mapped.
+/// let atom = unsafe { MmapAtom::open(&path) }.unwrap();
+/// assert_eq!(atom.cue().unwrap(), noun);
+/// # drop(atom);
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct MmapAtom {
+    map: Mmap,
+    /// The length of the atom in bytes, which is the length of the file less any trailing zero
+    /// bytes.
+    len: usize,
+}
+
+impl MmapAtom {
+    /// Maps the file at `path` into memory as an atom.
+    ///
+    /// Finding the length of the atom reads backwards from the end of the file past any trailing
+    /// zero bytes, but nothing else is read until the bytes are used.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified, truncated, or extended, by this process or any other, for as
+    /// long as the atom exists. The atom's bytes are the file's pages, so a concurrent write would
+    /// change them underneath borrows that Rust assumes are immutable, and truncating the file
+    /// makes reading the lost pages raise `SIGBUS` on Unix.
+    pub unsafe fn open(path: &Path) -> io::Result<Self> {
+        let file = File::open(path)?;
+        // SAFETY: the caller guarantees that nothing modifies the file while it's mapped.
+        let map = unsafe { Mmap::map(&file) }?;
+        let len = map
+            .iter()
+            .rposition(|byte| *byte != 0)
+            .map_or(0, |idx| idx + 1);
+        Ok(Self { map, len })
+    }
+
+    /// Returns the bytes of this atom, least significant byte first, without trailing zero
+    /// bytes, as [`Atom::as_bytes()`] does.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.map[..self.len]
+    }
+
+    /// Returns the length in bits of this atom.
+    pub fn bit_len(&self) -> u64 {
+        atom::bit_len(self.as_bytes())
+    }
+
+    /// Returns `true` if this atom is null, which is the case for an empty file or one of only
+    /// zero bytes.
+    pub fn is_null(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Copies this atom into memory.
+    pub fn to_atom(&self) -> Atom {
+        Atom::from(self.as_bytes().to_vec())
+    }
+
+    /// Cues the noun this atom is the jam of, like [`Noun::cue_bytes()`], without copying it into
+    /// memory first.
+    pub fn cue(&self) -> serdes::Result<Noun> {
+        self.cue_with_limits(&CueLimits::UNLIMITED)
+    }
+
+    /// Cues the noun this atom is the jam of within `limits`, like
+    /// [`Noun::cue_with_limits()`].
+    pub fn cue_with_limits(&self, limits: &CueLimits) -> serdes::Result<Noun> {
+        cue::decode_exact(&mut AtomIter::from_bytes(self.as_bytes()), limits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{cell::Cell, Rc};
+    use std::{fs, path::PathBuf};
+
+    /// A file in the temporary directory that's removed when this is dropped.
+    struct TempFile(PathBuf);
+
+    impl TempFile {
+        fn new(name: &str, bytes: &[u8]) -> Self {
+            let path = std::env::temp_dir().join(format!("noun-{}-{}", std::process::id(), name));
+            fs::write(&path, bytes).expect("write temp file");
+            Self(path)
+        }
+
+        fn open(&self) -> MmapAtom {
+            // SAFETY: nothing else modifies the file while it's mapped.
+            unsafe { MmapAtom::open(&self.0) }.expect("map temp file")
+        }
+    }
+
+    impl Drop for TempFile {
+        fn drop(&mut self) {
+            let _ = fs::remove_file(&self.0);
+        }
+    }
+
+    #[test]
+    fn mmap() {
+        // A jammed noun cues just as it does from memory.
+        {
+            let shared = Rc::new(Noun::from(Cell::from(["pill", "event"])));
+            let noun = Noun::from(Cell::from([
+                shared.clone(),
+                Rc::new(Noun::from(Atom::from(vec![0xab; 1000]))),
+                shared,
+            ]));
+            let jammed = noun.jam_to_vec();
+            let file = TempFile::new("jammed", &jammed);
+            let atom = file.open();
+            assert_eq!(atom.as_bytes(), jammed);
+            assert_eq!(atom.to_atom(), Atom::from(jammed.clone()));
+            assert_eq!(atom.bit_len(), atom.to_atom().bit_len_u64());
+            assert_eq!(atom.cue().expect("cue"), noun);
+            assert_eq!(Noun::cue_bytes(atom.as_bytes()).expect("cue"), noun);
+        }
+
+        // Trailing zero bytes are left in the file but aren't part of the atom.
+        {
+            let mut bytes = Noun::from(Cell::from([1u8, 2])).jam_to_vec();
+            let len = bytes.len();
+            bytes.extend([0; 5000]);
+            let file = TempFile::new("trailing-zeros", &bytes);
+            let atom = file.open();
+            assert_eq!(atom.as_bytes(), &bytes[..len]);
+            assert_eq!(
+                fs::metadata(&file.0).expect("metadata").len(),
+                len as u64 + 5000
+            );
+            assert_eq!(atom.cue().expect("cue"), Noun::from(Cell::from([1u8, 2])));
+        }
+
+        // Empty files and files of zero bytes are null.
+        {
+            for (name, bytes) in [("empty", &[][..]), ("zeros", &[0; 10][..])] {
+                let file = TempFile::new(name, bytes);
+                let atom = file.open();
+                assert!(atom.is_null());
+                assert_eq!(atom.bit_len(), 0);
+                assert_eq!(atom.to_atom(), Atom::null());
+            }
+        }
+
+        // Bytes that aren't a jammed noun, and a file that doesn't exist.
+        {
+            let file = TempFile::new("garbage", &[0x03]);
+            assert!(file.open().cue().is_err());
+            let missing = std::env::temp_dir().join("noun-mmap-missing-file");
+            // SAFETY: the file doesn't exist, so nothing can modify it.
+            let err = unsafe { MmapAtom::open(&missing) }.expect_err("missing file");
+            assert_eq!(err.kind(), io::ErrorKind::NotFound);
+        }
+    }
+}
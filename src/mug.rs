@@ -0,0 +1,304 @@
+//! Hashing nouns by their mugs.
+//!
+//! [`Noun::mug()`] is Urbit's 31-bit hash of a noun, which is the same on every platform and in
+//! every process and agrees with the runtime. A [`MugHasher`] hashes a noun to its mug through the
+//! noun's [`Hash`] implementation, so keying a map by mug is a matter of its hasher:
+//!
+//! ```
+//! # use noun::{cell::Cell, mug::BuildMugHasher, Noun, Rc};
+//! # use std::{collections::HashMap, hash::BuildHasher};
+//! let mut map: HashMap<Rc<Noun>, &str, BuildMugHasher> = HashMap::default();
+//! let key = Rc::new(Noun::from(Cell::from([1u8, 2u8])));
+//! map.insert(key.clone(), "value");
+//! assert_eq!(map.get(&*key), Some(&"value"));
+//! assert_eq!(BuildMugHasher::default().hash_one(&key), 1_781_973_465);
+//! ```
+//!
+//! Computing a mug visits every subnoun, just like hashing a noun with any other hasher does. A
+//! [`MuggedNoun`] computes the mug of its noun once, when it's created, and hashes in constant
+//! time after that.
+
+use crate::{
+    atom,
+    noun::{mug_both, Noun},
+    Rc,
+};
+use std::{
+    hash::{BuildHasherDefault, Hash, Hasher},
+    ops::Deref,
+};
+
+/// The byte that starts the hash of an atom, which continues with its length in bytes and then its
+/// bytes.
+pub(crate) const ATOM: u8 = 0;
+
+/// The byte that starts the hash of a cell, which continues with the hashes of its head and tail.
+pub(crate) const CELL: u8 = 1;
+
+/// The byte that starts the hash of a [`MuggedNoun`], which continues with its mug.
+const MUGGED: u8 = 2;
+
+/// What a [`MugHasher`] expects to be written next.
+#[derive(Clone, Copy, Debug, Default)]
+enum Next {
+    /// The byte that starts the hash of a noun.
+    #[default]
+    Start,
+    /// The length of an atom.
+    AtomLen,
+    /// The bytes of an atom.
+    AtomBytes,
+    /// The mug of a [`MuggedNoun`].
+    Mug,
+}
+
+/// A [`Hasher`] that hashes a [`Noun`], or an [`Rc<Noun>`](crate::Rc) or [`MuggedNoun`], to its
+/// mug.
+///
+/// Hashing several nouns, as hashing a tuple of them does, combines their mugs from left to right
+/// as if they were the mugs of the head and tail of a cell. Anything else that's written is
+/// hashed as an atom of the bytes written, so other keys hash deterministically too, just not to
+/// anything meaningful to Urbit.
+#[derive(Clone, Debug, Default)]
+pub struct MugHasher {
+    next: Next,
+    /// The cells whose head or tail is being hashed, innermost last, with the mug of the head
+    /// once it's been hashed.
+    cells: Vec<Option<u32>>,
+    /// The mug of everything hashed so far, or `None` if nothing has been.
+    mug: Option<u32>,
+}
+
+/// A [`BuildHasher`](std::hash::BuildHasher) of [`MugHasher`]s.
+pub type BuildMugHasher = BuildHasherDefault<MugHasher>;
+
+impl MugHasher {
+    /// Accounts for a noun with mug `mug` having been hashed in full.
+    fn hashed(&mut self, mut mug: u32) {
+        self.next = Next::Start;
+        loop {
+            match self.cells.last_mut() {
+                Some(head @ None) => {
+                    *head = Some(mug);
+                    return;
+                }
+                Some(Some(head)) => {
+                    mug = mug_both(*head, mug);
+                    self.cells.pop();
+                }
+                None => {
+                    self.mug = Some(match self.mug {
+                        Some(prev) => mug_both(prev, mug),
+                        None => mug,
+                    });
+                    return;
+                }
+            }
+        }
+    }
+}
+
+impl Hasher for MugHasher {
+    /// Returns the mug of what's been hashed, or `0` if nothing has been.
+    fn finish(&self) -> u64 {
+        self.mug.map_or(0, u64::from)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        // An atom's bytes never end in zero, but anything else's might.
+        let len = bytes
+            .iter()
+            .rposition(|byte| *byte != 0)
+            .map_or(0, |idx| idx + 1);
+        self.hashed(atom::mum(0xcafe_babe, 0x7fff, &bytes[..len]));
+    }
+
+    fn write_u8(&mut self, byte: u8) {
+        match (self.next, byte) {
+            (Next::Start, ATOM) => self.next = Next::AtomLen,
+            (Next::Start, CELL) => self.cells.push(None),
+            (Next::Start, MUGGED) => self.next = Next::Mug,
+            _ => self.write(&[byte]),
+        }
+    }
+
+    fn write_u32(&mut self, n: u32) {
+        match self.next {
+            Next::Mug => self.hashed(n),
+            _ => self.write(&n.to_le_bytes()),
+        }
+    }
+
+    fn write_usize(&mut self, n: usize) {
+        match self.next {
+            Next::AtomLen => self.next = Next::AtomBytes,
+            _ => self.write(&n.to_le_bytes()),
+        }
+    }
+}
+
+/// A noun along with its mug, which lets it hash in constant time.
+///
+/// Comparing two `MuggedNoun`s compares their mugs before their nouns, so nouns that aren't equal
+/// usually compare in constant time too.
+///
+/// A `MuggedNoun` hashes exactly like its noun with a [`MugHasher`], but not with other hashers,
+/// so a map keyed by `MuggedNoun` can only be queried with a `MuggedNoun`.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{cell::Cell, mug::{BuildMugHasher, MuggedNoun}, Noun};
+/// # use std::collections::HashSet;
+/// let noun = Noun::from(Cell::from(["hello", "world", ""]));
+/// let mut set: HashSet<MuggedNoun, BuildMugHasher> = HashSet::default();
+/// set.insert(MuggedNoun::from(noun.clone()));
+/// assert!(set.contains(&MuggedNoun::from(noun.clone())));
+/// assert_eq!(MuggedNoun::from(noun).mug(), 1_113_827_290);
+/// ```
+#[derive(Clone, Debug)]
+pub struct MuggedNoun {
+    noun: Rc<Noun>,
+    mug: u32,
+}
+
+impl MuggedNoun {
+    /// Computes the mug of `noun`.
+    pub fn new(noun: Rc<Noun>) -> Self {
+        let mug = noun.mug();
+        Self { noun, mug }
+    }
+
+    /// Returns the mug of this noun.
+    pub fn mug(&self) -> u32 {
+        self.mug
+    }
+
+    /// Returns the noun.
+    pub fn noun(&self) -> &Rc<Noun> {
+        &self.noun
+    }
+
+    /// Unwraps the noun.
+    pub fn into_noun(self) -> Rc<Noun> {
+        self.noun
+    }
+}
+
+impl Deref for MuggedNoun {
+    type Target = Noun;
+
+    fn deref(&self) -> &Noun {
+        &self.noun
+    }
+}
+
+impl Eq for MuggedNoun {}
+
+impl From<Noun> for MuggedNoun {
+    fn from(noun: Noun) -> Self {
+        Self::new(Rc::new(noun))
+    }
+}
+
+impl From<Rc<Noun>> for MuggedNoun {
+    fn from(noun: Rc<Noun>) -> Self {
+        Self::new(noun)
+    }
+}
+
+impl Hash for MuggedNoun {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u8(MUGGED);
+        state.write_u32(self.mug);
+    }
+}
+
+impl PartialEq for MuggedNoun {
+    fn eq(&self, other: &Self) -> bool {
+        self.mug == other.mug && self.noun == other.noun
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{atom::Atom, cell::Cell};
+    use std::{
+        collections::{HashMap, HashSet},
+        hash::BuildHasher,
+    };
+
+    #[test]
+    fn mug_hasher() {
+        let build = BuildMugHasher::default();
+        let nouns = [
+            (Noun::null(), 2_046_756_072),
+            (Noun::from(Atom::from(1u128 << 64)), 648_482_943),
+            (Noun::from("~zod"), 1_865_551_628),
+            (Noun::from(Cell::from([0u8, 0u8])), 422_532_488),
+            (
+                Noun::from(Cell::from([
+                    Noun::from(Cell::from([1u8, 2u8])),
+                    Noun::from(3u8),
+                ])),
+                797_794_207,
+            ),
+            (
+                Noun::from(Cell::from(["hello", "world", ""])),
+                1_113_827_290,
+            ),
+        ];
+
+        // Nouns, pointers to them, and mugged nouns hash to their mugs, which are the same in
+        // every process.
+        {
+            for (noun, mug) in &nouns {
+                assert_eq!(noun.mug(), *mug);
+                let rc = Rc::new(noun.clone());
+                let mugged = MuggedNoun::from(rc.clone());
+                assert_eq!(mugged.mug(), *mug);
+                assert_eq!(build.hash_one(noun), u64::from(*mug));
+                assert_eq!(build.hash_one(&rc), u64::from(*mug));
+                assert_eq!(build.hash_one(&mugged), u64::from(*mug));
+            }
+        }
+
+        // Nouns with shared and deep subnouns.
+        {
+            let shared = Rc::new(Noun::from(Cell::from(["a", "b"])));
+            let mut noun = Noun::from(Cell::from([shared.clone(), shared]));
+            for i in 0..100u8 {
+                noun = Noun::from(Cell::from([Noun::from(i), noun]));
+            }
+            assert_eq!(build.hash_one(&noun), u64::from(noun.mug()));
+        }
+
+        // A tuple of nouns hashes as a cell of them, and other keys hash deterministically.
+        {
+            let (a, b) = (&nouns[1].0, &nouns[3].0);
+            let cell = Noun::from(Cell::from([a.clone(), b.clone()]));
+            assert_eq!(build.hash_one((a, b)), u64::from(cell.mug()));
+            assert_eq!(build.hash_one(1u64), u64::from(Atom::from(1u8).mug()));
+            assert_eq!(build.hash_one("~zod"), build.hash_one("~zod"));
+            assert_eq!(MugHasher::default().finish(), 0);
+        }
+
+        // A map keyed by mug finds its keys by noun, pointer, or mugged noun.
+        {
+            let mut map: HashMap<Rc<Noun>, usize, BuildMugHasher> = HashMap::default();
+            let mut mugged: HashSet<MuggedNoun, BuildMugHasher> = HashSet::default();
+            for (i, (noun, _)) in nouns.iter().enumerate() {
+                map.insert(Rc::new(noun.clone()), i);
+                mugged.insert(MuggedNoun::from(noun.clone()));
+            }
+            for (i, (noun, _)) in nouns.iter().enumerate() {
+                assert_eq!(map.get(noun), Some(&i));
+                assert_eq!(map.get(&Rc::new(noun.clone())), Some(&i));
+                assert!(mugged.contains(&MuggedNoun::from(noun.clone())));
+            }
+            assert_eq!(map.get(&Noun::from(7u8)), None);
+            assert!(!mugged.contains(&MuggedNoun::from(Noun::from(7u8))));
+        }
+    }
+}
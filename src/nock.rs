@@ -0,0 +1,530 @@
+//! A minimal Nock 4K interpreter.
+//!
+//! [`nock()`] computes `*[subject formula]` following the [Nock 4K specification], which is enough
+//! to test jets against the formulas they replace and to replay simple kernels without a full
+//! runtime. It has no jets and no memoization, so it's slow, but it doesn't recurse: the formula
+//! being evaluated is driven by an explicit stack, so deep computations don't overflow the native
+//! stack. Every formula evaluated costs one unit of fuel, and running out of fuel stops the
+//! computation, which bounds both the time and the memory a computation can take.
+//!
+//! Hints (opcode 11) are transparent: a dynamic hint's formula is evaluated, and can crash, but its
+//! product is otherwise ignored.
+//!
+//! [Nock 4K specification]: https://urbit.org/docs/nock/definition
+
+use crate::{atom::Atom, cell::Cell, noun::Noun, Rc};
+use std::fmt::{self, Display, Formatter};
+
+/// The ways a Nock computation can crash.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum NockError {
+    /// A formula doesn't have the shape of any Nock formula, or its opcode is greater than 11.
+    InvalidFormula,
+    /// An axis is `0`, is too large to represent, or isn't in the noun it's applied to.
+    InvalidAxis,
+    /// Opcode 4 incremented a cell.
+    IncrementCell,
+    /// The condition of opcode 6 is neither `0` nor `1`.
+    NotLoobean,
+    /// The computation ran out of fuel.
+    OutOfFuel,
+}
+
+impl Display for NockError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        match self {
+            Self::InvalidFormula => write!(f, "the formula is not a valid Nock formula"),
+            Self::InvalidAxis => write!(f, "the axis is not in the noun"),
+            Self::IncrementCell => write!(f, "a cell cannot be incremented"),
+            Self::NotLoobean => write!(f, "the condition is neither 0 nor 1"),
+            Self::OutOfFuel => write!(f, "the computation ran out of fuel"),
+        }
+    }
+}
+
+impl std::error::Error for NockError {}
+
+/// A step of the computation on the work stack.
+enum Task {
+    /// Evaluates a formula against a subject and pushes the product.
+    Eval(Rc<Noun>, Rc<Noun>),
+    /// Pops a tail and a head and pushes the cell of them.
+    Cons,
+    /// Pops a formula and a subject and evaluates the formula against the subject (opcode 2).
+    Nock,
+    /// Pops a noun and pushes whether it's a cell (opcode 3).
+    Wut,
+    /// Pops an atom and pushes its increment (opcode 4).
+    Lus,
+    /// Pops two nouns and pushes whether they're equal (opcode 5).
+    Tis,
+    /// Pops a condition and evaluates one of two formulas against the subject (opcode 6).
+    If(Rc<Noun>, Rc<Noun>, Rc<Noun>),
+    /// Pops a subject and evaluates a formula against it (opcode 7).
+    Compose(Rc<Noun>),
+    /// Pops a noun and evaluates a formula against it pinned to the head of the subject (opcode 8).
+    Push(Rc<Noun>, Rc<Noun>),
+    /// Pops a core and evaluates the arm at an axis of it against it (opcode 9).
+    Arm(u64),
+    /// Pops a target and a replacement and pushes the target with the replacement at an axis
+    /// (opcode 10).
+    Edit(u64),
+    /// Pops the product of a hinted formula and drops the product of the hint under it (opcode
+    /// 11).
+    Hint,
+}
+
+/// Computes `*[subject formula]`, spending at most `fuel` steps.
+///
+/// Each formula evaluated, including each formula nested in another, is one step. Pass
+/// [`u64::MAX`] to run a computation that's known to terminate to completion.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{cell::Cell, nock::{nock, NockError}, Noun, Rc};
+/// // *[42 [8 [4 0 1] [0 1]]]
+/// let formula = Noun::from(Cell::from([
+///     Noun::from(8u8),
+///     Noun::from(Cell::from([4u8, 0, 1])),
+///     Noun::from(Cell::from([0u8, 1])),
+/// ]));
+/// let product = nock(Rc::new(Noun::from(42u8)), Rc::new(formula), u64::MAX).unwrap();
+/// assert_eq!(*product, Noun::from(Cell::from([43u8, 42])));
+///
+/// // *[[2 [0 1] 0 1] [2 [0 1] 0 1]] evaluates itself forever.
+/// let formula = Rc::new(Noun::from(Cell::from([
+///     Noun::from(2u8),
+///     Noun::from(Cell::from([0u8, 1])),
+///     Noun::from(Cell::from([0u8, 1])),
+/// ])));
+/// assert_eq!(nock(formula.clone(), formula, 10_000), Err(NockError::OutOfFuel));
+/// ```
+pub fn nock(subject: Rc<Noun>, formula: Rc<Noun>, mut fuel: u64) -> Result<Rc<Noun>, NockError> {
+    let mut tasks = vec![Task::Eval(subject, formula)];
+    let mut products: Vec<Rc<Noun>> = Vec::new();
+    while let Some(task) = tasks.pop() {
+        match task {
+            Task::Eval(subject, formula) => {
+                fuel = fuel.checked_sub(1).ok_or(NockError::OutOfFuel)?;
+                eval(subject, &formula, &mut tasks, &mut products)?;
+            }
+            Task::Cons => {
+                let (head, tail) = pop_pair(&mut products);
+                products.push(Rc::new(Noun::from(Cell::from([head, tail]))));
+            }
+            Task::Nock => {
+                let (subject, formula) = pop_pair(&mut products);
+                tasks.push(Task::Eval(subject, formula));
+            }
+            Task::Wut => {
+                let noun = pop(&mut products);
+                products.push(loobean(matches!(*noun, Noun::Cell(_))));
+            }
+            Task::Lus => match &*pop(&mut products) {
+                Noun::Atom(atom) => products.push(Rc::new(Noun::from(increment(atom)))),
+                Noun::Cell(_) => return Err(NockError::IncrementCell),
+            },
+            Task::Tis => {
+                let (a, b) = pop_pair(&mut products);
+                products.push(loobean(equal(&a, &b)));
+            }
+            Task::If(subject, yes, no) => match &*pop(&mut products) {
+                Noun::Atom(atom) if atom.is_null() => tasks.push(Task::Eval(subject, yes)),
+                Noun::Atom(atom) if atom == &1u8 => tasks.push(Task::Eval(subject, no)),
+                _ => return Err(NockError::NotLoobean),
+            },
+            Task::Compose(formula) => {
+                let subject = pop(&mut products);
+                tasks.push(Task::Eval(subject, formula));
+            }
+            Task::Push(subject, formula) => {
+                let head = pop(&mut products);
+                let subject = Rc::new(Noun::from(Cell::from([head, subject])));
+                tasks.push(Task::Eval(subject, formula));
+            }
+            Task::Arm(axis) => {
+                let core = pop(&mut products);
+                let arm = slot(&core, axis)?;
+                tasks.push(Task::Eval(core, arm));
+            }
+            Task::Edit(axis) => {
+                let (replacement, target) = pop_pair(&mut products);
+                products.push(edit(&target, axis, replacement)?);
+            }
+            Task::Hint => {
+                let (_, product) = pop_pair(&mut products);
+                products.push(product);
+            }
+        }
+    }
+    Ok(pop(&mut products))
+}
+
+/// Schedules the evaluation of `formula` against `subject`, pushing its product directly if it
+/// needs no further evaluation.
+fn eval(
+    subject: Rc<Noun>,
+    formula: &Noun,
+    tasks: &mut Vec<Task>,
+    products: &mut Vec<Rc<Noun>>,
+) -> Result<(), NockError> {
+    let Noun::Cell(formula) = formula else {
+        return Err(NockError::InvalidFormula);
+    };
+    let args = formula.tail();
+    let opcode = match formula.head_ref() {
+        Noun::Atom(opcode) => opcode.as_u8().ok_or(NockError::InvalidFormula)?,
+        // *[a [b c] d] -> [*[a b c] *[a d]]
+        Noun::Cell(_) => {
+            tasks.push(Task::Cons);
+            tasks.push(Task::Eval(subject.clone(), args));
+            tasks.push(Task::Eval(subject, formula.head()));
+            return Ok(());
+        }
+    };
+    match opcode {
+        // *[a 0 b] -> /[b a]
+        0 => products.push(slot(&subject, axis(&args)?)?),
+        // *[a 1 b] -> b
+        1 => products.push(args),
+        // *[a 2 b c] -> *[*[a b] *[a c]]
+        2 => {
+            let (b, c) = split(&args)?;
+            tasks.push(Task::Nock);
+            tasks.push(Task::Eval(subject.clone(), c));
+            tasks.push(Task::Eval(subject, b));
+        }
+        // *[a 3 b] -> ?*[a b]
+        3 => {
+            tasks.push(Task::Wut);
+            tasks.push(Task::Eval(subject, args));
+        }
+        // *[a 4 b] -> +*[a b]
+        4 => {
+            tasks.push(Task::Lus);
+            tasks.push(Task::Eval(subject, args));
+        }
+        // *[a 5 b c] -> =[*[a b] *[a c]]
+        5 => {
+            let (b, c) = split(&args)?;
+            tasks.push(Task::Tis);
+            tasks.push(Task::Eval(subject.clone(), c));
+            tasks.push(Task::Eval(subject, b));
+        }
+        // *[a 6 b c d] -> *[a *[[c d] 0 *[[2 3] 0 *[a 4 4 b]]]]
+        6 => {
+            let (b, cd) = split(&args)?;
+            let (c, d) = split(&cd)?;
+            tasks.push(Task::If(subject.clone(), c, d));
+            tasks.push(Task::Eval(subject, b));
+        }
+        // *[a 7 b c] -> *[*[a b] c]
+        7 => {
+            let (b, c) = split(&args)?;
+            tasks.push(Task::Compose(c));
+            tasks.push(Task::Eval(subject, b));
+        }
+        // *[a 8 b c] -> *[[*[a b] a] c]
+        8 => {
+            let (b, c) = split(&args)?;
+            tasks.push(Task::Push(subject.clone(), c));
+            tasks.push(Task::Eval(subject, b));
+        }
+        // *[a 9 b c] -> *[*[a c] 2 [0 1] 0 b]
+        9 => {
+            let (b, c) = split(&args)?;
+            tasks.push(Task::Arm(axis(&b)?));
+            tasks.push(Task::Eval(subject, c));
+        }
+        // *[a 10 [b c] d] -> #[b *[a c] *[a d]]
+        10 => {
+            let (bc, d) = split(&args)?;
+            let (b, c) = split(&bc)?;
+            tasks.push(Task::Edit(axis(&b)?));
+            tasks.push(Task::Eval(subject.clone(), d));
+            tasks.push(Task::Eval(subject, c));
+        }
+        11 => {
+            let (hint, formula) = split(&args)?;
+            match &*hint {
+                // *[a 11 [b c] d] -> *[[*[a c] *[a d]] 0 3]
+                Noun::Cell(hint) => {
+                    tasks.push(Task::Hint);
+                    tasks.push(Task::Eval(subject.clone(), formula));
+                    tasks.push(Task::Eval(subject, hint.tail()));
+                }
+                // *[a 11 b c] -> *[a c]
+                Noun::Atom(_) => tasks.push(Task::Eval(subject, formula)),
+            }
+        }
+        _ => return Err(NockError::InvalidFormula),
+    }
+    Ok(())
+}
+
+/// Splits the arguments of a formula into a head and a tail.
+fn split(args: &Noun) -> Result<(Rc<Noun>, Rc<Noun>), NockError> {
+    match args {
+        Noun::Cell(cell) => Ok((cell.head(), cell.tail())),
+        Noun::Atom(_) => Err(NockError::InvalidFormula),
+    }
+}
+
+/// Reads an axis, which must be an atom.
+fn axis(noun: &Noun) -> Result<u64, NockError> {
+    match noun {
+        Noun::Atom(atom) => atom.as_u64().ok_or(NockError::InvalidAxis),
+        Noun::Cell(_) => Err(NockError::InvalidFormula),
+    }
+}
+
+/// Returns the subnoun of `noun` at `axis`, `/[axis noun]`.
+fn slot(noun: &Rc<Noun>, axis: u64) -> Result<Rc<Noun>, NockError> {
+    if axis == 0 {
+        return Err(NockError::InvalidAxis);
+    }
+    let mut noun = noun.clone();
+    // The most significant bit of the axis is implicit because it's always 1.
+    for i in (0..u64::BITS - 1 - axis.leading_zeros()).rev() {
+        noun = match &*noun {
+            Noun::Atom(_) => return Err(NockError::InvalidAxis),
+            Noun::Cell(cell) if axis & (1 << i) == 0 => cell.head(),
+            Noun::Cell(cell) => cell.tail(),
+        };
+    }
+    Ok(noun)
+}
+
+/// Replaces the subnoun of `noun` at `axis` with `replacement`, `#[axis replacement noun]`.
+fn edit(noun: &Rc<Noun>, axis: u64, replacement: Rc<Noun>) -> Result<Rc<Noun>, NockError> {
+    if axis == 0 {
+        return Err(NockError::InvalidAxis);
+    }
+    let mut spine = Vec::new();
+    let mut noun = noun.clone();
+    for i in (0..u64::BITS - 1 - axis.leading_zeros()).rev() {
+        let go_tail = axis & (1 << i) != 0;
+        let next = match &*noun {
+            Noun::Atom(_) => return Err(NockError::InvalidAxis),
+            Noun::Cell(cell) if go_tail => cell.tail(),
+            Noun::Cell(cell) => cell.head(),
+        };
+        spine.push((noun, go_tail));
+        noun = next;
+    }
+    let mut edited = replacement;
+    while let Some((noun, go_tail)) = spine.pop() {
+        let Noun::Cell(cell) = &*noun else {
+            unreachable!("the spine is made of cells");
+        };
+        let pair = if go_tail {
+            [cell.head(), edited]
+        } else {
+            [edited, cell.tail()]
+        };
+        edited = Rc::new(Noun::from(Cell::from(pair)));
+    }
+    Ok(edited)
+}
+
+/// Returns `true` if two nouns are equal, without recursing.
+fn equal(a: &Rc<Noun>, b: &Rc<Noun>) -> bool {
+    let mut pairs = vec![(a.clone(), b.clone())];
+    while let Some((a, b)) = pairs.pop() {
+        if Rc::ptr_eq(&a, &b) {
+            continue;
+        }
+        match (&*a, &*b) {
+            (Noun::Atom(a), Noun::Atom(b)) if a == b => {}
+            (Noun::Cell(a), Noun::Cell(b)) => {
+                pairs.push((a.tail(), b.tail()));
+                pairs.push((a.head(), b.head()));
+            }
+            _ => return false,
+        }
+    }
+    true
+}
+
+/// Returns the increment of an atom.
+fn increment(atom: &Atom) -> Atom {
+    let mut bytes = atom.to_vec();
+    for byte in bytes.iter_mut() {
+        let (sum, carry) = byte.overflowing_add(1);
+        *byte = sum;
+        if !carry {
+            return Atom::from(bytes);
+        }
+    }
+    bytes.push(1);
+    Atom::from(bytes)
+}
+
+/// Converts a boolean into a loobean, where `0` is yes and `1` is no.
+fn loobean(yes: bool) -> Rc<Noun> {
+    Rc::new(Noun::from(Atom::from(u8::from(!yes))))
+}
+
+/// Pops the product on top of the stack.
+fn pop(products: &mut Vec<Rc<Noun>>) -> Rc<Noun> {
+    products.pop().expect("product")
+}
+
+/// Pops the two products on top of the stack, returning the one pushed first first.
+fn pop_pair(products: &mut Vec<Rc<Noun>>) -> (Rc<Noun>, Rc<Noun>) {
+    let second = pop(products);
+    let first = pop(products);
+    (first, second)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Parses a noun written the way the Nock specification writes them, like `[0 [1 2] 3]`.
+    fn parse(src: &str) -> Rc<Noun> {
+        let mut stack: Vec<Vec<Rc<Noun>>> = vec![Vec::new()];
+        let mut digits = String::new();
+        for ch in src.chars().chain([' ']) {
+            if ch.is_ascii_digit() {
+                digits.push(ch);
+                continue;
+            }
+            if !digits.is_empty() {
+                let atom = Atom::from(digits.parse::<u128>().expect("atom"));
+                stack
+                    .last_mut()
+                    .expect("open")
+                    .push(Rc::new(Noun::from(atom)));
+                digits.clear();
+            }
+            match ch {
+                '[' => stack.push(Vec::new()),
+                ']' => {
+                    let mut nouns = stack.pop().expect("open");
+                    let mut noun = nouns.pop().expect("tail");
+                    while let Some(head) = nouns.pop() {
+                        noun = Rc::new(Noun::from(Cell::from([head, noun])));
+                    }
+                    stack.last_mut().expect("open").push(noun);
+                }
+                _ => {}
+            }
+        }
+        stack.pop().expect("noun").pop().expect("noun")
+    }
+
+    const DEC: &str = "[8 [1 0] 8 [1 6 [5 [0 7] 4 0 6] [0 6] 9 2 [0 2] [4 0 6] 0 7] 9 2 0 1]";
+
+    #[test]
+    fn nock() {
+        // Examples from the Nock specification and its tutorials.
+        {
+            let cases = [
+                ("[[4 5] [6 14 15]]", "[0 7]", "[14 15]"),
+                ("[132 19]", "[0 3]", "19"),
+                ("42", "[1 153 218]", "[153 218]"),
+                ("77", "[2 [1 42] [1 1 153 218]]", "[153 218]"),
+                ("42", "[3 0 1]", "1"),
+                ("[42 43]", "[3 0 1]", "0"),
+                ("57", "[4 0 1]", "58"),
+                ("[132 19]", "[4 0 3]", "20"),
+                ("[1 1]", "[5 [0 2] [0 3]]", "0"),
+                ("[1 2]", "[5 [0 2] [0 3]]", "1"),
+                ("42", "[[4 0 1] [3 0 1]]", "[43 1]"),
+                ("42", "[6 [1 0] [4 0 1] [1 233]]", "43"),
+                ("42", "[6 [1 1] [4 0 1] [1 233]]", "233"),
+                ("42", "[7 [4 0 1] [4 0 1]]", "44"),
+                ("42", "[8 [4 0 1] [0 1]]", "[43 42]"),
+                ("42", "[8 [4 0 1] [4 0 3]]", "43"),
+                ("[1 2]", "[10 [2 [1 99]] [0 1]]", "[99 2]"),
+                ("[[1 2] 3]", "[10 [5 [4 0 3]] [0 1]]", "[[1 4] 3]"),
+                ("42", "[10 [1 [1 99]] [0 1]]", "99"),
+                ("42", "[11 1 [4 0 1]]", "43"),
+                ("42", "[11 [1 [1 7]] [4 0 1]]", "43"),
+                ("42", DEC, "41"),
+                // A core with an arm at axis 2 that returns its payload.
+                ("0", "[9 2 [1 [0 3] 17]]", "17"),
+            ];
+            for (subject, formula, product) in cases {
+                assert_eq!(
+                    super::nock(parse(subject), parse(formula), u64::MAX),
+                    Ok(parse(product)),
+                    "*[{} {}]",
+                    subject,
+                    formula
+                );
+            }
+        }
+
+        // Increment carries across bytes.
+        {
+            let cases = [
+                (Atom::from(0xffu8), Atom::from(0x100u16)),
+                (Atom::from(u64::MAX), Atom::from(1u128 << 64)),
+                (Atom::null(), Atom::from(1u8)),
+            ];
+            for (atom, incremented) in cases {
+                let subject = Rc::new(Noun::from(atom));
+                assert_eq!(
+                    *super::nock(subject, parse("[4 0 1]"), u64::MAX).expect("increment"),
+                    Noun::from(incremented)
+                );
+            }
+        }
+
+        // Long computations don't overflow the stack.
+        {
+            let product = super::nock(parse("100000"), parse(DEC), u64::MAX).expect("dec");
+            assert_eq!(*product, Noun::from(99_999u32));
+        }
+    }
+
+    #[test]
+    fn nock_errors() {
+        let cases = [
+            ("42", "[0 0]", NockError::InvalidAxis),
+            ("42", "[0 2]", NockError::InvalidAxis),
+            ("[1 2]", "[0 4]", NockError::InvalidAxis),
+            ("42", "[0 18446744073709551616]", NockError::InvalidAxis),
+            ("[1 2]", "[10 [0 [1 3]] [0 1]]", NockError::InvalidAxis),
+            ("42", "[10 [2 [1 3]] [0 1]]", NockError::InvalidAxis),
+            ("[1 2]", "[4 0 1]", NockError::IncrementCell),
+            ("42", "[6 [1 2] [1 0] [1 1]]", NockError::NotLoobean),
+            ("42", "42", NockError::InvalidFormula),
+            ("42", "[12 0 1]", NockError::InvalidFormula),
+            ("42", "[2 0 1]", NockError::InvalidFormula),
+            ("42", "[6 [1 0] 1]", NockError::InvalidFormula),
+            ("42", "[[0 1] 12 0]", NockError::InvalidFormula),
+            // A crashing dynamic hint crashes the computation.
+            ("42", "[11 [1 [0 0]] [4 0 1]]", NockError::InvalidAxis),
+            // Decrementing 0 loops forever.
+            ("0", DEC, NockError::OutOfFuel),
+            // So does a formula that evaluates itself.
+            ("[2 [0 1] 0 1]", "[2 [0 1] 0 1]", NockError::OutOfFuel),
+        ];
+        for (subject, formula, err) in cases {
+            assert_eq!(
+                super::nock(parse(subject), parse(formula), 100_000),
+                Err(err),
+                "*[{} {}]",
+                subject,
+                formula
+            );
+        }
+
+        // Fuel counts every formula evaluated.
+        {
+            assert_eq!(
+                super::nock(parse("42"), parse("[4 0 1]"), 2),
+                Ok(parse("43"))
+            );
+            assert_eq!(
+                super::nock(parse("42"), parse("[4 0 1]"), 1),
+                Err(NockError::OutOfFuel)
+            );
+        }
+    }
+}
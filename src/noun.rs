@@ -1,18 +1,43 @@
 use crate::{
-    atom::{Atom, Builder as AtomBuilder, Iter as AtomIter},
+    atom::{self, Atom, Iter as AtomIter},
+    axis::peg,
     cell::Cell,
-    convert,
-    serdes::{self, Cue, Jam},
+    convert, mug,
+    serdes::{
+        self,
+        cue::ReadSource,
+        jam::{CapExceeded, CappedSink, SizeSink, WriteSink},
+        Cue, CueCache, CueLimits, CueProgress, CueStats, Jam, JamProgress, JamReader, JamStats,
+        Progress, ProgressOptions,
+    },
+    shape::{Shape, ShapeError},
     Rc,
 };
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     fmt::{Display, Error, Formatter},
-    mem::drop,
+    hash::{Hash, Hasher},
+    io::{self, Read, Write},
+    iter::FusedIterator,
+    ops::ControlFlow,
+    ptr,
 };
 
 /// An [`Atom`] or a [`Cell`].
-#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+///
+/// Nouns are hashed and compared structurally, and [`Rc<Noun>`](crate::Rc) and `&Noun` hash and
+/// compare exactly like the nouns they point to, so a map or set keyed by `Rc<Noun>` can be
+/// queried with a `&Noun` without allocating:
+///
+/// ```
+/// # use noun::{cell::Cell, Noun, Rc};
+/// # use std::collections::HashMap;
+/// let mut map = HashMap::new();
+/// map.insert(Rc::new(Noun::from(Cell::from([1u8, 2u8]))), "a");
+/// let needle = Noun::from(Cell::from([1u8, 2u8]));
+/// assert_eq!(map.get::<Noun>(&needle), Some(&"a"));
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub enum Noun {
     /// An arbitrarily large unsigned integer.
     Atom(Atom),
@@ -35,6 +60,36 @@ impl Noun {
         }
     }
 
+    /// Returns `true` if this noun is a direct atom, as defined by [`Atom::is_direct()`].
+    pub fn is_direct_atom(&self) -> bool {
+        if let Noun::Atom(atom) = self {
+            atom.is_direct()
+        } else {
+            false
+        }
+    }
+
+    /// Creates the null-terminated list `[a1 a2 ... aN 0]` of a vector of nouns.
+    ///
+    /// No nouns is the empty list `0`, and one noun `x` is `[x 0]`. This differs from the pair
+    /// chain that [`Cell::try_from_vec()`](crate::cell::Cell::try_from_vec()) makes
+    /// of the same nouns, `[a1 a2 ... aN]`, whose last noun is the tail of the innermost cell.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::{cell::Cell, Noun, Rc};
+    /// let nouns = || vec![Rc::new(Noun::from(1u8)), Rc::new(Noun::from(2u8))];
+    /// assert_eq!(Noun::list_from(nouns()), Noun::from(Cell::from([1u8, 2, 0])));
+    /// assert_eq!(Noun::from(Cell::from(nouns())), Noun::from(Cell::from([1u8, 2])));
+    /// assert_eq!(Noun::list_from(Vec::new()), Noun::null());
+    /// ```
+    pub fn list_from(nouns: Vec<Rc<Noun>>) -> Self {
+        nouns.into_iter().rev().fold(Self::null(), |list, elem| {
+            Self::from(Cell::from([elem, Rc::new(list)]))
+        })
+    }
+
     /// Computes the hash of this noun.
     pub fn hash(&self) -> u64 {
         match self {
@@ -42,96 +97,1223 @@ impl Noun {
             Self::Cell(cell) => cell.hash(),
         }
     }
-}
 
-impl Cue for Noun {
-    fn cue(jammed_noun: Atom) -> serdes::Result<Self> {
-        fn decode_atom(bits: &mut AtomIter) -> serdes::Result<Atom> {
-            let len = {
-                let mut len_of_len = 0;
-                loop {
-                    match bits.next() {
-                        Some(true) => break,
-                        Some(false) => len_of_len += 1,
-                        None => return Err(serdes::Error::InvalidLen),
+    /// Computes the mug of this noun, Urbit's 31-bit hash of a noun.
+    ///
+    /// The mug of an atom is [`Atom::mug()`], and the mug of a cell hashes the mugs of its head and
+    /// tail. Mugs agree with Hoon's `++mug`, so they're the same on every platform and in every
+    /// process, and they determine the order of Hoon's maps and sets.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::{atom::Atom, cell::Cell, Noun};
+    /// assert_eq!(Noun::null().mug(), 2_046_756_072);
+    /// assert_ne!(Noun::from(Cell::from([1u8, 2u8])).mug(), Noun::from(Cell::from([2u8, 1u8])).mug());
+    /// ```
+    pub fn mug(&self) -> u32 {
+        // Computes mugs in post-order without recursing, since nouns can be arbitrarily deep.
+        let mut stack = vec![(self, false)];
+        let mut mugs = Vec::new();
+        while let Some((noun, children_mugged)) = stack.pop() {
+            match noun {
+                Self::Atom(atom) => mugs.push(atom.mug()),
+                Self::Cell(_) if children_mugged => {
+                    let tail = mugs.pop().expect("tail mug");
+                    let head = mugs.pop().expect("head mug");
+                    mugs.push(mug_both(head, tail));
+                }
+                Self::Cell(cell) => {
+                    stack.push((noun, true));
+                    stack.push((cell.tail_ref(), false));
+                    stack.push((cell.head_ref(), false));
+                }
+            }
+        }
+        mugs.pop().expect("mug")
+    }
+
+    /// Checks that every atom of this noun upholds the invariants that equality, hashing, and jam
+    /// rely on: no trailing zero bytes, and a cached length in bits that matches the atom's bytes.
+    ///
+    /// Atoms built through this crate always uphold them, so this is for nouns whose atoms came
+    /// from somewhere that might not, such as across an FFI boundary. The first atom in pre-order
+    /// that violates them is reported, along with its axis. Shared subnouns are checked once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::{cell::Cell, Noun};
+    /// assert_eq!(Noun::from(Cell::from([1u8, 2u8])).validate(), Ok(()));
+    /// ```
+    pub fn validate(&self) -> Result<(), InvariantError> {
+        let mut validated: HashSet<*const Noun> = HashSet::new();
+        let mut stack = vec![(self, Some(1))];
+        while let Some((noun, axis)) = stack.pop() {
+            if !validated.insert(ptr::addr_of!(*noun)) {
+                continue;
+            }
+            match noun {
+                Self::Atom(atom) => {
+                    if atom.has_trailing_zeros() {
+                        return Err(InvariantError::TrailingZeros { axis });
                     }
+                    let actual = atom.bytes_bit_len();
+                    if atom.bit_len_u64() != actual {
+                        return Err(InvariantError::BitLen {
+                            axis,
+                            cached: atom.bit_len_u64(),
+                            actual,
+                        });
+                    }
+                }
+                Self::Cell(cell) => {
+                    stack.push((cell.tail_ref(), axis.and_then(|axis| peg(axis, 3))));
+                    stack.push((cell.head_ref(), axis.and_then(|axis| peg(axis, 2))));
                 }
+            }
+        }
+        Ok(())
+    }
 
-                if len_of_len == 0 {
-                    0
-                } else {
-                    // The most significant bit of the length is implicit because it's always 1.
-                    let len_bits = len_of_len - 1;
-                    let mut len: u64 = 1 << len_bits;
-                    for i in 0..len_bits {
-                        match bits.next() {
-                            Some(true) => len |= 1 << i,
-                            Some(false) => len &= !(1 << i),
-                            None => return Err(serdes::Error::InvalidLen),
-                        }
+    /// Returns `true` if this noun matches `shape`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::{cell::Cell, shape::Shape, Noun};
+    /// let shape: Shape = "[@ @ [* ~]]".parse().unwrap();
+    /// assert!(Noun::from(Cell::from([1u8, 2u8, 3u8, 0u8])).matches(&shape));
+    /// assert!(!Noun::from(Cell::from([1u8, 2u8, 3u8])).matches(&shape));
+    /// ```
+    pub fn matches(&self, shape: &Shape) -> bool {
+        shape.check(self).is_ok()
+    }
+
+    /// Checks that this noun matches `shape`, reporting the axis of the first subnoun in pre-order
+    /// that doesn't, along with the kind of noun the shape expected there and the kind it found.
+    ///
+    /// Lists are checked one element at a time, so a long list doesn't overflow the stack.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::{cell::Cell, shape::{Kind, Shape}, Noun};
+    /// let shape: Shape = "(list @)".parse().unwrap();
+    /// let err = Noun::from(Cell::from([1u8, 2u8, 3u8])).check(&shape).unwrap_err();
+    /// assert_eq!(err.axis(), Some(7));
+    /// assert_eq!((err.expected(), err.found()), (Kind::Null, Kind::Atom));
+    /// ```
+    pub fn check(&self, shape: &Shape) -> Result<(), ShapeError> {
+        shape.check(self)
+    }
+
+    /// Fixes every atom of this noun that fails [`validate()`](Self::validate()).
+    ///
+    /// Only the offending atoms and the cells on the paths to them are rebuilt; every other
+    /// subnoun is shared with this noun, and a noun with nothing to fix is returned as is.
+    pub fn normalize(self) -> Self {
+        // Rebuilds in post-order without recursing, since nouns can be arbitrarily deep. Each
+        // distinct subnoun maps to its replacement, or to `None` if it didn't need one.
+        let mut rebuilt: HashMap<*const Noun, Option<Rc<Noun>>> = HashMap::new();
+        let mut stack = vec![(&self, false)];
+        while let Some((noun, children_rebuilt)) = stack.pop() {
+            let addr = ptr::addr_of!(*noun);
+            if !children_rebuilt && rebuilt.contains_key(&addr) {
+                continue;
+            }
+            let replacement = match noun {
+                Self::Atom(atom) => {
+                    (!atom.is_normalized()).then(|| Rc::new(Self::from(atom.normalized())))
+                }
+                Self::Cell(cell) if children_rebuilt => {
+                    let head = rebuilt[&ptr::addr_of!(*cell.head_ref())].clone();
+                    let tail = rebuilt[&ptr::addr_of!(*cell.tail_ref())].clone();
+                    if head.is_none() && tail.is_none() {
+                        None
+                    } else {
+                        Some(Rc::new(Self::from(Cell::from([
+                            head.unwrap_or_else(|| cell.head()),
+                            tail.unwrap_or_else(|| cell.tail()),
+                        ]))))
                     }
-                    len
+                }
+                Self::Cell(cell) => {
+                    stack.push((noun, true));
+                    stack.push((cell.tail_ref(), false));
+                    stack.push((cell.head_ref(), false));
+                    continue;
                 }
             };
-            if len == 0 {
-                Ok(Atom::from(0u8))
-            } else {
-                let mut atom_builder = Atom::builder();
-                for _ in 0..len {
-                    let bit = bits.next().ok_or(serdes::Error::AtomBuilding)?;
-                    atom_builder.push_bit(bit);
+            rebuilt.insert(addr, replacement);
+        }
+        match rebuilt.remove(&ptr::addr_of!(self)).flatten() {
+            Some(noun) => {
+                drop(rebuilt);
+                Rc::try_unwrap(noun).unwrap_or_else(|noun| (*noun).clone())
+            }
+            None => self,
+        }
+    }
+
+    /// Replaces every subnoun of this noun that's equal to `target` with `replacement`, returning
+    /// the new noun and the number of subnouns replaced.
+    ///
+    /// Every replaced subnoun shares `replacement`, which isn't searched for `target` itself, and
+    /// neither is a subnoun once it's been replaced. Cells with nothing to replace are shared with
+    /// this noun, so a noun with no matches comes back as a clone that shares its head and tail.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::{cell::Cell, Noun, Rc};
+    /// let noun = Noun::from(Cell::from(["~zod", "said", "~zod"]));
+    /// let (anonymized, count) = noun.replace_all(&Noun::from("~zod"), Rc::new(Noun::from("~")));
+    /// assert_eq!(anonymized, Noun::from(Cell::from(["~", "said", "~"])));
+    /// assert_eq!(count, 2);
+    /// ```
+    pub fn replace_all(&self, target: &Noun, replacement: Rc<Noun>) -> (Self, usize) {
+        // Rebuilds in post-order without recursing, like `normalize()`. Each distinct subnoun maps
+        // to its replacement, or to `None` if it doesn't have one, and to the number of matches
+        // within it, which counts a shared subnoun once for each place it appears.
+        let mut rebuilt: HashMap<*const Noun, (Option<Rc<Noun>>, usize)> = HashMap::new();
+        let mut stack = vec![(self, false)];
+        while let Some((noun, children_rebuilt)) = stack.pop() {
+            let addr = ptr::addr_of!(*noun);
+            if !children_rebuilt && rebuilt.contains_key(&addr) {
+                continue;
+            }
+            let entry = match noun {
+                _ if !children_rebuilt && (ptr::eq(noun, target) || noun == target) => {
+                    (Some(replacement.clone()), 1)
                 }
-                Ok(atom_builder.into_atom())
-            }
-        }
-
-        fn decode(
-            bits: &mut AtomIter,
-            cache: &mut HashMap<u64, Rc<Noun>>,
-        ) -> serdes::Result<Rc<Noun>> {
-            let pos = bits.pos() as u64;
-            match bits.next() {
-                Some(true) => {
-                    match bits.next() {
-                        // Back reference tag = 0b11.
-                        Some(true) => {
-                            let idx = decode_atom(bits)?
-                                .as_u64()
-                                .ok_or(serdes::Error::InvalidBackref)?;
-                            let noun = cache.get(&idx).ok_or(serdes::Error::CacheMiss)?;
-                            Ok(noun.clone())
-                        }
-                        // Cell tag = 0b01.
-                        Some(false) => {
-                            let head = decode(bits, cache)?;
-                            let tail = decode(bits, cache)?;
-
-                            let cell = Rc::<Noun>::from(Cell::from([head, tail]));
-                            cache.insert(pos, cell.clone());
-
-                            Ok(cell)
-                        }
-                        None => Err(serdes::Error::InvalidTag),
-                    }
+                Self::Atom(_) => (None, 0),
+                Self::Cell(cell) if children_rebuilt => {
+                    let (head, head_count) = rebuilt[&ptr::addr_of!(*cell.head_ref())].clone();
+                    let (tail, tail_count) = rebuilt[&ptr::addr_of!(*cell.tail_ref())].clone();
+                    let cell = if head.is_none() && tail.is_none() {
+                        None
+                    } else {
+                        Some(Rc::new(Self::from(Cell::from([
+                            head.unwrap_or_else(|| cell.head()),
+                            tail.unwrap_or_else(|| cell.tail()),
+                        ]))))
+                    };
+                    (cell, head_count + tail_count)
+                }
+                Self::Cell(cell) => {
+                    stack.push((noun, true));
+                    stack.push((cell.tail_ref(), false));
+                    stack.push((cell.head_ref(), false));
+                    continue;
+                }
+            };
+            rebuilt.insert(addr, entry);
+        }
+        match rebuilt.remove(&ptr::addr_of!(*self)) {
+            Some((Some(noun), count)) => {
+                drop(rebuilt);
+                drop(replacement);
+                (
+                    Rc::try_unwrap(noun).unwrap_or_else(|noun| (*noun).clone()),
+                    count,
+                )
+            }
+            _ => (self.clone(), 0),
+        }
+    }
+
+    /// Returns the subnoun at `axis` of this noun, returning `None` if there is no such subnoun.
+    ///
+    /// Axis `1` is the noun itself, axis `2` is the head of a cell, axis `3` is the tail of a
+    /// cell, axis `6` is the head of the tail of a cell, and so on. Axis `0` does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::{cell::Cell, Noun};
+    /// let noun = Noun::from(Cell::from([1u8, 2u8, 3u8]));
+    /// assert_eq!(*noun.get(2).unwrap(), 1u8);
+    /// assert_eq!(*noun.get(6).unwrap(), 2u8);
+    /// assert_eq!(*noun.get(7).unwrap(), 3u8);
+    /// assert_eq!(noun.get(4), None);
+    /// ```
+    pub fn get(&self, axis: u64) -> Option<&Self> {
+        if axis == 0 {
+            return None;
+        }
+        let mut noun = self;
+        // The most significant bit of the axis is implicit because it's always 1.
+        for i in (0..u64::BITS - 1 - axis.leading_zeros()).rev() {
+            match noun {
+                Self::Atom(_) => return None,
+                Self::Cell(cell) if axis & (1 << i) == 0 => noun = cell.head_ref(),
+                Self::Cell(cell) => noun = cell.tail_ref(),
+            }
+        }
+        Some(noun)
+    }
+
+    /// Returns the tag of this noun if it's a head-tagged cell like `[%add a b]`, i.e. a cell whose
+    /// head is a term (`@tas`): a non-null atom of lowercase letters, digits, and hyphens that
+    /// starts with a letter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::{atom::Atom, cell::Cell, Noun};
+    /// let noun = Noun::from(Cell::from(["neg", "a"]));
+    /// assert_eq!(noun.tag(), Some(&Atom::from("neg")));
+    /// assert_eq!(Noun::from(Cell::from(["Neg", "a"])).tag(), None);
+    /// assert_eq!(Noun::from(Atom::from("neg")).tag(), None);
+    /// ```
+    pub fn tag(&self) -> Option<&Atom> {
+        match self {
+            Self::Cell(cell) => match cell.head_ref() {
+                Self::Atom(tag) if is_term(tag) => Some(tag),
+                _ => None,
+            },
+            Self::Atom(_) => None,
+        }
+    }
+
+    /// Returns the tail of this noun if it's a cell whose head is `tag`.
+    ///
+    /// Fails with [`UnknownTag`](convert::Error::UnknownTag) if the head is a different atom.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::{cell::Cell, convert::Error, Noun};
+    /// let noun = Noun::from(Cell::from(["add", "a", "b"]));
+    /// assert_eq!(noun.untag("add").unwrap(), &Noun::from(Cell::from(["a", "b"])));
+    /// assert!(matches!(noun.untag("neg"), Err(Error::UnknownTag)));
+    /// ```
+    pub fn untag(&self, tag: &str) -> Result<&Self, convert::Error> {
+        match self {
+            Self::Atom(_) => Err(convert::Error::UnexpectedAtom),
+            Self::Cell(cell) => match cell.head_ref() {
+                Self::Atom(head) if head.as_bytes() == tag.as_bytes() => Ok(cell.tail_ref()),
+                Self::Atom(_) => Err(convert::Error::UnknownTag),
+                Self::Cell(_) => Err(convert::Error::UnexpectedCell.at(2)),
+            },
+        }
+    }
+
+    /// Serializes ("jams") this noun directly into a byte vector, skipping the intermediate
+    /// [`Atom`].
+    ///
+    /// The final bit of a jammed noun is always `1`, so the returned vector never ends in a zero
+    /// byte, though its final byte may be padded with zero bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::{atom::Atom, cell::Cell, serdes::Jam, Noun};
+    /// let noun = Noun::from(Cell::from([0u8, 19u8]));
+    /// assert_eq!(noun.jam_to_vec(), vec![0x09, 0x9b]);
+    /// assert_eq!(noun.jam_to_vec(), noun.jam().into_vec());
+    /// ```
+    #[doc(alias("serialize", "serialization"))]
+    pub fn jam_to_vec(&self) -> Vec<u8> {
+        let mut bits = Atom::builder();
+        serdes::jam::encode(self, &mut bits).unwrap_or_else(|never| match never {});
+        bits.into_vec()
+    }
+
+    /// Serializes ("jams") this noun without backreferences.
+    ///
+    /// Every occurrence of a repeated subnoun is encoded in full rather than being replaced by a
+    /// backreference to its first occurrence, which is useful when the output has to be decoded by
+    /// a decoder that doesn't support backreferences or when the size of the output must not
+    /// depend on how the noun happens to be shared. The output can be cued by
+    /// [`cue()`](Cue::cue()) like any other jammed noun, and it's identical to the output of
+    /// [`jam()`](Jam::jam()) for nouns without any repeated subnouns.
+    ///
+    /// **Beware**: the output grows with the *logical* size of this noun, which can be
+    /// exponentially larger than the memory the noun occupies when subnouns are shared (a noun
+    /// whose head and tail are the same noun, 64 levels deep, has 2^64 atoms). Use
+    /// [`jam_nodedup_capped()`](Self::jam_nodedup_capped()) for nouns that may be shared.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::{atom::Atom, cell::Cell, serdes::{Cue, Jam}, Noun};
+    /// let noun = Noun::from(Cell::from([10_000u16, 10_000u16]));
+    /// let jammed_noun = noun.jam_nodedup();
+    /// assert!(jammed_noun.bit_len() > noun.clone().jam().bit_len());
+    /// assert_eq!(Noun::cue(jammed_noun).unwrap(), noun);
+    /// ```
+    #[doc(alias("serialize", "serialization"))]
+    pub fn jam_nodedup(&self) -> Atom {
+        let mut bits = Atom::builder();
+        serdes::jam::encode_nodedup(self, &mut bits, u64::MAX)
+            .unwrap_or_else(|never| match never {});
+        bits.into_atom()
+    }
+
+    /// Serializes ("jams") this noun without backreferences like
+    /// [`jam_nodedup()`](Self::jam_nodedup()), giving up and returning `None` as soon as the
+    /// output exceeds `max_bits` bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::{cell::Cell, Noun};
+    /// let noun = Noun::from(Cell::from([0u8, 19u8]));
+    /// assert_eq!(noun.jam_nodedup_capped(16), Some(noun.jam_nodedup()));
+    /// assert_eq!(noun.jam_nodedup_capped(15), None);
+    /// ```
+    #[doc(alias("serialize", "serialization"))]
+    pub fn jam_nodedup_capped(&self, max_bits: u64) -> Option<Atom> {
+        let mut bits = Atom::builder();
+        serdes::jam::encode_nodedup(self, &mut bits, max_bits)
+            .unwrap_or_else(|never| match never {})
+            .then(|| bits.into_atom())
+    }
+
+    /// Serializes ("jams") this noun like [`jam()`](Jam::jam()), failing with
+    /// [`serdes::Error::LimitExceeded`] if the output would exceed `max_bits` bits.
+    ///
+    /// Encoding stops as soon as the next write would cross the cap, including before the bits of
+    /// an atom too large to fit, so no more than `max_bits` bits are ever allocated. This is the
+    /// encoding counterpart of [`cue_with_limits()`](Self::cue_with_limits()), for refusing to
+    /// serialize untrusted nouns that are too large.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::{cell::Cell, serdes::{Error, Limit}, Noun};
+    /// let noun = Noun::from(Cell::from([0u8, 19u8]));
+    /// assert_eq!(noun.jam_capped(16).unwrap().into_vec(), noun.jam_to_vec());
+    /// assert!(matches!(
+    ///     noun.jam_capped(15),
+    ///     Err(Error::LimitExceeded { which: Limit::JamBits, limit: 15 })
+    /// ));
+    /// ```
+    #[doc(alias("serialize", "serialization"))]
+    pub fn jam_capped(&self, max_bits: u64) -> serdes::Result<Atom> {
+        let mut sink = CappedSink::new(max_bits);
+        match serdes::jam::encode(self, &mut sink) {
+            Ok(()) => Ok(sink.into_atom()),
+            Err(CapExceeded) => {
+                let err = serdes::Error::LimitExceeded {
+                    which: serdes::Limit::JamBits,
+                    limit: max_bits,
+                };
+                serdes::trace::limit_exceeded(&err);
+                Err(err)
+            }
+        }
+    }
+
+    /// Returns the exact length in bits of this noun once serialized ("jammed"), without
+    /// serializing it.
+    ///
+    /// This walks the noun just like [`jam()`](Jam::jam()) does, deduplicating repeated subnouns
+    /// with backreferences, so it costs about as much time as jamming the noun but allocates no
+    /// memory for the output.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::{cell::Cell, serdes::Jam, Noun};
+    /// let noun = Noun::from(Cell::from([0u8, 19u8]));
+    /// assert_eq!(noun.jam_size_bits(), 16);
+    /// assert_eq!(noun.jam_size_bits(), noun.jam().bit_len() as u64);
+    /// ```
+    #[doc(alias("serialize", "serialization"))]
+    pub fn jam_size_bits(&self) -> u64 {
+        let mut size = SizeSink::default();
+        serdes::jam::encode(self, &mut size).unwrap_or_else(|never| match never {});
+        size.bit_len()
+    }
+
+    /// Serializes ("jams") this noun like [`jam()`](Jam::jam()), periodically reporting progress
+    /// to `report`.
+    ///
+    /// `report` is called once every [`interval`](ProgressOptions::interval) atoms, cells, and
+    /// backreferences encoded. Encoding stops with [`serdes::Error::Cancelled`] as soon as `report`
+    /// returns [`ControlFlow::Break`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::{cell::Cell, serdes::{Error, Jam, ProgressOptions}, Noun};
+    /// # use std::ops::ControlFlow;
+    /// let noun = Noun::from(Cell::from([1u8, 2u8, 3u8, 4u8]));
+    /// let options = ProgressOptions { interval: 2 };
+    ///
+    /// let mut nodes = Vec::new();
+    /// let jammed_noun = noun.jam_with_progress(&options, |progress| {
+    ///     nodes.push(progress.nodes);
+    ///     ControlFlow::Continue(())
+    /// });
+    /// assert_eq!(jammed_noun.unwrap(), noun.clone().jam());
+    /// assert_eq!(nodes, [1, 3, 5]);
+    ///
+    /// let res = noun.jam_with_progress(&options, |_| ControlFlow::Break(()));
+    /// assert!(matches!(res, Err(Error::Cancelled)));
+    /// ```
+    #[doc(alias("serialize", "serialization"))]
+    pub fn jam_with_progress(
+        &self,
+        options: &ProgressOptions,
+        report: impl FnMut(JamProgress) -> ControlFlow<()>,
+    ) -> serdes::Result<Atom> {
+        let mut progress = Progress::new(options, report);
+        let mut bits = Atom::builder();
+        match serdes::jam::encode_with_progress(self, &mut bits, Some(&mut progress), None) {
+            Ok(ControlFlow::Continue(())) => Ok(bits.into_atom()),
+            Ok(ControlFlow::Break(())) => Err(serdes::Error::Cancelled),
+            Err(never) => match never {},
+        }
+    }
+
+    /// Serializes ("jams") this noun like [`jam()`](Jam::jam()), also returning statistics about
+    /// the encoding, such as how many backreferences it has and how many bits they saved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::{cell::Cell, serdes::Jam, Noun};
+    /// let noun = Noun::from(Cell::from([10_000u16, 10_000u16]));
+    /// let (jammed_noun, stats) = noun.jam_with_stats();
+    /// assert_eq!(jammed_noun, noun.jam());
+    /// assert_eq!(stats.bits, 33);
+    /// assert_eq!((stats.atoms, stats.cells, stats.backrefs), (1, 1, 1));
+    /// assert_eq!(stats.backref_bits_saved, 15);
+    /// ```
+    #[doc(alias("serialize", "serialization"))]
+    pub fn jam_with_stats(&self) -> (Atom, JamStats) {
+        let mut stats = JamStats::default();
+        let mut bits = Atom::builder();
+        match serdes::jam::encode_with_progress(self, &mut bits, None, Some(&mut stats)) {
+            Ok(_) => (bits.into_atom(), stats),
+            Err(never) => match never {},
+        }
+    }
+
+    /// Serializes ("jams") this noun into `writer` as it is encoded, returning the number of
+    /// bytes written.
+    ///
+    /// Encoded bits are packed into bytes and written in small fixed-size chunks, so the jammed
+    /// noun is never materialized in memory in its entirety, which makes this method suitable for
+    /// writing very large nouns to a file or socket. The bytes written are identical to those
+    /// returned by [`jam_to_vec()`](Self::jam_to_vec()). `writer` is flushed before returning.
+    ///
+    /// Wrapping `writer` in a [`std::io::BufWriter`] is unnecessary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::{cell::Cell, Noun};
+    /// let noun = Noun::from(Cell::from([0u8, 19u8]));
+    /// let mut bytes = Vec::new();
+    /// assert_eq!(noun.jam_to_writer(&mut bytes).unwrap(), 2);
+    /// assert_eq!(bytes, noun.jam_to_vec());
+    /// ```
+    #[doc(alias("serialize", "serialization"))]
+    pub fn jam_to_writer<W: Write>(&self, writer: W) -> io::Result<u64> {
+        let mut sink = WriteSink::new(writer);
+        serdes::jam::encode(self, &mut sink)?;
+        sink.finish()
+    }
+
+    /// Returns a reader of the bytes of this noun serialized ("jammed"), which jams the noun
+    /// lazily as the bytes are read.
+    ///
+    /// This is the pull-based counterpart of [`jam_to_writer()`](Self::jam_to_writer()), for
+    /// consumers such as HTTP bodies that read from a source rather than being written to. The
+    /// bytes read are identical to those returned by [`jam_to_vec()`](Self::jam_to_vec()).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::{cell::Cell, Noun};
+    /// let noun = Noun::from(Cell::from([0u8, 19u8]));
+    /// let mut bytes = Vec::new();
+    /// std::io::copy(&mut noun.jam_reader(), &mut bytes).unwrap();
+    /// assert_eq!(bytes, noun.jam_to_vec());
+    /// ```
+    #[doc(alias("serialize", "serialization"))]
+    pub fn jam_reader(&self) -> JamReader<'_> {
+        JamReader::new(self)
+    }
+
+    /// Serializes ("jams") this noun into an asynchronous `writer`, returning the number of bytes
+    /// written.
+    ///
+    /// Only the writing is asynchronous: the noun is jammed synchronously a small fixed-size chunk
+    /// at a time, as by [`jam_reader()`](Self::jam_reader()), and each chunk is written before the
+    /// next is jammed, so the jammed noun is never materialized in memory in its entirety. The
+    /// bytes written are identical to those returned by [`jam_to_vec()`](Self::jam_to_vec()).
+    /// `writer` is flushed before returning.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::{cell::Cell, Noun};
+    /// # tokio_test::block_on(async {
+    /// let noun = Noun::from(Cell::from([0u8, 19u8]));
+    /// let mut bytes = Vec::new();
+    /// assert_eq!(noun.jam_to_async_writer(&mut bytes).await.unwrap(), 2);
+    /// assert_eq!(bytes, noun.jam_to_vec());
+    /// # });
+    /// ```
+    #[cfg(feature = "tokio")]
+    #[doc(alias("serialize", "serialization"))]
+    pub async fn jam_to_async_writer<W: tokio::io::AsyncWrite + Unpin>(
+        &self,
+        mut writer: W,
+    ) -> io::Result<u64> {
+        use tokio::io::AsyncWriteExt;
+
+        let mut jammed_noun = self.jam_reader();
+        let mut chunk = [0; 4096];
+        let mut len = 0;
+        loop {
+            match jammed_noun.read(&mut chunk)? {
+                0 => break,
+                read => {
+                    writer.write_all(&chunk[..read]).await?;
+                    len += read as u64;
                 }
-                // Atom tag = 0b0.
-                Some(false) => {
-                    let atom = Rc::<Noun>::from(decode_atom(bits)?);
-                    cache.insert(pos, atom.clone());
-                    Ok(atom)
+            }
+        }
+        writer.flush().await?;
+        Ok(len)
+    }
+
+    /// Serializes ("jams") this noun and compresses the jammed noun with zstd at compression
+    /// `level`, returning the compressed bytes.
+    ///
+    /// The jammed noun is streamed through the compressor rather than being materialized in
+    /// memory, at the cost of walking the noun twice: once to compute the length of the jammed
+    /// noun, which is recorded in the header described in [`serdes::compress`], and once to jam it.
+    /// `level` ranges from `1` to `22`, and `0` selects zstd's default level.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::{cell::Cell, Noun};
+    /// let noun = Noun::from(Cell::from([0u8, 19u8]));
+    /// let compressed = noun.jam_compressed(3);
+    /// assert_eq!(Noun::cue_compressed(&compressed, 1 << 20).unwrap(), noun);
+    /// ```
+    #[cfg(feature = "compress")]
+    #[doc(alias("serialize", "serialization"))]
+    pub fn jam_compressed(&self, level: i32) -> Vec<u8> {
+        serdes::compress::compress(self, level).expect("compressing into a vector failed")
+    }
+
+    /// Returns the SHA-256 hash of this noun serialized ("jammed").
+    ///
+    /// The hash is defined over the bytes returned by [`jam_to_vec()`](Self::jam_to_vec()), which
+    /// are canonical: equal nouns jam to the same bytes however their subnouns are shared in
+    /// memory, and jam's encoding is fixed by Urbit, so the hash is stable across processes,
+    /// platforms, and versions of this crate. Unlike [`mug()`](Self::mug()), it's suitable as a
+    /// content address.
+    ///
+    /// The jammed noun is streamed through the hash in small fixed-size chunks rather than being
+    /// materialized in memory.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::{cell::Cell, Noun};
+    /// let noun = Noun::from(Cell::from([0u8, 19u8]));
+    /// let hash = noun.content_hash();
+    /// assert_eq!(hash[..4], [0x63, 0xd4, 0x53, 0x3a]);
+    /// ```
+    #[cfg(feature = "crypto")]
+    pub fn content_hash(&self) -> [u8; 32] {
+        use sha2::{Digest, Sha256};
+
+        let mut hasher = Sha256::new();
+        let mut sink = WriteSink::new(&mut hasher);
+        serdes::jam::encode(self, &mut sink)
+            .and_then(|()| sink.finish())
+            .expect("hashing never fails");
+        hasher.finalize().into()
+    }
+
+    /// Returns the SHA-256 hash of this noun serialized ("jammed") as an atom, whose least
+    /// significant byte is the first byte of the hash.
+    ///
+    /// This is the same atom as Hoon's `(shax (jam noun))`. See
+    /// [`content_hash()`](Self::content_hash()).
+    #[cfg(feature = "crypto")]
+    pub fn content_hash_atom(&self) -> Atom {
+        Atom::from(self.content_hash().to_vec())
+    }
+
+    /// Deserializes ("cues") a noun directly from `reader` as it is decoded.
+    ///
+    /// The jammed noun is never loaded into memory in its entirety. Decoding stops as soon as the
+    /// last bit of the noun has been read, at which point `reader` is positioned just past the
+    /// byte containing that bit: because a jammed noun is padded with zero bits to a whole number
+    /// of bytes, this is exactly the number of bytes [`jam_to_writer()`](Self::jam_to_writer())
+    /// would have written for the noun, so several nouns written back to back can be read back one
+    /// at a time.
+    ///
+    /// To stop precisely at the end of the noun, bytes are read from `reader` one at a time, so
+    /// wrapping `reader` in a [`std::io::BufReader`] is strongly recommended when reading from a
+    /// file or socket. Short and interrupted reads are retried, and any other I/O error is
+    /// returned as [`serdes::Error::Io`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::{cell::Cell, Noun};
+    /// # use std::io::Cursor;
+    /// let mut reader = Cursor::new([0x09, 0x9b, 0x0c]);
+    /// let noun = Noun::cue_from_reader(&mut reader).unwrap();
+    /// assert_eq!(noun, Noun::from(Cell::from([0u8, 19u8])));
+    /// assert_eq!(reader.position(), 2);
+    /// ```
+    #[doc(alias("deserialize", "deserialization"))]
+    pub fn cue_from_reader<R: Read>(reader: R) -> serdes::Result<Self> {
+        serdes::cue::decode(&mut ReadSource::new(reader), &CueLimits::UNLIMITED)
+    }
+
+    /// Deserializes ("cues") a noun from an asynchronous `reader` as it arrives, failing if
+    /// decoding it would exceed any of `limits`.
+    ///
+    /// Only the reading is asynchronous: each chunk of bytes that `reader` has buffered is decoded
+    /// synchronously by a [`CueMachine`](serdes::CueMachine) before the next is awaited, so only the
+    /// bytes of an atom or backreference that's cut off by the end of a chunk are held in memory
+    /// besides the noun itself, and `limits` bound how much a peer can make this buffer. Decoding
+    /// stops as soon as the noun ends, without waiting for `reader` to reach its end, and the bytes
+    /// that follow the byte holding the last bit of the noun are left unconsumed in `reader`, so
+    /// several nouns sent back to back can be read one at a time, like with
+    /// [`cue_from_reader()`](Self::cue_from_reader()).
+    ///
+    /// Wrap a reader that isn't buffered, such as a socket, in a [`tokio::io::BufReader`]. An I/O
+    /// error is returned as [`serdes::Error::Io`], and `reader` reaching its end before the noun
+    /// does as [`serdes::Error::UnexpectedEof`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::{cell::Cell, serdes::CueLimits, Noun};
+    /// # tokio_test::block_on(async {
+    /// let mut reader = &[0x09, 0x9b, 0x0c][..];
+    /// let noun = Noun::cue_from_async_reader(&mut reader, &CueLimits::default())
+    ///     .await
+    ///     .unwrap();
+    /// assert_eq!(noun, Noun::from(Cell::from([0u8, 19u8])));
+    /// assert_eq!(reader, [0x0c]);
+    /// # });
+    /// ```
+    #[cfg(feature = "tokio")]
+    #[doc(alias("deserialize", "deserialization"))]
+    pub async fn cue_from_async_reader<R: tokio::io::AsyncBufRead + Unpin>(
+        mut reader: R,
+        limits: &CueLimits,
+    ) -> serdes::Result<Self> {
+        use tokio::io::AsyncBufReadExt;
+
+        let mut machine = serdes::CueMachine::new(limits);
+        loop {
+            let chunk = reader.fill_buf().await.map_err(serdes::Error::Io)?;
+            if chunk.is_empty() {
+                return Err(machine.truncated());
+            }
+            let len = chunk.len();
+            match machine.feed(chunk)? {
+                serdes::Status::NeedMore => reader.consume(len),
+                serdes::Status::Done {
+                    noun,
+                    trailing_bytes,
+                } => {
+                    reader.consume(len - trailing_bytes);
+                    return Ok(noun);
                 }
-                None => unimplemented!(),
             }
         }
+    }
+
+    /// Decompresses and deserializes ("cues") a noun compressed by
+    /// [`jam_compressed()`](Self::jam_compressed()).
+    ///
+    /// The jammed noun is streamed out of the decompressor rather than being materialized in
+    /// memory. Because a small compressed input can decompress to an enormous jammed noun, no more
+    /// than `max_len` bytes are ever decompressed: a header declaring a longer jammed noun is
+    /// rejected before decompression begins, and a payload that decompresses to more or fewer
+    /// bytes than its header declares is an error. A malformed header or length is reported as
+    /// [`serdes::Error::Compressed`], and a corrupt payload as [`serdes::Error::Io`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::{cell::Cell, serdes::{compress::CompressedError, Error}, Noun};
+    /// let compressed = Noun::from(Cell::from([0u8, 19u8])).jam_compressed(3);
+    /// match Noun::cue_compressed(&compressed, 1) {
+    ///     Err(Error::Compressed(CompressedError::TooLarge { len: 2, limit: 1 })) => {}
+    ///     res => panic!("unexpected result: {:?}", res),
+    /// }
+    /// ```
+    #[cfg(feature = "compress")]
+    #[doc(alias("deserialize", "deserialization"))]
+    pub fn cue_compressed(bytes: &[u8], max_len: u64) -> serdes::Result<Self> {
+        serdes::compress::decompress(bytes, max_len)
+    }
+
+    /// Deserializes ("cues") a noun directly from a little-endian byte slice, skipping the
+    /// intermediate [`Atom`].
+    ///
+    /// Trailing zero bytes are ignored, just as they would be if `bytes` were first converted into
+    /// an [`Atom`], so a jammed noun read into a zero-padded buffer cues to the same noun. Any set
+    /// bit following the jammed noun is an error ([`serdes::Error::TrailingData`]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::{cell::Cell, Noun};
+    /// let noun = Noun::from(Cell::from([0u8, 19u8]));
+    /// assert_eq!(Noun::cue_bytes(&[0x09, 0x9b]).unwrap(), noun);
+    /// assert_eq!(Noun::cue_bytes(&[0x09, 0x9b, 0x00, 0x00]).unwrap(), noun);
+    /// ```
+    #[doc(alias("deserialize", "deserialization"))]
+    pub fn cue_bytes(bytes: &[u8]) -> serdes::Result<Self> {
+        serdes::cue::decode_exact(&mut AtomIter::from_bytes(bytes), &CueLimits::UNLIMITED)
+    }
 
+    /// Deserializes ("cues") a jammed noun that may be followed by other data, returning the noun
+    /// along with the bit position one past the end of its encoding.
+    ///
+    /// Trailing zero bits are inherently invisible in an [`Atom`], but because the encoding of
+    /// every noun ends in a set bit, the returned position never exceeds the length of
+    /// `jammed_noun`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::{atom::Atom, cell::Cell, Noun};
+    /// // [0 19] followed by 0xff.
+    /// let (noun, len) = Noun::cue_partial(&Atom::from(0xff_9b09u32)).unwrap();
+    /// assert_eq!(noun, Noun::from(Cell::from([0u8, 19u8])));
+    /// assert_eq!(len, 16);
+    /// ```
+    #[doc(alias("deserialize", "deserialization"))]
+    pub fn cue_partial(jammed_noun: &Atom) -> serdes::Result<(Self, usize)> {
         let mut bits = jammed_noun.iter();
-        let mut cache = HashMap::new();
-        let noun = decode(&mut bits, &mut cache)?;
-        // Dropping the cache guarantees that the top level noun has exactly one reference, which
-        // makes it safe to move out of the Rc.
-        drop(cache);
-        let noun = Rc::try_unwrap(noun).unwrap();
-        Ok(noun)
+        let noun = serdes::cue::decode(&mut bits, &CueLimits::UNLIMITED)?;
+        Ok((noun, bits.pos()))
+    }
+
+    /// Deserializes ("cues") a jammed noun, failing with [`serdes::Error::TrailingData`] if any set
+    /// bits follow the end of its encoding.
+    ///
+    /// This is equivalent to [`cue()`](Cue::cue()) but doesn't take ownership of `jammed_noun`.
+    /// Trailing zero bits are inherently invisible in an [`Atom`] and are therefore never an error;
+    /// use [`cue_partial()`](Self::cue_partial()) to tolerate arbitrary trailing data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::{atom::Atom, cell::Cell, serdes::Error, Noun};
+    /// let noun = Noun::cue_strict(&Atom::from(0x9b09u16)).unwrap();
+    /// assert_eq!(noun, Noun::from(Cell::from([0u8, 19u8])));
+    /// assert!(matches!(
+    ///     Noun::cue_strict(&Atom::from(0xff_9b09u32)),
+    ///     Err(Error::TrailingData { bits: 8 })
+    /// ));
+    /// ```
+    #[doc(alias("deserialize", "deserialization"))]
+    pub fn cue_strict(jammed_noun: &Atom) -> serdes::Result<Self> {
+        serdes::cue::decode_exact(&mut jammed_noun.iter(), &CueLimits::UNLIMITED)
+    }
+
+    /// Deserializes ("cues") a jammed noun, additionally checking that `jammed_noun` is exactly
+    /// the encoding that [`jam()`](Jam::jam()) would produce for the noun.
+    ///
+    /// Many encodings decode to the same noun, but only one of them is produced by jamming it, so
+    /// this is useful wherever a jammed noun must be the unique representation of its noun, as in
+    /// content-addressed storage. Any encoding that decodes successfully but differs from that of
+    /// [`jam()`](Jam::jam()) fails with [`serdes::Error::NonCanonical`], whose
+    /// [`NonCanonicalReason`](serdes::NonCanonicalReason) is one of:
+    ///
+    /// - an atom or backreference index encoded with more bits than it has;
+    /// - a noun encoded in full even though jamming it would produce a backreference to an earlier
+    ///   occurrence, which is always the case for a repeated cell;
+    /// - a backreference to an atom that jamming would encode directly because the atom is no
+    ///   longer than the backreference's index;
+    /// - a backreference to an occurrence of a noun other than its first;
+    /// - trailing set bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::{atom::Atom, cell::Cell, serdes::{Error, Jam, NonCanonicalReason}, Noun};
+    /// let noun = Noun::from(Cell::from([10_000u16, 10_000u16]));
+    /// assert_eq!(Noun::cue_canonical(&noun.clone().jam()).unwrap(), noun);
+    /// match Noun::cue_canonical(&noun.jam_nodedup()) {
+    ///     Err(Error::NonCanonical { reason, bit_pos }) => {
+    ///         assert_eq!(reason, NonCanonicalReason::MissingBackref);
+    ///         assert_eq!(bit_pos, 25);
+    ///     }
+    ///     res => panic!("unexpected result: {:?}", res),
+    /// }
+    /// ```
+    #[doc(alias("deserialize", "deserialization"))]
+    pub fn cue_canonical(jammed_noun: &Atom) -> serdes::Result<Self> {
+        serdes::cue::decode_canonical(&mut jammed_noun.iter())
+    }
+
+    /// Returns `true` if `jammed_noun` is exactly the encoding that [`jam()`](Jam::jam()) would
+    /// produce for the noun it decodes to, as checked by
+    /// [`cue_canonical()`](Self::cue_canonical()).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::{atom::Atom, Noun};
+    /// // [19 0] jammed, and jammed with 19 given a 6-bit length.
+    /// assert!(Noun::is_canonical_jam(&Atom::from(0b1010_0110_1100_0001u32)));
+    /// assert!(!Noun::is_canonical_jam(&Atom::from(0b1_0010_0111_0100_0001u32)));
+    /// ```
+    pub fn is_canonical_jam(jammed_noun: &Atom) -> bool {
+        Self::cue_canonical(jammed_noun).is_ok()
+    }
+
+    /// Deserializes ("cues") a jammed noun that the caller promises has no backreferences, such as
+    /// the output of [`jam_nodedup()`](Self::jam_nodedup()).
+    ///
+    /// Decoded nouns are only remembered so that backreferences to them can be resolved, so
+    /// skipping that makes this faster than [`cue()`](Cue::cue()) and saves memory proportional
+    /// to the number of atoms and cells in the noun. Breaking the promise is an error: the first
+    /// backreference fails with [`serdes::Error::CacheMiss`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::{cell::Cell, serdes::{Error, Jam}, Noun};
+    /// let noun = Noun::from(Cell::from([10_000u16, 10_000u16]));
+    /// assert_eq!(Noun::cue_nodedup(&noun.jam_nodedup()).unwrap(), noun);
+    /// assert!(matches!(
+    ///     Noun::cue_nodedup(&noun.clone().jam()),
+    ///     Err(Error::CacheMiss(_))
+    /// ));
+    /// ```
+    #[doc(alias("deserialize", "deserialization"))]
+    pub fn cue_nodedup(jammed_noun: &Atom) -> serdes::Result<Self> {
+        serdes::cue::decode_nodedup(&mut jammed_noun.iter())
+    }
+
+    /// Deserializes ("cues") a jammed noun, failing with [`serdes::Error::LimitExceeded`] as soon
+    /// as decoding it would exceed any of `limits`.
+    ///
+    /// This should be preferred over [`cue()`](Cue::cue()), which places no limits on the size of
+    /// the decoded noun, whenever the jammed noun comes from an untrusted source.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::{atom::Atom, serdes::{CueLimits, Error, Limit}, Noun};
+    /// let jammed_noun = Atom::from(0x9b09u16);
+    /// let limits = CueLimits {
+    ///     max_nodes: 3,
+    ///     ..CueLimits::default()
+    /// };
+    /// assert!(Noun::cue_with_limits(jammed_noun.clone(), &limits).is_ok());
+    ///
+    /// let limits = CueLimits {
+    ///     max_nodes: 2,
+    ///     ..CueLimits::default()
+    /// };
+    /// assert!(matches!(
+    ///     Noun::cue_with_limits(jammed_noun, &limits),
+    ///     Err(Error::LimitExceeded { which: Limit::Nodes, limit: 2 })
+    /// ));
+    /// ```
+    #[doc(alias("deserialize", "deserialization"))]
+    pub fn cue_with_limits(jammed_noun: Atom, limits: &CueLimits) -> serdes::Result<Self> {
+        serdes::cue::decode_exact(&mut jammed_noun.iter(), limits)
+    }
+
+    /// Deserializes ("cues") a jammed noun, sharing structurally equal subnouns with the nouns
+    /// previously cued with `cache`.
+    ///
+    /// Every atom and cell that's equal to a noun in `cache` is replaced by the [`Rc<Noun>`] in
+    /// `cache` rather than being allocated anew, so cueing many jammed nouns that have most of
+    /// their structure in common uses little more memory than cueing one of them. Subnouns of the
+    /// returned noun are added to `cache`, evicting the least recently used nouns once it's full.
+    ///
+    /// Hashing every decoded noun makes this slower than [`cue()`](Cue::cue()) when there's
+    /// nothing to share.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::{cell::Cell, serdes::{CueCache, Jam}, Noun, Rc};
+    /// let mut cache = CueCache::new(1024);
+    /// let jammed_noun = Noun::from(Cell::from([1u8, 2u8])).jam();
+    /// let a = Noun::cue_with_cache(&mut cache, jammed_noun.clone()).unwrap();
+    /// let b = Noun::cue_with_cache(&mut cache, jammed_noun).unwrap();
+    /// assert!(Rc::ptr_eq(&a, &b));
+    /// ```
+    #[doc(alias("deserialize", "deserialization"))]
+    pub fn cue_with_cache(cache: &mut CueCache, jammed_noun: Atom) -> serdes::Result<Rc<Self>> {
+        serdes::cue::decode_shared(&mut jammed_noun.iter(), &CueLimits::UNLIMITED, cache)
+    }
+
+    /// Deserializes ("cues") a jammed noun like [`cue()`](Cue::cue()), periodically reporting
+    /// progress to `report`.
+    ///
+    /// `report` is called once every [`interval`](ProgressOptions::interval) atoms, cells, and
+    /// backreferences decoded. Decoding stops with [`serdes::Error::Cancelled`] as soon as
+    /// `report` returns [`ControlFlow::Break`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::{cell::Cell, serdes::{Error, Jam, ProgressOptions}, Noun};
+    /// # use std::ops::ControlFlow;
+    /// let jammed_noun = Noun::from(Cell::from([1u8, 2u8, 3u8, 4u8])).jam();
+    /// let options = ProgressOptions { interval: 2 };
+    ///
+    /// let mut bits_read = Vec::new();
+    /// Noun::cue_with_progress(&jammed_noun, &options, |progress| {
+    ///     bits_read.push(progress.bits_read);
+    ///     ControlFlow::Continue(())
+    /// })
+    /// .unwrap();
+    /// assert_eq!(bits_read, [2, 8, 17]);
+    ///
+    /// let res = Noun::cue_with_progress(&jammed_noun, &options, |_| ControlFlow::Break(()));
+    /// assert!(matches!(res, Err(Error::Cancelled)));
+    /// ```
+    #[doc(alias("deserialize", "deserialization"))]
+    pub fn cue_with_progress(
+        jammed_noun: &Atom,
+        options: &ProgressOptions,
+        report: impl FnMut(CueProgress) -> ControlFlow<()>,
+    ) -> serdes::Result<Self> {
+        let progress = Progress::new(options, report);
+        serdes::cue::decode_with_progress(&mut jammed_noun.iter(), progress)
+    }
+
+    /// Deserializes ("cues") a jammed noun like [`cue()`](Cue::cue()), also returning statistics
+    /// about the encoding, such as how many backreferences it has and how many bits they saved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::{atom::Atom, cell::Cell, Noun};
+    /// let jammed_noun = Atom::from(4_952_983_169u64);
+    /// let (noun, stats) = Noun::cue_with_stats(jammed_noun).unwrap();
+    /// assert_eq!(noun, Noun::from(Cell::from([10_000u16, 10_000u16])));
+    /// assert_eq!((stats.atoms, stats.cells, stats.backrefs), (1, 1, 1));
+    /// assert_eq!(stats.max_depth, 1);
+    /// ```
+    #[doc(alias("deserialize", "deserialization"))]
+    pub fn cue_with_stats(jammed_noun: Atom) -> serdes::Result<(Self, CueStats)> {
+        serdes::cue::decode_with_stats(&mut jammed_noun.iter())
+    }
+
+    /// Copies this noun into fresh allocations.
+    ///
+    /// Unlike [`clone()`](Self::clone()), which only bumps the reference counts of the head and
+    /// tail of a cell, the returned noun shares no allocations with this noun, which makes it
+    /// possible to free this noun while keeping the copy around. Subnouns that are shared within
+    /// this noun are also shared within the copy.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::{atom::Atom, cell::Cell, Noun, Rc};
+    /// let head = Rc::<Noun>::from(Atom::from("hello"));
+    /// let noun = Noun::from(Cell::from([head.clone(), head.clone()]));
+    ///
+    /// let copy = noun.deep_clone();
+    /// assert_eq!(copy, noun);
+    ///
+    /// drop(noun);
+    /// assert_eq!(Rc::strong_count(&head), 1);
+    /// ```
+    pub fn deep_clone(&self) -> Self {
+        match self {
+            Self::Atom(atom) => Self::Atom(atom.clone()),
+            Self::Cell(cell) => {
+                let mut copies = HashMap::new();
+                let head = deep_clone_rc(cell.head(), &mut copies);
+                let tail = deep_clone_rc(cell.tail(), &mut copies);
+                Self::Cell(Cell::from([head, tail]))
+            }
+        }
+    }
+
+    /// Copies the cells along the path from this noun to the subnoun at `axis` into fresh
+    /// allocations, returning `None` if there is no such subnoun.
+    ///
+    /// The subnoun at `axis` is copied shallowly (i.e. its head and tail are shared with this noun
+    /// if it's a cell), and every noun hanging off of the path is shared with this noun. This is a
+    /// cheaper alternative to [`deep_clone()`](Self::deep_clone()) when only the path to a
+    /// particular subnoun needs to be independent of this noun.
+    pub fn detach(&self, axis: u64) -> Option<Self> {
+        if axis == 0 {
+            return None;
+        }
+        let mut spine = Vec::new();
+        let mut noun = self;
+        for i in (0..u64::BITS - 1 - axis.leading_zeros()).rev() {
+            match noun {
+                Self::Atom(_) => return None,
+                Self::Cell(cell) => {
+                    let go_tail = axis & (1 << i) != 0;
+                    spine.push((cell, go_tail));
+                    noun = if go_tail {
+                        cell.tail_ref()
+                    } else {
+                        cell.head_ref()
+                    };
+                }
+            }
+        }
+        let mut detached = match noun {
+            Self::Atom(atom) => Self::Atom(atom.clone()),
+            Self::Cell(cell) => Self::Cell(Cell::from([cell.head(), cell.tail()])),
+        };
+        while let Some((cell, go_tail)) = spine.pop() {
+            let child = Rc::new(detached);
+            detached = if go_tail {
+                Self::Cell(Cell::from([cell.head(), child]))
+            } else {
+                Self::Cell(Cell::from([child, cell.tail()]))
+            };
+        }
+        Some(detached)
+    }
+
+    /// Returns a mutable reference to the cell `noun` points to, first copying it into a fresh
+    /// allocation if it's shared, or returns `None` if it's an atom.
+    ///
+    /// Copying a cell only copies its two pointers, so its head and tail stay shared with the
+    /// original. This is [`Rc::make_mut()`](std::rc::Rc::make_mut()) for cells.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::{cell::Cell, Noun, Rc};
+    /// let original = Rc::new(Noun::from(Cell::from([1u8, 2u8])));
+    /// let mut noun = original.clone();
+    /// *noun.make_cell_mut().unwrap().tail_mut() = Rc::new(Noun::from(3u8));
+    /// assert_eq!(*noun, Noun::from(Cell::from([1u8, 3u8])));
+    /// assert_eq!(*original, Noun::from(Cell::from([1u8, 2u8])));
+    /// ```
+    pub fn make_cell_mut(self: &mut Rc<Self>) -> Option<&mut Cell> {
+        // Checked first so that a shared atom isn't copied for nothing.
+        if let Self::Atom(_) = **self {
+            return None;
+        }
+        match Rc::make_mut(self) {
+            Self::Atom(_) => None,
+            Self::Cell(cell) => Some(cell),
+        }
+    }
+
+    /// Calls `f` on the subnoun at `axis` of `noun`, returning `false` without calling it if there
+    /// is no such subnoun.
+    ///
+    /// Every cell on the path to the subnoun is made mutable with
+    /// [`make_cell_mut()`](Self::make_cell_mut()), so only the cells on the path that are shared
+    /// are copied, and every noun hanging off of the path stays shared.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::{cell::Cell, Noun, Rc};
+    /// let mut noun = Rc::new(Noun::from(Cell::from([1u8, 2u8, 3u8])));
+    /// assert!(noun.modify_at(6, |two| *two = Rc::new(Noun::from(4u8))));
+    /// assert_eq!(*noun, Noun::from(Cell::from([1u8, 4u8, 3u8])));
+    /// assert!(!noun.modify_at(12, |_| unreachable!()));
+    /// ```
+    pub fn modify_at(self: &mut Rc<Self>, axis: u64, f: impl FnOnce(&mut Rc<Self>)) -> bool {
+        if self.get(axis).is_none() {
+            return false;
+        }
+        let mut noun = self;
+        for i in (0..u64::BITS - 1 - axis.leading_zeros()).rev() {
+            let cell = noun.make_cell_mut().expect("cell on the path to a subnoun");
+            noun = if axis & (1 << i) == 0 {
+                cell.head_mut()
+            } else {
+                cell.tail_mut()
+            };
+        }
+        f(noun);
+        true
+    }
+}
+
+/// Combines the mugs of the head and tail of a cell into the mug of the cell.
+pub(crate) fn mug_both(head: u32, tail: u32) -> u32 {
+    // Hoon hashes the atom whose low 32 bits are the head's mug and whose high 32 bits are the
+    // tail's, without the atom's trailing zero bytes.
+    let bytes = (u64::from(tail) << 32 | u64::from(head)).to_le_bytes();
+    let len = bytes
+        .iter()
+        .rposition(|byte| *byte != 0)
+        .map_or(0, |idx| idx + 1);
+    atom::mum(0xdead_beef, 0xfffe, &bytes[..len])
+}
+
+/// Returns `true` if `atom` is a non-null term (`@tas`).
+pub(crate) fn is_term(atom: &Atom) -> bool {
+    match atom.as_bytes() {
+        [first, rest @ ..] => {
+            first.is_ascii_lowercase()
+                && rest
+                    .iter()
+                    .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || *b == b'-')
+        }
+        [] => false,
+    }
+}
+
+/// Copies a reference-counted noun into fresh allocations without recursing.
+///
+/// `copies` maps the address of each noun that has already been copied to its copy, which
+/// preserves sharing within the noun being copied.
+fn deep_clone_rc(noun: Rc<Noun>, copies: &mut HashMap<*const Noun, Rc<Noun>>) -> Rc<Noun> {
+    let root = Rc::as_ptr(&noun);
+    let mut stack = vec![(noun, false)];
+    while let Some((noun, children_copied)) = stack.pop() {
+        let addr = Rc::as_ptr(&noun);
+        if copies.contains_key(&addr) {
+            continue;
+        }
+        match &*noun {
+            Noun::Atom(atom) => {
+                copies.insert(addr, Rc::new(Noun::Atom(atom.clone())));
+            }
+            Noun::Cell(cell) if children_copied => {
+                let head = copies[&Rc::as_ptr(&cell.head())].clone();
+                let tail = copies[&Rc::as_ptr(&cell.tail())].clone();
+                copies.insert(addr, Rc::new(Noun::Cell(Cell::from([head, tail]))));
+            }
+            Noun::Cell(cell) => {
+                let (head, tail) = (cell.head(), cell.tail());
+                stack.push((noun, true));
+                stack.push((tail, false));
+                stack.push((head, false));
+            }
+        }
+    }
+    copies[&root].clone()
+}
+
+impl AsRef<Noun> for Noun {
+    fn as_ref(&self) -> &Noun {
+        self
+    }
+}
+
+impl Cue for Noun {
+    fn cue(jammed_noun: Atom) -> serdes::Result<Self> {
+        Self::cue_strict(&jammed_noun)
     }
 }
 
@@ -144,6 +1326,26 @@ impl Display for Noun {
     }
 }
 
+/// Hashes an atom as a tag byte, its length in bytes, and its bytes, and a cell as a different tag
+/// byte and the hashes of its head and tail, which is what lets a
+/// [`MugHasher`](crate::mug::MugHasher) hash a noun to its mug.
+impl Hash for Noun {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            Self::Atom(atom) => {
+                state.write_u8(mug::ATOM);
+                state.write_usize(atom.as_bytes().len());
+                state.write(atom.as_bytes());
+            }
+            Self::Cell(cell) => {
+                state.write_u8(mug::CELL);
+                Hash::hash(cell.head_ref(), state);
+                Hash::hash(cell.tail_ref(), state);
+            }
+        }
+    }
+}
+
 impl From<Atom> for Noun {
     fn from(atom: Atom) -> Self {
         Self::Atom(atom)
@@ -170,74 +1372,84 @@ impl From<Cell> for Rc<Noun> {
 
 impl Jam for Noun {
     fn jam(self) -> Atom {
-        fn encode_len(mut len: u64, bits: &mut AtomBuilder) {
-            let len_of_len = u64::BITS - len.leading_zeros();
-            for _ in 0..len_of_len {
-                bits.push_bit(false);
-            }
-            bits.push_bit(true);
-            if len_of_len != 0 {
-                // Don't write the most significant bit of the length because it's always 1.
-                while len != 1 {
-                    bits.push_bit((len & 1) != 0);
-                    len >>= 1;
-                }
-            }
-        }
+        (&self).jam()
+    }
+}
 
-        fn encode_atom(atom: &Atom, bits: &mut AtomBuilder) {
-            // Atom tag = 0b0.
-            bits.push_bit(false);
-            encode_len(atom.bit_len() as u64, bits);
-            for bit in atom.iter() {
-                bits.push_bit(bit);
-            }
+impl Jam for &Noun {
+    fn jam(self) -> Atom {
+        Atom::from(self.jam_to_vec())
+    }
+}
+
+impl Jam for Rc<Noun> {
+    fn jam(self) -> Atom {
+        (&*self).jam()
+    }
+}
+
+impl Jam for Box<Noun> {
+    fn jam(self) -> Atom {
+        (&*self).jam()
+    }
+}
+
+impl PartialEq<str> for Noun {
+    fn eq(&self, other: &str) -> bool {
+        if let Self::Atom(atom) = self {
+            atom == other
+        } else {
+            false
         }
+    }
+}
 
-        fn encode(noun: Rc<Noun>, bits: &mut AtomBuilder, cache: &mut HashMap<Rc<Noun>, u64>) {
-            if let Some(idx) = cache.get(&noun) {
-                if let Noun::Atom(ref atom) = *noun {
-                    let idx_bit_len = u64::from(u64::BITS - idx.leading_zeros());
-                    let atom_bit_len = atom.bit_len() as u64;
-                    // Backreferences to atoms are only encoded if they're shorter than the atom it
-                    // would reference.
-                    if atom_bit_len <= idx_bit_len {
-                        encode_atom(atom, bits);
-                        return;
-                    }
-                }
-                let idx = Atom::from(*idx);
-                // Backreference tag = 0b11.
-                bits.push_bit(true);
-                bits.push_bit(true);
-                encode_len(idx.bit_len() as u64, bits);
-                for bit in idx.iter() {
-                    bits.push_bit(bit);
-                }
-                return;
-            }
-
-            cache.insert(noun.clone(), bits.pos() as u64);
-            match *noun {
-                Noun::Atom(ref atom) => encode_atom(atom, bits),
-                Noun::Cell(ref cell) => {
-                    // Cell tag = 0b01.
-                    bits.push_bit(true);
-                    bits.push_bit(false);
-                    encode(cell.head(), bits, cache);
-                    encode(cell.tail(), bits, cache);
+impl PartialEq<&str> for Noun {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
+impl PartialEq<Noun> for str {
+    fn eq(&self, other: &Noun) -> bool {
+        other == self
+    }
+}
+
+impl PartialEq<Noun> for &str {
+    fn eq(&self, other: &Noun) -> bool {
+        other == *self
+    }
+}
+
+/// Compares a noun to an unsigned integer primitive.
+macro_rules! impl_partial_eq_uint_for_noun {
+    ($uint:ty) => {
+        impl PartialEq<$uint> for Noun {
+            fn eq(&self, other: &$uint) -> bool {
+                if let Self::Atom(atom) = self {
+                    atom == other
+                } else {
+                    false
                 }
             }
         }
 
-        let noun = Rc::new(self);
-        let mut bits = Atom::builder();
-        let mut cache = HashMap::new();
-        encode(noun, &mut bits, &mut cache);
-        bits.into_atom()
-    }
+        impl PartialEq<Noun> for $uint {
+            fn eq(&self, other: &Noun) -> bool {
+                other == self
+            }
+        }
+    };
 }
 
+impl_partial_eq_uint_for_noun!(u8);
+impl_partial_eq_uint_for_noun!(u16);
+impl_partial_eq_uint_for_noun!(u32);
+impl_partial_eq_uint_for_noun!(u64);
+impl_partial_eq_uint_for_noun!(u128);
+impl_partial_eq_uint_for_noun!(usize);
+
 impl TryFrom<&&str> for Noun {
     type Error = ();
 
@@ -246,14 +1458,6 @@ impl TryFrom<&&str> for Noun {
     }
 }
 
-impl TryFrom<String> for Noun {
-    type Error = ();
-
-    fn try_from(string: String) -> Result<Self, Self::Error> {
-        Ok(Noun::from(Atom::from(string)))
-    }
-}
-
 impl<'a> TryFrom<&'a Noun> for &'a str {
     type Error = convert::Error;
 
@@ -266,6 +1470,71 @@ impl<'a> TryFrom<&'a Noun> for &'a str {
     }
 }
 
+impl<'a> TryFrom<&'a Noun> for &'a Atom {
+    type Error = convert::Error;
+
+    fn try_from(noun: &'a Noun) -> Result<Self, Self::Error> {
+        match noun {
+            Noun::Atom(atom) => Ok(atom),
+            Noun::Cell(_) => Err(convert::Error::UnexpectedCell),
+        }
+    }
+}
+
+/// Converts an atom into an array of its little-endian bytes, zero-extended to `N` bytes.
+///
+/// Atoms never have trailing zero bytes, so a 32-byte key whose last byte is zero is an atom of
+/// fewer than 32 bytes, and converts back into the same key. Fails with
+/// [`AtomToUint`](convert::Error::AtomToUint) if the atom has more than `N` bytes.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::atom::Atom;
+/// let key = [0xab; 31].into_iter().chain([0]).collect::<Vec<_>>();
+/// let atom = Atom::from(key.clone());
+/// assert_eq!(atom.as_bytes().len(), 31);
+/// assert_eq!(<[u8; 32]>::try_from(&atom).unwrap()[..], key[..]);
+/// assert!(<[u8; 16]>::try_from(&atom).is_err());
+/// ```
+impl<const N: usize> TryFrom<&Atom> for [u8; N] {
+    type Error = convert::Error;
+
+    fn try_from(atom: &Atom) -> Result<Self, Self::Error> {
+        let bytes = atom.as_bytes();
+        if bytes.len() > N {
+            return Err(convert::Error::AtomToUint);
+        }
+        let mut array = [0; N];
+        array[..bytes.len()].copy_from_slice(bytes);
+        Ok(array)
+    }
+}
+
+/// Converts an atom into an array of its little-endian bytes, zero-extended to `N` bytes, just
+/// like converting the atom itself.
+impl<const N: usize> TryFrom<&Noun> for [u8; N] {
+    type Error = convert::Error;
+
+    fn try_from(noun: &Noun) -> Result<Self, Self::Error> {
+        match noun {
+            Noun::Atom(atom) => Self::try_from(atom),
+            Noun::Cell(_) => Err(convert::Error::UnexpectedCell),
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a Noun> for &'a Cell {
+    type Error = convert::Error;
+
+    fn try_from(noun: &'a Noun) -> Result<Self, Self::Error> {
+        match noun {
+            Noun::Atom(_) => Err(convert::Error::UnexpectedAtom),
+            Noun::Cell(cell) => Ok(cell),
+        }
+    }
+}
+
 impl TryFrom<&Noun> for String {
     type Error = convert::Error;
 
@@ -282,47 +1551,184 @@ impl TryFrom<&Noun> for String {
     }
 }
 
-#[cfg(feature = "thread-safe")]
-unsafe impl Send for Noun {}
-
-#[cfg(feature = "thread-safe")]
-unsafe impl Sync for Noun {}
+impl<'a> IntoIterator for &'a Noun {
+    type Item = Result<&'a Noun, convert::Error>;
+    type IntoIter = ListIter<'a>;
+
+    /// Iterates over the elements of a null-terminated list `[e0 e1 ... eN 0]`.
+    ///
+    /// See [`ListIter`] for how nouns that aren't lists are iterated.
+    fn into_iter(self) -> ListIter<'a> {
+        ListIter {
+            rest: List::Noun(self),
+            index: 0,
+        }
+    }
+}
+
+/// An atom that violates the invariants every atom is supposed to uphold, as found by
+/// [`Noun::validate()`].
+///
+/// `axis` is the axis of the atom within the validated noun, or `None` if the atom is more than
+/// 63 cells deep, where its axis doesn't fit in a [`u64`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum InvariantError {
+    /// The atom has trailing zero bytes.
+    TrailingZeros { axis: Option<u64> },
+    /// The cached length in bits of the atom doesn't match its bytes.
+    BitLen {
+        axis: Option<u64>,
+        cached: u64,
+        actual: u64,
+    },
+}
+
+impl InvariantError {
+    /// Returns the axis of the atom that violates the invariants, if it fits in a [`u64`].
+    pub fn axis(&self) -> Option<u64> {
+        match self {
+            Self::TrailingZeros { axis } | Self::BitLen { axis, .. } => *axis,
+        }
+    }
+}
+
+impl Display for InvariantError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        match self.axis() {
+            Some(axis) => write!(f, "the atom at axis {} ", axis)?,
+            None => write!(f, "an atom more than 63 cells deep ")?,
+        }
+        match self {
+            Self::TrailingZeros { .. } => write!(f, "has trailing zero bytes"),
+            Self::BitLen { cached, actual, .. } => write!(
+                f,
+                "has a cached length of {} bits, but its bytes are {} bits long",
+                cached, actual
+            ),
+        }
+    }
+}
+
+impl std::error::Error for InvariantError {}
+
+/// An iterator over the elements of a null-terminated list `[e0 e1 ... eN 0]`.
+///
+/// A list ends at its null terminator, so null iterates as the empty list. Any other atom, whether
+/// it's the noun being iterated or the end of an improper list like `[e0 e1 5]`, ends the
+/// iteration with an [`ExpectedNull`](convert::Error::ExpectedNull) error at the position and axis
+/// of the atom, after the elements before it.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{cell::Cell, convert::Error, Noun};
+/// let list = Noun::from(Cell::from(["a", "b", ""]));
+/// let elems: Vec<_> = list.into_iter().collect::<Result<_, _>>().unwrap();
+/// assert_eq!(elems, [&Noun::from("a"), &Noun::from("b")]);
+///
+/// let improper = Noun::from(Cell::from(["a", "b"]));
+/// let mut iter = improper.into_iter();
+/// assert_eq!(iter.next().unwrap().unwrap(), &Noun::from("a"));
+/// let err = iter.next().unwrap().unwrap_err();
+/// assert!(matches!(err, Error::At { axis: Some(3), .. }));
+/// assert!(iter.next().is_none());
+///
+/// assert_eq!(Noun::null().into_iter().count(), 0);
+/// ```
+#[derive(Clone, Debug)]
+pub struct ListIter<'a> {
+    rest: List<'a>,
+    index: usize,
+}
+
+/// The rest of a list being iterated.
+#[derive(Clone, Debug)]
+enum List<'a> {
+    Cell(&'a Cell),
+    Noun(&'a Noun),
+    Done,
+}
+
+impl<'a> ListIter<'a> {
+    /// Creates an iterator over the list `cell`.
+    pub(crate) fn from_cell(cell: &'a Cell) -> Self {
+        Self {
+            rest: List::Cell(cell),
+            index: 0,
+        }
+    }
+}
+
+impl<'a> Iterator for ListIter<'a> {
+    type Item = Result<&'a Noun, convert::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let cell = match std::mem::replace(&mut self.rest, List::Done) {
+            List::Done => return None,
+            List::Cell(cell) | List::Noun(Noun::Cell(cell)) => cell,
+            List::Noun(Noun::Atom(atom)) if atom.is_null() => return None,
+            List::Noun(Noun::Atom(_)) => {
+                return Some(Err(convert::Error::ExpectedNull.nest(
+                    Some(convert::PathSegment::Index(self.index)),
+                    convert::tail_axis(self.index),
+                )))
+            }
+        };
+        self.rest = List::Noun(cell.tail_ref());
+        self.index += 1;
+        Some(Ok(cell.head_ref()))
+    }
+}
+
+impl FusedIterator for ListIter<'_> {}
+
+#[cfg(feature = "thread-safe")]
+unsafe impl Send for Noun {}
+
+#[cfg(feature = "thread-safe")]
+unsafe impl Sync for Noun {}
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::serdes::{EnclosingCell, Item, Limit, Position, Side};
 
     #[test]
     fn jam_cue_atom() {
         // 0 serializes to 2.
         {
-            let atom: Noun = Noun::from(Atom::from(0u8));
+            let atom: Noun = Noun::from(0u8);
             let jammed_atom = Atom::from(2u8);
             assert_eq!(atom.clone().jam(), jammed_atom);
+            assert_eq!(atom.jam_size_bits(), jammed_atom.bit_len() as u64);
             assert_eq!(Noun::cue(jammed_atom).expect("cue"), atom);
         }
 
         // 1 serializes to 12.
         {
-            let atom: Noun = Noun::from(Atom::from(1u8));
+            let atom: Noun = Noun::from(1u8);
             let jammed_atom = Atom::from(12u8);
             assert_eq!(atom.clone().jam(), jammed_atom);
+            assert_eq!(atom.jam_size_bits(), jammed_atom.bit_len() as u64);
             assert_eq!(Noun::cue(jammed_atom).expect("cue"), atom);
         }
 
         // 2 serializes to 72.
         {
-            let atom: Noun = Noun::from(Atom::from(2u8));
+            let atom: Noun = Noun::from(2u8);
             let jammed_atom = Atom::from(72u8);
             assert_eq!(atom.clone().jam(), jammed_atom);
+            assert_eq!(atom.jam_size_bits(), jammed_atom.bit_len() as u64);
             assert_eq!(Noun::cue(jammed_atom).expect("cue"), atom);
         }
 
         // 19 serializes to 2480.
         {
-            let atom: Noun = Noun::from(Atom::from(19u8));
+            let atom: Noun = Noun::from(19u8);
             let jammed_atom = Atom::from(2480u16);
             assert_eq!(atom.clone().jam(), jammed_atom);
+            assert_eq!(atom.jam_size_bits(), jammed_atom.bit_len() as u64);
             assert_eq!(Noun::cue(jammed_atom).expect("cue"), atom);
         }
 
@@ -331,10 +1737,65 @@ mod tests {
             let atom: Noun = Noun::from(Atom::from(581_949_002u32));
             let jammed_atom = Atom::from(1_191_831_557_952u64);
             assert_eq!(atom.clone().jam(), jammed_atom);
+            assert_eq!(atom.jam_size_bits(), jammed_atom.bit_len() as u64);
             assert_eq!(Noun::cue(jammed_atom).expect("cue"), atom);
         }
     }
 
+    #[test]
+    fn jam_cue_backref_crossover() {
+        // A repeated atom is encoded again, rather than as a backreference, when it's no longer
+        // than the backreference's index. These vectors come from a direct transliteration of
+        // `++jam` and `++mat` from hoon.hoon, and cover atoms on either side of the crossover.
+        let vectors: [(Noun, Atom); 8] = [
+            // Index 2 (2 bits): 3 is repeated, 4 is referenced.
+            (Noun::from(Cell::from([3u8, 3u8])), Atom::from(53_665u32)),
+            (Noun::from(Cell::from([4u8, 4u8])), Atom::from(151_137u32)),
+            // Index 16 (5 bits): 31 is repeated, 32 is referenced.
+            (
+                Noun::from(Cell::from([16u8, 31u8, 31u8])),
+                Atom::from(1_078_300_008_641u64),
+            ),
+            (
+                Noun::from(Cell::from([16u8, 32u8, 32u8])),
+                Atom::from(2_252_447_178_945u64),
+            ),
+            // Index 58 (6 bits): 63 is repeated, 64 is referenced.
+            (
+                Noun::from(Cell::from([1u64 << 40, 63, 63])),
+                Atom::from(vec![
+                    0x01, 0x26, 0x00, 0x00, 0x00, 0x00, 0x80, 0x41, 0x7f, 0xe8, 0x0f,
+                ]),
+            ),
+            (
+                Noun::from(Cell::from([1u64 << 40, 64, 64])),
+                Atom::from(vec![
+                    0x01, 0x26, 0x00, 0x00, 0x00, 0x00, 0x80, 0xc1, 0x81, 0xa3, 0x3a,
+                ]),
+            ),
+            // Index 120 (7 bits): 127 is repeated, 128 is referenced.
+            (
+                Noun::from(Cell::from([1u128 << 100, 127, 127])),
+                Atom::from(vec![
+                    0x01, 0x2c, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                    0x00, 0x60, 0xf0, 0x3f, 0xfc, 0x0f,
+                ]),
+            ),
+            (
+                Noun::from(Cell::from([1u128 << 100, 128, 128])),
+                Atom::from(vec![
+                    0x01, 0x2c, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                    0x00, 0x60, 0x20, 0x00, 0xc7, 0xf1,
+                ]),
+            ),
+        ];
+        for (noun, jammed_noun) in vectors {
+            assert_eq!(noun.clone().jam(), jammed_noun);
+            assert_eq!(noun.jam_size_bits(), jammed_noun.bit_len() as u64);
+            assert_eq!(Noun::cue(jammed_noun).expect("cue"), noun);
+        }
+    }
+
     #[test]
     fn jam_cue_cell() {
         // [0 19] serializes into 39.689.
@@ -342,6 +1803,7 @@ mod tests {
             let cell: Noun = Noun::from(Cell::from([0u8, 19u8]));
             let jammed_cell = Atom::from(39_689u16);
             assert_eq!(cell.clone().jam(), jammed_cell);
+            assert_eq!(cell.jam_size_bits(), jammed_cell.bit_len() as u64);
             assert_eq!(Noun::cue(jammed_cell).expect("cue"), cell);
         }
 
@@ -350,6 +1812,7 @@ mod tests {
             let cell: Noun = Noun::from(Cell::from([1u8, 1u8]));
             let jammed_cell = Atom::from(817u16);
             assert_eq!(cell.clone().jam(), jammed_cell);
+            assert_eq!(cell.jam_size_bits(), jammed_cell.bit_len() as u64);
             assert_eq!(Noun::cue(jammed_cell).expect("cue"), cell);
         }
 
@@ -358,6 +1821,7 @@ mod tests {
             let cell: Noun = Noun::from(Cell::from([10_000u16, 10_000u16]));
             let jammed_cell = Atom::from(0b100100111001110001000011010000001u64);
             assert_eq!(cell.clone().jam(), jammed_cell);
+            assert_eq!(cell.jam_size_bits(), jammed_cell.bit_len() as u64);
             assert_eq!(Noun::cue(jammed_cell).expect("cue"), cell);
         }
 
@@ -366,6 +1830,7 @@ mod tests {
             let cell: Noun = Noun::from(Cell::from([999_999_999u32, 999_999_999u32]));
             let jammed_cell = Atom::from(0b100100111110111001101011001001111111111110100000001u64);
             assert_eq!(cell.clone().jam(), jammed_cell);
+            assert_eq!(cell.jam_size_bits(), jammed_cell.bit_len() as u64);
             assert_eq!(Noun::cue(jammed_cell).expect("cue"), cell);
         }
 
@@ -374,6 +1839,7 @@ mod tests {
             let cell: Noun = Noun::from(Cell::from([222u16, 444u16, 888u16]));
             let jammed_cell = Atom::from(250_038_217_192_960_129u64);
             assert_eq!(cell.clone().jam(), jammed_cell);
+            assert_eq!(cell.jam_size_bits(), jammed_cell.bit_len() as u64);
             assert_eq!(Noun::cue(jammed_cell).expect("cue"), cell);
         }
 
@@ -383,6 +1849,7 @@ mod tests {
             let cell: Noun = Noun::from(Cell::from([head.clone(), head]));
             let jammed_cell = Atom::from(0b1001001111011101110000110101111100000101u64);
             assert_eq!(cell.clone().jam(), jammed_cell);
+            assert_eq!(cell.jam_size_bits(), jammed_cell.bit_len() as u64);
             assert_eq!(Noun::cue(jammed_cell).expect("cue"), cell);
         }
 
@@ -394,6 +1861,7 @@ mod tests {
 
             let jammed_cell = Atom::from(25_681_224_503_728_653_597_984_370_231_065u128);
             assert_eq!(cell.clone().jam(), jammed_cell);
+            assert_eq!(cell.jam_size_bits(), jammed_cell.bit_len() as u64);
             assert_eq!(Noun::cue(jammed_cell).expect("cue"), cell);
         }
 
@@ -402,6 +1870,7 @@ mod tests {
             let cell: Noun = Noun::from(Cell::from([99u8, 100u8, 101u8, 102u8, 103u8, 104u8, 0u8]));
             let jammed_cell = Atom::from(223_372_995_869_285_333_705_242_560_449u128);
             assert_eq!(cell.clone().jam(), jammed_cell);
+            assert_eq!(cell.jam_size_bits(), jammed_cell.bit_len() as u64);
             assert_eq!(Noun::cue(jammed_cell).expect("cue"), cell);
         }
 
@@ -411,6 +1880,7 @@ mod tests {
             let cell: Noun = Noun::from(Cell::from([head.clone(), head]));
             let jammed_cell = Atom::from(170_479_614_045_978_345_989u128);
             assert_eq!(cell.clone().jam(), jammed_cell);
+            assert_eq!(cell.jam_size_bits(), jammed_cell.bit_len() as u64);
             assert_eq!(Noun::cue(jammed_cell).expect("cue"), cell);
         }
 
@@ -421,10 +1891,11 @@ mod tests {
                 Noun::from(Cell::from([1u8, 2u8])),
                 Noun::from(Cell::from([2u8, 3u8])),
                 Noun::from(Cell::from([3u8, 4u8])),
-                Noun::from(Atom::from(0u8)),
+                Noun::from(0u8),
             ]));
             let jammed_cell = Atom::from(11_976_248_475_217_237_797u64);
             assert_eq!(cell.clone().jam(), jammed_cell);
+            assert_eq!(cell.jam_size_bits(), jammed_cell.bit_len() as u64);
             assert_eq!(Noun::cue(jammed_cell).expect("cue"), cell);
         }
 
@@ -441,13 +1912,14 @@ mod tests {
                 Noun::from(Cell::from([6u8, 7u8])),
                 Noun::from(Cell::from([7u8, 8u8])),
                 Noun::from(Cell::from([8u8, 9u8])),
-                Noun::from(Atom::from(0u8)),
+                Noun::from(0u8),
             ]));
             let jammed_cell = Atom::from(vec![
                 37, 23, 35, 11, 137, 46, 52, 102, 97, 226, 22, 46, 118, 97, 227, 23, 62, 4, 11,
                 130, 144, 20,
             ]);
             assert_eq!(cell.clone().jam(), jammed_cell);
+            assert_eq!(cell.jam_size_bits(), jammed_cell.bit_len() as u64);
             assert_eq!(Noun::cue(jammed_cell).expect("cue"), cell);
         }
 
@@ -467,13 +1939,14 @@ mod tests {
                 Noun::from(Cell::from([16u8, 17u8])),
                 Noun::from(Cell::from([18u8, 19u8])),
                 Noun::from(Cell::from([20u8, 21u8])),
-                Noun::from(Atom::from(0u8)),
+                Noun::from(0u8),
             ]));
             let jammed_cell = Atom::from(vec![
                 37, 23, 18, 93, 152, 184, 133, 141, 95, 16, 132, 100, 65, 20, 178, 5, 97, 72, 23,
                 196, 33, 95, 48, 8, 139, 5, 147, 176, 89, 48, 10, 171, 2,
             ]);
             assert_eq!(cell.clone().jam(), jammed_cell);
+            assert_eq!(cell.jam_size_bits(), jammed_cell.bit_len() as u64);
             assert_eq!(Noun::cue(jammed_cell).expect("cue"), cell);
         }
 
@@ -489,6 +1962,7 @@ mod tests {
                 86, 6, 71, 215, 82, 228, 54, 246, 70, 150, 230, 118, 6,
             ]);
             assert_eq!(cell.clone().jam(), jammed_cell);
+            assert_eq!(cell.jam_size_bits(), jammed_cell.bit_len() as u64);
             assert_eq!(Noun::cue(jammed_cell).expect("cue"), cell);
         }
 
@@ -497,6 +1971,7 @@ mod tests {
             let cell: Noun = Noun::from(Cell::from(["x-cached", "HIT"]));
             let jammed_cell = Atom::from(3_419_056_981_361_227_851_413_339_139_505_665u128);
             assert_eq!(cell.clone().jam(), jammed_cell);
+            assert_eq!(cell.jam_size_bits(), jammed_cell.bit_len() as u64);
             assert_eq!(Noun::cue(jammed_cell).expect("cue"), cell);
         }
 
@@ -513,7 +1988,7 @@ mod tests {
                 Noun::from(Cell::from(["x-cached", "HIT"])),
                 Noun::from(Cell::from(["vary", "Origin"])),
                 Noun::from(Cell::from(["vary", "Accept-Encoding"])),
-                Noun::from(Atom::from(0u8)),
+                Noun::from(0u8),
             ]));
             let jammed_cell = Atom::from(vec![
                 5, 248, 241, 90, 198, 194, 198, 208, 202, 200, 192, 67, 74, 162, 22, 240, 237, 194,
@@ -521,6 +1996,7 @@ mod tests {
                 28, 93, 75, 145, 219, 216, 27, 89, 154, 219, 89,
             ]);
             assert_eq!(cell.clone().jam(), jammed_cell);
+            assert_eq!(cell.jam_size_bits(), jammed_cell.bit_len() as u64);
             assert_eq!(Noun::cue(jammed_cell).expect("cue"), cell);
         }
 
@@ -539,7 +2015,7 @@ mod tests {
                 Noun::from(Cell::from(["vary", "Origin"])),
                 Noun::from(Cell::from(["vary", "Accept-Encoding"])),
                 Noun::from(Cell::from(["connection", "keep-alive"])),
-                Noun::from(Atom::from(0u8)),
+                Noun::from(0u8),
             ]));
             let jammed_cell = Atom::from(vec![
                 5, 248, 241, 90, 198, 194, 198, 208, 202, 200, 192, 67, 74, 162, 22, 240, 237, 194,
@@ -548,6 +2024,7 @@ mod tests {
                 99, 116, 105, 111, 110, 128, 207, 90, 89, 25, 92, 75, 24, 91, 154, 93, 89,
             ]);
             assert_eq!(cell.clone().jam(), jammed_cell);
+            assert_eq!(cell.jam_size_bits(), jammed_cell.bit_len() as u64);
             assert_eq!(Noun::cue(jammed_cell).expect("cue"), cell);
         }
 
@@ -568,7 +2045,7 @@ mod tests {
                 Noun::from(Cell::from(["vary", "Accept-Encoding"])),
                 Noun::from(Cell::from(["connection", "keep-alive"])),
                 Noun::from(Cell::from(["content-length", "59"])),
-                Noun::from(Atom::from(0u8)),
+                Noun::from(0u8),
             ]));
 
             let jammed_cell = Atom::from(vec![
@@ -579,6 +2056,7 @@ mod tests {
                 99, 111, 110, 116, 101, 110, 116, 45, 108, 101, 110, 103, 116, 104, 208, 53, 185,
             ]);
             assert_eq!(cell.clone().jam(), jammed_cell);
+            assert_eq!(cell.jam_size_bits(), jammed_cell.bit_len() as u64);
             assert_eq!(Noun::cue(jammed_cell).expect("cue"), cell);
         }
 
@@ -601,7 +2079,7 @@ mod tests {
                 Noun::from(Cell::from(["connection", "keep-alive"])),
                 Noun::from(Cell::from(["content-length", "59"])),
                 Noun::from(Cell::from(["content-type", "application/json"])),
-                Noun::from(Atom::from(0u8)),
+                Noun::from(0u8),
             ]));
 
             let jammed_cell = Atom::from(vec![
@@ -614,6 +2092,7 @@ mod tests {
                 56, 182, 180, 177, 48, 186, 180, 55, 183, 23, 181, 185, 55, 183,
             ]);
             assert_eq!(cell.clone().jam(), jammed_cell);
+            assert_eq!(cell.jam_size_bits(), jammed_cell.bit_len() as u64);
             assert_eq!(Noun::cue(jammed_cell).expect("cue"), cell);
         }
 
@@ -638,7 +2117,7 @@ mod tests {
                 Noun::from(Cell::from(["content-length", "59"])),
                 Noun::from(Cell::from(["content-type", "application/json"])),
                 Noun::from(Cell::from(["date", "Fri, 08 Jul 2022 16:43:50 GMT"])),
-                Noun::from(Atom::from(0u8)),
+                Noun::from(0u8),
             ]));
 
             let jammed_cell = Atom::from(vec![
@@ -653,6 +2132,7 @@ mod tests {
                 198, 70, 135, 102, 70, 167, 6, 6, 228, 168, 137, 42,
             ]);
             assert_eq!(cell.clone().jam(), jammed_cell);
+            assert_eq!(cell.jam_size_bits(), jammed_cell.bit_len() as u64);
             assert_eq!(Noun::cue(jammed_cell).expect("cue"), cell);
         }
 
@@ -665,6 +2145,7 @@ mod tests {
                 26, 23, 24, 16, 148, 42, 177, 58, 55, 186, 186, 20,
             ]);
             assert_eq!(cell.clone().jam(), jammed_cell);
+            assert_eq!(cell.jam_size_bits(), jammed_cell.bit_len() as u64);
             assert_eq!(Noun::cue(jammed_cell).expect("cue"), cell);
         }
 
@@ -692,7 +2173,7 @@ mod tests {
                 Noun::from(Cell::from(["content-type", "application/json"])),
                 Noun::from(Cell::from(["date", "Fri, 08 Jul 2022 16:43:50 GMT"])),
                 Noun::from(Cell::from(["server", "nginx/1.14.0 (Ubuntu)"])),
-                Noun::from(Atom::from(0u8)),
+                Noun::from(0u8),
             ]));
             let jammed_cell = Atom::from(vec![
                 5, 248, 241, 90, 198, 194, 198, 208, 202, 200, 192, 67, 74, 162, 22, 240, 237, 194,
@@ -727,19 +2208,19 @@ mod tests {
         // 534926240328183504043224467158150263359506153835684400054708654784265586779466767311401093568872399514089871794788465339360316141009784521401502584590628538383397474667076686296931914112162585777490924604432397967740482953038069595525949395091512693509388265834094423223982487236123144939682105684811115401159600617316591045520893570145126936115415644005172954075003434319780206191080707020476210689
         {
             let cell: Noun = Noun::from(Cell::from([
-                Noun::from(Atom::from("request")),
-                Noun::from(Atom::from(0u8)),
-                Noun::from(Atom::from("POST")),
-                Noun::from(Atom::from("http://eth-mainnet.urbit.org:8545")),
+                Noun::from("request"),
+                Noun::from(0u8),
+                Noun::from("POST"),
+                Noun::from("http://eth-mainnet.urbit.org:8545"),
                 Noun::from(Cell::from([
                     Noun::from(Cell::from([
                         Atom::from("Content-Type"),
                         Atom::from("application/json"),
                     ])),
-                    Noun::from(Atom::from(0u8)),
+                    Noun::from(0u8),
                 ])),
-                Noun::from(Atom::from(0u8)),
-                Noun::from(Atom::from(78u8)),
+                Noun::from(0u8),
+                Noun::from(78u8),
                 Noun::from(Atom::from(
                     r#"[{"params":[],"id":"block number","jsonrpc":"2.0","method":"eth_blockNumber"}]"#,
                 )),
@@ -760,4 +2241,1749 @@ mod tests {
             assert_eq!(Noun::cue(jammed_cell).expect("cue"), cell);
         }
     }
+
+    #[test]
+    fn jam_borrowed() {
+        // [[107 110] [107 110]] serializes to 635.080.761.093.
+        {
+            let head = Rc::<Noun>::from(Cell::from([107u8, 110u8]));
+            let cell: Noun = Noun::from(Cell::from([head.clone(), head]));
+            let jammed_cell = Atom::from(0b1001001111011101110000110101111100000101u64);
+            assert_eq!((&cell).jam(), jammed_cell);
+            assert_eq!(Rc::new(cell.clone()).jam(), jammed_cell);
+            assert_eq!(Box::new(cell.clone()).jam(), jammed_cell);
+            assert_eq!(cell.jam(), jammed_cell);
+        }
+
+        // [%x-cached 'HIT'] serializes to 3.419.056.981.361.227.851.413.339.139.505.665.
+        {
+            let cell = Rc::<Noun>::from(Cell::from(["x-cached", "HIT"]));
+            let jammed_cell = Atom::from(3_419_056_981_361_227_851_413_339_139_505_665u128);
+            assert_eq!((&*cell).jam(), jammed_cell);
+            assert_eq!(cell.clone().jam(), jammed_cell);
+            assert_eq!(cell.jam_size_bits(), jammed_cell.bit_len() as u64);
+            // Jamming a borrowed noun doesn't take ownership of it.
+            assert_eq!(Rc::strong_count(&cell), 1);
+        }
+
+        // Jamming a borrowed noun and jamming an owned noun agree.
+        {
+            let noun = Noun::from(Cell::from([
+                Noun::from(Cell::from([0u8, 1u8])),
+                Noun::from(Cell::from([1u8, 2u8])),
+                Noun::from(Cell::from([0u8, 1u8])),
+                Noun::from(0u8),
+            ]));
+            let jammed_noun = (&noun).jam();
+            assert_eq!(noun.clone().jam(), jammed_noun);
+            assert_eq!(noun.jam_size_bits(), jammed_noun.bit_len() as u64);
+            assert_eq!(Noun::cue(jammed_noun).expect("cue"), noun);
+        }
+    }
+
+    #[test]
+    fn jam_to_vec_cue_bytes() {
+        // 0 serializes to 0b10, which leaves the six most significant bits of the only byte
+        // unused.
+        {
+            let atom = Noun::from(0u8);
+            assert_eq!(atom.jam_to_vec(), vec![0b10]);
+            assert_eq!(Noun::cue_bytes(&[0b10]).expect("cue"), atom);
+            assert_eq!(Noun::cue_bytes(&[0b10, 0, 0, 0]).expect("cue"), atom);
+        }
+
+        // [0 1 2 3 4 5 6 7 8 9 10] serializes to 25.681.224.503.728.653.597.984.370.231.065.
+        {
+            let cell = Noun::from(Cell::from([
+                0u8, 1u8, 2u8, 3u8, 4u8, 5u8, 6u8, 7u8, 8u8, 9u8, 10u8,
+            ]));
+            let jammed_cell = 25_681_224_503_728_653_597_984_370_231_065u128.to_le_bytes();
+            let len = jammed_cell
+                .iter()
+                .rposition(|byte| *byte != 0)
+                .expect("nonzero")
+                + 1;
+            assert_eq!(cell.jam_to_vec(), &jammed_cell[..len]);
+            // The unused high bytes of the u128 are trailing zero bytes.
+            assert_eq!(Noun::cue_bytes(&jammed_cell).expect("cue"), cell);
+        }
+
+        // The byte-level and atom-level APIs agree, and jamming never produces a trailing zero
+        // byte.
+        {
+            let nouns = [
+                Noun::from(Atom::from(581_949_002u32)),
+                Noun::from(Cell::from([10_000u16, 10_000u16])),
+                Noun::from(Cell::from([
+                    Cell::from(["vary", "Origin"]),
+                    Cell::from(["vary", "Accept-Encoding"]),
+                ])),
+            ];
+            for noun in nouns {
+                let bytes = noun.jam_to_vec();
+                assert_ne!(bytes.last(), Some(&0));
+                assert_eq!(Atom::from(bytes.clone()), (&noun).jam());
+                assert_eq!(Noun::cue_bytes(&bytes).expect("cue"), noun);
+            }
+        }
+    }
+
+    /// A writer that accepts at most a few bytes per call and is sometimes interrupted.
+    struct ShortWriter {
+        bytes: Vec<u8>,
+        calls: usize,
+    }
+
+    impl Write for ShortWriter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.calls += 1;
+            if self.calls.is_multiple_of(5) {
+                return Err(io::Error::from(io::ErrorKind::Interrupted));
+            }
+            let len = buf.len().min(3);
+            self.bytes.extend_from_slice(&buf[..len]);
+            Ok(len)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn jam_nodedup() {
+        // Nouns without repeated subnouns jam identically either way.
+        {
+            let nouns = [
+                Noun::from(0u8),
+                Noun::from(19u8),
+                Noun::from(Cell::from([0u8, 19u8])),
+                Noun::from(Cell::from([222u16, 444u16, 888u16])),
+                Noun::from(Cell::from([
+                    Noun::from(Cell::from(["x-cached", "HIT"])),
+                    Noun::from(Cell::from(["content-type", "text/plain"])),
+                    Noun::from(0u8),
+                ])),
+            ];
+            for noun in nouns {
+                assert_eq!(noun.jam_nodedup(), noun.clone().jam());
+            }
+        }
+
+        // Nouns with repeated subnouns jam to larger output that cues to the same noun.
+        {
+            let header = Rc::new(Noun::from(Cell::from(["vary", "Accept-Encoding"])));
+            let nouns = [
+                Noun::from(Cell::from([10_000u16, 10_000u16])),
+                Noun::from(Cell::from([header.clone(), header.clone(), header])),
+            ];
+            for noun in nouns {
+                let jammed_noun = noun.jam_nodedup();
+                assert!(jammed_noun.bit_len() > noun.clone().jam().bit_len());
+                assert_eq!(Noun::cue(jammed_noun).expect("cue"), noun);
+            }
+        }
+
+        // The cap stops runaway output from heavily shared nouns.
+        {
+            let mut noun = Rc::new(Noun::from(Atom::from(0xdead_beef_u32)));
+            for _ in 0..64 {
+                noun = Rc::new(Noun::from(Cell::from([noun.clone(), noun])));
+            }
+            assert_eq!(noun.jam_nodedup_capped(1 << 20), None);
+        }
+    }
+
+    #[test]
+    fn jam_capped() {
+        let request = |body: Atom| {
+            Noun::from(Cell::from([
+                Noun::from("POST"),
+                Noun::from("/~/channel"),
+                Noun::from(body.clone()),
+                Noun::from(body),
+            ]))
+        };
+        let is_capped = |res: serdes::Result<Atom>, max_bits: u64| {
+            matches!(
+                res,
+                Err(serdes::Error::LimitExceeded {
+                    which: serdes::Limit::JamBits,
+                    limit,
+                }) if limit == max_bits
+            )
+        };
+
+        // A noun right at the cap jams just like it does without one, and a bit less fails.
+        {
+            let noun = request(Atom::from(vec![0xff; 1000]));
+            let max_bits = noun.jam_size_bits();
+            assert_eq!(noun.jam_capped(max_bits).ok(), Some(noun.clone().jam()));
+            assert_eq!(noun.jam_capped(u64::MAX).ok(), Some(noun.clone().jam()));
+            assert!(is_capped(noun.jam_capped(max_bits - 1), max_bits - 1));
+        }
+
+        // A body one bit longer than fits fails.
+        {
+            let max_bits = request(Atom::from(u64::MAX >> 1)).jam_size_bits();
+            let noun = request(Atom::from(u64::MAX));
+            assert!(is_capped(noun.jam_capped(max_bits), max_bits));
+        }
+
+        // A cap of nothing fails even for the smallest noun.
+        assert!(is_capped(Noun::null().jam_capped(0), 0));
+    }
+
+    #[test]
+    fn jam_shared() {
+        // A noun whose head and tail are both the same noun, 64 levels deep, which has 2^64 atoms
+        // but only 65 distinct allocations.
+        let mut noun = Rc::new(Noun::from(Atom::from(0xdead_beef_u32)));
+        for _ in 0..64 {
+            noun = Rc::new(Noun::from(Cell::from([noun.clone(), noun])));
+        }
+        let jammed_noun = noun.jam_to_vec();
+        assert!(jammed_noun.len() < 1024);
+        assert_eq!(
+            noun.jam_size_bits(),
+            Atom::from(jammed_noun.clone()).bit_len() as u64
+        );
+
+        // Value-equal subnouns that don't share an allocation are still deduplicated.
+        let mut copy = Rc::new(Noun::from(Atom::from(0xdead_beef_u32)));
+        for _ in 0..64 {
+            let head = copy.clone();
+            copy = Rc::new(Noun::from(Cell::from([head, Rc::new((*copy).clone())])));
+        }
+        assert_eq!(copy.jam_to_vec(), jammed_noun);
+
+        let cued = Noun::cue_bytes(&jammed_noun).expect("cue");
+        assert_eq!(cued.jam_to_vec(), jammed_noun);
+    }
+
+    #[test]
+    fn jam_to_writer() {
+        let nouns = [
+            Noun::from(0u8),
+            Noun::from(19u8),
+            Noun::from(Cell::from([222u16, 444u16, 888u16])),
+            Noun::from(Cell::from([
+                Noun::from(Cell::from(["x-cached", "HIT"])),
+                Noun::from(Cell::from(["vary", "Origin"])),
+                Noun::from(Cell::from(["vary", "Accept-Encoding"])),
+                Noun::from(0u8),
+            ])),
+            // Large enough to overflow the sink's internal buffer several times.
+            Noun::from(Cell::from([
+                Atom::from(vec![0xa5; 10_000]),
+                Atom::from(vec![0x5a; 10_001]),
+                Atom::from(vec![0xa5; 10_000]),
+            ])),
+        ];
+        for noun in nouns {
+            let expected = noun.jam_to_vec();
+
+            let mut bytes = Vec::new();
+            let len = noun.jam_to_writer(&mut bytes).expect("jam to Vec");
+            assert_eq!(len, expected.len() as u64);
+            assert_eq!(bytes, expected);
+
+            let mut writer = ShortWriter {
+                bytes: Vec::new(),
+                calls: 0,
+            };
+            let len = noun.jam_to_writer(&mut writer).expect("jam to ShortWriter");
+            assert_eq!(len, expected.len() as u64);
+            assert_eq!(writer.bytes, expected);
+        }
+    }
+
+    #[test]
+    fn jam_reader() {
+        let nouns = [
+            Noun::from(0u8),
+            Noun::from(19u8),
+            Noun::from(Cell::from([10_000u16, 10_000u16])),
+            Noun::from(Cell::from([
+                Noun::from(Cell::from(["x-cached", "HIT"])),
+                Noun::from(Cell::from(["vary", "Origin"])),
+                Noun::from(Cell::from(["vary", "Accept-Encoding"])),
+                Noun::from(0u8),
+            ])),
+            // Large enough to overflow the sink's internal buffer several times.
+            Noun::from(Cell::from([
+                Atom::from(vec![0xa5; 10_000]),
+                Atom::from(vec![0x5a; 10_001]),
+                Atom::from(vec![0xa5; 10_000]),
+            ])),
+        ];
+        for noun in nouns {
+            let expected = noun.jam_to_vec();
+
+            // All at once.
+            {
+                let mut reader = noun.jam_reader();
+                assert_eq!(reader.total_len(), expected.len() as u64);
+                let mut bytes = Vec::new();
+                let len = io::copy(&mut reader, &mut bytes).expect("copy");
+                assert_eq!(len, expected.len() as u64);
+                assert_eq!(bytes, expected);
+                assert_eq!(reader.read(&mut [0; 8]).expect("read at end"), 0);
+            }
+
+            // One byte at a time.
+            {
+                let mut reader = noun.jam_reader();
+                let mut bytes = Vec::new();
+                let mut byte = [0];
+                while reader.read(&mut byte).expect("read") == 1 {
+                    bytes.push(byte[0]);
+                }
+                assert_eq!(bytes, expected);
+            }
+        }
+
+        // A list too deep to jam recursively, read in odd-sized chunks.
+        {
+            let list = Noun::list_from(vec![Rc::new(Noun::from(7u8)); 100_000]);
+            let expected = list.jam_to_vec();
+            let mut reader = list.jam_reader();
+            let mut bytes = Vec::new();
+            let mut chunk = [0; 1000 - 7];
+            loop {
+                match reader.read(&mut chunk).expect("read") {
+                    0 => break,
+                    len => bytes.extend_from_slice(&chunk[..len]),
+                }
+            }
+            assert_eq!(bytes, expected);
+            std::mem::forget(list);
+        }
+    }
+
+    #[cfg(feature = "crypto")]
+    #[test]
+    fn content_hash() {
+        use sha2::{Digest, Sha256};
+
+        // [0 19], whose jam is [0x09, 0x9b].
+        {
+            let noun = Noun::from(Cell::from([0u8, 19u8]));
+            let expected = [
+                0x63, 0xd4, 0x53, 0x3a, 0xe1, 0x5f, 0x1b, 0x93, 0xfa, 0x04, 0x85, 0x71, 0x2b, 0x4d,
+                0x14, 0xe8, 0xd0, 0x56, 0xe7, 0x84, 0xa6, 0x9f, 0xd6, 0xdc, 0x14, 0x32, 0xd0, 0xb7,
+                0x81, 0x55, 0xd5, 0xdb,
+            ];
+            assert_eq!(noun.content_hash(), expected);
+            assert_eq!(noun.content_hash_atom(), Atom::from(expected.to_vec()));
+            assert_eq!(noun.content_hash_atom().as_bytes()[0], 0x63);
+        }
+
+        // The hash is that of the jammed noun, including nouns that jam to more than one chunk.
+        {
+            let nouns = [
+                Noun::null(),
+                Noun::from(Atom::from(vec![0xab; 10_000])),
+                Noun::from(Cell::from(["hello", "world", ""])),
+                Noun::list_from((0..2_000u32).map(|i| Rc::new(Noun::from(i))).collect()),
+            ];
+            for noun in &nouns {
+                let expected: [u8; 32] = Sha256::digest(noun.jam_to_vec()).into();
+                assert_eq!(noun.content_hash(), expected);
+            }
+        }
+
+        // Equal nouns hash identically however their subnouns are shared.
+        {
+            let shared = Rc::new(Noun::from(Cell::from(["a", "b"])));
+            let noun = Noun::from(Cell::from([shared.clone(), shared]));
+            let copy = Noun::from(Cell::from([
+                Noun::from(Cell::from(["a", "b"])),
+                Noun::from(Cell::from(["a", "b"])),
+            ]));
+            assert_eq!(noun.content_hash(), copy.content_hash());
+            assert_ne!(
+                noun.content_hash(),
+                Noun::from(Cell::from(["a", "b"])).content_hash()
+            );
+        }
+    }
+
+    /// A reader that returns at most one byte per call.
+    struct ByteReader<R: Read>(R);
+
+    impl<R: Read> Read for ByteReader<R> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let len = buf.len().min(1);
+            self.0.read(&mut buf[..len])
+        }
+    }
+
+    #[test]
+    fn cue_from_reader() {
+        let nouns = [
+            Noun::from(0u8),
+            Noun::from(19u8),
+            Noun::from(Cell::from([222u16, 444u16, 888u16])),
+            Noun::from(Cell::from([
+                Noun::from(Cell::from(["x-cached", "HIT"])),
+                Noun::from(Cell::from(["vary", "Origin"])),
+                Noun::from(Cell::from(["vary", "Accept-Encoding"])),
+                Noun::from(0u8),
+            ])),
+            Noun::from(Cell::from([
+                Atom::from(vec![0xa5; 10_000]),
+                Atom::from(vec![0x5a; 10_001]),
+                Atom::from(vec![0xa5; 10_000]),
+            ])),
+        ];
+
+        // Cue every noun, one after another, from a reader that is read one byte at a time.
+        {
+            let mut bytes = Vec::new();
+            for noun in &nouns {
+                noun.jam_to_writer(&mut bytes).expect("jam to Vec");
+            }
+            let mut reader = ByteReader(io::Cursor::new(&bytes));
+            for noun in &nouns {
+                assert_eq!(
+                    Noun::cue_from_reader(&mut reader).expect("cue from reader"),
+                    *noun
+                );
+            }
+            assert_eq!(reader.0.position(), bytes.len() as u64);
+        }
+
+        // Cue every noun, one after another, from a file.
+        {
+            let path = std::env::temp_dir().join(format!("noun-cue-{}.jam", std::process::id()));
+            {
+                let mut file = std::fs::File::create(&path).expect("create file");
+                for noun in &nouns {
+                    noun.jam_to_writer(&mut file).expect("jam to file");
+                }
+            }
+            let mut reader = io::BufReader::new(std::fs::File::open(&path).expect("open file"));
+            for noun in &nouns {
+                assert_eq!(
+                    Noun::cue_from_reader(&mut reader).expect("cue from file"),
+                    *noun
+                );
+            }
+            std::fs::remove_file(&path).expect("remove file");
+        }
+
+        // A truncated jam fails to cue.
+        {
+            let bytes = nouns[3].jam_to_vec();
+            let reader = io::Cursor::new(&bytes[..bytes.len() - 1]);
+            assert!(Noun::cue_from_reader(reader).is_err());
+        }
+
+        // I/O errors are surfaced.
+        {
+            struct FailingReader;
+
+            impl Read for FailingReader {
+                fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+                    Err(io::Error::from(io::ErrorKind::BrokenPipe))
+                }
+            }
+
+            match Noun::cue_from_reader(FailingReader) {
+                Err(serdes::Error::Io(err)) => assert_eq!(err.kind(), io::ErrorKind::BrokenPipe),
+                res => panic!("unexpected result: {:?}", res),
+            }
+        }
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn async_jam_cue() {
+        let noun = Noun::from(Cell::from([
+            Noun::from(Cell::from(["vary", "Origin"])),
+            Noun::from(Cell::from(["vary", "Accept-Encoding"])),
+            Noun::from(Atom::from(vec![0xa5; 10_000])),
+        ]));
+        let bytes = noun.jam_to_vec();
+
+        {
+            let mut writer = tokio_test::io::Builder::new().write(&bytes).build();
+            assert_eq!(
+                noun.jam_to_async_writer(&mut writer).await.expect("jam"),
+                bytes.len() as u64
+            );
+        }
+
+        // The jammed noun arrives in several pieces.
+        {
+            let mut reader = tokio_test::io::Builder::new();
+            for chunk in bytes.chunks(1000) {
+                reader.read(chunk);
+            }
+            let reader = tokio::io::BufReader::new(reader.build());
+            assert_eq!(
+                Noun::cue_from_async_reader(reader, &CueLimits::default())
+                    .await
+                    .expect("cue"),
+                noun
+            );
+        }
+
+        // Decoding stops once the noun ends, in the middle of a piece, leaving the bytes that
+        // follow it unread, without waiting for the reader to end.
+        {
+            let mut reader = tokio_test::io::Builder::new();
+            reader.read(&bytes[..1000]);
+            let mut rest = bytes[1000..].to_vec();
+            rest.extend_from_slice(&[0x09, 0x9b]);
+            reader.read(&rest);
+            reader.wait(std::time::Duration::from_secs(3600));
+            let mut reader = tokio::io::BufReader::new(reader.build());
+            let cued = tokio::time::timeout(
+                std::time::Duration::from_secs(60),
+                Noun::cue_from_async_reader(&mut reader, &CueLimits::default()),
+            )
+            .await
+            .expect("cue without waiting")
+            .expect("cue");
+            assert_eq!(cued, noun);
+            assert_eq!(reader.buffer(), [0x09, 0x9b]);
+            assert_eq!(
+                Noun::cue_from_async_reader(&mut reader, &CueLimits::default())
+                    .await
+                    .expect("cue"),
+                Noun::from(Cell::from([0u8, 19u8]))
+            );
+        }
+
+        // A truncated jam fails to cue, and so does one that exceeds the limits.
+        {
+            let reader = tokio_test::io::Builder::new()
+                .read(&bytes[..bytes.len() - 1])
+                .build();
+            let err = Noun::cue_from_async_reader(
+                tokio::io::BufReader::new(reader),
+                &CueLimits::default(),
+            )
+            .await
+            .expect_err("truncated");
+            assert!(matches!(err, serdes::Error::UnexpectedEof(_)));
+
+            let limits = CueLimits {
+                max_atom_bytes: 1000,
+                ..CueLimits::default()
+            };
+            let atom = Noun::from(Atom::from(vec![0xa5; 2000])).jam_to_vec();
+            let reader = tokio_test::io::Builder::new().read(&atom).build();
+            let err = Noun::cue_from_async_reader(tokio::io::BufReader::new(reader), &limits)
+                .await
+                .expect_err("limit");
+            assert!(matches!(err, serdes::Error::LimitExceeded { .. }));
+        }
+    }
+
+    #[test]
+    fn partial_eq() {
+        {
+            let noun = Noun::from("request");
+            assert_eq!(noun, "request");
+            assert_eq!("request", noun);
+            assert_ne!(noun, "response");
+        }
+
+        {
+            let noun = Noun::from(0u8);
+            assert_eq!(noun, 0u8);
+            assert_eq!(0u16, noun);
+            assert_eq!(noun, 0usize);
+            assert_ne!(noun, 1u8);
+        }
+
+        {
+            let noun = Noun::from(Atom::from(u128::MAX));
+            assert_eq!(noun, u128::MAX);
+            assert_ne!(noun, u64::MAX);
+        }
+
+        {
+            let cell = Cell::from(["vary", "Origin"]);
+            assert_eq!(*cell.head_ref(), "vary");
+            assert_eq!(*cell.tail_ref(), "Origin");
+
+            let noun = Noun::from(cell);
+            assert_ne!(noun, "vary");
+            assert_ne!(noun, 0u8);
+        }
+    }
+
+    #[test]
+    fn get() {
+        {
+            let noun = Noun::from(7u8);
+            assert_eq!(noun.get(0), None);
+            assert_eq!(noun.get(1), Some(&noun));
+            assert_eq!(noun.get(2), None);
+        }
+
+        {
+            let noun = Noun::from(Cell::from([
+                Noun::from(Cell::from([4u8, 5u8])),
+                Noun::from(6u8),
+                Noun::from(7u8),
+            ]));
+            assert_eq!(noun.get(1), Some(&noun));
+            assert_eq!(*noun.get(4).expect("axis 4"), 4u8);
+            assert_eq!(*noun.get(5).expect("axis 5"), 5u8);
+            assert_eq!(*noun.get(6).expect("axis 6"), 6u8);
+            assert_eq!(*noun.get(7).expect("axis 7"), 7u8);
+            assert_eq!(noun.get(8), None);
+            assert_eq!(noun.get(14), None);
+            assert_eq!(noun.get(u64::MAX), None);
+        }
+    }
+
+    #[test]
+    fn mug() {
+        // Atoms, including the examples from the Hoon docs.
+        {
+            let cases = [
+                (Atom::null(), 2_046_756_072),
+                (Atom::from(1u8), 1_901_865_568),
+                (Atom::from(10_000u16), 795_713_195),
+                (Atom::from(1u128 << 64), 648_482_943),
+                (Atom::from("~zod"), 1_865_551_628),
+            ];
+            for (atom, mug) in cases {
+                assert_eq!(atom.mug(), mug);
+                assert_eq!(Noun::from(atom).mug(), mug);
+            }
+        }
+
+        // Cells.
+        {
+            let cases = [
+                (Noun::from(Cell::from([0u8, 0u8])), 422_532_488),
+                (Noun::from(Cell::from([1u8, 2u8])), 1_781_973_465),
+                (
+                    Noun::from(Cell::from([
+                        Noun::from(Cell::from([1u8, 2u8])),
+                        Noun::from(3u8),
+                    ])),
+                    797_794_207,
+                ),
+                (
+                    Noun::from(Cell::from(["hello", "world", ""])),
+                    1_113_827_290,
+                ),
+            ];
+            for (noun, mug) in cases {
+                assert_eq!(noun.mug(), mug);
+            }
+        }
+
+        // Deep nouns don't overflow the stack.
+        {
+            let mut noun = Rc::new(Noun::null());
+            for i in 0..5_000u16 {
+                noun = Rc::new(Noun::from(Cell::from([
+                    Rc::new(Noun::from(Atom::from(i))),
+                    noun,
+                ])));
+            }
+            assert!(noun.mug() < 1 << 31);
+        }
+    }
+
+    #[test]
+    fn list_iter() {
+        // A proper list.
+        {
+            let noun = Noun::from(Cell::from(["a", "b", "c", ""]));
+            let elems: Vec<_> = (&noun)
+                .into_iter()
+                .map(|elem| elem.expect("elem"))
+                .collect();
+            assert_eq!(
+                elems,
+                [&Noun::from("a"), &Noun::from("b"), &Noun::from("c")]
+            );
+
+            let Noun::Cell(cell) = &noun else {
+                panic!("not a cell");
+            };
+            let mut count = 0;
+            for elem in cell {
+                assert!(elem.is_ok());
+                count += 1;
+            }
+            assert_eq!(count, 3);
+        }
+
+        // An improper list yields its elements and then an error at its terminator.
+        {
+            let noun = Noun::from(Cell::from(["a", "b", "c"]));
+            let mut iter = noun.into_iter();
+            assert_eq!(iter.next().map(Result::ok), Some(Some(&Noun::from("a"))));
+            assert_eq!(iter.next().map(Result::ok), Some(Some(&Noun::from("b"))));
+            match iter.next() {
+                Some(Err(convert::Error::At { axis, path, source })) => {
+                    assert_eq!(axis, Some(7));
+                    assert_eq!(path, [convert::PathSegment::Index(2)]);
+                    assert!(matches!(*source, convert::Error::ExpectedNull));
+                }
+                res => panic!("unexpected result: {:?}", res),
+            }
+            assert!(iter.next().is_none());
+            assert!(iter.next().is_none());
+        }
+
+        // Null is the empty list, and any other atom is an improper one.
+        {
+            assert!(Noun::null().into_iter().next().is_none());
+
+            let noun = Noun::from(5u8);
+            let elems: Vec<_> = noun.into_iter().collect();
+            assert_eq!(elems.len(), 1);
+            assert!(matches!(
+                elems[0],
+                Err(convert::Error::At { axis: Some(1), .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn byte_array() {
+        // Atoms shorter than the array are zero-extended, since their trailing zeros are trimmed.
+        {
+            let mut key = [0u8; 32];
+            key[..3].copy_from_slice(&[0x01, 0x02, 0x03]);
+            let atom = Atom::from(key);
+            assert_eq!(atom.as_bytes(), [0x01, 0x02, 0x03]);
+            assert_eq!(<[u8; 32]>::try_from(&atom).ok(), Some(key));
+            assert_eq!(<[u8; 32]>::try_from(&Noun::from(atom)).ok(), Some(key));
+            assert_eq!(<[u8; 16]>::try_from(&Noun::null()).ok(), Some([0; 16]));
+            assert_eq!(<[u8; 0]>::try_from(&Noun::null()).ok(), Some([]));
+        }
+
+        // Atoms that fill the array exactly.
+        {
+            let hash = [0xffu8; 16];
+            let noun = Noun::from(Atom::from(hash));
+            assert_eq!(<[u8; 16]>::try_from(&noun).ok(), Some(hash));
+            assert_eq!(
+                <[u8; 16] as convert::FromNoun>::from_noun(&noun).ok(),
+                Some(hash)
+            );
+        }
+
+        // Atoms that are too long, and cells.
+        {
+            let noun = Noun::from(Atom::from([0xffu8; 17]));
+            assert!(matches!(
+                <[u8; 16]>::try_from(&noun),
+                Err(convert::Error::AtomToUint)
+            ));
+            assert!(matches!(
+                <[u8; 0]>::try_from(&Noun::from(1u8)),
+                Err(convert::Error::AtomToUint)
+            ));
+            assert!(matches!(
+                <[u8; 2]>::try_from(&Noun::from(Cell::from([1u8, 2]))),
+                Err(convert::Error::UnexpectedCell)
+            ));
+        }
+    }
+
+    #[test]
+    fn validate() {
+        // Atoms that violate the invariants, which only code that bypasses the atom constructors
+        // can build.
+        let trailing_zeros = || Noun::from(Atom::from_raw_parts(vec![1, 0], 1));
+        let bad_bit_len = || Noun::from(Atom::from_raw_parts(vec![1], 9));
+
+        // The first violation in pre-order is reported along with its axis.
+        {
+            let noun = Noun::from(Cell::from([
+                Noun::from(1u8),
+                trailing_zeros(),
+                bad_bit_len(),
+            ]));
+            let err = noun.validate().expect_err("trailing zeros");
+            assert_eq!(err, InvariantError::TrailingZeros { axis: Some(6) });
+            assert_eq!(
+                err.to_string(),
+                "the atom at axis 6 has trailing zero bytes"
+            );
+            assert_eq!(
+                bad_bit_len().validate(),
+                Err(InvariantError::BitLen {
+                    axis: Some(1),
+                    cached: 9,
+                    actual: 1,
+                })
+            );
+            assert_eq!(Noun::from(Cell::from([1u8, 2u8])).validate(), Ok(()));
+        }
+
+        // Atoms too deep for their axes to fit in a `u64`.
+        {
+            let mut noun = Rc::new(trailing_zeros());
+            for _ in 0..64 {
+                noun = Rc::new(Noun::from(Cell::from([Rc::new(Noun::null()), noun])));
+            }
+            let err = noun.validate().expect_err("deep trailing zeros");
+            assert_eq!(err.axis(), None);
+            assert_eq!(
+                err.to_string(),
+                "an atom more than 63 cells deep has trailing zero bytes"
+            );
+        }
+
+        // Violations make equal nouns unequal, which normalizing fixes, rebuilding only the
+        // offending atoms and the cells above them.
+        {
+            let head = Rc::new(Noun::from(Cell::from(["GET", "/"])));
+            let noun = Noun::from(Cell::from([
+                head.clone(),
+                Rc::new(Noun::from(Cell::from([trailing_zeros(), Noun::from(3u8)]))),
+            ]));
+            let expected = Noun::from(Cell::from([
+                (*head).clone(),
+                Noun::from(Cell::from([1u8, 3u8])),
+            ]));
+            assert_ne!(noun, expected);
+            let normalized = noun.normalize();
+            assert_eq!(normalized.validate(), Ok(()));
+            assert_eq!(normalized, expected);
+            assert!(ptr::eq(normalized.get(2).unwrap(), &*head));
+
+            assert_eq!(bad_bit_len().normalize(), Noun::from(1u8));
+        }
+
+        // Normalizing a valid noun changes nothing.
+        {
+            let head = Rc::new(Noun::from(Cell::from([1u8, 2u8])));
+            let noun = Noun::from(Cell::from([head.clone(), head.clone()]));
+            let normalized = noun.normalize();
+            assert!(ptr::eq(normalized.get(2).unwrap(), &*head));
+            assert!(ptr::eq(normalized.get(3).unwrap(), &*head));
+        }
+    }
+
+    #[test]
+    fn replace_all() {
+        // A repeated atom, including where a shared cell holds it twice over.
+        {
+            let shared = Rc::new(Noun::from(Cell::from(["~zod", "~nec"])));
+            let noun = Noun::from(Cell::from([
+                shared.clone(),
+                Rc::new(Noun::from("~zod")),
+                shared.clone(),
+            ]));
+            let (replaced, count) = noun.replace_all(&Noun::from("~zod"), Rc::new(Noun::null()));
+            assert_eq!(count, 3);
+            assert_eq!(
+                replaced,
+                Noun::from(Cell::from([
+                    Noun::from(Cell::from(["", "~nec"])),
+                    Noun::null(),
+                    Noun::from(Cell::from(["", "~nec"])),
+                ]))
+            );
+            // The untouched `~nec` is shared with the original.
+            assert!(ptr::eq(replaced.get(5).unwrap(), shared.get(3).unwrap()));
+        }
+
+        // A replacement shaped like the target isn't searched, and neither is a subnoun once it's
+        // been replaced.
+        {
+            let target = Noun::from(Cell::from([1u8, 2]));
+            let replacement = Rc::new(Noun::from(Cell::from([
+                Noun::from(Cell::from([1u8, 2])),
+                Noun::from(Cell::from([1u8, 2])),
+            ])));
+            let noun = Noun::from(Cell::from([
+                Noun::from(Cell::from([1u8, 2])),
+                Noun::from(3u8),
+                Noun::from(Cell::from([1u8, 2])),
+            ]));
+            let (replaced, count) = noun.replace_all(&target, replacement.clone());
+            assert_eq!(count, 2);
+            assert!(ptr::eq(replaced.get(2).unwrap(), &*replacement));
+            assert!(ptr::eq(replaced.get(7).unwrap(), &*replacement));
+
+            let (replaced, count) = replaced.replace_all(&target, replacement.clone());
+            assert_eq!(count, 4);
+            assert_eq!(
+                *replaced.get(2).unwrap(),
+                Noun::from(Cell::from([replacement.clone(), replacement.clone()]))
+            );
+        }
+
+        // No matches.
+        {
+            let noun = Noun::from(Cell::from([1u8, 2, 3]));
+            let (replaced, count) = noun.replace_all(&Noun::from(4u8), Rc::new(Noun::null()));
+            assert_eq!(count, 0);
+            assert!(ptr::eq(replaced.get(2).unwrap(), noun.get(2).unwrap()));
+            assert!(ptr::eq(replaced.get(3).unwrap(), noun.get(3).unwrap()));
+
+            let (replaced, count) = noun.replace_all(&noun, Rc::new(Noun::null()));
+            assert_eq!((replaced, count), (Noun::null(), 1));
+        }
+
+        // A long list, which would overflow the stack if it were searched recursively.
+        {
+            let noun = Noun::list_from(
+                (0..100_000u32)
+                    .map(|i| Rc::new(Noun::from(i % 10)))
+                    .collect(),
+            );
+            let (replaced, count) = noun.replace_all(&Noun::from(7u8), Rc::new(Noun::null()));
+            assert_eq!(count, 10_000);
+            std::mem::forget(noun);
+            std::mem::forget(replaced);
+        }
+    }
+
+    #[test]
+    fn is_direct_atom() {
+        assert!(Noun::from(Atom::DIRECT_MAX).is_direct_atom());
+        assert!(!Noun::from(1u64 << 62).is_direct_atom());
+        assert!(!Noun::from(Cell::from([0u8, 0u8])).is_direct_atom());
+    }
+
+    #[test]
+    fn list_from() {
+        let nouns = |len: u8| {
+            (1..=len)
+                .map(|i| Rc::new(Noun::from(i)))
+                .collect::<Vec<_>>()
+        };
+
+        // No nouns is the empty list.
+        assert_eq!(Noun::list_from(nouns(0)), Noun::null());
+
+        // One noun is a one-element list, not the noun itself.
+        assert_eq!(Noun::list_from(nouns(1)), Noun::from(Cell::from([1u8, 0])));
+
+        // Two nouns are a two-element list, unlike their pair chain.
+        {
+            let list = Noun::list_from(nouns(2));
+            assert_eq!(list, Noun::from(Cell::from([1u8, 2, 0])));
+            assert_ne!(list, Noun::from(Cell::from(nouns(2))));
+            let elems: Vec<_> = list.into_iter().collect::<Result<_, _>>().expect("list");
+            assert_eq!(elems, [&Noun::from(1u8), &Noun::from(2u8)]);
+        }
+    }
+
+    #[test]
+    fn tag() {
+        {
+            let noun = Noun::from(Cell::from(["http-request", "a", "b"]));
+            assert_eq!(noun.tag(), Some(&Atom::from("http-request")));
+            assert_eq!(
+                noun.untag("http-request").expect("tagged"),
+                &Noun::from(Cell::from(["a", "b"]))
+            );
+            assert!(matches!(
+                noun.untag("http"),
+                Err(convert::Error::UnknownTag)
+            ));
+        }
+
+        // Heads that aren't terms.
+        {
+            for head in [
+                Noun::null(),
+                Noun::from("Add"),
+                Noun::from("1st"),
+                Noun::from("a b"),
+                Noun::from(Atom::from(u64::MAX)),
+                Noun::from(Cell::from(["add", "neg"])),
+            ] {
+                let noun = Noun::from(Cell::from([head, Noun::null()]));
+                assert_eq!(noun.tag(), None);
+            }
+            assert_eq!(Noun::from("add").tag(), None);
+        }
+
+        // Untagging doesn't require a term.
+        {
+            let noun = Noun::from(Cell::from([0u8, 1u8]));
+            assert_eq!(*noun.untag("").expect("null tag"), 1u8);
+            assert!(matches!(
+                Noun::from("add").untag("add"),
+                Err(convert::Error::UnexpectedAtom)
+            ));
+            let noun = Noun::from(Cell::from([
+                Noun::from(Cell::from(["add", "neg"])),
+                Noun::null(),
+            ]));
+            assert!(matches!(
+                noun.untag("add"),
+                Err(convert::Error::At { axis: Some(2), .. })
+            ));
+        }
+    }
+
+    /// Collects the address of every reference-counted subnoun of `noun`.
+    fn addrs(noun: &Noun, acc: &mut Vec<*const Noun>) {
+        if let Noun::Cell(cell) = noun {
+            for child in [cell.head(), cell.tail()] {
+                acc.push(Rc::as_ptr(&child));
+                addrs(&child, acc);
+            }
+        }
+    }
+
+    #[test]
+    fn cue_error_position() {
+        fn pos(bit: u64, item: Item, cell: Option<(u64, Side)>) -> Position {
+            Position {
+                bit,
+                item,
+                cell: cell.map(|(bit, side)| EnclosingCell { bit, side }),
+            }
+        }
+
+        // [0 19] with the last 5 bits of its tail missing.
+        match Noun::cue(Atom::from(0x0b09u16)) {
+            Err(serdes::Error::UnexpectedEof(p)) => {
+                assert_eq!(p, pos(11, Item::Atom, Some((0, Side::Tail))))
+            }
+            res => panic!("unexpected result: {:?}", res),
+        }
+
+        // [0 19] with the length of its tail truncated.
+        match Noun::cue(Atom::from(0x0109u16)) {
+            Err(serdes::Error::UnexpectedEof(p)) => {
+                assert_eq!(p, pos(5, Item::AtomLen, Some((0, Side::Tail))))
+            }
+            res => panic!("unexpected result: {:?}", res),
+        }
+
+        // [0 19] with a truncated 3-bit head.
+        match Noun::cue(Atom::from(0xe1u8)) {
+            Err(serdes::Error::UnexpectedEof(p)) => {
+                assert_eq!(p, pos(7, Item::Atom, Some((0, Side::Head))))
+            }
+            res => panic!("unexpected result: {:?}", res),
+        }
+
+        // [0 0 <truncated>], which reports the innermost cell.
+        match Noun::cue(Atom::from(2201u16)) {
+            Err(serdes::Error::UnexpectedEof(p)) => {
+                assert_eq!(p, pos(9, Item::AtomLen, Some((4, Side::Tail))))
+            }
+            res => panic!("unexpected result: {:?}", res),
+        }
+
+        // [0 <truncated tag>].
+        match Noun::cue(Atom::from(0b11001u8)) {
+            Err(serdes::Error::UnexpectedEof(p)) => {
+                assert_eq!(p, pos(4, Item::Tag, Some((0, Side::Tail))))
+            }
+            res => panic!("unexpected result: {:?}", res),
+        }
+
+        // [10.000 10.000] with the backreference index changed from 2 to 3.
+        match Noun::cue(Atom::from(4_952_983_169u64 | 1 << 31)) {
+            Err(err @ serdes::Error::CacheMiss(_)) => {
+                assert_eq!(
+                    err.position(),
+                    Some(pos(31, Item::BackrefIdx, Some((0, Side::Tail))))
+                );
+                assert_eq!(
+                    err.to_string(),
+                    "a key that was expected to be in the cache was missing from the cache at bit \
+                     31 while decoding a backreference in the tail of the cell at bit 0"
+                );
+            }
+            res => panic!("unexpected result: {:?}", res),
+        }
+
+        // A top level atom with a truncated length.
+        match Noun::cue(Atom::from(0b1000u8)) {
+            Err(err @ serdes::Error::UnexpectedEof(_)) => {
+                assert_eq!(err.position(), Some(pos(1, Item::AtomLen, None)));
+                assert_eq!(
+                    err.to_string(),
+                    "the jammed noun ended unexpectedly at bit 1 while decoding the length of an atom"
+                );
+            }
+            res => panic!("unexpected result: {:?}", res),
+        }
+    }
+
+    #[test]
+    fn cue_malformed() {
+        /// A xorshift PRNG, which is all that's needed to generate garbage.
+        struct Rng(u64);
+
+        impl Rng {
+            fn next(&mut self) -> u64 {
+                self.0 ^= self.0 << 13;
+                self.0 ^= self.0 >> 7;
+                self.0 ^= self.0 << 17;
+                self.0
+            }
+        }
+
+        /// Cues `bytes` in every supported way, which must not panic.
+        fn cue_all(bytes: &[u8]) {
+            let _ = Noun::cue(Atom::from(bytes.to_vec()));
+            let _ = Noun::cue_bytes(bytes);
+            let _ = Noun::cue_from_reader(bytes);
+            let limits = CueLimits {
+                max_nodes: 1 << 16,
+                ..CueLimits::default()
+            };
+            let _ = Noun::cue_with_limits(Atom::from(bytes.to_vec()), &limits);
+        }
+
+        // Empty input.
+        {
+            match Noun::cue_bytes(&[]) {
+                Err(serdes::Error::UnexpectedEof(pos)) => {
+                    assert_eq!(pos.bit, 0);
+                    assert_eq!(pos.item, Item::Tag);
+                    assert_eq!(pos.cell, None);
+                }
+                res => panic!("unexpected result: {:?}", res),
+            }
+            assert!(Noun::cue_from_reader(io::empty()).is_err());
+        }
+
+        // A length of more than 64 bits.
+        {
+            let mut bytes = vec![0; 9];
+            bytes.push(0xff);
+            match Noun::cue_bytes(&bytes) {
+                Err(serdes::Error::InvalidLen(pos)) => assert_eq!(pos.bit, 1),
+                res => panic!("unexpected result: {:?}", res),
+            }
+        }
+
+        // A backreference whose index is more than 64 bits.
+        {
+            let mut builder = Atom::builder();
+            // Backreference tag.
+            builder.push_bit(true);
+            builder.push_bit(true);
+            // 65-bit length.
+            for bit in [false; 7]
+                .into_iter()
+                .chain([true, true, false, false, false, false, false])
+            {
+                builder.push_bit(bit);
+            }
+            for _ in 0..65 {
+                builder.push_bit(true);
+            }
+            match Noun::cue(builder.into_atom()) {
+                Err(serdes::Error::InvalidBackref(pos)) => assert_eq!(pos.bit, 16),
+                res => panic!("unexpected result: {:?}", res),
+            }
+        }
+
+        // Every truncation of some valid jammed nouns.
+        {
+            let nouns = [
+                Noun::from(Cell::from([222u16, 444u16, 888u16])),
+                Noun::from(Cell::from([
+                    Noun::from(Cell::from(["x-cached", "HIT"])),
+                    Noun::from(Cell::from(["vary", "Origin"])),
+                    Noun::from(Cell::from(["vary", "Accept-Encoding"])),
+                    Noun::from(0u8),
+                ])),
+            ];
+            for noun in nouns {
+                let bytes = noun.jam_to_vec();
+                for len in 0..bytes.len() {
+                    assert!(Noun::cue_bytes(&bytes[..len]).is_err());
+                    cue_all(&bytes[..len]);
+                }
+            }
+        }
+
+        // Random garbage.
+        {
+            let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+            for _ in 0..5_000 {
+                let len = (rng.next() % 64) as usize;
+                let bytes: Vec<u8> = (0..len).map(|_| rng.next() as u8).collect();
+                cue_all(&bytes);
+            }
+        }
+    }
+
+    #[test]
+    fn cue_deep() {
+        // Deep enough to overflow the stack of a recursive decoder.
+        let depth = 200_000;
+        let mut builder = Atom::builder();
+        for _ in 0..depth {
+            // A cell whose head is 0.
+            for bit in [true, false, false, true] {
+                builder.push_bit(bit);
+            }
+        }
+        // The innermost tail, 1.
+        for bit in [false, false, true, true] {
+            builder.push_bit(bit);
+        }
+        let limits = CueLimits {
+            max_depth: depth,
+            ..CueLimits::default()
+        };
+        let noun = Noun::cue_with_limits(builder.into_atom(), &limits).expect("cue");
+        let mut tail = &noun;
+        for _ in 0..depth {
+            tail = tail.get(3).expect("tail");
+        }
+        assert_eq!(*tail, 1u8);
+        // Dropping a noun this deep recursively would overflow the stack.
+        std::mem::forget(noun);
+    }
+
+    #[test]
+    fn cue_trailing_data() {
+        let noun = Noun::from(Cell::from([0u8, 19u8]));
+
+        // Exact length.
+        {
+            let jammed_noun = Atom::from(0x9b09u16);
+            assert_eq!(
+                Noun::cue_partial(&jammed_noun).expect("cue_partial"),
+                (noun.clone(), 16)
+            );
+            assert_eq!(Noun::cue_strict(&jammed_noun).expect("cue_strict"), noun);
+            assert_eq!(Noun::cue(jammed_noun).expect("cue"), noun);
+        }
+
+        // Trailing garbage.
+        {
+            let jammed_noun = Atom::from(0x0005_9b09u32);
+            assert_eq!(
+                Noun::cue_partial(&jammed_noun).expect("cue_partial"),
+                (noun.clone(), 16)
+            );
+            match Noun::cue_strict(&jammed_noun) {
+                Err(serdes::Error::TrailingData { bits }) => assert_eq!(bits, 3),
+                res => panic!("unexpected result: {:?}", res),
+            }
+            match Noun::cue_bytes(&[0x09, 0x9b, 0x05, 0x00]) {
+                Err(serdes::Error::TrailingData { bits }) => assert_eq!(bits, 3),
+                res => panic!("unexpected result: {:?}", res),
+            }
+            assert!(Noun::cue(jammed_noun.clone()).is_err());
+            assert!(Noun::cue_with_limits(jammed_noun, &CueLimits::default()).is_err());
+        }
+
+        // Trailing zeros.
+        {
+            let jammed_noun = Atom::from(vec![0x09, 0x9b, 0x00, 0x00]);
+            assert_eq!(
+                Noun::cue_partial(&jammed_noun).expect("cue_partial"),
+                (noun.clone(), 16)
+            );
+            assert_eq!(Noun::cue_strict(&jammed_noun).expect("cue_strict"), noun);
+            assert_eq!(
+                Noun::cue_bytes(&[0x09, 0x9b, 0x00, 0x00]).expect("cue_bytes"),
+                noun
+            );
+        }
+    }
+
+    #[test]
+    fn cue_with_limits() {
+        /// Builds a noun whose head and tail are both the same noun of height `levels - 1`, which
+        /// jams to a handful of bytes thanks to backreferences.
+        fn bomb(leaf: Noun, levels: u32) -> Noun {
+            let mut noun = Rc::new(leaf);
+            for _ in 0..levels {
+                noun = Rc::new(Noun::from(Cell::from([noun.clone(), noun])));
+            }
+            Rc::try_unwrap(noun).unwrap()
+        }
+
+        fn limit_exceeded(jammed_noun: &Atom, limits: &CueLimits) -> Option<(Limit, u64)> {
+            match Noun::cue_with_limits(jammed_noun.clone(), limits) {
+                Err(serdes::Error::LimitExceeded { which, limit }) => Some((which, limit)),
+                Ok(_) => None,
+                Err(err) => panic!("unexpected error: {}", err),
+            }
+        }
+
+        // 2^13 - 1 nodes, 2^12 * 100 atom bytes, and a depth of 12.
+        let noun = bomb(Noun::from(Atom::from(vec![0xff; 100])), 12);
+        let jammed_noun = noun.clone().jam();
+        assert!(jammed_noun.as_bytes().len() < 256);
+
+        assert_eq!(Noun::cue(jammed_noun.clone()).expect("cue"), noun);
+        assert_eq!(
+            Noun::cue_with_limits(jammed_noun.clone(), &CueLimits::default()).expect("cue"),
+            noun
+        );
+
+        // Exact limits aren't exceeded.
+        {
+            let limits = CueLimits {
+                max_atom_bytes: 100,
+                max_total_atom_bytes: 100 << 12,
+                max_nodes: (1 << 13) - 1,
+                max_depth: 12,
+            };
+            assert_eq!(
+                Noun::cue_with_limits(jammed_noun.clone(), &limits).expect("cue"),
+                noun
+            );
+        }
+
+        {
+            let limits = CueLimits {
+                max_atom_bytes: 99,
+                ..CueLimits::default()
+            };
+            assert_eq!(
+                limit_exceeded(&jammed_noun, &limits),
+                Some((Limit::AtomBytes, 99))
+            );
+        }
+
+        {
+            let limits = CueLimits {
+                max_total_atom_bytes: 100_000,
+                ..CueLimits::default()
+            };
+            assert_eq!(
+                limit_exceeded(&jammed_noun, &limits),
+                Some((Limit::TotalAtomBytes, 100_000))
+            );
+        }
+
+        {
+            let limits = CueLimits {
+                max_nodes: 1_000,
+                ..CueLimits::default()
+            };
+            assert_eq!(
+                limit_exceeded(&jammed_noun, &limits),
+                Some((Limit::Nodes, 1_000))
+            );
+        }
+
+        {
+            let limits = CueLimits {
+                max_depth: 11,
+                ..CueLimits::default()
+            };
+            assert_eq!(
+                limit_exceeded(&jammed_noun, &limits),
+                Some((Limit::Depth, 11))
+            );
+        }
+
+        // A long list trips the depth limit without any backreferences.
+        {
+            let mut list = Noun::null();
+            for i in 0..100u8 {
+                list = Noun::from(Cell::from([Noun::from(Atom::from(i)), list]));
+            }
+            let jammed_list = list.jam();
+            let limits = CueLimits {
+                max_depth: 50,
+                ..CueLimits::default()
+            };
+            assert_eq!(
+                limit_exceeded(&jammed_list, &limits),
+                Some((Limit::Depth, 50))
+            );
+        }
+
+        // An atom that claims to be 2^40 - 1 bits long is rejected before any of its bits are read.
+        {
+            let mut builder = Atom::builder();
+            // Atom tag.
+            builder.push_bit(false);
+            // 40-bit length.
+            for _ in 0..40 {
+                builder.push_bit(false);
+            }
+            for _ in 0..40 {
+                builder.push_bit(true);
+            }
+            let jammed_atom = builder.into_atom();
+            let limits = CueLimits {
+                max_atom_bytes: 1 << 20,
+                ..CueLimits::default()
+            };
+            assert_eq!(
+                limit_exceeded(&jammed_atom, &limits),
+                Some((Limit::AtomBytes, 1 << 20))
+            );
+            let limits = CueLimits {
+                max_total_atom_bytes: 1 << 20,
+                ..CueLimits::default()
+            };
+            assert_eq!(
+                limit_exceeded(&jammed_atom, &limits),
+                Some((Limit::TotalAtomBytes, 1 << 20))
+            );
+        }
+    }
+
+    #[test]
+    fn stats() {
+        // Jams `noun`, checking that cueing it finds what jamming it wrote.
+        fn stats(noun: &Noun) -> (JamStats, CueStats) {
+            let (jammed_noun, jam_stats) = noun.jam_with_stats();
+            assert_eq!(jammed_noun, noun.clone().jam());
+            let (cued, cue_stats) = Noun::cue_with_stats(jammed_noun).expect("cue");
+            assert_eq!(cued, *noun);
+            assert_eq!(cue_stats.bits, jam_stats.bits);
+            assert_eq!(cue_stats.atoms, jam_stats.atoms);
+            assert_eq!(cue_stats.cells, jam_stats.cells);
+            assert_eq!(cue_stats.backrefs, jam_stats.backrefs);
+            assert_eq!(cue_stats.backref_bits_saved, jam_stats.backref_bits_saved);
+            assert_eq!(cue_stats.max_depth, jam_stats.max_depth);
+            (jam_stats, cue_stats)
+        }
+
+        // `[10.000 10.000]`, whose tail is a backreference of 8 bits to a head of 23 bits.
+        {
+            let (jam_stats, cue_stats) = stats(&Noun::from(Cell::from([10_000u16, 10_000u16])));
+            assert_eq!(
+                jam_stats,
+                JamStats {
+                    bits: 33,
+                    atoms: 1,
+                    cells: 1,
+                    backrefs: 1,
+                    backref_bits_saved: 15,
+                    max_depth: 1,
+                    cache_entries: 2,
+                }
+            );
+            assert_eq!(cue_stats.cache_entries, 2);
+        }
+
+        // `[1 1]`, where the repeated atom is shorter than a backreference to it.
+        {
+            let (jam_stats, cue_stats) = stats(&Noun::from(Cell::from([1u8, 1u8])));
+            assert_eq!(
+                jam_stats,
+                JamStats {
+                    bits: 10,
+                    atoms: 2,
+                    cells: 1,
+                    backrefs: 0,
+                    backref_bits_saved: 0,
+                    max_depth: 1,
+                    cache_entries: 2,
+                }
+            );
+            // The decoder caches both atoms, not knowing that they're equal.
+            assert_eq!(cue_stats.cache_entries, 3);
+        }
+
+        // `[[0 0] 0 0]`, whose tail is a backreference of 8 bits to a head of only 6 bits.
+        {
+            let pair = Noun::from(Cell::from([0u8, 0u8]));
+            let (jam_stats, cue_stats) = stats(&Noun::from(Cell::from([pair.clone(), pair])));
+            assert_eq!(
+                jam_stats,
+                JamStats {
+                    bits: 16,
+                    atoms: 2,
+                    cells: 2,
+                    backrefs: 1,
+                    backref_bits_saved: -2,
+                    max_depth: 2,
+                    cache_entries: 3,
+                }
+            );
+            assert_eq!(cue_stats.cache_entries, 4);
+        }
+
+        // A lone atom.
+        {
+            let (jam_stats, _) = stats(&Noun::from(Atom::from(19u8)));
+            assert_eq!(jam_stats.bits, 12);
+            assert_eq!((jam_stats.atoms, jam_stats.cells), (1, 0));
+            assert_eq!(jam_stats.max_depth, 0);
+        }
+
+        // A list of the same 100 cords, each a backreference to the first.
+        {
+            let noun = (0..100).fold(Noun::null(), |tail, _| {
+                Noun::from(Cell::from([Noun::from("hello"), tail]))
+            });
+            let (jam_stats, _) = stats(&noun);
+            assert_eq!(jam_stats.cells, 100);
+            assert_eq!(jam_stats.atoms, 2);
+            assert_eq!(jam_stats.backrefs, 99);
+            assert_eq!(jam_stats.max_depth, 100);
+            assert!(jam_stats.backref_bits_saved > 0);
+        }
+
+        // Stats serialize like any other struct.
+        #[cfg(feature = "serde")]
+        {
+            let (_, jam_stats) = Noun::from(Cell::from([10_000u16, 10_000u16])).jam_with_stats();
+            assert_eq!(
+                crate::serde::to_noun(&jam_stats).expect("serialize"),
+                Noun::from(Cell::from([33u8, 1, 1, 1, 30, 1, 2]))
+            );
+        }
+    }
+
+    #[test]
+    fn progress() {
+        // A list of 100 lists of 100 distinct atoms each, which has no backreferences and is
+        // encoded in 20,201 steps: 10,000 atoms, 100 inner lists of 100 cells and a null
+        // terminator each, and 100 outer cells and a null terminator.
+        let list = |atoms: Vec<Noun>| {
+            atoms.into_iter().rev().fold(Noun::null(), |tail, head| {
+                Noun::from(Cell::from([head, tail]))
+            })
+        };
+        let noun = list(
+            (0..100u32)
+                .map(|i| {
+                    list(
+                        (0..100u32)
+                            .map(|j| Noun::from(Atom::from((i * 100 + j) << 8)))
+                            .collect(),
+                    )
+                })
+                .collect(),
+        );
+        let jammed_noun = noun.clone().jam();
+        let total_bits = jammed_noun.bit_len() as u64;
+        let options = ProgressOptions { interval: 100 };
+
+        // Progress is reported at the requested granularity and never goes backwards.
+        {
+            let mut reports: Vec<CueProgress> = Vec::new();
+            let cued = Noun::cue_with_progress(&jammed_noun, &options, |progress| {
+                reports.push(progress);
+                ControlFlow::Continue(())
+            })
+            .expect("cue");
+            assert_eq!(cued, noun);
+            assert_eq!(reports.len(), 20_201 / 100);
+            for pair in reports.windows(2) {
+                assert!(pair[0].bits_read < pair[1].bits_read);
+                assert_eq!(pair[1].nodes - pair[0].nodes, 100);
+            }
+            assert!(reports.iter().all(|report| report.total_bits == total_bits));
+            assert!(reports.last().unwrap().bits_read < total_bits);
+
+            let mut reports: Vec<JamProgress> = Vec::new();
+            let jammed = noun
+                .jam_with_progress(&options, |progress| {
+                    reports.push(progress);
+                    ControlFlow::Continue(())
+                })
+                .expect("jam");
+            assert_eq!(jammed, jammed_noun);
+            assert_eq!(reports.len(), 20_201 / 100);
+            for pair in reports.windows(2) {
+                assert!(pair[0].bits_written < pair[1].bits_written);
+                assert_eq!(pair[1].nodes - pair[0].nodes, 100);
+            }
+        }
+
+        // Cancelling stops partway through.
+        {
+            let mut calls = 0;
+            let mut bits_read = 0;
+            let res = Noun::cue_with_progress(&jammed_noun, &options, |progress| {
+                calls += 1;
+                bits_read = progress.bits_read;
+                if calls == 10 {
+                    ControlFlow::Break(())
+                } else {
+                    ControlFlow::Continue(())
+                }
+            });
+            assert!(matches!(res, Err(serdes::Error::Cancelled)));
+            assert_eq!(calls, 10);
+            assert!(bits_read < total_bits / 10);
+
+            let mut calls = 0;
+            let res = noun.jam_with_progress(&options, |_| {
+                calls += 1;
+                ControlFlow::Break(())
+            });
+            assert!(matches!(res, Err(serdes::Error::Cancelled)));
+            assert_eq!(calls, 1);
+        }
+
+        // An interval of zero reports every step.
+        {
+            let mut calls = 0;
+            let jammed_noun = Noun::from(Cell::from([1u8, 2u8])).jam();
+            let options = ProgressOptions { interval: 0 };
+            Noun::cue_with_progress(&jammed_noun, &options, |_| {
+                calls += 1;
+                ControlFlow::Continue(())
+            })
+            .expect("cue");
+            assert_eq!(calls, 3);
+        }
+    }
+
+    #[test]
+    fn deep_clone() {
+        {
+            let shared = Rc::<Noun>::from(Cell::from(["x-cached", "HIT"]));
+            let noun = Noun::from(Cell::from([
+                shared.clone(),
+                Rc::<Noun>::from(Atom::from(0u8)),
+                shared.clone(),
+            ]));
+            let copy = noun.deep_clone();
+            assert_eq!(copy, noun);
+
+            let (mut src_addrs, mut copy_addrs) = (Vec::new(), Vec::new());
+            addrs(&noun, &mut src_addrs);
+            addrs(&copy, &mut copy_addrs);
+            assert!(copy_addrs.iter().all(|addr| !src_addrs.contains(addr)));
+
+            // Sharing within the source is preserved within the copy.
+            if let Noun::Cell(cell) = &copy {
+                let [head, _, last] = cell.to_array::<3>().expect("cell to array");
+                assert!(Rc::ptr_eq(&head, &last));
+            } else {
+                panic!("unexpected atom");
+            }
+
+            drop(noun);
+            assert_eq!(Rc::strong_count(&shared), 1);
+        }
+
+        {
+            let big = Rc::<Noun>::from(Atom::from(vec![0xff; 1 << 16]));
+            let small = Rc::<Noun>::from(Cell::from([1u8, 2u8]));
+            let noun = Noun::from(Cell::from([big.clone(), small.clone()]));
+            let piece = noun.get(3).expect("axis 3").deep_clone();
+            drop(noun);
+            assert_eq!(Rc::strong_count(&big), 1);
+            assert_eq!(Rc::strong_count(&small), 1);
+            assert_eq!(piece, *small);
+        }
+    }
+
+    #[test]
+    fn detach() {
+        {
+            let noun = Noun::from(7u8);
+            assert_eq!(noun.detach(0), None);
+            assert_eq!(noun.detach(2), None);
+            assert_eq!(noun.detach(1), Some(noun));
+        }
+
+        {
+            let head = Rc::<Noun>::from(Cell::from([4u8, 5u8]));
+            let noun = Noun::from(Cell::from([
+                head.clone(),
+                Rc::<Noun>::from(Atom::from(6u8)),
+                Rc::<Noun>::from(Atom::from(7u8)),
+            ]));
+            let detached = noun.detach(7).expect("detach");
+            assert_eq!(detached, noun);
+            if let (Noun::Cell(src), Noun::Cell(dst)) = (&noun, &detached) {
+                // The head hangs off of the path and is therefore shared.
+                assert!(Rc::ptr_eq(&src.head(), &dst.head()));
+                // The tail is on the path and is therefore copied.
+                assert!(!Rc::ptr_eq(&src.tail(), &dst.tail()));
+            } else {
+                panic!("unexpected atom");
+            }
+            assert_eq!(Rc::strong_count(&head), 3);
+            drop(noun);
+            assert_eq!(Rc::strong_count(&head), 2);
+        }
+    }
+
+    #[test]
+    fn modify_at() {
+        // Modifying a noun that shares its spine with another copies only the shared cells.
+        {
+            let sibling = Rc::<Noun>::from(Cell::from([4u8, 5u8]));
+            let original = Rc::new(Noun::from(Cell::from([
+                sibling.clone(),
+                Rc::<Noun>::from(Atom::from(6u8)),
+                Rc::<Noun>::from(Atom::from(7u8)),
+            ])));
+            let mut noun = original.clone();
+            assert!(noun.modify_at(7, |seven| *seven = Rc::new(Noun::from(8u8))));
+            assert_eq!(
+                *noun,
+                Noun::from(Cell::from([
+                    Noun::from(Cell::from([4u8, 5u8])),
+                    Noun::from(6u8),
+                    Noun::from(8u8),
+                ]))
+            );
+            assert_eq!(
+                *original,
+                Noun::from(Cell::from([
+                    Noun::from(Cell::from([4u8, 5u8])),
+                    Noun::from(6u8),
+                    Noun::from(7u8),
+                ]))
+            );
+            assert!(!Rc::ptr_eq(&noun, &original));
+            // The head and the atom at axis 6 hang off of the path and stay shared.
+            assert!(std::ptr::eq(noun.get(2).unwrap(), &*sibling));
+            assert!(std::ptr::eq(noun.get(6).unwrap(), original.get(6).unwrap()));
+            assert_eq!(Rc::strong_count(&sibling), 3);
+        }
+
+        // Modifying a uniquely owned noun copies nothing.
+        {
+            let mut noun = Rc::new(Noun::from(Cell::from([1u8, 2u8, 3u8])));
+            let spine: Vec<*const Noun> = [1, 3]
+                .into_iter()
+                .map(|axis| noun.get(axis).unwrap() as *const Noun)
+                .collect();
+            let untouched = noun.get(7).unwrap() as *const Noun;
+            assert!(noun.modify_at(6, |two| {
+                *Rc::make_mut(two) = Noun::from(Cell::from([2u8, 2u8]));
+            }));
+            assert_eq!(
+                *noun,
+                Noun::from(Cell::from([
+                    Noun::from(1u8),
+                    Noun::from(Cell::from([2u8, 2u8])),
+                    Noun::from(3u8),
+                ]))
+            );
+            assert_eq!(noun.get(1).unwrap() as *const Noun, spine[0]);
+            assert_eq!(noun.get(3).unwrap() as *const Noun, spine[1]);
+            assert_eq!(noun.get(7).unwrap() as *const Noun, untouched);
+        }
+
+        // Axes that don't exist, and atoms, which have no cell to make mutable.
+        {
+            let original = Rc::new(Noun::from(Cell::from([1u8, 2u8])));
+            let mut noun = original.clone();
+            assert!(!noun.modify_at(0, |_| unreachable!()));
+            assert!(!noun.modify_at(4, |_| unreachable!()));
+            assert!(Rc::ptr_eq(&noun, &original));
+
+            let atom = Rc::new(Noun::from(1u8));
+            let mut noun = atom.clone();
+            assert!(noun.make_cell_mut().is_none());
+            assert!(Rc::ptr_eq(&noun, &atom));
+            assert!(noun.modify_at(1, |one| *one = Rc::new(Noun::from(2u8))));
+            assert_eq!(*noun, Noun::from(2u8));
+            assert_eq!(*atom, Noun::from(1u8));
+        }
+    }
 }
@@ -0,0 +1,163 @@
+//! Conversions to and from `octs`, Hoon's byte strings.
+//!
+//! An atom has no trailing zero bytes (they're leading zeros of the number it represents), so an
+//! atom alone can't hold a byte string that ends in zeros. Hoon pairs the atom with the length of
+//! the byte string instead, `[p=@ud q=@]`, which is how HTTP bodies, Ames packets, and file
+//! contents travel between Urbit and the outside world.
+
+use crate::{
+    atom::Atom,
+    cell::Cell,
+    convert::{Error, FromNoun, PathSegment},
+    noun::Noun,
+};
+
+/// A byte string that converts to and from `octs`, `[p=@ud q=@]`.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{atom::Atom, cell::Cell, octs::Octs, Noun};
+/// let octs = Octs::from(&b"ok\0\0"[..]);
+/// let noun = Noun::from(octs.clone());
+/// assert_eq!(noun, Noun::from(Cell::from([Atom::from(4u8), Atom::from("ok")])));
+/// assert_eq!(Octs::try_from(&noun).unwrap(), octs);
+/// ```
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Octs(Vec<u8>);
+
+impl Octs {
+    /// Returns the bytes of this byte string.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Converts this byte string into a byte vector.
+    pub fn into_vec(self) -> Vec<u8> {
+        self.0
+    }
+
+    /// Returns the length of this byte string in bytes.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if this byte string is empty.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl From<Vec<u8>> for Octs {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+}
+
+impl From<&[u8]> for Octs {
+    fn from(bytes: &[u8]) -> Self {
+        Self(bytes.to_vec())
+    }
+}
+
+impl From<Octs> for Vec<u8> {
+    fn from(octs: Octs) -> Self {
+        octs.0
+    }
+}
+
+impl From<Octs> for Noun {
+    fn from(octs: Octs) -> Self {
+        let len = Atom::from(octs.len());
+        Noun::from(Cell::from([len, Atom::from(octs.0)]))
+    }
+}
+
+impl TryFrom<&Noun> for Octs {
+    type Error = Error;
+
+    /// Converts `[p q]` into the first `p` bytes of `q`, padding `q` with zero bytes as needed.
+    ///
+    /// Fails if `q` has more than `p` bytes.
+    fn try_from(noun: &Noun) -> Result<Self, Error> {
+        let (len, data) = <(usize, Atom)>::from_noun(noun)?;
+        let mut bytes = data.into_vec();
+        if bytes.len() > len {
+            return Err(Error::ImplType.at_segment(PathSegment::Index(1), 3));
+        }
+        bytes.resize(len, 0);
+        Ok(Self(bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::convert::IntoNoun;
+
+    #[test]
+    fn octs() {
+        // Payloads ending in zero bytes keep them.
+        {
+            let cases: [(&[u8], u8, Atom); 5] = [
+                (b"", 0, Atom::null()),
+                (b"\0", 1, Atom::null()),
+                (b"\0\0\0", 3, Atom::null()),
+                (b"body\0\0", 6, Atom::from("body")),
+                (b"\0a\0", 3, Atom::from(0x6100u16)),
+            ];
+            for (bytes, len, data) in cases {
+                let octs = Octs::from(bytes);
+                let noun = Noun::from(octs.clone());
+                assert_eq!(noun, Noun::from(Cell::from([Atom::from(len), data])));
+                assert_eq!(Octs::try_from(&noun).expect("round trip"), octs);
+                assert_eq!(octs.len(), usize::from(len));
+                assert_eq!(octs.into_vec(), bytes);
+            }
+        }
+
+        // Octs compose with the other conversions.
+        {
+            let body = Some(Octs::from(b"{}\0".to_vec()));
+            let noun = body.clone().into_noun();
+            assert_eq!(
+                noun,
+                Noun::from(Cell::from([
+                    Noun::null(),
+                    Noun::from(3u8),
+                    Noun::from("{}"),
+                ]))
+            );
+            assert_eq!(Option::<Octs>::from_noun(&noun).expect("round trip"), body);
+        }
+    }
+
+    #[test]
+    fn octs_errors() {
+        // The atom is longer than the stated length.
+        {
+            for (len, data) in [(0u8, Atom::from(1u8)), (3, Atom::from("body"))] {
+                let noun = Noun::from(Cell::from([Atom::from(len), data]));
+                assert!(matches!(
+                    Octs::try_from(&noun),
+                    Err(Error::At { axis: Some(3), ref source, .. })
+                        if matches!(**source, Error::ImplType)
+                ));
+            }
+        }
+
+        // Nouns that aren't [@ @].
+        {
+            for noun in [
+                Noun::from("body"),
+                Noun::from(Cell::from([
+                    Noun::from(4u8),
+                    Noun::from(Cell::from(["bo", "dy"])),
+                ])),
+                Noun::from(Cell::from([Atom::from(u128::MAX), Atom::from("body")])),
+            ] {
+                assert!(Octs::try_from(&noun).is_err());
+            }
+        }
+    }
+}
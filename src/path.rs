@@ -0,0 +1,251 @@
+//! Conversions to and from paths and wires.
+//!
+//! A path like `/gx/~zod/graph-store/updates` is a null-terminated list of knots (`@ta`), atoms of
+//! text made of lowercase letters, digits, `-`, `.`, `~`, and `_`. Segments are kept as text: a
+//! segment that looks like a number or a date is not interpreted.
+
+use crate::{
+    convert::{self, list_from_noun, IntoNoun},
+    noun::Noun,
+};
+use std::{
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+/// Errors that occur when parsing a path from a string.
+#[derive(Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum PathError {
+    /// The string doesn't start with `/`.
+    MissingLeadingSlash,
+    /// A segment isn't a knot (`@ta`).
+    InvalidKnot(String),
+}
+
+impl Display for PathError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        match self {
+            Self::MissingLeadingSlash => write!(f, "a path must start with `/`"),
+            Self::InvalidKnot(segment) => write!(f, "`{}` is not a valid knot", segment),
+        }
+    }
+}
+
+impl std::error::Error for PathError {}
+
+/// A path, a null-terminated list of knots (`@ta`).
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{cell::Cell, path::Path, Noun};
+/// let path: Path = "/gx/~zod/graph-store/updates".parse().unwrap();
+/// assert_eq!(path.segments(), ["gx", "~zod", "graph-store", "updates"]);
+/// assert_eq!(path.to_string(), "/gx/~zod/graph-store/updates");
+///
+/// let noun = Noun::from(path.clone());
+/// assert_eq!(noun, Noun::from(Cell::from(["gx", "~zod", "graph-store", "updates", ""])));
+/// assert_eq!(Path::try_from(&noun).unwrap(), path);
+/// ```
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Path(Vec<String>);
+
+/// A wire, the path an agent tags its outgoing requests with so it can tell their responses apart.
+pub type Wire = Path;
+
+impl Path {
+    /// Creates the empty path, `/`.
+    pub fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Returns the segments of this path.
+    pub fn segments(&self) -> &[String] {
+        &self.0
+    }
+
+    /// Returns `true` if this is the empty path, `/`.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Appends `segment` to this path, failing if it isn't a knot.
+    pub fn push(&mut self, segment: impl Into<String>) -> Result<(), PathError> {
+        let segment = segment.into();
+        if !is_knot(segment.as_bytes()) {
+            return Err(PathError::InvalidKnot(segment));
+        }
+        self.0.push(segment);
+        Ok(())
+    }
+}
+
+/// Returns `true` if `bytes` is a non-empty knot (`@ta`).
+pub(crate) fn is_knot(bytes: &[u8]) -> bool {
+    !bytes.is_empty()
+        && bytes.iter().all(|b| {
+            b.is_ascii_lowercase() || b.is_ascii_digit() || matches!(b, b'-' | b'.' | b'~' | b'_')
+        })
+}
+
+impl FromStr for Path {
+    type Err = PathError;
+
+    /// Parses a path like `/a/b/c`, where `/` is the empty path.
+    fn from_str(path: &str) -> Result<Self, PathError> {
+        let segments = path
+            .strip_prefix('/')
+            .ok_or(PathError::MissingLeadingSlash)?;
+        let mut path = Self::new();
+        if !segments.is_empty() {
+            for segment in segments.split('/') {
+                path.push(segment)?;
+            }
+        }
+        Ok(path)
+    }
+}
+
+impl Display for Path {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        if self.is_empty() {
+            return write!(f, "/");
+        }
+        for segment in &self.0 {
+            write!(f, "/{}", segment)?;
+        }
+        Ok(())
+    }
+}
+
+impl From<Path> for Noun {
+    fn from(path: Path) -> Self {
+        path.0.into_noun()
+    }
+}
+
+impl TryFrom<&Noun> for Path {
+    type Error = convert::Error;
+
+    /// Converts a null-terminated list of knots into a path.
+    ///
+    /// A segment that isn't a knot fails with [`ImplType`](convert::Error::ImplType).
+    fn try_from(noun: &Noun) -> Result<Self, convert::Error> {
+        let segments = list_from_noun(noun, |segment| {
+            let Noun::Atom(segment) = segment else {
+                return Err(convert::Error::UnexpectedCell);
+            };
+            if !is_knot(segment.as_bytes()) {
+                return Err(convert::Error::ImplType);
+            }
+            segment
+                .as_str()
+                .map(String::from)
+                .map_err(|_| convert::Error::AtomToStr)
+        })?;
+        Ok(Self(segments))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{atom::Atom, cell::Cell, convert::PathSegment};
+
+    #[test]
+    fn path() {
+        // Round trips through strings and nouns.
+        {
+            let cases = [
+                "/",
+                "/gx/~zod/graph-store/updates",
+                "/app/hood",
+                // Numbers and dates aren't interpreted.
+                "/1.024/~2022.7.8..16.43.50/0v1.abcde/.~sync_log",
+            ];
+            for string in cases {
+                let path: Path = string.parse().expect("valid path");
+                assert_eq!(path.to_string(), string);
+                let noun = Noun::from(path.clone());
+                assert_eq!(Path::try_from(&noun).expect("round trip"), path);
+            }
+        }
+
+        // The empty path is null.
+        {
+            let path = Path::new();
+            assert!(path.is_empty());
+            assert_eq!("/".parse::<Path>(), Ok(path.clone()));
+            assert!(Noun::from(path).is_null());
+        }
+
+        // Segments are atoms of their text.
+        {
+            let path: Path = "/gx/1".parse().expect("valid path");
+            assert_eq!(
+                Noun::from(path),
+                Noun::from(Cell::from([
+                    Noun::from("gx"),
+                    Noun::from(b'1'),
+                    Noun::null(),
+                ]))
+            );
+        }
+    }
+
+    #[test]
+    fn path_errors() {
+        // Invalid strings.
+        {
+            assert_eq!(
+                "gx/~zod".parse::<Path>(),
+                Err(PathError::MissingLeadingSlash)
+            );
+            assert_eq!("".parse::<Path>(), Err(PathError::MissingLeadingSlash));
+            for (string, segment) in [
+                ("/Gx", "Gx"),
+                ("/a b", "a b"),
+                ("/a//b", ""),
+                ("/a/", ""),
+                ("/caf\u{e9}", "caf\u{e9}"),
+                ("/a/%b", "%b"),
+            ] {
+                assert_eq!(
+                    string.parse::<Path>(),
+                    Err(PathError::InvalidKnot(String::from(segment)))
+                );
+            }
+            assert!(Path::new().push("ok").is_ok());
+            assert!(Path::new().push("not/ok").is_err());
+        }
+
+        // Invalid nouns.
+        {
+            let noun = Noun::from(Cell::from(["gx", "~Zod", ""]));
+            match Path::try_from(&noun) {
+                Err(convert::Error::At {
+                    axis: Some(6),
+                    path,
+                    source,
+                }) => {
+                    assert_eq!(path, [PathSegment::Index(1)]);
+                    assert!(matches!(*source, convert::Error::ImplType));
+                }
+                res => panic!("unexpected result: {:?}", res),
+            }
+
+            for noun in [
+                Noun::from(Cell::from(["gx", "no terminator"])),
+                Noun::from(Cell::from([
+                    Noun::from(Cell::from(["gx", "~zod"])),
+                    Noun::null(),
+                ])),
+                Noun::from(Cell::from([Noun::null(), Noun::null()])),
+                Noun::from(Atom::from(0xffu8)),
+            ] {
+                assert!(Path::try_from(&noun).is_err());
+            }
+        }
+    }
+}
@@ -0,0 +1,341 @@
+//! Loading and saving boot pills.
+//!
+//! A pill is the jammed noun a ship boots from, `[%pill nam=term boot-ova=(list) kernel-ova=(list)
+//! userspace-ova=(list)]`: the events that boot Arvo, the events that install its vanes, and the
+//! events that install the initial desks. Solid and brass pills both have this shape; ivory pills,
+//! `[%ivory p=(list)]`, which only boot the runtime's standard library, aren't supported.
+
+use crate::{
+    cell::Cell,
+    convert::{self, tail_axis, PathSegment},
+    noun::Noun,
+    serdes::{self, cue::ReadSource, CueLimits},
+    Rc,
+};
+use std::{
+    fmt::{self, Display, Formatter},
+    fs::File,
+    io::{self, BufReader, BufWriter, Write},
+    path::Path,
+};
+
+/// Errors that occur when loading a pill.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum PillError {
+    /// The file couldn't be opened.
+    Io(io::Error),
+    /// The file isn't a jammed noun, or cueing it exceeded the limits.
+    Cue(serdes::Error),
+    /// The noun isn't a pill.
+    Shape(convert::Error),
+}
+
+impl Display for PillError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        match self {
+            Self::Io(err) => write!(f, "the pill could not be read: {}", err),
+            Self::Cue(err) => write!(f, "the pill is not a jammed noun: {}", err),
+            Self::Shape(err) => write!(f, "the noun is not a pill: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for PillError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Cue(err) => Some(err),
+            Self::Shape(err) => Some(err),
+        }
+    }
+}
+
+impl From<io::Error> for PillError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<serdes::Error> for PillError {
+    fn from(err: serdes::Error) -> Self {
+        Self::Cue(err)
+    }
+}
+
+impl From<convert::Error> for PillError {
+    fn from(err: convert::Error) -> Self {
+        Self::Shape(err)
+    }
+}
+
+/// A pill, `[%pill nam=term boot-ova=(list) kernel-ova=(list) userspace-ova=(list)]`.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{pill::Pill, Noun, Rc};
+/// let pill = Pill {
+///     name: String::from("solid"),
+///     boot_ova: vec![Rc::new(Noun::from("boot"))],
+///     kernel_ova: Vec::new(),
+///     userspace_ova: Vec::new(),
+/// };
+/// let noun = Noun::from(pill.clone());
+/// assert_eq!(Pill::try_from(&noun).unwrap(), pill);
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Pill {
+    /// The name of the pill, like `solid` or `brass`.
+    pub name: String,
+    /// The events that boot Arvo.
+    pub boot_ova: Vec<Rc<Noun>>,
+    /// The events that install the vanes.
+    pub kernel_ova: Vec<Rc<Noun>>,
+    /// The events that install the initial desks.
+    pub userspace_ova: Vec<Rc<Noun>>,
+}
+
+impl Pill {
+    /// Loads a pill from a file, cueing it as it's read.
+    ///
+    /// This is [`load_with_limits()`](Self::load_with_limits()) with [`CueLimits::UNLIMITED`].
+    pub fn load(path: &Path) -> Result<Self, PillError> {
+        Self::load_with_limits(path, &CueLimits::UNLIMITED)
+    }
+
+    /// Loads a pill from a file, cueing it as it's read and failing if the noun exceeds any of
+    /// `limits`.
+    ///
+    /// Bytes following the jammed noun are ignored.
+    pub fn load_with_limits(path: &Path, limits: &CueLimits) -> Result<Self, PillError> {
+        let reader = BufReader::new(File::open(path)?);
+        let noun = serdes::cue::decode(&mut ReadSource::new(reader), limits)?;
+        Ok(Self::try_from(&noun)?)
+    }
+
+    /// Jams this pill into a file, creating the file if it doesn't exist and truncating it if it
+    /// does.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        Noun::from(self.clone()).jam_to_writer(&mut writer)?;
+        writer.flush()
+    }
+}
+
+impl From<Pill> for Noun {
+    fn from(pill: Pill) -> Self {
+        let mut noun = ova_into_noun(pill.userspace_ova);
+        for field in [
+            ova_into_noun(pill.kernel_ova),
+            ova_into_noun(pill.boot_ova),
+            Rc::new(Noun::from(pill.name)),
+            Rc::new(Noun::from("pill")),
+        ] {
+            noun = Rc::new(Noun::from(Cell::from([field, noun])));
+        }
+        Rc::try_unwrap(noun).unwrap_or_else(|noun| (*noun).clone())
+    }
+}
+
+impl TryFrom<&Noun> for Pill {
+    type Error = convert::Error;
+
+    /// Converts `[%pill nam boot-ova kernel-ova userspace-ova]` into a pill.
+    ///
+    /// Fails with [`UnknownTag`](convert::Error::UnknownTag) if the tag isn't `%pill`, and with
+    /// [`UnexpectedAtom`](convert::Error::UnexpectedAtom) or
+    /// [`MissingValue`](convert::Error::MissingValue) if the noun has fewer than five elements.
+    fn try_from(noun: &Noun) -> Result<Self, convert::Error> {
+        let fields = noun.untag("pill").map_err(|err| match err {
+            convert::Error::UnknownTag => err.at(2),
+            err => err,
+        })?;
+        let Noun::Cell(fields) = fields else {
+            return Err(convert::Error::UnexpectedAtom.at(3));
+        };
+        let [name, boot_ova, kernel_ova, userspace_ova] = fields
+            .to_array_ref::<4>()
+            .ok_or(convert::Error::MissingValue.at(31))?;
+        let name =
+            String::try_from(name).map_err(|err| err.at_segment(PathSegment::Field("name"), 6))?;
+        let ova = |ova: &Noun, field, axis| {
+            ova_from_noun(ova).map_err(|err| err.at_segment(PathSegment::Field(field), axis))
+        };
+        Ok(Self {
+            name,
+            boot_ova: ova(boot_ova, "boot_ova", 14)?,
+            kernel_ova: ova(kernel_ova, "kernel_ova", 30)?,
+            userspace_ova: ova(userspace_ova, "userspace_ova", 31)?,
+        })
+    }
+}
+
+/// Converts a list of events into a noun, sharing the events.
+fn ova_into_noun(ova: Vec<Rc<Noun>>) -> Rc<Noun> {
+    let mut noun = Rc::new(Noun::null());
+    for ovum in ova.into_iter().rev() {
+        noun = Rc::new(Noun::from(Cell::from([ovum, noun])));
+    }
+    noun
+}
+
+/// Converts a null-terminated list of events into a [`Vec`], sharing the events.
+fn ova_from_noun(mut list: &Noun) -> Result<Vec<Rc<Noun>>, convert::Error> {
+    let mut ova = Vec::new();
+    loop {
+        match list {
+            Noun::Cell(cell) => {
+                ova.push(cell.head());
+                list = cell.tail_ref();
+            }
+            Noun::Atom(atom) if atom.is_null() => return Ok(ova),
+            Noun::Atom(_) => {
+                let index = ova.len();
+                return Err(convert::Error::ExpectedNull
+                    .nest(Some(PathSegment::Index(index)), tail_axis(index)));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{atom::Atom, serdes::Limit};
+
+    /// A pill with a couple of events of each kind.
+    fn pill() -> Pill {
+        let ovum = |wire: &str, card: Noun| {
+            Rc::new(Noun::from(Cell::from([
+                Noun::from(Cell::from([wire, ""])),
+                card,
+            ])))
+        };
+        Pill {
+            name: String::from("solid"),
+            boot_ova: vec![
+                Rc::new(Noun::from(Cell::from([1u8, 2, 3]))),
+                Rc::new(Noun::from("arvo")),
+            ],
+            kernel_ova: vec![ovum(
+                "",
+                Noun::from(Cell::from([
+                    "veer",
+                    "a",
+                    "/sys/vane/ames",
+                    "|%  ++  ames  ~",
+                ])),
+            )],
+            userspace_ova: vec![
+                ovum("c", Noun::from(Cell::from(["into", "base", "&", ""]))),
+                ovum("c", Noun::from(Cell::from(["into", "kids", "&", ""]))),
+            ],
+        }
+    }
+
+    #[test]
+    fn pill_file() {
+        let path = std::env::temp_dir().join(format!("noun-pill-{}.pill", std::process::id()));
+
+        // Saving a pill and loading it back.
+        {
+            let pill = pill();
+            pill.save(&path).expect("save");
+            assert_eq!(Pill::load(&path).expect("load"), pill);
+            assert_eq!(
+                std::fs::read(&path).expect("read"),
+                Noun::from(pill).jam_to_vec()
+            );
+        }
+
+        // Loading a pill within limits.
+        {
+            let limits = CueLimits {
+                max_nodes: 4,
+                ..CueLimits::default()
+            };
+            assert!(matches!(
+                Pill::load_with_limits(&path, &limits),
+                Err(PillError::Cue(serdes::Error::LimitExceeded {
+                    which: Limit::Nodes,
+                    limit: 4
+                }))
+            ));
+        }
+
+        // Loading files that aren't pills.
+        {
+            std::fs::write(&path, Noun::from(Cell::from(["ivory", ""])).jam_to_vec())
+                .expect("write");
+            assert!(matches!(Pill::load(&path), Err(PillError::Shape(_))));
+
+            std::fs::write(&path, [0u8; 0]).expect("write");
+            assert!(matches!(Pill::load(&path), Err(PillError::Cue(_))));
+
+            std::fs::remove_file(&path).expect("remove file");
+            assert!(matches!(Pill::load(&path), Err(PillError::Io(_))));
+        }
+    }
+
+    #[test]
+    fn pill_shape() {
+        // Events are shared with the noun rather than copied.
+        {
+            let noun = Noun::from(pill());
+            let pill = Pill::try_from(&noun).expect("pill");
+            assert!(std::ptr::eq(
+                &*pill.userspace_ova[1],
+                noun.get(126).expect("event")
+            ));
+        }
+
+        let cases = [
+            // The wrong tag.
+            (Noun::from(Cell::from(["ivory", "", "", "", ""])), Some(2)),
+            // Too few fields.
+            (Noun::from(Cell::from(["pill", "solid", "", ""])), Some(31)),
+            (Noun::from(Cell::from(["pill", "solid"])), Some(3)),
+            // A name that isn't text.
+            (
+                Noun::from(Cell::from([
+                    Noun::from("pill"),
+                    Noun::from(Atom::from(vec![0xff])),
+                    Noun::null(),
+                    Noun::null(),
+                    Noun::null(),
+                ])),
+                Some(6),
+            ),
+            // An improperly terminated list of events.
+            (
+                Noun::from(Cell::from([
+                    Noun::from("pill"),
+                    Noun::from("solid"),
+                    Noun::null(),
+                    Noun::from(Cell::from([1u8, 2])),
+                    Noun::null(),
+                ])),
+                Some(61),
+            ),
+            (Noun::from("pill"), None),
+        ];
+        for (noun, axis) in cases {
+            match (Pill::try_from(&noun), axis) {
+                (Err(convert::Error::At { axis: at, .. }), Some(_)) => assert_eq!(at, axis),
+                (Err(convert::Error::UnexpectedAtom), None) => {}
+                (res, _) => panic!("unexpected result for {}: {:?}", noun, res),
+            }
+        }
+    }
+
+    /// Loads the pill at `$NOUN_PILL`, like a solid pill built with `+solid`.
+    #[test]
+    #[ignore = "needs a real pill at $NOUN_PILL"]
+    fn real_pill() {
+        let path = std::env::var_os("NOUN_PILL").expect("$NOUN_PILL");
+        let pill = Pill::load(Path::new(&path)).expect("load");
+        assert!(!pill.boot_ova.is_empty());
+        assert!(!pill.kernel_ova.is_empty());
+    }
+}
@@ -0,0 +1,403 @@
+//! Python bindings for cueing, jamming, and taking apart nouns.
+//!
+//! Built as an extension module, for example with
+//! `maturin develop --features python,pyo3/extension-module`, this exposes a `noun` module with a
+//! [`Noun`](PyNoun) class and a `cue()` function. The crate isn't a `cdylib`, which only an
+//! extension module needs, so that crates that depend on it don't build a dynamic library too:
+//! maturin passes `--crate-type cdylib` to `cargo rustc` itself when the crate doesn't declare it,
+//! and building without maturin takes
+//! `cargo rustc --release --features python,pyo3/extension-module --crate-type cdylib`.
+//!
+//! ```python
+//! import noun
+//!
+//! n = noun.cue(b"\x31\x12")
+//! n.is_cell()                             # True
+//! n.get(2)                                # Noun(0x1)
+//! n.jam()                                 # b'1\x12'
+//! noun.Noun([1, "a", b"\xff"]).to_list() # [Noun(0x1), Noun(0x61), Noun(0xff)]
+//! ```
+//!
+//! # Conversions
+//!
+//! `Noun(value)` converts a Python value into a noun:
+//!
+//! - an `int`, which must not be negative, is the atom of the same value, however large;
+//! - `bytes` are an atom, least significant byte first;
+//! - a `str` is a cord, the atom of its UTF-8 bytes;
+//! - a `bool` is a loobean, so `True` is `0` and `False` is `1`, as in Hoon;
+//! - a `tuple` of at least two elements is a cell, nested to the right, so `(1, 2, 3)` is
+//!   `[1 2 3]`;
+//! - a `list` is a null-terminated list;
+//! - a `dict` is a Hoon map;
+//! - a `Noun` is itself.
+//!
+//! Going the other way, an atom converts to an `int` of the same value, however large, with
+//! `to_int()` or `int()`; to `bytes`, least significant byte first and without trailing zero bytes,
+//! with `to_bytes()`; and to a `str`, if it's UTF-8, with `to_str()`. A list converts to a Python
+//! `list` of nouns with `to_list()`, and a Hoon map to a `dict` of nouns with `to_dict()`.
+//!
+//! # Errors
+//!
+//! Taking a noun apart the wrong way, like taking the head of an atom, cueing bytes that aren't a
+//! jammed noun, or converting a noun that isn't a list with `to_list()`, raises `ValueError`.
+//! `get()` raises `IndexError` if there's no noun at the axis, and `Noun()` raises `TypeError` for
+//! a value it can't convert.
+//!
+//! Unless the `thread-safe` feature is enabled, nouns can't be shared between threads, and using
+//! one from a thread other than the one that created it raises `RuntimeError`.
+
+use crate::{atom::Atom, cell::Cell, hoon_map, noun::Noun, Rc};
+use pyo3::{
+    exceptions::{PyIndexError, PyTypeError, PyValueError},
+    prelude::*,
+    types::{PyBool, PyBytes, PyDict, PyInt, PyList, PyString, PyTuple},
+};
+
+/// A noun, `noun.Noun` in Python.
+///
+/// Taking the head or tail of a cell doesn't copy it. Nouns compare and hash by value, so they can
+/// be the keys of a `dict`.
+#[pyclass(name = "Noun", module = "noun", eq, hash, frozen, unsendable)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct PyNoun(Rc<Noun>);
+
+#[pymethods]
+impl PyNoun {
+    /// Converts a Python value into a noun, as the [module](self) documentation describes.
+    #[new]
+    fn new(value: &Bound<'_, PyAny>) -> PyResult<Self> {
+        noun_from_py(value).map(Self)
+    }
+
+    /// Serializes ("jams") this noun into its bytes, least significant byte first.
+    fn jam<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        PyBytes::new(py, &self.0.jam_to_vec())
+    }
+
+    fn is_atom(&self) -> bool {
+        matches!(*self.0, Noun::Atom(_))
+    }
+
+    fn is_cell(&self) -> bool {
+        matches!(*self.0, Noun::Cell(_))
+    }
+
+    /// Returns the head of this noun, raising `ValueError` if it's an atom.
+    fn head(&self) -> PyResult<Self> {
+        Ok(Self(self.cell()?.head()))
+    }
+
+    /// Returns the tail of this noun, raising `ValueError` if it's an atom.
+    fn tail(&self) -> PyResult<Self> {
+        Ok(Self(self.cell()?.tail()))
+    }
+
+    /// Returns the noun at `axis` of this noun, raising `IndexError` if there isn't one.
+    fn get(&self, axis: u64) -> PyResult<Self> {
+        self.0
+            .get(axis)
+            .map(|noun| Self(Rc::new(noun.clone())))
+            .ok_or_else(|| PyIndexError::new_err(format!("no noun at axis {}", axis)))
+    }
+
+    /// Returns the value of this atom, raising `ValueError` if it's a cell.
+    fn to_int<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let bytes = PyBytes::new(py, self.atom()?.as_bytes());
+        py.get_type::<PyInt>()
+            .call_method1("from_bytes", (bytes, "little"))
+    }
+
+    fn __int__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        self.to_int(py)
+    }
+
+    /// Returns the bytes of this atom, least significant byte first, raising `ValueError` if it's
+    /// a cell.
+    fn to_bytes<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        Ok(PyBytes::new(py, self.atom()?.as_bytes()))
+    }
+
+    /// Returns the text of this cord, raising `ValueError` if it's a cell or isn't UTF-8.
+    fn to_str(&self) -> PyResult<String> {
+        self.atom()?
+            .as_str()
+            .map(String::from)
+            .map_err(|err| PyValueError::new_err(err.to_string()))
+    }
+
+    /// Returns the elements of this list, raising `ValueError` if it isn't a list.
+    fn to_list(&self) -> PyResult<Vec<Self>> {
+        let mut elems = Vec::new();
+        let mut rest = self.0.clone();
+        while let Noun::Cell(cell) = &*rest {
+            elems.push(Self(cell.head()));
+            rest = cell.tail();
+        }
+        if !rest.is_null() {
+            return Err(PyValueError::new_err("not a null-terminated list"));
+        }
+        Ok(elems)
+    }
+
+    /// Returns the entries of this Hoon map, raising `ValueError` if it isn't one.
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let entries = hoon_map::from_map_noun::<Rc<Noun>, Rc<Noun>>(&self.0)
+            .map_err(|err| PyValueError::new_err(err.to_string()))?;
+        let dict = PyDict::new(py);
+        for (key, val) in entries {
+            dict.set_item(Self(key), Self(val))?;
+        }
+        Ok(dict)
+    }
+
+    fn __str__(&self) -> String {
+        self.0.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Noun({})", self.0)
+    }
+}
+
+impl PyNoun {
+    fn atom(&self) -> PyResult<&Atom> {
+        match &*self.0 {
+            Noun::Atom(atom) => Ok(atom),
+            Noun::Cell(_) => Err(PyValueError::new_err("expected an atom, found a cell")),
+        }
+    }
+
+    fn cell(&self) -> PyResult<&Cell> {
+        match &*self.0 {
+            Noun::Atom(_) => Err(PyValueError::new_err("expected a cell, found an atom")),
+            Noun::Cell(cell) => Ok(cell),
+        }
+    }
+}
+
+impl From<Noun> for PyNoun {
+    fn from(noun: Noun) -> Self {
+        Self(Rc::new(noun))
+    }
+}
+
+impl From<PyNoun> for Rc<Noun> {
+    fn from(noun: PyNoun) -> Self {
+        noun.0
+    }
+}
+
+/// Converts a Python value into a noun.
+fn noun_from_py(value: &Bound<'_, PyAny>) -> PyResult<Rc<Noun>> {
+    if let Ok(noun) = value.downcast::<PyNoun>() {
+        return Ok(noun.borrow().0.clone());
+    }
+    // `bool` is a subclass of `int`, so it has to be checked first.
+    if let Ok(loobean) = value.downcast::<PyBool>() {
+        return Ok(Rc::new(Noun::from(Atom::from(u8::from(
+            !loobean.is_true(),
+        )))));
+    }
+    if let Ok(int) = value.downcast::<PyInt>() {
+        if int.lt(0)? {
+            return Err(PyValueError::new_err("atoms can't be negative"));
+        }
+        let len = int
+            .call_method0("bit_length")?
+            .extract::<usize>()?
+            .div_ceil(8);
+        let bytes = int.call_method1("to_bytes", (len, "little"))?;
+        return Ok(Rc::new(Noun::from(Atom::from(
+            bytes.downcast::<PyBytes>()?.as_bytes().to_vec(),
+        ))));
+    }
+    if let Ok(bytes) = value.downcast::<PyBytes>() {
+        return Ok(Rc::new(Noun::from(Atom::from(bytes.as_bytes().to_vec()))));
+    }
+    if let Ok(text) = value.downcast::<PyString>() {
+        return Ok(Rc::new(Noun::from(Atom::from(text.to_str()?))));
+    }
+    if let Ok(tuple) = value.downcast::<PyTuple>() {
+        if tuple.len() < 2 {
+            return Err(PyValueError::new_err("a cell needs at least two elements"));
+        }
+        let elems = tuple
+            .iter()
+            .map(|elem| noun_from_py(&elem))
+            .collect::<PyResult<Vec<_>>>()?;
+        return Ok(Rc::new(Noun::from(Cell::from(elems))));
+    }
+    if let Ok(list) = value.downcast::<PyList>() {
+        let elems = list
+            .iter()
+            .map(|elem| noun_from_py(&elem))
+            .collect::<PyResult<Vec<_>>>()?;
+        return Ok(Rc::new(Noun::list_from(elems)));
+    }
+    if let Ok(dict) = value.downcast::<PyDict>() {
+        let entries = dict
+            .iter()
+            .map(|(key, val)| Ok((noun_from_py(&key)?, noun_from_py(&val)?)))
+            .collect::<PyResult<Vec<_>>>()?;
+        return Ok(Rc::new(hoon_map::to_map_noun(entries)));
+    }
+    Err(PyTypeError::new_err(format!(
+        "can't convert {} to a noun",
+        value.get_type().name()?
+    )))
+}
+
+/// Deserializes ("cues") a jammed noun from its bytes, least significant byte first, raising
+/// `ValueError` if they aren't a jammed noun.
+#[pyfunction]
+fn cue(bytes: &[u8]) -> PyResult<PyNoun> {
+    Noun::cue_bytes(bytes)
+        .map(PyNoun::from)
+        .map_err(|err| PyValueError::new_err(err.to_string()))
+}
+
+/// The `noun` Python module.
+#[pymodule]
+#[pyo3(name = "noun")]
+pub fn noun_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyNoun>()?;
+    m.add_function(wrap_pyfunction!(cue, m)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pyo3::ffi::c_str;
+
+    #[test]
+    fn python() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let m = PyModule::new(py, "noun").expect("module");
+            noun_module(&m).expect("init module");
+            let globals = PyDict::new(py);
+            globals.set_item("noun", m).expect("globals");
+            let run = |code: &std::ffi::CStr| {
+                py.run(code, Some(&globals), None)
+                    .unwrap_or_else(|err| panic!("{}", err))
+            };
+
+            // A round trip through the jam of `[0 19]` from the cue documentation, taking it apart
+            // on the way.
+            run(c_str!(
+                r#"
+n = noun.cue(b"\x09\x9b")
+assert n.is_cell() and not n.is_atom()
+assert n.head() == noun.Noun(0) and n.tail() == noun.Noun(19)
+assert n.get(3) == n.tail() and n.get(1) == n
+assert n.jam() == b"\x09\x9b"
+assert str(n) == "[0x0 0x13]" and repr(n) == "Noun([0x0 0x13])"
+"#
+            ));
+
+            // Large atoms convert to and from `int` and `bytes` exactly.
+            run(c_str!(
+                r#"
+big = 2 ** 200 + 5
+n = noun.Noun(big)
+assert n.to_int() == big and int(n) == big
+assert n.to_bytes() == big.to_bytes(26, "little")
+assert noun.Noun(n.to_bytes()) == n
+assert noun.Noun(b"hi\x00\x00").to_bytes() == b"hi"
+assert noun.Noun(0).to_bytes() == b""
+assert noun.Noun("hello").to_str() == "hello"
+assert noun.Noun("hello") == noun.Noun(int.from_bytes(b"hello", "little"))
+assert noun.Noun(True) == noun.Noun(0) and noun.Noun(False) == noun.Noun(1)
+assert noun.cue(n.jam()) == n
+"#
+            ));
+
+            // Lists, cells, and maps.
+            run(c_str!(
+                r#"
+l = noun.Noun([1, "a", (2, 3)])
+assert l == noun.Noun((1, "a", (2, 3), 0))
+assert [str(e) for e in l.to_list()] == ["0x1", "0x61", "[0x2 0x3]"]
+assert noun.Noun([]).to_list() == []
+d = noun.Noun({"a": 1, "b": (2, 3)}).to_dict()
+assert d == {noun.Noun("a"): noun.Noun(1), noun.Noun("b"): noun.Noun((2, 3))}
+"#
+            ));
+
+            // Errors are raised as exceptions.
+            run(c_str!(
+                r#"
+def raises(exc, f):
+    try:
+        f()
+    except exc:
+        return
+    raise AssertionError(f"expected {exc.__name__}")
+
+atom, cell = noun.Noun(5), noun.Noun((1, 2))
+raises(ValueError, lambda: noun.cue(b"\x03"))
+raises(ValueError, atom.head)
+raises(ValueError, atom.tail)
+raises(ValueError, cell.to_int)
+raises(ValueError, cell.to_bytes)
+raises(ValueError, cell.to_list)
+raises(ValueError, atom.to_dict)
+raises(ValueError, noun.Noun(b"\xff").to_str)
+raises(ValueError, lambda: noun.Noun(-1))
+raises(ValueError, lambda: noun.Noun((1,)))
+raises(IndexError, lambda: cell.get(4))
+raises(IndexError, lambda: cell.get(0))
+raises(TypeError, lambda: noun.Noun(1.5))
+"#
+            ));
+        });
+
+        // The example in the module documentation, run as it's written, with the result in the
+        // comment on a line checked against the `repr()` of the line's expression.
+        Python::with_gil(|py| {
+            let m = PyModule::new(py, "noun").expect("module");
+            noun_module(&m).expect("init module");
+            py.import("sys")
+                .and_then(|sys| sys.getattr("modules"))
+                .and_then(|modules| modules.set_item("noun", m))
+                .expect("sys.modules");
+
+            let example = include_str!("python.rs")
+                .split("//! ```python\n")
+                .nth(1)
+                .and_then(|rest| rest.split("//! ```\n").next())
+                .expect("example");
+            let mut code = String::new();
+            for line in example.lines() {
+                let line = line
+                    .strip_prefix("//! ")
+                    .or_else(|| line.strip_prefix("//!"))
+                    .unwrap_or(line);
+                match line.split_once(" # ") {
+                    Some((expr, repr)) => code.push_str(&format!(
+                        "assert repr({0}) == {1:?}, repr({0})\n",
+                        expr.trim_end(),
+                        repr
+                    )),
+                    None => code.push_str(&format!("{}\n", line)),
+                }
+            }
+            assert_eq!(code.matches("assert").count(), 4);
+            let code = std::ffi::CString::new(code).expect("code");
+            py.run(&code, None, None)
+                .unwrap_or_else(|err| panic!("{}", err));
+        });
+
+        // The same nouns from Rust.
+        Python::with_gil(|py| {
+            let noun = PyNoun::from(Noun::from(Cell::from([0u8, 19])));
+            assert_eq!(noun.jam(py).as_bytes(), [0x09, 0x9b]);
+            assert_eq!(cue(&[0x09, 0x9b]).expect("cue"), noun);
+            assert_eq!(noun.head().expect("head"), PyNoun::from(Noun::from(0u8)));
+            let err = noun.to_str().expect_err("cell");
+            assert!(err.is_instance_of::<PyValueError>(py));
+        });
+    }
+}
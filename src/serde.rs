@@ -0,0 +1,171 @@
+//! [Serde] support: serializing any [`Serialize`] type into a [`Noun`] and deserializing any
+//! [`Deserialize`] type from one.
+//!
+//! # Data model
+//!
+//! Every type in the serde data model maps to a noun as follows. The mapping agrees with
+//! [`convert`](mod@crate::convert) and its derive macros wherever they overlap, so a type serialized
+//! with serde produces the same noun as the equivalent derived [`IntoNoun`] impl, with the
+//! exception that serde doesn't convert variant names to `kebab-case` (use
+//! `#[serde(rename_all = "kebab-case")]` for that).
+//!
+//! | Serde type                           | Noun                                                   |
+//! |--------------------------------------|--------------------------------------------------------|
+//! | `bool`                               | a loobean: `0` for `true` and `1` for `false`          |
+//! | `u8`, `u16`, `u32`, `u64`, `u128`    | an atom (`@ud`)                                        |
+//! | `i8`, `i16`, `i32`, `i64`, `i128`    | a signed atom (`@s`): `2n` for `n >= 0` and `-2n - 1` for `n < 0` |
+//! | `f32`                                | the IEEE 754 bits of the float as an atom (`@rs`)      |
+//! | `f64`                                | the IEEE 754 bits of the float as an atom (`@rd`)      |
+//! | `char`                               | an atom of the char's UTF-8 bytes (`@t`)               |
+//! | string                               | an atom of the string's UTF-8 bytes (a cord, `@t`)     |
+//! | byte array                           | an atom of the bytes, least significant byte first      |
+//! | option                               | a unit: `~` for `None` and `[~ t]` for `Some(t)`       |
+//! | unit, unit struct                    | `~`                                                    |
+//! | unit variant                         | the variant name as a term, `%name`                    |
+//! | newtype struct                       | the wrapped value                                      |
+//! | newtype variant                      | `[%name value]`                                        |
+//! | seq                                  | a null-terminated list `[e0 e1 ... eN ~]`              |
+//! | tuple, tuple struct, struct          | a right-nested cell of the fields `[f0 f1 ... fN]`     |
+//! | tuple variant, struct variant        | `[%name f0 f1 ... fN]`                                 |
+//! | map                                  | a null-terminated list of pairs `[[k0 v0] ... [kN vN] ~]` |
+//!
+//! Struct field names don't appear in the noun: a struct is identified by its shape alone, just
+//! like in Hoon. A tuple, struct, or variant with a single field is that field's noun rather than
+//! a cell, and one with no fields is `~` (or `%name` for a variant).
+//!
+//! Atoms have no leading zeros, so a string or byte array that ends in zero bytes loses them, and
+//! the empty string, the empty byte array, `0`, `true`, and `~` are all the same atom. A noun
+//! doesn't say which type it holds; the receiver has to know.
+//!
+//! # Deserialization
+//!
+//! [`from_noun()`] reads the same data model back. A noun that doesn't have the shape of the type
+//! being deserialized fails with [`Error::At`], which records the axis of the offending noun and
+//! the struct fields and list indices leading to it. Because a noun doesn't describe its own type,
+//! self-describing deserialization (`deserialize_any`, used by untagged enums among others) is a
+//! best guess: an atom is read as an unsigned integer if it fits in a `u128` and as bytes
+//! otherwise, and a cell as a pair of its head and tail.
+//!
+//! # Nouns in other formats
+//!
+//! [`Noun`] itself implements [`Serialize`] and [`Deserialize`] as its jammed bytes (a hex string
+//! in human-readable formats), so a noun can be a field of a type serialized with any serde
+//! format, like a config file or a snapshot.
+//!
+//! [Serde]: https://serde.rs
+//! [`IntoNoun`]: crate::convert::IntoNoun
+//! [`Noun`]: crate::Noun
+//! [`Deserialize`]: ::serde::Deserialize
+//! [`Serialize`]: ::serde::Serialize
+
+mod de;
+mod jammed;
+mod ser;
+
+pub use crate::convert::PathSegment;
+pub use de::{from_noun, Deserializer};
+pub use ser::{to_noun, Serializer};
+
+use crate::axis::peg;
+use std::fmt::{self, Display, Formatter};
+
+/// Errors that occur when serializing to or deserializing from a noun.
+#[derive(Debug)]
+pub enum Error {
+    /// A [`Serialize`](::serde::Serialize) or [`Deserialize`](::serde::Deserialize) impl failed,
+    /// or a noun didn't have the shape of the type being deserialized.
+    Custom(String),
+    /// Deserializing failed somewhere inside a noun.
+    At {
+        /// The axis of the noun that failed to deserialize, or [`None`] if the axis doesn't fit in
+        /// a `u64`.
+        axis: Option<u64>,
+        /// The fields and elements leading to the noun that failed to deserialize, outermost first.
+        path: Vec<PathSegment>,
+        /// The error deserializing the noun.
+        source: Box<Error>,
+    },
+}
+
+impl Error {
+    /// Notes that this error occurred in the noun at `segment`, whose axis is `axis` relative to
+    /// the noun containing it.
+    pub(crate) fn at(self, segment: PathSegment, axis: Option<u64>) -> Self {
+        self.nest(Some(segment), axis)
+    }
+
+    /// Notes that this error occurred in the noun at `axis` relative to the noun containing it,
+    /// which isn't a field or element.
+    pub(crate) fn at_axis(self, axis: Option<u64>) -> Self {
+        self.nest(None, axis)
+    }
+
+    fn nest(self, segment: Option<PathSegment>, outer: Option<u64>) -> Self {
+        match self {
+            Self::At {
+                axis,
+                mut path,
+                source,
+            } => {
+                if let Some(segment) = segment {
+                    path.insert(0, segment);
+                }
+                Self::At {
+                    axis: outer.zip(axis).and_then(|(outer, axis)| peg(outer, axis)),
+                    path,
+                    source,
+                }
+            }
+            err => Self::At {
+                axis: outer,
+                path: segment.into_iter().collect(),
+                source: Box::new(err),
+            },
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        match self {
+            Self::Custom(msg) => write!(f, "{}", msg),
+            Self::At { axis, path, source } => {
+                write!(f, "{} at ", source)?;
+                for (i, segment) in path.iter().enumerate() {
+                    match segment {
+                        PathSegment::Field(name) if i == 0 => write!(f, "{}", name)?,
+                        PathSegment::Field(name) => write!(f, ".{}", name)?,
+                        PathSegment::Index(index) => write!(f, "[{}]", index)?,
+                    }
+                }
+                match axis {
+                    Some(axis) if path.is_empty() => write!(f, "axis {}", axis),
+                    Some(axis) => write!(f, " (axis {})", axis),
+                    None if path.is_empty() => write!(f, "an axis too large to represent"),
+                    None => Ok(()),
+                }
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Custom(_) => None,
+            Self::At { source, .. } => Some(&**source),
+        }
+    }
+}
+
+impl ::serde::de::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Self::Custom(msg.to_string())
+    }
+}
+
+impl ::serde::ser::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Self::Custom(msg.to_string())
+    }
+}
@@ -0,0 +1,821 @@
+//! Deserializing from a noun.
+
+use super::{Error, PathSegment};
+use crate::{
+    atom::Atom,
+    convert::{elem_axis, tail_axis},
+    noun::Noun,
+};
+use ::serde::de::{
+    self, value::BorrowedStrDeserializer, DeserializeSeed, Expected, Unexpected, Visitor,
+};
+
+/// Deserializes a `T` from `noun`, following the [data model](super#data-model).
+///
+/// If `noun` doesn't have the shape of a `T`, the error reports the [path](Error::At) to the part
+/// of `noun` that doesn't.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{atom::Atom, cell::Cell, serde::from_noun, Noun};
+/// let noun = Noun::from(Cell::from([
+///     Noun::from(Atom::from(7u8)),
+///     Noun::from(Cell::from([Atom::null(), Atom::from("seven")])),
+///     Noun::from(Cell::from([1u8, 2u8, 0u8])),
+/// ]));
+/// let (n, name, list): (u8, Option<&str>, Vec<i8>) = from_noun(&noun).unwrap();
+/// assert_eq!((n, name, list), (7, Some("seven"), vec![-1, 1]));
+/// ```
+pub fn from_noun<'de, T: de::Deserialize<'de>>(noun: &'de Noun) -> Result<T, Error> {
+    T::deserialize(Deserializer::new(noun))
+}
+
+/// A [`Deserializer`](de::Deserializer) that reads from a noun.
+///
+/// This is usually used through [`from_noun()`].
+#[derive(Clone, Copy, Debug)]
+pub struct Deserializer<'de> {
+    noun: &'de Noun,
+}
+
+impl<'de> Deserializer<'de> {
+    /// Creates a deserializer that reads from `noun`.
+    pub fn new(noun: &'de Noun) -> Self {
+        Self { noun }
+    }
+
+    fn atom(&self, exp: &dyn Expected) -> Result<&'de Atom, Error> {
+        match self.noun {
+            Noun::Atom(atom) => Ok(atom),
+            Noun::Cell(_) => Err(de::Error::invalid_type(Unexpected::Other("a cell"), exp)),
+        }
+    }
+
+    fn null(&self, exp: &dyn Expected) -> Result<(), Error> {
+        if self.atom(exp)?.is_null() {
+            Ok(())
+        } else {
+            Err(de::Error::invalid_value(
+                Unexpected::Other("a non-null atom"),
+                exp,
+            ))
+        }
+    }
+
+    /// Reads a signed atom (`@s`), undoing its zigzag encoding.
+    fn signed(&self, exp: &dyn Expected) -> Result<i128, Error> {
+        let atom = self.atom(exp)?;
+        let zigzag = atom
+            .as_u128()
+            .ok_or_else(|| de::Error::invalid_value(Unexpected::Other("a large atom"), exp))?;
+        Ok((zigzag >> 1) as i128 ^ -((zigzag & 1) as i128))
+    }
+
+    fn str(&self, exp: &dyn Expected) -> Result<&'de str, Error> {
+        self.atom(exp)?
+            .as_str()
+            .map_err(|_| de::Error::invalid_value(Unexpected::Other("a non-UTF-8 atom"), exp))
+    }
+}
+
+/// Implements the `deserialize_*()` methods for an integer type.
+macro_rules! deserialize_int {
+    (unsigned $method:ident, $visit:ident, $as_uint:ident) => {
+        fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+            let atom = self.atom(&visitor)?;
+            match atom.$as_uint() {
+                Some(n) => visitor.$visit(n),
+                None => Err(de::Error::invalid_value(
+                    Unexpected::Other("a large atom"),
+                    &visitor,
+                )),
+            }
+        }
+    };
+    (signed $method:ident, $visit:ident, $int:ty) => {
+        fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+            let n = self.signed(&visitor)?;
+            match <$int>::try_from(n) {
+                Ok(n) => visitor.$visit(n),
+                Err(_) => Err(de::Error::invalid_value(
+                    Unexpected::Other("a large signed atom"),
+                    &visitor,
+                )),
+            }
+        }
+    };
+}
+
+impl<'de> de::Deserializer<'de> for Deserializer<'de> {
+    type Error = Error;
+
+    /// Deserializes whatever the noun looks like, which is only a guess because a noun doesn't
+    /// describe its own type: an atom is an unsigned integer if it fits in a `u128` and bytes
+    /// otherwise, and a cell is a pair of its head and tail.
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.noun {
+            Noun::Atom(atom) => {
+                if let Some(n) = atom.as_u64() {
+                    visitor.visit_u64(n)
+                } else if let Some(n) = atom.as_u128() {
+                    visitor.visit_u128(n)
+                } else {
+                    visitor.visit_borrowed_bytes(atom.as_bytes())
+                }
+            }
+            Noun::Cell(_) => visitor.visit_seq(Fields::new(self.noun, 2, None)),
+        }
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.atom(&visitor)?.as_u8() {
+            Some(0) => visitor.visit_bool(true),
+            Some(1) => visitor.visit_bool(false),
+            _ => Err(de::Error::invalid_value(
+                Unexpected::Other("an atom other than 0 or 1"),
+                &visitor,
+            )),
+        }
+    }
+
+    deserialize_int!(signed deserialize_i8, visit_i8, i8);
+    deserialize_int!(signed deserialize_i16, visit_i16, i16);
+    deserialize_int!(signed deserialize_i32, visit_i32, i32);
+    deserialize_int!(signed deserialize_i64, visit_i64, i64);
+    deserialize_int!(signed deserialize_i128, visit_i128, i128);
+    deserialize_int!(unsigned deserialize_u8, visit_u8, as_u8);
+    deserialize_int!(unsigned deserialize_u16, visit_u16, as_u16);
+    deserialize_int!(unsigned deserialize_u32, visit_u32, as_u32);
+    deserialize_int!(unsigned deserialize_u64, visit_u64, as_u64);
+    deserialize_int!(unsigned deserialize_u128, visit_u128, as_u128);
+
+    fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.atom(&visitor)?.as_u32() {
+            Some(bits) => visitor.visit_f32(f32::from_bits(bits)),
+            None => Err(de::Error::invalid_value(
+                Unexpected::Other("an atom longer than 32 bits"),
+                &visitor,
+            )),
+        }
+    }
+
+    fn deserialize_f64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.atom(&visitor)?.as_u64() {
+            Some(bits) => visitor.visit_f64(f64::from_bits(bits)),
+            None => Err(de::Error::invalid_value(
+                Unexpected::Other("an atom longer than 64 bits"),
+                &visitor,
+            )),
+        }
+    }
+
+    fn deserialize_char<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let s = self.str(&visitor)?;
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => visitor.visit_char(c),
+            _ => Err(de::Error::invalid_value(Unexpected::Str(s), &visitor)),
+        }
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let s = self.str(&visitor)?;
+        visitor.visit_borrowed_str(s)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let bytes = self.atom(&visitor)?.as_bytes();
+        visitor.visit_borrowed_bytes(bytes)
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.noun {
+            Noun::Atom(atom) if atom.is_null() => visitor.visit_none(),
+            Noun::Cell(cell) if cell.head_ref().is_null() => visitor
+                .visit_some(Deserializer::new(cell.tail_ref()))
+                .map_err(|err| err.at_axis(Some(3))),
+            _ => Err(de::Error::invalid_value(
+                Unexpected::Other("a noun other than ~ or [~ value]"),
+                &visitor,
+            )),
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.null(&visitor)?;
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_seq(List {
+            noun: self.noun,
+            index: 0,
+        })
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_seq(Fields::new(self.noun, len, None))
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_map(Map {
+            list: List {
+                noun: self.noun,
+                index: 0,
+            },
+            val: None,
+        })
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_seq(Fields::new(self.noun, fields.len(), Some(fields)))
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        let (tag, payload) = match self.noun {
+            Noun::Atom(tag) => (tag, None),
+            Noun::Cell(cell) => match cell.head_ref() {
+                Noun::Atom(tag) => (tag, Some(cell.tail_ref())),
+                Noun::Cell(_) => {
+                    return Err(de::Error::invalid_type(
+                        Unexpected::Other("a cell with a cell for a head"),
+                        &visitor,
+                    ))
+                }
+            },
+        };
+        let tag = tag.as_str().map_err(|_| {
+            de::Error::invalid_value(Unexpected::Other("a non-UTF-8 tag"), &visitor)
+        })?;
+        // The variant's name from the list of variants is 'static, unlike the tag.
+        let variant = variants.iter().copied().find(|variant| *variant == tag);
+        visitor.visit_enum(Enum {
+            tag,
+            variant,
+            payload,
+        })
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+/// Accesses the elements of a null-terminated list.
+struct List<'de> {
+    /// The rest of the list.
+    noun: &'de Noun,
+    /// The index of the next element.
+    index: usize,
+}
+
+impl<'de> List<'de> {
+    /// Returns the next element of the list along with its index.
+    fn next(&mut self) -> Result<Option<(&'de Noun, usize)>, Error> {
+        match self.noun {
+            Noun::Atom(atom) if atom.is_null() => Ok(None),
+            Noun::Atom(_) => Err(<Error as de::Error>::invalid_value(
+                Unexpected::Other("a non-null atom"),
+                &"a null-terminated list",
+            )
+            .at(PathSegment::Index(self.index), tail_axis(self.index))),
+            Noun::Cell(cell) => {
+                let index = self.index;
+                self.noun = cell.tail_ref();
+                self.index += 1;
+                Ok(Some((cell.head_ref(), index)))
+            }
+        }
+    }
+}
+
+impl<'de> de::SeqAccess<'de> for List<'de> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Error> {
+        let Some((elem, index)) = self.next()? else {
+            return Ok(None);
+        };
+        seed.deserialize(Deserializer::new(elem))
+            .map(Some)
+            .map_err(|err| err.at(PathSegment::Index(index), elem_axis(index)))
+    }
+}
+
+/// Accesses the entries of a null-terminated list of pairs.
+struct Map<'de> {
+    list: List<'de>,
+    /// The value of the current entry and its index.
+    val: Option<(&'de Noun, usize)>,
+}
+
+impl<'de> de::MapAccess<'de> for Map<'de> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        let Some((entry, index)) = self.list.next()? else {
+            return Ok(None);
+        };
+        let at = |err: Error, segment, axis| {
+            err.at(segment, axis)
+                .at(PathSegment::Index(index), elem_axis(index))
+        };
+        let Noun::Cell(entry) = entry else {
+            return Err(at(
+                de::Error::invalid_type(Unexpected::Other("an atom"), &"a key-value pair"),
+                PathSegment::Index(0),
+                Some(1),
+            ));
+        };
+        self.val = Some((entry.tail_ref(), index));
+        seed.deserialize(Deserializer::new(entry.head_ref()))
+            .map(Some)
+            .map_err(|err| at(err, PathSegment::Index(0), Some(2)))
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        let (val, index) = self
+            .val
+            .take()
+            .expect("next_value_seed() called before next_key_seed()");
+        seed.deserialize(Deserializer::new(val)).map_err(|err| {
+            err.at(PathSegment::Index(1), Some(3))
+                .at(PathSegment::Index(index), elem_axis(index))
+        })
+    }
+}
+
+/// Accesses the fields of a right-nested cell.
+struct Fields<'de> {
+    /// The rest of the fields.
+    noun: &'de Noun,
+    /// The number of fields.
+    len: usize,
+    /// The index of the next field.
+    index: usize,
+    /// The names of the fields, if they have names.
+    names: Option<&'static [&'static str]>,
+}
+
+impl<'de> Fields<'de> {
+    fn new(noun: &'de Noun, len: usize, names: Option<&'static [&'static str]>) -> Self {
+        Self {
+            noun,
+            len,
+            index: 0,
+            names,
+        }
+    }
+}
+
+impl<'de> de::SeqAccess<'de> for Fields<'de> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Error> {
+        if self.index == self.len {
+            return Ok(None);
+        }
+        let index = self.index;
+        let segment = match self.names.and_then(|names| names.get(index)) {
+            Some(name) => PathSegment::Field(name),
+            None => PathSegment::Index(index),
+        };
+        self.index += 1;
+        // The last field is the rest of the cell.
+        let (field, axis) = if self.index == self.len {
+            (self.noun, tail_axis(index))
+        } else {
+            match self.noun {
+                Noun::Atom(_) => {
+                    return Err(<Error as de::Error>::invalid_length(
+                        index + 1,
+                        &format!("a cell of {} fields", self.len).as_str(),
+                    )
+                    .at(segment, tail_axis(index)))
+                }
+                Noun::Cell(cell) => {
+                    self.noun = cell.tail_ref();
+                    (cell.head_ref(), elem_axis(index))
+                }
+            }
+        };
+        seed.deserialize(Deserializer::new(field))
+            .map(Some)
+            .map_err(|err| err.at(segment, axis))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.len - self.index)
+    }
+}
+
+/// Accesses a head-tagged enum variant.
+struct Enum<'de> {
+    /// The variant's tag.
+    tag: &'de str,
+    /// The variant's name, if the tag names a variant.
+    variant: Option<&'static str>,
+    /// The variant's fields, if it has any.
+    payload: Option<&'de Noun>,
+}
+
+impl<'de> Enum<'de> {
+    /// Notes that `err` occurred in the variant's fields.
+    fn at(&self, err: Error) -> Error {
+        match self.variant {
+            Some(variant) => err.at(PathSegment::Field(variant), Some(3)),
+            None => err.at_axis(Some(3)),
+        }
+    }
+
+    fn payload(&self, exp: &dyn Expected) -> Result<&'de Noun, Error> {
+        self.payload
+            .ok_or_else(|| <Error as de::Error>::invalid_type(Unexpected::UnitVariant, exp))
+    }
+}
+
+impl<'de> de::EnumAccess<'de> for Enum<'de> {
+    type Error = Error;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self), Error> {
+        let tag = BorrowedStrDeserializer::<Error>::new(self.tag);
+        let variant = seed.deserialize(tag)?;
+        Ok((variant, self))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for Enum<'de> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        match self.payload {
+            None => Ok(()),
+            Some(_) => Err(de::Error::invalid_type(
+                Unexpected::Other("a cell"),
+                &"a unit variant",
+            )),
+        }
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, Error> {
+        let payload = self.payload(&"a newtype variant")?;
+        seed.deserialize(Deserializer::new(payload))
+            .map_err(|err| self.at(err))
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value, Error> {
+        let payload = self.payload(&visitor)?;
+        visitor
+            .visit_seq(Fields::new(payload, len, None))
+            .map_err(|err| self.at(err))
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        let payload = self.payload(&visitor)?;
+        visitor
+            .visit_seq(Fields::new(payload, fields.len(), Some(fields)))
+            .map_err(|err| self.at(err))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{cell::Cell, serde::to_noun};
+    use ::serde::{Deserialize, Serialize};
+    use serde_bytes::ByteBuf;
+    use std::{
+        collections::{BTreeMap, HashMap},
+        fmt::Debug,
+    };
+
+    #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+    #[serde(rename_all = "kebab-case")]
+    enum Mark {
+        Json,
+        Custom(String),
+    }
+
+    #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+    #[serde(rename_all = "kebab-case")]
+    enum Action {
+        Poke {
+            app: String,
+            mark: Mark,
+            data: ByteBuf,
+        },
+        Request(u32, Vec<(String, String)>),
+        Heartbeat,
+    }
+
+    #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+    struct Id(u64);
+
+    #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+    struct Empty;
+
+    #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+    struct Channel {
+        id: Id,
+        ship: String,
+        last_event: Option<u64>,
+        actions: Vec<Action>,
+        subscriptions: BTreeMap<String, (u16, bool)>,
+        acks: HashMap<u64, Option<i64>>,
+        delta: i32,
+        ratio: f64,
+        initial: char,
+        empty: Empty,
+        unit: (),
+    }
+
+    fn channel() -> Channel {
+        Channel {
+            id: Id(3),
+            ship: String::from("~zod"),
+            last_event: Some(41),
+            actions: vec![
+                Action::Poke {
+                    app: String::from("hood"),
+                    mark: Mark::Json,
+                    data: ByteBuf::from(vec![0, 1, 2]),
+                },
+                Action::Request(9, vec![(String::from("host"), String::from("localhost"))]),
+                Action::Poke {
+                    app: String::from("chat"),
+                    mark: Mark::Custom(String::from("chat-action")),
+                    data: ByteBuf::new(),
+                },
+                Action::Heartbeat,
+            ],
+            subscriptions: BTreeMap::from([
+                (String::from("/a"), (1, true)),
+                (String::from("/b"), (2, false)),
+            ]),
+            acks: HashMap::from([(1, Some(-1)), (2, None), (3, Some(i64::MIN))]),
+            delta: -5,
+            ratio: 0.5,
+            initial: 'z',
+            empty: Empty,
+            unit: (),
+        }
+    }
+
+    /// Returns `noun` with the noun at `axis` replaced by `new`.
+    fn replace(noun: &Noun, axis: u64, new: Noun) -> Noun {
+        if axis == 1 {
+            return new;
+        }
+        let Noun::Cell(cell) = noun else {
+            panic!("no noun at axis");
+        };
+        // The axis relative to the head or tail.
+        let depth = u64::BITS - 1 - axis.leading_zeros();
+        let rest = axis ^ (1 << depth) | 1 << (depth - 1);
+        if axis >> (depth - 1) == 2 {
+            Noun::from(Cell::from([
+                replace(cell.head_ref(), rest, new),
+                cell.tail_ref().clone(),
+            ]))
+        } else {
+            Noun::from(Cell::from([
+                cell.head_ref().clone(),
+                replace(cell.tail_ref(), rest, new),
+            ]))
+        }
+    }
+
+    fn round_trip<T>(value: &T)
+    where
+        T: Debug + for<'de> Deserialize<'de> + PartialEq + Serialize,
+    {
+        let noun = to_noun(value).expect("serialize");
+        assert_eq!(super::from_noun::<T>(&noun).expect("deserialize"), *value);
+    }
+
+    #[test]
+    fn from_noun() {
+        // Round trips through the serializer.
+        {
+            round_trip(&channel());
+            for action in channel().actions {
+                round_trip(&action);
+            }
+            round_trip(&Some(Some(())));
+            round_trip(&Option::<Vec<u8>>::None);
+            round_trip(&vec![Some(1u8), None]);
+            round_trip(&HashMap::from([(String::from("a"), vec![1i8, -1])]));
+            round_trip(&ByteBuf::from(vec![1, 2, 3]));
+            round_trip(&(i128::MIN, i128::MAX, u128::MAX, -0.0f32, f64::NAN.to_bits()));
+            round_trip(&String::from("hello"));
+        }
+
+        // Borrowed strings and bytes.
+        {
+            let noun = to_noun(&("borrowed", "bytes")).expect("serialize");
+            let (s, b): (&str, &[u8]) = super::from_noun(&noun).expect("deserialize");
+            assert_eq!((s, b), ("borrowed", &b"bytes"[..]));
+        }
+
+        // Trailing zero bytes are lost.
+        {
+            let noun = to_noun(&ByteBuf::from(vec![1, 0])).expect("serialize");
+            let bytes: ByteBuf = super::from_noun(&noun).expect("deserialize");
+            assert_eq!(bytes, ByteBuf::from(vec![1]));
+        }
+
+        // Self-describing deserialization is a best guess.
+        {
+            #[derive(Debug, Deserialize, PartialEq)]
+            #[serde(untagged)]
+            enum Guess {
+                Pair(u64, Box<Guess>),
+                Int(u64),
+            }
+
+            let noun = Noun::from(Cell::from([1u8, 2u8, 3u8]));
+            assert_eq!(
+                super::from_noun::<Guess>(&noun).expect("deserialize"),
+                Guess::Pair(1, Box::new(Guess::Pair(2, Box::new(Guess::Int(3)))))
+            );
+        }
+    }
+
+    #[test]
+    fn from_noun_errors() {
+        let path = |noun: &Noun| match super::from_noun::<Channel>(noun) {
+            Err(Error::At { axis, path, source }) => (axis, path, source.to_string()),
+            res => panic!("unexpected result: {:?}", res),
+        };
+        let replace =
+            |axis: u64, noun: Noun| replace(&to_noun(&channel()).expect("serialize"), axis, noun);
+
+        // A field nested three cells deep: the app of the first action.
+        {
+            // actions is the 4th of 11 fields, at axis 30; its first element is at 60; the poke's
+            // fields are at 121; and the app, its first field, is at 242.
+            let noun = replace(242, Noun::from(Cell::from([1u8, 2u8])));
+            let (axis, segments, msg) = path(&noun);
+            assert_eq!(axis, Some(242));
+            assert_eq!(
+                segments,
+                [
+                    PathSegment::Field("actions"),
+                    PathSegment::Index(0),
+                    PathSegment::Field("poke"),
+                    PathSegment::Field("app"),
+                ]
+            );
+            assert_eq!(msg, "invalid type: a cell, expected a string");
+            assert_eq!(
+                super::from_noun::<Channel>(&noun)
+                    .expect_err("error")
+                    .to_string(),
+                "invalid type: a cell, expected a string at actions[0].poke.app (axis 242)"
+            );
+        }
+
+        // A tuple in a map value: the value of the first subscription.
+        {
+            let noun = replace(249, Noun::null());
+            assert_eq!(
+                super::from_noun::<Channel>(&noun)
+                    .expect_err("error")
+                    .to_string(),
+                "invalid length 1, expected a cell of 2 fields at subscriptions[0][1][0] (axis 249)"
+            );
+        }
+
+        // A list without a null terminator.
+        {
+            let noun = replace(
+                30,
+                Noun::from(Cell::from([
+                    Noun::from(Atom::from("heartbeat")),
+                    Noun::from(Atom::from(1u8)),
+                ])),
+            );
+            let (axis, segments, _) = path(&noun);
+            assert_eq!(axis, Some(61));
+            assert_eq!(
+                segments,
+                [PathSegment::Field("actions"), PathSegment::Index(1)]
+            );
+        }
+
+        // An unknown tag.
+        {
+            let noun = replace(60, Noun::from(Atom::from("reboot")));
+            assert_eq!(
+                super::from_noun::<Channel>(&noun)
+                    .expect_err("error")
+                    .to_string(),
+                "unknown variant `reboot`, expected one of `poke`, `request`, `heartbeat` \
+                 at actions[0] (axis 60)"
+            );
+        }
+
+        // A struct that's too short.
+        {
+            let noun = Noun::from(Cell::from([1u8, 2u8]));
+            assert_eq!(
+                super::from_noun::<Channel>(&noun)
+                    .expect_err("error")
+                    .to_string(),
+                "invalid length 2, expected a cell of 11 fields at ship (axis 3)"
+            );
+        }
+
+        // Axes too large for a u64.
+        {
+            let mut list = vec![true; 100];
+            list[80] = false;
+            let mut noun = to_noun(&list).expect("serialize");
+            noun = super::from_noun::<Vec<u8>>(&noun)
+                .map(|mut list| {
+                    list[80] = 2;
+                    to_noun(&list).expect("serialize")
+                })
+                .expect("deserialize");
+            match super::from_noun::<Vec<bool>>(&noun) {
+                Err(Error::At { axis, path, .. }) => {
+                    assert_eq!(axis, None);
+                    assert_eq!(path, [PathSegment::Index(80)]);
+                }
+                res => panic!("unexpected result: {:?}", res),
+            }
+            assert_eq!(
+                super::from_noun::<Vec<bool>>(&noun)
+                    .expect_err("error")
+                    .to_string(),
+                "invalid value: an atom other than 0 or 1, expected a boolean at [80]"
+            );
+        }
+    }
+}
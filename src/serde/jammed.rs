@@ -0,0 +1,208 @@
+//! Serializing a [`Noun`] itself as its jammed bytes.
+
+use crate::noun::Noun;
+use ::serde::{
+    de::{self, Deserialize, Deserializer, SeqAccess, Visitor},
+    ser::{Serialize, Serializer},
+};
+use std::{
+    fmt::{self, Formatter, Write},
+    str,
+};
+
+/// Serializes a noun as the bytes of its jam, as returned by [`Noun::jam_to_vec()`].
+///
+/// Human-readable formats like JSON get the bytes as a string of lowercase hex digits, two per
+/// byte, in the same order as the bytes. Other formats get the bytes themselves.
+///
+/// This is for embedding a noun in a type that's serialized with some other format; it has nothing
+/// to do with the [data model](super#data-model) that maps types onto nouns. In particular,
+/// [`to_noun()`](super::to_noun()) of a noun is the atom of its jammed bytes, not the noun.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{cell::Cell, Noun};
+/// let noun = Noun::from(Cell::from([0u8, 19u8]));
+/// let json = serde_json::to_string(&noun).unwrap();
+/// assert_eq!(json, r#""099b""#);
+/// assert_eq!(serde_json::from_str::<Noun>(&json).unwrap(), noun);
+/// ```
+impl Serialize for Noun {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let bytes = self.jam_to_vec();
+        if serializer.is_human_readable() {
+            let mut hex = String::with_capacity(bytes.len() * 2);
+            for byte in &bytes {
+                write!(hex, "{:02x}", byte).expect("write to string");
+            }
+            serializer.serialize_str(&hex)
+        } else {
+            serializer.serialize_bytes(&bytes)
+        }
+    }
+}
+
+/// Deserializes a noun by cueing the bytes it was [serialized](Noun#impl-Serialize-for-Noun) as.
+///
+/// Bytes that aren't a jammed noun fail with a deserialization error rather than a panic.
+impl<'de> Deserialize<'de> for Noun {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(JammedVisitor)
+        } else {
+            deserializer.deserialize_bytes(JammedVisitor)
+        }
+    }
+}
+
+/// Visits the hex string or bytes of a jammed noun.
+struct JammedVisitor;
+
+impl JammedVisitor {
+    fn cue<E: de::Error>(bytes: &[u8]) -> Result<Noun, E> {
+        Noun::cue_bytes(bytes)
+            .map_err(|err| E::custom(format_args!("invalid jammed noun: {}", err)))
+    }
+}
+
+impl<'de> Visitor<'de> for JammedVisitor {
+    type Value = Noun;
+
+    fn expecting(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "the bytes of a jammed noun, or a hex string of them")
+    }
+
+    fn visit_str<E: de::Error>(self, hex: &str) -> Result<Noun, E> {
+        if !hex.len().is_multiple_of(2) {
+            return Err(E::invalid_length(
+                hex.len(),
+                &"an even number of hex digits",
+            ));
+        }
+        let bytes = hex
+            .as_bytes()
+            .chunks(2)
+            .map(|pair| {
+                str::from_utf8(pair)
+                    .ok()
+                    .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+                    .ok_or_else(|| E::invalid_value(de::Unexpected::Str(hex), &self))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::cue(&bytes)
+    }
+
+    fn visit_bytes<E: de::Error>(self, bytes: &[u8]) -> Result<Noun, E> {
+        Self::cue(bytes)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Noun, A::Error> {
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(byte) = seq.next_element::<u8>()? {
+            bytes.push(byte);
+        }
+        Self::cue(&bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{atom::Atom, cell::Cell, noun::Noun, Rc};
+    use ::serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Deserialize, PartialEq, Serialize)]
+    struct Snapshot {
+        name: String,
+        state: Noun,
+    }
+
+    #[test]
+    fn jammed() {
+        // `[[%sample 0x1.0000] [%sample 0x1.0000] 19]`, with the repeated cell shared.
+        let shared = Rc::new(Noun::from(Cell::from([
+            Atom::from("sample"),
+            Atom::from(0x1_0000u32),
+        ])));
+        let snapshot = Snapshot {
+            name: String::from("sample"),
+            state: Noun::from(Cell::from([
+                shared.clone(),
+                Rc::new(Noun::from(Cell::from([shared, Rc::new(Noun::from(19u8))]))),
+            ])),
+        };
+
+        // JSON gets hex.
+        {
+            let json = serde_json::to_value(&snapshot).expect("serialize");
+            let hex = json["state"].as_str().expect("hex");
+            assert_eq!(hex.len(), snapshot.state.jam_to_vec().len() * 2);
+            assert!(hex.bytes().all(|c| matches!(c, b'0'..=b'9' | b'a'..=b'f')));
+            assert_eq!(
+                serde_json::from_value::<Snapshot>(json).expect("deserialize"),
+                snapshot
+            );
+        }
+
+        // Bincode gets the bytes.
+        {
+            let bytes = bincode::serialize(&snapshot).expect("serialize");
+            let jammed = snapshot.state.jam_to_vec();
+            assert!(bytes.ends_with(&jammed));
+            let decoded: Snapshot = bincode::deserialize(&bytes).expect("deserialize");
+            assert_eq!(decoded, snapshot);
+            // The repeated cell was jammed as a backreference, and cued back into a shared cell.
+            let (Some(a), Some(b)) = (decoded.state.get(2), decoded.state.get(6)) else {
+                panic!("subnouns exist");
+            };
+            assert!(std::ptr::eq(a, b));
+        }
+
+        // Atoms, including `0`, which jams to a single byte.
+        {
+            for noun in [Noun::null(), Noun::from(Atom::from(vec![0xff; 100]))] {
+                let json = serde_json::to_string(&noun).expect("serialize");
+                assert_eq!(serde_json::from_str::<Noun>(&json).ok(), Some(noun));
+            }
+            assert_eq!(
+                serde_json::to_string(&Noun::null()).ok().as_deref(),
+                Some(r#""02""#)
+            );
+        }
+    }
+
+    #[test]
+    fn jammed_errors() {
+        // Malformed hex.
+        {
+            for json in [r#""099""#, r#""zz9b""#, r#""09 b""#, "[9, 155]"] {
+                assert!(serde_json::from_str::<Noun>(json).is_err(), "{}", json);
+            }
+            let err = serde_json::from_str::<Noun>(r#""099""#).expect_err("odd length");
+            assert!(err.to_string().contains("an even number of hex digits"));
+        }
+
+        // Well-formed hex of bytes that aren't a jammed noun: a backreference tag and nothing more.
+        {
+            let err = serde_json::from_str::<Noun>(r#""03""#).expect_err("truncated");
+            assert!(err.to_string().starts_with("invalid jammed noun: "));
+        }
+
+        // Corrupted bytes.
+        {
+            let snapshot = Snapshot {
+                name: String::from("sample"),
+                state: Noun::from(Cell::from([10_000u16, 10_000u16])),
+            };
+            let mut bytes = bincode::serialize(&snapshot).expect("serialize");
+            // The jammed noun is the last 5 bytes, and its last byte holds the tail of the
+            // backreference.
+            let last = bytes.len() - 1;
+            bytes[last] = 0xff;
+            let err = bincode::deserialize::<Snapshot>(&bytes).expect_err("corrupted");
+            assert!(err.to_string().starts_with("invalid jammed noun: "));
+            bytes.truncate(last - 1);
+            assert!(bincode::deserialize::<Snapshot>(&bytes).is_err());
+        }
+    }
+}
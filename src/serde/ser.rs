@@ -0,0 +1,555 @@
+//! Serializing into a noun.
+
+use super::Error;
+use crate::{
+    atom::Atom,
+    cell::Cell,
+    convert::{fields_into_noun, list_into_noun},
+    noun::Noun,
+};
+use ::serde::ser::{self, Serialize};
+use std::convert::Infallible;
+
+/// Serializes `value` into a noun, following the [data model](super#data-model).
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{atom::Atom, cell::Cell, serde::to_noun, Noun};
+/// let noun = to_noun(&(7u8, Some("seven"), vec![-1i8, 1i8])).unwrap();
+/// assert_eq!(noun, Noun::from(Cell::from([
+///     Noun::from(Atom::from(7u8)),
+///     Noun::from(Cell::from([Atom::null(), Atom::from("seven")])),
+///     Noun::from(Cell::from([1u8, 2u8, 0u8])),
+/// ])));
+/// ```
+pub fn to_noun<T: Serialize + ?Sized>(value: &T) -> Result<Noun, Error> {
+    value.serialize(Serializer)
+}
+
+/// A [`Serializer`](ser::Serializer) whose output is a noun.
+///
+/// This is usually used through [`to_noun()`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Serializer;
+
+/// Converts a signed integer to a signed atom (`@s`), which is the integer's zigzag encoding.
+fn signed(n: i128) -> Noun {
+    Noun::from(Atom::from(((n << 1) ^ (n >> (i128::BITS - 1))) as u128))
+}
+
+/// Returns the term naming an enum variant.
+fn tag(variant: &'static str) -> Noun {
+    Noun::from(Atom::from(variant))
+}
+
+impl ser::Serializer for Serializer {
+    type Ok = Noun;
+    type Error = Error;
+    type SerializeSeq = SerializeList;
+    type SerializeTuple = SerializeFields;
+    type SerializeTupleStruct = SerializeFields;
+    type SerializeTupleVariant = SerializeFields;
+    type SerializeMap = SerializeMap;
+    type SerializeStruct = SerializeFields;
+    type SerializeStructVariant = SerializeFields;
+
+    fn serialize_bool(self, v: bool) -> Result<Noun, Error> {
+        Ok(Noun::from(Atom::from(u8::from(!v))))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Noun, Error> {
+        Ok(signed(i128::from(v)))
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Noun, Error> {
+        Ok(signed(i128::from(v)))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Noun, Error> {
+        Ok(signed(i128::from(v)))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Noun, Error> {
+        Ok(signed(i128::from(v)))
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<Noun, Error> {
+        Ok(signed(v))
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Noun, Error> {
+        Ok(Noun::from(Atom::from(v)))
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Noun, Error> {
+        Ok(Noun::from(Atom::from(v)))
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Noun, Error> {
+        Ok(Noun::from(Atom::from(v)))
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Noun, Error> {
+        Ok(Noun::from(Atom::from(v)))
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<Noun, Error> {
+        Ok(Noun::from(Atom::from(v)))
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Noun, Error> {
+        Ok(Noun::from(Atom::from(v.to_bits())))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Noun, Error> {
+        Ok(Noun::from(Atom::from(v.to_bits())))
+    }
+
+    fn serialize_char(self, v: char) -> Result<Noun, Error> {
+        Ok(Noun::from(Atom::from(&*v.encode_utf8(&mut [0; 4]))))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Noun, Error> {
+        Ok(Noun::from(Atom::from(v)))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Noun, Error> {
+        Ok(Noun::from(Atom::from(v.to_vec())))
+    }
+
+    fn serialize_none(self) -> Result<Noun, Error> {
+        Ok(Noun::null())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Noun, Error> {
+        Ok(Noun::from(Cell::from([
+            Noun::null(),
+            value.serialize(self)?,
+        ])))
+    }
+
+    fn serialize_unit(self) -> Result<Noun, Error> {
+        Ok(Noun::null())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Noun, Error> {
+        Ok(Noun::null())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Noun, Error> {
+        Ok(tag(variant))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Noun, Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Noun, Error> {
+        Ok(Noun::from(Cell::from([
+            tag(variant),
+            value.serialize(self)?,
+        ])))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SerializeList, Error> {
+        Ok(SerializeList {
+            elems: Vec::with_capacity(len.unwrap_or(0)),
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SerializeFields, Error> {
+        Ok(SerializeFields::new(None, len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SerializeFields, Error> {
+        Ok(SerializeFields::new(None, len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeFields, Error> {
+        Ok(SerializeFields::new(Some(variant), len))
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<SerializeMap, Error> {
+        Ok(SerializeMap {
+            pairs: SerializeList {
+                elems: Vec::with_capacity(len.unwrap_or(0)),
+            },
+            key: None,
+        })
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<SerializeFields, Error> {
+        Ok(SerializeFields::new(None, len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeFields, Error> {
+        Ok(SerializeFields::new(Some(variant), len))
+    }
+}
+
+/// Serializes a sequence into a null-terminated list.
+#[doc(hidden)]
+pub struct SerializeList {
+    elems: Vec<Noun>,
+}
+
+impl SerializeList {
+    fn finish(self) -> Noun {
+        list_into_noun(self.elems.into_iter().map(Ok))
+            .unwrap_or_else(|never: Infallible| match never {})
+    }
+}
+
+impl ser::SerializeSeq for SerializeList {
+    type Ok = Noun;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.elems.push(value.serialize(Serializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Noun, Error> {
+        Ok(self.finish())
+    }
+}
+
+/// Serializes the fields of a tuple, struct, or variant into a right-nested cell, preceded by the
+/// variant's tag.
+#[doc(hidden)]
+pub struct SerializeFields {
+    fields: Vec<Noun>,
+}
+
+impl SerializeFields {
+    fn new(variant: Option<&'static str>, len: usize) -> Self {
+        let mut fields = Vec::with_capacity(len + 1);
+        fields.extend(variant.map(tag));
+        Self { fields }
+    }
+
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.fields.push(value.serialize(Serializer)?);
+        Ok(())
+    }
+}
+
+impl ser::SerializeTuple for SerializeFields {
+    type Ok = Noun;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Noun, Error> {
+        Ok(fields_into_noun(self.fields))
+    }
+}
+
+impl ser::SerializeTupleStruct for SerializeFields {
+    type Ok = Noun;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Noun, Error> {
+        Ok(fields_into_noun(self.fields))
+    }
+}
+
+impl ser::SerializeTupleVariant for SerializeFields {
+    type Ok = Noun;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Noun, Error> {
+        Ok(fields_into_noun(self.fields))
+    }
+}
+
+impl ser::SerializeStruct for SerializeFields {
+    type Ok = Noun;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        _key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Noun, Error> {
+        Ok(fields_into_noun(self.fields))
+    }
+}
+
+impl ser::SerializeStructVariant for SerializeFields {
+    type Ok = Noun;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        _key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Noun, Error> {
+        Ok(fields_into_noun(self.fields))
+    }
+}
+
+/// Serializes a map into a null-terminated list of pairs.
+#[doc(hidden)]
+pub struct SerializeMap {
+    pairs: SerializeList,
+    key: Option<Noun>,
+}
+
+impl ser::SerializeMap for SerializeMap {
+    type Ok = Noun;
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Error> {
+        self.key = Some(key.serialize(Serializer)?);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        let key = self
+            .key
+            .take()
+            .expect("serialize_value() called before serialize_key()");
+        let value = value.serialize(Serializer)?;
+        self.pairs.elems.push(Noun::from(Cell::from([key, value])));
+        Ok(())
+    }
+
+    fn end(self) -> Result<Noun, Error> {
+        Ok(self.pairs.finish())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::serde::Serialize;
+    use std::collections::BTreeMap;
+
+    #[derive(Serialize)]
+    #[serde(rename_all = "kebab-case")]
+    enum Mark {
+        Json,
+        Custom(String),
+    }
+
+    #[derive(Serialize)]
+    struct Header {
+        key: String,
+        value: String,
+    }
+
+    #[derive(Serialize)]
+    #[serde(rename_all = "kebab-case")]
+    enum Action {
+        Poke {
+            app: String,
+            mark: Mark,
+            data: Vec<u8>,
+        },
+        Request(u32, Vec<Header>),
+        Heartbeat,
+    }
+
+    #[derive(Serialize)]
+    struct Id(u64);
+
+    #[derive(Serialize)]
+    struct Empty;
+
+    #[derive(Serialize)]
+    struct Channel {
+        id: Id,
+        ship: String,
+        last_event: Option<u64>,
+        actions: Vec<Action>,
+        subscriptions: BTreeMap<String, (u16, bool)>,
+        delta: i32,
+        ratio: f64,
+        #[serde(with = "bytes")]
+        key: Vec<u8>,
+        empty: Empty,
+    }
+
+    /// Serializes a byte vector as bytes rather than as a sequence.
+    mod bytes {
+        pub fn serialize<S: ::serde::Serializer>(bytes: &[u8], s: S) -> Result<S::Ok, S::Error> {
+            s.serialize_bytes(bytes)
+        }
+    }
+
+    fn atom<T: Into<Atom>>(atom: T) -> Noun {
+        Noun::from(atom.into())
+    }
+
+    fn cell<const N: usize>(nouns: [Noun; N]) -> Noun
+    where
+        Cell: From<[Noun; N]>,
+    {
+        Noun::from(Cell::from(nouns))
+    }
+
+    #[test]
+    fn to_noun() {
+        // Scalars.
+        {
+            assert_eq!(super::to_noun(&true).expect("bool"), atom(0u8));
+            assert_eq!(super::to_noun(&false).expect("bool"), atom(1u8));
+            assert_eq!(super::to_noun(&u128::MAX).expect("u128"), atom(u128::MAX));
+            for (n, s) in [(0i64, 0u64), (1, 2), (-1, 1), (2, 4), (-2, 3)] {
+                assert_eq!(super::to_noun(&n).expect("i64"), atom(s));
+            }
+            assert_eq!(
+                super::to_noun(&i8::MIN).expect("i8"),
+                atom(u8::MAX),
+                "the most negative i8 is the largest 8-bit @s"
+            );
+            assert_eq!(super::to_noun(&i128::MIN).expect("i128"), atom(u128::MAX));
+            assert_eq!(super::to_noun(&1.5f32).expect("f32"), atom(0x3fc0_0000u32));
+            assert_eq!(super::to_noun(&'~').expect("char"), atom("~"));
+            assert_eq!(super::to_noun(&()).expect("unit"), Noun::null());
+            assert_eq!(super::to_noun(&Empty).expect("unit struct"), Noun::null());
+            assert_eq!(super::to_noun(&Id(7)).expect("newtype"), atom(7u8));
+        }
+
+        // A realistic nested struct.
+        {
+            let channel = Channel {
+                id: Id(3),
+                ship: String::from("~zod"),
+                last_event: Some(41),
+                actions: vec![
+                    Action::Poke {
+                        app: String::from("hood"),
+                        mark: Mark::Json,
+                        data: vec![1, 2],
+                    },
+                    Action::Request(
+                        9,
+                        vec![Header {
+                            key: String::from("host"),
+                            value: String::from("localhost"),
+                        }],
+                    ),
+                    Action::Poke {
+                        app: String::from("chat"),
+                        mark: Mark::Custom(String::from("chat-action")),
+                        data: vec![],
+                    },
+                    Action::Heartbeat,
+                ],
+                subscriptions: BTreeMap::from([
+                    (String::from("/a"), (1, true)),
+                    (String::from("/b"), (2, false)),
+                ]),
+                delta: -5,
+                ratio: 0.5,
+                key: vec![0xff, 0x01],
+                empty: Empty,
+            };
+            let expected = cell([
+                atom(3u8),
+                atom("~zod"),
+                cell([Noun::null(), atom(41u8)]),
+                cell([
+                    cell([
+                        atom("poke"),
+                        atom("hood"),
+                        atom("json"),
+                        cell([atom(1u8), atom(2u8), Noun::null()]),
+                    ]),
+                    cell([
+                        atom("request"),
+                        atom(9u8),
+                        cell([atom("host"), atom("localhost")]),
+                        Noun::null(),
+                    ]),
+                    cell([
+                        atom("poke"),
+                        atom("chat"),
+                        cell([atom("custom"), atom("chat-action")]),
+                        Noun::null(),
+                    ]),
+                    atom("heartbeat"),
+                    Noun::null(),
+                ]),
+                cell([
+                    cell([atom("/a"), atom(1u8), atom(0u8)]),
+                    cell([atom("/b"), atom(2u8), atom(1u8)]),
+                    Noun::null(),
+                ]),
+                atom(9u8),
+                atom(0.5f64.to_bits()),
+                atom(0x01ffu16),
+                Noun::null(),
+            ]);
+            assert_eq!(super::to_noun(&channel).expect("channel"), expected);
+        }
+
+        // Errors from Serialize impls are passed through.
+        {
+            struct Failing;
+
+            impl Serialize for Failing {
+                fn serialize<S: ::serde::Serializer>(&self, _: S) -> Result<S::Ok, S::Error> {
+                    Err(ser::Error::custom("failed"))
+                }
+            }
+
+            match super::to_noun(&vec![Some(Failing)]) {
+                Err(Error::Custom(msg)) => assert_eq!(msg, "failed"),
+                res => panic!("unexpected result: {:?}", res),
+            }
+        }
+    }
+}
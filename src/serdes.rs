@@ -40,13 +40,15 @@
 //! bitstream appears again during encoding. A backreference is simply an index into a prior part
 //! of the bitstream at which the encoding of the first occurence of the noun in question was
 //! encoded. A backreference is encoded just like an atom, except the atom tag `0b0` is replaced
-//! with the backreference tag `0b11`. However, if the noun in question is an atom and the encoded
-//! atom requires fewer bits than the corresponding backreference, then the atom is encoded into the
-//! bitstream rather than the backreference.
+//! with the backreference tag `0b11`. However, if the noun in question is an atom and the atom is
+//! no longer (in bits) than the index the backreference would encode, then the atom is encoded into
+//! the bitstream rather than the backreference. This compares the payloads only, not the full
+//! encodings with their tags and lengths, which is exactly what Hoon's `++jam` and the runtime do;
+//! every implementation has to make the same choice for jammed nouns to be byte-identical.
 //!
 //! The cell `[1 1]`, for example, does not have any backreferences in its encoding because `1`
-//! requires fewer bits to encode than the backreference that would replace the second occurence of
-//! `1` in the bitstream. The cell `[10_000 10_000]`, which does have a backreference in its
+//! is shorter than the index `2` that a backreference replacing the second occurence of `1` in the
+//! bitstream would encode. The cell `[10_000 10_000]`, which does have a backreference in its
 //! encoding, serializes ("jams") to `4_952_983_169`, or `0b100100111001110001000011010000001`,
 //! which breaks down as follows (notice how `tail` is a backreference, which decodes into the
 //! index `2`, which is the start of the encoding of the head):
@@ -65,39 +67,476 @@
 //! [Jam]: https://developers.urbit.org/reference/hoon/stdlib/2p#jam
 //! [Cue]: https://developers.urbit.org/reference/hoon/stdlib/2p#cue
 
+#[cfg(feature = "bitstream-io")]
+pub(crate) mod bitio;
+pub(crate) mod borrowed;
+pub(crate) mod cache;
+#[cfg(feature = "compress")]
+pub mod compress;
+pub(crate) mod cue;
+pub(crate) mod jam;
+pub(crate) mod lazy;
+pub mod newt;
+pub(crate) mod trace;
+
+#[cfg(feature = "bitstream-io")]
+pub use bitio::{cue_from_bitreader, jam_into_bitwriter};
+pub use borrowed::{cue_borrowed, AtomRef, CellRef, NounRef};
+pub use cache::CueCache;
+pub use cue::{cue_many, CueMachine, CueStream, Status};
+pub use jam::{jam_many, JamReader};
+pub use lazy::{cue_lazy, LazyNoun};
+
 use crate::{atom::Atom, marker::Nounish};
 use std::{
     fmt::{self, Display, Formatter},
+    io,
+    ops::ControlFlow,
     result,
 };
 
+/// The hash map used by the encoder and decoder.
+#[cfg(feature = "fast-hash")]
+pub(crate) type HashMap<K, V> = rustc_hash::FxHashMap<K, V>;
+/// The hash map used by the encoder and decoder.
+#[cfg(not(feature = "fast-hash"))]
+pub(crate) type HashMap<K, V> = std::collections::HashMap<K, V>;
+
+/// The hash set used by the encoder and decoder.
+#[cfg(feature = "fast-hash")]
+pub(crate) type HashSet<T> = rustc_hash::FxHashSet<T>;
+/// The hash set used by the encoder and decoder.
+#[cfg(not(feature = "fast-hash"))]
+pub(crate) type HashSet<T> = std::collections::HashSet<T>;
+
+/// The hasher used by the encoder to compute structural hashes of nouns.
+#[cfg(feature = "fast-hash")]
+pub(crate) type NounHasher = rustc_hash::FxHasher;
+/// The hasher used by the encoder to compute structural hashes of nouns.
+#[cfg(not(feature = "fast-hash"))]
+pub(crate) type NounHasher = std::collections::hash_map::DefaultHasher;
+
 /// Errors that occur when serializing/deserializing.
+///
+/// Errors caused by a corrupt jammed noun carry the [`Position`] at which decoding failed.
 #[derive(Debug)]
 pub enum Error {
-    /// Building up an atom with [`atom::Builder`](crate::atom::Builder) failed.
-    AtomBuilding,
     /// A key lookup in the cache failed.
-    CacheMiss,
+    CacheMiss(Position),
+    /// A progress callback asked for the operation to stop.
+    Cancelled,
+    /// A compressed jammed noun was malformed.
+    #[cfg(feature = "compress")]
+    Compressed(compress::CompressedError),
     /// A corrupt backreference was encountered.
-    InvalidBackref,
-    /// A corrupt length encoding was encountered.
-    InvalidLen,
-    /// A corrupt tag was encountered.
-    InvalidTag,
+    InvalidBackref(Position),
+    /// A length that's too large to be valid was encountered.
+    InvalidLen(Position),
+    /// Reading from an [`io::Read`] source failed.
+    Io(io::Error),
+    /// Decoding the noun would exceed one of the [`CueLimits`], or encoding it would exceed the
+    /// cap of [`Noun::jam_capped()`](crate::Noun::jam_capped()).
+    LimitExceeded {
+        /// The limit that would be exceeded.
+        which: Limit,
+        /// The value of the limit that would be exceeded.
+        limit: u64,
+    },
+    /// The jammed noun decodes successfully but isn't the encoding that jamming the noun would
+    /// produce.
+    NonCanonical {
+        /// Why the encoding isn't canonical.
+        reason: NonCanonicalReason,
+        /// The offset in bits, from the start of the jammed noun, of the start of the offending
+        /// noun, or of the first bit following the jammed noun for
+        /// [`NonCanonicalReason::TrailingData`].
+        bit_pos: u64,
+    },
+    /// Set bits follow the end of the jammed noun.
+    TrailingData {
+        /// The number of bits following the end of the jammed noun, up to and including the last
+        /// set bit.
+        bits: u64,
+    },
+    /// The jammed noun ended before the noun was completely decoded.
+    UnexpectedEof(Position),
+}
+
+impl Error {
+    /// Returns the position at which decoding failed if this error was caused by a corrupt jammed
+    /// noun.
+    pub fn position(&self) -> Option<Position> {
+        match self {
+            Self::CacheMiss(pos)
+            | Self::InvalidBackref(pos)
+            | Self::InvalidLen(pos)
+            | Self::UnexpectedEof(pos) => Some(*pos),
+            #[cfg(feature = "compress")]
+            Self::Compressed(_) => None,
+            Self::Cancelled
+            | Self::Io(_)
+            | Self::LimitExceeded { .. }
+            | Self::NonCanonical { .. }
+            | Self::TrailingData { .. } => None,
+        }
+    }
 }
 
 impl Display for Error {
     fn fmt(&self, f: &mut Formatter<'_>) -> result::Result<(), fmt::Error> {
         match self {
-            Self::AtomBuilding => write!(f, "building an atom a bit at a time failed"),
-            Self::CacheMiss => write!(
+            Self::CacheMiss(pos) => write!(
                 f,
-                "a key that was expected to be in the cache was missing from the cache"
+                "a key that was expected to be in the cache was missing from the cache {}",
+                pos
             ),
-            Self::InvalidBackref => write!(f, "encountered an invalid backreference"),
-            Self::InvalidLen => write!(f, "encountered an invalid length"),
-            Self::InvalidTag => write!(f, "encountered an invalid tag"),
+            Self::Cancelled => write!(f, "the operation was cancelled"),
+            #[cfg(feature = "compress")]
+            Self::Compressed(err) => write!(f, "the compressed jammed noun is malformed: {}", err),
+            Self::InvalidBackref(pos) => write!(f, "encountered an invalid backreference {}", pos),
+            Self::InvalidLen(pos) => write!(f, "encountered an invalid length {}", pos),
+            Self::Io(err) => write!(f, "reading the jammed noun failed: {}", err),
+            Self::LimitExceeded { which, limit } => {
+                write!(f, "the {} limit of {} was exceeded", which, limit)
+            }
+            Self::NonCanonical { reason, bit_pos } => {
+                write!(
+                    f,
+                    "the jammed noun isn't canonical at bit {}: {}",
+                    bit_pos, reason
+                )
+            }
+            Self::TrailingData { bits } => {
+                write!(
+                    f,
+                    "found {} bits of trailing data after the jammed noun",
+                    bits
+                )
+            }
+            Self::UnexpectedEof(pos) => write!(f, "the jammed noun ended unexpectedly {}", pos),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            #[cfg(feature = "compress")]
+            Self::Compressed(err) => Some(err),
+            Self::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// The location within a jammed noun at which decoding failed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Position {
+    /// The offset in bits, from the start of the jammed noun, of the start of the item that could
+    /// not be decoded.
+    pub bit: u64,
+    /// The kind of item that could not be decoded.
+    pub item: Item,
+    /// The innermost cell enclosing the item, or `None` if the item is part of the top level noun.
+    pub cell: Option<EnclosingCell>,
+}
+
+impl Display for Position {
+    fn fmt(&self, f: &mut Formatter<'_>) -> result::Result<(), fmt::Error> {
+        write!(f, "at bit {} while decoding {}", self.bit, self.item)?;
+        if let Some(cell) = self.cell {
+            write!(f, " in the {} of the cell at bit {}", cell.side, cell.bit)?;
+        }
+        Ok(())
+    }
+}
+
+/// A kind of item in a jammed noun.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Item {
+    /// The bits of an atom.
+    Atom,
+    /// The encoded length of an atom.
+    AtomLen,
+    /// The index of a backreference.
+    BackrefIdx,
+    /// The encoded length of the index of a backreference.
+    BackrefLen,
+    /// The tag of an atom, cell, or backreference.
+    Tag,
+}
+
+impl Display for Item {
+    fn fmt(&self, f: &mut Formatter<'_>) -> result::Result<(), fmt::Error> {
+        match self {
+            Self::Atom => write!(f, "an atom"),
+            Self::AtomLen => write!(f, "the length of an atom"),
+            Self::BackrefIdx => write!(f, "a backreference"),
+            Self::BackrefLen => write!(f, "the length of a backreference"),
+            Self::Tag => write!(f, "a tag"),
+        }
+    }
+}
+
+/// The cell enclosing an item in a jammed noun.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct EnclosingCell {
+    /// The offset in bits, from the start of the jammed noun, of the start of the cell.
+    pub bit: u64,
+    /// The side of the cell that contains the item.
+    pub side: Side,
+}
+
+/// A side of a cell.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Side {
+    /// The head of a cell.
+    Head,
+    /// The tail of a cell.
+    Tail,
+}
+
+impl Display for Side {
+    fn fmt(&self, f: &mut Formatter<'_>) -> result::Result<(), fmt::Error> {
+        match self {
+            Self::Head => write!(f, "head"),
+            Self::Tail => write!(f, "tail"),
+        }
+    }
+}
+
+/// A resource bounded by [`CueLimits`] or by [`Noun::jam_capped()`](crate::Noun::jam_capped()).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Limit {
+    /// [`CueLimits::max_atom_bytes`].
+    AtomBytes,
+    /// [`CueLimits::max_depth`].
+    Depth,
+    /// [`CueLimits::max_nodes`].
+    Nodes,
+    /// [`CueLimits::max_total_atom_bytes`].
+    TotalAtomBytes,
+    /// The `max_bits` of [`Noun::jam_capped()`](crate::Noun::jam_capped()).
+    JamBits,
+}
+
+impl Display for Limit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> result::Result<(), fmt::Error> {
+        match self {
+            Self::AtomBytes => write!(f, "atom size"),
+            Self::Depth => write!(f, "depth"),
+            Self::Nodes => write!(f, "node count"),
+            Self::TotalAtomBytes => write!(f, "total atom size"),
+            Self::JamBits => write!(f, "jammed size"),
+        }
+    }
+}
+
+/// A way in which a jammed noun can differ from the encoding that jamming its noun would produce.
+///
+/// See [`Noun::cue_canonical()`](crate::Noun::cue_canonical()).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum NonCanonicalReason {
+    /// A noun is encoded in full even though it occurred earlier and, being a cell or an atom
+    /// longer than the index of its earlier occurrence, should have been encoded as a
+    /// backreference.
+    MissingBackref,
+    /// A backreference refers to an atom whose direct encoding is no longer than the
+    /// backreference, so the atom should have been encoded directly.
+    NeedlessBackref,
+    /// An atom or backreference index is encoded with a length longer than its number of
+    /// significant bits.
+    NonMinimalLen,
+    /// A backreference refers to a later occurrence of a noun instead of its first occurrence.
+    NotFirstOccurrence,
+    /// Set bits follow the end of the jammed noun.
+    TrailingData,
+}
+
+impl Display for NonCanonicalReason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> result::Result<(), fmt::Error> {
+        match self {
+            Self::MissingBackref => write!(f, "a repeated noun isn't a backreference"),
+            Self::NeedlessBackref => write!(f, "a backreference is no shorter than its atom"),
+            Self::NonMinimalLen => write!(f, "a length is longer than necessary"),
+            Self::NotFirstOccurrence => write!(
+                f,
+                "a backreference doesn't refer to the first occurrence of its noun"
+            ),
+            Self::TrailingData => write!(f, "set bits follow the jammed noun"),
+        }
+    }
+}
+
+/// Bounds on the resources that deserializing ("cueing") a single noun may consume.
+///
+/// Because backreferences allow a subnoun to be repeated any number of times at the cost of a few
+/// bits, a jammed noun only a few dozen bytes long can decode to a noun with billions of atoms and
+/// cells. Every limit is therefore measured against the *logical* size of the decoded noun, as if
+/// none of its subnouns were shared, and decoding is aborted with [`Error::LimitExceeded`] as soon
+/// as a limit would be exceeded, before any memory is allocated for the offending atom or cell.
+///
+/// The default limits are unlimited. Individual limits can be set using struct update syntax:
+///
+/// ```
+/// # use noun::serdes::CueLimits;
+/// let limits = CueLimits {
+///     max_nodes: 1_000_000,
+///     max_depth: 10_000,
+///     ..CueLimits::default()
+/// };
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct CueLimits {
+    /// The maximum number of bytes in any single atom.
+    pub max_atom_bytes: u64,
+    /// The maximum number of bytes in all of the atoms of the noun combined.
+    pub max_total_atom_bytes: u64,
+    /// The maximum number of atoms and cells in the noun combined.
+    pub max_nodes: u64,
+    /// The maximum number of cells enclosing any atom of the noun.
+    pub max_depth: u64,
+}
+
+impl CueLimits {
+    /// Limits that never abort decoding.
+    pub const UNLIMITED: Self = Self {
+        max_atom_bytes: u64::MAX,
+        max_total_atom_bytes: u64::MAX,
+        max_nodes: u64::MAX,
+        max_depth: u64::MAX,
+    };
+}
+
+impl Default for CueLimits {
+    fn default() -> Self {
+        Self::UNLIMITED
+    }
+}
+
+/// How often the progress callbacks of [`Noun::cue_with_progress()`] and
+/// [`Noun::jam_with_progress()`] are called.
+///
+/// [`Noun::cue_with_progress()`]: crate::Noun::cue_with_progress()
+/// [`Noun::jam_with_progress()`]: crate::Noun::jam_with_progress()
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ProgressOptions {
+    /// The number of atoms, cells, and backreferences to decode or encode between calls.
+    ///
+    /// Small intervals make progress smoother but spend more time in the callback. An interval of
+    /// `0` is treated as `1`.
+    pub interval: u64,
+}
+
+impl Default for ProgressOptions {
+    fn default() -> Self {
+        Self { interval: 1 << 16 }
+    }
+}
+
+/// The progress of a cue, as reported to the callback of
+/// [`Noun::cue_with_progress()`](crate::Noun::cue_with_progress()).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct CueProgress {
+    /// The number of bits of the jammed noun decoded so far.
+    pub bits_read: u64,
+    /// The length in bits of the jammed noun.
+    pub total_bits: u64,
+    /// The number of atoms and cells decoded so far, counted as in [`CueLimits::max_nodes`].
+    pub nodes: u64,
+}
+
+/// The progress of a jam, as reported to the callback of
+/// [`Noun::jam_with_progress()`](crate::Noun::jam_with_progress()).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct JamProgress {
+    /// The number of bits of the jammed noun written so far.
+    pub bits_written: u64,
+    /// The number of atoms, cells, and backreferences encoded so far.
+    pub nodes: u64,
+}
+
+/// What went into a jammed noun, as returned by [`Noun::jam_with_stats()`].
+///
+/// [`Noun::jam_with_stats()`]: crate::Noun::jam_with_stats()
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize))]
+pub struct JamStats {
+    /// The length in bits of the jammed noun.
+    pub bits: u64,
+    /// The number of atoms encoded in full, including repeated atoms too short to backreference.
+    pub atoms: u64,
+    /// The number of cells encoded in full.
+    pub cells: u64,
+    /// The number of backreferences encoded.
+    pub backrefs: u64,
+    /// The number of bits saved by encoding backreferences instead of the nouns they refer to.
+    ///
+    /// This can be negative: a repeated cell is always backreferenced, even when its encoding is
+    /// shorter than the backreference.
+    pub backref_bits_saved: i64,
+    /// The maximum number of cells enclosing any atom, cell, or backreference.
+    pub max_depth: u64,
+    /// The number of distinct subnouns in the backreference cache once jamming finished.
+    pub cache_entries: u64,
+}
+
+/// What was found in a jammed noun, as returned by [`Noun::cue_with_stats()`].
+///
+/// [`Noun::cue_with_stats()`]: crate::Noun::cue_with_stats()
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize))]
+pub struct CueStats {
+    /// The length in bits of the jammed noun.
+    pub bits: u64,
+    /// The number of atoms decoded from their full encoding.
+    pub atoms: u64,
+    /// The number of cells decoded from their full encoding.
+    pub cells: u64,
+    /// The number of backreferences decoded.
+    pub backrefs: u64,
+    /// The number of bits saved by the backreferences, compared to encoding the nouns they refer
+    /// to in full. As with [`JamStats::backref_bits_saved`], this can be negative.
+    pub backref_bits_saved: i64,
+    /// The maximum number of cells enclosing any atom, cell, or backreference.
+    pub max_depth: u64,
+    /// The number of nouns in the backreference cache once cueing finished.
+    pub cache_entries: u64,
+}
+
+/// Calls a progress callback once every [`ProgressOptions::interval`] steps.
+pub(crate) struct Progress<'a, P> {
+    // Boxing the callback rather than borrowing it keeps this type covariant in `'a`.
+    report: Box<dyn FnMut(P) -> ControlFlow<()> + 'a>,
+    interval: u64,
+    /// The number of steps left until the next call.
+    countdown: u64,
+}
+
+impl<'a, P> Progress<'a, P> {
+    /// Creates a counter that calls `report` as often as `options` asks.
+    pub(crate) fn new(
+        options: &ProgressOptions,
+        report: impl FnMut(P) -> ControlFlow<()> + 'a,
+    ) -> Self {
+        let interval = options.interval.max(1);
+        Self {
+            report: Box::new(report),
+            interval,
+            countdown: interval,
+        }
+    }
+
+    /// Counts a step, calling the callback with the progress computed by `progress` if it's due,
+    /// and returning what the callback returns.
+    #[inline]
+    pub(crate) fn step(&mut self, progress: impl FnOnce() -> P) -> ControlFlow<()> {
+        self.countdown -= 1;
+        if self.countdown > 0 {
+            return ControlFlow::Continue(());
         }
+        self.countdown = self.interval;
+        (self.report)(progress())
     }
 }
 
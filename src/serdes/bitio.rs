@@ -0,0 +1,183 @@
+//! Jamming into and cueing from the bitstreams of the [`bitstream_io`] crate.
+
+use super::{
+    cue::{self, BitSource},
+    jam::{self, BitSink},
+    CueLimits, Error, Result,
+};
+use crate::noun::Noun;
+use bitstream_io::{BitRead, BitWrite};
+use std::io;
+
+/// A [`BitSink`] that writes to a [`BitWrite`], counting the bits written.
+struct BitWriteSink<'a, W: BitWrite + ?Sized> {
+    writer: &'a mut W,
+    bit_idx: u64,
+}
+
+impl<W: BitWrite + ?Sized> BitSink for BitWriteSink<'_, W> {
+    type Error = io::Error;
+
+    fn pos(&self) -> u64 {
+        self.bit_idx
+    }
+
+    fn push_bit(&mut self, bit: bool) -> io::Result<()> {
+        self.writer.write_bit(bit)?;
+        self.bit_idx += 1;
+        Ok(())
+    }
+}
+
+/// A [`BitSource`] that reads from a [`BitRead`], counting the bits read.
+struct BitReadSource<'a, R: BitRead + ?Sized> {
+    reader: &'a mut R,
+    bit_idx: u64,
+}
+
+impl<R: BitRead + ?Sized> BitSource for BitReadSource<'_, R> {
+    fn pos(&self) -> u64 {
+        self.bit_idx
+    }
+
+    fn next_bit(&mut self) -> Result<Option<bool>> {
+        match self.reader.read_bit() {
+            Ok(bit) => {
+                self.bit_idx += 1;
+                Ok(Some(bit))
+            }
+            Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => Ok(None),
+            Err(err) => Err(Error::Io(err)),
+        }
+    }
+}
+
+/// Serializes ("jams") `noun` into `writer`, starting at the current bit position of `writer`,
+/// which doesn't have to be byte-aligned.
+///
+/// Backreferences are relative to the start of the noun, so the bits written are exactly the bits
+/// of [`jam()`](super::Jam::jam()) wherever they're written, and `writer` is left positioned
+/// immediately after the last bit of the noun. Bits are written one at a time in the order they
+/// appear in the jammed noun, so the endianness of `writer` determines how they're packed into
+/// bytes; use [`LittleEndian`](bitstream_io::LittleEndian) for the byte layout of
+/// [`Noun::jam_to_vec()`].
+///
+/// # Examples
+///
+/// ```
+/// # use bitstream_io::{BitWrite, BitWriter, LittleEndian};
+/// # use noun::{cell::Cell, serdes::jam_into_bitwriter, Noun};
+/// let mut writer = BitWriter::endian(Vec::new(), LittleEndian);
+/// writer.write_bit(true).unwrap();
+/// jam_into_bitwriter(&Noun::from(Cell::from([0u8, 19u8])), &mut writer).unwrap();
+/// writer.byte_align().unwrap();
+/// // `[0 19]` jams to `0x9b09`, shifted over by one bit.
+/// assert_eq!(writer.into_writer(), [0x13, 0x36, 0x01]);
+/// ```
+pub fn jam_into_bitwriter<W: BitWrite + ?Sized>(noun: &Noun, writer: &mut W) -> io::Result<()> {
+    jam::encode(noun, &mut BitWriteSink { writer, bit_idx: 0 })
+}
+
+/// Deserializes ("cues") a noun from `reader`, starting at the current bit position of `reader`,
+/// which doesn't have to be byte-aligned.
+///
+/// Backreferences are relative to the start of the noun, and `reader` is left positioned
+/// immediately after the last bit of the noun, so other fields can follow it. Running out of bits
+/// fails with [`Error::UnexpectedEof`], and any other I/O error is returned as [`Error::Io`].
+///
+/// # Examples
+///
+/// ```
+/// # use bitstream_io::{BitRead, BitReader, LittleEndian};
+/// # use noun::{cell::Cell, serdes::cue_from_bitreader, Noun};
+/// let bytes = [0x13, 0x36, 0x01];
+/// let mut reader = BitReader::endian(&bytes[..], LittleEndian);
+/// assert!(reader.read_bit().unwrap());
+/// let noun = cue_from_bitreader(&mut reader).unwrap();
+/// assert_eq!(noun, Noun::from(Cell::from([0u8, 19u8])));
+/// assert!(!reader.byte_aligned());
+/// ```
+pub fn cue_from_bitreader<R: BitRead + ?Sized>(reader: &mut R) -> Result<Noun> {
+    cue::decode(
+        &mut BitReadSource { reader, bit_idx: 0 },
+        &CueLimits::UNLIMITED,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{atom::Atom, cell::Cell, serdes::Jam};
+    use bitstream_io::{BigEndian, BitReader, BitWriter, LittleEndian};
+
+    #[test]
+    fn bitstream() {
+        let nouns = [
+            Noun::from(Cell::from([10_000u16, 10_000u16])),
+            Noun::from(Atom::from(19u8)),
+            Noun::from(Cell::from([
+                Noun::from("sample"),
+                Noun::from(Cell::from(["sample", "sample"])),
+                Noun::null(),
+            ])),
+            Noun::null(),
+        ];
+
+        // Tags of 1 to 7 bits alternate with nouns, so every noun starts at a different
+        // alignment, and the bits of each noun are exactly its jam.
+        {
+            let mut writer = BitWriter::endian(Vec::new(), LittleEndian);
+            let mut expected = Atom::builder();
+            for (i, noun) in nouns.iter().enumerate() {
+                let tag_len = i as u32 % 7 + 1;
+                writer.write(tag_len, 1u8).expect("tag");
+                jam_into_bitwriter(noun, &mut writer).expect("jam");
+                expected.push_bits(1, tag_len);
+                expected.push_atom(&noun.clone().jam());
+            }
+            writer.byte_align().expect("align");
+            let bytes = writer.into_writer();
+            assert_eq!(bytes, expected.into_atom().as_bytes());
+
+            let mut reader = BitReader::endian(&bytes[..], LittleEndian);
+            for (i, noun) in nouns.iter().enumerate() {
+                let tag_len = i as u32 % 7 + 1;
+                assert_eq!(reader.read::<u8>(tag_len).expect("tag"), 1);
+                assert_eq!(cue_from_bitreader(&mut reader).expect("cue"), *noun);
+            }
+            assert_eq!(reader.read_to_vec(1).ok(), None);
+        }
+
+        // Big-endian streams pack the same bits differently, but round-trip all the same.
+        {
+            let mut writer = BitWriter::endian(Vec::new(), BigEndian);
+            for noun in &nouns {
+                writer.write(3, 0b101u8).expect("tag");
+                jam_into_bitwriter(noun, &mut writer).expect("jam");
+            }
+            writer.byte_align().expect("align");
+            let bytes = writer.into_writer();
+            let mut reader = BitReader::endian(&bytes[..], BigEndian);
+            for noun in &nouns {
+                assert_eq!(reader.read::<u8>(3).expect("tag"), 0b101);
+                assert_eq!(cue_from_bitreader(&mut reader).expect("cue"), *noun);
+            }
+        }
+
+        // Running out of bits partway through a noun.
+        {
+            let mut writer = BitWriter::endian(Vec::new(), LittleEndian);
+            writer.write(5, 0u8).expect("tag");
+            jam_into_bitwriter(&nouns[2], &mut writer).expect("jam");
+            writer.byte_align().expect("align");
+            let mut bytes = writer.into_writer();
+            bytes.pop();
+            let mut reader = BitReader::endian(&bytes[..], LittleEndian);
+            reader.skip(5).expect("tag");
+            assert!(matches!(
+                cue_from_bitreader(&mut reader),
+                Err(Error::UnexpectedEof(_))
+            ));
+        }
+    }
+}
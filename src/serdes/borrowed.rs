@@ -0,0 +1,502 @@
+//! Zero-copy deserialization, which decodes atoms that are byte-aligned in a jammed noun as
+//! references into it rather than copies of it.
+
+use super::{
+    cue::{self, BackrefIdx, Tag},
+    EnclosingCell, Error, HashMap, Item, Position, Result, Side,
+};
+use crate::{
+    atom::{self, Atom, Iter as AtomIter},
+    cell::Cell,
+    noun::Noun,
+    Rc,
+};
+use std::str::{self, Utf8Error};
+
+/// The bytes of an atom decoded by [`cue_borrowed()`], either borrowed from the jammed noun or
+/// copied out of it.
+#[derive(Clone, Debug)]
+enum Bytes<'a> {
+    Borrowed(&'a [u8]),
+    Owned(Rc<Atom>),
+}
+
+/// An atom decoded by [`cue_borrowed()`].
+///
+/// Cloning an atom never copies its bytes.
+#[derive(Clone, Debug)]
+pub struct AtomRef<'a>(Bytes<'a>);
+
+impl<'a> AtomRef<'a> {
+    /// Returns `true` if this atom's bytes are borrowed from the jammed noun it was decoded from
+    /// rather than copied out of it.
+    pub fn is_borrowed(&self) -> bool {
+        matches!(self.0, Bytes::Borrowed(_))
+    }
+
+    /// Returns the bytes of this atom, least significant byte first, as [`Atom::as_bytes()`]
+    /// does.
+    pub fn as_bytes(&self) -> &[u8] {
+        match &self.0 {
+            Bytes::Borrowed(bytes) => bytes,
+            Bytes::Owned(atom) => atom.as_bytes(),
+        }
+    }
+
+    /// Returns the length in bits of this atom.
+    pub fn bit_len(&self) -> u64 {
+        atom::bit_len(self.as_bytes())
+    }
+
+    /// Returns `true` if this atom is null.
+    pub fn is_null(&self) -> bool {
+        self.as_bytes().is_empty()
+    }
+
+    /// Converts this atom into a `u64`, returning `None` if it's too large, as
+    /// [`Atom::as_u64()`] does.
+    pub fn as_u64(&self) -> Option<u64> {
+        let bytes = self.as_bytes();
+        let mut buf = [0; 8];
+        buf.get_mut(..bytes.len())?.copy_from_slice(bytes);
+        Some(u64::from_le_bytes(buf))
+    }
+
+    /// Converts this atom into a string slice, failing if it isn't UTF-8, as [`Atom::as_str()`]
+    /// does.
+    pub fn as_str(&self) -> std::result::Result<&str, Utf8Error> {
+        str::from_utf8(self.as_bytes())
+    }
+
+    /// Copies this atom into an [`Atom`].
+    pub fn to_atom(&self) -> Atom {
+        match &self.0 {
+            Bytes::Borrowed(bytes) => Atom::from(bytes.to_vec()),
+            Bytes::Owned(atom) => (**atom).clone(),
+        }
+    }
+}
+
+/// A cell decoded by [`cue_borrowed()`].
+///
+/// Cloning a cell never copies its head or tail.
+#[derive(Clone, Debug)]
+pub struct CellRef<'a>(Rc<[NounRef<'a>; 2]>);
+
+impl<'a> CellRef<'a> {
+    /// Returns the head of this cell.
+    pub fn head(&self) -> &NounRef<'a> {
+        &self.0[0]
+    }
+
+    /// Returns the tail of this cell.
+    pub fn tail(&self) -> &NounRef<'a> {
+        &self.0[1]
+    }
+}
+
+/// A noun decoded by [`cue_borrowed()`], whose atoms may borrow the bytes of the jammed noun it
+/// was decoded from.
+///
+/// Subnouns that are shared by means of backreferences in the jammed noun are shared here too.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{atom::Atom, cell::Cell, serdes::{cue_borrowed, NounRef}, Noun};
+/// let noun = Noun::from(Cell::from([
+///     Noun::from(Atom::from(2u8)),
+///     Noun::from(Atom::from(vec![0xab; 40_000])),
+/// ]));
+/// let jammed = noun.jam_to_vec();
+/// let noun_ref = cue_borrowed(&jammed).unwrap();
+///
+/// let big = noun_ref.get(3).and_then(NounRef::as_atom).unwrap();
+/// assert!(big.is_borrowed());
+/// assert_eq!(big.as_bytes(), &[0xab; 40_000][..]);
+/// assert_eq!(noun_ref.to_owned(), noun);
+/// ```
+#[derive(Clone, Debug)]
+pub enum NounRef<'a> {
+    /// An atom.
+    Atom(AtomRef<'a>),
+    /// A cell.
+    Cell(CellRef<'a>),
+}
+
+impl<'a> NounRef<'a> {
+    /// Returns `true` if this noun is an atom.
+    pub fn is_atom(&self) -> bool {
+        matches!(self, Self::Atom(_))
+    }
+
+    /// Returns `true` if this noun is a cell.
+    pub fn is_cell(&self) -> bool {
+        matches!(self, Self::Cell(_))
+    }
+
+    /// Returns `true` if this noun is a null atom.
+    pub fn is_null(&self) -> bool {
+        matches!(self, Self::Atom(atom) if atom.is_null())
+    }
+
+    /// Returns this noun as an atom, or `None` if it's a cell.
+    pub fn as_atom(&self) -> Option<&AtomRef<'a>> {
+        match self {
+            Self::Atom(atom) => Some(atom),
+            Self::Cell(_) => None,
+        }
+    }
+
+    /// Returns this noun as a cell, or `None` if it's an atom.
+    pub fn as_cell(&self) -> Option<&CellRef<'a>> {
+        match self {
+            Self::Atom(_) => None,
+            Self::Cell(cell) => Some(cell),
+        }
+    }
+
+    /// Retrieves the subnoun at tree address `axis`.
+    ///
+    /// See [`Noun::get()`].
+    pub fn get(&self, axis: u64) -> Option<&Self> {
+        if axis == 0 {
+            return None;
+        }
+        let mut noun = self;
+        // The most significant bit of the axis is implicit because it's always 1.
+        for i in (0..u64::BITS - 1 - axis.leading_zeros()).rev() {
+            match noun {
+                Self::Atom(_) => return None,
+                Self::Cell(cell) if axis & (1 << i) == 0 => noun = cell.head(),
+                Self::Cell(cell) => noun = cell.tail(),
+            }
+        }
+        Some(noun)
+    }
+
+    /// Copies this noun into a [`Noun`] that doesn't borrow the jammed noun.
+    ///
+    /// Cells that are shared in this noun are shared in the returned noun too.
+    pub fn to_owned(&self) -> Noun {
+        // Every cell copied so far, keyed by address.
+        let mut copied: HashMap<*const [NounRef; 2], Rc<Noun>> = HashMap::default();
+        let copy_atom = |atom: &AtomRef| Rc::new(Noun::from(atom.to_atom()));
+        let root = match self {
+            Self::Atom(atom) => return Noun::from(atom.to_atom()),
+            Self::Cell(cell) => cell,
+        };
+        // Cells to copy, along with whether their head and tail have been copied.
+        let mut stack = vec![(root, false)];
+        while let Some((cell, children_copied)) = stack.pop() {
+            let key = Rc::as_ptr(&cell.0);
+            if copied.contains_key(&key) {
+                continue;
+            }
+            if children_copied {
+                let [head, tail] = [cell.head(), cell.tail()].map(|noun| match noun {
+                    Self::Atom(atom) => copy_atom(atom),
+                    Self::Cell(cell) => copied[&Rc::as_ptr(&cell.0)].clone(),
+                });
+                copied.insert(key, Rc::new(Noun::from(Cell::from([head, tail]))));
+            } else {
+                stack.push((cell, true));
+                for child in [cell.tail(), cell.head()] {
+                    if let Self::Cell(child) = child {
+                        stack.push((child, false));
+                    }
+                }
+            }
+        }
+        let noun = copied.remove(&Rc::as_ptr(&root.0)).expect("copied");
+        // Dropping every other noun guarantees that this noun has exactly one reference, so this
+        // never clones.
+        drop(copied);
+        Rc::try_unwrap(noun).unwrap_or_else(|noun| (*noun).clone())
+    }
+}
+
+/// A cell whose head or tail is being decoded.
+struct Frame<'a> {
+    /// Position of the cell.
+    pos: u64,
+    /// Index of the cell's entry in the cache.
+    slot: usize,
+    /// The decoded head of the cell, or `None` if the head is being decoded.
+    head: Option<NounRef<'a>>,
+}
+
+/// State shared by every step of decoding a jammed noun.
+struct Decoder<'a> {
+    /// The jammed noun without any trailing zero bytes.
+    bytes: &'a [u8],
+    bits: AtomIter<'a>,
+    /// Every noun decoded so far in order of position, with cells reserved as soon as their tag
+    /// is decoded and filled in once their tail is.
+    cache: Vec<(u64, Option<NounRef<'a>>)>,
+}
+
+impl<'a> Decoder<'a> {
+    /// Decodes the atom whose length has just been decoded as `len` within `cell`, borrowing its
+    /// bytes if it starts on a byte boundary and doesn't share its last byte with what follows
+    /// it.
+    fn decode_atom(&mut self, len: u64, cell: Option<EnclosingCell>) -> Result<AtomRef<'a>> {
+        let start = self.bits.pos_u64();
+        if len > self.bits.remaining() {
+            return Err(Error::UnexpectedEof(Position {
+                bit: start,
+                item: Item::Atom,
+                cell,
+            }));
+        }
+        let end = start + len;
+        let bytes = &self.bytes[(start / 8) as usize..end.div_ceil(8) as usize];
+        let unshared = match (end % 8, bytes.last()) {
+            (0, _) | (_, None) => true,
+            (shift, Some(last)) => last >> shift == 0,
+        };
+        if start.is_multiple_of(8) && unshared {
+            self.bits.seek(end);
+            // A length that isn't minimal leaves zero bytes at the end.
+            let len = bytes
+                .iter()
+                .rposition(|byte| *byte != 0)
+                .map_or(0, |idx| idx + 1);
+            Ok(AtomRef(Bytes::Borrowed(&bytes[..len])))
+        } else {
+            let atom = self.bits.read_bits_u64(len).expect("enough bits");
+            Ok(AtomRef(Bytes::Owned(Rc::new(atom))))
+        }
+    }
+
+    /// Decodes the backreference whose tag has just been decoded within `cell`.
+    fn decode_backref(&mut self, cell: Option<EnclosingCell>) -> Result<NounRef<'a>> {
+        let BackrefIdx {
+            idx, pos: idx_pos, ..
+        } = cue::decode_backref_idx(&mut self.bits, 0, cell)?;
+        let slot = self
+            .cache
+            .binary_search_by_key(&idx, |(pos, _)| *pos)
+            .map_err(|_| Error::CacheMiss(idx_pos))?;
+        self.cache[slot].1.clone().ok_or(Error::CacheMiss(idx_pos))
+    }
+
+    /// Decodes a noun with an explicit stack rather than recursively, so that arbitrarily deep
+    /// nouns can be decoded without overflowing the call stack.
+    fn decode_noun(&mut self) -> Result<NounRef<'a>> {
+        let mut stack: Vec<Frame<'a>> = Vec::new();
+        loop {
+            let cell = stack.last().map(|frame| EnclosingCell {
+                bit: frame.pos,
+                side: if frame.head.is_none() {
+                    Side::Head
+                } else {
+                    Side::Tail
+                },
+            });
+            let pos = self.bits.pos_u64();
+            let mut decoded = match cue::decode_tag(&mut self.bits, 0, cell)? {
+                Tag::Backref => self.decode_backref(cell)?,
+                Tag::Cell => {
+                    self.cache.push((pos, None));
+                    stack.push(Frame {
+                        pos,
+                        slot: self.cache.len() - 1,
+                        head: None,
+                    });
+                    continue;
+                }
+                Tag::Atom => {
+                    let len = cue::decode_len(&mut self.bits, 0, Item::AtomLen, cell)?;
+                    let atom = NounRef::Atom(self.decode_atom(len, cell)?);
+                    self.cache.push((pos, Some(atom.clone())));
+                    atom
+                }
+            };
+
+            // Complete every cell whose tail was just decoded.
+            loop {
+                match stack.pop() {
+                    None => return Ok(decoded),
+                    Some(Frame {
+                        pos,
+                        slot,
+                        head: None,
+                    }) => {
+                        stack.push(Frame {
+                            pos,
+                            slot,
+                            head: Some(decoded),
+                        });
+                        break;
+                    }
+                    Some(Frame {
+                        slot,
+                        head: Some(head),
+                        ..
+                    }) => {
+                        decoded = NounRef::Cell(CellRef(Rc::new([head, decoded])));
+                        self.cache[slot].1 = Some(decoded.clone());
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Deserializes ("cues") a noun from its little-endian bytes without copying its atoms where
+/// possible.
+///
+/// An atom whose bits start on a byte boundary of `bytes`, and whose last byte holds nothing that
+/// follows it, borrows its bytes from `bytes`; any other atom is copied out of it. Otherwise this
+/// decodes exactly like [`Noun::cue_bytes()`], which it fails just like: trailing zero bytes are
+/// ignored, but any set bit following the jammed noun is an error.
+///
+/// Whether an atom is byte-aligned depends on everything encoded before it, so this is only a
+/// saving for a jammed noun whose large atoms happen to land on byte boundaries; the rest cost
+/// what they would to cue normally.
+#[doc(alias("deserialize", "deserialization"))]
+pub fn cue_borrowed(bytes: &[u8]) -> Result<NounRef<'_>> {
+    let len = bytes
+        .iter()
+        .rposition(|byte| *byte != 0)
+        .map_or(0, |idx| idx + 1);
+    let bytes = &bytes[..len];
+    let mut decoder = Decoder {
+        bytes,
+        bits: AtomIter::from_bytes(bytes),
+        cache: Vec::new(),
+    };
+    let noun = decoder.decode_noun()?;
+    // The last bit of an atom is always set, so any bits left over must include a set bit.
+    match decoder.bits.remaining() {
+        0 => Ok(noun),
+        len => Err(Error::TrailingData { bits: len }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{atom::Atom, cell::Cell};
+
+    /// Counts the atoms of `noun` that borrow their bytes.
+    fn borrowed_atoms(noun: &NounRef) -> usize {
+        let mut count = 0;
+        let mut stack = vec![noun];
+        while let Some(noun) = stack.pop() {
+            match noun {
+                NounRef::Atom(atom) => count += usize::from(atom.is_borrowed()),
+                NounRef::Cell(cell) => stack.extend([cell.tail(), cell.head()]),
+            }
+        }
+        count
+    }
+
+    #[test]
+    fn cue_borrowed() {
+        // Every noun cues to exactly what it does normally, whether or not any of its atoms are
+        // borrowed.
+        {
+            let shared = Noun::from(Cell::from([10_000u16, 10_000u16]));
+            let nouns = [
+                Noun::null(),
+                Noun::from(19u8),
+                Noun::from(Atom::from(vec![0xff; 1000])),
+                Noun::from(Cell::from([0u8, 19u8])),
+                Noun::from(Cell::from([shared.clone(), shared.clone(), shared])),
+                Noun::from(Cell::from(["poke", "wire", "path", ""])),
+                Noun::from(Cell::from([
+                    Noun::from(Atom::from(2u8)),
+                    Noun::from(Atom::from(vec![0xab; 40_000])),
+                ])),
+                Noun::from(Cell::from([
+                    Noun::from(Atom::from(vec![0x80; 300])),
+                    Noun::from(Atom::from(vec![0x80; 300])),
+                    Noun::from(Atom::from(vec![0x01; 3000])),
+                ])),
+            ];
+            for noun in nouns {
+                let jammed = noun.jam_to_vec();
+                let noun_ref = super::cue_borrowed(&jammed).expect("cue");
+                assert_eq!(noun_ref.to_owned(), noun);
+                assert_eq!(noun_ref.to_owned(), Noun::cue_bytes(&jammed).expect("cue"));
+            }
+        }
+
+        // A large atom that starts on a byte boundary and fills its last byte is borrowed, and the
+        // small atom before it is copied.
+        {
+            let big = vec![0xab; 40_000];
+            let noun = Noun::from(Cell::from([
+                Noun::from(Atom::from(2u8)),
+                Noun::from(Atom::from(big.clone())),
+            ]));
+            let jammed = noun.jam_to_vec();
+            let noun_ref = super::cue_borrowed(&jammed).expect("cue");
+            assert!(noun_ref.is_cell());
+            assert_eq!(borrowed_atoms(&noun_ref), 1);
+            let head = noun_ref.get(2).and_then(NounRef::as_atom).expect("head");
+            assert!(!head.is_borrowed());
+            assert_eq!(head.as_u64(), Some(2));
+            let tail = noun_ref.get(3).and_then(NounRef::as_atom).expect("tail");
+            assert!(tail.is_borrowed());
+            assert_eq!(tail.as_bytes(), big);
+            assert_eq!(tail.as_bytes().as_ptr(), jammed[6..].as_ptr());
+            assert_eq!(tail.bit_len(), 320_000);
+            assert_eq!(tail.as_u64(), None);
+            assert_eq!(tail.to_atom(), Atom::from(big));
+            assert!(noun_ref.get(6).is_none());
+        }
+
+        // Backreferences share what they refer to.
+        {
+            let shared = Noun::from(Cell::from(["event", "data"]));
+            let noun = Noun::from(Cell::from([shared.clone(), shared]));
+            let jammed = noun.jam_to_vec();
+            let noun_ref = super::cue_borrowed(&jammed).expect("cue");
+            let [head, tail] = [2, 3].map(|axis| noun_ref.get(axis).and_then(NounRef::as_cell));
+            assert!(Rc::ptr_eq(&head.expect("head").0, &tail.expect("tail").0));
+            assert_eq!(
+                noun_ref
+                    .get(7)
+                    .and_then(NounRef::as_atom)
+                    .map(AtomRef::as_str),
+                Some(Ok("data"))
+            );
+            let owned = noun_ref.to_owned();
+            let (Noun::Cell(head), Noun::Cell(tail)) =
+                (owned.get(2).unwrap(), owned.get(3).unwrap())
+            else {
+                panic!("cells");
+            };
+            assert_eq!(head.head(), tail.head());
+            assert_eq!(owned, noun);
+        }
+
+        // Trailing zero bytes are ignored, and null is an atom without any bytes.
+        {
+            let noun_ref = super::cue_borrowed(&[0x02, 0x00, 0x00]).expect("cue");
+            assert!(noun_ref.is_null());
+            assert!(noun_ref.is_atom());
+            assert_eq!(noun_ref.as_atom().map(AtomRef::bit_len), Some(0));
+            assert_eq!(noun_ref.to_owned(), Noun::null());
+        }
+
+        // Invalid jammed nouns fail just like they do when cued normally.
+        for jammed_noun in [
+            Atom::from(0u8),
+            Atom::from(0b1_0011_1010_0001u16),
+            Atom::from(0xff_9b09u32),
+            Atom::from(0b1111u8),
+            Atom::from(0b0111_1101u8),
+            Atom::from(0b0111_0101u8),
+            Atom::from(vec![0; 12].into_iter().chain([1]).collect::<Vec<_>>()),
+            Atom::from(0x1_9b09u32),
+        ] {
+            let expected = Noun::cue_bytes(jammed_noun.as_bytes()).expect_err("cue");
+            let err = super::cue_borrowed(jammed_noun.as_bytes()).expect_err("cue borrowed");
+            assert_eq!(err.to_string(), expected.to_string());
+        }
+    }
+}
@@ -0,0 +1,275 @@
+//! A table of decoded nouns shared between cues.
+
+use super::HashMap;
+use crate::{atom::Atom, cell::Cell, noun::Noun, Rc};
+use std::{
+    collections::{hash_map::DefaultHasher, BTreeMap},
+    hash::Hasher,
+    ptr,
+};
+
+#[cfg(test)]
+thread_local! {
+    /// Number of nouns allocated by [`CueCache`]s on the current thread.
+    static ALLOCATED: std::cell::Cell<u64> = const { std::cell::Cell::new(0) };
+}
+
+/// A size-bounded table of previously decoded nouns that lets separate cues share structurally
+/// equal subnouns.
+///
+/// Every atom and cell decoded by [`Noun::cue_with_cache()`] is looked up in the table by its
+/// [hash](Noun::hash()). If a structurally equal noun is already in the table, the existing
+/// [`Rc<Noun>`](crate::Rc) is reused instead of allocating a new one; hash collisions are detected
+/// by comparing the nouns, so a collision only costs a missed opportunity to share. When the table
+/// is full, the least recently used noun is evicted.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{cell::Cell, serdes::{CueCache, Jam}, Noun, Rc};
+/// let mut cache = CueCache::new(1024);
+/// let a = Noun::cue_with_cache(&mut cache, Noun::from(Cell::from([1u8, 2u8, 3u8])).jam()).unwrap();
+/// let b = Noun::cue_with_cache(&mut cache, Noun::from(Cell::from([0u8, 2u8, 3u8])).jam()).unwrap();
+/// // The two nouns share their common tail.
+/// let (Noun::Cell(a), Noun::Cell(b)) = (&*a, &*b) else { unreachable!() };
+/// assert!(Rc::ptr_eq(&a.tail(), &b.tail()));
+/// ```
+pub struct CueCache {
+    /// Every noun in the table along with the tick at which it was last used, keyed by hash.
+    entries: HashMap<u64, (Rc<Noun>, u64)>,
+    /// The hash of every noun in the table, keyed by the tick at which it was last used.
+    lru: BTreeMap<u64, u64>,
+    /// The number of nouns looked up so far, which orders uses.
+    tick: u64,
+    /// The maximum number of nouns in the table.
+    capacity: usize,
+}
+
+impl CueCache {
+    /// Creates an empty table that holds at most `capacity` nouns.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: HashMap::default(),
+            lru: BTreeMap::new(),
+            tick: 0,
+            capacity,
+        }
+    }
+
+    /// Returns the maximum number of nouns in this table.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of nouns in this table.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if this table holds no nouns.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Removes every noun from this table.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.lru.clear();
+    }
+
+    /// Returns the shared atom equal to `atom`, adding `atom` to this table if there isn't one.
+    pub(crate) fn atom(&mut self, atom: Atom, hash: u64) -> Rc<Noun> {
+        let hit = self.get(hash, |noun| match noun {
+            Noun::Atom(shared) => *shared == atom,
+            Noun::Cell(_) => false,
+        });
+        hit.unwrap_or_else(|| self.insert(hash, Rc::<Noun>::from(atom)))
+    }
+
+    /// Returns the shared cell equal to `[head tail]`, adding it to this table if there isn't
+    /// one.
+    pub(crate) fn cell(&mut self, head: Rc<Noun>, tail: Rc<Noun>, hash: u64) -> Rc<Noun> {
+        let hit = self.get(hash, |noun| match noun {
+            Noun::Atom(_) => false,
+            // The head and tail are usually shared too, in which case comparing them is cheap.
+            Noun::Cell(shared) => {
+                (ptr::eq(shared.head_ref(), &*head) || *shared.head_ref() == *head)
+                    && (ptr::eq(shared.tail_ref(), &*tail) || *shared.tail_ref() == *tail)
+            }
+        });
+        hit.unwrap_or_else(|| self.insert(hash, Rc::<Noun>::from(Cell::from([head, tail]))))
+    }
+
+    /// Returns the noun with hash `hash` if it satisfies `eq`, marking it as the most recently
+    /// used noun.
+    fn get(&mut self, hash: u64, eq: impl FnOnce(&Noun) -> bool) -> Option<Rc<Noun>> {
+        self.tick += 1;
+        let (noun, tick) = self.entries.get_mut(&hash)?;
+        if !eq(noun) {
+            return None;
+        }
+        self.lru.remove(tick);
+        self.lru.insert(self.tick, hash);
+        *tick = self.tick;
+        Some(noun.clone())
+    }
+
+    /// Adds `noun` with hash `hash` to this table, replacing any colliding noun and evicting the
+    /// least recently used noun if this table is full.
+    fn insert(&mut self, hash: u64, noun: Rc<Noun>) -> Rc<Noun> {
+        #[cfg(test)]
+        ALLOCATED.with(|allocated| allocated.set(allocated.get() + 1));
+        if self.capacity == 0 {
+            return noun;
+        }
+        if let Some((_, tick)) = self.entries.insert(hash, (noun.clone(), self.tick)) {
+            self.lru.remove(&tick);
+        } else if self.entries.len() > self.capacity {
+            if let Some((_, evicted)) = self.lru.pop_first() {
+                self.entries.remove(&evicted);
+            }
+        }
+        self.lru.insert(self.tick, hash);
+        noun
+    }
+}
+
+/// Returns the hash of a cell whose head and tail have hashes `head` and `tail`, which is the
+/// same as [`Cell::hash()`].
+pub(crate) fn cell_hash(head: u64, tail: u64) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    hasher.write_u64(head);
+    hasher.write_u64(tail);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::serdes::Jam;
+
+    fn allocated() -> u64 {
+        ALLOCATED.with(|allocated| allocated.get())
+    }
+
+    /// Returns an event `[id payload]` whose payload is shared by every event.
+    fn event(id: u32, payload: &Noun) -> Noun {
+        Noun::from(Cell::from([
+            Rc::new(Noun::from(Atom::from(id))),
+            Rc::new(payload.clone()),
+        ]))
+    }
+
+    #[test]
+    fn cue_with_cache() {
+        // A payload with 255 cells and 256 distinct atoms.
+        let payload = (1..256u32).fold(Noun::from(Atom::from(0u8)), |tail, i| {
+            Noun::from(Cell::from([
+                Rc::new(Noun::from(Atom::from(i << 8))),
+                Rc::new(tail),
+            ]))
+        });
+
+        // Overlapping nouns share their common subnouns, and only the differing nouns are
+        // allocated.
+        {
+            let mut cache = CueCache::new(1 << 16);
+            let before = allocated();
+            let a = Noun::cue_with_cache(&mut cache, event(1, &payload).jam()).expect("cue");
+            assert_eq!(*a, event(1, &payload));
+            assert_eq!(allocated() - before, 2 + 511);
+            assert_eq!(cache.len(), 2 + 511);
+
+            let before = allocated();
+            let b = Noun::cue_with_cache(&mut cache, event(2, &payload).jam()).expect("cue");
+            assert_eq!(*b, event(2, &payload));
+            assert_eq!(allocated() - before, 2);
+
+            let (Noun::Cell(a), Noun::Cell(b)) = (&*a, &*b) else {
+                panic!("events are cells");
+            };
+            assert!(Rc::ptr_eq(&a.tail(), &b.tail()));
+            assert!(!Rc::ptr_eq(&a.head(), &b.head()));
+
+            // Cueing the same noun again allocates nothing.
+            let before = allocated();
+            let c = Noun::cue_with_cache(&mut cache, event(2, &payload).jam()).expect("cue");
+            assert_eq!(allocated(), before);
+            let Noun::Cell(c) = &*c else {
+                panic!("events are cells");
+            };
+            assert!(Rc::ptr_eq(&c.head(), &b.head()));
+        }
+
+        // Subnouns shared by backreferences within a single noun are shared with the cache too.
+        {
+            let mut cache = CueCache::new(1 << 16);
+            let noun = Noun::from(Cell::from([
+                Rc::new(payload.clone()),
+                Rc::new(payload.clone()),
+            ]));
+            let noun = Noun::cue_with_cache(&mut cache, noun.jam()).expect("cue");
+            let Noun::Cell(cell) = &*noun else {
+                panic!("noun is a cell");
+            };
+            assert!(Rc::ptr_eq(&cell.head(), &cell.tail()));
+            assert_eq!(cache.len(), 1 + 511);
+        }
+
+        // The least recently used nouns are evicted once the cache is full.
+        {
+            let mut cache = CueCache::new(8);
+            let mut cue = |noun: &Noun| {
+                let before = allocated();
+                let decoded = Noun::cue_with_cache(&mut cache, noun.clone().jam()).expect("cue");
+                assert_eq!(*decoded, *noun);
+                allocated() - before
+            };
+            let [a, b, c] = [[1u8, 2u8], [3, 4], [5, 6]].map(|cell| Noun::from(Cell::from(cell)));
+            assert_eq!(cue(&a), 3);
+            assert_eq!(cue(&b), 3);
+            assert_eq!(cue(&a), 0);
+            // Makes room by evicting the atom 3, the least recently used noun.
+            assert_eq!(cue(&c), 3);
+            assert_eq!(cue(&a), 0);
+            assert_eq!(cue(&b), 3);
+
+            // A noun larger than the cache evicts everything.
+            cue(&payload);
+            assert_eq!(cue(&a), 3);
+            assert_eq!(cache.len(), 8);
+
+            cache.clear();
+            assert!(cache.is_empty());
+        }
+
+        // A cache with no capacity shares nothing.
+        {
+            let mut cache = CueCache::new(0);
+            let a = Noun::cue_with_cache(&mut cache, event(1, &payload).jam()).expect("cue");
+            let b = Noun::cue_with_cache(&mut cache, event(2, &payload).jam()).expect("cue");
+            assert_eq!(*a, event(1, &payload));
+            assert_eq!(*b, event(2, &payload));
+            assert!(cache.is_empty());
+        }
+
+        // A hash collision doesn't share unequal nouns.
+        {
+            let mut cache = CueCache::new(16);
+            let atom = cache.atom(Atom::from(1u8), 0);
+            let cell = cache.cell(atom.clone(), atom.clone(), 0);
+            assert_eq!(*cell, Noun::from(Cell::from([1u8, 1u8])));
+            assert_eq!(*cache.atom(Atom::from(2u8), 0), Noun::from(Atom::from(2u8)));
+            assert_eq!(cache.len(), 1);
+        }
+
+        // Corrupt jammed nouns are rejected just like by cue().
+        {
+            let mut cache = CueCache::new(16);
+            match Noun::cue_with_cache(&mut cache, Atom::from(0b1010u8)) {
+                Err(crate::serdes::Error::TrailingData { .. }) => {}
+                res => panic!("unexpected result: {:?}", res),
+            }
+        }
+    }
+}
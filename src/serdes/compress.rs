@@ -0,0 +1,269 @@
+//! Compressed jammed nouns.
+//!
+//! Jamming deduplicates repeated subnouns but does nothing about the entropy of the bytes that
+//! remain, so jammed nouns often compress well. A compressed jammed noun is the jammed noun
+//! compressed with [zstd], preceded by a 13-byte header: a 4-byte magic number, a codec byte
+//! identifying the compressor, which is always [`CODEC_ZSTD`], and the length in bytes of the
+//! uncompressed jammed noun as a little-endian `u64`.
+//!
+//! ```text
+//! +---------+---------+------------------+---------------------+
+//! | magic   | codec   | length (LE u64)  | compressed jam      |
+//! | 4 bytes | 1 byte  | 8 bytes          | remaining bytes     |
+//! +---------+---------+------------------+---------------------+
+//! ```
+//!
+//! See [`Noun::jam_compressed()`] and [`Noun::cue_compressed()`].
+//!
+//! [zstd]: https://facebook.github.io/zstd/
+
+use crate::{
+    noun::Noun,
+    serdes::{self, cue::ReadSource, jam::WriteSink, CueLimits},
+};
+use std::{
+    fmt::{self, Display, Formatter},
+    io::{self, BufReader, Read},
+    result,
+};
+
+/// The magic number that begins every compressed jammed noun.
+pub const MAGIC: [u8; 4] = *b"jamz";
+
+/// The codec byte of a jammed noun compressed with zstd, the only supported codec.
+pub const CODEC_ZSTD: u8 = 0;
+
+/// The length in bytes of the header of a compressed jammed noun.
+pub const HEADER_LEN: usize = 13;
+
+/// Ways in which a compressed jammed noun can be malformed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CompressedError {
+    /// The header had an unsupported codec.
+    Codec(u8),
+    /// The jammed noun decompressed to a different number of bytes than its header declared.
+    LenMismatch {
+        /// The length declared by the header.
+        expected: u64,
+    },
+    /// The header didn't begin with [`MAGIC`].
+    Magic,
+    /// The header declared a jammed noun longer than the caller's limit.
+    TooLarge {
+        /// The length declared by the header.
+        len: u64,
+        /// The caller's limit.
+        limit: u64,
+    },
+    /// The input was shorter than a header.
+    Truncated,
+}
+
+impl Display for CompressedError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> result::Result<(), fmt::Error> {
+        match self {
+            Self::Codec(codec) => write!(f, "unsupported compression codec {}", codec),
+            Self::LenMismatch { expected } => write!(
+                f,
+                "the jammed noun didn't decompress to the declared {} bytes",
+                expected
+            ),
+            Self::Magic => write!(f, "the header has the wrong magic number"),
+            Self::TooLarge { len, limit } => write!(
+                f,
+                "the declared length of {} bytes exceeds the limit of {} bytes",
+                len, limit
+            ),
+            Self::Truncated => write!(f, "the header is truncated"),
+        }
+    }
+}
+
+impl std::error::Error for CompressedError {}
+
+/// Jams `noun` and compresses it with zstd at compression `level`, streaming the jammed noun
+/// through the compressor.
+pub(crate) fn compress(noun: &Noun, level: i32) -> io::Result<Vec<u8>> {
+    // The header comes first, so the length has to be computed before the noun is jammed.
+    let len = noun.jam_size_bits().div_ceil(u64::from(u8::BITS));
+    let mut compressed = Vec::with_capacity(HEADER_LEN);
+    compressed.extend_from_slice(&MAGIC);
+    compressed.push(CODEC_ZSTD);
+    compressed.extend_from_slice(&len.to_le_bytes());
+
+    let mut encoder = zstd::Encoder::new(compressed, level)?;
+    let mut sink = WriteSink::new(&mut encoder);
+    serdes::jam::encode(noun, &mut sink)?;
+    sink.finish()?;
+    encoder.finish()
+}
+
+/// Decompresses and cues a compressed jammed noun, refusing to decompress more than `max_len`
+/// bytes.
+pub(crate) fn decompress(bytes: &[u8], max_len: u64) -> serdes::Result<Noun> {
+    let (len, payload) = parse_header(bytes).map_err(serdes::Error::Compressed)?;
+    if len > max_len {
+        return Err(serdes::Error::Compressed(CompressedError::TooLarge {
+            len,
+            limit: max_len,
+        }));
+    }
+    let mismatch = || serdes::Error::Compressed(CompressedError::LenMismatch { expected: len });
+
+    // Reading through take() means that a corrupt payload can't decompress to more than the
+    // declared length, however much it would otherwise expand.
+    let decoder = zstd::Decoder::with_buffer(payload).map_err(serdes::Error::Io)?;
+    let mut jammed_noun = BufReader::new(decoder.take(len));
+    let noun = serdes::cue::decode(
+        &mut ReadSource::new(&mut jammed_noun),
+        &CueLimits::UNLIMITED,
+    )?;
+
+    // The jammed noun has to end exactly where the header says it does, which means that nothing
+    // is left over in the buffer, in the declared length, or in the decompressed payload.
+    if !jammed_noun.buffer().is_empty() {
+        return Err(mismatch());
+    }
+    let jammed_noun = jammed_noun.into_inner();
+    if jammed_noun.limit() != 0 {
+        return Err(mismatch());
+    }
+    if jammed_noun
+        .into_inner()
+        .read(&mut [0])
+        .map_err(serdes::Error::Io)?
+        != 0
+    {
+        return Err(mismatch());
+    }
+    Ok(noun)
+}
+
+/// Parses and validates the header of a compressed jammed noun, returning the declared length of
+/// the jammed noun and the compressed payload.
+fn parse_header(bytes: &[u8]) -> result::Result<(u64, &[u8]), CompressedError> {
+    if bytes.len() < HEADER_LEN {
+        return Err(CompressedError::Truncated);
+    }
+    let (header, payload) = bytes.split_at(HEADER_LEN);
+    if header[..MAGIC.len()] != MAGIC {
+        return Err(CompressedError::Magic);
+    }
+    let codec = header[MAGIC.len()];
+    if codec != CODEC_ZSTD {
+        return Err(CompressedError::Codec(codec));
+    }
+    let mut len = [0; 8];
+    len.copy_from_slice(&header[MAGIC.len() + 1..]);
+    Ok((u64::from_le_bytes(len), payload))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{atom::Atom, cell::Cell, Rc};
+
+    #[test]
+    fn compressed() {
+        // A list of 1000 distinct atoms whose jam is mostly low-entropy bytes.
+        let list = (0..1000u32)
+            .rev()
+            .fold(Noun::from(Atom::null()), |tail, i| {
+                Noun::from(Cell::from([
+                    Rc::new(Noun::from(Atom::from(i * 1000))),
+                    Rc::new(tail),
+                ]))
+            });
+        let nouns = [
+            Noun::from(Atom::from(0u8)),
+            Noun::from(Cell::from([0u8, 19u8])),
+            Noun::from(Cell::from([10_000u16, 10_000u16])),
+            Noun::from(Atom::from(vec![0xab; 100_000])),
+            list,
+        ];
+
+        // Round trip at several levels.
+        for noun in &nouns {
+            let len = noun.jam_to_vec().len() as u64;
+            for level in [0, 1, 3, 19] {
+                let compressed = noun.jam_compressed(level);
+                assert_eq!(compressed[..MAGIC.len()], MAGIC);
+                assert_eq!(compressed[MAGIC.len()], CODEC_ZSTD);
+                assert_eq!(compressed[MAGIC.len() + 1..HEADER_LEN], len.to_le_bytes());
+                assert_eq!(Noun::cue_compressed(&compressed, len).expect("cue"), *noun);
+            }
+        }
+
+        // Highly repetitive jams shrink.
+        {
+            let noun = &nouns[3];
+            assert!(noun.jam_compressed(3).len() < noun.jam_to_vec().len() / 100);
+        }
+
+        let compressed = nouns[1].jam_compressed(3);
+        let cue = |bytes: &[u8]| Noun::cue_compressed(bytes, u64::MAX);
+
+        // Corrupted headers.
+        {
+            match cue(&compressed[..HEADER_LEN - 1]) {
+                Err(err @ serdes::Error::Compressed(CompressedError::Truncated)) => {
+                    let source = std::error::Error::source(&err).expect("source");
+                    assert_eq!(
+                        source.downcast_ref::<CompressedError>(),
+                        Some(&CompressedError::Truncated)
+                    );
+                }
+                res => panic!("unexpected result: {:?}", res),
+            }
+
+            let mut bytes = compressed.clone();
+            bytes[0] ^= 1;
+            match cue(&bytes) {
+                Err(serdes::Error::Compressed(CompressedError::Magic)) => {}
+                res => panic!("unexpected result: {:?}", res),
+            }
+
+            let mut bytes = compressed.clone();
+            bytes[MAGIC.len()] = 7;
+            match cue(&bytes) {
+                Err(serdes::Error::Compressed(CompressedError::Codec(7))) => {}
+                res => panic!("unexpected result: {:?}", res),
+            }
+        }
+
+        // A declared length that disagrees with the payload.
+        for len in [1u64, 3, u64::MAX] {
+            let mut bytes = compressed.clone();
+            bytes[MAGIC.len() + 1..HEADER_LEN].copy_from_slice(&len.to_le_bytes());
+            match cue(&bytes) {
+                Err(serdes::Error::Compressed(CompressedError::LenMismatch { expected }))
+                    if expected == len => {}
+                // Cut off partway through the jammed noun.
+                Err(serdes::Error::UnexpectedEof(_)) if len == 1 => {}
+                res => panic!("unexpected result for length {}: {:?}", len, res),
+            }
+        }
+
+        // A decompression bomb is rejected before it's decompressed.
+        {
+            let noun = Noun::from(Atom::from(vec![0xff; 1 << 20]));
+            let compressed = noun.jam_compressed(3);
+            match Noun::cue_compressed(&compressed, 1 << 16) {
+                Err(serdes::Error::Compressed(CompressedError::TooLarge { limit, .. })) => {
+                    assert_eq!(limit, 1 << 16)
+                }
+                res => panic!("unexpected result: {:?}", res),
+            }
+        }
+
+        // A corrupt payload.
+        {
+            let mut bytes = compressed.clone();
+            bytes.truncate(bytes.len() - 1);
+            match cue(&bytes) {
+                Err(serdes::Error::Io(_)) | Err(serdes::Error::UnexpectedEof(_)) => {}
+                res => panic!("unexpected result: {:?}", res),
+            }
+        }
+    }
+}
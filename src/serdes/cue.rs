@@ -0,0 +1,1653 @@
+//! The cue decoder shared by every [`Cue`](super::Cue) implementation.
+
+use super::{
+    cache::{self, CueCache},
+    trace::{self, OpSpan},
+    CueLimits, CueProgress, CueStats, EnclosingCell, Error, HashMap, Item, Limit,
+    NonCanonicalReason, Position, Progress, Result, Side,
+};
+use crate::{
+    atom::{copy_bits, Atom, Iter as AtomIter},
+    cell::Cell,
+    noun::Noun,
+    Rc,
+};
+use std::{
+    io::{self, Read},
+    mem,
+};
+
+/// A source of the bits of a jammed noun.
+pub(crate) trait BitSource {
+    /// Returns the number of bits read from this source so far.
+    fn pos(&self) -> u64;
+
+    /// Reads the next bit from this source, returning `None` if the source is exhausted.
+    fn next_bit(&mut self) -> Result<Option<bool>>;
+
+    /// Reads the next `len` bits from this source as an atom, returning `None` if the source is
+    /// exhausted first.
+    fn next_bits(&mut self, len: u64) -> Result<Option<Atom>> {
+        let mut atom_builder = Atom::builder();
+        for _ in 0..len {
+            match self.next_bit()? {
+                Some(bit) => atom_builder.push_bit(bit),
+                None => return Ok(None),
+            }
+        }
+        Ok(Some(atom_builder.into_atom()))
+    }
+
+    /// Returns the number of bits left in this source, if known.
+    fn remaining(&self) -> Option<u64> {
+        None
+    }
+}
+
+impl BitSource for AtomIter<'_> {
+    fn pos(&self) -> u64 {
+        self.pos_u64()
+    }
+
+    fn remaining(&self) -> Option<u64> {
+        Some(self.remaining())
+    }
+
+    fn next_bit(&mut self) -> Result<Option<bool>> {
+        Ok(self.next())
+    }
+
+    fn next_bits(&mut self, len: u64) -> Result<Option<Atom>> {
+        Ok(self.read_bits_u64(len))
+    }
+}
+
+/// A [`BitSource`] that reads bytes from an [`io::Read`] as their bits are needed.
+///
+/// Only a single byte is read from the underlying reader at a time so that the reader is never
+/// advanced past the byte containing the last bit that was requested.
+pub(crate) struct ReadSource<R: Read> {
+    reader: R,
+    /// Byte that is currently being consumed.
+    byte: u8,
+    /// Number of bits read from the source so far.
+    bit_idx: u64,
+}
+
+impl<R: Read> ReadSource<R> {
+    /// Creates a new source that reads from `reader`.
+    pub(crate) fn new(reader: R) -> Self {
+        Self {
+            reader,
+            byte: 0,
+            bit_idx: 0,
+        }
+    }
+}
+
+impl<R: Read> BitSource for ReadSource<R> {
+    fn pos(&self) -> u64 {
+        self.bit_idx
+    }
+
+    fn next_bit(&mut self) -> Result<Option<bool>> {
+        let shift = self.bit_idx % u64::from(u8::BITS);
+        if shift == 0 {
+            let mut buf = [0u8];
+            // read_exact() retries short and interrupted reads.
+            match self.reader.read_exact(&mut buf) {
+                Ok(()) => self.byte = buf[0],
+                Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+                Err(err) => return Err(Error::Io(err)),
+            }
+        }
+        self.bit_idx += 1;
+        Ok(Some(self.byte & (1 << shift) != 0))
+    }
+
+    fn next_bits(&mut self, len: u64) -> Result<Option<Atom>> {
+        if len == 0 {
+            return Ok(Some(Atom::from(0u8)));
+        }
+        let shift = self.bit_idx % u64::from(u8::BITS);
+        // The unread bits of the current byte, if any, come first.
+        let mut bytes = if shift == 0 { vec![] } else { vec![self.byte] };
+        let byte_len = match shift.checked_add(len) {
+            Some(bit_len) => bit_len.div_ceil(u64::from(u8::BITS)),
+            // No source is this long.
+            None => return Ok(None),
+        };
+        let unread = byte_len - bytes.len() as u64;
+        // Reading through take() rather than into a preallocated buffer means that a corrupt
+        // length can't cause more memory to be allocated than there is input.
+        match (&mut self.reader).take(unread).read_to_end(&mut bytes) {
+            Ok(_) if bytes.len() as u64 == byte_len => {}
+            Ok(_) => return Ok(None),
+            Err(err) => return Err(Error::Io(err)),
+        }
+        let atom = Atom::from(copy_bits(&bytes, shift, len));
+        if let Some(&last) = bytes.last() {
+            self.byte = last;
+        }
+        self.bit_idx += len;
+        Ok(Some(atom))
+    }
+}
+
+/// The kind of noun that a tag introduces.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum Tag {
+    /// An atom, tagged `0b0`.
+    Atom,
+    /// A cell, tagged `0b01`.
+    Cell,
+    /// A backreference, tagged `0b11`.
+    Backref,
+}
+
+// The encoding rules below are shared by every decoder, which differ only in what they do with
+// what's decoded. Positions are relative to `start`, the position of `bits` when decoding began.
+
+/// Reads the next bit, failing with [`Error::UnexpectedEof`] at `pos` if there are no bits left.
+fn next_bit<S: BitSource>(bits: &mut S, pos: Position) -> Result<bool> {
+    bits.next_bit()?.ok_or(Error::UnexpectedEof(pos))
+}
+
+/// Reads the next `len` bits as an atom, failing with [`Error::UnexpectedEof`] at `pos` if fewer
+/// than `len` bits are left.
+pub(crate) fn next_bits<S: BitSource>(bits: &mut S, len: u64, pos: Position) -> Result<Atom> {
+    if len == 0 {
+        Ok(Atom::from(0u8))
+    } else {
+        bits.next_bits(len)?.ok_or(Error::UnexpectedEof(pos))
+    }
+}
+
+/// Decodes the tag of a noun within `cell`.
+pub(crate) fn decode_tag<S: BitSource>(
+    bits: &mut S,
+    start: u64,
+    cell: Option<EnclosingCell>,
+) -> Result<Tag> {
+    let pos = Position {
+        bit: bits.pos() - start,
+        item: Item::Tag,
+        cell,
+    };
+    Ok(if !next_bit(bits, pos)? {
+        Tag::Atom
+    } else if !next_bit(bits, pos)? {
+        Tag::Cell
+    } else {
+        Tag::Backref
+    })
+}
+
+/// Decodes the length of an item of kind `item` within `cell`.
+pub(crate) fn decode_len<S: BitSource>(
+    bits: &mut S,
+    start: u64,
+    item: Item,
+    cell: Option<EnclosingCell>,
+) -> Result<u64> {
+    let pos = Position {
+        bit: bits.pos() - start,
+        item,
+        cell,
+    };
+    let mut len_of_len = 0;
+    while !next_bit(bits, pos)? {
+        len_of_len += 1;
+        // A length that doesn't fit in a u64 can't be valid.
+        if len_of_len > u64::BITS {
+            return Err(Error::InvalidLen(pos));
+        }
+    }
+
+    if len_of_len == 0 {
+        Ok(0)
+    } else {
+        // The most significant bit of the length is implicit because it's always 1.
+        let len_bits = len_of_len - 1;
+        let mut len: u64 = 1 << len_bits;
+        for i in 0..len_bits {
+            if next_bit(bits, pos)? {
+                len |= 1 << i;
+            }
+        }
+        Ok(len)
+    }
+}
+
+/// The index of a backreference, which is the position of the noun it refers to.
+pub(crate) struct BackrefIdx {
+    pub(crate) idx: u64,
+    /// The length of the index in bits.
+    pub(crate) len: u64,
+    /// The position of the index, at which a reference to a noun that can't be referred to fails.
+    pub(crate) pos: Position,
+}
+
+/// Decodes the length and index of a backreference whose tag has just been decoded within
+/// `cell`.
+pub(crate) fn decode_backref_idx<S: BitSource>(
+    bits: &mut S,
+    start: u64,
+    cell: Option<EnclosingCell>,
+) -> Result<BackrefIdx> {
+    let len = decode_len(bits, start, Item::BackrefLen, cell)?;
+    let pos = Position {
+        bit: bits.pos() - start,
+        item: Item::BackrefIdx,
+        cell,
+    };
+    // An index that doesn't fit in a u64 can't refer to a cached noun, so there's no point
+    // reading it.
+    if len > u64::from(u64::BITS) {
+        return Err(Error::InvalidBackref(pos));
+    }
+    let idx = next_bits(bits, len, pos)?
+        .as_u64()
+        .ok_or(Error::InvalidBackref(pos))?;
+    Ok(BackrefIdx { idx, len, pos })
+}
+
+/// Resources consumed by a decoded noun, counted as if none of its subnouns were shared.
+#[derive(Clone, Copy)]
+struct Usage {
+    /// Number of atoms and cells in the noun.
+    nodes: u64,
+    /// Number of bytes in all of the atoms of the noun.
+    atom_bytes: u64,
+    /// Number of cells on the longest path from the noun to one of its atoms.
+    height: u64,
+    /// Number of bits in the encoding of the noun where it was encoded in full, which does count
+    /// shared subnouns only once.
+    bits: u64,
+}
+
+/// The state needed to check that a jammed noun is canonical, i.e. exactly the encoding that
+/// jamming its noun would produce.
+///
+/// Every noun is identified by the position of its first occurrence, which is where the encoder
+/// points every backreference to it. As long as everything decoded so far is canonical, equal
+/// nouns have equal identifiers, so a cell can be identified by the identifiers of its head and
+/// tail without comparing nouns.
+#[derive(Default)]
+struct Canon {
+    /// The position of the first occurrence of every noun encoded in full, keyed by position.
+    firsts: HashMap<u64, u64>,
+    /// The position of the first occurrence of every atom.
+    atoms: HashMap<Atom, u64>,
+    /// The position of the first occurrence of every cell, keyed by the positions of the first
+    /// occurrences of its head and tail.
+    cells: HashMap<(u64, u64), u64>,
+}
+
+/// The state needed to share decoded nouns through a [`CueCache`].
+struct Shared<'a> {
+    cache: &'a mut CueCache,
+    /// The hash of every noun decoded so far, keyed by its position.
+    hashes: HashMap<u64, u64>,
+}
+
+/// Returns the number of significant bits in `n`.
+fn bit_len(n: u64) -> u64 {
+    u64::from(u64::BITS - n.leading_zeros())
+}
+
+/// The position of a noun, along with the noun and its resource usage once it's been decoded.
+type PosEntry = (u64, Option<(Rc<Noun>, Usage)>);
+
+/// Every noun decoded so far along with its position, for resolving backreferences.
+///
+/// Nouns are encoded in pre-order, so a noun's position is greater than that of every noun encoded
+/// before it. A cell's entry is reserved as soon as its tag is decoded, before its head and tail
+/// are, which keeps the entries sorted by position so that they can be binary searched. The entry
+/// is filled in once the cell's tail has been decoded.
+struct PosCache {
+    /// The entries in order of position, or `None` if nouns aren't cached because the jammed noun
+    /// has no backreferences.
+    entries: Option<Vec<PosEntry>>,
+}
+
+impl PosCache {
+    /// Reserves an entry for the noun at `pos`, returning its index.
+    fn reserve(&mut self, pos: u64) -> usize {
+        match &mut self.entries {
+            Some(entries) => {
+                debug_assert!(entries.last().is_none_or(|(last, _)| *last < pos));
+                entries.push((pos, None));
+                entries.len() - 1
+            }
+            None => 0,
+        }
+    }
+
+    /// Fills in the entry at index `slot` with a decoded noun.
+    fn fill(&mut self, slot: usize, noun: &Rc<Noun>, usage: Usage) {
+        if let Some(entries) = &mut self.entries {
+            entries[slot].1 = Some((noun.clone(), usage));
+        }
+    }
+
+    /// Returns the decoded noun at `pos`, if any.
+    fn get(&self, pos: u64) -> Option<&(Rc<Noun>, Usage)> {
+        let entries = self.entries.as_ref()?;
+        let slot = entries.binary_search_by_key(&pos, |(pos, _)| *pos).ok()?;
+        entries[slot].1.as_ref()
+    }
+}
+
+/// A cell whose head or tail is being decoded.
+struct Frame {
+    /// Position of the cell relative to `start`.
+    pos: u64,
+    /// Index of the cell's entry in the cache.
+    slot: usize,
+    /// The decoded head of the cell along with the position of its encoding in full, or `None`
+    /// if the head is being decoded.
+    head: Option<(Rc<Noun>, Usage, u64)>,
+}
+
+/// State shared by every step of decoding a single noun.
+struct Decoder<'a, S: BitSource> {
+    bits: &'a mut S,
+    /// Position of `bits` when decoding began, which backreferences are relative to.
+    start: u64,
+    limits: &'a CueLimits,
+    /// Every noun decoded so far, with positions relative to `start`.
+    cache: PosCache,
+    /// The cells whose heads or tails are being decoded, innermost last.
+    stack: Vec<Frame>,
+    /// Number of atoms and cells decoded so far.
+    nodes: u64,
+    /// Number of atom bytes decoded so far.
+    atom_bytes: u64,
+    /// The state needed to check that the jammed noun is canonical, or `None` if it isn't checked.
+    canon: Option<Canon>,
+    /// The state needed to share decoded nouns, or `None` if they aren't shared.
+    shared: Option<Shared<'a>>,
+    /// The progress callback, or `None` if progress isn't reported.
+    progress: Option<Progress<'a, CueProgress>>,
+    /// What has been decoded so far.
+    stats: CueStats,
+}
+
+impl<'a, S: BitSource> Decoder<'a, S> {
+    /// Starts decoding from the current position of `bits`, caching every decoded noun unless
+    /// `backrefs` is `false`.
+    fn new(
+        bits: &'a mut S,
+        limits: &'a CueLimits,
+        backrefs: bool,
+        canon: Option<Canon>,
+        shared: Option<Shared<'a>>,
+        progress: Option<Progress<'a, CueProgress>>,
+    ) -> Self {
+        let start = bits.pos();
+        let cache = PosCache {
+            entries: backrefs.then(|| {
+                let capacity = bits.remaining().map_or(0, |bits| {
+                    (bits / BITS_PER_NODE).min(MAX_CACHE_CAPACITY as u64) as usize
+                });
+                Vec::with_capacity(capacity)
+            }),
+        };
+        Self {
+            bits,
+            start,
+            limits,
+            cache,
+            stack: Vec::new(),
+            nodes: 0,
+            atom_bytes: 0,
+            canon,
+            shared,
+            progress,
+            stats: CueStats::default(),
+        }
+    }
+
+    /// Accounts for a noun with resource usage `usage` being decoded `depth` cells below the root
+    /// noun, failing if doing so would exceed any of the limits.
+    fn charge(&mut self, usage: Usage, depth: u64) -> Result<()> {
+        let limits = self.limits;
+        if depth.saturating_add(usage.height) > limits.max_depth {
+            return Err(Error::LimitExceeded {
+                which: Limit::Depth,
+                limit: limits.max_depth,
+            });
+        }
+        self.nodes = self.nodes.saturating_add(usage.nodes);
+        if self.nodes > limits.max_nodes {
+            return Err(Error::LimitExceeded {
+                which: Limit::Nodes,
+                limit: limits.max_nodes,
+            });
+        }
+        self.atom_bytes = self.atom_bytes.saturating_add(usage.atom_bytes);
+        if self.atom_bytes > limits.max_total_atom_bytes {
+            return Err(Error::LimitExceeded {
+                which: Limit::TotalAtomBytes,
+                limit: limits.max_total_atom_bytes,
+            });
+        }
+        Ok(())
+    }
+
+    /// Returns the position of `bits` relative to `start`.
+    fn pos(&self) -> u64 {
+        self.bits.pos() - self.start
+    }
+
+    /// Fails with [`Error::NonCanonical`] for `reason` at `pos`.
+    fn non_canonical<T>(reason: NonCanonicalReason, pos: u64) -> Result<T> {
+        Err(Error::NonCanonical {
+            reason,
+            bit_pos: pos,
+        })
+    }
+
+    /// Decodes an atom at `pos` `depth` cells below the root noun within `cell`, checking it
+    /// against the limits before any of its bits are read.
+    fn decode_atom(
+        &mut self,
+        pos: u64,
+        depth: u64,
+        cell: Option<EnclosingCell>,
+    ) -> Result<(Rc<Noun>, Usage)> {
+        let len = decode_len(self.bits, self.start, Item::AtomLen, cell)?;
+        let bytes = len.div_ceil(u64::from(u8::BITS));
+        if bytes > self.limits.max_atom_bytes {
+            return Err(Error::LimitExceeded {
+                which: Limit::AtomBytes,
+                limit: self.limits.max_atom_bytes,
+            });
+        }
+        let mut usage = Usage {
+            nodes: 1,
+            atom_bytes: bytes,
+            height: 0,
+            bits: 0,
+        };
+        self.charge(usage, depth)?;
+        let atom_pos = Position {
+            bit: self.pos(),
+            item: Item::Atom,
+            cell,
+        };
+        let atom = next_bits(self.bits, len, atom_pos)?;
+        usage.bits = self.pos() - pos;
+        self.stats.atoms += 1;
+        if let Some(canon) = &mut self.canon {
+            if atom.bit_len_u64() != len {
+                return Self::non_canonical(NonCanonicalReason::NonMinimalLen, pos);
+            }
+            let first = *canon.atoms.entry(atom.clone()).or_insert(pos);
+            // The encoder only uses a backreference for an atom if it's shorter than the atom.
+            if first != pos && len > bit_len(first) {
+                return Self::non_canonical(NonCanonicalReason::MissingBackref, pos);
+            }
+            canon.firsts.insert(pos, first);
+        }
+        let atom = match &mut self.shared {
+            Some(shared) => {
+                let hash = atom.hash();
+                shared.hashes.insert(pos, hash);
+                shared.cache.atom(atom, hash)
+            }
+            None => Rc::<Noun>::from(atom),
+        };
+        Ok((atom, usage))
+    }
+
+    /// Decodes a backreference at `pos` `depth` cells below the root noun within `cell`,
+    /// returning the referenced noun along with its position.
+    fn decode_backref(
+        &mut self,
+        pos: u64,
+        depth: u64,
+        cell: Option<EnclosingCell>,
+    ) -> Result<(Rc<Noun>, Usage, u64)> {
+        let BackrefIdx {
+            idx,
+            len,
+            pos: idx_pos,
+        } = decode_backref_idx(self.bits, self.start, cell)?;
+        let (noun, usage) = self.cache.get(idx).ok_or(Error::CacheMiss(idx_pos))?;
+        let (noun, usage) = (noun.clone(), *usage);
+        if let Some(canon) = &self.canon {
+            if bit_len(idx) != len {
+                return Self::non_canonical(NonCanonicalReason::NonMinimalLen, pos);
+            }
+            if canon.firsts[&idx] != idx {
+                return Self::non_canonical(NonCanonicalReason::NotFirstOccurrence, pos);
+            }
+            if let Noun::Atom(atom) = &*noun {
+                if atom.bit_len_u64() <= bit_len(idx) {
+                    return Self::non_canonical(NonCanonicalReason::NeedlessBackref, pos);
+                }
+            }
+        }
+        self.charge(usage, depth)?;
+        self.stats.backrefs += 1;
+        self.stats.backref_bits_saved += usage.bits as i64 - (self.pos() - pos) as i64;
+        Ok((noun, usage, idx))
+    }
+
+    /// Decodes a noun, caching every decoded noun.
+    ///
+    /// Cells are decoded with an explicit stack rather than recursively so that arbitrarily deep
+    /// nouns can be decoded without overflowing the call stack.
+    fn decode_noun(&mut self) -> Result<Rc<Noun>> {
+        loop {
+            if let Some(noun) = self.decode_next()? {
+                return Ok(noun);
+            }
+        }
+    }
+
+    /// Decodes the next atom, cell, or backreference, returning the root noun once it's been
+    /// decoded in full.
+    ///
+    /// If this fails with [`Error::UnexpectedEof`], the only state that has changed is what's
+    /// been charged against the limits and recorded in `stats`, so a caller can restore those,
+    /// move `bits` back to where it was, and try again once more bits are available.
+    fn decode_next(&mut self) -> Result<Option<Rc<Noun>>> {
+        let depth = self.stack.len() as u64;
+        let cell = self.stack.last().map(|frame| EnclosingCell {
+            bit: frame.pos,
+            side: if frame.head.is_none() {
+                Side::Head
+            } else {
+                Side::Tail
+            },
+        });
+        let pos = self.pos();
+        self.stats.max_depth = self.stats.max_depth.max(depth);
+        if let Some(progress) = &mut self.progress {
+            let nodes = self.nodes;
+            let total_bits = pos + self.bits.remaining().unwrap_or(0);
+            let flow = progress.step(|| CueProgress {
+                bits_read: pos,
+                total_bits,
+                nodes,
+            });
+            if flow.is_break() {
+                return Err(Error::Cancelled);
+            }
+        }
+        let mut decoded = match decode_tag(self.bits, self.start, cell)? {
+            Tag::Backref => self.decode_backref(pos, depth, cell)?,
+            Tag::Cell => {
+                self.charge(
+                    Usage {
+                        nodes: 1,
+                        atom_bytes: 0,
+                        height: 0,
+                        bits: 0,
+                    },
+                    depth,
+                )?;
+                self.stats.cells += 1;
+                let slot = self.cache.reserve(pos);
+                self.stack.push(Frame {
+                    pos,
+                    slot,
+                    head: None,
+                });
+                return Ok(None);
+            }
+            Tag::Atom => {
+                let (atom, usage) = self.decode_atom(pos, depth, cell)?;
+                let slot = self.cache.reserve(pos);
+                self.cache.fill(slot, &atom, usage);
+                (atom, usage, pos)
+            }
+        };
+        // Complete every cell whose tail was just decoded.
+        while let Some(frame) = self.stack.pop() {
+            match frame.head {
+                None => {
+                    self.stack.push(Frame {
+                        head: Some(decoded),
+                        ..frame
+                    });
+                    return Ok(None);
+                }
+                Some((head, head_usage, head_pos)) => {
+                    let (tail, tail_usage, tail_pos) = decoded;
+                    if let Some(canon) = &mut self.canon {
+                        let key = (canon.firsts[&head_pos], canon.firsts[&tail_pos]);
+                        let first = *canon.cells.entry(key).or_insert(frame.pos);
+                        // The encoder always uses a backreference for a repeated cell.
+                        if first != frame.pos {
+                            return Self::non_canonical(
+                                NonCanonicalReason::MissingBackref,
+                                frame.pos,
+                            );
+                        }
+                        canon.firsts.insert(frame.pos, first);
+                    }
+                    let noun = match &mut self.shared {
+                        Some(shared) => {
+                            let hash = cache::cell_hash(
+                                shared.hashes[&head_pos],
+                                shared.hashes[&tail_pos],
+                            );
+                            shared.hashes.insert(frame.pos, hash);
+                            shared.cache.cell(head, tail, hash)
+                        }
+                        None => Rc::<Noun>::from(Cell::from([head, tail])),
+                    };
+                    let usage = Usage {
+                        nodes: head_usage
+                            .nodes
+                            .saturating_add(tail_usage.nodes)
+                            .saturating_add(1),
+                        atom_bytes: head_usage.atom_bytes.saturating_add(tail_usage.atom_bytes),
+                        height: head_usage.height.max(tail_usage.height) + 1,
+                        bits: self.pos() - frame.pos,
+                    };
+                    self.cache.fill(frame.slot, &noun, usage);
+                    decoded = (noun, usage, frame.pos);
+                }
+            }
+        }
+        Ok(Some(decoded.0))
+    }
+}
+
+/// Cues a noun from `bits`, starting at the current position of `bits`, failing if decoding the
+/// noun would exceed any of `limits`.
+///
+/// Backreferences are relative to the position of `bits` when this function is called.
+pub(crate) fn decode<S: BitSource>(bits: &mut S, limits: &CueLimits) -> Result<Noun> {
+    decode_with(bits, limits, true, None, None, None).map(|(noun, _)| unwrap_root(noun))
+}
+
+/// Cues a noun from `bits` like [`decode_exact()`], also returning what was decoded.
+pub(crate) fn decode_with_stats(bits: &mut AtomIter) -> Result<(Noun, CueStats)> {
+    let (noun, stats) = decode_with(bits, &CueLimits::UNLIMITED, true, None, None, None)?;
+    match bits.remaining() {
+        0 => Ok((unwrap_root(noun), stats)),
+        len => Err(Error::TrailingData { bits: len }),
+    }
+}
+
+/// Cues a noun without backreferences from `bits` like [`decode_exact()`], failing with
+/// [`Error::CacheMiss`] at the first backreference.
+pub(crate) fn decode_nodedup(bits: &mut AtomIter) -> Result<Noun> {
+    let noun = decode_with(bits, &CueLimits::UNLIMITED, false, None, None, None)
+        .map(|(noun, _)| unwrap_root(noun))?;
+    match bits.remaining() {
+        0 => Ok(noun),
+        len => Err(Error::TrailingData { bits: len }),
+    }
+}
+
+/// Cues a noun from `bits` like [`decode_exact()`], sharing its subnouns with the nouns in
+/// `cache`.
+pub(crate) fn decode_shared(
+    bits: &mut AtomIter,
+    limits: &CueLimits,
+    cache: &mut CueCache,
+) -> Result<Rc<Noun>> {
+    let shared = Shared {
+        cache,
+        hashes: HashMap::default(),
+    };
+    let (noun, _) = decode_with(bits, limits, true, None, Some(shared), None)?;
+    match bits.remaining() {
+        0 => Ok(noun),
+        len => Err(Error::TrailingData { bits: len }),
+    }
+}
+
+/// Cues a noun from `bits` like [`decode_exact()`], counting every atom, cell, and backreference
+/// as a step of `progress` and failing with [`Error::Cancelled`] if `progress` breaks.
+pub(crate) fn decode_with_progress(
+    bits: &mut AtomIter,
+    progress: Progress<CueProgress>,
+) -> Result<Noun> {
+    let noun = decode_with(
+        bits,
+        &CueLimits::UNLIMITED,
+        true,
+        None,
+        None,
+        Some(progress),
+    )
+    .map(|(noun, _)| unwrap_root(noun))?;
+    match bits.remaining() {
+        0 => Ok(noun),
+        len => Err(Error::TrailingData { bits: len }),
+    }
+}
+
+/// Cues a noun from `bits`, checking that `bits` holds exactly the encoding that jamming the noun
+/// would produce, and nothing more.
+pub(crate) fn decode_canonical(bits: &mut AtomIter) -> Result<Noun> {
+    let start = bits.pos_u64();
+    let noun = decode_with(
+        bits,
+        &CueLimits::UNLIMITED,
+        true,
+        Some(Canon::default()),
+        None,
+        None,
+    )
+    .map(|(noun, _)| unwrap_root(noun))?;
+    if bits.remaining() != 0 {
+        return Err(Error::NonCanonical {
+            reason: NonCanonicalReason::TrailingData,
+            bit_pos: bits.pos_u64() - start,
+        });
+    }
+    Ok(noun)
+}
+
+/// A low estimate of the average number of bits per noun encoded in full in a jammed noun, used to
+/// size the decoder's cache up front.
+const BITS_PER_NODE: u64 = 32;
+
+/// The largest capacity the decoder's cache is given up front, so that a jammed noun made up of a
+/// few large atoms doesn't cause a huge cache to be allocated.
+const MAX_CACHE_CAPACITY: usize = 1 << 20;
+
+/// Cues a noun from `bits`, caching every decoded noun unless `backrefs` is `false`, and returns
+/// it along with what was decoded.
+fn decode_with<S: BitSource>(
+    bits: &mut S,
+    limits: &CueLimits,
+    backrefs: bool,
+    canon: Option<Canon>,
+    shared: Option<Shared>,
+    progress: Option<Progress<CueProgress>>,
+) -> Result<(Rc<Noun>, CueStats)> {
+    let span = OpSpan::cue();
+    let mut decoder = Decoder::new(bits, limits, backrefs, canon, shared, progress);
+    let noun = decoder.decode_noun().inspect_err(trace::limit_exceeded)?;
+    let stats = CueStats {
+        bits: decoder.pos(),
+        cache_entries: decoder.cache.entries.as_ref().map_or(0, Vec::len) as u64,
+        ..decoder.stats
+    };
+    span.record("bits", stats.bits);
+    span.record("nodes", decoder.nodes);
+    span.record("atoms", stats.atoms);
+    span.record("cells", stats.cells);
+    span.record("backrefs", stats.backrefs);
+    Ok((noun, stats))
+}
+
+/// Deserializes ("cues") `count` nouns jammed one after another by [`jam_many()`].
+///
+/// Subnouns that were replaced by backreferences to earlier nouns are shared with them. Fails with
+/// [`Error::TrailingData`] if any set bits follow the last noun.
+///
+/// [`jam_many()`]: super::jam_many()
+#[doc(alias("deserialize", "deserialization"))]
+pub fn cue_many(jammed_nouns: Atom, count: usize) -> Result<Vec<Rc<Noun>>> {
+    let mut bits = jammed_nouns.iter();
+    let nouns = {
+        let span = OpSpan::cue();
+        let mut decoder = Decoder::new(&mut bits, &CueLimits::UNLIMITED, true, None, None, None);
+        let nouns = (0..count)
+            .map(|_| decoder.decode_noun())
+            .collect::<Result<Vec<_>>>()?;
+        span.record("bits", decoder.pos());
+        span.record("nodes", decoder.nodes);
+        span.record("atoms", decoder.stats.atoms);
+        span.record("cells", decoder.stats.cells);
+        span.record("backrefs", decoder.stats.backrefs);
+        nouns
+    };
+    match bits.remaining() {
+        0 => Ok(nouns),
+        len => Err(Error::TrailingData { bits: len }),
+    }
+}
+
+/// Unwraps the root noun returned by [`decode_with()`] once the decoder has been dropped.
+fn unwrap_root(noun: Rc<Noun>) -> Noun {
+    // Once the decoder and its cache have been dropped, the root noun has exactly one reference,
+    // so this never clones.
+    Rc::try_unwrap(noun).unwrap_or_else(|noun| (*noun).clone())
+}
+
+/// Cues a noun from `bits`, failing with [`Error::TrailingData`] if any set bits follow the noun.
+pub(crate) fn decode_exact(bits: &mut AtomIter, limits: &CueLimits) -> Result<Noun> {
+    let noun = decode(bits, limits)?;
+    // The last bit of an atom is always set, so any bits left over must include a set bit.
+    match bits.remaining() {
+        0 => Ok(noun),
+        len => Err(Error::TrailingData { bits: len }),
+    }
+}
+
+/// An iterator over a stream of jammed nouns written back to back without any framing.
+///
+/// A jammed noun is self-delimiting, so the nouns can be cued one after another, each starting
+/// where the previous one ended. Two layouts are supported:
+///
+/// - [`CueStream::new()`] reads nouns packed bit by bit, each starting at the bit immediately
+///   following the last bit of the previous one, as when jammed atoms are concatenated with
+///   [`AtomBuilder::push_atom()`](crate::AtomBuilder::push_atom()).
+/// - [`CueStream::from_bytes()`] reads nouns that each start on a byte boundary, as when nouns are
+///   written back to back with [`Noun::jam_to_writer()`] or by concatenating the output of
+///   [`Noun::jam_to_vec()`]. The zero bits padding the last byte of each noun are skipped.
+///
+/// The stream ends when only zero bits remain. Because the encoding of every noun ends in a set
+/// bit, no noun can be encoded in zero bits alone, so trailing zero bits (including any trailing
+/// zero bytes) are never mistaken for a truncated noun. Any other remainder that doesn't hold a
+/// complete noun is an error, such as [`Error::UnexpectedEof`] for a truncated final noun.
+///
+/// After an error, the iterator yields `None` and [`bit_pos()`](Self::bit_pos()) remains at the
+/// start of the noun that failed to cue.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{atom::Atom, cell::Cell, serdes::CueStream, Noun};
+/// let first = Noun::from(Cell::from([0u8, 19u8]));
+/// let second = Noun::from(Atom::from(19u8));
+/// let mut bytes = first.jam_to_vec();
+/// bytes.extend(second.jam_to_vec());
+///
+/// let mut nouns = CueStream::from_bytes(&bytes);
+/// assert_eq!(nouns.next().unwrap().unwrap(), first);
+/// assert_eq!(nouns.bit_pos(), 16);
+/// assert_eq!(nouns.next().unwrap().unwrap(), second);
+/// assert!(nouns.next().is_none());
+/// ```
+pub struct CueStream<'a> {
+    bits: AtomIter<'a>,
+    /// Whether every noun starts on a byte boundary.
+    byte_aligned: bool,
+    /// Whether a noun failed to cue.
+    failed: bool,
+}
+
+impl<'a> CueStream<'a> {
+    /// Creates an iterator over the jammed nouns packed bit by bit into `source`.
+    pub fn new(source: &'a Atom) -> Self {
+        Self {
+            bits: source.iter(),
+            byte_aligned: false,
+            failed: false,
+        }
+    }
+
+    /// Creates an iterator over the jammed nouns in a little-endian byte slice, each starting on
+    /// a byte boundary.
+    pub fn from_bytes(bytes: &'a [u8]) -> Self {
+        Self {
+            bits: AtomIter::from_bytes(bytes),
+            byte_aligned: true,
+            failed: false,
+        }
+    }
+
+    /// Returns the bitwise position in the source of the start of the next noun.
+    ///
+    /// The position can be saved as a checkpoint and later passed to [`seek()`](Self::seek()) to
+    /// resume reading the same source from that noun.
+    pub fn bit_pos(&self) -> u64 {
+        self.bits.pos_u64()
+    }
+
+    /// Moves this iterator to bit `bit_pos` of the source, which should be the start of a noun,
+    /// typically one previously returned by [`bit_pos()`](Self::bit_pos()).
+    ///
+    /// This also resets the iterator after an error.
+    pub fn seek(&mut self, bit_pos: u64) {
+        self.bits.seek(bit_pos);
+        self.failed = false;
+    }
+}
+
+impl Iterator for CueStream<'_> {
+    type Item = Result<Noun>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // The source never includes trailing zero bits, so only zero bits remain exactly when no
+        // bits remain.
+        if self.failed || self.bits.remaining() == 0 {
+            return None;
+        }
+        let start = self.bits.pos_u64();
+        match decode(&mut self.bits, &CueLimits::UNLIMITED) {
+            Ok(noun) => {
+                if self.byte_aligned {
+                    let u8_bits = u64::from(u8::BITS);
+                    self.bits
+                        .seek(self.bits.pos_u64().next_multiple_of(u8_bits));
+                }
+                Some(Ok(noun))
+            }
+            Err(err) => {
+                self.bits.seek(start);
+                self.failed = true;
+                Some(Err(err))
+            }
+        }
+    }
+}
+
+/// A [`BitSource`] over the bytes fed to a [`CueMachine`], less the bytes that have been read in
+/// full.
+#[derive(Default)]
+struct BufSource {
+    bytes: Vec<u8>,
+    /// Number of bytes discarded from the front of `bytes`.
+    discarded: u64,
+    /// Number of bits read from the source so far.
+    bit_idx: u64,
+}
+
+impl BufSource {
+    /// Returns the position of the next bit within `bytes`.
+    fn buf_idx(&self) -> u64 {
+        self.bit_idx - self.discarded * u64::from(u8::BITS)
+    }
+
+    /// Discards the bytes that have been read in full, if they make up at least half of `bytes`,
+    /// which keeps the cost of discarding them linear in the number of bytes fed.
+    fn discard_read(&mut self) {
+        let read = (self.buf_idx() / u64::from(u8::BITS)) as usize;
+        if read > 0 && read * 2 >= self.bytes.len() {
+            self.bytes.drain(..read);
+            self.discarded += read as u64;
+        }
+    }
+}
+
+impl BitSource for BufSource {
+    fn pos(&self) -> u64 {
+        self.bit_idx
+    }
+
+    fn next_bit(&mut self) -> Result<Option<bool>> {
+        let idx = self.buf_idx();
+        let Some(&byte) = self.bytes.get((idx / u64::from(u8::BITS)) as usize) else {
+            return Ok(None);
+        };
+        self.bit_idx += 1;
+        Ok(Some(byte & (1 << (idx % u64::from(u8::BITS))) != 0))
+    }
+
+    fn next_bits(&mut self, len: u64) -> Result<Option<Atom>> {
+        let idx = self.buf_idx();
+        if len > self.bytes.len() as u64 * u64::from(u8::BITS) - idx {
+            return Ok(None);
+        }
+        let atom = Atom::from(copy_bits(&self.bytes, idx, len));
+        self.bit_idx += len;
+        Ok(Some(atom))
+    }
+}
+
+/// The result of feeding bytes to a [`CueMachine`].
+#[derive(Debug, Eq, PartialEq)]
+pub enum Status {
+    /// The jammed noun hasn't ended yet.
+    NeedMore,
+    /// The jammed noun ended.
+    Done {
+        /// The cued noun.
+        noun: Noun,
+        /// The number of bytes at the end of the bytes just fed that follow the byte holding the
+        /// last bit of the jammed noun.
+        trailing_bytes: usize,
+    },
+}
+
+/// A decoder of a jammed noun that's fed bytes as they arrive, for callers that can't hand the
+/// decoder an [`io::Read`], such as a server reading from a non-blocking socket.
+///
+/// The machine never does any I/O itself. Every call to [`feed()`](Self::feed()) decodes as much
+/// of the jammed noun as the bytes fed so far hold, keeping the work stack and the backreference
+/// cache between calls, and buffering only the bytes of the atom or backreference that was cut
+/// off, if any. The limits are enforced as decoding goes, so a jammed noun that exceeds them fails
+/// as soon as it's fed far enough to tell.
+///
+/// Once the machine returns [`Status::Done`] or an error, it starts over on a new jammed noun, so
+/// nouns sent back to back can be cued by feeding the trailing bytes back in.
+///
+/// With the `tokio` feature, `Noun::cue_from_async_reader()` wraps a machine, feeding it each chunk of bytes as it
+/// arrives. [`Noun::cue_from_reader()`] doesn't need to: a blocking reader can be asked for
+/// exactly the bits the decoder needs next, so it decodes straight from the reader without
+/// buffering the bytes of an atom that's cut off and decoding its length again.
+///
+/// [`Noun::cue_from_reader()`]: crate::Noun::cue_from_reader()
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{cell::Cell, serdes::{CueLimits, CueMachine, Status}, Noun};
+/// let mut machine = CueMachine::new(&CueLimits::default());
+/// assert_eq!(machine.feed(&[0x09]).unwrap(), Status::NeedMore);
+/// assert_eq!(
+///     machine.feed(&[0x9b, 0x02]).unwrap(),
+///     Status::Done {
+///         noun: Noun::from(Cell::from([0u8, 19u8])),
+///         trailing_bytes: 1,
+///     }
+/// );
+/// ```
+pub struct CueMachine {
+    limits: CueLimits,
+    bits: BufSource,
+    /// The decoder's state, which is moved into a [`Decoder`] for the duration of each call to
+    /// [`feed()`](Self::feed()).
+    cache: PosCache,
+    stack: Vec<Frame>,
+    nodes: u64,
+    atom_bytes: u64,
+    /// Where the jammed noun ended unexpectedly the last time the bytes fed ran out.
+    eof: Option<Position>,
+}
+
+impl CueMachine {
+    /// Creates a machine that cues a single jammed noun at a time, failing if decoding a noun
+    /// would exceed any of `limits`.
+    pub fn new(limits: &CueLimits) -> Self {
+        Self {
+            limits: *limits,
+            bits: BufSource::default(),
+            cache: PosCache {
+                entries: Some(Vec::new()),
+            },
+            stack: Vec::new(),
+            nodes: 0,
+            atom_bytes: 0,
+            eof: None,
+        }
+    }
+
+    /// Feeds the next bytes of the jammed noun to this machine, returning the noun once it's
+    /// ended.
+    pub fn feed(&mut self, bytes: &[u8]) -> Result<Status> {
+        self.bits.bytes.extend_from_slice(bytes);
+        let decoded = {
+            let mut decoder = Decoder {
+                bits: &mut self.bits,
+                start: 0,
+                limits: &self.limits,
+                cache: mem::replace(&mut self.cache, PosCache { entries: None }),
+                stack: mem::take(&mut self.stack),
+                nodes: self.nodes,
+                atom_bytes: self.atom_bytes,
+                canon: None,
+                shared: None,
+                progress: None,
+                stats: CueStats::default(),
+            };
+            let decoded = loop {
+                let (pos, nodes, atom_bytes) =
+                    (decoder.bits.pos(), decoder.nodes, decoder.atom_bytes);
+                match decoder.decode_next() {
+                    Ok(Some(noun)) => break Ok(Some(noun)),
+                    Ok(None) => {}
+                    // Nothing but what's been charged against the limits changes before the
+                    // bits of an atom, cell, or backreference run out, so it can be decoded
+                    // again from the start once more bits are fed.
+                    Err(Error::UnexpectedEof(eof)) => {
+                        self.eof = Some(eof);
+                        decoder.bits.bit_idx = pos;
+                        decoder.nodes = nodes;
+                        decoder.atom_bytes = atom_bytes;
+                        break Ok(None);
+                    }
+                    Err(err) => break Err(err),
+                }
+            };
+            (self.cache, self.stack) = (decoder.cache, decoder.stack);
+            (self.nodes, self.atom_bytes) = (decoder.nodes, decoder.atom_bytes);
+            decoded
+        };
+
+        match decoded {
+            Ok(None) => {
+                self.bits.discard_read();
+                Ok(Status::NeedMore)
+            }
+            Ok(Some(noun)) => {
+                let end = self.bits.bit_idx.div_ceil(u64::from(u8::BITS)) - self.bits.discarded;
+                let trailing_bytes = self.bits.bytes.len() - end as usize;
+                *self = Self::new(&self.limits);
+                Ok(Status::Done {
+                    noun: unwrap_root(noun),
+                    trailing_bytes,
+                })
+            }
+            Err(err) => {
+                *self = Self::new(&self.limits);
+                Err(err)
+            }
+        }
+    }
+
+    /// Returns the error that the jammed noun fails with if no more bytes are fed to this machine,
+    /// which is an [`Error::UnexpectedEof`] unless the bytes fed so far are already corrupt, and
+    /// starts over on a new jammed noun.
+    #[cfg(feature = "tokio")]
+    pub(crate) fn truncated(&mut self) -> Error {
+        let err = match self.feed(&[]) {
+            Ok(Status::NeedMore) => {
+                Error::UnexpectedEof(self.eof.expect("decoding ran out of bytes"))
+            }
+            Ok(Status::Done { .. }) => unreachable!("a jammed noun ended without more bytes"),
+            Err(err) => return err,
+        };
+        *self = Self::new(&self.limits);
+        err
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::serdes::Jam;
+
+    /// A [`BitSource`] that reads a bit at a time, which is how atoms were decoded before whole
+    /// bytes were copied at a time.
+    struct BitwiseSource<S: BitSource>(S);
+
+    impl<S: BitSource> BitSource for BitwiseSource<S> {
+        fn pos(&self) -> u64 {
+            self.0.pos()
+        }
+
+        fn next_bit(&mut self) -> Result<Option<bool>> {
+            self.0.next_bit()
+        }
+    }
+
+    /// A xorshift PRNG.
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        /// Generates a random noun with at most `nodes` atoms and cells.
+        fn noun(&mut self, nodes: u64) -> Noun {
+            if nodes < 3 || self.next().is_multiple_of(3) {
+                let len = match self.next() % 4 {
+                    0 => 0,
+                    1 => self.next() % 4,
+                    2 => self.next() % 32,
+                    _ => self.next() % 1024,
+                };
+                let bytes: Vec<u8> = (0..len).map(|_| self.next() as u8).collect();
+                Noun::from(Atom::from(bytes))
+            } else {
+                let head_nodes = self.next() % (nodes - 1);
+                let head = self.noun(head_nodes);
+                // Repeat the head sometimes so that backreferences are exercised.
+                let tail = if self.next().is_multiple_of(4) {
+                    head.clone()
+                } else {
+                    self.noun(nodes - 1 - head_nodes)
+                };
+                Noun::from(Cell::from([head, tail]))
+            }
+        }
+    }
+
+    /// Checks that decoding from `bits` by copying whole bytes at a time gives the same result as
+    /// decoding a bit at a time from `bitwise`.
+    fn check_source<S: BitSource>(mut bits: S, bitwise: S) -> Result<Noun> {
+        let bytewise = decode(&mut bits, &CueLimits::UNLIMITED);
+        let bitwise = decode(&mut BitwiseSource(bitwise), &CueLimits::UNLIMITED);
+        match (&bitwise, &bytewise) {
+            (Ok(bitwise), Ok(bytewise)) => assert_eq!(bitwise, bytewise),
+            (Err(bitwise), Err(bytewise)) => assert_eq!(bitwise.to_string(), bytewise.to_string()),
+            results => panic!("decoders disagree: {:?}", results),
+        }
+        bytewise
+    }
+
+    /// Decodes `bytes` from both an atom and a reader, checking each against decoding a bit at a
+    /// time.
+    fn check(bytes: &[u8]) -> Result<Noun> {
+        let _ = check_source(ReadSource::new(bytes), ReadSource::new(bytes));
+        check_source(AtomIter::from_bytes(bytes), AtomIter::from_bytes(bytes))
+    }
+
+    #[test]
+    fn next_bits() {
+        let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+
+        // Random nouns.
+        for _ in 0..500 {
+            let nodes = rng.next() % 64;
+            let noun = rng.noun(nodes);
+            let jammed_noun = noun.clone().jam();
+            assert_eq!(check(jammed_noun.as_bytes()).expect("decode"), noun);
+
+            // Truncated and corrupted.
+            let bytes = jammed_noun.as_bytes();
+            if !bytes.is_empty() {
+                let len = (rng.next() as usize) % bytes.len();
+                let _ = check(&bytes[..len]);
+                let mut bytes = bytes.to_vec();
+                let idx = (rng.next() as usize) % bytes.len();
+                bytes[idx] ^= 1 << (rng.next() % 8);
+                let _ = check(&bytes);
+            }
+        }
+
+        // Random garbage.
+        for _ in 0..2_000 {
+            let len = (rng.next() % 64) as usize;
+            let bytes: Vec<u8> = (0..len).map(|_| rng.next() as u8).collect();
+            let _ = check(&bytes);
+        }
+    }
+
+    #[test]
+    fn cue_stream() {
+        let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+        let mut nouns = vec![
+            Noun::from(Atom::from(0u8)),
+            Noun::from(Atom::from(1u8)),
+            Noun::from(Atom::from(19u8)),
+            Noun::from(Cell::from([0u8, 19u8])),
+            Noun::from(Cell::from([1u8, 1u8])),
+            Noun::from(Cell::from([10_000u16, 10_000u16])),
+            Noun::from(Cell::from([999_999_999u32, 999_999_999u32])),
+            Noun::from(Cell::from([222u16, 444u16, 888u16])),
+        ];
+        for _ in 0..20 {
+            let nodes = rng.next() % 64;
+            nouns.push(rng.noun(nodes));
+        }
+
+        // Packed bit by bit.
+        {
+            let mut bits = Atom::builder();
+            let mut ends = Vec::new();
+            for noun in &nouns {
+                bits.push_atom(&noun.clone().jam());
+                ends.push(bits.pos() as u64);
+            }
+            let source = bits.into_atom();
+            let mut stream = CueStream::new(&source);
+            for (noun, end) in nouns.iter().zip(&ends) {
+                assert_eq!(stream.next().expect("noun").expect("cue"), *noun);
+                assert_eq!(stream.bit_pos(), *end);
+            }
+            assert!(stream.next().is_none());
+
+            // Resume from a checkpoint.
+            stream.seek(ends[3]);
+            let rest: Vec<Noun> = stream.collect::<Result<_>>().expect("cue");
+            assert_eq!(rest, nouns[4..]);
+        }
+
+        // Byte aligned, followed by zero bytes.
+        {
+            let mut bytes = Vec::new();
+            for noun in &nouns {
+                noun.jam_to_writer(&mut bytes).expect("jam");
+            }
+            bytes.extend([0; 4]);
+            let stream = CueStream::from_bytes(&bytes);
+            let cued: Vec<Noun> = stream.collect::<Result<_>>().expect("cue");
+            assert_eq!(cued, nouns);
+        }
+
+        // Nothing at all.
+        assert!(CueStream::new(&Atom::from(0u8)).next().is_none());
+        assert!(CueStream::from_bytes(&[0, 0]).next().is_none());
+
+        // A truncated final noun.
+        {
+            let mut bytes = nouns[3].jam_to_vec();
+            let start = bytes.len() as u64 * 8;
+            bytes.extend(nouns[5].jam_to_vec());
+            bytes.pop();
+            let mut stream = CueStream::from_bytes(&bytes);
+            assert_eq!(stream.next().expect("noun").expect("cue"), nouns[3]);
+            assert!(matches!(stream.next(), Some(Err(Error::UnexpectedEof(_)))));
+            assert_eq!(stream.bit_pos(), start);
+            assert!(stream.next().is_none());
+        }
+    }
+
+    #[test]
+    fn cue_many() {
+        // Events that share paths jam to less together than apart, and the paths are shared again
+        // once cued.
+        {
+            let paths: Vec<_> = ["hood", "dojo", "spider"]
+                .into_iter()
+                .map(|agent| Rc::new(Noun::from(Cell::from(["gall", "use", agent, ""]))))
+                .collect();
+            let events: Vec<_> = (0..300u32)
+                .map(|i| {
+                    Rc::new(Noun::from(Cell::from([
+                        paths[i as usize % 3].clone(),
+                        Rc::new(Noun::from(Atom::from(i))),
+                    ])))
+                })
+                .collect();
+            let jammed = super::super::jam_many(&events);
+            let separately: usize = events.iter().map(|noun| noun.jam_to_vec().len()).sum();
+            assert!(jammed.as_bytes().len() < separately / 2);
+
+            let cued = super::cue_many(jammed, events.len()).expect("cue");
+            assert_eq!(cued, events);
+            let Noun::Cell(first) = &*cued[0] else {
+                panic!("cell");
+            };
+            let Noun::Cell(fourth) = &*cued[3] else {
+                panic!("cell");
+            };
+            assert!(Rc::ptr_eq(&first.head(), &fourth.head()));
+        }
+
+        // Random nouns, the first of which is jammed just as it would be on its own.
+        {
+            let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+            let nouns: Vec<_> = (0..30)
+                .map(|_| {
+                    let nodes = rng.next() % 64;
+                    Rc::new(rng.noun(nodes))
+                })
+                .collect();
+            let jammed = super::super::jam_many(&nouns);
+            let first = nouns[0].jam_to_vec();
+            assert_eq!(
+                jammed.as_bytes()[..first.len() - 1],
+                first[..first.len() - 1]
+            );
+            assert_eq!(super::cue_many(jammed, nouns.len()).expect("cue"), nouns);
+        }
+
+        // No nouns at all.
+        {
+            let jammed = super::super::jam_many(&[]);
+            assert!(jammed.is_null());
+            assert!(super::cue_many(jammed, 0).expect("cue").is_empty());
+        }
+
+        // Asking for more nouns than there are, or fewer.
+        {
+            let nouns = [
+                Rc::new(Noun::from(Cell::from([1u8, 2]))),
+                Rc::new(Noun::from(Cell::from([1u8, 2]))),
+            ];
+            let jammed = super::super::jam_many(&nouns);
+            assert!(matches!(
+                super::cue_many(jammed.clone(), 3),
+                Err(Error::UnexpectedEof(_))
+            ));
+            assert!(matches!(
+                super::cue_many(jammed.clone(), 1),
+                Err(Error::TrailingData { .. })
+            ));
+            assert_eq!(super::cue_many(jammed, 2).expect("cue"), nouns);
+        }
+    }
+
+    #[test]
+    fn decode_canonical() {
+        // Everything jammed is canonical.
+        {
+            let mut rng = Rng(0x853c_49e6_748f_ea9b);
+            for _ in 0..500 {
+                let nodes = rng.next() % 64;
+                let noun = rng.noun(nodes);
+                let jammed_noun = noun.clone().jam();
+                assert_eq!(Noun::cue_canonical(&jammed_noun).expect("cue"), noun);
+            }
+            for noun in [
+                Noun::from(Atom::from(0u8)),
+                Noun::from(Cell::from([0u8, 19u8])),
+                Noun::from(Cell::from([1u8, 1u8])),
+                Noun::from(Cell::from([10_000u16, 10_000u16])),
+                Noun::from(Cell::from([999_999_999u32, 999_999_999u32])),
+                Noun::from(Cell::from([222u16, 444u16, 888u16])),
+            ] {
+                assert!(Noun::is_canonical_jam(&noun.jam()));
+            }
+        }
+
+        // Hand-built encodings that decode successfully but aren't canonical.
+        let cases: [(u64, Noun, NonCanonicalReason, u64); 7] = [
+            // [19 0] with 19 given a 6-bit length.
+            (
+                0b1_0010_0111_0100_0001,
+                Noun::from(Cell::from([19u8, 0u8])),
+                NonCanonicalReason::NonMinimalLen,
+                2,
+            ),
+            // [[1.000 1.000] 0] with the backreference index 4 given a 4-bit length.
+            (
+                0x12_11fd_0a05,
+                Noun::from(Cell::from([
+                    Noun::from(Cell::from([1_000u16, 1_000u16])),
+                    Noun::from(Atom::from(0u8)),
+                ])),
+                NonCanonicalReason::NonMinimalLen,
+                23,
+            ),
+            // [[1 2] [1 2]] with the tail encoded in full.
+            (
+                0x918_c8c5,
+                Noun::from(Cell::from([
+                    Noun::from(Cell::from([1u8, 2u8])),
+                    Noun::from(Cell::from([1u8, 2u8])),
+                ])),
+                NonCanonicalReason::MissingBackref,
+                15,
+            ),
+            // [1.000 1.000] with the tail encoded in full.
+            (
+                0xfa_141f_4281,
+                Noun::from(Cell::from([1_000u16, 1_000u16])),
+                NonCanonicalReason::MissingBackref,
+                21,
+            ),
+            // [1 1] with the tail as a backreference to the head.
+            (
+                0x24f1,
+                Noun::from(Cell::from([1u8, 1u8])),
+                NonCanonicalReason::NeedlessBackref,
+                6,
+            ),
+            // [0 5 5 5] with the last 5 as a backreference to the second rather than the first.
+            (
+                0x10_63b8_6e19,
+                Noun::from(Cell::from([0u8, 5u8, 5u8, 5u8])),
+                NonCanonicalReason::NotFirstOccurrence,
+                24,
+            ),
+            // 19 followed by a set bit.
+            (
+                0x10_09b0,
+                Noun::from(Atom::from(19u8)),
+                NonCanonicalReason::TrailingData,
+                12,
+            ),
+        ];
+        for (jammed_noun, noun, expected_reason, expected_bit_pos) in cases {
+            let jammed_noun = Atom::from(jammed_noun);
+            if expected_reason != NonCanonicalReason::TrailingData {
+                assert_eq!(Noun::cue_strict(&jammed_noun).expect("cue"), noun);
+            }
+            match Noun::cue_canonical(&jammed_noun) {
+                Err(Error::NonCanonical { reason, bit_pos }) => {
+                    assert_eq!(reason, expected_reason);
+                    assert_eq!(bit_pos, expected_bit_pos);
+                }
+                res => panic!("unexpected result: {:?}", res),
+            }
+            assert!(!Noun::is_canonical_jam(&jammed_noun));
+        }
+    }
+
+    #[test]
+    fn backrefs() {
+        // Backreferences resolve to the noun at their index, and only to complete nouns.
+        {
+            // [0 0] with the tail as a backreference to the head at index 2.
+            assert_eq!(
+                check(&2361u16.to_le_bytes()).expect("cue"),
+                Noun::from(Cell::from([0u8, 0u8]))
+            );
+            // [0 ^1], where index 1 is within the tag of the cell.
+            assert!(matches!(
+                check(&441u16.to_le_bytes()),
+                Err(Error::CacheMiss(_))
+            ));
+            // A cell whose head is a backreference to the cell itself.
+            assert!(matches!(check(&[29]), Err(Error::CacheMiss(_))));
+        }
+
+        // Nouns without backreferences can be cued without caching, but nouns with them can't.
+        {
+            let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+            for _ in 0..500 {
+                let nodes = rng.next() % 64;
+                let noun = rng.noun(nodes);
+                let jammed_noun = noun.jam_nodedup();
+                assert_eq!(Noun::cue_nodedup(&jammed_noun).expect("cue"), noun);
+            }
+
+            let noun = Noun::from(Cell::from([10_000u16, 10_000u16]));
+            assert!(matches!(
+                Noun::cue_nodedup(&noun.jam()),
+                Err(Error::CacheMiss(_))
+            ));
+            assert!(matches!(
+                Noun::cue_nodedup(&Atom::from(0b1010u8)),
+                Err(Error::TrailingData { bits: 2 })
+            ));
+        }
+    }
+
+    /// Feeds `chunks` to a new machine, checking that every chunk but the last needs more, and
+    /// returns the result of the last.
+    fn feed_chunks<'a>(chunks: impl IntoIterator<Item = &'a [u8]>) -> Result<Status> {
+        let mut machine = CueMachine::new(&CueLimits::UNLIMITED);
+        let mut status = Ok(Status::NeedMore);
+        for chunk in chunks {
+            assert!(matches!(status, Ok(Status::NeedMore)), "{:?}", status);
+            status = machine.feed(chunk);
+        }
+        status
+    }
+
+    #[test]
+    fn cue_machine() {
+        let mut rng = Rng(0x5851_f42d_4c95_7f2d);
+        let mut nouns = vec![
+            Noun::from(0u8),
+            Noun::from(Cell::from([0u8, 19u8])),
+            Noun::from(Cell::from([10_000u16, 10_000u16])),
+            Noun::from(Cell::from([
+                Atom::from(vec![0xa5; 1000]),
+                Atom::from(vec![0x5a; 1001]),
+                Atom::from(vec![0xa5; 1000]),
+            ])),
+        ];
+        nouns.extend((0..20).map(|_| rng.noun(200)));
+
+        for noun in &nouns {
+            let bytes = noun.jam_to_vec();
+            let done = Status::Done {
+                noun: noun.clone(),
+                trailing_bytes: 0,
+            };
+
+            // A byte at a time.
+            assert_eq!(feed_chunks(bytes.chunks(1)).expect("bytewise"), done);
+
+            // Split in two at every byte, including with an empty first chunk.
+            for i in 0..bytes.len() {
+                let (a, b) = bytes.split_at(i);
+                assert_eq!(feed_chunks([a, b]).expect("split"), done);
+            }
+
+            // Split at random.
+            for _ in 0..10 {
+                let mut chunks = Vec::new();
+                let mut rest = &bytes[..];
+                while !rest.is_empty() {
+                    let len = (rng.next() % 64) as usize;
+                    let (chunk, tail) = rest.split_at(len.min(rest.len()));
+                    chunks.push(chunk);
+                    rest = tail;
+                }
+                assert_eq!(feed_chunks(chunks).expect("random split"), done);
+            }
+
+            // Truncated input never completes.
+            {
+                let mut machine = CueMachine::new(&CueLimits::UNLIMITED);
+                for byte in &bytes[..bytes.len() - 1] {
+                    assert_eq!(machine.feed(&[*byte]).expect("truncated"), Status::NeedMore);
+                }
+                assert_eq!(machine.feed(&[]).expect("nothing more"), Status::NeedMore);
+            }
+        }
+
+        // Bytes past the end of a noun are trailing, and can be fed back in to cue the next noun.
+        {
+            let mut bytes = nouns[1].jam_to_vec();
+            bytes.extend(nouns[2].jam_to_vec());
+            let mut machine = CueMachine::new(&CueLimits::UNLIMITED);
+            assert_eq!(machine.feed(&bytes[..1]).expect("first"), Status::NeedMore);
+            let Status::Done {
+                noun,
+                trailing_bytes,
+            } = machine.feed(&bytes[1..]).expect("first")
+            else {
+                panic!("first noun is done");
+            };
+            assert_eq!((noun, trailing_bytes), (nouns[1].clone(), bytes.len() - 2));
+            assert_eq!(
+                machine.feed(&bytes[2..]).expect("second"),
+                Status::Done {
+                    noun: nouns[2].clone(),
+                    trailing_bytes: 0,
+                }
+            );
+        }
+
+        // Limits are enforced as soon as the bytes fed show they're exceeded, not once the noun
+        // ends, and the machine starts over afterward.
+        {
+            let limits = CueLimits {
+                max_atom_bytes: 100,
+                ..CueLimits::UNLIMITED
+            };
+            let bytes = nouns[3].jam_to_vec();
+            let mut machine = CueMachine::new(&limits);
+            assert!(matches!(
+                machine.feed(&bytes[..10]),
+                Err(Error::LimitExceeded {
+                    which: Limit::AtomBytes,
+                    limit: 100,
+                })
+            ));
+            assert_eq!(
+                machine.feed(&nouns[1].jam_to_vec()).expect("next noun"),
+                Status::Done {
+                    noun: nouns[1].clone(),
+                    trailing_bytes: 0,
+                }
+            );
+        }
+    }
+}
@@ -0,0 +1,855 @@
+//! The jam encoder shared by every [`Jam`](super::Jam) implementation.
+
+use super::{trace::OpSpan, HashMap, HashSet, JamProgress, JamStats, NounHasher, Progress};
+use crate::{
+    atom::{Atom, Builder as AtomBuilder},
+    noun::Noun,
+    Rc,
+};
+use std::{
+    collections::hash_map::Entry,
+    convert::Infallible,
+    hash::{Hash, Hasher},
+    io::{self, Write},
+    ops::ControlFlow,
+    ptr,
+};
+
+/// A destination for the bits of a jammed noun.
+pub(crate) trait BitSink {
+    /// The type of error returned when a bit cannot be written.
+    type Error;
+
+    /// Returns the number of bits written to this sink so far.
+    fn pos(&self) -> u64;
+
+    /// Writes a single bit to the end of this sink.
+    fn push_bit(&mut self, bit: bool) -> Result<(), Self::Error>;
+
+    /// Writes the `len` least significant bits of `bits` to the end of this sink, starting with
+    /// the least significant bit.
+    fn push_u64(&mut self, mut bits: u64, len: u32) -> Result<(), Self::Error> {
+        for _ in 0..len {
+            self.push_bit(bits & 1 != 0)?;
+            bits >>= 1;
+        }
+        Ok(())
+    }
+
+    /// Writes the bits of `atom` to the end of this sink, starting with the least significant
+    /// bit.
+    fn push_atom(&mut self, atom: &Atom) -> Result<(), Self::Error> {
+        for bit in atom.iter() {
+            self.push_bit(bit)?;
+        }
+        Ok(())
+    }
+
+    /// Hints that at least `bit_len` more bits are about to be written to this sink.
+    fn reserve(&mut self, _bit_len: u64) {}
+}
+
+impl BitSink for AtomBuilder {
+    type Error = Infallible;
+
+    fn pos(&self) -> u64 {
+        self.pos_u64()
+    }
+
+    fn push_bit(&mut self, bit: bool) -> Result<(), Self::Error> {
+        self.push_bit(bit);
+        Ok(())
+    }
+
+    fn push_u64(&mut self, bits: u64, len: u32) -> Result<(), Self::Error> {
+        self.push_bits(bits, len);
+        Ok(())
+    }
+
+    fn push_atom(&mut self, atom: &Atom) -> Result<(), Self::Error> {
+        self.push_atom(atom);
+        Ok(())
+    }
+
+    fn reserve(&mut self, bit_len: u64) {
+        self.reserve_u64(bit_len);
+    }
+}
+
+/// A [`BitSink`] that discards bits, only counting how many were written.
+#[derive(Default)]
+pub(crate) struct SizeSink {
+    bit_len: u64,
+}
+
+impl SizeSink {
+    /// Returns the number of bits written to this sink.
+    pub(crate) fn bit_len(&self) -> u64 {
+        self.bit_len
+    }
+}
+
+impl BitSink for SizeSink {
+    type Error = Infallible;
+
+    fn pos(&self) -> u64 {
+        self.bit_len
+    }
+
+    fn push_bit(&mut self, _bit: bool) -> Result<(), Self::Error> {
+        self.bit_len += 1;
+        Ok(())
+    }
+
+    fn push_u64(&mut self, _bits: u64, len: u32) -> Result<(), Self::Error> {
+        self.bit_len += u64::from(len);
+        Ok(())
+    }
+
+    fn push_atom(&mut self, atom: &Atom) -> Result<(), Self::Error> {
+        self.bit_len += atom.bit_len_u64();
+        Ok(())
+    }
+}
+
+/// The error returned by a [`CappedSink`] when a write would take it past its cap.
+#[derive(Debug)]
+pub(crate) struct CapExceeded;
+
+/// A [`BitSink`] that builds an atom, failing instead of writing more than `max_bits` bits.
+///
+/// Every write is checked before it's made, so the builder never holds more than `max_bits` bits,
+/// not even partway through a large atom.
+pub(crate) struct CappedSink {
+    builder: AtomBuilder,
+    max_bits: u64,
+}
+
+impl CappedSink {
+    /// Creates an empty sink that holds at most `max_bits` bits.
+    pub(crate) fn new(max_bits: u64) -> Self {
+        Self {
+            builder: AtomBuilder::new(),
+            max_bits,
+        }
+    }
+
+    /// Converts this sink into the atom of the bits written to it, consuming the sink.
+    pub(crate) fn into_atom(self) -> Atom {
+        self.builder.into_atom()
+    }
+
+    /// Fails if writing `len` more bits would exceed the cap.
+    fn check(&self, len: u64) -> Result<(), CapExceeded> {
+        if self.builder.pos_u64().saturating_add(len) > self.max_bits {
+            Err(CapExceeded)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl BitSink for CappedSink {
+    type Error = CapExceeded;
+
+    fn pos(&self) -> u64 {
+        self.builder.pos_u64()
+    }
+
+    fn push_bit(&mut self, bit: bool) -> Result<(), Self::Error> {
+        self.check(1)?;
+        self.builder.push_bit(bit);
+        Ok(())
+    }
+
+    fn push_u64(&mut self, bits: u64, len: u32) -> Result<(), Self::Error> {
+        self.check(u64::from(len))?;
+        self.builder.push_bits(bits, len);
+        Ok(())
+    }
+
+    fn push_atom(&mut self, atom: &Atom) -> Result<(), Self::Error> {
+        self.check(atom.bit_len_u64())?;
+        self.builder.push_atom(atom);
+        Ok(())
+    }
+
+    fn reserve(&mut self, bit_len: u64) {
+        // Never allocate room for bits that would exceed the cap.
+        let room = self.max_bits.saturating_sub(self.builder.pos_u64());
+        self.builder.reserve_u64(bit_len.min(room));
+    }
+}
+
+/// The number of bytes [`WriteSink`] buffers before writing them to the underlying writer.
+const WRITE_BUF_LEN: usize = 4096;
+
+/// A [`BitSink`] that packs bits into bytes and writes them to an [`io::Write`] in fixed-size
+/// chunks, which bounds how much of a jammed noun is held in memory at once.
+pub(crate) struct WriteSink<W: Write> {
+    writer: W,
+    /// Bytes that have been filled but not yet written.
+    buf: [u8; WRITE_BUF_LEN],
+    /// Number of filled bytes in `buf`.
+    buf_len: usize,
+    /// Byte that is currently being filled.
+    byte: u8,
+    /// Number of bits written to the sink so far.
+    bit_idx: u64,
+}
+
+impl<W: Write> WriteSink<W> {
+    /// Creates a new sink that writes to `writer`.
+    pub(crate) fn new(writer: W) -> Self {
+        Self {
+            writer,
+            buf: [0; WRITE_BUF_LEN],
+            buf_len: 0,
+            byte: 0,
+            bit_idx: 0,
+        }
+    }
+
+    /// Writes a whole byte to the end of this sink.
+    fn push_byte(&mut self, byte: u8) -> io::Result<()> {
+        let shift = (self.bit_idx % u64::from(u8::BITS)) as u32;
+        self.byte |= byte << shift;
+        self.buf[self.buf_len] = self.byte;
+        self.buf_len += 1;
+        // The bits of `byte` that didn't fit in the byte that was just filled.
+        self.byte = byte.checked_shr(u8::BITS - shift).unwrap_or(0);
+        self.bit_idx += u64::from(u8::BITS);
+        if self.buf_len == WRITE_BUF_LEN {
+            self.writer.write_all(&self.buf)?;
+            self.buf_len = 0;
+        }
+        Ok(())
+    }
+
+    /// Writes the final partially filled byte (if any) and any buffered bytes to the underlying
+    /// writer, returning the total number of bytes written by this sink.
+    pub(crate) fn finish(mut self) -> io::Result<u64> {
+        self.write_remaining()?;
+        self.writer.flush()?;
+        Ok(self.bit_idx.div_ceil(u64::from(u8::BITS)))
+    }
+
+    /// Writes the final partially filled byte (if any) and any buffered bytes to the underlying
+    /// writer, after which nothing more should be written to this sink.
+    fn write_remaining(&mut self) -> io::Result<()> {
+        if !self.bit_idx.is_multiple_of(u64::from(u8::BITS)) {
+            // The unused high bits of the final byte are zero.
+            self.buf[self.buf_len] = self.byte;
+            self.buf_len += 1;
+        }
+        self.writer.write_all(&self.buf[..self.buf_len])?;
+        self.buf_len = 0;
+        Ok(())
+    }
+}
+
+impl<W: Write> BitSink for WriteSink<W> {
+    type Error = io::Error;
+
+    fn pos(&self) -> u64 {
+        self.bit_idx
+    }
+
+    fn push_bit(&mut self, bit: bool) -> Result<(), Self::Error> {
+        let shift = self.bit_idx % u64::from(u8::BITS);
+        if bit {
+            self.byte |= 1 << shift;
+        }
+        self.bit_idx += 1;
+        if shift == u64::from(u8::BITS) - 1 {
+            self.buf[self.buf_len] = self.byte;
+            self.buf_len += 1;
+            self.byte = 0;
+            if self.buf_len == WRITE_BUF_LEN {
+                self.writer.write_all(&self.buf)?;
+                self.buf_len = 0;
+            }
+        }
+        Ok(())
+    }
+
+    fn push_u64(&mut self, bits: u64, len: u32) -> Result<(), Self::Error> {
+        let bytes = bits.to_le_bytes();
+        let whole_bytes = (len / u8::BITS) as usize;
+        for &byte in &bytes[..whole_bytes] {
+            self.push_byte(byte)?;
+        }
+        let mut bits = bits.checked_shr(len - len % u8::BITS).unwrap_or(0);
+        for _ in 0..len % u8::BITS {
+            self.push_bit(bits & 1 != 0)?;
+            bits >>= 1;
+        }
+        Ok(())
+    }
+
+    fn push_atom(&mut self, atom: &Atom) -> Result<(), Self::Error> {
+        let bytes = atom.as_bytes();
+        let bit_len = atom.bit_len_u64();
+        let whole_bytes = usize::try_from(bit_len / u64::from(u8::BITS)).expect("u64 to usize");
+        for &byte in &bytes[..whole_bytes] {
+            self.push_byte(byte)?;
+        }
+        if let Some(&last) = bytes.get(whole_bytes) {
+            for i in 0..bit_len % u64::from(u8::BITS) {
+                self.push_bit(last & (1 << i) != 0)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A reader of the bytes of a jammed noun, as returned by [`Noun::jam_reader()`].
+///
+/// The noun is jammed lazily, as its bytes are read: only the encoder's stack, its backreference
+/// cache, and a small buffer of encoded bytes are held in memory, never the jammed noun in its
+/// entirety. The bytes read are identical to those returned by [`Noun::jam_to_vec()`].
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{cell::Cell, Noun};
+/// # use std::io::Read;
+/// let noun = Noun::from(Cell::from([0u8, 19u8]));
+/// let mut reader = noun.jam_reader();
+/// assert_eq!(reader.total_len(), 2);
+///
+/// let mut bytes = Vec::new();
+/// reader.read_to_end(&mut bytes).unwrap();
+/// assert_eq!(bytes, noun.jam_to_vec());
+/// ```
+pub struct JamReader<'a> {
+    noun: &'a Noun,
+    encoder: Encoder<'a>,
+    /// The encoded bytes are written to the `Vec` at the bottom of this sink, which is emptied
+    /// whenever it's been read in full.
+    sink: WriteSink<Vec<u8>>,
+    /// The number of bytes at the front of `sink.writer` that have already been read.
+    read: usize,
+    /// Whether the noun has been encoded in full.
+    done: bool,
+}
+
+impl<'a> JamReader<'a> {
+    /// Creates a reader of the bytes of `noun` jammed.
+    pub(crate) fn new(noun: &'a Noun) -> Self {
+        let mut sink = WriteSink::new(Vec::new());
+        let encoder = Encoder::new(noun, &mut sink, false);
+        Self {
+            noun,
+            encoder,
+            sink,
+            read: 0,
+            done: false,
+        }
+    }
+
+    /// Returns the total number of bytes this reader yields, including any already read, which is
+    /// what an HTTP `Content-Length` header should be set to.
+    ///
+    /// This computes the length with [`Noun::jam_size_bits()`], so it costs about as much time as
+    /// jamming the noun.
+    pub fn total_len(&self) -> u64 {
+        self.noun.jam_size_bits().div_ceil(u64::from(u8::BITS))
+    }
+}
+
+impl io::Read for JamReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            let pending = &self.sink.writer[self.read..];
+            if !pending.is_empty() || buf.is_empty() {
+                let len = pending.len().min(buf.len());
+                buf[..len].copy_from_slice(&pending[..len]);
+                self.read += len;
+                if self.read == self.sink.writer.len() {
+                    self.sink.writer.clear();
+                    self.read = 0;
+                }
+                return Ok(len);
+            }
+            if self.done {
+                return Ok(0);
+            }
+            if self.encoder.has_next(self.sink.pos()) {
+                self.encoder.encode_next(&mut self.sink)?;
+            } else {
+                self.sink.write_remaining()?;
+                self.done = true;
+            }
+        }
+    }
+}
+
+/// Returns the length in bits of `n`.
+fn bit_len(n: u64) -> u32 {
+    u64::BITS - n.leading_zeros()
+}
+
+/// Encodes a length.
+fn encode_len<S: BitSink>(len: u64, sink: &mut S) -> Result<(), S::Error> {
+    let len_of_len = bit_len(len);
+    sink.push_u64(0, len_of_len)?;
+    sink.push_bit(true)?;
+    if len_of_len != 0 {
+        // Don't write the most significant bit of the length because it's always 1.
+        sink.push_u64(len, len_of_len - 1)?;
+    }
+    Ok(())
+}
+
+/// Encodes an atom.
+fn encode_atom<S: BitSink>(atom: &Atom, sink: &mut S) -> Result<(), S::Error> {
+    // Atom tag = 0b0.
+    sink.push_bit(false)?;
+    encode_len(atom.bit_len_u64(), sink)?;
+    sink.push_atom(atom)
+}
+
+/// Encodes a backreference to the noun that was encoded at bit `idx`.
+fn encode_backref<S: BitSink>(idx: u64, sink: &mut S) -> Result<(), S::Error> {
+    // Backreference tag = 0b11.
+    sink.push_bit(true)?;
+    sink.push_bit(true)?;
+    encode_len(u64::from(bit_len(idx)), sink)?;
+    sink.push_u64(idx, bit_len(idx))
+}
+
+/// Adds a structural hash of every distinct (by address) subnoun of `noun` to `hashes`, returning
+/// the total length in bits of the distinct (by value) atoms of `noun` that weren't already hashed.
+///
+/// Each subnoun is hashed exactly once, no matter how many times it's shared, so this is linear in
+/// the number of allocations rather than in the logical size of `noun`.
+fn hash_subnouns(noun: &Noun, hashes: &mut HashMap<*const Noun, u64>) -> u64 {
+    let mut atom_hashes: HashSet<u64> = HashSet::default();
+    let mut atom_bit_len = 0;
+    let mut stack = vec![(noun, false)];
+    while let Some((noun, children_hashed)) = stack.pop() {
+        let addr = ptr::addr_of!(*noun);
+        if hashes.contains_key(&addr) {
+            continue;
+        }
+        let mut hasher = NounHasher::default();
+        match noun {
+            Noun::Atom(atom) => {
+                atom.as_bytes().hash(&mut hasher);
+                if atom_hashes.insert(hasher.finish()) {
+                    atom_bit_len += atom.bit_len_u64();
+                }
+            }
+            Noun::Cell(cell) if children_hashed => {
+                hasher.write_u64(hashes[&ptr::addr_of!(*cell.head_ref())]);
+                hasher.write_u64(hashes[&ptr::addr_of!(*cell.tail_ref())]);
+            }
+            Noun::Cell(cell) => {
+                stack.push((noun, true));
+                stack.push((cell.tail_ref(), false));
+                stack.push((cell.head_ref(), false));
+                continue;
+            }
+        }
+        hashes.insert(addr, hasher.finish());
+    }
+    atom_bit_len
+}
+
+/// A subnoun used as a key in the backreference cache.
+///
+/// Hashing and comparing a [`Noun`] directly traverses the entire noun, which makes every cache
+/// operation linear in the logical size of the subnoun (and exponential for subnouns that share
+/// structure internally). Keys instead carry the precomputed hash of their subnoun, and equality
+/// is checked by address first and only then structurally, without revisiting pairs of subnouns
+/// that have already been compared.
+struct Key<'a> {
+    noun: &'a Noun,
+    /// The hash of `noun`.
+    hash: u64,
+}
+
+impl<'a> Key<'a> {
+    fn new(noun: &'a Noun, hashes: &HashMap<*const Noun, u64>) -> Self {
+        let hash = hashes[&ptr::addr_of!(*noun)];
+        Self { noun, hash }
+    }
+}
+
+impl Hash for Key<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.hash);
+    }
+}
+
+impl PartialEq for Key<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        if self.hash != other.hash {
+            return false;
+        }
+        let mut compared: HashSet<(*const Noun, *const Noun)> = HashSet::default();
+        let mut stack = vec![(self.noun, other.noun)];
+        while let Some((a, b)) = stack.pop() {
+            if ptr::eq(a, b) || !compared.insert((a, b)) {
+                continue;
+            }
+            match (a, b) {
+                (Noun::Atom(a), Noun::Atom(b)) if a == b => {}
+                (Noun::Cell(a), Noun::Cell(b)) => {
+                    stack.push((a.tail_ref(), b.tail_ref()));
+                    stack.push((a.head_ref(), b.head_ref()));
+                }
+                _ => return false,
+            }
+        }
+        true
+    }
+}
+
+impl Eq for Key<'_> {}
+
+/// A jam in progress, which encodes a noun one atom, cell, or backreference at a time.
+///
+/// Between steps, the only state is the explicit stack of subnouns still to encode and the
+/// backreference cache, so a caller can stop after any step and pick up where it left off.
+pub(crate) struct Encoder<'a> {
+    /// The hash of every distinct (by address) subnoun of the noun being encoded.
+    hashes: HashMap<*const Noun, u64>,
+    /// The position of every distinct (by value) subnoun encoded so far.
+    cache: HashMap<Key<'a>, u64>,
+    /// The subnouns still to encode, next last. `None` marks the end of a cell's encoding, which
+    /// is only needed for `stats`.
+    stack: Vec<Option<&'a Noun>>,
+    /// The position of the sink when encoding started, which backreferences are relative to.
+    start: u64,
+    /// The number of atoms, cells, and backreferences encoded so far.
+    nodes: u64,
+    /// What's been encoded so far, if it's being recorded.
+    stats: Option<JamStats>,
+    /// The positions of the cells whose encodings haven't ended yet, and the length of the
+    /// encoding of every noun encoded in full keyed by its position, which are only kept for
+    /// `stats`.
+    open_cells: Vec<u64>,
+    lens: HashMap<u64, u64>,
+}
+
+impl<'a> Encoder<'a> {
+    /// Starts jamming `noun` into `sink` at the current position of `sink`, recording what's
+    /// encoded if `with_stats` is `true`.
+    pub(crate) fn new<S: BitSink>(noun: &'a Noun, sink: &mut S, with_stats: bool) -> Self {
+        let mut hashes = HashMap::default();
+        // Every distinct atom has to be written out at least once.
+        sink.reserve(hash_subnouns(noun, &mut hashes));
+        // There can't be more distinct subnouns than there are allocations.
+        let cache = HashMap::with_capacity_and_hasher(hashes.len(), Default::default());
+        Self {
+            hashes,
+            cache,
+            stack: vec![Some(noun)],
+            start: sink.pos(),
+            nodes: 0,
+            stats: with_stats.then(JamStats::default),
+            open_cells: Vec::new(),
+            lens: HashMap::default(),
+        }
+    }
+
+    /// Queues `noun` to be encoded after everything queued so far, with backreferences to the
+    /// nouns encoded before it.
+    fn push<S: BitSink>(&mut self, noun: &'a Noun, sink: &mut S) {
+        sink.reserve(hash_subnouns(noun, &mut self.hashes));
+        self.stack.push(Some(noun));
+    }
+
+    /// Returns `true` if there's anything left to encode, given the current position of the sink.
+    pub(crate) fn has_next(&mut self, pos: u64) -> bool {
+        while let Some(None) = self.stack.last() {
+            self.stack.pop();
+            let cell_pos = self.open_cells.pop().expect("open cell");
+            self.lens.insert(cell_pos, pos - self.start - cell_pos);
+        }
+        !self.stack.is_empty()
+    }
+
+    /// Returns how far encoding has gotten, given the current position of the sink.
+    fn progress(&self, pos: u64) -> JamProgress {
+        JamProgress {
+            bits_written: pos - self.start,
+            nodes: self.nodes,
+        }
+    }
+
+    /// Encodes the next atom, cell, or backreference into `sink`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`has_next()`](Self::has_next()) wasn't just called and didn't return `true`.
+    pub(crate) fn encode_next<S: BitSink>(&mut self, sink: &mut S) -> Result<(), S::Error> {
+        let noun = self.stack.pop().flatten().expect("noun to encode");
+        self.nodes += 1;
+        if let Some(stats) = &mut self.stats {
+            stats.max_depth = stats.max_depth.max(self.open_cells.len() as u64);
+        }
+        match self.cache.entry(Key::new(noun, &self.hashes)) {
+            Entry::Occupied(entry) => {
+                let idx = *entry.get();
+                match noun {
+                    // Backreferences to atoms are only encoded if their index is shorter than the
+                    // atom they would reference. This intentionally ignores the differing tag and
+                    // length overheads of the two encodings because that's the comparison Hoon's
+                    // `++jam` makes (`(lte (met 0 a) (met 0 u.c))`), and jam's output has to be
+                    // byte-identical to Hoon's.
+                    Noun::Atom(atom) if atom.bit_len_u64() <= u64::from(bit_len(idx)) => {
+                        encode_atom(atom, sink)?;
+                        if let Some(stats) = &mut self.stats {
+                            stats.atoms += 1;
+                        }
+                    }
+                    _ => {
+                        let pos = sink.pos();
+                        encode_backref(idx, sink)?;
+                        if let Some(stats) = &mut self.stats {
+                            stats.backrefs += 1;
+                            stats.backref_bits_saved +=
+                                self.lens[&idx] as i64 - (sink.pos() - pos) as i64;
+                        }
+                    }
+                }
+                return Ok(());
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(sink.pos() - self.start);
+            }
+        }
+
+        let pos = sink.pos() - self.start;
+        match noun {
+            Noun::Atom(atom) => {
+                encode_atom(atom, sink)?;
+                if let Some(stats) = &mut self.stats {
+                    stats.atoms += 1;
+                    self.lens.insert(pos, sink.pos() - self.start - pos);
+                }
+            }
+            Noun::Cell(cell) => {
+                // Cell tag = 0b01.
+                sink.push_bit(true)?;
+                sink.push_bit(false)?;
+                if let Some(stats) = &mut self.stats {
+                    stats.cells += 1;
+                    self.open_cells.push(pos);
+                    self.stack.push(None);
+                }
+                self.stack.push(Some(cell.tail_ref()));
+                self.stack.push(Some(cell.head_ref()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Finishes encoding, returning what was encoded if it was being recorded, given the final
+    /// position of the sink.
+    fn finish(self, pos: u64) -> Option<JamStats> {
+        let cache_entries = self.cache.len() as u64;
+        self.stats.map(|stats| JamStats {
+            bits: pos - self.start,
+            cache_entries,
+            ..stats
+        })
+    }
+}
+
+/// Jams `noun` into `sink`, starting at the current position of `sink`.
+///
+/// Backreferences are relative to the position of `sink` when this function is called. The noun
+/// is traversed with an explicit stack rather than recursively so that arbitrarily deep nouns can
+/// be jammed.
+///
+/// Any subnoun that's equal to a previously encoded subnoun is replaced by a backreference (or by
+/// the atom itself if that's shorter), whether or not the two subnouns share an allocation.
+pub(crate) fn encode<S: BitSink>(noun: &Noun, sink: &mut S) -> Result<(), S::Error> {
+    encode_with_progress(noun, sink, None, None).map(|_| ())
+}
+
+/// Jams `noun` into `sink` like [`encode()`], counting every atom, cell, and backreference as a
+/// step of `progress` and stopping early if `progress` breaks, and recording what was encoded in
+/// `stats`.
+pub(crate) fn encode_with_progress<S: BitSink>(
+    noun: &Noun,
+    sink: &mut S,
+    mut progress: Option<&mut Progress<JamProgress>>,
+    stats: Option<&mut JamStats>,
+) -> Result<ControlFlow<()>, S::Error> {
+    let span = OpSpan::jam();
+    let mut encoder = Encoder::new(noun, sink, stats.is_some());
+    while encoder.has_next(sink.pos()) {
+        if let Some(progress) = &mut progress {
+            let flow = progress.step(|| encoder.progress(sink.pos()));
+            if flow.is_break() {
+                return Ok(flow);
+            }
+        }
+        encoder.encode_next(sink)?;
+    }
+    span.record("bits", sink.pos() - encoder.start);
+    span.record("nodes", encoder.nodes);
+    if let (Some(stats), Some(encoded)) = (stats, encoder.finish(sink.pos())) {
+        *stats = encoded;
+    }
+    Ok(ControlFlow::Continue(()))
+}
+
+/// Jams `nouns` into `sink` one after another, starting at the current position of `sink`, with a
+/// single backreference cache so that each noun can reference the nouns before it.
+pub(crate) fn encode_many<S: BitSink>(nouns: &[Rc<Noun>], sink: &mut S) -> Result<(), S::Error> {
+    let Some((first, rest)) = nouns.split_first() else {
+        return Ok(());
+    };
+    let span = OpSpan::jam();
+    let mut encoder = Encoder::new(first, sink, false);
+    let mut rest = rest.iter();
+    loop {
+        while encoder.has_next(sink.pos()) {
+            encoder.encode_next(sink)?;
+        }
+        match rest.next() {
+            Some(noun) => encoder.push(noun, sink),
+            None => break,
+        }
+    }
+    span.record("bits", sink.pos() - encoder.start);
+    span.record("nodes", encoder.nodes);
+    Ok(())
+}
+
+/// Serializes ("jams") `nouns` one after another into a single atom, with a backreference cache
+/// shared by all of them.
+///
+/// Each noun is encoded just as [`Noun::jam_to_vec()`] would encode it, immediately after the last
+/// bit of the noun before it, except that any of its subnouns equal to a subnoun of an earlier
+/// noun is replaced by a backreference to it. Nouns that share structure with each other, like
+/// events that refer to the same paths, therefore jam to less than the sum of their individual
+/// jams. Backreferences are relative to the start of the atom, so only [`cue_many()`] can decode
+/// the nouns after the first.
+///
+/// [`cue_many()`]: super::cue_many()
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{cell::Cell, serdes, Noun, Rc};
+/// let path = Rc::new(Noun::from(Cell::from(["gall", "use", "hood", ""])));
+/// let nouns: Vec<_> = (0..3u8)
+///     .map(|i| Rc::new(Noun::from(Cell::from([path.clone(), Rc::new(Noun::from(i))]))))
+///     .collect();
+///
+/// let jammed_nouns = serdes::jam_many(&nouns);
+/// let separately: usize = nouns.iter().map(|noun| noun.jam_to_vec().len()).sum();
+/// assert!(jammed_nouns.as_bytes().len() < separately);
+/// assert_eq!(serdes::cue_many(jammed_nouns, 3).unwrap(), nouns);
+/// ```
+#[doc(alias("serialize", "serialization"))]
+pub fn jam_many(nouns: &[Rc<Noun>]) -> Atom {
+    let mut bits = Atom::builder();
+    encode_many(nouns, &mut bits).unwrap_or_else(|never| match never {});
+    bits.into_atom()
+}
+
+/// Jams `noun` into `sink` without any backreferences, starting at the current position of
+/// `sink`, returning `false` as soon as more than `max_bits` bits have been written.
+///
+/// Every occurrence of a repeated subnoun is encoded in full, so the output can be exponentially
+/// larger than the output of [`encode()`] for nouns that share structure.
+pub(crate) fn encode_nodedup<S: BitSink>(
+    noun: &Noun,
+    sink: &mut S,
+    max_bits: u64,
+) -> Result<bool, S::Error> {
+    let start = sink.pos();
+    let mut stack = vec![noun];
+    while let Some(noun) = stack.pop() {
+        match noun {
+            Noun::Atom(atom) => encode_atom(atom, sink)?,
+            Noun::Cell(cell) => {
+                // Cell tag = 0b01.
+                sink.push_bit(true)?;
+                sink.push_bit(false)?;
+                stack.push(cell.tail_ref());
+                stack.push(cell.head_ref());
+            }
+        }
+        if sink.pos() - start > max_bits {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        cell::Cell,
+        serdes::{Cue, Item, Position},
+    };
+
+    #[test]
+    fn capped_sink() {
+        // A noun that fits is jammed exactly like it is without a cap.
+        {
+            let noun = Noun::from(Cell::from([0u8, 19u8]));
+            let mut sink = CappedSink::new(16);
+            assert!(encode(&noun, &mut sink).is_ok());
+            assert_eq!(sink.into_atom().into_vec(), noun.jam_to_vec());
+        }
+
+        // Encoding stops before the bits of an atom that doesn't fit are written, rather than
+        // after.
+        {
+            let big = Atom::from(vec![0xff; 1 << 20]);
+            let noun = Noun::from(Cell::from([Atom::from(1u8), big]));
+            // A cell tag, then `1`, then the tag of the big atom and its 24-bit length.
+            let before_big = 2 + 4 + 1 + 24 + 1 + 23;
+            let mut sink = CappedSink::new(1 << 20);
+            assert!(encode(&noun, &mut sink).is_err());
+            assert_eq!(sink.pos(), before_big);
+        }
+    }
+
+    #[test]
+    fn huge_positions() {
+        // An atom of more bits than fit in a u32, whose length is encoded in 33 + 1 + 32 bits.
+        let len = (1 << 32) + 10;
+
+        // Sizing a jam past bit u32::MAX, as sizing a jam of over 4 Gbit does.
+        {
+            let start = u64::from(u32::MAX) - 1;
+            let mut sink = SizeSink { bit_len: start };
+            encode_len(len, &mut sink).unwrap_or_else(|never| match never {});
+            assert_eq!(sink.bit_len(), start + 33 + 1 + 32);
+            // A backreference to bit `len` is its tag, the length of its 33-bit index, and the
+            // index.
+            encode_backref(len, &mut sink).unwrap_or_else(|never| match never {});
+            assert_eq!(sink.bit_len(), start + 66 + 2 + (6 + 1 + 5) + 33);
+        }
+
+        // Cueing the header of the atom decodes its length in full and fails for want of its
+        // bits, without allocating them.
+        {
+            let mut bits = Atom::builder();
+            bits.push_bit(false);
+            encode_len(len, &mut bits).unwrap_or_else(|never| match never {});
+            bits.push_bit(true);
+            let err = Noun::cue(bits.into_atom()).expect_err("truncated");
+            assert_eq!(
+                err.position(),
+                Some(Position {
+                    bit: 1 + 66,
+                    item: Item::Atom,
+                    cell: None,
+                })
+            );
+        }
+    }
+}
@@ -0,0 +1,456 @@
+//! Lazy deserialization, which decodes the parts of a jammed noun only as they're accessed.
+
+use super::{
+    cue::{self, BackrefIdx, BitSource, Tag},
+    EnclosingCell, Error, HashMap, HashSet, Item, Position, Result, Side,
+};
+use crate::{
+    atom::{Atom, Iter as AtomIter},
+    cell::Cell,
+    noun::Noun,
+};
+use std::{cell::OnceCell, rc::Rc};
+
+#[cfg(test)]
+thread_local! {
+    /// Number of bits of jammed nouns read by the current thread.
+    static BITS_READ: std::cell::Cell<u64> = const { std::cell::Cell::new(0) };
+}
+
+/// A cursor into a jammed noun.
+struct Bits<'a>(AtomIter<'a>);
+
+impl<'a> Bits<'a> {
+    /// Creates a cursor at bit `pos` of `jammed_noun`.
+    fn new(jammed_noun: &'a Atom, pos: u64) -> Self {
+        let mut iter = jammed_noun.iter();
+        iter.seek(pos);
+        Self(iter)
+    }
+
+    /// Skips the next `len` bits without reading them, failing with [`Error::UnexpectedEof`] at
+    /// `pos` if fewer than `len` bits are left.
+    fn skip(&mut self, len: u64, pos: Position) -> Result<()> {
+        if len > self.0.remaining() {
+            return Err(Error::UnexpectedEof(pos));
+        }
+        self.0.seek(self.0.pos_u64() + len);
+        Ok(())
+    }
+}
+
+impl BitSource for Bits<'_> {
+    fn pos(&self) -> u64 {
+        self.0.pos_u64()
+    }
+
+    fn next_bit(&mut self) -> Result<Option<bool>> {
+        #[cfg(test)]
+        BITS_READ.with(|bits_read| bits_read.set(bits_read.get() + 1));
+        Ok(self.0.next())
+    }
+
+    fn next_bits(&mut self, len: u64) -> Result<Option<Atom>> {
+        #[cfg(test)]
+        BITS_READ.with(|bits_read| bits_read.set(bits_read.get() + len));
+        Ok(self.0.read_bits_u64(len))
+    }
+}
+
+/// The positions needed to navigate a jammed noun without decoding it.
+struct Index {
+    /// The position of the tail of every cell, keyed by the position of the cell.
+    tails: HashMap<u64, u64>,
+    /// The position of the noun referred to by every backreference, keyed by the position of
+    /// the backreference.
+    backrefs: HashMap<u64, u64>,
+}
+
+impl Index {
+    /// Scans `jammed_noun`, checking that it's a valid jammed noun without any trailing data
+    /// and recording the position of every cell's tail and every backreference's noun.
+    ///
+    /// Only tags, lengths, and backreference indices are read: the bits of atoms are skipped.
+    fn scan(jammed_noun: &Atom) -> Result<Self> {
+        let mut index = Self {
+            tails: HashMap::default(),
+            backrefs: HashMap::default(),
+        };
+        // Nouns encoded in full that have been completely scanned, which are the only valid
+        // targets of a backreference.
+        let mut complete = HashSet::default();
+        // Cells whose head or tail is being scanned, along with which is being scanned.
+        let mut stack: Vec<(u64, Side)> = Vec::new();
+        let mut bits = Bits::new(jammed_noun, 0);
+        'scan: loop {
+            let pos = bits.pos();
+            let cell = stack.last().map(|&(bit, side)| EnclosingCell { bit, side });
+            match cue::decode_tag(&mut bits, 0, cell)? {
+                Tag::Backref => {
+                    let BackrefIdx {
+                        idx, pos: idx_pos, ..
+                    } = cue::decode_backref_idx(&mut bits, 0, cell)?;
+                    if !complete.contains(&idx) {
+                        return Err(Error::CacheMiss(idx_pos));
+                    }
+                    index.backrefs.insert(pos, idx);
+                }
+                Tag::Cell => {
+                    stack.push((pos, Side::Head));
+                    continue;
+                }
+                Tag::Atom => {
+                    let len = cue::decode_len(&mut bits, 0, Item::AtomLen, cell)?;
+                    let atom_pos = Position {
+                        bit: bits.pos(),
+                        item: Item::Atom,
+                        cell,
+                    };
+                    bits.skip(len, atom_pos)?;
+                    complete.insert(pos);
+                }
+            }
+
+            // Complete every cell whose tail was just scanned.
+            while let Some((cell_pos, side)) = stack.pop() {
+                match side {
+                    Side::Head => {
+                        index.tails.insert(cell_pos, bits.pos());
+                        stack.push((cell_pos, Side::Tail));
+                        continue 'scan;
+                    }
+                    Side::Tail => {
+                        complete.insert(cell_pos);
+                    }
+                }
+            }
+            break;
+        }
+        match bits.0.remaining() {
+            0 => Ok(index),
+            len => Err(Error::TrailingData { bits: len }),
+        }
+    }
+}
+
+/// A validated jammed noun shared by every [`LazyNoun`] decoded from it.
+struct Source {
+    jammed_noun: Atom,
+    index: Index,
+}
+
+impl Source {
+    /// Returns the position of the encoding in full of the noun encoded at `pos`, which differs
+    /// from `pos` if the noun is encoded as a backreference.
+    fn resolve(&self, pos: u64) -> u64 {
+        self.index.backrefs.get(&pos).copied().unwrap_or(pos)
+    }
+
+    /// Returns `true` if the noun encoded in full at `pos` is a cell.
+    fn is_cell(&self, pos: u64) -> bool {
+        Bits::new(&self.jammed_noun, pos)
+            .next_bit()
+            .expect("scanned")
+            .expect("scanned")
+    }
+
+    /// Decodes the atom encoded in full at `pos`.
+    fn atom(&self, pos: u64) -> Atom {
+        let mut bits = Bits::new(&self.jammed_noun, pos + 1);
+        let len = cue::decode_len(&mut bits, 0, Item::AtomLen, None).expect("scanned");
+        cue::next_bits(&mut bits, len, Self::valid(pos)).expect("scanned")
+    }
+
+    /// Returns the positions of the head and tail of the cell encoded in full at `pos`.
+    fn children(&self, pos: u64) -> (u64, u64) {
+        (self.resolve(pos + 2), self.resolve(self.index.tails[&pos]))
+    }
+
+    /// A position for errors that can't occur because the jammed noun has been scanned.
+    fn valid(pos: u64) -> Position {
+        Position {
+            bit: pos,
+            item: Item::Tag,
+            cell: None,
+        }
+    }
+}
+
+/// The decoded form of a [`LazyNoun`].
+enum Decoded {
+    Atom(Atom),
+    Cell(Box<[LazyNoun; 2]>),
+}
+
+/// A jammed noun that's decoded only as it's accessed.
+///
+/// A `LazyNoun` is created by [`cue_lazy()`], which checks that the whole jammed noun is valid
+/// without decoding it. Accessing the head or tail of a cell or the value of an atom decodes just
+/// that part of the jammed noun and caches the result, so the accessors never fail. The bits of
+/// the atoms that are never accessed are never read.
+///
+/// Unlike a [`Noun`], a `LazyNoun` can't be shared between threads.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{atom::Atom, cell::Cell, serdes::{cue_lazy, Jam}, Noun};
+/// let noun = Noun::from(Cell::from([
+///     Noun::from(Atom::from("poke")),
+///     Noun::from(Atom::from(vec![0xff; 1 << 20])),
+/// ]));
+/// let lazy_noun = cue_lazy(noun.clone().jam()).unwrap();
+/// let tag = lazy_noun.head().unwrap().as_atom().unwrap();
+/// assert_eq!(tag, "poke");
+/// assert_eq!(lazy_noun.force(), noun);
+/// ```
+pub struct LazyNoun {
+    source: Rc<Source>,
+    /// Position of the encoding in full of this noun.
+    pos: u64,
+    decoded: OnceCell<Decoded>,
+}
+
+impl LazyNoun {
+    fn new(source: Rc<Source>, pos: u64) -> Self {
+        Self {
+            source,
+            pos,
+            decoded: OnceCell::new(),
+        }
+    }
+
+    fn decoded(&self) -> &Decoded {
+        self.decoded.get_or_init(|| {
+            if self.source.is_cell(self.pos) {
+                let (head, tail) = self.source.children(self.pos);
+                Decoded::Cell(Box::new([
+                    Self::new(self.source.clone(), head),
+                    Self::new(self.source.clone(), tail),
+                ]))
+            } else {
+                Decoded::Atom(self.source.atom(self.pos))
+            }
+        })
+    }
+
+    /// Returns `true` if this noun is an atom, without decoding it.
+    pub fn is_atom(&self) -> bool {
+        !self.is_cell()
+    }
+
+    /// Returns `true` if this noun is a cell, without decoding it.
+    pub fn is_cell(&self) -> bool {
+        match self.decoded.get() {
+            Some(decoded) => matches!(decoded, Decoded::Cell(_)),
+            None => self.source.is_cell(self.pos),
+        }
+    }
+
+    /// Decodes this noun as an atom, returning `None` if it's a cell.
+    pub fn as_atom(&self) -> Option<&Atom> {
+        match self.decoded() {
+            Decoded::Atom(atom) => Some(atom),
+            Decoded::Cell(_) => None,
+        }
+    }
+
+    /// Returns the head of this noun, returning `None` if it's an atom.
+    ///
+    /// The head itself isn't decoded until it's accessed.
+    pub fn head(&self) -> Option<&Self> {
+        match self.decoded() {
+            Decoded::Atom(_) => None,
+            Decoded::Cell(cell) => Some(&cell[0]),
+        }
+    }
+
+    /// Returns the tail of this noun, returning `None` if it's an atom.
+    ///
+    /// The tail itself isn't decoded until it's accessed.
+    pub fn tail(&self) -> Option<&Self> {
+        match self.decoded() {
+            Decoded::Atom(_) => None,
+            Decoded::Cell(cell) => Some(&cell[1]),
+        }
+    }
+
+    /// Retrieves the subnoun at tree address `axis`, decoding only the cells along the way.
+    ///
+    /// See [`Noun::get()`].
+    pub fn get(&self, axis: u64) -> Option<&Self> {
+        if axis == 0 {
+            return None;
+        }
+        let mut noun = self;
+        // The most significant bit of the axis is implicit because it's always 1.
+        for i in (0..u64::BITS - 1 - axis.leading_zeros()).rev() {
+            noun = if axis & (1 << i) == 0 {
+                noun.head()?
+            } else {
+                noun.tail()?
+            };
+        }
+        Some(noun)
+    }
+
+    /// Decodes all of this noun.
+    ///
+    /// Subnouns that are shared by means of backreferences in the jammed noun are shared in the
+    /// returned noun too.
+    pub fn force(&self) -> Noun {
+        let source = &self.source;
+        // Every noun decoded so far, keyed by position.
+        let mut decoded: HashMap<u64, crate::Rc<Noun>> = HashMap::default();
+        // Nouns to decode, along with whether their head and tail have been decoded.
+        let mut stack = vec![(self.pos, false)];
+        while let Some((pos, children_decoded)) = stack.pop() {
+            if decoded.contains_key(&pos) {
+                continue;
+            }
+            if !source.is_cell(pos) {
+                decoded.insert(pos, crate::Rc::new(Noun::from(source.atom(pos))));
+                continue;
+            }
+            let (head, tail) = source.children(pos);
+            if children_decoded {
+                let cell = Cell::from([decoded[&head].clone(), decoded[&tail].clone()]);
+                decoded.insert(pos, crate::Rc::new(Noun::from(cell)));
+            } else {
+                stack.push((pos, true));
+                stack.push((tail, false));
+                stack.push((head, false));
+            }
+        }
+        let noun = decoded.remove(&self.pos).expect("decoded");
+        // Dropping every other noun guarantees that this noun has exactly one reference, so this
+        // never clones.
+        drop(decoded);
+        crate::Rc::try_unwrap(noun).unwrap_or_else(|noun| (*noun).clone())
+    }
+}
+
+/// Deserializes ("cues") a noun lazily, decoding its parts only as they're accessed.
+///
+/// The whole of `jammed_noun` is checked up front, failing just like
+/// [`Noun::cue_strict()`] would for an invalid jammed noun, but only its tags and lengths are
+/// read: decoding the atoms and building cells is deferred until the returned [`LazyNoun`] is
+/// accessed. This makes picking a few fields out of a large jammed noun much cheaper than cueing
+/// it in its entirety.
+#[doc(alias("deserialize", "deserialization"))]
+pub fn cue_lazy(jammed_noun: Atom) -> Result<LazyNoun> {
+    let index = Index::scan(&jammed_noun)?;
+    let source = Rc::new(Source { jammed_noun, index });
+    let pos = source.resolve(0);
+    Ok(LazyNoun::new(source, pos))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::serdes::Jam;
+
+    fn bits_read() -> u64 {
+        BITS_READ.with(|bits_read| bits_read.get())
+    }
+
+    #[test]
+    fn cue_lazy() {
+        let event = Noun::from(Cell::from([
+            Noun::from(Atom::from("poke")),
+            Noun::from(Cell::from(["wire", "path"])),
+            Noun::from(Atom::from(vec![0xa5; 1 << 20])),
+            Noun::from(Atom::from(vec![0x5a; 1 << 20])),
+        ]));
+        let jammed_event = event.clone().jam();
+        let jammed_bits = jammed_event.bit_len() as u64;
+
+        // Accessing axis 2 reads only a small fraction of the jammed noun.
+        {
+            let before = bits_read();
+            let lazy_event = super::cue_lazy(jammed_event.clone()).expect("cue");
+            let tag = lazy_event.get(2).expect("axis 2").as_atom().expect("atom");
+            assert_eq!(tag, "poke");
+            let read = bits_read() - before;
+            assert!(
+                read < jammed_bits / 1000,
+                "read {} of {} bits",
+                read,
+                jammed_bits
+            );
+        }
+
+        // Forcing reads everything.
+        {
+            let lazy_event = super::cue_lazy(jammed_event.clone()).expect("cue");
+            assert!(lazy_event.is_cell());
+            assert_eq!(
+                lazy_event.get(6).expect("axis 6").force(),
+                event.get(6).unwrap().clone()
+            );
+            assert_eq!(lazy_event.force(), event);
+        }
+
+        // Backreferences, including to a noun that hasn't been accessed.
+        {
+            let shared = Noun::from(Cell::from([10_000u16, 10_000u16]));
+            let noun = Noun::from(Cell::from([shared.clone(), shared.clone(), shared]));
+            let lazy_noun = super::cue_lazy(noun.clone().jam()).expect("cue");
+            assert_eq!(
+                lazy_noun.get(7).expect("axis 7").force(),
+                *noun.get(7).unwrap()
+            );
+            assert_eq!(
+                lazy_noun.get(13).expect("axis 13").as_atom(),
+                Some(&Atom::from(10_000u16))
+            );
+            assert!(lazy_noun.get(15).expect("axis 15").is_atom());
+            assert!(lazy_noun.get(30).is_none());
+            assert_eq!(lazy_noun.force(), noun);
+        }
+
+        // Invalid jammed nouns fail up front, just like they do when cued eagerly.
+        for jammed_noun in [
+            Atom::from(0u8),
+            Atom::from(0b1_0011_1010_0001u16),
+            Atom::from(0xff_9b09u32),
+            Atom::from(0b1111u8),
+        ] {
+            let expected = Noun::cue_strict(&jammed_noun).expect_err("cue");
+            let err = super::cue_lazy(jammed_noun).err().expect("cue lazily");
+            assert_eq!(err.to_string(), expected.to_string());
+        }
+    }
+
+    #[cfg(target_pointer_width = "64")]
+    #[test]
+    #[ignore = "allocates 512 MiB; run with --ignored"]
+    fn cue_lazy_huge() {
+        // [a 5], where a is an atom of 2^32 + 1 bits, so 5 starts past bit 2^32. The jammed noun
+        // is built in a zeroed allocation, which is mapped lazily, so only the pages that are
+        // written to take up memory. a is never decoded.
+        let len: u64 = (1 << 32) + 1;
+        let total_len = 2 + (1 + 33 + 1 + 32) + len + (1 + 4 + 3);
+        let mut bytes = vec![0u8; total_len.div_ceil(8) as usize];
+        let mut set = |bit: u64| bytes[(bit / 8) as usize] |= 1 << (bit % 8);
+        // Cell tag, then a's atom tag and 33-bit length.
+        set(0);
+        set(2 + 1 + 33);
+        set(2 + 1 + 33 + 1);
+        // The most significant bit of a.
+        let a_end = 2 + (1 + 33 + 1 + 32) + len;
+        set(a_end - 1);
+        // 5's atom tag and 3-bit length, then 5.
+        set(a_end + 3);
+        set(a_end + 4);
+        set(a_end + 5);
+        set(a_end + 7);
+
+        let lazy_noun = super::cue_lazy(Atom::from(bytes)).expect("cue");
+        assert!(lazy_noun.head().expect("head").is_atom());
+        assert_eq!(
+            lazy_noun.tail().expect("tail").as_atom(),
+            Some(&Atom::from(5u8))
+        );
+    }
+}
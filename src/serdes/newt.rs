@@ -0,0 +1,324 @@
+//! Newt framing, the length-prefixed jam encoding that the Urbit runtime uses for IPC.
+//!
+//! Every message between the runtime's processes (and over the loopback "conn" socket) is a
+//! jammed noun preceded by a 5-byte header: a protocol version byte, which is always `0`,
+//! followed by the length in bytes of the jammed noun as a little-endian `u32`.
+//!
+//! ```text
+//! +---------+------------------+---------------------+
+//! | version | length (LE u32)  | jammed noun         |
+//! | 1 byte  | 4 bytes          | `length` bytes      |
+//! +---------+------------------+---------------------+
+//! ```
+//!
+//! Older runtimes used an 8-byte header holding a little-endian `u64` length and no version
+//! byte. That format is not supported.
+
+#[cfg(feature = "tokio")]
+pub mod tokio;
+
+use crate::{
+    noun::Noun,
+    serdes::{self, trace::OpSpan},
+};
+use std::{
+    fmt::{self, Display, Formatter},
+    io::{self, Read, Write},
+    result,
+};
+
+/// The only supported newt protocol version.
+pub const VERSION: u8 = 0;
+
+/// The length in bytes of a newt header.
+pub const HEADER_LEN: usize = 5;
+
+/// Errors that occur when reading a newt frame.
+#[derive(Debug)]
+pub enum NewtError {
+    /// The frame's jammed noun could not be cued.
+    Cue(serdes::Error),
+    /// The frame's header declared a length of zero, which can't hold a jammed noun.
+    EmptyFrame,
+    /// Reading the frame failed, including when the stream ended partway through a frame.
+    Io(io::Error),
+    /// The frame's header had an unsupported protocol version.
+    Version(u8),
+}
+
+impl Display for NewtError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> result::Result<(), fmt::Error> {
+        match self {
+            Self::Cue(err) => write!(f, "cueing the framed noun failed: {}", err),
+            Self::EmptyFrame => write!(f, "the frame is empty"),
+            Self::Io(err) => write!(f, "reading the frame failed: {}", err),
+            Self::Version(version) => write!(f, "unsupported newt version {}", version),
+        }
+    }
+}
+
+impl std::error::Error for NewtError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Cue(err) => Some(err),
+            Self::Io(err) => Some(err),
+            Self::EmptyFrame | Self::Version(_) => None,
+        }
+    }
+}
+
+/// Jams `noun` and writes it to `writer` as a single newt frame.
+///
+/// Fails with [`io::ErrorKind::InvalidInput`] if the jammed noun is longer than [`u32::MAX`]
+/// bytes, which is the longest frame the header can describe.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{cell::Cell, serdes::newt, Noun};
+/// let noun = Noun::from(Cell::from([0u8, 19u8]));
+/// let mut frame = Vec::new();
+/// newt::write_newt(&mut frame, &noun).unwrap();
+/// assert_eq!(frame, [0x00, 0x02, 0x00, 0x00, 0x00, 0x09, 0x9b]);
+/// ```
+pub fn write_newt<W: Write>(mut writer: W, noun: &Noun) -> io::Result<()> {
+    let span = OpSpan::newt_write();
+    let jammed_noun = noun.jam_to_vec();
+    span.record("bytes", jammed_noun.len() as u64);
+    writer.write_all(&encode_header(jammed_noun.len())?)?;
+    writer.write_all(&jammed_noun)?;
+    writer.flush()
+}
+
+/// Encodes the header of a newt frame holding a jammed noun `len` bytes long.
+pub(crate) fn encode_header(len: usize) -> io::Result<[u8; HEADER_LEN]> {
+    let len = u32::try_from(len).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "jammed noun is too long for a newt frame",
+        )
+    })?;
+    let mut header = [VERSION; HEADER_LEN];
+    header[1..].copy_from_slice(&len.to_le_bytes());
+    Ok(header)
+}
+
+/// Reads a single newt frame from `reader` and cues the noun it contains.
+///
+/// Exactly one frame is consumed from `reader`. Reaching the end of `reader` before the frame is
+/// complete, including before its first byte, is an [`io::ErrorKind::UnexpectedEof`] error; use
+/// [`NewtReader`] to read frames until the end of a stream.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{cell::Cell, serdes::newt, Noun};
+/// let frame = [0x00, 0x02, 0x00, 0x00, 0x00, 0x09, 0x9b];
+/// let noun = newt::read_newt(&frame[..]).unwrap();
+/// assert_eq!(noun, Noun::from(Cell::from([0u8, 19u8])));
+/// ```
+pub fn read_newt<R: Read>(mut reader: R) -> Result<Noun, NewtError> {
+    match read_frame(&mut reader)? {
+        Some(noun) => Ok(noun),
+        None => Err(NewtError::Io(io::ErrorKind::UnexpectedEof.into())),
+    }
+}
+
+/// Reads a single newt frame from `reader`, returning `None` if `reader` is already at its end.
+fn read_frame<R: Read>(reader: &mut R) -> Result<Option<Noun>, NewtError> {
+    let mut header = [0; HEADER_LEN];
+    // Read the first byte separately to tell a stream that ended between frames apart from one
+    // that ended partway through a frame.
+    loop {
+        match reader.read(&mut header[..1]) {
+            Ok(0) => return Ok(None),
+            Ok(_) => break,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(NewtError::Io(err)),
+        }
+    }
+    reader.read_exact(&mut header[1..]).map_err(NewtError::Io)?;
+    let (version, len) = parse_header(&header)?;
+    debug_assert_eq!(version, VERSION);
+    let span = OpSpan::newt_read();
+    span.record("bytes", u64::from(len));
+
+    // Reading through take() rather than into a buffer of the declared length means that a
+    // corrupt header can't cause more memory to be allocated than there is input.
+    let mut jammed_noun = Vec::new();
+    reader
+        .take(u64::from(len))
+        .read_to_end(&mut jammed_noun)
+        .map_err(NewtError::Io)?;
+    if jammed_noun.len() != len as usize {
+        return Err(NewtError::Io(io::ErrorKind::UnexpectedEof.into()));
+    }
+    Noun::cue_bytes(&jammed_noun)
+        .map(Some)
+        .map_err(NewtError::Cue)
+}
+
+/// Parses and validates a newt header, returning its version and length.
+pub(crate) fn parse_header(header: &[u8; HEADER_LEN]) -> Result<(u8, u32), NewtError> {
+    let version = header[0];
+    if version != VERSION {
+        return Err(NewtError::Version(version));
+    }
+    let len = u32::from_le_bytes([header[1], header[2], header[3], header[4]]);
+    if len == 0 {
+        return Err(NewtError::EmptyFrame);
+    }
+    Ok((version, len))
+}
+
+/// An iterator over the nouns in a stream of newt frames.
+///
+/// The iterator ends when the underlying reader reaches its end between two frames. Reaching the
+/// end partway through a frame yields an error.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{atom::Atom, serdes::newt::{self, NewtReader}, Noun};
+/// let mut stream = Vec::new();
+/// newt::write_newt(&mut stream, &Noun::from(Atom::from(1u8))).unwrap();
+/// newt::write_newt(&mut stream, &Noun::from(Atom::from(2u8))).unwrap();
+///
+/// let nouns: Vec<Noun> = NewtReader::new(&stream[..]).map(Result::unwrap).collect();
+/// assert_eq!(nouns, [Noun::from(Atom::from(1u8)), Noun::from(Atom::from(2u8))]);
+/// ```
+pub struct NewtReader<R: Read> {
+    reader: R,
+    /// Whether the end of the stream or an error has been reached.
+    done: bool,
+}
+
+impl<R: Read> NewtReader<R> {
+    /// Creates an iterator over the nouns in the newt frames read from `reader`.
+    ///
+    /// Frames are read with many small reads, so wrapping `reader` in a [`io::BufReader`] is
+    /// recommended.
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            done: false,
+        }
+    }
+
+    /// Consumes this iterator, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: Read> Iterator for NewtReader<R> {
+    type Item = Result<Noun, NewtError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let res = read_frame(&mut self.reader).transpose();
+        // There's no way to resynchronize with the stream after a bad frame.
+        if !matches!(res, Some(Ok(_))) {
+            self.done = true;
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{atom::Atom, cell::Cell};
+
+    #[test]
+    fn newt() {
+        // Hand-assembled frames: the version byte, the little-endian length, and the jammed noun.
+        let frames: [(Noun, &[u8]); 3] = [
+            (
+                Noun::from(Atom::from(0u8)),
+                &[0x00, 0x01, 0x00, 0x00, 0x00, 0x02],
+            ),
+            (
+                Noun::from(Cell::from([0u8, 19u8])),
+                &[0x00, 0x02, 0x00, 0x00, 0x00, 0x09, 0x9b],
+            ),
+            (
+                Noun::from(Cell::from([10_000u16, 10_000u16])),
+                &[0x00, 0x05, 0x00, 0x00, 0x00, 0x81, 0x86, 0x38, 0x27, 0x01],
+            ),
+        ];
+
+        // Write and read individual frames.
+        for (noun, frame) in &frames {
+            let mut bytes = Vec::new();
+            write_newt(&mut bytes, noun).expect("write frame");
+            assert_eq!(bytes, *frame);
+            assert_eq!(read_newt(*frame).expect("read frame"), *noun);
+        }
+
+        // Read a stream of frames.
+        {
+            let stream: Vec<u8> = frames
+                .iter()
+                .flat_map(|(_, frame)| frame.to_vec())
+                .collect();
+            let nouns: Vec<Noun> = NewtReader::new(&stream[..])
+                .collect::<Result<_, _>>()
+                .expect("read stream");
+            let expected: Vec<Noun> = frames.iter().map(|(noun, _)| noun.clone()).collect();
+            assert_eq!(nouns, expected);
+        }
+
+        // An empty stream has no frames, but an empty frame is an error.
+        {
+            assert!(NewtReader::new(io::empty()).next().is_none());
+            match read_newt(io::empty()) {
+                Err(NewtError::Io(err)) => assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof),
+                res => panic!("unexpected result: {:?}", res),
+            }
+            assert!(matches!(
+                read_newt(&[0x00, 0x00, 0x00, 0x00, 0x00][..]),
+                Err(NewtError::EmptyFrame)
+            ));
+        }
+
+        // A stream that ends partway through a frame.
+        {
+            let (_, frame) = frames[1];
+            for len in 1..frame.len() {
+                let mut frames = NewtReader::new(&frame[..len]);
+                match frames.next() {
+                    Some(Err(NewtError::Io(err))) => {
+                        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof)
+                    }
+                    res => panic!("unexpected result: {:?}", res),
+                }
+                assert!(frames.next().is_none());
+            }
+        }
+
+        // A bad version, which is also what an old 8-byte header with a length of at least 256
+        // looks like.
+        assert!(matches!(
+            read_newt(&[0x01, 0x02, 0x00, 0x00, 0x00, 0x09, 0x9b][..]),
+            Err(NewtError::Version(1))
+        ));
+
+        // A corrupt length can't cause a huge allocation.
+        assert!(matches!(
+            read_newt(&[0x00, 0xff, 0xff, 0xff, 0xff, 0x09, 0x9b][..]),
+            Err(NewtError::Io(_))
+        ));
+
+        // A frame whose payload isn't a valid jammed noun, which is the source of the error.
+        {
+            let err =
+                read_newt(&[0x00, 0x01, 0x00, 0x00, 0x00, 0x01][..]).expect_err("bad payload");
+            assert!(matches!(err, NewtError::Cue(_)));
+            let source = std::error::Error::source(&err).expect("source");
+            assert!(source.downcast_ref::<serdes::Error>().is_some());
+        }
+    }
+}
@@ -0,0 +1,244 @@
+//! Asynchronous newt framing over [`tokio`] I/O.
+//!
+//! Only the I/O is asynchronous: once a frame has been read in its entirety, its jammed noun is
+//! cued synchronously, and a noun is jammed synchronously before its frame is written.
+
+use super::{encode_header, parse_header, NewtError, HEADER_LEN};
+use crate::{noun::Noun, serdes::trace::OpSpan};
+use ::tokio::io::{self, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use std::mem;
+
+/// Jams `noun` and writes it to `writer` as a single newt frame.
+///
+/// This is the asynchronous counterpart of [`newt::write_newt()`](super::write_newt()).
+///
+/// # Cancel safety
+///
+/// This function is not cancel safe: if it's cancelled, part of the frame may have been written.
+pub async fn write_newt<W: AsyncWrite + Unpin>(mut writer: W, noun: &Noun) -> io::Result<()> {
+    let frame = {
+        // The span can't stay entered across an await, so it only covers jamming the noun.
+        let span = OpSpan::newt_write();
+        let jammed_noun = noun.jam_to_vec();
+        span.record("bytes", jammed_noun.len() as u64);
+        let mut frame = Vec::with_capacity(HEADER_LEN + jammed_noun.len());
+        frame.extend_from_slice(&encode_header(jammed_noun.len())?);
+        frame.extend_from_slice(&jammed_noun);
+        frame
+    };
+    writer.write_all(&frame).await?;
+    writer.flush().await
+}
+
+/// Reads a single newt frame from `reader` and cues the noun it contains.
+///
+/// This is the asynchronous counterpart of [`newt::read_newt()`](super::read_newt()).
+///
+/// # Cancel safety
+///
+/// This function is not cancel safe: if it's cancelled, the part of the frame read so far is
+/// lost, and `reader` is left in the middle of a frame. Use [`NewtReader::read_frame()`] in a
+/// `select!` loop or under a timeout.
+pub async fn read_newt<R: AsyncRead + Unpin>(reader: R) -> Result<Noun, NewtError> {
+    match NewtReader::new(reader).read_frame().await? {
+        Some(noun) => Ok(noun),
+        None => Err(NewtError::Io(io::ErrorKind::UnexpectedEof.into())),
+    }
+}
+
+/// A reader of newt frames that keeps partially read frames across cancellation.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{atom::Atom, serdes::newt::tokio::{self as newt, NewtReader}, Noun};
+/// # tokio_test::block_on(async {
+/// let mut stream = Vec::new();
+/// newt::write_newt(&mut stream, &Noun::from(Atom::from(1u8))).await.unwrap();
+/// newt::write_newt(&mut stream, &Noun::from(Atom::from(2u8))).await.unwrap();
+///
+/// let mut frames = NewtReader::new(&stream[..]);
+/// assert_eq!(frames.read_frame().await.unwrap(), Some(Noun::from(Atom::from(1u8))));
+/// assert_eq!(frames.read_frame().await.unwrap(), Some(Noun::from(Atom::from(2u8))));
+/// assert_eq!(frames.read_frame().await.unwrap(), None);
+/// # });
+/// ```
+pub struct NewtReader<R> {
+    reader: R,
+    /// The header of the frame being read.
+    header: [u8; HEADER_LEN],
+    /// The number of bytes of `header` read so far.
+    header_len: usize,
+    /// The part of the frame's jammed noun read so far.
+    jammed_noun: Vec<u8>,
+}
+
+impl<R: AsyncRead + Unpin> NewtReader<R> {
+    /// Creates a reader of the newt frames read from `reader`.
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            header: [0; HEADER_LEN],
+            header_len: 0,
+            jammed_noun: Vec::new(),
+        }
+    }
+
+    /// Reads the next newt frame and cues the noun it contains, returning `None` if the
+    /// underlying reader reached its end between two frames.
+    ///
+    /// Reaching the end partway through a frame is an [`io::ErrorKind::UnexpectedEof`] error.
+    /// There's no way to resynchronize with the stream after an error, so reading from this
+    /// reader again after an error yields unspecified nouns or errors.
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is cancel safe: the bytes of a frame read before the returned future is
+    /// dropped are kept, and the next call picks up where the previous one left off.
+    pub async fn read_frame(&mut self) -> Result<Option<Noun>, NewtError> {
+        let res = self.read_frame_inner().await;
+        // Only reached if the future wasn't dropped, so a partially read frame is never discarded.
+        self.header_len = 0;
+        self.jammed_noun.clear();
+        res
+    }
+
+    async fn read_frame_inner(&mut self) -> Result<Option<Noun>, NewtError> {
+        // Every await point below is a single read, which either reads some bytes into `self` or
+        // reads nothing at all if cancelled.
+        while self.header_len < HEADER_LEN {
+            let len = self
+                .reader
+                .read(&mut self.header[self.header_len..])
+                .await
+                .map_err(NewtError::Io)?;
+            if len == 0 {
+                if self.header_len == 0 {
+                    return Ok(None);
+                }
+                return Err(NewtError::Io(io::ErrorKind::UnexpectedEof.into()));
+            }
+            self.header_len += len;
+        }
+        let (_, len) = parse_header(&self.header)?;
+
+        // Reading through take() rather than into a buffer of the declared length means that a
+        // corrupt header can't cause more memory to be allocated than there is input.
+        let len = len as usize;
+        while self.jammed_noun.len() < len {
+            let remaining = (len - self.jammed_noun.len()) as u64;
+            let read = (&mut self.reader)
+                .take(remaining)
+                .read_buf(&mut self.jammed_noun)
+                .await
+                .map_err(NewtError::Io)?;
+            if read == 0 {
+                return Err(NewtError::Io(io::ErrorKind::UnexpectedEof.into()));
+            }
+        }
+        let jammed_noun = mem::take(&mut self.jammed_noun);
+        // The span can't stay entered across an await, so it only covers cueing the frame.
+        let span = OpSpan::newt_read();
+        span.record("bytes", jammed_noun.len() as u64);
+        Noun::cue_bytes(&jammed_noun)
+            .map(Some)
+            .map_err(NewtError::Cue)
+    }
+
+    /// Consumes this reader, returning the underlying reader.
+    ///
+    /// Any partially read frame is discarded.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{atom::Atom, cell::Cell};
+    use ::tokio::time::{self, Duration};
+    use tokio_test::io::Builder;
+
+    #[::tokio::test]
+    async fn newt() {
+        let noun = Noun::from(Cell::from([10_000u16, 10_000u16]));
+        let frame = [0x00, 0x05, 0x00, 0x00, 0x00, 0x81, 0x86, 0x38, 0x27, 0x01];
+
+        // Write a frame.
+        {
+            let mut writer = Builder::new().write(&frame).build();
+            write_newt(&mut writer, &noun).await.expect("write frame");
+        }
+
+        // Read a frame delivered in pieces that split both the header and the jammed noun.
+        {
+            let reader = Builder::new()
+                .read(&frame[..1])
+                .read(&frame[1..3])
+                .read(&frame[3..7])
+                .read(&frame[7..])
+                .build();
+            assert_eq!(read_newt(reader).await.expect("read frame"), noun);
+        }
+
+        // Read a stream of frames, with the second frame starting in the middle of a read.
+        {
+            let other = Noun::from(Atom::from(0u8));
+            let other_frame = [0x00, 0x01, 0x00, 0x00, 0x00, 0x02];
+            let stream: Vec<u8> = frame.iter().chain(&other_frame).copied().collect();
+            let reader = Builder::new()
+                .read(&stream[..8])
+                .read(&stream[8..13])
+                .read(&stream[13..])
+                .build();
+            let mut frames = NewtReader::new(reader);
+            assert_eq!(
+                frames.read_frame().await.expect("read frame"),
+                Some(noun.clone())
+            );
+            assert_eq!(frames.read_frame().await.expect("read frame"), Some(other));
+            assert_eq!(frames.read_frame().await.expect("read end"), None);
+        }
+
+        // A stream that ends partway through a frame.
+        for len in 1..frame.len() {
+            let reader = Builder::new().read(&frame[..len]).build();
+            match NewtReader::new(reader).read_frame().await {
+                Err(NewtError::Io(err)) => assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof),
+                res => panic!("unexpected result: {:?}", res),
+            }
+        }
+
+        // A bad version.
+        {
+            let reader = Builder::new().read(&[0x01, 0x00, 0x00, 0x00, 0x00]).build();
+            assert!(matches!(
+                read_newt(reader).await,
+                Err(NewtError::Version(1))
+            ));
+        }
+    }
+
+    #[::tokio::test(start_paused = true)]
+    async fn newt_cancel() {
+        let noun = Noun::from(Cell::from([10_000u16, 10_000u16]));
+        let frame = [0x00, 0x05, 0x00, 0x00, 0x00, 0x81, 0x86, 0x38, 0x27, 0x01];
+        let timeout = Duration::from_millis(10);
+        let delay = Duration::from_secs(1);
+
+        // Cancelled partway through the header, and then partway through the jammed noun.
+        let reader = Builder::new()
+            .read(&frame[..3])
+            .wait(delay)
+            .read(&frame[3..7])
+            .wait(delay)
+            .read(&frame[7..])
+            .build();
+        let mut frames = NewtReader::new(reader);
+        assert!(time::timeout(timeout, frames.read_frame()).await.is_err());
+        assert!(time::timeout(timeout, frames.read_frame()).await.is_err());
+        assert_eq!(frames.read_frame().await.expect("read frame"), Some(noun));
+        assert_eq!(frames.read_frame().await.expect("read end"), None);
+    }
+}
@@ -0,0 +1,283 @@
+//! Spans and events for jamming and cueing, which compile to nothing unless the `tracing` feature
+//! is enabled.
+//!
+//! Every jam and cue runs in a `DEBUG` span, named `jam` or `cue`, that records how much was
+//! encoded or decoded once it's done, and newt frames are read and written in `newt_read` and
+//! `newt_write` spans. Each span records `duration_us`, the time it was open in microseconds,
+//! when it closes. A jam or cue that fails because it would exceed a limit emits a `WARN` event
+//! with the [`Limit`] that was hit, `limit`, and its value, `max`.
+//!
+//! | Span         | Fields                                             |
+//! |--------------|----------------------------------------------------|
+//! | `jam`        | `bits`, `nodes`                                    |
+//! | `cue`        | `bits`, `nodes`, `atoms`, `cells`, `backrefs`      |
+//! | `newt_read`  | `bytes`, the length of the frame's jammed noun     |
+//! | `newt_write` | `bytes`, the length of the frame's jammed noun     |
+//!
+//! A `newt_read` span starts once the frame's header has been read, so that the time spent
+//! waiting for a frame to arrive isn't counted. The asynchronous newt functions only record the
+//! jam or cue of a frame, not the I/O around it, because a span can't stay entered across an
+//! `.await`.
+
+use super::{Error, Limit};
+#[cfg(feature = "tracing")]
+use std::time::Instant;
+
+/// A span around a jam, cue, or newt frame, entered for as long as this exists.
+pub(crate) struct OpSpan {
+    #[cfg(feature = "tracing")]
+    span: tracing::span::EnteredSpan,
+    #[cfg(feature = "tracing")]
+    start: Instant,
+}
+
+/// Enters a `DEBUG` span named `$name` with the given fields, which are recorded later, and
+/// `duration_us`.
+macro_rules! op_span {
+    ($name:literal $(, $field:ident)*) => {{
+        #[cfg(feature = "tracing")]
+        let span = OpSpan {
+            span: tracing::debug_span!(
+                $name,
+                $($field = tracing::field::Empty,)*
+                duration_us = tracing::field::Empty
+            )
+            .entered(),
+            start: Instant::now(),
+        };
+        #[cfg(not(feature = "tracing"))]
+        let span = OpSpan {};
+        span
+    }};
+}
+
+impl OpSpan {
+    /// Enters the span of a jam.
+    #[inline]
+    pub(crate) fn jam() -> Self {
+        op_span!("jam", bits, nodes)
+    }
+
+    /// Enters the span of a cue.
+    #[inline]
+    pub(crate) fn cue() -> Self {
+        op_span!("cue", bits, nodes, atoms, cells, backrefs)
+    }
+
+    /// Enters the span of reading a newt frame.
+    #[inline]
+    pub(crate) fn newt_read() -> Self {
+        op_span!("newt_read", bytes)
+    }
+
+    /// Enters the span of writing a newt frame.
+    #[inline]
+    pub(crate) fn newt_write() -> Self {
+        op_span!("newt_write", bytes)
+    }
+
+    /// Records `value` as the field `field` of this span.
+    #[inline]
+    pub(crate) fn record(&self, field: &'static str, value: u64) {
+        #[cfg(feature = "tracing")]
+        self.span.record(field, value);
+        #[cfg(not(feature = "tracing"))]
+        let _ = (field, value);
+    }
+}
+
+#[cfg(feature = "tracing")]
+impl Drop for OpSpan {
+    fn drop(&mut self) {
+        let duration_us = u64::try_from(self.start.elapsed().as_micros()).unwrap_or(u64::MAX);
+        self.span.record("duration_us", duration_us);
+    }
+}
+
+/// Emits a `WARN` event if `err` is a [`Error::LimitExceeded`].
+#[inline]
+pub(crate) fn limit_exceeded(err: &Error) {
+    if let Error::LimitExceeded { which, limit } = err {
+        warned(*which, *limit);
+    }
+}
+
+#[cfg(feature = "tracing")]
+fn warned(which: Limit, limit: u64) {
+    tracing::warn!(limit = ?which, max = limit, "limit exceeded");
+}
+
+#[cfg(not(feature = "tracing"))]
+#[inline]
+fn warned(_: Limit, _: u64) {}
+
+#[cfg(all(test, feature = "tracing"))]
+mod tests {
+    use crate::{
+        atom::Atom,
+        cell::Cell,
+        noun::Noun,
+        serdes::{newt, CueLimits},
+    };
+    use std::{
+        collections::BTreeMap,
+        fmt::Debug,
+        sync::{Arc, Mutex},
+    };
+    use tracing::{
+        field::{Field, Visit},
+        span::{Attributes, Id, Record},
+        Event, Level, Subscriber,
+    };
+    use tracing_subscriber::{layer::Context, prelude::*, registry::LookupSpan, Layer};
+
+    /// The fields of a span or event.
+    #[derive(Clone, Debug, Default)]
+    struct Fields(BTreeMap<&'static str, String>);
+
+    impl Visit for Fields {
+        fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+            self.0.insert(field.name(), format!("{:?}", value));
+        }
+    }
+
+    /// A closed span or an event, with its fields.
+    #[derive(Debug)]
+    enum Captured {
+        Span(&'static str, Fields),
+        Event(Level, Fields),
+    }
+
+    /// A layer that captures every span when it closes and every event.
+    #[derive(Clone, Default)]
+    struct Capture(Arc<Mutex<Vec<Captured>>>);
+
+    impl<S: Subscriber + for<'a> LookupSpan<'a>> Layer<S> for Capture {
+        fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+            let mut fields = Fields::default();
+            attrs.record(&mut fields);
+            ctx.span(id).expect("span").extensions_mut().insert(fields);
+        }
+
+        fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+            let span = ctx.span(id).expect("span");
+            let mut extensions = span.extensions_mut();
+            values.record(extensions.get_mut::<Fields>().expect("fields"));
+        }
+
+        fn on_event(&self, event: &Event<'_>, _: Context<'_, S>) {
+            let mut fields = Fields::default();
+            event.record(&mut fields);
+            let level = *event.metadata().level();
+            self.0.lock().unwrap().push(Captured::Event(level, fields));
+        }
+
+        fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+            let span = ctx.span(&id).expect("span");
+            let fields = span.extensions().get::<Fields>().expect("fields").clone();
+            self.0
+                .lock()
+                .unwrap()
+                .push(Captured::Span(span.name(), fields));
+        }
+    }
+
+    /// Runs `f`, returning what it traced.
+    fn capture(f: impl FnOnce()) -> Vec<Captured> {
+        let capture = Capture::default();
+        let subscriber = tracing_subscriber::registry().with(capture.clone());
+        tracing::subscriber::with_default(subscriber, f);
+        let captured = std::mem::take(&mut *capture.0.lock().unwrap());
+        captured
+    }
+
+    /// Returns the fields of the only span named `name`.
+    fn span<'a>(captured: &'a [Captured], name: &str) -> &'a Fields {
+        let mut spans = captured.iter().filter_map(|captured| match captured {
+            Captured::Span(span, fields) if *span == name => Some(fields),
+            _ => None,
+        });
+        let fields = spans.next().unwrap_or_else(|| panic!("no {} span", name));
+        assert!(spans.next().is_none(), "more than one {} span", name);
+        fields
+    }
+
+    #[test]
+    fn trace() {
+        let shared = Noun::from(Cell::from([10_000u16, 10_000u16]));
+        let noun = Noun::from(Cell::from([shared.clone(), shared]));
+        let jammed = noun.jam_to_vec();
+
+        // Jam and cue record what they encoded and decoded, and how long it took.
+        {
+            let captured = capture(|| {
+                noun.jam_to_vec();
+            });
+            let jam = &span(&captured, "jam").0;
+            assert_eq!(jam["bits"], (noun.jam_size_bits()).to_string());
+            assert_eq!(jam["nodes"], "5");
+            assert!(jam.contains_key("duration_us"));
+
+            let captured = capture(|| {
+                Noun::cue_bytes(&jammed).expect("cue");
+            });
+            let (_, stats) = Noun::cue_with_stats(Atom::from(jammed.clone())).expect("cue");
+            let cue = &span(&captured, "cue").0;
+            assert_eq!(cue["bits"], stats.bits.to_string());
+            assert_eq!(cue["atoms"], stats.atoms.to_string());
+            assert_eq!(cue["cells"], stats.cells.to_string());
+            assert_eq!(cue["backrefs"], stats.backrefs.to_string());
+            assert_eq!(cue["nodes"], "7");
+            assert!(cue.contains_key("duration_us"));
+            assert!(!captured
+                .iter()
+                .any(|captured| matches!(captured, Captured::Event(..))));
+        }
+
+        // Exceeding a limit warns.
+        {
+            let limits = CueLimits {
+                max_nodes: 3,
+                ..CueLimits::default()
+            };
+            let captured = capture(|| {
+                Noun::cue_with_limits(Atom::from(jammed.clone()), &limits).expect_err("limit");
+            });
+            let warning = captured.iter().find_map(|captured| match captured {
+                Captured::Event(Level::WARN, fields) => Some(&fields.0),
+                _ => None,
+            });
+            let warning = warning.expect("warning");
+            assert_eq!(warning["limit"], "Nodes");
+            assert_eq!(warning["max"], "3");
+            span(&captured, "cue");
+
+            let captured = capture(|| {
+                noun.jam_capped(8).expect_err("limit");
+            });
+            assert!(captured
+                .iter()
+                .any(|captured| matches!(captured, Captured::Event(Level::WARN, _))));
+        }
+
+        // Newt frames, with the jam or cue of the frame inside them.
+        {
+            let mut frame = Vec::new();
+            let captured = capture(|| newt::write_newt(&mut frame, &noun).expect("write"));
+            assert_eq!(
+                span(&captured, "newt_write").0["bytes"],
+                jammed.len().to_string()
+            );
+            span(&captured, "jam");
+
+            let captured = capture(|| {
+                newt::read_newt(&frame[..]).expect("read");
+            });
+            assert_eq!(
+                span(&captured, "newt_read").0["bytes"],
+                jammed.len().to_string()
+            );
+            span(&captured, "cue");
+        }
+    }
+}
@@ -0,0 +1,523 @@
+//! Shapes, patterns that describe the structure of a noun.
+//!
+//! A shape is a cheap structural precheck for a noun that came from somewhere untrusted, run
+//! before converting it. Shapes are written in a compact notation borrowed from Hoon's molds:
+//!
+//! | Pattern      | Matches                                                        |
+//! |--------------|----------------------------------------------------------------|
+//! | `*`          | any noun                                                       |
+//! | `@`          | any atom, including null                                       |
+//! | `^`          | any cell                                                       |
+//! | `~`          | null                                                           |
+//! | `[a b]`      | a cell whose head matches `a` and whose tail matches `b`       |
+//! | `[a b c]`    | `[a [b c]]`, and so on for any number of elements              |
+//! | `(list a)`   | zero or more nouns that match `a`, then null                   |
+//!
+//! Whitespace separates the elements of a cell and may appear between any two tokens.
+//!
+//! # Examples
+//!
+//! ```
+//! # use noun::{cell::Cell, shape::{Kind, Shape}, Noun, Rc};
+//! let shape: Shape = "[@ (list [@ @]) ~]".parse().unwrap();
+//! let pairs = Noun::list_from(vec![
+//!     Rc::new(Noun::from(Cell::from([2u8, 3u8]))),
+//!     Rc::new(Noun::from(Cell::from([4u8, 5u8]))),
+//! ]);
+//! let noun = Noun::from(Cell::from([Noun::from(1u8), pairs, Noun::null()]));
+//! assert!(noun.matches(&shape));
+//!
+//! let err = Noun::from(Cell::from([1u8, 2u8, 3u8])).check(&shape).unwrap_err();
+//! assert_eq!(err.axis(), Some(6));
+//! assert_eq!(err.expected(), Kind::Null);
+//! assert_eq!(err.found(), Kind::Atom);
+//! ```
+
+use crate::{axis::peg, noun::Noun};
+use std::{
+    fmt::{Display, Error, Formatter},
+    str::FromStr,
+};
+
+/// A pattern that a noun either matches or doesn't.
+///
+/// See the [module documentation](self) for the notation shapes are parsed from, which their
+/// [`Display`] implementation writes.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Shape {
+    /// Any noun, `*`.
+    Any,
+    /// Any atom, `@`.
+    Atom,
+    /// Any cell, `^`.
+    Cell,
+    /// Null, `~`.
+    Null,
+    /// A cell whose head and tail match these shapes, `[a b]`.
+    Pair(Box<Shape>, Box<Shape>),
+    /// A null-terminated list whose elements match this shape, `(list a)`.
+    List(Box<Shape>),
+}
+
+impl Shape {
+    /// Checks `noun` against this shape, reporting the first mismatch in pre-order.
+    pub(crate) fn check(&self, noun: &Noun) -> Result<(), ShapeError> {
+        // Lists are checked one element at a time, so the stack doesn't grow with their length.
+        let mut stack = vec![(noun, self, Some(1))];
+        while let Some((noun, shape, axis)) = stack.pop() {
+            match (shape, noun) {
+                (Self::Any, _) | (Self::Atom, Noun::Atom(_)) | (Self::Cell, Noun::Cell(_)) => {}
+                (Self::Null | Self::List(_), noun) if noun.is_null() => {}
+                (Self::Pair(head, tail), Noun::Cell(cell)) => {
+                    stack.push((cell.tail_ref(), tail, axis.and_then(|axis| peg(axis, 3))));
+                    stack.push((cell.head_ref(), head, axis.and_then(|axis| peg(axis, 2))));
+                }
+                (Self::List(elem), Noun::Cell(cell)) => {
+                    stack.push((cell.tail_ref(), shape, axis.and_then(|axis| peg(axis, 3))));
+                    stack.push((cell.head_ref(), elem, axis.and_then(|axis| peg(axis, 2))));
+                }
+                _ => {
+                    return Err(ShapeError {
+                        axis,
+                        expected: shape.expected(),
+                        found: Kind::of(noun),
+                    })
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the kind of noun this shape expects when it doesn't match.
+    fn expected(&self) -> Kind {
+        match self {
+            Self::Atom | Self::Any => Kind::Atom,
+            Self::Cell | Self::Pair(..) => Kind::Cell,
+            // A list only fails to match an atom other than null.
+            Self::Null | Self::List(_) => Kind::Null,
+        }
+    }
+}
+
+impl Display for Shape {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        match self {
+            Self::Any => write!(f, "*"),
+            Self::Atom => write!(f, "@"),
+            Self::Cell => write!(f, "^"),
+            Self::Null => write!(f, "~"),
+            Self::Pair(head, tail) => {
+                write!(f, "[{}", head)?;
+                let mut tail = &**tail;
+                while let Self::Pair(head, rest) = tail {
+                    write!(f, " {}", head)?;
+                    tail = rest;
+                }
+                write!(f, " {}]", tail)
+            }
+            Self::List(elem) => write!(f, "(list {})", elem),
+        }
+    }
+}
+
+impl FromStr for Shape {
+    type Err = ParseShapeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser { s, index: 0 };
+        let shape = parser.shape()?;
+        parser.skip_whitespace();
+        match parser.peek() {
+            Some(character) => Err(parser.unexpected(character)),
+            None => Ok(shape),
+        }
+    }
+}
+
+/// A recursive descent parser of shapes.
+struct Parser<'a> {
+    s: &'a str,
+    /// The byte offset of the next character.
+    index: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<char> {
+        self.s[self.index..].chars().next()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(character) = self.peek().filter(char::is_ascii_whitespace) {
+            self.index += character.len_utf8();
+        }
+    }
+
+    fn unexpected(&self, character: char) -> ParseShapeError {
+        ParseShapeError::UnexpectedCharacter {
+            index: self.index,
+            character,
+        }
+    }
+
+    fn shape(&mut self) -> Result<Shape, ParseShapeError> {
+        self.skip_whitespace();
+        let start = self.index;
+        let character = self.peek().ok_or(ParseShapeError::UnexpectedEnd)?;
+        self.index += character.len_utf8();
+        match character {
+            '*' => Ok(Shape::Any),
+            '@' => Ok(Shape::Atom),
+            '^' => Ok(Shape::Cell),
+            '~' => Ok(Shape::Null),
+            '[' => {
+                let mut elems = Vec::new();
+                loop {
+                    self.skip_whitespace();
+                    if self.peek() == Some(']') {
+                        self.index += 1;
+                        break;
+                    }
+                    elems.push(self.shape()?);
+                }
+                let mut elems = elems.into_iter().rev();
+                match (elems.next(), elems.next()) {
+                    (Some(tail), Some(head)) => {
+                        let pair = Shape::Pair(Box::new(head), Box::new(tail));
+                        Ok(elems.fold(pair, |tail, head| {
+                            Shape::Pair(Box::new(head), Box::new(tail))
+                        }))
+                    }
+                    _ => Err(ParseShapeError::TooFewElements { index: start }),
+                }
+            }
+            '(' => {
+                self.skip_whitespace();
+                if !self.s[self.index..].starts_with("list") {
+                    return Err(match self.peek() {
+                        Some(character) => self.unexpected(character),
+                        None => ParseShapeError::UnexpectedEnd,
+                    });
+                }
+                self.index += "list".len();
+                let elem = self.shape()?;
+                self.skip_whitespace();
+                match self.peek() {
+                    Some(')') => {
+                        self.index += 1;
+                        Ok(Shape::List(Box::new(elem)))
+                    }
+                    Some(character) => Err(self.unexpected(character)),
+                    None => Err(ParseShapeError::UnexpectedEnd),
+                }
+            }
+            _ => {
+                self.index = start;
+                Err(self.unexpected(character))
+            }
+        }
+    }
+}
+
+/// Errors that occur when parsing a shape from a string.
+#[derive(Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ParseShapeError {
+    /// The string ends before the shape does.
+    UnexpectedEnd,
+    /// A character can't appear where it does.
+    UnexpectedCharacter {
+        /// The byte offset of the character.
+        index: usize,
+        /// The character.
+        character: char,
+    },
+    /// A cell has fewer than two elements, like `[@]`.
+    TooFewElements {
+        /// The byte offset of the cell's `[`.
+        index: usize,
+    },
+}
+
+impl Display for ParseShapeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        match self {
+            Self::UnexpectedEnd => write!(f, "unexpected end of shape"),
+            Self::UnexpectedCharacter { index, character } => {
+                write!(f, "unexpected character {:?} at index {}", character, index)
+            }
+            Self::TooFewElements { index } => {
+                write!(f, "the cell at index {} has fewer than two elements", index)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseShapeError {}
+
+/// The kind of a noun, as far as shapes are concerned.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Kind {
+    /// An atom other than null.
+    Atom,
+    /// A cell.
+    Cell,
+    /// Null.
+    Null,
+}
+
+impl Kind {
+    /// Returns the kind of `noun`.
+    pub fn of(noun: &Noun) -> Self {
+        match noun {
+            Noun::Cell(_) => Self::Cell,
+            noun if noun.is_null() => Self::Null,
+            Noun::Atom(_) => Self::Atom,
+        }
+    }
+}
+
+impl Display for Kind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        match self {
+            Self::Atom => write!(f, "an atom"),
+            Self::Cell => write!(f, "a cell"),
+            Self::Null => write!(f, "null"),
+        }
+    }
+}
+
+/// The error returned when a noun doesn't match a shape, describing the first subnoun in pre-order
+/// that doesn't.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ShapeError {
+    axis: Option<u64>,
+    expected: Kind,
+    found: Kind,
+}
+
+impl ShapeError {
+    /// Returns the axis of the subnoun that doesn't match, if it fits in a [`u64`].
+    pub fn axis(&self) -> Option<u64> {
+        self.axis
+    }
+
+    /// Returns the kind of noun the shape expected at the axis.
+    pub fn expected(&self) -> Kind {
+        self.expected
+    }
+
+    /// Returns the kind of noun at the axis.
+    pub fn found(&self) -> Kind {
+        self.found
+    }
+}
+
+impl Display for ShapeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        write!(f, "expected {}, found {} ", self.expected, self.found)?;
+        match self.axis {
+            Some(axis) => write!(f, "at axis {}", axis),
+            None => write!(f, "more than 63 cells deep"),
+        }
+    }
+}
+
+impl std::error::Error for ShapeError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{atom::Atom, cell::Cell, Rc};
+
+    fn parse(s: &str) -> Shape {
+        s.parse().unwrap_or_else(|err| panic!("{:?}: {}", s, err))
+    }
+
+    #[test]
+    fn shape() {
+        let null = || Noun::null();
+        let atom = |n: u8| Noun::from(n);
+        let cell = |head: Noun, tail: Noun| Noun::from(Cell::from([head, tail]));
+
+        // Patterns against nouns that match them.
+        {
+            let cases = [
+                ("*", atom(5)),
+                ("*", cell(null(), null())),
+                ("@", atom(5)),
+                ("@", null()),
+                ("^", cell(atom(1), atom(2))),
+                ("~", null()),
+                ("[@ @]", cell(atom(1), atom(2))),
+                (
+                    "[@ @ [* ~]]",
+                    cell(atom(1), cell(atom(2), cell(atom(3), null()))),
+                ),
+                (
+                    "[@ @ * ~]",
+                    cell(atom(1), cell(atom(2), cell(atom(3), null()))),
+                ),
+                (
+                    "[[@ @] ^]",
+                    cell(cell(atom(1), atom(2)), cell(null(), null())),
+                ),
+                ("(list @)", null()),
+                ("(list @)", cell(atom(1), cell(atom(2), null()))),
+                (
+                    "(list [@ ~])",
+                    cell(cell(atom(1), null()), cell(cell(atom(2), null()), null())),
+                ),
+                (
+                    "(list (list *))",
+                    cell(cell(atom(1), null()), cell(null(), null())),
+                ),
+                ("[(list @) @]", cell(null(), atom(7))),
+                ("  [ @\n\t@ ]  ", cell(atom(1), atom(2))),
+                ("[@@]", cell(atom(1), atom(2))),
+                ("(  list@ )", null()),
+            ];
+            for (pattern, noun) in cases {
+                assert!(noun.matches(&parse(pattern)), "{} {}", pattern, noun);
+                assert_eq!(noun.check(&parse(pattern)), Ok(()));
+            }
+        }
+
+        // Patterns against nouns that don't match them, with the axis, expected kind, and found
+        // kind of the first mismatch.
+        {
+            let cases = [
+                ("^", atom(5), 1, Kind::Cell, Kind::Atom),
+                ("@", cell(atom(1), atom(2)), 1, Kind::Atom, Kind::Cell),
+                ("~", atom(5), 1, Kind::Null, Kind::Atom),
+                ("~", cell(null(), null()), 1, Kind::Null, Kind::Cell),
+                ("[@ @]", null(), 1, Kind::Cell, Kind::Null),
+                ("[^ @]", cell(atom(1), atom(2)), 2, Kind::Cell, Kind::Atom),
+                (
+                    "[@ @ [* ~]]",
+                    cell(atom(1), cell(atom(2), cell(atom(3), atom(4)))),
+                    15,
+                    Kind::Null,
+                    Kind::Atom,
+                ),
+                // The head is checked before the tail.
+                ("[^ ^]", atom(1), 1, Kind::Cell, Kind::Atom),
+                (
+                    "[[@ ^] ^]",
+                    cell(cell(atom(1), atom(2)), atom(3)),
+                    5,
+                    Kind::Cell,
+                    Kind::Atom,
+                ),
+                ("(list @)", atom(5), 1, Kind::Null, Kind::Atom),
+                (
+                    "(list @)",
+                    cell(atom(1), cell(atom(2), atom(3))),
+                    7,
+                    Kind::Null,
+                    Kind::Atom,
+                ),
+                (
+                    "(list @)",
+                    cell(atom(1), cell(cell(null(), null()), null())),
+                    6,
+                    Kind::Atom,
+                    Kind::Cell,
+                ),
+                (
+                    "(list [@ ~])",
+                    cell(cell(atom(1), null()), cell(cell(atom(2), atom(3)), null())),
+                    13,
+                    Kind::Null,
+                    Kind::Atom,
+                ),
+            ];
+            for (pattern, noun, axis, expected, found) in cases {
+                let err = noun.check(&parse(pattern)).unwrap_err();
+                assert_eq!(
+                    (err.axis(), err.expected(), err.found()),
+                    (Some(axis), expected, found),
+                    "{} {}",
+                    pattern,
+                    noun
+                );
+                assert!(!noun.matches(&parse(pattern)));
+            }
+            let err = cell(atom(1), atom(2)).check(&parse("[@ ~]")).unwrap_err();
+            assert_eq!(err.to_string(), "expected null, found an atom at axis 3");
+        }
+
+        // A long list is checked without growing the stack, and a mismatch too deep for its axis
+        // to fit in a u64 has no axis.
+        {
+            let len = 100_000;
+            let list = Noun::list_from((0..len).map(|_| Rc::new(Noun::null())).collect());
+            assert!(list.matches(&parse("(list ~)")));
+            let err = list.check(&parse("(list ^)")).unwrap_err();
+            assert_eq!((err.axis(), err.found()), (Some(2), Kind::Null));
+
+            let mut improper = Noun::from(Atom::from(1u8));
+            for _ in 0..len {
+                improper = Noun::from(Cell::from([Noun::null(), improper]));
+            }
+            let err = improper.check(&parse("(list ~)")).unwrap_err();
+            assert_eq!((err.axis(), err.found()), (None, Kind::Atom));
+            assert_eq!(
+                err.to_string(),
+                "expected null, found an atom more than 63 cells deep"
+            );
+            std::mem::forget(list);
+            std::mem::forget(improper);
+        }
+
+        // Shapes display in the notation they're parsed from.
+        {
+            for pattern in [
+                "*",
+                "[@ ^ ~]",
+                "[[@ @] @]",
+                "(list [@ (list *)])",
+                "[(list @) ~]",
+            ] {
+                assert_eq!(parse(pattern).to_string(), pattern);
+            }
+            assert_eq!(parse("[@ [@ @]]").to_string(), "[@ @ @]");
+            assert_eq!(
+                parse("[@ (list ~)]"),
+                Shape::Pair(
+                    Box::new(Shape::Atom),
+                    Box::new(Shape::List(Box::new(Shape::Null)))
+                )
+            );
+        }
+
+        // Malformed patterns.
+        {
+            let unexpected =
+                |index, character| ParseShapeError::UnexpectedCharacter { index, character };
+            let cases = [
+                ("", ParseShapeError::UnexpectedEnd),
+                ("   ", ParseShapeError::UnexpectedEnd),
+                ("[@ @", ParseShapeError::UnexpectedEnd),
+                ("(list @", ParseShapeError::UnexpectedEnd),
+                ("(list", ParseShapeError::UnexpectedEnd),
+                ("(", ParseShapeError::UnexpectedEnd),
+                ("[]", ParseShapeError::TooFewElements { index: 0 }),
+                ("[@ [@]]", ParseShapeError::TooFewElements { index: 3 }),
+                ("a", unexpected(0, 'a')),
+                ("@ @", unexpected(2, '@')),
+                ("[@ @]]", unexpected(5, ']')),
+                ("[@ %]", unexpected(3, '%')),
+                ("(set @)", unexpected(1, 's')),
+                ("(list @ @)", unexpected(8, '@')),
+                ("[@ é]", unexpected(3, 'é')),
+            ];
+            for (pattern, err) in cases {
+                assert_eq!(pattern.parse::<Shape>(), Err(err), "{:?}", pattern);
+            }
+            assert_eq!(
+                "[@ %]".parse::<Shape>().unwrap_err().to_string(),
+                "unexpected character '%' at index 3"
+            );
+        }
+    }
+}
@@ -0,0 +1,174 @@
+//! Ship classification and sponsorship.
+//!
+//! A ship is an atom of at most 128 bits, and its width determines its class: galaxies fit in a
+//! byte, stars in two, planets in four, moons in eight, and comets in sixteen. Every ship but a
+//! galaxy has a default sponsor, the ship whose address space it's carved out of, which is found
+//! by keeping its low bits: a star's galaxy is its low byte, a planet's star is its low two bytes,
+//! and a moon's planet is its low four bytes. A comet is sponsored by the star in its low two
+//! bytes. [`clan()`] and [`sein()`] compute these like Hoon's `++clan:title` and `++sein:title`.
+
+use crate::atom::Atom;
+use std::fmt::{self, Display, Formatter};
+
+/// The class of a ship, Hoon's `rank`.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Class {
+    /// A galaxy, `%czar`, a ship of at most 8 bits.
+    Galaxy,
+    /// A star, `%king`, a ship of 9 to 16 bits.
+    Star,
+    /// A planet, `%duke`, a ship of 17 to 32 bits.
+    Planet,
+    /// A moon, `%earl`, a ship of 33 to 64 bits.
+    Moon,
+    /// A comet, `%pawn`, a ship of 65 to 128 bits.
+    Comet,
+}
+
+impl Display for Class {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        let class = match self {
+            Self::Galaxy => "galaxy",
+            Self::Star => "star",
+            Self::Planet => "planet",
+            Self::Moon => "moon",
+            Self::Comet => "comet",
+        };
+        write!(f, "{}", class)
+    }
+}
+
+/// Errors that occur when an atom isn't a ship.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ShipError {
+    /// The atom is wider than 128 bits.
+    TooWide(Atom),
+}
+
+impl Display for ShipError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        match self {
+            Self::TooWide(atom) => write!(f, "{} is not a ship: it's wider than 128 bits", atom),
+        }
+    }
+}
+
+impl std::error::Error for ShipError {}
+
+/// Returns the class of a ship, like Hoon's `++clan:title`.
+///
+/// Fails if the atom is wider than 128 bits.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{atom::Atom, ship::{self, Class}};
+/// assert_eq!(ship::clan(&Atom::from(0u8)).unwrap(), Class::Galaxy);
+/// assert_eq!(ship::clan(&Atom::from(0x100u16)).unwrap(), Class::Star);
+/// assert_eq!(ship::clan(&Atom::from(u128::MAX)).unwrap(), Class::Comet);
+/// assert!(ship::clan(&Atom::from(vec![1; 17])).is_err());
+/// ```
+pub fn clan(ship: &Atom) -> Result<Class, ShipError> {
+    match ship.as_bytes().len() {
+        0 | 1 => Ok(Class::Galaxy),
+        2 => Ok(Class::Star),
+        3 | 4 => Ok(Class::Planet),
+        5..=8 => Ok(Class::Moon),
+        9..=16 => Ok(Class::Comet),
+        _ => Err(ShipError::TooWide(ship.clone())),
+    }
+}
+
+/// Returns the default sponsor of a ship, like Hoon's `++sein:title`.
+///
+/// A galaxy is its own sponsor. Fails if the atom is wider than 128 bits.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{atom::Atom, ship};
+/// // ~marzod, the first star of ~zod.
+/// assert_eq!(ship::sein(&Atom::from(0x100u16)).unwrap(), 0u8);
+/// // A moon of the planet 0x1234.5678.
+/// assert_eq!(ship::sein(&Atom::from(0xabcd_1234_5678u64)).unwrap(), 0x1234_5678u32);
+/// ```
+pub fn sein(ship: &Atom) -> Result<Atom, ShipError> {
+    let len = match clan(ship)? {
+        Class::Galaxy => return Ok(ship.clone()),
+        Class::Star => 1,
+        Class::Planet | Class::Comet => 2,
+        Class::Moon => 4,
+    };
+    Ok(Atom::from(ship.as_bytes()[..len].to_vec()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clan() {
+        let cases = [
+            // ~zod, ~nec, and ~fes, the first, second, and last galaxies.
+            (Atom::null(), Class::Galaxy),
+            (Atom::from(1u8), Class::Galaxy),
+            (Atom::from(0xffu8), Class::Galaxy),
+            // ~marzod, ~binzod, and the last star.
+            (Atom::from(0x100u16), Class::Star),
+            (Atom::from(0x200u16), Class::Star),
+            (Atom::from(0xffffu16), Class::Star),
+            (Atom::from(0x1_0000u32), Class::Planet),
+            (Atom::from(0xffff_ffffu32), Class::Planet),
+            (Atom::from(0x1_0000_0000u64), Class::Moon),
+            (Atom::from(u64::MAX), Class::Moon),
+            (Atom::from(1u128 << 64), Class::Comet),
+            (Atom::from(u128::MAX), Class::Comet),
+        ];
+        for (ship, class) in cases {
+            assert_eq!(super::clan(&ship), Ok(class), "{}", ship);
+        }
+
+        let too_wide = Atom::from(vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]);
+        let err = super::clan(&too_wide).expect_err("too wide");
+        assert_eq!(err, ShipError::TooWide(too_wide.clone()));
+        assert_eq!(
+            err.to_string(),
+            "0x0000.0000.0000.0000.1 is not a ship: it's wider than 128 bits"
+        );
+    }
+
+    #[test]
+    fn sein() {
+        let cases = [
+            // Galaxies sponsor themselves.
+            (Atom::null(), Atom::null()),
+            (Atom::from(0xffu8), Atom::from(0xffu8)),
+            // ~zod's stars, ~marzod and ~binzod, and the last star of ~fes.
+            (Atom::from(0x100u16), Atom::null()),
+            (Atom::from(0x200u16), Atom::null()),
+            (Atom::from(0xffffu16), Atom::from(0xffu8)),
+            // Planets are sponsored by the star in their low 16 bits.
+            (Atom::from(0x1_0000u32), Atom::null()),
+            (Atom::from(0x1234_0201u32), Atom::from(0x201u16)),
+            // Moons by the planet in their low 32 bits, even when it's a galaxy or a star.
+            (Atom::from(0xabcd_1234_5678u64), Atom::from(0x1234_5678u32)),
+            (Atom::from(0x1_0000_0100u64), Atom::from(0x100u16)),
+            // Comets by the star in their low 16 bits.
+            (
+                Atom::from(0xdead_beef_0000_0000_0000_0000_0000_0102u128),
+                Atom::from(0x102u16),
+            ),
+            (Atom::from(u128::MAX), Atom::from(0xffffu16)),
+        ];
+        for (ship, sponsor) in cases {
+            assert_eq!(super::sein(&ship), Ok(sponsor), "{}", ship);
+        }
+
+        let too_wide = Atom::from(vec![1; 17]);
+        assert_eq!(
+            super::sein(&too_wide),
+            Err(ShipError::TooWide(too_wide.clone()))
+        );
+    }
+}
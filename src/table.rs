@@ -0,0 +1,212 @@
+//! Hash-consing: building nouns so that equal nouns share a single allocation.
+//!
+//! Nouns built through a [`NounTable`] are maximally shared: two equal nouns from the same table
+//! are always the same [`Rc<Noun>`](crate::Rc), so comparing them is a pointer comparison, and a
+//! noun with a lot of repeated structure takes no more memory than its distinct subnouns.
+
+use crate::{atom::Atom, cell::Cell, noun::Noun, serdes::HashMap, Rc};
+#[cfg(not(feature = "thread-safe"))]
+use std::rc::Weak;
+#[cfg(feature = "thread-safe")]
+use std::sync::Weak;
+
+/// A hash-consing table of nouns.
+///
+/// The table only holds weak references, so it never keeps a noun alive by itself, but the
+/// entries of dropped nouns linger until [`purge()`](Self::purge()) removes them.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{atom::Atom, table::NounTable, Rc};
+/// let mut table = NounTable::new();
+/// let pair = |table: &mut NounTable| {
+///     let head = table.atom(Atom::from("hello"));
+///     let tail = table.atom(Atom::from("world"));
+///     table.cell(head, tail)
+/// };
+/// let a = pair(&mut table);
+/// let b = pair(&mut table);
+/// assert!(Rc::ptr_eq(&a, &b));
+///
+/// drop((a, b));
+/// assert_eq!(table.purge(), 3);
+/// assert!(table.is_empty());
+/// ```
+#[derive(Debug, Default)]
+pub struct NounTable {
+    /// Every atom in the table, keyed by [`Atom::hash()`].
+    atoms: HashMap<u64, Vec<Weak<Noun>>>,
+    /// Every cell in the table, keyed by the addresses of its head and tail, which are themselves
+    /// shared.
+    cells: HashMap<(usize, usize), Weak<Noun>>,
+}
+
+impl NounTable {
+    /// Creates an empty table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of nouns in this table, including dropped nouns that haven't been
+    /// purged.
+    pub fn len(&self) -> usize {
+        self.atoms.values().map(Vec::len).sum::<usize>() + self.cells.len()
+    }
+
+    /// Returns `true` if this table holds no nouns.
+    pub fn is_empty(&self) -> bool {
+        self.atoms.is_empty() && self.cells.is_empty()
+    }
+
+    /// Returns the shared atom equal to `atom`, adding it to this table if there isn't one.
+    pub fn atom(&mut self, atom: Atom) -> Rc<Noun> {
+        let entries = self.atoms.entry(atom.hash()).or_default();
+        let shared = entries
+            .iter()
+            .filter_map(Weak::upgrade)
+            .find(|noun| matches!(&**noun, Noun::Atom(shared) if *shared == atom));
+        shared.unwrap_or_else(|| {
+            let noun = Rc::new(Noun::from(atom));
+            entries.retain(|entry| entry.strong_count() > 0);
+            entries.push(Rc::downgrade(&noun));
+            noun
+        })
+    }
+
+    /// Returns the shared cell `[head tail]`, adding it to this table if there isn't one.
+    ///
+    /// Sharing is only maximal if `head` and `tail` came from this table; use
+    /// [`intern()`](Self::intern()) to add a noun built some other way.
+    pub fn cell(&mut self, head: Rc<Noun>, tail: Rc<Noun>) -> Rc<Noun> {
+        let key = (
+            Rc::as_ptr(&head) as *const () as usize,
+            Rc::as_ptr(&tail) as *const () as usize,
+        );
+        // A live entry holds its head and tail, so no other noun can be at their addresses.
+        if let Some(shared) = self.cells.get(&key).and_then(Weak::upgrade) {
+            return shared;
+        }
+        let noun = Rc::new(Noun::from(Cell::from([head, tail])));
+        self.cells.insert(key, Rc::downgrade(&noun));
+        noun
+    }
+
+    /// Returns the shared noun equal to `noun`, adding it and its subnouns to this table as
+    /// needed.
+    pub fn intern(&mut self, noun: &Noun) -> Rc<Noun> {
+        // Interns subnouns in post-order without recursing, since nouns can be arbitrarily deep.
+        let mut stack = vec![(noun, false)];
+        let mut interned = Vec::new();
+        while let Some((noun, children_interned)) = stack.pop() {
+            match noun {
+                Noun::Atom(atom) => interned.push(self.atom(atom.clone())),
+                Noun::Cell(_) if children_interned => {
+                    let tail = interned.pop().expect("interned tail");
+                    let head = interned.pop().expect("interned head");
+                    interned.push(self.cell(head, tail));
+                }
+                Noun::Cell(cell) => {
+                    stack.push((noun, true));
+                    stack.push((cell.tail_ref(), false));
+                    stack.push((cell.head_ref(), false));
+                }
+            }
+        }
+        interned.pop().expect("interned noun")
+    }
+
+    /// Removes the entries of dropped nouns from this table, returning how many were removed.
+    pub fn purge(&mut self) -> usize {
+        let before = self.len();
+        self.atoms.retain(|_, entries| {
+            entries.retain(|entry| entry.strong_count() > 0);
+            !entries.is_empty()
+        });
+        self.cells.retain(|_, entry| entry.strong_count() > 0);
+        before - self.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds a complete binary tree of the given height whose leaves are `0` and `1`
+    /// alternately, which has only `height + 2` distinct subnouns, without sharing any of them.
+    fn tree(height: u32) -> Noun {
+        if height == 1 {
+            Noun::from(Cell::from([0u8, 1]))
+        } else {
+            Noun::from(Cell::from([tree(height - 1), tree(height - 1)]))
+        }
+    }
+
+    #[test]
+    fn table() {
+        // Building the same noun twice shares everything.
+        {
+            let mut table = NounTable::new();
+            let build = |table: &mut NounTable| {
+                let head = table.atom(Atom::from("http-request"));
+                let id = table.atom(Atom::from(7u8));
+                let url = table.atom(Atom::from("/~/login"));
+                let tail = table.cell(id, url);
+                table.cell(head, tail)
+            };
+            let a = build(&mut table);
+            let b = build(&mut table);
+            assert!(Rc::ptr_eq(&a, &b));
+            assert_eq!(table.len(), 5);
+
+            // Interning an equal noun built without the table finds the same noun.
+            let c = table.intern(&Noun::from(Cell::from([
+                Noun::from("http-request"),
+                Noun::from(7u8),
+                Noun::from("/~/login"),
+            ])));
+            assert!(Rc::ptr_eq(&a, &c));
+            assert_eq!(table.len(), 5);
+        }
+
+        // Equal subnouns of an interned noun are pointer-identical.
+        {
+            let mut table = NounTable::new();
+            let noun = table.intern(&tree(12));
+            assert_eq!(table.len(), 2 + 12);
+            let (Some(a), Some(b)) = (noun.get(0b1000), noun.get(0b1111)) else {
+                panic!("subnouns exist");
+            };
+            assert!(std::ptr::eq(a, b));
+        }
+
+        // Dropped nouns are purged, and nouns still in use aren't.
+        {
+            let mut table = NounTable::new();
+            let kept = table.intern(&Noun::from(Cell::from([1u8, 2])));
+            for i in 0..1000u32 {
+                table.intern(&Noun::from(Cell::from([i, i + 1])));
+            }
+            let len = table.len();
+            assert_eq!(table.purge(), len - 3);
+            assert_eq!(table.len(), 3);
+            assert!(Rc::ptr_eq(
+                &table.intern(&Noun::from(Cell::from([1u8, 2]))),
+                &kept
+            ));
+            drop(kept);
+            assert_eq!(table.purge(), 3);
+            assert!(table.is_empty());
+        }
+
+        // Jamming a hash-consed noun backreferences every repeated subnoun, just as jamming an
+        // equal noun with no sharing at all does.
+        {
+            let mut table = NounTable::new();
+            let shared = table.intern(&tree(12));
+            let unshared = tree(12);
+            assert_eq!(shared.jam_to_vec(), unshared.jam_to_vec());
+            assert!(shared.jam_to_vec().len() * 100 < unshared.jam_nodedup().as_bytes().len());
+        }
+    }
+}
@@ -0,0 +1,439 @@
+//! Rendering tanks and tangs, Hoon's pretty-printed text, into strings.
+//!
+//! Error traces, `~&` output, and anything else a ship prints arrive as a `tank`, or as a `tang`,
+//! a list of tanks:
+//!
+//! ```hoon
+//! +$  tank
+//!   $@  cord
+//!   $%  [%leaf p=tape]
+//!       [%palm p=(qual tape tape tape tape) q=(list tank)]
+//!       [%rose p=(trel tape tape tape) q=(list tank)]
+//!   ==
+//! ```
+//!
+//! A leaf (or a cord) is a line of text. A rose is a list of tanks with a separator, an opening,
+//! and a closing, like `[" " "[" "]"]`, and a palm is a list of tanks rendered the way Hoon renders
+//! runes, with the children stepping back toward the left margin. [`render()`] lays a tank out the
+//! way Hoon's `++wash` does: on one line if it fits in the width, and otherwise over several lines,
+//! with each child of a rose indented on its own line and text too long for a line wrapped between
+//! `\/` markers. Widths are counted in characters.
+
+use crate::{
+    convert::{list_from_noun, Error, FromNoun},
+    match_tags,
+    noun::Noun,
+};
+
+/// A line of text being laid out.
+type Line = Vec<char>;
+
+/// A parsed tank.
+enum Tank {
+    Leaf(Line),
+    Palm([Line; 4], Vec<Tank>),
+    Rose([Line; 3], Vec<Tank>),
+}
+
+/// Renders a tank in `width` columns, like Hoon's `(wash [0 width] tank)`.
+///
+/// Hoon crashes on a width below 3, and on a palm nested so deeply that its indentation passes the
+/// width; this function lays both out as best it can instead.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{cell::Cell, tank, text, Noun};
+/// let leaf = |text: &str| Noun::from(Cell::from([Noun::from("leaf"), text::string_to_tape(text)]));
+/// let rose = Noun::from(Cell::from([
+///     Noun::from("rose"),
+///     Noun::from(Cell::from([
+///         text::string_to_tape(" "),
+///         text::string_to_tape("["),
+///         text::string_to_tape("]"),
+///     ])),
+///     leaf("%foo"),
+///     leaf("%bar"),
+///     leaf("%baz"),
+///     Noun::null(),
+/// ]));
+/// assert_eq!(tank::render(&rose, 80).unwrap(), "[%foo %bar %baz]");
+/// assert_eq!(tank::render(&rose, 10).unwrap(), "[ %foo\n  %bar\n  %baz\n]");
+/// ```
+pub fn render(tank: &Noun, width: usize) -> Result<String, Error> {
+    let tank = tank_from_noun(tank)?;
+    Ok(join(wash(&tank, width)))
+}
+
+/// Renders each tank of a tang in `width` columns, one after the other.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{cell::Cell, tank, Noun};
+/// let tang = Noun::from(Cell::from(["bail: exit", "/app/foo/hoon:<[53 3].[60 5]>", ""]));
+/// assert_eq!(
+///     tank::render_tang(&tang, 80).unwrap(),
+///     "bail: exit\n/app/foo/hoon:<[53 3].[60 5]>"
+/// );
+/// ```
+pub fn render_tang(tang: &Noun, width: usize) -> Result<String, Error> {
+    let tanks = list_from_noun(tang, tank_from_noun)?;
+    Ok(join(tanks.iter().flat_map(|tank| wash(tank, width))))
+}
+
+/// Joins lines with newlines.
+fn join(lines: impl IntoIterator<Item = Line>) -> String {
+    let lines: Vec<String> = lines
+        .into_iter()
+        .map(|line| line.into_iter().collect())
+        .collect();
+    lines.join("\n")
+}
+
+/// Lays out a tank in `edg` columns, `++wash`.
+fn wash(tank: &Tank, edg: usize) -> Vec<Line> {
+    let mut lines = win(tank, 0, edg, Vec::new());
+    lines.reverse();
+    lines
+}
+
+fn tank_from_noun(noun: &Noun) -> Result<Tank, Error> {
+    if let Noun::Atom(cord) = noun {
+        return Ok(Tank::Leaf(
+            cord.as_str()
+                .map_err(|_| Error::AtomToStr)?
+                .chars()
+                .collect(),
+        ));
+    }
+    match_tags!(noun, {
+        "leaf" => |tape| Ok(Tank::Leaf(tape_from_noun(tape).map_err(|err| err.at(3))?)),
+        "palm" => |args| {
+            let (p, q) = split(args).map_err(|err| err.at(3))?;
+            let p = <(Vec<u8>, Vec<u8>, Vec<u8>, Vec<u8>)>::from_noun(p)
+                .and_then(|p| Ok([line(p.0)?, line(p.1)?, line(p.2)?, line(p.3)?]))
+                .map_err(|err| err.at(6))?;
+            let q = list_from_noun(q, tank_from_noun).map_err(|err| err.at(7))?;
+            Ok(Tank::Palm(p, q))
+        },
+        "rose" => |args| {
+            let (p, q) = split(args).map_err(|err| err.at(3))?;
+            let p = <(Vec<u8>, Vec<u8>, Vec<u8>)>::from_noun(p)
+                .and_then(|p| Ok([line(p.0)?, line(p.1)?, line(p.2)?]))
+                .map_err(|err| err.at(6))?;
+            let q = list_from_noun(q, tank_from_noun).map_err(|err| err.at(7))?;
+            Ok(Tank::Rose(p, q))
+        },
+        else => |_| Err(Error::UnknownTag.at(2)),
+    })
+}
+
+/// Splits a cell into its head and tail.
+fn split(noun: &Noun) -> Result<(&Noun, &Noun), Error> {
+    match noun {
+        Noun::Cell(cell) => Ok((cell.head_ref(), cell.tail_ref())),
+        Noun::Atom(_) => Err(Error::UnexpectedAtom),
+    }
+}
+
+/// Reads a tape as a line of characters.
+fn tape_from_noun(tape: &Noun) -> Result<Line, Error> {
+    line(Vec::<u8>::from_noun(tape)?)
+}
+
+/// Decodes the UTF-8 bytes of a tape into a line of characters.
+fn line(bytes: Vec<u8>) -> Result<Line, Error> {
+    let string = String::from_utf8(bytes).map_err(|_| Error::AtomToStr)?;
+    Ok(string.chars().collect())
+}
+
+/// Lays out a tank on one line, `++ram`.
+fn ram(tank: &Tank) -> Line {
+    match tank {
+        Tank::Leaf(text) => text.clone(),
+        Tank::Palm([p, q, r, s], kids) => rose_ram(p, &[&q[..], r].concat(), s, kids),
+        Tank::Rose([p, q, r], kids) => rose_ram(p, q, r, kids),
+    }
+}
+
+fn rose_ram(sep: &[char], open: &[char], close: &[char], kids: &[Tank]) -> Line {
+    let mut line = open.to_vec();
+    for (i, kid) in kids.iter().enumerate() {
+        if i > 0 {
+            line.extend_from_slice(sep);
+        }
+        line.extend(ram(kid));
+    }
+    line.extend_from_slice(close);
+    line
+}
+
+/// Lays out a tank at indentation `tab` in `edg` columns above the lines in `lug`, `++win`.
+///
+/// The lines in `lug`, and the lines returned, are in reverse order, so that adding a line above
+/// them is a push.
+fn win(tank: &Tank, tab: usize, edg: usize, mut lug: Vec<Line>) -> Vec<Line> {
+    let flat = match tank {
+        Tank::Leaf(text) => return rig(text, tab, edg, lug),
+        _ => ram(tank),
+    };
+    if flat.len() <= edg.saturating_sub(tab) {
+        return rig(&flat, tab, edg, lug);
+    }
+    match tank {
+        Tank::Leaf(_) => unreachable!("leaves are laid out above"),
+        Tank::Palm([_, open, _, _], kids) => match &kids[..] {
+            [] => rig(open, tab, edg, lug),
+            [kid] => {
+                let lug = win(kid, tab, edg, lug);
+                rig(open, tab + 2, edg, lug)
+            }
+            // Each child steps back two columns toward `tab`, and the last lines up with it.
+            _ => {
+                for (back, kid) in kids.iter().rev().enumerate() {
+                    lug = win(kid, tab + 2 * back, edg, lug);
+                }
+                wig(open, tab, edg, lug)
+            }
+        },
+        Tank::Rose([_, open, close], kids) => {
+            if !close.is_empty() {
+                lug = rig(close, tab, edg, lug);
+            }
+            // Hoon takes this modulo 0, which crashes, if the width is below 3.
+            let din = match 2 * (edg / 3) {
+                0 => tab + 2,
+                modulus => (tab + 2) % modulus,
+            };
+            for kid in kids.iter().rev() {
+                lug = win(kid, din, edg, lug);
+            }
+            if open.is_empty() {
+                lug
+            } else {
+                wig(open, tab, edg, lug)
+            }
+        }
+    }
+}
+
+/// Adds `hom` at indentation `tab` above the lines in `lug`, wrapping it between `\/` markers if
+/// it doesn't fit in `edg` columns, `++rig`.
+fn rig(hom: &[char], tab: usize, edg: usize, mut lug: Vec<Line>) -> Vec<Line> {
+    if hom.len() <= edg.saturating_sub(tab) {
+        lug.push(indent(tab, hom));
+        return lug;
+    }
+    let edg = edg.saturating_sub(2);
+    // Hoon loops forever if there's no room for any text.
+    let len = edg.saturating_sub(tab + 2).max(1);
+    let mut chunks = hom.chunks(len);
+    let first = chunks.next().expect("text");
+    let mut lines = vec![indent(tab, &[&['\\', '/'], first, &['\\', '/']].concat())];
+    lines.extend(chunks.map(|chunk| indent(tab + 2, chunk)));
+    let mut last = vec!['\\', '/'];
+    last.resize(len + 2, ' ');
+    last.extend(['\\', '/']);
+    lines.push(indent(tab, &last));
+    lug.extend(lines.into_iter().rev());
+    lug
+}
+
+/// Adds `hom` at indentation `tab` above the lines in `lug`, on the same line as the first of them
+/// if it's indented far enough to leave a space after `hom`, `++wig`.
+fn wig(hom: &[char], tab: usize, edg: usize, mut lug: Vec<Line>) -> Vec<Line> {
+    let wug = 1 + tab + hom.len();
+    match lug.last() {
+        Some(first) if first.len() > wug && first[..wug].iter().all(|&ch| ch == ' ') => {
+            let mut line = indent(tab, hom);
+            line.push(' ');
+            line.extend_from_slice(&first[wug..]);
+            *lug.last_mut().expect("line") = line;
+            lug
+        }
+        _ => rig(hom, tab, edg, lug),
+    }
+}
+
+/// Indents text by `tab` spaces.
+fn indent(tab: usize, text: &[char]) -> Line {
+    let mut line = vec![' '; tab];
+    line.extend_from_slice(text);
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{cell::Cell, text::string_to_tape};
+
+    fn leaf(text: &str) -> Noun {
+        Noun::from(Cell::from([Noun::from("leaf"), string_to_tape(text)]))
+    }
+
+    fn rose(p: [&str; 3], q: Vec<Noun>) -> Noun {
+        let p = p.map(string_to_tape);
+        Noun::from(Cell::from([
+            Noun::from("rose"),
+            Noun::from(Cell::from(p)),
+            Noun::from(q),
+        ]))
+    }
+
+    fn palm(p: [&str; 4], q: Vec<Noun>) -> Noun {
+        let p = p.map(string_to_tape);
+        Noun::from(Cell::from([
+            Noun::from("palm"),
+            Noun::from(Cell::from(p)),
+            Noun::from(q),
+        ]))
+    }
+
+    /// A stack trace like a crashing agent's, outermost frame first.
+    fn trace() -> Noun {
+        let spot = |path: &str, pos: &str| rose([":", "", ""], vec![leaf(path), leaf(pos)]);
+        Noun::from(vec![
+            spot("/sys/vane/gall/hoon", "<[1.372 9].[1.372 37]>"),
+            spot("/app/chat-store/hoon", "<[224 5].[224 47]>"),
+            leaf("bad-message"),
+            rose(
+                [" ", "[", "]"],
+                vec![
+                    leaf("%add-messages"),
+                    leaf("~sampel-palnet"),
+                    leaf("/chat/general"),
+                    rose([" ", "~[", "]"], vec![leaf("0x1234"), leaf("0x5678")]),
+                ],
+            ),
+            Noun::from("bail: exit"),
+        ])
+    }
+
+    // The expected renderings follow hoon.hoon's `++win` by hand.
+    #[test]
+    fn tank() {
+        // A trace that fits on the lines of a wide terminal.
+        {
+            assert_eq!(
+                render_tang(&trace(), 80).expect("render"),
+                [
+                    "/sys/vane/gall/hoon:<[1.372 9].[1.372 37]>",
+                    "/app/chat-store/hoon:<[224 5].[224 47]>",
+                    "bad-message",
+                    "[%add-messages ~sampel-palnet /chat/general ~[0x1234 0x5678]]",
+                    "bail: exit",
+                ]
+                .join("\n")
+            );
+        }
+
+        // The same trace in narrower ones, where roses break over lines and long text wraps.
+        {
+            assert_eq!(
+                render_tang(&trace(), 24).expect("render"),
+                [
+                    "  /sys/vane/gall/hoon",
+                    "  <[1.372 9].[1.372 37]>",
+                    "  /app/chat-store/hoon",
+                    "  <[224 5].[224 47]>",
+                    "bad-message",
+                    "[ %add-messages",
+                    "  ~sampel-palnet",
+                    "  /chat/general",
+                    "  ~[0x1234 0x5678]",
+                    "]",
+                    "bail: exit",
+                ]
+                .join("\n")
+            );
+            assert_eq!(
+                render_tang(&trace(), 16).expect("render"),
+                [
+                    "  \\//sys/vane/\\/",
+                    "    gall/hoon",
+                    "  \\/          \\/",
+                    "  \\/<[1.372 9]\\/",
+                    "    .[1.372 37",
+                    "    ]>",
+                    "  \\/          \\/",
+                    "  \\//app/chat-\\/",
+                    "    store/hoon",
+                    "  \\/          \\/",
+                    "  \\/<[224 5].[\\/",
+                    "    224 47]>",
+                    "  \\/          \\/",
+                    "bad-message",
+                    "[ %add-messages",
+                    "  ~sampel-palnet",
+                    "  /chat/general",
+                    "  ~[",
+                    "    0x1234",
+                    "    0x5678",
+                    "  ]",
+                    "]",
+                    "bail: exit",
+                ]
+                .join("\n")
+            );
+        }
+
+        // Palms step their children back toward the left margin.
+        {
+            let tank = palm(
+                [" ", "?:", "", ""],
+                vec![leaf("%.y"), leaf("%yes"), leaf("%no")],
+            );
+            assert_eq!(render(&tank, 80).expect("render"), "?:%.y %yes %no");
+            assert_eq!(render(&tank, 10).expect("render"), "?:  %.y\n  %yes\n%no");
+        }
+
+        // Text too long for a line wraps.
+        {
+            let tank = leaf("abcdefghijklmnopqrstuvwxy");
+            assert_eq!(
+                render(&tank, 10).expect("render"),
+                [
+                    "\\/abcdef\\/",
+                    "  ghijkl",
+                    "  mnopqr",
+                    "  stuvwx",
+                    "  y",
+                    "\\/      \\/",
+                ]
+                .join("\n")
+            );
+        }
+
+        // Widths count characters rather than bytes.
+        {
+            let tank = rose([" ", "", ""], vec![leaf("caf\u{e9}"), leaf("\u{1f680}")]);
+            assert_eq!(render(&tank, 6).expect("render"), "caf\u{e9} \u{1f680}");
+        }
+    }
+
+    #[test]
+    fn tank_errors() {
+        let cases = [
+            (Noun::from(Cell::from(["lead", "x"])), Some(2)),
+            (
+                Noun::from(Cell::from([
+                    Noun::from("leaf"),
+                    Noun::from(Cell::from([0x1234u16, 0])),
+                ])),
+                Some(6),
+            ),
+            (
+                Noun::from(Cell::from([Noun::from("rose"), Noun::from(1u8)])),
+                Some(3),
+            ),
+            (rose([" ", "", ""], vec![Noun::from(0xffu8)]), Some(14)),
+        ];
+        for (noun, axis) in cases {
+            match render(&noun, 80) {
+                Err(Error::At { axis: at, .. }) => assert_eq!(at, axis),
+                res => panic!("unexpected result for {}: {:?}", noun, res),
+            }
+        }
+    }
+}
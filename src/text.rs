@@ -0,0 +1,170 @@
+//! Conversions between Rust strings and Hoon's two kinds of text.
+//!
+//! A cord (`@t`) is a single atom of the UTF-8 bytes of its text, least significant byte first,
+//! which is how [`String`] and [`&str`](prim@str) convert to and from nouns. A tape is a
+//! null-terminated list of those same bytes, one atom per byte. The elements of a tape are bytes
+//! rather than characters, so a character that takes more than one byte in UTF-8 spans more than
+//! one element: `"é"` is the tape `[195 169 ~]`, and its length is 2. Hoon's `++trip` and `++crip`
+//! convert cords to tapes and back, and [`trip()`] and [`crip()`] do the same here.
+
+use crate::{
+    atom::Atom,
+    convert::{list_from_noun, Error, FromNoun},
+    noun::Noun,
+};
+
+/// Converts a tape into a string.
+///
+/// Fails if an element of the tape isn't a byte or if the bytes aren't UTF-8.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{cell::Cell, text, Noun};
+/// let tape = Noun::from(Cell::from([b'h', b'i', 0]));
+/// assert_eq!(text::tape_to_string(&tape).unwrap(), "hi");
+/// ```
+pub fn tape_to_string(tape: &Noun) -> Result<String, Error> {
+    String::from_utf8(tape_bytes(tape)?).map_err(|_| Error::AtomToStr)
+}
+
+/// Converts a string into a tape of its UTF-8 bytes.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{cell::Cell, text, Noun};
+/// assert_eq!(text::string_to_tape("é"), Noun::from(Cell::from([195u8, 169, 0])));
+/// assert!(text::string_to_tape("").is_null());
+/// ```
+pub fn string_to_tape(string: &str) -> Noun {
+    Noun::from(string.as_bytes().to_vec())
+}
+
+/// Converts a tape into a cord, like Hoon's `++crip`.
+///
+/// Every element of the tape must be a byte. As in Hoon, where `++crip` concatenates the bytes of
+/// each element and `0` has no bytes, elements that are `0` are left out of the cord. The bytes
+/// aren't required to be UTF-8.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{atom::Atom, text};
+/// let tape = text::string_to_tape("~zod");
+/// assert_eq!(text::crip(&tape).unwrap(), Atom::from("~zod"));
+/// ```
+pub fn crip(tape: &Noun) -> Result<Atom, Error> {
+    let mut bytes = tape_bytes(tape)?;
+    bytes.retain(|&byte| byte != 0);
+    Ok(Atom::from(bytes))
+}
+
+/// Converts a cord into a tape, like Hoon's `++trip`.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{atom::Atom, text};
+/// let tape = text::trip(&Atom::from("~zod"));
+/// assert_eq!(text::tape_to_string(&tape).unwrap(), "~zod");
+/// ```
+pub fn trip(cord: &Atom) -> Noun {
+    Noun::from(cord.as_bytes().to_vec())
+}
+
+/// Reads the bytes of a tape.
+fn tape_bytes(tape: &Noun) -> Result<Vec<u8>, Error> {
+    list_from_noun(tape, u8::from_noun)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{cell::Cell, convert::PathSegment};
+
+    #[test]
+    fn tape() {
+        // Round trips through tapes and cords.
+        {
+            let cases = [
+                "",
+                "hello, world",
+                "~sampel-palnet",
+                "caf\u{e9}",
+                "\u{4e2d}\u{6587}",
+                "\u{1f680} to the moon",
+            ];
+            for string in cases {
+                let tape = string_to_tape(string);
+                assert_eq!(tape_to_string(&tape).expect("tape"), string);
+
+                let cord = crip(&tape).expect("crip");
+                assert_eq!(cord, Atom::from(string));
+                assert_eq!(trip(&cord), tape);
+            }
+        }
+
+        // Multi-byte characters span elements.
+        {
+            let tape = string_to_tape("a\u{e9}\u{1f680}");
+            assert_eq!(
+                tape,
+                Noun::from(Cell::from([b'a', 0xc3, 0xa9, 0xf0, 0x9f, 0x9a, 0x80, 0]))
+            );
+            assert_eq!(Vec::<u8>::from_noun(&tape).expect("tape").len(), 7);
+        }
+
+        // crip leaves out null elements, and trip doesn't add trailing ones back.
+        {
+            let tape = Noun::from(Cell::from([b'a', 0, b'b', 0, 0]));
+            assert_eq!(crip(&tape).expect("crip"), Atom::from("ab"));
+            assert_eq!(tape_to_string(&tape).expect("tape"), "a\0b\0");
+            assert_eq!(trip(&Atom::from("a\0b\0")), string_to_tape("a\0b"));
+        }
+    }
+
+    #[test]
+    fn tape_errors() {
+        // An element that isn't a byte.
+        {
+            let tape = Noun::from(Cell::from([
+                Noun::from(b'a'),
+                Noun::from("bc"),
+                Noun::null(),
+            ]));
+            for res in [tape_to_string(&tape).map(|_| ()), crip(&tape).map(|_| ())] {
+                match res {
+                    Err(Error::At { axis, path, source }) => {
+                        assert_eq!(axis, Some(6));
+                        assert_eq!(path, [PathSegment::Index(1)]);
+                        assert!(matches!(*source, Error::AtomToUint));
+                    }
+                    res => panic!("unexpected result: {:?}", res),
+                }
+            }
+        }
+
+        // Bytes that aren't UTF-8.
+        {
+            let tape = Noun::from(Cell::from([b'a', 0xc3, 0]));
+            assert!(matches!(tape_to_string(&tape), Err(Error::AtomToStr)));
+            assert_eq!(crip(&tape).expect("crip"), Atom::from(vec![b'a', 0xc3]));
+        }
+
+        // Nouns that aren't lists.
+        {
+            for tape in [
+                Noun::from("abc"),
+                Noun::from(Cell::from([b'a', b'b'])),
+                Noun::from(Cell::from([
+                    Noun::from(Cell::from([b'a', b'b'])),
+                    Noun::null(),
+                ])),
+            ] {
+                assert!(tape_to_string(&tape).is_err());
+                assert!(crip(&tape).is_err());
+            }
+        }
+    }
+}
@@ -0,0 +1,166 @@
+//! JavaScript bindings for cueing and inspecting nouns in the browser or Node.
+//!
+//! This exposes a single class, `JsNoun`, which wraps a reference to a noun:
+//!
+//! ```js
+//! import { JsNoun } from "noun";
+//!
+//! const noun = JsNoun.cue(new Uint8Array([0x31, 0x12]));
+//! noun.isAtom();          // false
+//! noun.head().toString(); // "0x1"
+//! noun.jam();             // Uint8Array [0x31, 0x12]
+//! ```
+//!
+//! Methods that can fail throw a JavaScript `Error` rather than panicking. Panics, which abort
+//! the WebAssembly instance, are logged to the console with their message by a panic hook that's
+//! installed the first time a noun is cued.
+//!
+//! # Building
+//!
+//! The crate isn't a `cdylib`, which only a WebAssembly module needs, so that crates that depend
+//! on it don't build a dynamic library too. Build the module as one by passing the crate type to
+//! `cargo rustc`, and then generate the JavaScript glue with `wasm-bindgen`:
+//!
+//! ```sh
+//! cargo rustc --release --target wasm32-unknown-unknown --no-default-features --features wasm \
+//!     --crate-type cdylib
+//! wasm-bindgen --out-dir pkg target/wasm32-unknown-unknown/release/noun.wasm
+//! ```
+//!
+//! The tests run under `wasm-pack test --node --features wasm`, which builds test binaries rather
+//! than the library and so doesn't need it to be a `cdylib`.
+
+use crate::{noun::Noun, Rc};
+use wasm_bindgen::prelude::*;
+
+/// A noun, `JsNoun` in JavaScript.
+///
+/// Taking the head or tail of a cell doesn't copy it.
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct JsNoun(Rc<Noun>);
+
+#[wasm_bindgen]
+impl JsNoun {
+    /// Deserializes ("cues") a jammed noun from its bytes, least significant byte first, throwing
+    /// if they aren't a jammed noun.
+    pub fn cue(bytes: &[u8]) -> Result<JsNoun, JsError> {
+        console_error_panic_hook::set_once();
+        Noun::cue_bytes(bytes)
+            .map(|noun| Self(Rc::new(noun)))
+            .map_err(|err| JsError::new(&err.to_string()))
+    }
+
+    /// Serializes ("jams") this noun into its bytes, least significant byte first.
+    pub fn jam(&self) -> Vec<u8> {
+        self.0.jam_to_vec()
+    }
+
+    /// Returns `true` if this noun is an atom.
+    #[wasm_bindgen(js_name = isAtom)]
+    pub fn is_atom(&self) -> bool {
+        matches!(*self.0, Noun::Atom(_))
+    }
+
+    /// Returns the head of this noun, throwing if it's an atom.
+    pub fn head(&self) -> Result<JsNoun, JsError> {
+        match &*self.0 {
+            Noun::Atom(_) => Err(JsError::new("head of an atom")),
+            Noun::Cell(cell) => Ok(Self(cell.head())),
+        }
+    }
+
+    /// Returns the tail of this noun, throwing if it's an atom.
+    pub fn tail(&self) -> Result<JsNoun, JsError> {
+        match &*self.0 {
+            Noun::Atom(_) => Err(JsError::new("tail of an atom")),
+            Noun::Cell(cell) => Ok(Self(cell.tail())),
+        }
+    }
+
+    /// Returns the bytes of this noun, least significant byte first, throwing if it's a cell.
+    #[wasm_bindgen(js_name = atomBytes)]
+    pub fn atom_bytes(&self) -> Result<Vec<u8>, JsError> {
+        match &*self.0 {
+            Noun::Atom(atom) => Ok(atom.as_bytes().to_vec()),
+            Noun::Cell(_) => Err(JsError::new("bytes of a cell")),
+        }
+    }
+
+    /// Pretty-prints this noun as its [`Display`](std::fmt::Display) implementation does.
+    #[wasm_bindgen(js_name = toString)]
+    #[allow(clippy::inherent_to_string)]
+    pub fn to_string(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+impl From<Noun> for JsNoun {
+    fn from(noun: Noun) -> Self {
+        Self(Rc::new(noun))
+    }
+}
+
+impl From<JsNoun> for Rc<Noun> {
+    fn from(noun: JsNoun) -> Self {
+        noun.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cell::Cell;
+    #[cfg(target_arch = "wasm32")]
+    use {crate::atom::Atom, wasm_bindgen_test::wasm_bindgen_test};
+
+    /// The example in the module documentation, which never throws, so it runs natively too.
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn module_example() {
+        let jammed = Noun::from(Cell::from([1u8, 2u8])).jam_to_vec();
+        assert_eq!(jammed, [0x31, 0x12]);
+        let noun = JsNoun::cue(&[0x31, 0x12]).expect("cue");
+        assert!(!noun.is_atom());
+        assert_eq!(noun.head().expect("head").to_string(), "0x1");
+        assert_eq!(noun.jam(), [0x31, 0x12]);
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    #[wasm_bindgen_test]
+    fn wasm() {
+        // A round trip through jam and cue, taking the noun apart on the way.
+        {
+            let noun = Noun::from(Cell::from([
+                Noun::from(Atom::from(vec![0xff; 20])),
+                Noun::from(Cell::from(["a", "b"])),
+            ]));
+            let js = JsNoun::cue(&noun.jam_to_vec()).expect("cue");
+            assert!(!js.is_atom());
+            assert_eq!(js.to_string(), noun.to_string());
+            assert_eq!(
+                JsNoun::cue(&js.jam()).expect("cue").to_string(),
+                js.to_string()
+            );
+
+            let head = js.head().expect("head");
+            assert!(head.is_atom());
+            assert_eq!(head.atom_bytes().expect("bytes"), vec![0xff; 20]);
+            let tail = js.tail().expect("tail");
+            assert_eq!(
+                tail.head().expect("head").atom_bytes().expect("bytes"),
+                b"a"
+            );
+        }
+
+        // Failures throw instead of panicking.
+        {
+            assert!(JsNoun::cue(&[0x03]).is_err());
+            let atom = JsNoun::from(Noun::from(5u8));
+            assert!(atom.head().is_err());
+            assert!(atom.tail().is_err());
+            let cell = JsNoun::from(Noun::from(Cell::from([1u8, 2])));
+            assert!(cell.atom_bytes().is_err());
+        }
+    }
+}
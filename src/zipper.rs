@@ -0,0 +1,283 @@
+//! Navigating and editing nouns with a zipper.
+//!
+//! A [`NounZipper`] focuses on one subnoun of a noun and remembers the path back up to the root,
+//! so moving around and replacing the focus are cheap local operations. Rebuilding the edited
+//! noun only allocates new cells along the paths that were edited; everything else is shared with
+//! the original.
+
+use crate::{cell::Cell, noun::Noun, Rc};
+
+/// A step down from a cell to one of its children.
+#[derive(Debug)]
+struct Crumb {
+    /// The cell stepped down from, which holds the sibling of the focus.
+    parent: Rc<Noun>,
+    /// `true` if the step was to the tail.
+    tail: bool,
+    /// `true` if `parent` has been edited relative to its own parent.
+    parent_edited: bool,
+}
+
+/// A zipper over a noun.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{cell::Cell, zipper::NounZipper, Noun, Rc};
+/// let noun = Rc::new(Noun::from(Cell::from([1u8, 2u8, 3u8])));
+/// let mut zipper = NounZipper::new(noun.clone());
+/// assert!(zipper.down_tail());
+/// assert!(zipper.down_head());
+/// assert_eq!(zipper.axis(), Some(6));
+/// zipper.replace(Rc::new(Noun::from(4u8)));
+///
+/// let edited = zipper.rebuild();
+/// assert_eq!(*edited, Noun::from(Cell::from([1u8, 4u8, 3u8])));
+/// // The head wasn't on the edited path, so it's shared.
+/// assert!(std::ptr::eq(edited.get(2).unwrap(), noun.get(2).unwrap()));
+/// ```
+#[derive(Debug)]
+pub struct NounZipper {
+    /// The subnoun in focus.
+    focus: Rc<Noun>,
+    /// `true` if the focus has been edited relative to its parent.
+    edited: bool,
+    /// The steps from the root to the focus, outermost first.
+    trail: Vec<Crumb>,
+}
+
+impl NounZipper {
+    /// Creates a zipper focused on the root of `noun`.
+    pub fn new(noun: Rc<Noun>) -> Self {
+        Self {
+            focus: noun,
+            edited: false,
+            trail: Vec::new(),
+        }
+    }
+
+    /// Returns the subnoun in focus.
+    pub fn focus(&self) -> &Rc<Noun> {
+        &self.focus
+    }
+
+    /// Returns the number of steps from the root to the focus.
+    pub fn depth(&self) -> usize {
+        self.trail.len()
+    }
+
+    /// Returns the axis of the focus within the root, or [`None`] if the focus is more than 63
+    /// steps deep, where its axis doesn't fit in a [`u64`].
+    pub fn axis(&self) -> Option<u64> {
+        if self.trail.len() >= u64::BITS as usize {
+            return None;
+        }
+        Some(
+            self.trail
+                .iter()
+                .fold(1, |axis, crumb| axis << 1 | u64::from(crumb.tail)),
+        )
+    }
+
+    /// Moves the focus to its head, returning `false` without moving if the focus is an atom.
+    pub fn down_head(&mut self) -> bool {
+        self.down(false)
+    }
+
+    /// Moves the focus to its tail, returning `false` without moving if the focus is an atom.
+    pub fn down_tail(&mut self) -> bool {
+        self.down(true)
+    }
+
+    /// Moves the focus to the subnoun at `axis` of the focus, returning `false` without moving if
+    /// there is no such subnoun.
+    pub fn down_axis(&mut self, axis: u64) -> bool {
+        if self.focus.get(axis).is_none() {
+            return false;
+        }
+        for i in (0..u64::BITS - 1 - axis.leading_zeros()).rev() {
+            self.down(axis & (1 << i) != 0);
+        }
+        true
+    }
+
+    fn down(&mut self, tail: bool) -> bool {
+        let child = match &*self.focus {
+            Noun::Atom(_) => return false,
+            Noun::Cell(cell) if tail => cell.tail(),
+            Noun::Cell(cell) => cell.head(),
+        };
+        let parent = std::mem::replace(&mut self.focus, child);
+        self.trail.push(Crumb {
+            parent,
+            tail,
+            parent_edited: self.edited,
+        });
+        self.edited = false;
+        true
+    }
+
+    /// Moves the focus to its parent, returning `false` if the focus is the root.
+    ///
+    /// If the focus was edited, its parent is replaced by a new cell of the edited focus and its
+    /// sibling.
+    pub fn up(&mut self) -> bool {
+        let Some(crumb) = self.trail.pop() else {
+            return false;
+        };
+        if self.edited {
+            let Noun::Cell(parent) = &*crumb.parent else {
+                unreachable!("the parent of a subnoun is a cell");
+            };
+            let cell = if crumb.tail {
+                Cell::from([parent.head(), self.focus.clone()])
+            } else {
+                Cell::from([self.focus.clone(), parent.tail()])
+            };
+            self.focus = Rc::new(Noun::from(cell));
+        } else {
+            self.focus = crumb.parent;
+        }
+        self.edited |= crumb.parent_edited;
+        true
+    }
+
+    /// Replaces the focus, returning the subnoun it replaced.
+    pub fn replace(&mut self, noun: Rc<Noun>) -> Rc<Noun> {
+        self.edited = true;
+        std::mem::replace(&mut self.focus, noun)
+    }
+
+    /// Moves the focus back up to the root and returns the edited noun.
+    ///
+    /// This takes time proportional to the depth of the focus. Subnouns that weren't on the path
+    /// to an edit are shared with the original noun, and if nothing was edited the original noun
+    /// itself is returned.
+    pub fn rebuild(mut self) -> Rc<Noun> {
+        while self.up() {}
+        self.focus
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::atom::Atom;
+
+    #[test]
+    fn zipper() {
+        // [[1 2] [3 4] 5]
+        let original = Rc::new(Noun::from(Cell::from([
+            Noun::from(Cell::from([1u8, 2])),
+            Noun::from(Cell::from([3u8, 4])),
+            Noun::from(5u8),
+        ])));
+
+        // Moves and replacements in several places.
+        {
+            let mut zipper = NounZipper::new(original.clone());
+            assert_eq!(zipper.axis(), Some(1));
+            assert!(zipper.down_tail());
+            assert!(zipper.down_head());
+            assert!(zipper.down_tail());
+            assert_eq!(zipper.axis(), Some(13));
+            assert_eq!(**zipper.focus(), 4u8);
+            assert!(!zipper.down_head());
+            let four = zipper.replace(Rc::new(Noun::from(Cell::from([6u8, 7]))));
+            assert_eq!(*four, 4u8);
+            assert!(zipper.down_head());
+            zipper.replace(Rc::new(Noun::from(8u8)));
+            assert!(zipper.up());
+            assert!(zipper.up());
+            assert!(zipper.up());
+            assert_eq!(zipper.axis(), Some(3));
+            assert!(zipper.down_axis(3));
+            assert_eq!(zipper.axis(), Some(7));
+            zipper.replace(Rc::new(Noun::from(9u8)));
+
+            let edited = zipper.rebuild();
+            assert_eq!(
+                *edited,
+                Noun::from(Cell::from([
+                    Noun::from(Cell::from([1u8, 2])),
+                    Noun::from(Cell::from([
+                        Noun::from(3u8),
+                        Noun::from(Cell::from([8u8, 7])),
+                    ])),
+                    Noun::from(9u8),
+                ]))
+            );
+            // The untouched head and the untouched atom 3 are shared with the original.
+            assert!(std::ptr::eq(
+                edited.get(2).unwrap(),
+                original.get(2).unwrap()
+            ));
+            assert!(std::ptr::eq(
+                edited.get(12).unwrap(),
+                original.get(12).unwrap()
+            ));
+            assert_eq!(
+                *original,
+                Noun::from(Cell::from([
+                    Noun::from(Cell::from([1u8, 2])),
+                    Noun::from(Cell::from([3u8, 4])),
+                    Noun::from(5u8),
+                ]))
+            );
+        }
+
+        // Wandering around without editing rebuilds the original noun.
+        {
+            let mut zipper = NounZipper::new(original.clone());
+            assert!(zipper.down_axis(6));
+            assert!(zipper.up());
+            assert!(zipper.down_tail());
+            assert_eq!(zipper.depth(), 2);
+            assert!(Rc::ptr_eq(&zipper.rebuild(), &original));
+        }
+
+        // An edit above a later move down isn't forgotten when moving back up through it.
+        {
+            let mut zipper = NounZipper::new(original.clone());
+            assert!(zipper.down_head());
+            zipper.replace(Rc::new(Noun::from(Cell::from([0u8, 0]))));
+            assert!(zipper.down_tail());
+            assert!(zipper.up());
+            assert!(zipper.up());
+            assert_eq!(
+                *zipper.focus().get(2).unwrap(),
+                Noun::from(Cell::from([0u8, 0]))
+            );
+            assert!(!zipper.up());
+        }
+
+        // Moves that don't exist.
+        {
+            let mut zipper = NounZipper::new(Rc::new(Noun::from(Atom::from(1u8))));
+            assert!(!zipper.up());
+            assert!(!zipper.down_tail());
+            assert!(!zipper.down_axis(0));
+            assert!(zipper.down_axis(1));
+            assert_eq!(zipper.depth(), 0);
+
+            let mut zipper = NounZipper::new(original.clone());
+            assert!(!zipper.down_axis(8));
+            assert_eq!(zipper.axis(), Some(1));
+        }
+
+        // Axes too deep for a `u64`.
+        {
+            let mut noun = Rc::new(Noun::null());
+            for _ in 0..64 {
+                noun = Rc::new(Noun::from(Cell::from([noun, Rc::new(Noun::null())])));
+            }
+            let mut zipper = NounZipper::new(noun);
+            for _ in 0..63 {
+                assert!(zipper.down_head());
+            }
+            assert_eq!(zipper.axis(), Some(1 << 63));
+            assert!(zipper.down_head());
+            assert_eq!(zipper.axis(), None);
+        }
+    }
+}
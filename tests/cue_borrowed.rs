@@ -0,0 +1,97 @@
+//! Cueing a jammed noun without copying its byte-aligned atoms.
+//!
+//! This is its own test binary because it counts allocations with a global allocator.
+
+use noun::{
+    atom::Atom,
+    cell::Cell,
+    serdes::{cue_borrowed, NounRef},
+    Noun,
+};
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+/// The system allocator, counting allocations and the bytes allocated.
+struct Counting;
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static ALLOCATED_BYTES: AtomicU64 = AtomicU64::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+        ALLOCATED_BYTES.fetch_add(layout.size() as u64, Ordering::SeqCst);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+        ALLOCATED_BYTES.fetch_add(new_size as u64, Ordering::SeqCst);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+/// Returns the number of allocations `f` makes and the number of bytes it allocates.
+fn allocations<T>(f: impl FnOnce() -> T) -> (T, u64, u64) {
+    let (before, before_bytes) = (
+        ALLOCATIONS.load(Ordering::SeqCst),
+        ALLOCATED_BYTES.load(Ordering::SeqCst),
+    );
+    let res = f();
+    (
+        res,
+        ALLOCATIONS.load(Ordering::SeqCst) - before,
+        ALLOCATED_BYTES.load(Ordering::SeqCst) - before_bytes,
+    )
+}
+
+/// Returns `[32 a 2 b]`, whose jam puts `a` and `b` on byte boundaries as long as both are between
+/// 2^15 and 2^16 bytes long and their most significant bytes are at least `0x80`.
+fn aligned(a: Vec<u8>, b: Vec<u8>) -> Noun {
+    Noun::from(Cell::from([
+        Noun::from(32u8),
+        Noun::from(Atom::from(a)),
+        Noun::from(2u8),
+        Noun::from(Atom::from(b)),
+    ]))
+}
+
+#[test]
+fn cue_borrowed_allocations() {
+    let small = aligned(vec![0xab; 40_000], vec![0xcd; 50_000]);
+    let large = aligned(vec![0xef; 60_000], vec![0x99; 65_000]);
+
+    let mut counts = Vec::new();
+    for noun in [&small, &large] {
+        let jammed = noun.jam_to_vec();
+        let (noun_ref, count, bytes) = allocations(|| cue_borrowed(&jammed).expect("cue borrowed"));
+        for axis in [6, 15] {
+            let atom = noun_ref.get(axis).and_then(NounRef::as_atom).expect("atom");
+            assert!(atom.is_borrowed(), "axis {}", axis);
+            assert_eq!(Noun::from(atom.to_atom()), *noun.get(axis).unwrap());
+        }
+        assert_eq!(noun_ref.to_owned(), *noun);
+        assert_eq!(noun_ref.to_owned(), Noun::cue_bytes(&jammed).expect("cue"));
+
+        // None of the atoms' bytes are copied: only the cells, the small atoms, and the
+        // decoder's bookkeeping are allocated.
+        assert!(bytes < 1024, "allocated {} bytes", bytes);
+        counts.push((count, bytes));
+
+        // Cueing normally copies them.
+        let (_, _, copied) = allocations(|| Noun::cue_bytes(&jammed).expect("cue"));
+        assert!(copied > 90_000, "allocated {} bytes", copied);
+    }
+
+    // What's allocated doesn't depend on how large the borrowed atoms are.
+    assert_eq!(counts[0], counts[1]);
+}
@@ -0,0 +1,250 @@
+#![cfg(feature = "derive")]
+
+use noun::{
+    atom::Atom,
+    cell::Cell,
+    convert::{Error, FromNoun, IntoNoun, PathSegment},
+    Noun,
+};
+use std::collections::HashMap;
+
+#[derive(Clone, Debug, Default, FromNoun, IntoNoun, PartialEq)]
+struct Subscriber {
+    ship: u64,
+    paths: Vec<String>,
+    acks: HashMap<String, Option<u32>>,
+    nickname: Option<String>,
+    #[noun(skip)]
+    cached: Vec<u8>,
+}
+
+#[derive(Clone, Debug, FromNoun, IntoNoun, PartialEq)]
+struct Wrapper(Subscriber);
+
+#[derive(Clone, Debug, FromNoun, IntoNoun, PartialEq)]
+struct Pair<T>(T, T);
+
+#[derive(Clone, Debug, FromNoun, IntoNoun, PartialEq)]
+struct Unit;
+
+#[derive(Clone, Debug, FromNoun, IntoNoun, PartialEq)]
+enum Event {
+    Boot,
+    HttpRequest {
+        id: u32,
+        url: String,
+        headers: Vec<(String, String)>,
+    },
+    Subscribe(Subscriber),
+    #[noun(rename = "kick")]
+    Unsubscribe(u64, String),
+    Sleep {
+        #[noun(skip)]
+        reason: String,
+    },
+}
+
+fn subscriber() -> Subscriber {
+    Subscriber {
+        ship: 0xdead,
+        paths: vec![String::from("/foo"), String::from("/bar")],
+        acks: HashMap::from([(String::from("a"), Some(1)), (String::from("b"), None)]),
+        nickname: Some(String::from("zod")),
+        cached: Vec::new(),
+    }
+}
+
+#[test]
+fn derive_struct() {
+    {
+        let sub = subscriber();
+        let noun = sub.clone().into_noun();
+        let Noun::Cell(cell) = &noun else {
+            panic!("struct is a cell");
+        };
+        assert_eq!(*cell.head_ref(), Noun::from(Atom::from(0xdeadu16)));
+        assert_eq!(noun.get(6), Some(&sub.paths.clone().into_noun()));
+        assert_eq!(noun.get(15), Some(&Some("zod").into_noun()));
+        assert_eq!(Subscriber::from_noun(&noun).expect("round trip"), sub);
+    }
+
+    // Skipped fields aren't part of the noun and are defaulted.
+    {
+        let sub = subscriber();
+        let noun = Subscriber {
+            cached: vec![1, 2, 3],
+            ..sub.clone()
+        }
+        .into_noun();
+        assert_eq!(noun, sub.into_noun());
+        assert_eq!(
+            Subscriber::from_noun(&noun).expect("round trip"),
+            subscriber()
+        );
+    }
+
+    // Empty containers.
+    {
+        let sub = Subscriber::default();
+        let noun = sub.clone().into_noun();
+        assert_eq!(noun, Noun::from(Cell::from([0u8, 0, 0, 0])));
+        assert_eq!(Subscriber::from_noun(&noun).expect("round trip"), sub);
+    }
+
+    // A struct with a single field is that field's noun.
+    {
+        let wrapper = Wrapper(subscriber());
+        let noun = wrapper.clone().into_noun();
+        assert_eq!(noun, wrapper.0.clone().into_noun());
+        assert_eq!(Wrapper::from_noun(&noun).expect("round trip"), wrapper);
+    }
+
+    // Generic structs.
+    {
+        let pair = Pair(Some(1u8), None);
+        let noun = pair.clone().into_noun();
+        assert_eq!(
+            noun,
+            Noun::from(Cell::from([Noun::from(Cell::from([0u8, 1])), Noun::null()]))
+        );
+        assert_eq!(Pair::from_noun(&noun).expect("round trip"), pair);
+    }
+
+    // A struct with no fields is null.
+    {
+        assert!(Unit.into_noun().is_null());
+        assert_eq!(Unit::from_noun(&Noun::null()).expect("null"), Unit);
+        assert!(matches!(
+            Unit::from_noun(&Noun::from(Atom::from(1u8))),
+            Err(Error::ExpectedNull)
+        ));
+    }
+}
+
+#[test]
+fn derive_enum() {
+    let events = [
+        Event::Boot,
+        Event::HttpRequest {
+            id: 7,
+            url: String::from("/~/login"),
+            headers: vec![(String::from("host"), String::from("localhost"))],
+        },
+        Event::Subscribe(subscriber()),
+        Event::Unsubscribe(1, String::from("/foo")),
+        Event::Sleep {
+            reason: String::new(),
+        },
+    ];
+    for event in events {
+        let noun = event.clone().into_noun();
+        assert_eq!(Event::from_noun(&noun).expect("round trip"), event);
+    }
+
+    // Variants are head-tagged with a kebab-case term, and variants without fields are bare tags.
+    {
+        assert_eq!(Event::Boot.into_noun(), Noun::from(Atom::from("boot")));
+        assert_eq!(
+            Event::Unsubscribe(1, String::from("/foo")).into_noun(),
+            Noun::from(Cell::from([
+                Noun::from(Atom::from("kick")),
+                Noun::from(Atom::from(1u8)),
+                Noun::from(Atom::from("/foo")),
+            ]))
+        );
+        let noun = Event::HttpRequest {
+            id: 7,
+            url: String::new(),
+            headers: Vec::new(),
+        }
+        .into_noun();
+        assert_eq!(noun.get(2), Some(&Noun::from(Atom::from("http-request"))));
+        assert_eq!(
+            Event::Sleep {
+                reason: String::from("tired"),
+            }
+            .into_noun(),
+            Noun::from(Atom::from("sleep"))
+        );
+    }
+
+    // Unknown tags.
+    {
+        for noun in [
+            Noun::from(Atom::from("unsubscribe")),
+            Noun::from(Cell::from(["boot", "extra"])),
+            Noun::from(Atom::from("sleep-")),
+        ] {
+            assert!(matches!(Event::from_noun(&noun), Err(Error::UnknownTag)));
+        }
+        let noun = Noun::from(Cell::from([Noun::from(Cell::from([1u8, 2])), Noun::null()]));
+        assert!(matches!(
+            Event::from_noun(&noun),
+            Err(Error::UnexpectedCell)
+        ));
+    }
+}
+
+#[test]
+fn derive_errors() {
+    // Errors name the field that failed to convert and its axis.
+    {
+        let noun = Noun::from(Cell::from([
+            Noun::from(Atom::from("http-request")),
+            Noun::from(Atom::from(u64::MAX)),
+            Noun::null(),
+            Noun::null(),
+        ]));
+        match Event::from_noun(&noun) {
+            Err(Error::At {
+                axis: Some(6),
+                path,
+                source,
+            }) => {
+                assert_eq!(
+                    path,
+                    [PathSegment::Field("http-request"), PathSegment::Field("id")]
+                );
+                assert!(matches!(*source, Error::AtomToUint));
+            }
+            res => panic!("unexpected result: {:?}", res),
+        }
+    }
+
+    // Nested failures are reported from the outermost field inwards.
+    {
+        // The subscriber's paths are an improper list.
+        let sub = subscriber().into_noun();
+        let noun = Noun::from(Cell::from([
+            Noun::from(Atom::from("subscribe")),
+            sub.get(2).expect("ship").clone(),
+            Noun::from(Cell::from(["/foo", "/bar"])),
+            sub.get(7).expect("rest").clone(),
+        ]));
+        let err = Event::from_noun(&noun).expect_err("improper list");
+        assert_eq!(
+            err.to_string(),
+            "a null atom was expected at subscribe[0].paths[1] (axis 29)"
+        );
+        assert_eq!(noun.get(29), Some(&Noun::from(Atom::from("/bar"))));
+    }
+
+    // A cell that's too short fails at the field that's missing.
+    {
+        let noun = Noun::from(Cell::from([
+            Noun::from(Atom::from("kick")),
+            Noun::from(Atom::from(1u8)),
+        ]));
+        match Event::from_noun(&noun) {
+            Err(Error::At {
+                axis: Some(3),
+                path,
+                source,
+            }) => {
+                assert_eq!(path, [PathSegment::Field("kick"), PathSegment::Index(0)]);
+                assert!(matches!(*source, Error::UnexpectedAtom));
+            }
+            res => panic!("unexpected result: {:?}", res),
+        }
+    }
+}
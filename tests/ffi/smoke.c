@@ -0,0 +1,62 @@
+/*
+ * A smoke test of the C interface: builds [1 0x1234], jams it, cues it back,
+ * and takes it apart.
+ *
+ * From the root of the repository:
+ *
+ *   cargo build --features ffi
+ *   cc -Iinclude tests/ffi/smoke.c target/debug/libnoun.a -lpthread -ldl -lm \
+ *     -o target/smoke
+ *   target/smoke
+ */
+
+#include <noun.h>
+#include <stdio.h>
+#include <stdlib.h>
+
+#define CHECK(cond)                                                            \
+  do {                                                                         \
+    if (!(cond)) {                                                             \
+      fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__, __LINE__, #cond); \
+      exit(1);                                                                 \
+    }                                                                          \
+  } while (0)
+
+int main(void) {
+  const uint8_t one[] = {0x01};
+  const uint8_t big[] = {0x34, 0x12};
+  noun_t *head = noun_atom_new(one, sizeof(one));
+  noun_t *tail = noun_atom_new(big, sizeof(big));
+  CHECK(head != NULL && tail != NULL);
+  noun_t *cell = noun_cell_new(head, tail);
+  CHECK(cell != NULL && noun_is_cell(cell));
+  noun_free(head);
+  noun_free(tail);
+
+  uint8_t *jammed;
+  size_t jammed_len;
+  CHECK(noun_jam(cell, &jammed, &jammed_len) == NOUN_OK);
+
+  noun_t *cued;
+  CHECK(noun_cue(jammed, jammed_len, &cued) == NOUN_OK);
+  noun_bytes_free(jammed, jammed_len);
+  CHECK(noun_equal(cell, cued));
+
+  noun_t *part;
+  const uint8_t *bytes;
+  size_t len;
+  CHECK(noun_tail(cued, &part) == NOUN_OK);
+  CHECK(noun_atom_bytes(part, &bytes, &len) == NOUN_OK);
+  CHECK(len == 2 && bytes[0] == 0x34 && bytes[1] == 0x12);
+  CHECK(noun_head(part, &head) == NOUN_UNEXPECTED_ATOM);
+  noun_free(part);
+
+  CHECK(noun_atom_bytes(cued, &bytes, &len) == NOUN_UNEXPECTED_CELL);
+  CHECK(noun_cue(NULL, 0, &part) == NOUN_CUE_FAILED);
+  CHECK(noun_head(NULL, &part) == NOUN_NULL_POINTER);
+
+  noun_free(cued);
+  noun_free(cell);
+  puts("ok");
+  return 0;
+}
@@ -0,0 +1,88 @@
+//! Looking up maps and sets keyed by `Rc<Noun>` with a `&Noun`.
+//!
+//! This is its own test binary because it counts allocations with a global allocator.
+
+use noun::{atom::Atom, cell::Cell, Noun, Rc};
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    collections::{hash_map::DefaultHasher, HashMap, HashSet},
+    hash::{Hash, Hasher},
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+/// The system allocator, counting allocations.
+struct Counting;
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+/// Returns the number of allocations `f` makes.
+fn allocations<T>(f: impl FnOnce() -> T) -> (T, u64) {
+    let before = ALLOCATIONS.load(Ordering::SeqCst);
+    let res = f();
+    (res, ALLOCATIONS.load(Ordering::SeqCst) - before)
+}
+
+fn hash<T: Hash + ?Sized>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn lookup() {
+    let nouns = [
+        Noun::null(),
+        Noun::from(Atom::from("hello")),
+        Noun::from(Cell::from([1u8, 2u8])),
+        Noun::from(Cell::from([
+            Noun::from("http-request"),
+            Noun::from(Cell::from([7u8, 8u8])),
+            Noun::null(),
+        ])),
+    ];
+
+    // A noun, a reference to it, and a pointer to a copy of it hash and compare alike.
+    for noun in &nouns {
+        let rc = Rc::new(noun.clone());
+        assert_eq!(hash(noun), hash(&noun));
+        assert_eq!(hash(noun), hash(&rc));
+        assert_eq!(*rc, *noun);
+    }
+
+    let map: HashMap<Rc<Noun>, usize> = nouns
+        .iter()
+        .enumerate()
+        .map(|(i, noun)| (Rc::new(noun.clone()), i))
+        .collect();
+    let set: HashSet<Rc<Noun>> = map.keys().cloned().collect();
+
+    // Looking up equal nouns that share nothing with the keys allocates nothing.
+    for (i, noun) in nouns.iter().enumerate() {
+        let needle = noun.deep_clone();
+        let (found, allocated) = allocations(|| map.get::<Noun>(&needle).copied());
+        assert_eq!(found, Some(i));
+        assert_eq!(allocated, 0);
+        let (found, allocated) = allocations(|| set.contains::<Noun>(&needle));
+        assert!(found);
+        assert_eq!(allocated, 0);
+    }
+
+    // Nouns that aren't keys aren't found.
+    let missing = Noun::from(Cell::from([2u8, 1u8]));
+    assert_eq!(map.get::<Noun>(&missing), None);
+    assert!(!set.contains::<Noun>(&missing));
+}